@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Build script for Weaver, exposing the build information (git commit,
+//! rustc version, target and profile) to the `version` command.
+
+use std::process::Command;
+
+fn main() {
+    if let Some(git_sha) = command_output("git", &["rev-parse", "HEAD"]) {
+        println!("cargo:rustc-env=WEAVER_GIT_SHA={}", git_sha);
+    }
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+    if let Some(rustc_version) = command_output(&rustc, &["--version"]) {
+        println!("cargo:rustc-env=WEAVER_RUSTC_VERSION={}", rustc_version);
+    }
+    for var in ["TARGET", "PROFILE"] {
+        if let Ok(value) = std::env::var(var) {
+            println!("cargo:rustc-env=WEAVER_{}={}", var, value);
+        }
+    }
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+    println!("cargo:rerun-if-changed=build.rs");
+}
+
+/// Returns the trimmed standard output of a successful command.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_owned()).filter(|stdout| !stdout.is_empty())
+}
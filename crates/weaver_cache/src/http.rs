@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! The HTTP transport of the git operations of the cache.
+//!
+//! The built-in transports of gitoxide ignore the proxy and CA settings of
+//! weaver, so the git HTTP(S) requests are sent via the HTTP client of
+//! `weaver_net` instead.
+//!
+//! A request is only sent when its response is first read, after the body to
+//! post (if any) has been fully written.
+
+use std::any::Any;
+use std::io::{BufReader, Cursor, Read, Write};
+use std::sync::{Arc, Mutex};
+
+use gix::protocol::transport::client::http::{
+    self, GetResponse, Http, PostBodyDataKind, PostResponse,
+};
+use gix::protocol::transport::client::Transport;
+use gix::protocol::transport::Protocol;
+use gix::remote::{Connection, Direction};
+
+/// The transport of the git connections.
+type BoxedTransport = Box<dyn Transport + Send>;
+
+/// The headers (one `name:value` per line) and the body of a response, or the
+/// kind and message of the error.
+type Response = Result<(Cursor<Vec<u8>>, Box<dyn Read + Send>), (std::io::ErrorKind, String)>;
+
+/// Replaces the transport of the given connection by one using the HTTP client
+/// of `weaver_net` if the remote is reached via HTTP(S).
+pub(crate) fn use_weaver_http(connection: &mut Connection<'_, '_, BoxedTransport>) {
+    let url = match connection.remote().url(Direction::Fetch) {
+        Some(url) if matches!(url.scheme, gix::url::Scheme::Http | gix::url::Scheme::Https) => {
+            url.clone()
+        }
+        _ => return,
+    };
+    *connection.transport_mut() = Box::new(http::Transport::new_http(
+        WeaverHttp,
+        url,
+        Protocol::V2,
+        false,
+    ));
+}
+
+/// An implementation of the HTTP operations needed by the git transport based
+/// on the HTTP client of `weaver_net`.
+struct WeaverHttp;
+
+impl Http for WeaverHttp {
+    type Headers = BufReader<ResponsePart>;
+    type ResponseBody = BufReader<ResponsePart>;
+    type PostBody = PostBody;
+
+    fn get(
+        &mut self,
+        url: &str,
+        _base_url: &str,
+        headers: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<GetResponse<Self::Headers, Self::ResponseBody>, http::Error> {
+        Ok(request("GET", url, headers)?.into())
+    }
+
+    fn post(
+        &mut self,
+        url: &str,
+        _base_url: &str,
+        headers: impl IntoIterator<Item = impl AsRef<str>>,
+        _body: PostBodyDataKind,
+    ) -> Result<PostResponse<Self::Headers, Self::ResponseBody, Self::PostBody>, http::Error> {
+        request("POST", url, headers)
+    }
+
+    fn configure(
+        &mut self,
+        _config: &dyn Any,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
+
+/// Prepares a request whose response is read via the returned readers, or
+/// returns the configuration error of the HTTP client.
+fn request(
+    method: &str,
+    url: &str,
+    headers: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<PostResponse<BufReader<ResponsePart>, BufReader<ResponsePart>, PostBody>, http::Error> {
+    let client = weaver_net::client().map_err(|e| http::Error::Detail {
+        description: e.to_string(),
+    })?;
+    let mut request = client.agent(url).request(method, url);
+    for header in headers {
+        if let Some((name, value)) = header.as_ref().split_once(':') {
+            request = request.set(name.trim(), value.trim());
+        }
+    }
+
+    let exchange = Arc::new(Mutex::new(Exchange {
+        url: url.to_owned(),
+        request: Some(request),
+        body: vec![],
+        response: None,
+    }));
+    Ok(PostResponse {
+        post_body: PostBody(exchange.clone()),
+        headers: BufReader::new(ResponsePart {
+            exchange: exchange.clone(),
+            headers: true,
+        }),
+        body: BufReader::new(ResponsePart {
+            exchange,
+            headers: false,
+        }),
+    })
+}
+
+/// A request and its response.
+struct Exchange {
+    /// The requested URL.
+    url: String,
+    /// The request to send (`None` once sent).
+    request: Option<ureq::Request>,
+    /// The body to post.
+    body: Vec<u8>,
+    /// The response (`None` until sent).
+    response: Option<Response>,
+}
+
+impl Exchange {
+    /// Sends the request if not already done.
+    fn send(&mut self) {
+        let request = match self.request.take() {
+            Some(request) => request,
+            None => return,
+        };
+        let result = if request.method() == "GET" {
+            request.call()
+        } else {
+            request.send_bytes(&self.body)
+        };
+
+        self.response = Some(match result {
+            Ok(response) => {
+                let mut headers = vec![];
+                for name in response.headers_names() {
+                    if let Some(value) = response.header(&name) {
+                        headers.extend_from_slice(format!("{}:{}\n", name, value).as_bytes());
+                    }
+                }
+                Ok((Cursor::new(headers), Box::new(response.into_reader())))
+            }
+            // The git transport expects a `PermissionDenied` error on 401 to
+            // ask for credentials.
+            Err(ureq::Error::Status(status, _)) => Err((
+                match status {
+                    401 => std::io::ErrorKind::PermissionDenied,
+                    500.. => std::io::ErrorKind::ConnectionAborted,
+                    _ => std::io::ErrorKind::Other,
+                },
+                format!("Received HTTP status {}", status),
+            )),
+            Err(e) => Err((
+                std::io::ErrorKind::Other,
+                weaver_net::request_error(&self.url, &e).to_string(),
+            )),
+        });
+    }
+}
+
+/// The body to post, buffered until the response is read.
+pub(crate) struct PostBody(Arc<Mutex<Exchange>>);
+
+impl Write for PostBody {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .expect("exchange lock failed")
+            .body
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The headers or the body of a response.
+pub(crate) struct ResponsePart {
+    exchange: Arc<Mutex<Exchange>>,
+    headers: bool,
+}
+
+impl Read for ResponsePart {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut exchange = self.exchange.lock().expect("exchange lock failed");
+        exchange.send();
+        match exchange.response.as_mut() {
+            Some(Ok((headers, _))) if self.headers => headers.read(buf),
+            Some(Ok((_, body))) => body.read(buf),
+            Some(Err((kind, message))) => Err(std::io::Error::new(*kind, message.clone())),
+            None => Ok(0),
+        }
+    }
+}
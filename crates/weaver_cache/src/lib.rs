@@ -6,19 +6,32 @@
 //! locally to avoid fetching them from the network every time.
 
 use std::default::Default;
-use std::fs::create_dir_all;
+use std::fs::{create_dir_all, remove_dir_all, rename};
 use std::num::NonZeroU32;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::lock::{stable_key, FileLock};
 use crate::Error::GitError;
+use gix::bstr::{BString, ByteSlice};
 use gix::clone::PrepareFetch;
 use gix::create::Kind;
-use gix::remote::fetch::Shallow;
+use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+use gix::refs::Target;
+use gix::remote::fetch::{Shallow, Tags};
+use gix::remote::Direction;
 use gix::{create, open, progress};
 use tempdir::TempDir;
 
+mod http;
+mod lock;
+pub mod verify;
+
+/// The environment variables consulted to locate the cache directory.
+pub const ENV_VARS: &[&str] = &["WEAVER_CACHE_DIR", "XDG_CACHE_HOME", "LOCALAPPDATA"];
+
 /// An error that can occur while creating or using a cache.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -42,6 +55,15 @@ pub enum Error {
         message: String,
     },
 
+    /// A cache lock could not be acquired.
+    #[error("Cache lock `{path}` not acquired: {message}")]
+    LockNotAcquired {
+        /// The path of the lock file
+        path: String,
+        /// The error message
+        message: String,
+    },
+
     /// A git error occurred.
     #[error("Git error occurred while cloning `{repo_url}`: {message}")]
     GitError {
@@ -50,31 +72,62 @@ pub enum Error {
         /// The error message
         message: String,
     },
+
+    /// A cache entry could not be repaired.
+    #[error("Cache entry `{path}` not repaired: {message}")]
+    RepairFailed {
+        /// The path of the cache entry
+        path: String,
+        /// The error message
+        message: String,
+    },
 }
 
 /// A cache system for OTel Weaver.
 #[derive(Default)]
 pub struct Cache {
     path: PathBuf,
+    refresh: bool,
     git_repo_dirs: Mutex<std::collections::HashMap<String, GitRepo>>,
 }
 
-/// A git repo that is cloned into a tempdir.
+/// The git reference checked out by default (i.e. the default branch of the
+/// remote repo).
+const DEFAULT_GIT_REF: &str = "HEAD";
+
+/// A git repo that is cloned into the cache.
 struct GitRepo {
-    /// Need to allow dead code because we need to keep the tempdir live
-    /// for the lifetime of the GitRepo.
-    #[allow(dead_code)]
-    temp_dir: TempDir,
     path: PathBuf,
+    info: GitRepoInfo,
+}
+
+/// Information about a git repo fetched by the cache.
+#[derive(Debug, Clone, Default)]
+pub struct GitRepoInfo {
+    /// The commit id of the HEAD of the cloned repo.
+    pub commit: Option<String>,
+    /// The git reference (e.g. branch name) of the HEAD of the cloned repo.
+    pub git_ref: Option<String>,
+    /// The time at which the repo was fetched, in seconds since the Unix epoch.
+    pub fetched_at: Option<u64>,
 }
 
 impl Cache {
-    /// Creates the `.otel-weaver/cache` directory in the home directory.
-    /// This directory is used to store the semantic conventions, schemas
-    /// and other assets that are fetched from the network.
-    pub fn try_new() -> Result<Self, Error> {
-        let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
-        let cache_path = home.join(".otel-weaver/cache");
+    /// Creates the cache directory used to store the semantic conventions,
+    /// schemas and other assets that are fetched from the network.
+    ///
+    /// The cache directory is, by order of precedence:
+    /// - the `cache_dir` parameter (e.g. set via the `--cache-dir` CLI flag),
+    /// - the `WEAVER_CACHE_DIR` environment variable,
+    /// - `$XDG_CACHE_HOME/otel-weaver` if `XDG_CACHE_HOME` is defined,
+    /// - `%LOCALAPPDATA%\otel-weaver\cache` on Windows,
+    /// - `~/Library/Caches/otel-weaver` on macOS,
+    /// - `~/.otel-weaver/cache` otherwise.
+    pub fn try_new(cache_dir: Option<PathBuf>) -> Result<Self, Error> {
+        let cache_path = match cache_dir {
+            Some(cache_dir) => cache_dir,
+            None => default_cache_dir(|var| std::env::var_os(var).map(PathBuf::from))?,
+        };
 
         create_dir_all(cache_path.as_path()).map_err(|e| Error::CacheDirNotCreated {
             message: e.to_string(),
@@ -86,6 +139,19 @@ impl Cache {
         })
     }
 
+    /// Forces the git repos to be cloned again instead of reusing the clones
+    /// persisted in the cache directory.
+    #[must_use]
+    pub fn with_refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Returns the path of the cache directory.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
     /// The given repo_url is cloned into the cache and the path to the repo is returned.
     /// The optional path parameter is relative to the root of the repo.
     /// The intent is to allow the caller to specify a subdirectory of the repo and
@@ -93,33 +159,133 @@ impl Cache {
     /// path is checked to exist in the repo and an error is returned if it doesn't.
     /// If the path exists in the repo, the returned pathbuf is the path to the
     /// subdirectory in the git repo directory.
+    ///
+    /// Clones are persisted in a deterministic directory of the cache (derived
+    /// from the repo URL, the checked out reference and the commit) and reused
+    /// across runs as long as the remote reference still points to the cached
+    /// commit. If the remote can't be reached, the cached clone is used as is.
+    /// A fresh clone is always made when the cache has been created with
+    /// [`Cache::with_refresh`].
+    ///
+    /// A clone is never modified once installed: a new commit is cloned into a
+    /// new directory and the reference then points to it, so the processes
+    /// sharing the cache directory can keep reading the clone they got without
+    /// holding any lock.
     pub fn git_repo(&self, repo_url: String, path: Option<String>) -> Result<PathBuf, Error> {
-        // Checks if a tempdir already exists for this repo
+        self.git_repo_at(repo_url, None, path)
+    }
+
+    /// Same as [`Cache::git_repo`], checking out the given git reference (a
+    /// branch or a tag) instead of the default branch of the remote repo.
+    pub fn git_repo_at(
+        &self,
+        repo_url: String,
+        git_ref: Option<String>,
+        path: Option<String>,
+    ) -> Result<PathBuf, Error> {
+        let git_ref = git_ref.unwrap_or_else(|| DEFAULT_GIT_REF.to_owned());
+        let repo_key = repo_key(&repo_url, &git_ref);
+
+        // Checks if the repo has already been resolved by this process
         if let Some(git_repo_dir) = self
             .git_repo_dirs
             .lock()
             .expect("git_repo_dirs lock failed")
-            .get(&repo_url)
+            .get(&repo_key)
         {
-            return Ok(git_repo_dir.path.clone());
+            return Self::repo_sub_path(&repo_url, &git_repo_dir.path, &path);
         }
 
-        // Otherwise creates a tempdir for the repo and keeps track of it
-        // in the git_repo_dirs hashmap.
-        let git_repo_dir = TempDir::new_in(self.path.as_path(), "git-repo").map_err(|e| {
-            Error::GitRepoNotCreated {
-                repo_url: repo_url.to_string(),
-                message: e.to_string(),
+        let key = stable_key(&format!("{}#{}", repo_url, git_ref));
+        let git_dir = self.path.join("git");
+        let head_path = self.path.join("heads").join(&key);
+
+        let current = ClonesHead::read(&head_path).and_then(|head| head.current_path(&git_dir));
+        let cached = match &current {
+            Some(current) if !self.refresh => {
+                cached_repo_info(current, &git_ref).map(|info| (current.clone(), info))
             }
-        })?;
-        let git_repo_pathbuf = git_repo_dir.path().to_path_buf();
-        let git_repo_path = git_repo_pathbuf.as_path();
+            _ => None,
+        };
+
+        let (git_repo_path, info) = match cached {
+            Some(cached) => cached,
+            None => {
+                // The clones of a given repo are installed under an exclusive
+                // lock, across the weaver processes sharing this cache
+                // directory. The lock is not needed to read them.
+                let _lock = self.acquire_lock(&key)?;
+                // Another process may have installed a new clone while this one
+                // was waiting for the lock.
+                let latest =
+                    ClonesHead::read(&head_path).and_then(|head| head.current_path(&git_dir));
+                match latest.filter(|latest| Some(latest) != current.as_ref()) {
+                    Some(latest) => {
+                        let info = local_repo_info(&latest, &git_ref).unwrap_or_default();
+                        (latest, info)
+                    }
+                    None => Self::clone_repo(&repo_url, &git_ref, &git_dir, &head_path)?,
+                }
+            }
+        };
 
-        // Clones the repo into the tempdir.
+        let result = Self::repo_sub_path(&repo_url, &git_repo_path, &path);
+
+        // Adds the repo to the git_repo_dirs hashmap.
+        _ = self
+            .git_repo_dirs
+            .lock()
+            .expect("git_repo_dirs lock failed")
+            .insert(
+                repo_key,
+                GitRepo {
+                    path: git_repo_path,
+                    info,
+                },
+            );
+
+        result
+    }
+
+    /// Blocks until the exclusive lock of the clones of the given key is
+    /// acquired.
+    fn acquire_lock(&self, key: &str) -> Result<FileLock, Error> {
+        let locks_dir = self.path.join("locks");
+        let lock_path = locks_dir.join(format!("{}.lock", key));
+        create_dir_all(&locks_dir)
+            .and_then(|_| FileLock::acquire(&lock_path))
+            .map_err(|e| Error::LockNotAcquired {
+                path: lock_path.display().to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Clones the given reference of the repo into a new directory of
+    /// `git_dir`, and makes it the current clone of the head file
+    /// `head_path`. Returns the path of the clone and its information.
+    ///
+    /// The repo is cloned into a staging directory and then renamed, so a
+    /// partially cloned repo is never observed. The staging directory is
+    /// removed if the clone fails. The clones superseded before the previous
+    /// one are removed.
+    fn clone_repo(
+        repo_url: &str,
+        git_ref: &str,
+        git_dir: &Path,
+        head_path: &Path,
+    ) -> Result<(PathBuf, GitRepoInfo), Error> {
+        let not_created = |e: std::io::Error| Error::GitRepoNotCreated {
+            repo_url: repo_url.to_string(),
+            message: e.to_string(),
+        };
+        create_dir_all(git_dir).map_err(not_created)?;
+        let staging_dir = TempDir::new_in(git_dir, "staging").map_err(not_created)?;
+
+        // Clones the repo into the staging dir.
         // Use shallow clone to save time and space.
         let mut fetch = PrepareFetch::new(
-            repo_url.as_str(),
-            git_repo_path,
+            repo_url,
+            staging_dir.path(),
             Kind::WithWorktree,
             create::Options {
                 destination_must_be_empty: true,
@@ -131,54 +297,372 @@ impl Cache {
             repo_url: repo_url.to_string(),
             message: e.to_string(),
         })?
-        .with_shallow(Shallow::DepthAtRemote(NonZeroU32::new(1).unwrap()));
+        .with_shallow(Shallow::DepthAtRemote(NonZeroU32::new(1).unwrap()))
+        .configure_connection(|connection| {
+            http::use_weaver_http(connection);
+            Ok(())
+        });
+        if git_ref != DEFAULT_GIT_REF {
+            // Only the given branch or tag is fetched.
+            let specs = ref_specs(git_ref);
+            fetch = fetch.configure_remote(move |mut remote| {
+                remote.replace_refspecs(specs.iter().map(String::as_str), Direction::Fetch)?;
+                Ok(remote.with_fetch_tags(Tags::None))
+            });
+        }
 
         let (mut prepare, _outcome) = fetch
             .fetch_then_checkout(progress::Discard, &AtomicBool::new(false))
             .map_err(|e| GitError {
                 repo_url: repo_url.to_string(),
-                message: e.to_string(),
+                message: error_chain(&e),
+            })?;
+        if git_ref != DEFAULT_GIT_REF {
+            // The worktree is checked out from HEAD, detached at the fetched
+            // reference.
+            detach_head(prepare.repo(), git_ref).map_err(|message| GitError {
+                repo_url: repo_url.to_string(),
+                message,
             })?;
+        }
 
-        let (_repo, _outcome) = prepare
+        let (repo, _outcome) = prepare
             .main_worktree(progress::Discard, &AtomicBool::new(false))
             .map_err(|e| GitError {
                 repo_url: repo_url.to_string(),
                 message: e.to_string(),
             })?;
 
-        // Determines the path to the repo.
-        let git_repo_path = if let Some(path) = &path {
-            // Checks the existence of the path in the repo.
-            // If the path doesn't exist, returns an error.
-            if !git_repo_path.join(path).exists() {
-                return Err(Error::GitError {
-                    repo_url: repo_url.to_string(),
-                    message: format!("Path `{}` not found in repo", path),
-                });
-            }
+        let mut info = repo_info(&repo, git_ref);
+        let fetched_at = unix_time(SystemTime::now());
+        info.fetched_at = Some(fetched_at);
+        drop(repo);
+
+        let key = head_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let commit = info.commit.as_deref().unwrap_or("unborn");
+        let dir_name = install_clone(
+            staging_dir,
+            git_dir,
+            &format!("{}-{}", key, &commit[..commit.len().min(12)]),
+        )
+        .map_err(not_created)?;
 
-            git_repo_path.join(path)
-        } else {
-            git_repo_path.to_path_buf()
+        let previous = ClonesHead::read(head_path).map(|head| head.current);
+        let head = ClonesHead {
+            git_ref: git_ref.to_owned(),
+            current: dir_name.clone(),
+            previous: previous.filter(|previous| *previous != dir_name),
         };
+        head.write(head_path).map_err(not_created)?;
+        head.remove_superseded(git_dir, &key);
 
-        // Adds the repo to the git_repo_dirs hashmap.
+        Ok((git_dir.join(dir_name), info))
+    }
+
+    /// Returns the path of the optional sub-directory `path` in the given repo
+    /// or an error if this path doesn't exist.
+    fn repo_sub_path(
+        repo_url: &str,
+        git_repo_path: &Path,
+        path: &Option<String>,
+    ) -> Result<PathBuf, Error> {
+        match path {
+            Some(path) => {
+                // Checks the existence of the path in the repo.
+                // If the path doesn't exist, returns an error.
+                if !git_repo_path.join(path).exists() {
+                    return Err(Error::GitError {
+                        repo_url: repo_url.to_string(),
+                        message: format!("Path `{}` not found in repo", path),
+                    });
+                }
+                Ok(git_repo_path.join(path))
+            }
+            None => Ok(git_repo_path.to_path_buf()),
+        }
+    }
+
+    /// Evicts the git repos resolved by this cache, so the following calls to
+    /// [`Cache::git_repo`] check their remote again (and clone the new remote
+    /// commits). The clones themselves are left in the cache directory.
+    pub fn evict_git_repos(&self) {
         self.git_repo_dirs
             .lock()
             .expect("git_repo_dirs lock failed")
-            .insert(
-                repo_url.to_string(),
-                GitRepo {
-                    temp_dir: git_repo_dir,
-                    path: git_repo_path,
+            .clear();
+    }
+
+    /// Returns the information (commit, reference, fetch time) of a git repo
+    /// previously fetched by [`Cache::git_repo`], or `None` if the repo is not
+    /// in the cache.
+    pub fn git_repo_info(&self, repo_url: &str) -> Option<GitRepoInfo> {
+        self.git_repo_dirs
+            .lock()
+            .expect("git_repo_dirs lock failed")
+            .get(repo_url)
+            .map(|git_repo| git_repo.info.clone())
+    }
+}
+
+/// Moves the clone of the given staging directory to a new directory of
+/// `git_dir` named after `dir_name` (suffixed if this directory already
+/// exists, e.g. when the same commit is cloned again) and returns the name of
+/// this directory. The staging directory is removed if it can't be moved.
+fn install_clone(staging_dir: TempDir, git_dir: &Path, dir_name: &str) -> std::io::Result<String> {
+    let dir_name = (0..)
+        .map(|i| {
+            if i == 0 {
+                dir_name.to_owned()
+            } else {
+                format!("{}-{}", dir_name, i)
+            }
+        })
+        .find(|name| !git_dir.join(name).exists())
+        .expect("infinite iterator");
+    rename(staging_dir.path(), git_dir.join(&dir_name))?;
+    // The staging directory has been moved, there is nothing left to remove.
+    _ = staging_dir.into_path();
+    Ok(dir_name)
+}
+
+/// The head file of the clones of a git reference (`heads/<key>`), pointing
+/// to the directory of its current clone. The file is replaced atomically.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ClonesHead {
+    /// The cloned git reference.
+    pub(crate) git_ref: String,
+    /// The directory name of the current clone.
+    pub(crate) current: String,
+    /// The directory name of the clone superseded by the current one, kept
+    /// for the processes still reading it.
+    pub(crate) previous: Option<String>,
+}
+
+impl ClonesHead {
+    /// Reads the given head file, or returns `None` if it doesn't exist or is
+    /// invalid.
+    pub(crate) fn read(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let field = |name: &str| {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(": "))
+                .map(str::to_owned)
+        };
+        Some(Self {
+            git_ref: field("ref")?,
+            current: field("current")?,
+            previous: field("previous"),
+        })
+    }
+
+    /// Writes this head to the given file, atomically replacing it.
+    fn write(&self, path: &Path) -> std::io::Result<()> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        create_dir_all(dir)?;
+        let mut content = format!("ref: {}\ncurrent: {}\n", self.git_ref, self.current);
+        if let Some(previous) = &self.previous {
+            content.push_str(&format!("previous: {}\n", previous));
+        }
+        let staging_dir = TempDir::new_in(dir, "staging")?;
+        let staging_file = staging_dir.path().join("head");
+        std::fs::write(&staging_file, content)?;
+        rename(staging_file, path)
+    }
+
+    /// Returns the path of the current clone in `git_dir`, if it exists.
+    fn current_path(&self, git_dir: &Path) -> Option<PathBuf> {
+        Some(git_dir.join(&self.current)).filter(|path| path.is_dir())
+    }
+
+    /// Returns true if the given directory name is the current or the
+    /// previous clone of this head.
+    pub(crate) fn references(&self, dir_name: &str) -> bool {
+        self.current == dir_name || self.previous.as_deref() == Some(dir_name)
+    }
+
+    /// Removes the clones of the given key not referenced by this head.
+    fn remove_superseded(&self, git_dir: &Path, key: &str) {
+        let prefix = format!("{}-", key);
+        for entry in std::fs::read_dir(git_dir).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with(&prefix) && !self.references(&name) {
+                _ = remove_dir_all(entry.path());
+            }
+        }
+    }
+}
+
+/// Returns the key of a git reference of a repo in the clones resolved by a
+/// cache.
+fn repo_key(repo_url: &str, git_ref: &str) -> String {
+    if git_ref == DEFAULT_GIT_REF {
+        repo_url.to_owned()
+    } else {
+        format!("{}@{}", repo_url, git_ref)
+    }
+}
+
+/// Returns the refspecs fetching the given branch or tag.
+fn ref_specs(git_ref: &str) -> Vec<String> {
+    vec![
+        format!("+refs/heads/{0}:refs/remotes/origin/{0}", git_ref),
+        format!("+refs/tags/{0}:refs/tags/{0}", git_ref),
+    ]
+}
+
+/// Detaches the HEAD of the given repo at the commit of the given fetched
+/// branch or tag.
+fn detach_head(repo: &gix::Repository, git_ref: &str) -> Result<(), String> {
+    let commit = [
+        format!("refs/remotes/origin/{}", git_ref),
+        format!("refs/tags/{}", git_ref),
+    ]
+    .iter()
+    .find_map(|name| repo.find_reference(name.as_str()).ok())
+    .ok_or_else(|| format!("Branch or tag `{}` not found", git_ref))?
+    .peel_to_id_in_place()
+    .map_err(|e| e.to_string())?
+    .detach();
+
+    _ = repo
+        .edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("checkout: moving to {}", git_ref).into(),
                 },
-            );
+                expected: PreviousValue::Any,
+                new: Target::Peeled(commit),
+            },
+            name: "HEAD"
+                .try_into()
+                .map_err(|e: gix::refs::name::Error| e.to_string())?,
+            deref: false,
+        })
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Returns the information of a clone persisted in the cache if this clone
+/// is still up to date with its remote, or `None` if it must be cloned again.
+/// A clone that can't be checked against its remote (e.g. no network) is
+/// considered up to date.
+fn cached_repo_info(git_repo_path: &Path, git_ref: &str) -> Option<GitRepoInfo> {
+    let info = local_repo_info(git_repo_path, git_ref)?;
+    let repo = open(git_repo_path).ok()?;
+    match remote_ref_id(&repo, git_ref) {
+        Some(remote_commit) if Some(&remote_commit) != info.commit.as_ref() => None,
+        _ => Some(info),
+    }
+}
+
+/// Returns the information of a clone persisted in the cache, without
+/// checking it against its remote.
+fn local_repo_info(git_repo_path: &Path, git_ref: &str) -> Option<GitRepoInfo> {
+    let repo = open(git_repo_path).ok()?;
+    let mut info = repo_info(&repo, git_ref);
+    info.fetched_at = std::fs::metadata(git_repo_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .map(unix_time);
+    Some(info)
+}
+
+/// Returns the commit id the remote currently associates with the reference
+/// checked out in the given repo, or `None` if the remote can't be queried.
+fn remote_ref_id(repo: &gix::Repository, git_ref: &str) -> Option<String> {
+    let ref_names: Vec<BString> = if git_ref == DEFAULT_GIT_REF {
+        vec![repo.head_name().ok()??.as_bstr().to_owned()]
+    } else {
+        vec![
+            format!("refs/heads/{}", git_ref).into(),
+            format!("refs/tags/{}", git_ref).into(),
+        ]
+    };
+    let remote = repo.find_default_remote(Direction::Fetch)?.ok()?;
+    let mut connection = remote.connect(Direction::Fetch).ok()?;
+    http::use_weaver_http(&mut connection);
+    let ref_map = connection
+        .ref_map(progress::Discard, Default::default())
+        .ok()?;
+
+    ref_map.remote_refs.iter().find_map(|remote_ref| {
+        let (name, id, peeled) = remote_ref.unpack();
+        ref_names
+            .iter()
+            .any(|ref_name| name == ref_name.as_bstr())
+            .then(|| peeled.or(id).map(|id| id.to_string()))
+            .flatten()
+    })
+}
 
-        Ok(git_repo_pathbuf)
+/// Returns the commit and reference checked out in the given repo (the given
+/// reference if the HEAD is detached).
+fn repo_info(repo: &gix::Repository, git_ref: &str) -> GitRepoInfo {
+    GitRepoInfo {
+        commit: repo.head_id().ok().map(|id| id.to_string()),
+        git_ref: repo
+            .head_name()
+            .ok()
+            .flatten()
+            .map(|name| name.shorten().to_string())
+            .or_else(|| (git_ref != DEFAULT_GIT_REF).then(|| git_ref.to_owned())),
+        fetched_at: None,
     }
 }
 
+/// Returns the message of the given error followed by the messages of its
+/// sources (e.g. the TLS verification failure behind a generic IO error).
+fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(error) = source {
+        let cause = error.to_string();
+        if !message.contains(&cause) {
+            message.push_str(": ");
+            message.push_str(&cause);
+        }
+        source = error.source();
+    }
+    message
+}
+
+/// Converts a system time into seconds since the Unix epoch.
+fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Determines the default cache directory from the environment (see
+/// [`Cache::try_new`]). The environment is accessed via the `env` closure to
+/// keep this function testable.
+fn default_cache_dir(env: impl Fn(&str) -> Option<PathBuf>) -> Result<PathBuf, Error> {
+    if let Some(dir) = env("WEAVER_CACHE_DIR").filter(|dir| !dir.as_os_str().is_empty()) {
+        return Ok(dir);
+    }
+    if let Some(dir) = env("XDG_CACHE_HOME").filter(|dir| dir.is_absolute()) {
+        return Ok(dir.join("otel-weaver"));
+    }
+    if cfg!(windows) {
+        if let Some(dir) = env("LOCALAPPDATA").or_else(dirs::cache_dir) {
+            return Ok(dir.join("otel-weaver").join("cache"));
+        }
+    }
+    if cfg!(target_os = "macos") {
+        if let Some(dir) = dirs::cache_dir() {
+            return Ok(dir.join("otel-weaver"));
+        }
+    }
+    let home = dirs::home_dir().ok_or(Error::HomeDirNotFound)?;
+    Ok(home.join(".otel-weaver").join("cache"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,7 +672,7 @@ mod tests {
     #[test]
     #[ignore]
     fn test_cache() {
-        let cache = Cache::try_new().unwrap();
+        let cache = Cache::try_new(None).unwrap();
         let result = cache.git_repo(
             "https://github.com/open-telemetry/semantic-conventions.git".into(),
             Some("model".into()),
@@ -196,4 +680,139 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().exists());
     }
+
+    #[test]
+    fn test_install_clone() {
+        let git_dir = TempDir::new("weaver-git").unwrap();
+        let install = |content: &str| {
+            let staging_dir = TempDir::new_in(git_dir.path(), "staging").unwrap();
+            std::fs::write(staging_dir.path().join("file"), content).unwrap();
+            install_clone(staging_dir, git_dir.path(), "repo")
+        };
+        let entries = || {
+            let mut entries: Vec<String> = std::fs::read_dir(git_dir.path())
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            entries.sort();
+            entries
+        };
+
+        // An installed clone is never replaced.
+        assert_eq!(install("v1").unwrap(), "repo");
+        assert_eq!(install("v2").unwrap(), "repo-1");
+        assert_eq!(
+            std::fs::read_to_string(git_dir.path().join("repo/file")).unwrap(),
+            "v1"
+        );
+        assert_eq!(entries(), vec!["repo", "repo-1"]);
+
+        // A clone that can't be moved doesn't leave its staging directory
+        // behind.
+        let staging_dir = TempDir::new_in(git_dir.path(), "staging").unwrap();
+        assert!(install_clone(staging_dir, git_dir.path(), "missing/repo").is_err());
+        assert_eq!(entries(), vec!["repo", "repo-1"]);
+    }
+
+    #[test]
+    fn test_git_repo() {
+        let git = |dir: &Path, args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=weaver",
+                    "-c",
+                    "user.email=weaver@example.com",
+                ])
+                .args(args)
+                .current_dir(dir)
+                .status();
+            assert!(
+                status.is_ok_and(|status| status.success()),
+                "git {:?}",
+                args
+            );
+        };
+        let remote = TempDir::new("weaver-remote").unwrap();
+        git(remote.path(), &["init", "-q", "-b", "main"]);
+        std::fs::write(remote.path().join("file"), "v1").unwrap();
+        git(remote.path(), &["add", "file"]);
+        git(remote.path(), &["commit", "-q", "-m", "v1"]);
+        git(remote.path(), &["tag", "v1"]);
+        std::fs::write(remote.path().join("file"), "v2").unwrap();
+        git(remote.path(), &["commit", "-q", "-am", "v2"]);
+        let url = format!("file://{}", remote.path().display());
+
+        let cache_dir = TempDir::new("weaver-cache").unwrap();
+        let read = |path: PathBuf| std::fs::read_to_string(path.join("file")).unwrap();
+        let cache = Cache::try_new(Some(cache_dir.path().to_path_buf())).unwrap();
+        let head = cache.git_repo(url.clone(), None).unwrap();
+        let tag = cache
+            .git_repo_at(url.clone(), Some("v1".to_owned()), None)
+            .unwrap();
+        assert_eq!(read(head.clone()), "v2");
+        assert_eq!(read(tag.clone()), "v1");
+        let info = cache.git_repo_info(&url).unwrap();
+        assert_eq!(info.git_ref.as_deref(), Some("main"));
+
+        // Another cache reuses the up to date clones.
+        let cache = Cache::try_new(Some(cache_dir.path().to_path_buf())).unwrap();
+        assert_eq!(cache.git_repo(url.clone(), None).unwrap(), head);
+
+        // A new remote commit is cloned into a new directory once the repo is
+        // evicted, the previous clone is left untouched for the processes
+        // still reading it.
+        std::fs::write(remote.path().join("file"), "v3").unwrap();
+        git(remote.path(), &["commit", "-q", "-am", "v3"]);
+        assert_eq!(cache.git_repo(url.clone(), None).unwrap(), head);
+        cache.evict_git_repos();
+        let new_head = cache.git_repo(url.clone(), None).unwrap();
+        assert_ne!(new_head, head);
+        assert_eq!(read(new_head.clone()), "v3");
+        assert_eq!(read(head.clone()), "v2");
+
+        // A refresh clones again, and only the current and previous clones
+        // are kept.
+        let cache = Cache::try_new(Some(cache_dir.path().to_path_buf()))
+            .unwrap()
+            .with_refresh(true);
+        let refreshed = cache.git_repo(url.clone(), None).unwrap();
+        assert_ne!(refreshed, new_head);
+        assert!(new_head.exists());
+        assert!(!head.exists());
+        assert!(tag.exists());
+        assert!(cache
+            .verify()
+            .iter()
+            .all(|entry| entry.status == verify::EntryStatus::Valid));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_default_cache_dir() {
+        let env = |vars: Vec<(&'static str, PathBuf)>| {
+            move |var: &str| {
+                vars.iter()
+                    .find(|(name, _)| *name == var)
+                    .map(|(_, value)| value.clone())
+            }
+        };
+        let root = PathBuf::from("/tmp");
+
+        // WEAVER_CACHE_DIR takes precedence over XDG_CACHE_HOME.
+        let dir = default_cache_dir(env(vec![
+            ("WEAVER_CACHE_DIR", root.join("weaver")),
+            ("XDG_CACHE_HOME", root.join("xdg")),
+        ]))
+        .unwrap();
+        assert_eq!(dir, root.join("weaver"));
+
+        // XDG_CACHE_HOME is only used when absolute (per the XDG spec).
+        let dir = default_cache_dir(env(vec![("XDG_CACHE_HOME", root.join("xdg"))])).unwrap();
+        assert_eq!(dir, root.join("xdg").join("otel-weaver"));
+        let dir =
+            default_cache_dir(env(vec![("XDG_CACHE_HOME", PathBuf::from("relative"))])).unwrap();
+        assert!(!dir.starts_with("relative"));
+        assert!(dir.ends_with("cache") || dir.ends_with("otel-weaver"));
+    }
 }
@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Advisory file locks used to coordinate concurrent weaver processes
+//! sharing the same cache directory.
+
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+
+use fs2::FileExt;
+
+/// An exclusive advisory lock on a lock file, held by the processes modifying
+/// the locked entry. The lock is released when the guard is dropped.
+pub(crate) struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Blocks until an exclusive lock is acquired on the given lock file.
+    /// The lock file is created if it doesn't exist.
+    pub(crate) fn acquire(path: &Path) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        file.lock_exclusive()?;
+        Ok(Self { file })
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        // Closing the file also releases the lock, so an unlock error can be
+        // safely ignored.
+        _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Returns a stable key (FNV-1a 64-bit hash) for the given string. Unlike the
+/// std hashers, the result is stable across Rust versions and platforms, so it
+/// can be used to name files shared between weaver processes.
+pub(crate) fn stable_key(value: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in value.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock() {
+        let dir = tempdir::TempDir::new("weaver-lock").unwrap();
+        let path = dir.path().join("repo.lock");
+        {
+            let _lock = FileLock::acquire(&path).unwrap();
+            assert!(path.exists());
+        }
+        // The lock is released once the guard is dropped.
+        let lock = FileLock::acquire(&path).unwrap();
+        drop(lock);
+
+        // An exclusive lock excludes the other ones.
+        let lock = FileLock::acquire(&path).unwrap();
+        let file = File::open(&path).unwrap();
+        assert!(file.try_lock_exclusive().is_err());
+        drop(lock);
+        assert!(file.try_lock_exclusive().is_ok());
+
+        assert_eq!(stable_key(""), "cbf29ce484222325");
+        assert_ne!(stable_key("a"), stable_key("b"));
+    }
+}
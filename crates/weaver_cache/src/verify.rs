@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Integrity checks of the cache directory.
+//!
+//! The cache directory contains the git repos cloned by [`Cache::git_repo`]
+//! (`git/<key>-<commit>`), the head files pointing to the current clone of
+//! each repo (`heads/<key>`) and the lock files coordinating the weaver
+//! processes cloning into the cache (`locks/<key>.lock`). The files imported
+//! from a URL are not
+//! cached, so only the git repos are checked: the objects of the checked out
+//! tree must be present and the content of every checked out file must match
+//! the hash of its git object.
+
+use std::fs::{remove_dir_all, remove_file};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::{Cache, ClonesHead, Error, DEFAULT_GIT_REF};
+
+/// Delay after which a staging directory left by a clone is considered as
+/// the leftover of an interrupted clone (and not of a clone in progress).
+const STALE_STAGING_DELAY: Duration = Duration::from_secs(3600);
+
+/// The status of an entry of the cache directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntryStatus {
+    /// The entry is valid.
+    Valid,
+    /// The entry is corrupt (e.g. missing git objects, modified files).
+    Corrupt {
+        /// A description of the corruption.
+        message: String,
+    },
+    /// The entry is not used by the cache (e.g. the leftover of an
+    /// interrupted clone).
+    Orphaned {
+        /// The reason why the entry is orphaned.
+        message: String,
+    },
+}
+
+/// An entry of the cache directory and its status.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    /// The path of the entry.
+    pub path: PathBuf,
+    /// The URL of the git repo cloned in this entry (if known).
+    pub repo_url: Option<String>,
+    /// The status of the entry.
+    pub status: EntryStatus,
+}
+
+impl Cache {
+    /// Walks the cache directory and checks the integrity of every git repo,
+    /// head and lock file. The entries are returned sorted by path.
+    pub fn verify(&self) -> Vec<CacheEntry> {
+        let mut entries = vec![];
+        let git_dir = self.path.join("git");
+        let heads_dir = self.path.join("heads");
+        let orphaned = |path: PathBuf, message: &str| CacheEntry {
+            path,
+            repo_url: None,
+            status: EntryStatus::Orphaned {
+                message: message.to_owned(),
+            },
+        };
+
+        for path in dir_entries(&git_dir) {
+            let file_name = file_name(&path);
+            if is_leftover(&path) {
+                entries.push(orphaned(path, "leftover of an interrupted clone"));
+                continue;
+            }
+            if file_name.starts_with("staging") || file_name.starts_with("stale") {
+                // Clone in progress.
+                continue;
+            }
+            // The clones are never modified once installed, they can be
+            // checked without any lock.
+            let key = file_name.split('-').next().unwrap_or_default();
+            match ClonesHead::read(&heads_dir.join(key)) {
+                Some(head) if head.references(&file_name) => {
+                    let (repo_url, status) = verify_git_repo(&path);
+                    entries.push(CacheEntry {
+                        path,
+                        repo_url,
+                        status,
+                    });
+                }
+                _ => entries.push(orphaned(path, "clone superseded by a newer clone")),
+            }
+        }
+
+        for path in dir_entries(&heads_dir) {
+            if file_name(&path).starts_with("staging") {
+                if is_leftover(&path) {
+                    entries.push(orphaned(path, "leftover of an interrupted update"));
+                }
+                continue;
+            }
+            match ClonesHead::read(&path) {
+                Some(head) if git_dir.join(&head.current).is_dir() => entries.push(CacheEntry {
+                    path,
+                    repo_url: None,
+                    status: EntryStatus::Valid,
+                }),
+                Some(_) => entries.push(orphaned(path, "head of a clone that is not in the cache")),
+                None => entries.push(orphaned(path, "invalid head file")),
+            }
+        }
+
+        for path in dir_entries(&self.path.join("locks")) {
+            let key = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if heads_dir.join(&key).exists() {
+                entries.push(CacheEntry {
+                    path,
+                    repo_url: None,
+                    status: EntryStatus::Valid,
+                });
+            } else {
+                entries.push(orphaned(
+                    path,
+                    "lock of a git repo that is not in the cache",
+                ));
+            }
+        }
+
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
+    }
+
+    /// Repairs the given entry: a corrupt git repo is cloned again from its
+    /// URL (or removed if its URL is unknown), an orphaned entry is removed.
+    pub fn repair(&self, entry: &CacheEntry) -> Result<(), Error> {
+        let remove = |path: &Path| {
+            let result = if path.is_dir() {
+                remove_dir_all(path)
+            } else {
+                remove_file(path)
+            };
+            result.map_err(|e| Error::RepairFailed {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })
+        };
+
+        match (&entry.status, &entry.repo_url) {
+            (EntryStatus::Valid, _) => Ok(()),
+            (EntryStatus::Corrupt { .. }, Some(repo_url)) => {
+                let file_name = file_name(&entry.path);
+                let key = file_name.split('-').next().unwrap_or_default();
+                let head_path = self.path.join("heads").join(key);
+                let git_ref = ClonesHead::read(&head_path)
+                    .map(|head| head.git_ref)
+                    .unwrap_or_else(|| DEFAULT_GIT_REF.to_owned());
+                let _lock = self.acquire_lock(key)?;
+                _ = Self::clone_repo(repo_url, &git_ref, &self.path.join("git"), &head_path)?;
+                remove(&entry.path)
+            }
+            (EntryStatus::Corrupt { .. }, None) | (EntryStatus::Orphaned { .. }, _) => {
+                remove(&entry.path)
+            }
+        }
+    }
+}
+
+/// Returns the file name of the given path.
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Returns true if the given path is a staging directory left by an
+/// interrupted clone or head update (and not by one in progress).
+fn is_leftover(path: &Path) -> bool {
+    let file_name = file_name(path);
+    (file_name.starts_with("staging") || file_name.starts_with("stale"))
+        && std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age > STALE_STAGING_DELAY)
+}
+
+/// Returns the paths of the entries of the given directory (empty if the
+/// directory doesn't exist).
+fn dir_entries(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Checks the git repo cloned in the given directory and returns its URL (if
+/// known) and its status.
+fn verify_git_repo(path: &Path) -> (Option<String>, EntryStatus) {
+    let corrupt = |message: String| EntryStatus::Corrupt { message };
+
+    let repo = match gix::open(path) {
+        Ok(repo) => repo,
+        Err(e) => return (None, corrupt(format!("not a git repo ({})", e))),
+    };
+    let repo_url = repo
+        .find_default_remote(gix::remote::Direction::Fetch)
+        .and_then(|remote| remote.ok())
+        .and_then(|remote| {
+            remote
+                .url(gix::remote::Direction::Fetch)
+                .map(|url| url.to_bstring().to_string())
+        });
+
+    let Some(work_dir) = repo.work_dir().map(Path::to_path_buf) else {
+        return (repo_url, corrupt("no checked out files".to_owned()));
+    };
+    let files = match repo
+        .head_commit()
+        .map_err(|e| e.to_string())
+        .and_then(|commit| commit.tree().map_err(|e| e.to_string()))
+        .and_then(|tree| {
+            tree.traverse()
+                .breadthfirst
+                .files()
+                .map_err(|e| e.to_string())
+        }) {
+        Ok(files) => files,
+        Err(e) => return (repo_url, corrupt(format!("invalid HEAD tree ({})", e))),
+    };
+
+    for file in files.iter().filter(|file| file.mode.is_blob()) {
+        if !repo.has_object(file.oid) {
+            return (
+                repo_url,
+                corrupt(format!("missing git object for `{}`", file.filepath)),
+            );
+        }
+        let file_path = work_dir.join(file.filepath.to_string());
+        match std::fs::read(&file_path) {
+            Ok(content) => {
+                let hash =
+                    gix::objs::compute_hash(file.oid.kind(), gix::objs::Kind::Blob, &content);
+                if hash != file.oid {
+                    return (
+                        repo_url,
+                        corrupt(format!("content of `{}` doesn't match", file.filepath)),
+                    );
+                }
+            }
+            Err(e) => {
+                return (
+                    repo_url,
+                    corrupt(format!("`{}` not readable ({})", file.filepath, e)),
+                );
+            }
+        }
+    }
+
+    (repo_url, EntryStatus::Valid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_and_repair() {
+        let dir = tempdir::TempDir::new("weaver-cache").unwrap();
+        let cache = Cache::try_new(Some(dir.path().to_path_buf())).unwrap();
+        for clone in ["0000000000000001-aaaa", "0000000000000001-bbbb"] {
+            std::fs::create_dir_all(dir.path().join("git").join(clone)).unwrap();
+        }
+        std::fs::create_dir_all(dir.path().join("heads")).unwrap();
+        std::fs::write(
+            dir.path().join("heads/0000000000000001"),
+            "ref: HEAD\ncurrent: 0000000000000001-bbbb\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("heads/0000000000000003"),
+            "ref: HEAD\ncurrent: 0000000000000003-cccc\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("locks")).unwrap();
+        std::fs::write(dir.path().join("locks/0000000000000001.lock"), "").unwrap();
+        std::fs::write(dir.path().join("locks/0000000000000002.lock"), "").unwrap();
+
+        let entries = cache.verify();
+        let statuses: Vec<(&str, bool, bool)> = entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.path.file_name().unwrap().to_str().unwrap(),
+                    matches!(entry.status, EntryStatus::Corrupt { .. }),
+                    matches!(entry.status, EntryStatus::Orphaned { .. }),
+                )
+            })
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("0000000000000001-aaaa", false, true),
+                ("0000000000000001-bbbb", true, false),
+                ("0000000000000001", false, false),
+                ("0000000000000003", false, true),
+                ("0000000000000001.lock", false, false),
+                ("0000000000000002.lock", false, true),
+            ]
+        );
+
+        for entry in entries.iter() {
+            cache.repair(entry).unwrap();
+        }
+        assert!(!dir.path().join("git/0000000000000001-aaaa").exists());
+        assert!(!dir.path().join("git/0000000000000001-bbbb").exists());
+        assert!(!dir.path().join("heads/0000000000000003").exists());
+        assert!(!dir.path().join("locks/0000000000000002.lock").exists());
+    }
+}
@@ -0,0 +1,75 @@
+/* SPDX-License-Identifier: Apache-2.0 */
+
+#ifndef WEAVER_FFI_H
+#define WEAVER_FFI_H
+
+/* Generated by cbindgen from `src/lib.rs` (`just ffi`), do not edit. */
+
+/**
+ * A resolved semantic convention registry (opaque to the C callers).
+ */
+typedef struct WeaverRegistry WeaverRegistry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Frees a registry returned by [`weaver_resolve_registry`]. Does nothing if
+ * `registry` is null.
+ *
+ * # Safety
+ *
+ * `registry` must be a registry returned by [`weaver_resolve_registry`] and
+ * not yet freed.
+ */
+void weaver_free_registry(WeaverRegistry *registry);
+
+/**
+ * Frees a string returned by [`weaver_registry_json`]. Does nothing if
+ * `string` is null.
+ *
+ * # Safety
+ *
+ * `string` must be a string returned by [`weaver_registry_json`] and not yet
+ * freed.
+ */
+void weaver_free_string(char *string);
+
+/**
+ * Returns the message of the last error that occurred on the calling thread,
+ * or null if no error occurred. The message remains valid until the next
+ * failing call on this thread and must not be freed.
+ */
+const char *weaver_last_error(void);
+
+/**
+ * Returns the JSON representation of the given resolved registry. Returns
+ * null on error (see [`weaver_last_error`]).
+ *
+ * The returned string must be freed with [`weaver_free_string`].
+ *
+ * # Safety
+ *
+ * `registry` must be a registry returned by [`weaver_resolve_registry`] and
+ * not yet freed.
+ */
+char *weaver_registry_json(const WeaverRegistry *registry);
+
+/**
+ * Loads and resolves the semantic convention registry stored in the given
+ * local directory. Returns null on error (see [`weaver_last_error`]).
+ *
+ * The returned registry must be freed with [`weaver_free_registry`].
+ *
+ * # Safety
+ *
+ * `registry_path` must be a valid NUL-terminated string.
+ */
+WeaverRegistry *weaver_resolve_registry(const char *registry_path);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WEAVER_FFI_H */
@@ -0,0 +1,236 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A C ABI to embed the resolver of OTel Weaver in other language toolchains
+//! (e.g. the Java and Python build plugins) instead of shelling out to the
+//! CLI.
+//!
+//! The functions are declared in `include/weaver_ffi.h`, generated from this
+//! file by cbindgen (`just ffi`). A typical usage is:
+//!
+//! ```c
+//! WeaverRegistry *registry = weaver_resolve_registry("model");
+//! if (registry == NULL) {
+//!     fprintf(stderr, "%s\n", weaver_last_error());
+//!     return 1;
+//! }
+//! char *json = weaver_registry_json(registry);
+//! /* ... */
+//! weaver_free_string(json);
+//! weaver_free_registry(registry);
+//! ```
+//!
+//! A panic in weaver is reported as an error of the failing call only if the
+//! libraries are built with `panic = "unwind"`: the workspace `release`
+//! profile aborts on panic, so the libraries are built with the `release-ffi`
+//! profile (`cargo build -p weaver_ffi --profile release-ffi`).
+
+#![deny(missing_docs)]
+#![deny(clippy::print_stdout)]
+#![deny(clippy::print_stderr)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr::null_mut;
+
+use weaver_cache::Cache;
+use weaver_logger::NullLogger;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::SemConvImport;
+use weaver_semconv::ResolverConfig;
+
+thread_local! {
+    /// The message of the last error that occurred on the current thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A resolved semantic convention registry (opaque to the C callers).
+pub struct WeaverRegistry {
+    schema: ResolvedTelemetrySchema,
+}
+
+/// Records the given error as the last error of the current thread.
+fn set_last_error(message: String) {
+    // An error message can't contain a NUL character, they are replaced.
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+}
+
+/// Runs the given function, converting an error or a panic into a null
+/// pointer and recording the corresponding last error. A panic aborts the
+/// process instead if the crate is built with `panic = "abort"`.
+fn ffi_call<T>(f: impl FnOnce() -> Result<*mut T, String>) -> *mut T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(ptr)) => ptr,
+        Ok(Err(message)) => {
+            set_last_error(message);
+            null_mut()
+        }
+        Err(_) => {
+            set_last_error("Unexpected panic in weaver".to_owned());
+            null_mut()
+        }
+    }
+}
+
+/// Loads and resolves the semantic convention registry stored in the given
+/// local directory. Returns null on error (see [`weaver_last_error`]).
+///
+/// The returned registry must be freed with [`weaver_free_registry`].
+///
+/// # Safety
+///
+/// `registry_path` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_resolve_registry(
+    registry_path: *const c_char,
+) -> *mut WeaverRegistry {
+    ffi_call(|| {
+        if registry_path.is_null() {
+            return Err("The registry path is null".to_owned());
+        }
+        let registry_path = CStr::from_ptr(registry_path)
+            .to_str()
+            .map_err(|e| format!("Invalid registry path: {}", e))?;
+        if !Path::new(registry_path).exists() {
+            return Err(format!("Registry `{}` not found", registry_path));
+        }
+
+        let cache = Cache::try_new(None).map_err(|e| e.to_string())?;
+        let imports = [SemConvImport::LocalPath {
+            local_path: registry_path.to_owned(),
+        }];
+        let log = NullLogger::new();
+        let mut registry = SchemaResolver::semconv_registry_from_imports(
+            &imports,
+            ResolverConfig::with_keep_specs(),
+            &cache,
+            log.clone(),
+        )
+        .map_err(|e| e.to_string())?;
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry, log)
+            .map_err(|e| e.to_string())?;
+
+        Ok(Box::into_raw(Box::new(WeaverRegistry { schema })))
+    })
+}
+
+/// Returns the JSON representation of the given resolved registry. Returns
+/// null on error (see [`weaver_last_error`]).
+///
+/// The returned string must be freed with [`weaver_free_string`].
+///
+/// # Safety
+///
+/// `registry` must be a registry returned by [`weaver_resolve_registry`] and
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_registry_json(registry: *const WeaverRegistry) -> *mut c_char {
+    ffi_call(|| {
+        let Some(registry) = registry.as_ref() else {
+            return Err("The registry is null".to_owned());
+        };
+        let json = serde_json::to_string(&registry.schema).map_err(|e| e.to_string())?;
+        CString::new(json)
+            .map(CString::into_raw)
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Returns the message of the last error that occurred on the calling thread,
+/// or null if no error occurred. The message remains valid until the next
+/// failing call on this thread and must not be freed.
+#[no_mangle]
+pub extern "C" fn weaver_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Frees a registry returned by [`weaver_resolve_registry`]. Does nothing if
+/// `registry` is null.
+///
+/// # Safety
+///
+/// `registry` must be a registry returned by [`weaver_resolve_registry`] and
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_free_registry(registry: *mut WeaverRegistry) {
+    if !registry.is_null() {
+        drop(Box::from_raw(registry));
+    }
+}
+
+/// Frees a string returned by [`weaver_registry_json`]. Does nothing if
+/// `string` is null.
+///
+/// # Safety
+///
+/// `string` must be a string returned by [`weaver_registry_json`] and not yet
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn weaver_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_registry() {
+        let path =
+            CString::new("../weaver_resolver/data/registry-test-3-extends/registry").unwrap();
+        unsafe {
+            let registry = weaver_resolve_registry(path.as_ptr());
+            assert!(!registry.is_null());
+
+            let json = weaver_registry_json(registry);
+            assert!(!json.is_null());
+            let value: serde_json::Value =
+                serde_json::from_str(CStr::from_ptr(json).to_str().unwrap()).unwrap();
+            assert!(!value["catalog"]["attributes"]
+                .as_array()
+                .unwrap()
+                .is_empty());
+
+            weaver_free_string(json);
+            weaver_free_registry(registry);
+        }
+    }
+
+    #[test]
+    fn test_resolve_registry_error() {
+        let path = CString::new("does/not/exist").unwrap();
+        unsafe {
+            assert!(weaver_resolve_registry(path.as_ptr()).is_null());
+            assert!(!weaver_last_error().is_null());
+            assert!(weaver_resolve_registry(std::ptr::null()).is_null());
+            assert_eq!(
+                CStr::from_ptr(weaver_last_error()).to_str().unwrap(),
+                "The registry path is null"
+            );
+        }
+    }
+
+    #[test]
+    fn test_panic() {
+        let result: *mut u8 = ffi_call(|| panic!("broken"));
+        assert!(result.is_null());
+        unsafe {
+            assert_eq!(
+                CStr::from_ptr(weaver_last_error()).to_str().unwrap(),
+                "Unexpected panic in weaver"
+            );
+        }
+    }
+}
@@ -6,7 +6,11 @@
 #![deny(clippy::print_stdout)]
 #![deny(clippy::print_stderr)]
 
+use std::io::Write;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use paris::formatter::colorize_string;
 
 /// A trait that defines the interface of a logger.
 pub trait Logger {
@@ -45,6 +49,34 @@ pub trait Logger {
 
     /// Logs a message without icon.
     fn log(&self, message: &str) -> &Self;
+
+    /// Records the duration of a phase of the command (e.g. `parse`,
+    /// `resolve`, `render`). The durations of the same phase are cumulated.
+    fn phase(&self, name: &str, duration: Duration) -> &Self;
+}
+
+/// The durations of the phases recorded by a logger, shared by its clones.
+#[derive(Default, Clone, Debug)]
+pub struct PhaseTimings {
+    /// Phase name -> cumulated duration, in the order of the first record.
+    phases: Arc<Mutex<Vec<(String, Duration)>>>,
+}
+
+impl PhaseTimings {
+    /// Adds a duration to the given phase.
+    pub fn record(&self, name: &str, duration: Duration) {
+        let mut phases = self.phases.lock().expect("Failed to lock timings");
+        match phases.iter_mut().find(|(phase, _)| phase == name) {
+            Some((_, total)) => *total += duration,
+            None => phases.push((name.to_owned(), duration)),
+        }
+    }
+
+    /// Returns the recorded phases with their cumulated duration, in the
+    /// order of their first record.
+    pub fn phases(&self) -> Vec<(String, Duration)> {
+        self.phases.lock().expect("Failed to lock timings").clone()
+    }
 }
 
 /// A generic logger that can be used to log messages to the console.
@@ -53,6 +85,8 @@ pub trait Logger {
 pub struct ConsoleLogger {
     logger: Arc<Mutex<paris::Logger<'static>>>,
     debug_level: u8,
+    no_color: bool,
+    timings: PhaseTimings,
 }
 
 impl ConsoleLogger {
@@ -61,55 +95,117 @@ impl ConsoleLogger {
         ConsoleLogger {
             logger: Arc::new(Mutex::new(paris::Logger::new())),
             debug_level,
+            no_color: false,
+            timings: PhaseTimings::default(),
+        }
+    }
+
+    /// Returns the durations of the phases recorded by this logger (and its
+    /// clones).
+    pub fn timings(&self) -> PhaseTimings {
+        self.timings.clone()
+    }
+
+    /// Enables or disables the ANSI colors and styles of the messages.
+    /// Without colors, the messages are written as plain text (icons
+    /// included) and the loading spinner is replaced by a plain message
+    /// logged only with debug enabled.
+    #[must_use]
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.no_color = !color;
+        self
+    }
+
+    /// Logs a message on stdout, without colors if they are disabled.
+    fn stdout(&self, icon: &str, message: &str) {
+        let mut logger = self.logger.lock().expect("Failed to lock logger");
+        if self.no_color {
+            _ = logger.log(plain(&format!("{}{}", icon, message)));
+            return;
+        }
+        match icon {
+            INFO => _ = logger.info(message),
+            SUCCESS => _ = logger.success(message),
+            WARN => _ = logger.warn(message),
+            _ => _ = logger.log(message),
+        }
+    }
+
+    /// Logs an error message on stderr, without colors if they are
+    /// disabled.
+    fn stderr(&self, message: &str) {
+        let mut logger = self.logger.lock().expect("Failed to lock logger");
+        if self.no_color {
+            _ = logger.done();
+            // Nothing sensible to do if stderr is closed.
+            _ = writeln!(
+                std::io::stderr(),
+                "{}",
+                plain(&format!("{}{}", ERROR, message))
+            );
+            return;
         }
+        _ = logger.error(message);
     }
 }
 
+/// The icons (with their colors) prefixing the messages.
+const INFO: &str = "<cyan><info></> ";
+const SUCCESS: &str = "<green><tick></> ";
+const WARN: &str = "<yellow><warn></> ";
+const ERROR: &str = "<red><cross></> ";
+
+/// Returns a message as plain text, i.e. with its keys (`<cyan>`, `<info>`,
+/// ...) replaced by their icon or removed.
+fn plain(message: &str) -> String {
+    let colorized = colorize_string(message);
+    let mut plain = String::with_capacity(colorized.len());
+    let mut chars = colorized.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // Skips the ANSI escape sequence (`ESC [ params letter`).
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
 impl Logger for ConsoleLogger {
     /// Logs an trace message (only with debug enabled).
     fn trace(&self, message: &str) -> &Self {
         if self.debug_level > 0 {
-            self.logger
-                .lock()
-                .expect("Failed to lock logger")
-                .log(message);
+            self.stdout("", message);
         }
         self
     }
 
     /// Logs an info message.
     fn info(&self, message: &str) -> &Self {
-        self.logger
-            .lock()
-            .expect("Failed to lock logger")
-            .info(message);
+        self.stdout(INFO, message);
         self
     }
 
     /// Logs a warning message.
     fn warn(&self, message: &str) -> &Self {
-        self.logger
-            .lock()
-            .expect("Failed to lock logger")
-            .warn(message);
+        self.stdout(WARN, message);
         self
     }
 
     /// Logs an error message.
     fn error(&self, message: &str) -> &Self {
-        self.logger
-            .lock()
-            .expect("Failed to lock logger")
-            .error(message);
+        self.stderr(message);
         self
     }
 
     /// Logs a success message.
     fn success(&self, message: &str) -> &Self {
-        self.logger
-            .lock()
-            .expect("Failed to lock logger")
-            .success(message);
+        self.stdout(SUCCESS, message);
         self
     }
 
@@ -147,6 +243,9 @@ impl Logger for ConsoleLogger {
 
     /// Logs a loading message with a spinner.
     fn loading(&self, message: &str) -> &Self {
+        if self.no_color {
+            return self.trace(message);
+        }
         self.logger
             .lock()
             .expect("Failed to lock logger")
@@ -162,10 +261,13 @@ impl Logger for ConsoleLogger {
 
     /// Logs a message without icon.
     fn log(&self, message: &str) -> &Self {
-        self.logger
-            .lock()
-            .expect("Failed to lock logger")
-            .log(message);
+        self.stdout("", message);
+        self
+    }
+
+    /// Records the duration of a phase of the command.
+    fn phase(&self, name: &str, duration: Duration) -> &Self {
+        self.timings.record(name, duration);
         self
     }
 }
@@ -239,4 +341,43 @@ impl Logger for NullLogger {
     fn log(&self, _: &str) -> &Self {
         self
     }
+
+    /// Records the duration of a phase of the command.
+    fn phase(&self, _: &str, _: Duration) -> &Self {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{plain, ConsoleLogger, Logger, ERROR, INFO};
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(plain(&format!("{}{}", INFO, "message")), "ℹ message");
+        assert_eq!(
+            plain(&format!("{}{}", ERROR, "<bold>bold</> message")),
+            "✖ bold message"
+        );
+        assert_eq!(plain("no keys"), "no keys");
+    }
+
+    #[test]
+    fn test_phase_timings() {
+        let log = ConsoleLogger::new(0);
+        _ = log
+            .clone()
+            .phase("parse", Duration::from_millis(10))
+            .phase("resolve", Duration::from_millis(5));
+        _ = log.phase("parse", Duration::from_millis(20));
+        assert_eq!(
+            log.timings().phases(),
+            vec![
+                ("parse".to_owned(), Duration::from_millis(30)),
+                ("resolve".to_owned(), Duration::from_millis(5)),
+            ]
+        );
+    }
 }
@@ -4,7 +4,7 @@
 
 //! Specification of a resolved attribute.
 
-use crate::catalog::Stability;
+use crate::catalog::{Deprecated, Stability};
 use crate::tags::Tags;
 use crate::value::Value;
 use ordered_float::OrderedFloat;
@@ -21,6 +21,7 @@ pub struct Attribute {
     /// array type, a template type or an enum definition.
     pub r#type: AttributeType,
     /// A brief description of the attribute.
+    #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub brief: String,
     /// Sequence of example values for the attribute or single example
@@ -42,7 +43,9 @@ pub struct Attribute {
     /// specify the conditions under which the attribute is required.
     pub requirement_level: RequirementLevel,
     /// Specifies if the attribute is (especially) relevant for sampling
-    /// and thus should be set at span start. It defaults to false.
+    /// and thus should be set at span start, or when the event or log
+
+    /// record is created. It defaults to false.
     /// Note: this field is experimental.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sampling_relevant: Option<bool>,
@@ -58,11 +61,11 @@ pub struct Attribute {
     /// error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stability: Option<Stability>,
-    /// Specifies if the attribute is deprecated. The string
-    /// provided as <description> MUST specify why it's deprecated and/or what
-    /// to use instead. See also stability.
+    /// Specifies if the attribute is deprecated, either with a note or with a
+    /// structured deprecation (reason, `renamed_to`, `removed_in`). See also
+    /// stability.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub deprecated: Option<String>,
+    pub deprecated: Option<Deprecated>,
     /// A set of tags for the attribute.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Tags>,
@@ -151,6 +154,14 @@ pub struct EnumEntries {
     /// It defaults to an empty string.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
+    /// Specifies the stability of the enum entry.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// Specifies if the enum entry is deprecated. See also stability.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Deprecated>,
 }
 
 /// The different types of examples.
@@ -232,6 +243,7 @@ pub enum RequirementLevel {
     /// A conditional requirement level.
     ConditionallyRequired {
         /// The description of the condition.
+        #[serde(default)]
         #[serde(skip_serializing_if = "String::is_empty")]
         text: String,
     },
@@ -3,10 +3,12 @@
 //! Defines the catalog of attributes, metrics, and other telemetry items
 //! that are shared across multiple signals in the Resolved Telemetry Schema.
 
-use crate::attribute::Attribute;
+use crate::attribute::{Attribute, AttributeType};
 use crate::metric::Metric;
+use crate::value::Value;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::mem::discriminant;
 
 /// A catalog of attributes, metrics, and other telemetry signals that are shared
 /// in the Resolved Telemetry Schema.
@@ -14,13 +16,47 @@ use std::fmt::Debug;
 #[serde(deny_unknown_fields)]
 pub struct Catalog {
     /// Catalog of attributes used in the schema.
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub attributes: Vec<Attribute>,
     /// Catalog of metrics used in the schema.
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub metrics: Vec<Metric>,
 }
 
+impl Catalog {
+    /// Returns the attributes of the given type.
+    /// Enum attributes are matched regardless of their members, i.e. any
+    /// `AttributeType::Enum` returns all the enum attributes.
+    pub fn attributes_of_type<'a>(
+        &'a self,
+        r#type: &'a AttributeType,
+    ) -> impl Iterator<Item = &'a Attribute> + 'a {
+        self.attributes
+            .iter()
+            .filter(move |attr| discriminant(&attr.r#type) == discriminant(r#type))
+    }
+
+    /// Returns the enum attributes having a member with the given value.
+    pub fn attributes_with_enum_member<'a>(
+        &'a self,
+        value: &'a Value,
+    ) -> impl Iterator<Item = &'a Attribute> + 'a {
+        self.attributes.iter().filter(move |attr| {
+            matches!(&attr.r#type, AttributeType::Enum { members, .. }
+                if members.iter().any(|member| member.value == *value))
+        })
+    }
+
+    /// Returns the metrics measured in the given unit.
+    pub fn metrics_with_unit<'a>(&'a self, unit: &'a str) -> impl Iterator<Item = &'a Metric> + 'a {
+        self.metrics
+            .iter()
+            .filter(move |metric| metric.unit.as_deref() == Some(unit))
+    }
+}
+
 /// The level of stability for a definition.
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Stability {
@@ -28,6 +64,117 @@ pub enum Stability {
     Deprecated,
     /// An experimental definition.
     Experimental,
+    /// A release candidate definition.
+    ReleaseCandidate,
     /// A stable definition.
     Stable,
+    /// A stability level unknown to the resolver, preserved as is.
+    Other(String),
+}
+
+/// The deprecation of a definition, either a free-form note or a structured
+/// deprecation.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(untagged)]
+pub enum Deprecated {
+    /// A free-form deprecation note.
+    Note(String),
+    /// A structured deprecation.
+    Structured(StructuredDeprecation),
+}
+
+/// A structured deprecation.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct StructuredDeprecation {
+    /// Why the definition is deprecated.
+    pub reason: String,
+    /// The id of the attribute or the name of the metric replacing the
+    /// deprecated definition, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<String>,
+    /// The version in which the deprecated definition is planned to be
+    /// removed, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_in: Option<String>,
+}
+
+impl Deprecated {
+    /// Returns the reason of the deprecation (the note of a free-form
+    /// deprecation).
+    pub fn reason(&self) -> &str {
+        match self {
+            Deprecated::Note(note) => note,
+            Deprecated::Structured(deprecation) => &deprecation.reason,
+        }
+    }
+
+    /// Returns the id or the name replacing the deprecated definition, if
+    /// declared by a structured deprecation.
+    pub fn renamed_to(&self) -> Option<&str> {
+        match self {
+            Deprecated::Note(_) => None,
+            Deprecated::Structured(deprecation) => deprecation.renamed_to.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{Attribute, AttributeType, EnumEntries};
+    use crate::catalog::Catalog;
+    use crate::test_utils::{attribute, metric};
+    use crate::value::Value;
+
+    #[test]
+    fn test_catalog_queries() {
+        let get = Value::String {
+            value: "GET".to_owned(),
+        };
+        let catalog = Catalog {
+            attributes: vec![
+                attribute("server.port", AttributeType::Int),
+                attribute("server.address", AttributeType::String),
+                attribute(
+                    "http.request.method",
+                    AttributeType::Enum {
+                        allow_custom_values: true,
+                        members: vec![EnumEntries {
+                            id: "get".to_owned(),
+                            value: get.clone(),
+                            brief: None,
+                            note: None,
+                            stability: None,
+                            deprecated: None,
+                        }],
+                    },
+                ),
+            ],
+            metrics: vec![metric("http.server.request.duration", "s")],
+        };
+
+        let names = |attrs: Vec<&Attribute>| -> Vec<String> {
+            attrs.iter().map(|attr| attr.name.clone()).collect()
+        };
+        assert_eq!(
+            names(catalog.attributes_of_type(&AttributeType::Int).collect()),
+            vec!["server.port"]
+        );
+        let any_enum = AttributeType::Enum {
+            allow_custom_values: false,
+            members: vec![],
+        };
+        assert_eq!(
+            names(catalog.attributes_of_type(&any_enum).collect()),
+            vec!["http.request.method"]
+        );
+        assert_eq!(
+            names(catalog.attributes_with_enum_member(&get).collect()),
+            vec!["http.request.method"]
+        );
+        assert_eq!(catalog.metrics_with_unit("s").count(), 1);
+        assert_eq!(catalog.metrics_with_unit("ms").count(), 0);
+    }
 }
@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flat view of the entities of a resolved telemetry schema, used to stream
+//! a resolved schema one entity at a time (e.g. in NDJSON).
+
+use serde::Serialize;
+
+use crate::attribute::Attribute;
+use crate::metric::Metric;
+use crate::registry::Group;
+use crate::ResolvedTelemetrySchema;
+
+/// An entity of a resolved telemetry schema tagged with its kind.
+#[derive(Serialize, Debug)]
+#[serde(tag = "entity", rename_all = "snake_case")]
+pub enum Entity<'a> {
+    /// An attribute of the catalog.
+    Attribute {
+        /// The reference of the attribute, as used by the groups.
+        r#ref: u32,
+        /// The attribute.
+        #[serde(flatten)]
+        attribute: &'a Attribute,
+    },
+    /// A group of a registry.
+    Group {
+        /// The URL of the registry containing the group.
+        #[serde(skip_serializing_if = "str::is_empty")]
+        registry_url: &'a str,
+        /// The group.
+        #[serde(flatten)]
+        group: &'a Group,
+    },
+    /// A metric of the catalog.
+    Metric(&'a Metric),
+}
+
+impl ResolvedTelemetrySchema {
+    /// Returns the attributes of the catalog, then the groups of the
+    /// registries, then the metrics of the catalog.
+    pub fn entities(&self) -> impl Iterator<Item = Entity<'_>> {
+        let attributes = self
+            .catalog
+            .attributes
+            .iter()
+            .enumerate()
+            .map(|(index, attribute)| Entity::Attribute {
+                r#ref: index as u32,
+                attribute,
+            });
+        let groups = self.registries.iter().flat_map(|registry| {
+            registry.groups.iter().map(|group| Entity::Group {
+                registry_url: &registry.registry_url,
+                group,
+            })
+        });
+        let metrics = self.catalog.metrics.iter().map(Entity::Metric);
+        attributes.chain(groups).chain(metrics)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{AttributeRef, AttributeType};
+    use crate::registry::TypedGroup;
+    use crate::test_utils::{attribute, group, schema};
+
+    #[test]
+    fn test_entities() {
+        let schema = schema(
+            vec![group(
+                "registry.server",
+                TypedGroup::AttributeGroup {},
+                vec![AttributeRef(0)],
+            )],
+            vec![attribute("server.port", AttributeType::Int)],
+        );
+
+        let lines: Vec<serde_json::Value> = schema
+            .entities()
+            .map(|entity| serde_json::to_value(entity).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["entity"], "attribute");
+        assert_eq!(lines[0]["ref"], 0);
+        assert_eq!(lines[0]["name"], "server.port");
+        assert_eq!(lines[1]["entity"], "group");
+        assert_eq!(lines[1]["registry_url"], "https://semconv-registry.com");
+        assert_eq!(lines[1]["id"], "registry.server");
+        assert_eq!(lines[1]["attributes"][0], 0);
+    }
+}
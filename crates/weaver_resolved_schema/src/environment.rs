@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Define the resolution environment, i.e. the inputs other than the
+//! registries themselves that affected the production of a resolved
+//! telemetry schema. Together with the registry manifest, this makes it
+//! possible to audit (and reproduce) a resolution.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The environment in which a resolved telemetry schema was produced.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResolutionEnvironment {
+    /// The command line arguments of the weaver process (credentials
+    /// embedded in URLs are redacted).
+    pub command_line: Vec<String>,
+    /// The environment variables consulted by weaver that were defined. Their
+    /// values are `***` unless explicitly recorded (credentials embedded in
+    /// URLs being redacted).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env_vars: BTreeMap<String, String>,
+    /// The configuration files read by weaver (e.g. network configuration,
+    /// CA bundle).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub config_files: Vec<ConfigFile>,
+    /// The build of weaver that produced the resolved schema.
+    pub build: BuildInfo,
+}
+
+/// A configuration file read during the resolution.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    /// The path of the file.
+    pub path: String,
+    /// The SHA-256 of the content of the file (hex encoded), or none if the
+    /// file could not be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Information about a build of weaver.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BuildInfo {
+    /// The version of weaver.
+    pub version: String,
+    /// The commit id weaver was built from (if built from a git checkout).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// The version of the Rust compiler used to build weaver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rustc_version: Option<String>,
+    /// The target triple weaver was built for.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// The build profile (`debug` or `release`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flattened view of a resolved telemetry schema, for the consumers that
+//! don't understand the attribute references: the attributes of each group
+//! (and of the resource) are materialized inline instead of being referenced
+//! in the catalog. The attributes shared by several groups are repeated in
+//! each of them, trading size for simplicity.
+
+use serde::Serialize;
+
+use crate::attribute::{Attribute, AttributeRef};
+use crate::catalog::{Catalog, Deprecated, Stability};
+use crate::environment::ResolutionEnvironment;
+use crate::manifest::RegistryManifest;
+use crate::metric::Metric;
+use crate::registry::{Constraint, Group, Registry, TypedGroup};
+use crate::ResolvedTelemetrySchema;
+use weaver_version::Versions;
+
+/// A resolved telemetry schema without catalog of attributes.
+///
+/// Note: the instrumentation library and the dependencies are not part of
+/// the flattened view (a resolved registry has none).
+#[derive(Serialize, Debug)]
+pub struct FlatTelemetrySchema<'a> {
+    /// Version of the file structure.
+    pub file_format: &'a str,
+    /// Schema URL that this file is published at.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub schema_url: &'a str,
+    /// The semantic convention registries with their attributes inline.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub registries: Vec<FlatRegistry<'a>>,
+    /// The attributes of the resource (only for application).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<Vec<&'a Attribute>>,
+    /// The metrics of the catalog.
+    #[serde(skip_serializing_if = "<[Metric]>::is_empty")]
+    pub metrics: &'a [Metric],
+    /// Definitions for each schema version in this family.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub versions: Option<&'a Versions>,
+    /// The manifest of the imported semantic convention registries.
+    #[serde(skip_serializing_if = "<[RegistryManifest]>::is_empty")]
+    pub registry_manifest: &'a [RegistryManifest],
+    /// The environment in which the resolved schema was produced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_environment: Option<&'a ResolutionEnvironment>,
+}
+
+/// A semantic convention registry with the attributes of its groups inline.
+#[derive(Serialize, Debug)]
+pub struct FlatRegistry<'a> {
+    /// The semantic convention registry url.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub registry_url: &'a str,
+    /// The groups of the registry.
+    pub groups: Vec<FlatGroup<'a>>,
+}
+
+/// A group with its attributes inline.
+///
+/// Note: the lineage of the group is not part of the flattened view as it
+/// identifies the attributes by their reference in the catalog.
+#[derive(Serialize, Debug)]
+pub struct FlatGroup<'a> {
+    /// The id that uniquely identifies the semantic convention.
+    pub id: &'a str,
+    /// The type of the group including the specific fields for each type.
+    pub typed_group: &'a TypedGroup,
+    /// A brief description of the semantic convention.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub brief: &'a str,
+    /// A more elaborate description of the semantic convention.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub note: &'a str,
+    /// Prefix for the attributes for this semantic convention.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub prefix: &'a str,
+    /// The id of the extended semantic convention.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<&'a str>,
+    /// The stability of the semantic convention.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<&'a Stability>,
+    /// The deprecation of the semantic convention.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<&'a Deprecated>,
+    /// Additional constraints.
+    #[serde(skip_serializing_if = "<[Constraint]>::is_empty")]
+    pub constraints: &'a [Constraint],
+    /// The attributes of the group.
+    pub attributes: Vec<&'a Attribute>,
+}
+
+impl ResolvedTelemetrySchema {
+    /// Returns the flattened view of the resolved schema, i.e. the registries
+    /// and the resource with their attributes inline. The references missing
+    /// from the catalog (only in an invalid schema) are ignored.
+    pub fn flatten(&self) -> FlatTelemetrySchema<'_> {
+        FlatTelemetrySchema {
+            file_format: &self.file_format,
+            schema_url: &self.schema_url,
+            registries: self
+                .registries
+                .iter()
+                .map(|registry| registry.flatten(&self.catalog))
+                .collect(),
+            resource: self
+                .resource
+                .as_ref()
+                .map(|resource| attributes(&self.catalog, &resource.attributes)),
+            metrics: &self.catalog.metrics,
+            versions: self.versions.as_ref(),
+            registry_manifest: &self.registry_manifest,
+            resolution_environment: self.resolution_environment.as_ref(),
+        }
+    }
+}
+
+impl Registry {
+    /// Returns the registry with the attributes of its groups inline, the
+    /// attribute references being resolved with the given catalog.
+    pub fn flatten<'a>(&'a self, catalog: &'a Catalog) -> FlatRegistry<'a> {
+        FlatRegistry {
+            registry_url: &self.registry_url,
+            groups: self
+                .groups
+                .iter()
+                .map(|group| group.flatten(catalog))
+                .collect(),
+        }
+    }
+}
+
+impl Group {
+    /// Returns the group with its attributes inline, the attribute
+    /// references being resolved with the given catalog.
+    pub fn flatten<'a>(&'a self, catalog: &'a Catalog) -> FlatGroup<'a> {
+        FlatGroup {
+            id: &self.id,
+            typed_group: &self.typed_group,
+            brief: &self.brief,
+            note: &self.note,
+            prefix: &self.prefix,
+            extends: self.extends.as_deref(),
+            stability: self.stability.as_ref(),
+            deprecated: self.deprecated.as_ref(),
+            constraints: &self.constraints,
+            attributes: attributes(catalog, &self.attributes),
+        }
+    }
+}
+
+/// Returns the attributes of the catalog with the given references.
+fn attributes<'a>(catalog: &'a Catalog, attr_refs: &[AttributeRef]) -> Vec<&'a Attribute> {
+    attr_refs
+        .iter()
+        .filter_map(|attr_ref| catalog.attributes.get(attr_ref.0 as usize))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{AttributeRef, AttributeType};
+    use crate::registry::TypedGroup;
+    use crate::test_utils::{attribute, group, schema};
+
+    #[test]
+    fn test_flatten() {
+        let schema = schema(
+            vec![
+                group(
+                    "registry.server",
+                    TypedGroup::AttributeGroup {},
+                    vec![AttributeRef(0), AttributeRef(1)],
+                ),
+                group(
+                    "server",
+                    TypedGroup::AttributeGroup {},
+                    vec![AttributeRef(1)],
+                ),
+            ],
+            vec![
+                attribute("server.address", AttributeType::Int),
+                attribute("server.port", AttributeType::Int),
+            ],
+        );
+
+        let flat = serde_json::to_value(schema.flatten()).unwrap();
+        assert!(flat.get("catalog").is_none());
+        let groups = &flat["registries"][0]["groups"];
+        assert_eq!(groups[0]["id"], "registry.server");
+        assert_eq!(groups[0]["typed_group"]["type"], "AttributeGroup");
+        assert_eq!(groups[0]["attributes"][0]["name"], "server.address");
+        assert_eq!(groups[0]["attributes"][1]["name"], "server.port");
+        // The shared attribute is repeated in each group.
+        assert_eq!(groups[1]["attributes"][0]["name"], "server.port");
+    }
+}
@@ -9,7 +9,9 @@
 #![deny(clippy::print_stderr)]
 
 use crate::catalog::Catalog;
+use crate::environment::ResolutionEnvironment;
 use crate::instrumentation_library::InstrumentationLibrary;
+use crate::manifest::RegistryManifest;
 use crate::registry::Registry;
 use crate::resource::Resource;
 use serde::{Deserialize, Serialize};
@@ -17,13 +19,21 @@ use weaver_version::Versions;
 
 pub mod attribute;
 pub mod catalog;
+pub mod entity;
+pub mod environment;
+pub mod flat;
 pub mod instrumentation_library;
 pub mod lineage;
+pub mod manifest;
+pub mod merge;
 pub mod metric;
 pub mod registry;
 pub mod resource;
+pub mod resource_entity;
 pub mod signal;
 pub mod tags;
+#[cfg(test)]
+mod test_utils;
 pub mod value;
 
 /// Weaver protobuf definitions.
@@ -46,6 +56,7 @@ pub struct ResolvedTelemetrySchema {
     pub schema_url: String,
     /// A list of semantic convention registries that can be used in this schema
     /// and its descendants.
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub registries: Vec<Registry>,
     /// Catalog of unique items that are shared across multiple registries
@@ -59,6 +70,7 @@ pub struct ResolvedTelemetrySchema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instrumentation_library: Option<InstrumentationLibrary>,
     /// The list of dependencies of the current instrumentation application or library.
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<InstrumentationLibrary>,
     /// Definitions for each schema version in this family.
@@ -69,6 +81,16 @@ pub struct ResolvedTelemetrySchema {
     /// <https://github.com/open-telemetry/oteps/blob/main/text/0152-telemetry-schemas.md>
     #[serde(skip_serializing_if = "Option::is_none")]
     pub versions: Option<Versions>,
+    /// The manifest of the semantic convention registries imported to
+    /// produce this resolved schema (URL, commit, fetch time, ...).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub registry_manifest: Vec<RegistryManifest>,
+    /// The environment in which this resolved schema was produced (command
+    /// line, environment variables, configuration files, weaver build).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_environment: Option<ResolutionEnvironment>,
 }
 
 #[cfg(test)]
@@ -13,6 +13,11 @@ pub enum ResolutionMode {
     Reference,
     /// Represents the resolution of an `extends` clause.
     Extends,
+    /// Represents the expansion of an `include` constraint.
+    Include,
+    /// Represents a default value inherited from the group of the attribute
+    /// (e.g. the group stability).
+    GroupDefault,
 }
 
 /// Field id.
@@ -83,6 +88,18 @@ pub struct FieldLineage {
     pub group_id: String,
 }
 
+/// A field of a referenced attribute redefined with a different value by an
+/// attribute reference.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldOverride {
+    /// The id of the group defining the referenced attribute.
+    pub group_id: String,
+    /// The value of the field in the referenced attribute (JSON).
+    pub before: String,
+    /// The value of the field in the attribute reference (JSON).
+    pub after: String,
+}
+
 /// Group lineage.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct GroupLineage {
@@ -96,6 +113,10 @@ pub struct GroupLineage {
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     #[serde(default)]
     attributes: BTreeMap<AttributeRef, BTreeMap<FieldId, FieldLineage>>,
+    /// The fields of the referenced attributes overridden per attribute.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[serde(default)]
+    attribute_overrides: BTreeMap<AttributeRef, BTreeMap<FieldId, FieldOverride>>,
 }
 
 impl GroupLineage {
@@ -105,6 +126,7 @@ impl GroupLineage {
             provenance,
             fields: BTreeMap::new(),
             attributes: BTreeMap::new(),
+            attribute_overrides: BTreeMap::new(),
         }
     }
 
@@ -130,6 +152,21 @@ impl GroupLineage {
         }
     }
 
+    /// Adds a field of a referenced attribute overridden by an attribute
+    /// reference.
+    pub fn add_attribute_field_override(
+        &mut self,
+        attr_ref: AttributeRef,
+        field_id: FieldId,
+        field_override: FieldOverride,
+    ) {
+        _ = self
+            .attribute_overrides
+            .entry(attr_ref)
+            .or_default()
+            .insert(field_id, field_override);
+    }
+
     /// Returns the provenance of the group.
     pub fn provenance(&self) -> &str {
         &self.provenance
@@ -139,4 +176,21 @@ impl GroupLineage {
     pub fn field_lineage(&self, field_id: &FieldId) -> Option<&FieldLineage> {
         self.fields.get(field_id)
     }
+
+    /// Returns the lineage of the fields of the specified attribute.
+    pub fn attribute_lineage(
+        &self,
+        attr_ref: &AttributeRef,
+    ) -> Option<&BTreeMap<FieldId, FieldLineage>> {
+        self.attributes.get(attr_ref)
+    }
+
+    /// Returns the fields of the referenced attribute overridden by the
+    /// specified attribute.
+    pub fn attribute_overrides(
+        &self,
+        attr_ref: &AttributeRef,
+    ) -> Option<&BTreeMap<FieldId, FieldOverride>> {
+        self.attribute_overrides.get(attr_ref)
+    }
 }
@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Define the registry manifest, i.e. the exact provenance of the semantic
+//! convention registries used to produce a resolved telemetry schema.
+//!
+//! The manifest is built by the loader of the semantic convention registries,
+//! see [`weaver_semconv::SemConvSpecs::registry_manifest`].
+
+pub use weaver_semconv::manifest::RegistryManifest;
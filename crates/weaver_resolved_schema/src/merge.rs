@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Composition of resolved catalogs and registries, for the tools combining
+//! resolved artifacts after their resolution.
+//!
+//! The attributes are deduplicated by content (as done by the resolver), the
+//! attribute references of the merged groups are remapped to the merged
+//! catalog, and the conflicting definitions are reported as
+//! [`MergeConflict`]s. The metrics and groups keep their first definition,
+//! the attributes defined with different types are both kept (the groups of
+//! each side reference their own definition).
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::attribute::{Attribute, AttributeRef};
+use crate::catalog::Catalog;
+use crate::registry::{Group, Registry};
+use crate::ResolvedTelemetrySchema;
+
+/// A definition of the merged catalog or registry conflicting with an
+/// existing one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeConflict {
+    /// An attribute defined with different types.
+    Attribute {
+        /// The name of the attribute.
+        name: String,
+    },
+    /// A metric defined with different instruments, units or descriptions.
+    Metric {
+        /// The name of the metric.
+        name: String,
+    },
+    /// A group defined with different contents in the same registry.
+    Group {
+        /// The url of the registry.
+        registry_url: String,
+        /// The id of the group.
+        id: String,
+    },
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeConflict::Attribute { name } => {
+                write!(f, "attribute `{}` is defined with different types", name)
+            }
+            MergeConflict::Metric { name } => {
+                write!(f, "metric `{}` is defined differently", name)
+            }
+            MergeConflict::Group { registry_url, id } if registry_url.is_empty() => {
+                write!(f, "group `{}` is defined differently", id)
+            }
+            MergeConflict::Group { registry_url, id } => write!(
+                f,
+                "group `{}` is defined differently in registry `{}`",
+                id, registry_url
+            ),
+        }
+    }
+}
+
+/// The mapping of the attribute references of a merged catalog to the
+/// references of the catalog it has been merged into (see
+/// [`Catalog::merge`]).
+#[derive(Debug, Clone, Default)]
+pub struct AttributeRemap {
+    refs: Vec<AttributeRef>,
+}
+
+impl AttributeRemap {
+    /// Returns the reference in the merged catalog of an attribute of the
+    /// catalog that has been merged, or `None` if the reference is unknown.
+    pub fn get(&self, attr_ref: AttributeRef) -> Option<AttributeRef> {
+        self.refs.get(attr_ref.0 as usize).copied()
+    }
+
+    /// Remaps a list of attribute references, dropping the unknown
+    /// references.
+    pub fn remap(&self, attr_refs: &[AttributeRef]) -> Vec<AttributeRef> {
+        attr_refs
+            .iter()
+            .filter_map(|attr_ref| self.get(*attr_ref))
+            .collect()
+    }
+}
+
+impl Catalog {
+    /// Merges the attributes and the metrics of another catalog into this
+    /// catalog. The attributes already present (same content) are reused,
+    /// the metrics already present (same name) are kept. Returns the mapping
+    /// of the attribute references of the other catalog and the conflicts,
+    /// i.e. the attributes defined with different types and the metrics
+    /// defined differently.
+    pub fn merge(&mut self, other: &Catalog) -> (AttributeRemap, Vec<MergeConflict>) {
+        let mut conflicts = vec![];
+        let mut attr_refs: HashMap<Attribute, AttributeRef> = self
+            .attributes
+            .iter()
+            .enumerate()
+            .map(|(index, attr)| (attr.clone(), AttributeRef(index as u32)))
+            .collect();
+        let mut remap = AttributeRemap::default();
+        for attr in other.attributes.iter() {
+            if let Some(attr_ref) = attr_refs.get(attr) {
+                remap.refs.push(*attr_ref);
+                continue;
+            }
+            let conflict = MergeConflict::Attribute {
+                name: attr.name.clone(),
+            };
+            let conflicting = self
+                .attributes
+                .iter()
+                .any(|existing| existing.name == attr.name && existing.r#type != attr.r#type);
+            if conflicting && !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+            // Attributes with the same name but different briefs, notes or
+            // requirement levels coexist in the catalog (see the resolver).
+            self.attributes.push(attr.clone());
+            let attr_ref = AttributeRef((self.attributes.len() - 1) as u32);
+            _ = attr_refs.insert(attr.clone(), attr_ref);
+            remap.refs.push(attr_ref);
+        }
+
+        for metric in other.metrics.iter() {
+            match self
+                .metrics
+                .iter()
+                .find(|existing| existing.name == metric.name)
+            {
+                Some(existing) if existing != metric => conflicts.push(MergeConflict::Metric {
+                    name: metric.name.clone(),
+                }),
+                Some(_) => {}
+                None => self.metrics.push(metric.clone()),
+            }
+        }
+        (remap, conflicts)
+    }
+}
+
+impl Registry {
+    /// Merges the groups of another registry into this registry, remapping
+    /// their attribute references with the mapping returned by
+    /// [`Catalog::merge`]. The groups already present (same id and same
+    /// content, lineage excluded) are skipped. Returns the groups defined
+    /// with a different content, which are not merged.
+    pub fn merge(&mut self, other: &Registry, remap: &AttributeRemap) -> Vec<MergeConflict> {
+        let mut conflicts = vec![];
+        for group in other.groups.iter() {
+            let group = Group {
+                attributes: remap.remap(&group.attributes),
+                ..group.clone()
+            };
+            match self.groups.iter().find(|existing| existing.id == group.id) {
+                Some(existing) if !same_content(existing, &group) => {
+                    conflicts.push(MergeConflict::Group {
+                        registry_url: self.registry_url.clone(),
+                        id: group.id.clone(),
+                    })
+                }
+                Some(_) => {}
+                None => self.groups.push(group),
+            }
+        }
+        conflicts
+    }
+}
+
+impl ResolvedTelemetrySchema {
+    /// Merges the catalog and the registries of another resolved schema
+    /// into this schema. The registries with the same url are merged, the
+    /// other ones are added. Returns the conflicts of the merge.
+    ///
+    /// Note: the resource, the instrumentation library, the dependencies and
+    /// the versions of the other schema are not merged.
+    pub fn merge_registries(&mut self, other: &ResolvedTelemetrySchema) -> Vec<MergeConflict> {
+        let (remap, mut conflicts) = self.catalog.merge(&other.catalog);
+        for registry in other.registries.iter() {
+            match self
+                .registries
+                .iter_mut()
+                .find(|existing| existing.registry_url == registry.registry_url)
+            {
+                Some(existing) => conflicts.extend(existing.merge(registry, &remap)),
+                None => {
+                    let mut merged = Registry {
+                        registry_url: registry.registry_url.clone(),
+                        groups: vec![],
+                    };
+                    conflicts.extend(merged.merge(registry, &remap));
+                    self.registries.push(merged);
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Returns true if two groups have the same content, regardless of their
+/// lineage (the provenance of the groups usually differs).
+fn same_content(a: &Group, b: &Group) -> bool {
+    Group {
+        lineage: None,
+        ..a.clone()
+    } == Group {
+        lineage: None,
+        ..b.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{AttributeRef, AttributeType};
+    use crate::catalog::Catalog;
+    use crate::merge::MergeConflict;
+    use crate::registry::{Group, Registry, TypedGroup};
+    use crate::test_utils::{attribute, group, metric};
+
+    /// Returns an attribute group with the given brief.
+    fn attribute_group(id: &str, brief: &str, attributes: Vec<AttributeRef>) -> Group {
+        Group {
+            brief: brief.to_owned(),
+            ..group(id, TypedGroup::AttributeGroup {}, attributes)
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut catalog = Catalog {
+            attributes: vec![
+                attribute("server.address", AttributeType::String),
+                attribute("server.port", AttributeType::Int),
+            ],
+            metrics: vec![metric("http.server.request.duration", "s")],
+        };
+        let mut registry = Registry {
+            registry_url: String::new(),
+            groups: vec![attribute_group(
+                "registry.server",
+                "Server.",
+                vec![AttributeRef(0), AttributeRef(1)],
+            )],
+        };
+        let other_catalog = Catalog {
+            attributes: vec![
+                attribute("server.port", AttributeType::String),
+                attribute("client.address", AttributeType::String),
+                attribute("server.address", AttributeType::String),
+            ],
+            metrics: vec![
+                metric("http.server.request.duration", "ms"),
+                metric("http.client.request.duration", "s"),
+            ],
+        };
+        let other_registry = Registry {
+            registry_url: String::new(),
+            groups: vec![
+                attribute_group("registry.server", "Server.", vec![AttributeRef(2)]),
+                attribute_group("registry.client", "Client.", vec![AttributeRef(1)]),
+            ],
+        };
+
+        let (remap, conflicts) = catalog.merge(&other_catalog);
+        // The identical attribute is reused, the other ones are added.
+        assert_eq!(catalog.attributes.len(), 4);
+        assert_eq!(remap.get(AttributeRef(0)), Some(AttributeRef(2)));
+        assert_eq!(remap.get(AttributeRef(1)), Some(AttributeRef(3)));
+        assert_eq!(remap.get(AttributeRef(2)), Some(AttributeRef(0)));
+        assert_eq!(remap.get(AttributeRef(3)), None);
+        assert_eq!(catalog.metrics.len(), 2);
+        assert_eq!(
+            conflicts,
+            vec![
+                MergeConflict::Attribute {
+                    name: "server.port".to_owned()
+                },
+                MergeConflict::Metric {
+                    name: "http.server.request.duration".to_owned()
+                },
+            ]
+        );
+
+        let conflicts = registry.merge(&other_registry, &remap);
+        assert_eq!(
+            conflicts,
+            vec![MergeConflict::Group {
+                registry_url: String::new(),
+                id: "registry.server".to_owned()
+            }]
+        );
+        assert_eq!(
+            conflicts[0].to_string(),
+            "group `registry.server` is defined differently"
+        );
+        assert_eq!(registry.groups.len(), 2);
+        assert_eq!(registry.groups[1].attributes, vec![AttributeRef(3)]);
+
+        // Merging the same registry twice is a no-op.
+        let (remap, conflicts) = catalog.merge(&catalog.clone());
+        assert!(conflicts.is_empty());
+        assert!(registry.merge(&registry.clone(), &remap).is_empty());
+        assert_eq!(catalog.attributes.len(), 4);
+        assert_eq!(registry.groups.len(), 2);
+    }
+}
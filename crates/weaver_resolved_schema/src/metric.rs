@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 pub struct MetricRef(pub u32);
 
 /// A metric definition.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Metric {
     /// Metric name.
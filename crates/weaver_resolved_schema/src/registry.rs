@@ -7,16 +7,17 @@
 use crate::attribute::{AttributeRef, UnresolvedAttribute};
 use serde::{Deserialize, Serialize};
 
-use crate::catalog::Stability;
+use crate::catalog::{Deprecated, Stability};
 use crate::lineage::GroupLineage;
 use crate::metric::Instrument;
-use crate::signal::SpanKind;
+use crate::signal::{BodyField, SpanKind, SpanName};
 
 /// A semantic convention registry.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct Registry {
     /// The semantic convention registry url.
+    #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub registry_url: String,
     /// A list of semantic convention groups.
@@ -30,6 +31,9 @@ pub struct UnresolvedRegistry {
     pub registry: Registry,
     /// List of unresolved groups that belong to the registry.
     pub groups: Vec<UnresolvedGroup>,
+    /// True if the attributes without `stability` inherit the stability of
+    /// their group.
+    pub stability_inheritance: bool,
 }
 
 /// Group specification.
@@ -40,6 +44,7 @@ pub struct Group {
     /// The type of the group including the specific fields for each type.
     pub typed_group: TypedGroup,
     /// A brief description of the semantic convention.
+    #[serde(default)]
     #[serde(skip_serializing_if = "String::is_empty")]
     pub brief: String,
     /// A more elaborate description of the semantic convention.
@@ -64,11 +69,11 @@ pub struct Group {
     /// error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stability: Option<Stability>,
-    /// Specifies if the semantic convention is deprecated. The string
-    /// provided as <description> MUST specify why it's deprecated and/or what
-    /// to use instead. See also stability.
+    /// Specifies if the semantic convention is deprecated, either with a note or with a
+    /// structured deprecation (reason, `renamed_to`, `removed_in`). See also
+    /// stability.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub deprecated: Option<String>,
+    pub deprecated: Option<Deprecated>,
     /// Additional constraints.
     /// Allow to define additional requirements on the semantic convention.
     /// It defaults to an empty list.
@@ -112,12 +117,22 @@ pub enum TypedGroup {
         /// Note: only valid if type is span (the default)
         #[serde(default)]
         events: Vec<String>,
+        /// Guidance on the name of the spans (recommended format and
+        /// examples).
+        /// Note: only valid if type is span (the default)
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        span_name: Option<SpanName>,
     },
     /// A semantic convention group representing an event.
     Event {
         /// The name of the event. If not specified, the prefix is used.
         /// If prefix is empty (or unspecified), name is required.
         name: Option<String>,
+        /// The fields of the body of the event (structured body).
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        body: Vec<BodyField>,
     },
     /// A semantic convention group representing a metric.
     Metric {
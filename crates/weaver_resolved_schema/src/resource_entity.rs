@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Catalog of the resource entities of a resolved telemetry schema, for the
+//! telemetry backends modeling the entities producing the telemetry (hosts,
+//! services, k8s pods, ...). Each resource group of the registries is an
+//! entity type: its required attributes identify an entity and its other
+//! attributes describe it.
+
+use serde::Serialize;
+
+use crate::attribute::{Attribute, AttributeRef, RequirementLevel};
+use crate::catalog::{Catalog, Deprecated, Stability};
+use crate::registry::{Group, TypedGroup};
+use crate::ResolvedTelemetrySchema;
+
+/// The resource entities of a resolved telemetry schema.
+#[derive(Serialize, Debug)]
+pub struct ResourceEntityCatalog<'a> {
+    /// Schema URL of the resolved telemetry schema.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub schema_url: &'a str,
+    /// The entity types defined by the resource groups of the registries.
+    pub entities: Vec<ResourceEntity<'a>>,
+    /// The attribute set of the resource (only for application).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<ResourceAttributeSet<'a>>,
+}
+
+/// An entity type defined by a resource group.
+#[derive(Serialize, Debug)]
+pub struct ResourceEntity<'a> {
+    /// The type of the entity, i.e. the prefix of the group (or its id if
+    /// the group has no prefix).
+    pub r#type: &'a str,
+    /// The id of the resource group.
+    pub group_id: &'a str,
+    /// A brief description of the entity.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub brief: &'a str,
+    /// The stability of the entity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<&'a Stability>,
+    /// The deprecation of the entity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<&'a Deprecated>,
+    /// The attributes of the entity.
+    #[serde(flatten)]
+    pub attributes: ResourceAttributeSet<'a>,
+}
+
+/// The attributes of a resource, split between the attributes identifying
+/// the entity and the attributes describing it.
+#[derive(Serialize, Debug)]
+pub struct ResourceAttributeSet<'a> {
+    /// The required attributes, identifying the entity.
+    pub identifying_attributes: Vec<&'a Attribute>,
+    /// The other attributes, describing the entity.
+    pub descriptive_attributes: Vec<&'a Attribute>,
+}
+
+impl ResolvedTelemetrySchema {
+    /// Returns the catalog of the resource entities, i.e. the resource groups
+    /// of the registries (in the order of the registries) and the resource of
+    /// the schema. The references missing from the catalog (only in an
+    /// invalid schema) are ignored.
+    pub fn resource_entities(&self) -> ResourceEntityCatalog<'_> {
+        ResourceEntityCatalog {
+            schema_url: &self.schema_url,
+            entities: self
+                .registries
+                .iter()
+                .flat_map(|registry| registry.groups.iter())
+                .filter(|group| matches!(group.typed_group, TypedGroup::Resource {}))
+                .map(|group| resource_entity(&self.catalog, group))
+                .collect(),
+            resource: self
+                .resource
+                .as_ref()
+                .map(|resource| attribute_set(&self.catalog, &resource.attributes)),
+        }
+    }
+}
+
+/// Returns the entity type defined by a resource group.
+fn resource_entity<'a>(catalog: &'a Catalog, group: &'a Group) -> ResourceEntity<'a> {
+    ResourceEntity {
+        r#type: if group.prefix.is_empty() {
+            &group.id
+        } else {
+            &group.prefix
+        },
+        group_id: &group.id,
+        brief: &group.brief,
+        stability: group.stability.as_ref(),
+        deprecated: group.deprecated.as_ref(),
+        attributes: attribute_set(catalog, &group.attributes),
+    }
+}
+
+/// Returns the attributes of the catalog with the given references, split
+/// between the identifying and the descriptive attributes.
+fn attribute_set<'a>(catalog: &'a Catalog, attr_refs: &[AttributeRef]) -> ResourceAttributeSet<'a> {
+    let (identifying_attributes, descriptive_attributes) = attr_refs
+        .iter()
+        .filter_map(|attr_ref| catalog.attributes.get(attr_ref.0 as usize))
+        .partition(|attribute| attribute.requirement_level == RequirementLevel::Required);
+    ResourceAttributeSet {
+        identifying_attributes,
+        descriptive_attributes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{Attribute, AttributeRef, AttributeType, RequirementLevel};
+    use crate::registry::TypedGroup;
+    use crate::resource::Resource;
+    use crate::test_utils::{attribute, group, schema};
+    use crate::ResolvedTelemetrySchema;
+
+    fn string_attribute(name: &str, requirement_level: RequirementLevel) -> Attribute {
+        Attribute {
+            requirement_level,
+            ..attribute(name, AttributeType::String)
+        }
+    }
+
+    #[test]
+    fn test_resource_entities() {
+        let schema = ResolvedTelemetrySchema {
+            schema_url: "https://opentelemetry.io/schemas/1.21.0".to_owned(),
+            resource: Some(Resource {
+                attributes: vec![AttributeRef(1)],
+            }),
+            ..schema(
+                vec![
+                    group(
+                        "host",
+                        TypedGroup::Resource {},
+                        vec![AttributeRef(0), AttributeRef(1)],
+                    ),
+                    group(
+                        "server",
+                        TypedGroup::AttributeGroup {},
+                        vec![AttributeRef(2)],
+                    ),
+                ],
+                vec![
+                    string_attribute("host.id", RequirementLevel::Required),
+                    string_attribute("host.name", RequirementLevel::Recommended { text: None }),
+                    string_attribute("server.address", RequirementLevel::Required),
+                ],
+            )
+        };
+
+        let catalog = serde_json::to_value(schema.resource_entities()).unwrap();
+        let entities = catalog["entities"].as_array().unwrap();
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0]["type"], "host");
+        assert_eq!(entities[0]["group_id"], "host");
+        assert_eq!(entities[0]["identifying_attributes"][0]["name"], "host.id");
+        assert_eq!(
+            entities[0]["descriptive_attributes"][0]["name"],
+            "host.name"
+        );
+        assert_eq!(
+            catalog["resource"]["identifying_attributes"]
+                .as_array()
+                .unwrap()
+                .len(),
+            0
+        );
+        assert_eq!(
+            catalog["resource"]["descriptive_attributes"][0]["name"],
+            "host.name"
+        );
+    }
+}
@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::attribute::AttributeRef;
+use crate::attribute::{AttributeRef, Example, RequirementLevel};
 use crate::metric::MetricRef;
 use crate::tags::Tags;
 
@@ -107,6 +107,71 @@ pub enum SpanKind {
     Consumer,
 }
 
+/// Guidance on the name of the spans of a span group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpanName {
+    /// The recommended format of the span name, the attributes being referred
+    /// to by their id between braces.
+    pub format: String,
+    /// Examples of span names following the format.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+}
+
+/// A field of the body of an event group.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct BodyField {
+    /// The id of the field.
+    pub id: String,
+    /// The type of the field.
+    pub r#type: BodyFieldType,
+    /// A brief description of the field.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub brief: String,
+    /// A more elaborate description of the field.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// Specifies if the field is mandatory.
+    pub requirement_level: RequirementLevel,
+    /// Sequence of example values for the field or single example value.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Example>,
+    /// The nested fields of a `map` field.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<BodyField>,
+}
+
+/// The types of the body fields.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyFieldType {
+    /// A boolean field.
+    Boolean,
+    /// A integer field (signed 64 bit integer).
+    Int,
+    /// A double field (double precision floating point (IEEE 754-1985)).
+    Double,
+    /// A string field.
+    String,
+    /// An array of strings field.
+    Strings,
+    /// An array of integer field.
+    Ints,
+    /// An array of double field.
+    Doubles,
+    /// An array of boolean field.
+    Booleans,
+    /// A map of nested fields.
+    Map,
+}
+
 /// A span event specification.
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
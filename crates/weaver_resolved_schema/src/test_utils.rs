@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Factories of the items of a resolved telemetry schema shared by the unit
+//! tests. The optional fields are left empty, a test sets the fields it checks
+//! with the struct update syntax.
+
+use crate::attribute::{Attribute, AttributeRef, AttributeType, RequirementLevel};
+use crate::catalog::Catalog;
+use crate::metric::{Instrument, Metric};
+use crate::registry::{Group, Registry, TypedGroup};
+use crate::ResolvedTelemetrySchema;
+
+/// Returns a recommended attribute of the given type.
+pub(crate) fn attribute(name: &str, r#type: AttributeType) -> Attribute {
+    Attribute {
+        name: name.to_owned(),
+        r#type,
+        brief: String::new(),
+        examples: None,
+        tag: None,
+        requirement_level: RequirementLevel::Recommended { text: None },
+        sampling_relevant: None,
+        note: String::new(),
+        stability: None,
+        deprecated: None,
+        tags: None,
+        value: None,
+    }
+}
+
+/// Returns a histogram metric with the given unit.
+pub(crate) fn metric(name: &str, unit: &str) -> Metric {
+    Metric {
+        name: name.to_owned(),
+        brief: String::new(),
+        note: String::new(),
+        instrument: Instrument::Histogram,
+        unit: Some(unit.to_owned()),
+        tags: None,
+    }
+}
+
+/// Returns a group of the given type referencing the given attributes.
+pub(crate) fn group(id: &str, typed_group: TypedGroup, attributes: Vec<AttributeRef>) -> Group {
+    Group {
+        id: id.to_owned(),
+        typed_group,
+        brief: String::new(),
+        note: String::new(),
+        prefix: String::new(),
+        extends: None,
+        stability: None,
+        deprecated: None,
+        constraints: vec![],
+        attributes,
+        lineage: None,
+    }
+}
+
+/// Returns a schema made of a single registry with the given groups, and a
+/// catalog of the given attributes.
+pub(crate) fn schema(groups: Vec<Group>, attributes: Vec<Attribute>) -> ResolvedTelemetrySchema {
+    ResolvedTelemetrySchema {
+        file_format: "1.0.0".to_owned(),
+        schema_url: String::new(),
+        registries: vec![Registry {
+            registry_url: "https://semconv-registry.com".to_owned(),
+            groups,
+        }],
+        catalog: Catalog {
+            attributes,
+            metrics: vec![],
+        },
+        resource: None,
+        instrumentation_library: None,
+        dependencies: vec![],
+        versions: None,
+        registry_manifest: vec![],
+        resolution_environment: None,
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Benchmark of the resolution of a large semantic convention registry
+//! (`cargo bench -p weaver_resolver`).
+//!
+//! The registry is generated: `GROUPS` attribute groups of `ATTRIBUTES`
+//! attributes each, and as many span groups referencing them (half of the
+//! references overriding the brief of the referenced attribute). The
+//! benchmark reports the best and the mean duration of the resolution of the
+//! registry, loading excluded.
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use weaver_logger::NullLogger;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::{ResolverConfig, SemConvSpecs};
+
+const GROUPS: usize = 200;
+const ATTRIBUTES: usize = 50;
+const RUNS: usize = 10;
+
+/// Returns the semantic convention file of the generated registry.
+fn registry() -> String {
+    let mut yaml = "groups:\n".to_owned();
+    for group in 0..GROUPS {
+        _ = write!(
+            yaml,
+            "  - id: registry.ns{group}\n    type: attribute_group\n    brief: \"Namespace {group}.\"\n    prefix: ns{group}\n    attributes:\n"
+        );
+        for attr in 0..ATTRIBUTES {
+            _ = write!(
+                yaml,
+                "      - id: attr{attr}\n        type: string\n        brief: \"Attribute {attr}.\"\n        examples: [\"value\"]\n"
+            );
+        }
+    }
+    for group in 0..GROUPS {
+        _ = write!(
+            yaml,
+            "  - id: span.ns{group}\n    type: span\n    brief: \"Span {group}.\"\n    attributes:\n"
+        );
+        for attr in 0..ATTRIBUTES {
+            _ = writeln!(yaml, "      - ref: ns{group}.attr{attr}");
+            if attr % 2 == 0 {
+                _ = writeln!(yaml, "        brief: \"Overridden brief.\"");
+            }
+        }
+    }
+    yaml
+}
+
+fn main() {
+    let yaml = registry();
+    let mut durations = Vec::with_capacity(RUNS);
+    for _ in 0..RUNS {
+        let mut registry = SemConvSpecs::default();
+        registry
+            .load_from_str(&yaml, "registry.yaml")
+            .expect("Failed to load the registry");
+        let start = Instant::now();
+        _ = registry
+            .resolve(ResolverConfig::with_keep_specs())
+            .expect("Failed to resolve the semantic conventions");
+        _ = SchemaResolver::resolve_semantic_convention_registry(&mut registry, NullLogger::new())
+            .expect("Failed to resolve the registry");
+        durations.push(start.elapsed());
+    }
+
+    let best = durations.iter().min().copied().unwrap_or_default();
+    let mean = durations.iter().sum::<Duration>() / RUNS as u32;
+    println!(
+        "resolution of {} attributes and {} references: best {:.2}ms, mean {:.2}ms ({} runs)",
+        GROUPS * ATTRIBUTES,
+        GROUPS * ATTRIBUTES,
+        best.as_secs_f64() * 1000.0,
+        mean.as_secs_f64() * 1000.0,
+        RUNS
+    );
+}
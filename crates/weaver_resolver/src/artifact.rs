@@ -0,0 +1,612 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Loading of the parent schemas, which can be source telemetry schemas or
+//! already resolved schema artifacts (e.g. the output of `weaver resolve
+//! registry`, in JSON or YAML).
+//!
+//! A resolved artifact is detected by its `catalog` section. Its resolved
+//! registry is converted back to semantic convention specs (the attribute ids
+//! being fully qualified and the inherited attributes materialized), so a
+//! stable parent is not re-resolved from its sources on every build of its
+//! children. Its versions are merged like the versions of a source parent.
+//! Protobuf artifacts are not supported yet.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use regex::Regex;
+use url::Url;
+
+use weaver_resolved_schema::attribute::{Attribute, AttributeType, Example, RequirementLevel};
+use weaver_resolved_schema::catalog::Catalog;
+use weaver_resolved_schema::metric::Instrument;
+use weaver_resolved_schema::registry::{Group, TypedGroup};
+use weaver_resolved_schema::signal::{BodyField, BodyFieldType, SpanKind};
+use weaver_resolved_schema::value::Value;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_schema::TelemetrySchema;
+use weaver_semconv::attribute::{
+    AttributeSpec, AttributeTypeSpec, BasicRequirementLevelSpec, EnumEntriesSpec, ExamplesSpec,
+    PrimitiveOrArrayTypeSpec, RequirementLevelSpec, TemplateTypeSpec, ValueSpec,
+};
+use weaver_semconv::group::{
+    BodyFieldSpec, BodyFieldTypeSpec, ConstraintSpec, ConvTypeSpec, GroupSpec, InstrumentSpec,
+    SpanKindSpec, SpanNameSpec,
+};
+use weaver_semconv::{SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs};
+
+use crate::stability::{deprecated_spec, stability_spec};
+use crate::Error;
+
+/// Loads a parent schema from a URL or a path, either a source telemetry
+/// schema or a resolved schema artifact.
+pub(crate) fn load_parent_schema(parent_schema_url: &str) -> Result<TelemetrySchema, Error> {
+    if is_protobuf(parent_schema_url) {
+        return Err(Error::InvalidResolvedSchema {
+            path_or_url: parent_schema_url.to_owned(),
+            error: "protobuf resolved schemas are not supported yet (use JSON or YAML)".to_owned(),
+        });
+    }
+
+    let url_pattern =
+        Regex::new(r"^(https|http|file):.*").expect("invalid regex, please report this bug");
+    if !url_pattern.is_match(parent_schema_url) {
+        return load_from_file(parent_schema_url);
+    }
+    let url = Url::parse(parent_schema_url).map_err(|e| Error::InvalidUrl {
+        url: parent_schema_url.to_owned(),
+        error: e.to_string(),
+    })?;
+    match url.scheme() {
+        "http" | "https" => {
+            let content = weaver_net::client()
+                .and_then(|client| client.get(url.as_ref()))
+                .map_err(|e| e.to_string())
+                .and_then(|response| response.into_string().map_err(|e| e.to_string()))
+                .map_err(|error| {
+                    Error::ParentSchemaError(weaver_schema::Error::SchemaNotFound {
+                        path_or_url: parent_schema_url.to_owned(),
+                        error,
+                    })
+                })?;
+            match parse_resolved_schema(&content, parent_schema_url)? {
+                Some(resolved) => schema_from_resolved(resolved, parent_schema_url),
+                None => serde_yaml::from_str(&content).map_err(|e| {
+                    Error::ParentSchemaError(weaver_schema::Error::InvalidSchema {
+                        path_or_url: parent_schema_url.to_owned(),
+                        line: e.location().map(|loc| loc.line()),
+                        column: e.location().map(|loc| loc.column()),
+                        error: e.to_string(),
+                    })
+                }),
+            }
+        }
+        "file" => load_from_file(url.path()),
+        _ => TelemetrySchema::load_from_url(&url).map_err(Error::ParentSchemaError),
+    }
+}
+
+/// Loads a parent schema from a local file, either a source telemetry schema
+/// or a resolved schema artifact.
+fn load_from_file(path: &str) -> Result<TelemetrySchema, Error> {
+    // The source schemas are loaded by `TelemetrySchema::load_from_file`,
+    // which reports the missing files and makes the local imports relative
+    // to the schema file.
+    if let Ok(content) = fs::read_to_string(path) {
+        if let Some(resolved) = parse_resolved_schema(&content, path)? {
+            return schema_from_resolved(resolved, path);
+        }
+    }
+    TelemetrySchema::load_from_file(path).map_err(Error::ParentSchemaError)
+}
+
+/// Returns true if the path or URL of a parent schema has a protobuf
+/// extension.
+fn is_protobuf(path_or_url: &str) -> bool {
+    path_or_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+        .ends_with(".pb")
+}
+
+/// Parses the content of a parent schema (JSON or YAML) as a resolved
+/// schema, or returns `None` if the content is not a resolved schema (i.e. it
+/// has no `catalog` section).
+///
+/// JSON content is parsed with the JSON parser, the numeric map keys of the
+/// lineages (attribute references) being strings in JSON.
+fn parse_resolved_schema(
+    content: &str,
+    path_or_url: &str,
+) -> Result<Option<ResolvedTelemetrySchema>, Error> {
+    let invalid_resolved_schema = |error: String| Error::InvalidResolvedSchema {
+        path_or_url: path_or_url.to_owned(),
+        error,
+    };
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(content) {
+        if value.get("catalog").is_none() {
+            return Ok(None);
+        }
+        return serde_json::from_value(value)
+            .map(Some)
+            .map_err(|e| invalid_resolved_schema(e.to_string()));
+    }
+    let Ok(value) = serde_yaml::from_str::<serde_yaml::Value>(content) else {
+        // Reported by the loader of the source schemas.
+        return Ok(None);
+    };
+    if value.get("catalog").is_none() {
+        return Ok(None);
+    }
+    serde_yaml::from_value(value)
+        .map(Some)
+        .map_err(|e| invalid_resolved_schema(e.to_string()))
+}
+
+/// Converts a resolved schema into a telemetry schema carrying its versions
+/// and its resolved registry converted back to semantic convention specs.
+fn schema_from_resolved(
+    resolved: ResolvedTelemetrySchema,
+    path_or_url: &str,
+) -> Result<TelemetrySchema, Error> {
+    let spec = spec_from_resolved(&resolved, path_or_url)?;
+    Ok(TelemetrySchema {
+        file_format: resolved.file_format,
+        parent_schema_url: None,
+        schema_url: resolved.schema_url,
+        semantic_conventions: vec![],
+        schema: None,
+        versions: resolved.versions,
+        parent_schema: None,
+        semantic_convention_registry: SemConvSpecs::default(),
+        resolved_semantic_conventions: vec![SemConvSpecWithProvenance {
+            spec,
+            provenance: path_or_url.to_owned(),
+        }],
+    })
+}
+
+/// Converts the resolved registries of a resolved schema back to a semantic
+/// convention spec. The first group referencing an attribute defines it (with
+/// its fully qualified id), the other groups refer to it and override the
+/// fields resolved with a different value.
+fn spec_from_resolved(
+    resolved: &ResolvedTelemetrySchema,
+    path_or_url: &str,
+) -> Result<SemConvSpec, Error> {
+    let mut definitions: HashMap<&str, &Attribute> = HashMap::new();
+    let groups = resolved
+        .registries
+        .iter()
+        .flat_map(|registry| registry.groups.iter())
+        .map(|group| group_spec(group, &resolved.catalog, &mut definitions, path_or_url))
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(SemConvSpec { groups })
+}
+
+/// Converts a resolved group back to a group specification.
+fn group_spec<'a>(
+    group: &Group,
+    catalog: &'a Catalog,
+    definitions: &mut HashMap<&'a str, &'a Attribute>,
+    path_or_url: &str,
+) -> Result<GroupSpec, Error> {
+    let attributes = group
+        .attributes
+        .iter()
+        .map(|attr_ref| {
+            let attr = catalog.attributes.get(attr_ref.0 as usize).ok_or_else(|| {
+                Error::InvalidResolvedSchema {
+                    path_or_url: path_or_url.to_owned(),
+                    error: format!(
+                        "the attribute {} of the group `{}` is not in the catalog",
+                        attr_ref.0, group.id
+                    ),
+                }
+            })?;
+            Ok(match definitions.get(attr.name.as_str()) {
+                Some(definition) => attribute_ref_spec(attr, definition),
+                None => {
+                    _ = definitions.insert(attr.name.as_str(), attr);
+                    attribute_spec(attr)
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut spec = GroupSpec {
+        id: group.id.clone(),
+        r#type: ConvTypeSpec::AttributeGroup,
+        brief: group.brief.clone(),
+        note: group.note.clone(),
+        // The attribute ids are fully qualified.
+        prefix: String::new(),
+        // The inherited attributes are materialized.
+        extends: None,
+        stability: group.stability.as_ref().map(stability_spec),
+        deprecated: group.deprecated.as_ref().map(deprecated_spec),
+        attributes,
+        // The included constraints are materialized.
+        constraints: group
+            .constraints
+            .iter()
+            .filter(|constraint| !constraint.any_of.is_empty())
+            .map(|constraint| ConstraintSpec {
+                any_of: constraint.any_of.clone(),
+                include: None,
+            })
+            .collect(),
+        span_kind: None,
+        events: vec![],
+        span_name: None,
+        metric_name: None,
+        instrument: None,
+        unit: None,
+        monotonic: None,
+        advice: None,
+        name: None,
+        body: vec![],
+        annotations: BTreeMap::new(),
+        position: None,
+        attribute_positions: BTreeMap::new(),
+        pointer: None,
+    };
+    match &group.typed_group {
+        TypedGroup::AttributeGroup {} => {}
+        TypedGroup::Span {
+            span_kind,
+            events,
+            span_name,
+        } => {
+            spec.r#type = ConvTypeSpec::Span;
+            spec.span_kind = span_kind.as_ref().map(span_kind_spec);
+            spec.events = events.clone();
+            spec.span_name = span_name.as_ref().map(|span_name| SpanNameSpec {
+                format: span_name.format.clone(),
+                examples: span_name.examples.clone(),
+            });
+        }
+        TypedGroup::Event { name, body } => {
+            spec.r#type = ConvTypeSpec::Event;
+            // The name of an event defaults to its prefix.
+            spec.name = name
+                .clone()
+                .or_else(|| (!group.prefix.is_empty()).then(|| group.prefix.clone()));
+            spec.body = body.iter().map(body_field_spec).collect();
+        }
+        TypedGroup::Metric {
+            metric_name,
+            instrument,
+            unit,
+        } => {
+            spec.r#type = ConvTypeSpec::Metric;
+            spec.metric_name = metric_name.clone();
+            spec.instrument = instrument.as_ref().map(instrument_spec);
+            spec.unit = unit.clone();
+        }
+        TypedGroup::MetricGroup {} => spec.r#type = ConvTypeSpec::MetricGroup,
+        TypedGroup::Resource {} => spec.r#type = ConvTypeSpec::Resource,
+        TypedGroup::Scope {} => spec.r#type = ConvTypeSpec::Scope,
+    }
+    Ok(spec)
+}
+
+/// Converts a resolved attribute back to an attribute definition.
+fn attribute_spec(attr: &Attribute) -> AttributeSpec {
+    AttributeSpec::Id {
+        id: attr.name.clone(),
+        r#type: attr_type_spec(&attr.r#type),
+        brief: attr.brief.clone(),
+        examples: attr.examples.as_ref().map(examples_spec),
+        tag: attr.tag.clone(),
+        requirement_level: req_level_spec(&attr.requirement_level),
+        sampling_relevant: attr.sampling_relevant,
+        note: attr.note.clone(),
+        stability: attr.stability.as_ref().map(stability_spec),
+        deprecated: attr.deprecated.as_ref().map(deprecated_spec),
+        annotations: BTreeMap::new(),
+    }
+}
+
+/// Converts a resolved attribute back to a reference to its definition,
+/// overriding the fields resolved with a different value.
+fn attribute_ref_spec(attr: &Attribute, definition: &Attribute) -> AttributeSpec {
+    AttributeSpec::Ref {
+        r#ref: attr.name.clone(),
+        brief: (attr.brief != definition.brief).then(|| attr.brief.clone()),
+        examples: attr
+            .examples
+            .as_ref()
+            .filter(|_| attr.examples != definition.examples)
+            .map(examples_spec),
+        tag: attr.tag.clone().filter(|_| attr.tag != definition.tag),
+        requirement_level: (attr.requirement_level != definition.requirement_level)
+            .then(|| req_level_spec(&attr.requirement_level)),
+        sampling_relevant: attr
+            .sampling_relevant
+            .filter(|_| attr.sampling_relevant != definition.sampling_relevant),
+        note: (attr.note != definition.note).then(|| attr.note.clone()),
+        stability: attr
+            .stability
+            .as_ref()
+            .filter(|_| attr.stability != definition.stability)
+            .map(stability_spec),
+        deprecated: attr
+            .deprecated
+            .as_ref()
+            .filter(|_| attr.deprecated != definition.deprecated)
+            .map(deprecated_spec),
+        annotations: BTreeMap::new(),
+    }
+}
+
+fn attr_type_spec(attr_type: &AttributeType) -> AttributeTypeSpec {
+    match attr_type {
+        AttributeType::Boolean => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Boolean)
+        }
+        AttributeType::Int => AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Int),
+        AttributeType::Double => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Double)
+        }
+        AttributeType::String => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::String)
+        }
+        AttributeType::Strings => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Strings)
+        }
+        AttributeType::Ints => AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Ints),
+        AttributeType::Doubles => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Doubles)
+        }
+        AttributeType::Booleans => {
+            AttributeTypeSpec::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Booleans)
+        }
+        AttributeType::TemplateBoolean => AttributeTypeSpec::Template(TemplateTypeSpec::Boolean),
+        AttributeType::TemplateInt => AttributeTypeSpec::Template(TemplateTypeSpec::Int),
+        AttributeType::TemplateDouble => AttributeTypeSpec::Template(TemplateTypeSpec::Double),
+        AttributeType::TemplateString => AttributeTypeSpec::Template(TemplateTypeSpec::String),
+        AttributeType::TemplateStrings => AttributeTypeSpec::Template(TemplateTypeSpec::Strings),
+        AttributeType::TemplateInts => AttributeTypeSpec::Template(TemplateTypeSpec::Ints),
+        AttributeType::TemplateDoubles => AttributeTypeSpec::Template(TemplateTypeSpec::Doubles),
+        AttributeType::TemplateBooleans => AttributeTypeSpec::Template(TemplateTypeSpec::Booleans),
+        AttributeType::Enum {
+            allow_custom_values,
+            members,
+        } => AttributeTypeSpec::Enum {
+            allow_custom_values: *allow_custom_values,
+            members: members
+                .iter()
+                .map(|member| EnumEntriesSpec {
+                    id: member.id.clone(),
+                    value: match &member.value {
+                        Value::String { value } => ValueSpec::String(value.clone()),
+                        Value::Int { value } => ValueSpec::Int(*value),
+                        Value::Double { value } => ValueSpec::Double(*value),
+                    },
+                    brief: member.brief.clone(),
+                    note: member.note.clone(),
+                    stability: member.stability.as_ref().map(stability_spec),
+                    deprecated: member.deprecated.as_ref().map(deprecated_spec),
+                })
+                .collect(),
+        },
+    }
+}
+
+fn examples_spec(examples: &Example) -> ExamplesSpec {
+    match examples {
+        Example::Bool { value } => ExamplesSpec::Bool(*value),
+        Example::Int { value } => ExamplesSpec::Int(*value),
+        Example::Double { value } => ExamplesSpec::Double(*value),
+        Example::String { value } => ExamplesSpec::String(value.clone()),
+        Example::Ints { values } => ExamplesSpec::Ints(values.clone()),
+        Example::Doubles { values } => ExamplesSpec::Doubles(values.clone()),
+        Example::Bools { values } => ExamplesSpec::Bools(values.clone()),
+        Example::Strings { values } => ExamplesSpec::Strings(values.clone()),
+    }
+}
+
+fn req_level_spec(req_level: &RequirementLevel) -> RequirementLevelSpec {
+    match req_level {
+        RequirementLevel::Required => {
+            RequirementLevelSpec::Basic(BasicRequirementLevelSpec::Required)
+        }
+        RequirementLevel::Recommended { text: None } => {
+            RequirementLevelSpec::Basic(BasicRequirementLevelSpec::Recommended)
+        }
+        RequirementLevel::Recommended { text: Some(text) } => {
+            RequirementLevelSpec::Recommended { text: text.clone() }
+        }
+        RequirementLevel::OptIn => RequirementLevelSpec::Basic(BasicRequirementLevelSpec::OptIn),
+        RequirementLevel::ConditionallyRequired { text } => {
+            RequirementLevelSpec::ConditionallyRequired { text: text.clone() }
+        }
+    }
+}
+
+fn body_field_spec(field: &BodyField) -> BodyFieldSpec {
+    BodyFieldSpec {
+        id: field.id.clone(),
+        r#type: match field.r#type {
+            BodyFieldType::Boolean => BodyFieldTypeSpec::Boolean,
+            BodyFieldType::Int => BodyFieldTypeSpec::Int,
+            BodyFieldType::Double => BodyFieldTypeSpec::Double,
+            BodyFieldType::String => BodyFieldTypeSpec::String,
+            BodyFieldType::Strings => BodyFieldTypeSpec::Strings,
+            BodyFieldType::Ints => BodyFieldTypeSpec::Ints,
+            BodyFieldType::Doubles => BodyFieldTypeSpec::Doubles,
+            BodyFieldType::Booleans => BodyFieldTypeSpec::Booleans,
+            BodyFieldType::Map => BodyFieldTypeSpec::Map,
+        },
+        brief: field.brief.clone(),
+        note: field.note.clone(),
+        requirement_level: req_level_spec(&field.requirement_level),
+        examples: field.examples.as_ref().map(examples_spec),
+        fields: field.fields.iter().map(body_field_spec).collect(),
+    }
+}
+
+fn instrument_spec(instrument: &Instrument) -> InstrumentSpec {
+    match instrument {
+        Instrument::UpDownCounter => InstrumentSpec::UpDownCounter,
+        Instrument::Counter => InstrumentSpec::Counter,
+        Instrument::Gauge => InstrumentSpec::Gauge,
+        Instrument::Histogram => InstrumentSpec::Histogram,
+    }
+}
+
+fn span_kind_spec(span_kind: &SpanKind) -> SpanKindSpec {
+    match span_kind {
+        SpanKind::Internal => SpanKindSpec::Internal,
+        SpanKind::Client => SpanKindSpec::Client,
+        SpanKind::Server => SpanKindSpec::Server,
+        SpanKind::Producer => SpanKindSpec::Producer,
+        SpanKind::Consumer => SpanKindSpec::Consumer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_logger::ConsoleLogger;
+    use weaver_semconv::attribute::{
+        AttributeSpec, BasicRequirementLevelSpec, RequirementLevelSpec,
+    };
+    use weaver_semconv::group::InstrumentSpec;
+    use weaver_semconv::{ResolverConfig, SemConvSpecs};
+
+    use crate::artifact::{load_parent_schema, parse_resolved_schema, schema_from_resolved};
+    use crate::attribute::AttributeCatalog;
+    use crate::registry::{resolve_registry, unresolved_registry_from_specs};
+    use crate::{Error, SchemaResolver};
+
+    const PARENT: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: "HTTP request method."
+        examples: ["GET"]
+        stability: stable
+  - id: metric.http.client.request.duration
+    type: metric
+    metric_name: http.client.request.duration
+    brief: "Duration of HTTP client requests."
+    instrument: histogram
+    unit: "s"
+    attributes:
+      - ref: http.request.method
+        requirement_level: required
+  - id: event.feature_flag
+    type: event
+    name: feature_flag.evaluation
+    brief: "A feature flag evaluation."
+    body:
+      - id: result
+        type: map
+        fields:
+          - id: variant
+            type: string
+            requirement_level: required
+            examples: ["on"]
+"#;
+
+    const CHILD: &str = r#"groups:
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: "HTTP client span."
+    attributes:
+      - ref: http.request.method
+"#;
+
+    #[test]
+    fn test_resolved_parent_schema() {
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(PARENT, "parent.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::with_keep_specs()).unwrap();
+        let mut resolved = SchemaResolver::resolve_semantic_convention_registry(
+            &mut registry,
+            ConsoleLogger::new(0),
+        )
+        .unwrap();
+        // The attribute references of the artifact keep their overrides.
+        let mut attr_catalog = AttributeCatalog::default();
+        resolved.registries = vec![resolve_registry(
+            unresolved_registry_from_specs("", &registry),
+            &mut attr_catalog,
+        )
+        .unwrap()];
+        resolved.catalog.attributes = attr_catalog.drain_attributes();
+        resolved.schema_url = "https://example.com/schemas/1.2.0".to_owned();
+        let content = serde_json::to_string(&resolved).unwrap();
+
+        // A source schema is not a resolved schema.
+        assert!(parse_resolved_schema(
+            "file_format: 1.2.0\nschema_url: https://example.com",
+            "s.yaml"
+        )
+        .unwrap()
+        .is_none());
+
+        let resolved = parse_resolved_schema(&content, "parent.json")
+            .unwrap()
+            .expect("a resolved schema");
+        let parent = schema_from_resolved(resolved, "parent.json").unwrap();
+        assert_eq!(parent.schema_url, "https://example.com/schemas/1.2.0");
+        let specs = parent.merged_resolved_semantic_conventions();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].provenance, "parent.json");
+
+        // The attribute is defined by the first group with its fully
+        // qualified id, and the metric only overrides its requirement level.
+        let groups = &specs[0].spec.groups;
+        assert!(groups[0].prefix.is_empty());
+        match &groups[0].attributes[0] {
+            AttributeSpec::Id { id, .. } => assert_eq!(id, "http.request.method"),
+            attr => panic!("Expected an attribute definition, found {:?}", attr),
+        }
+        match &groups[1].attributes[0] {
+            AttributeSpec::Ref {
+                r#ref,
+                brief,
+                requirement_level,
+                ..
+            } => {
+                assert_eq!(r#ref, "http.request.method");
+                assert!(brief.is_none());
+                assert!(matches!(
+                    requirement_level,
+                    Some(RequirementLevelSpec::Basic(
+                        BasicRequirementLevelSpec::Required
+                    ))
+                ));
+            }
+            attr => panic!("Expected an attribute reference, found {:?}", attr),
+        }
+        // The body of the event is kept.
+        assert_eq!(groups[2].body[0].fields[0].id, "variant");
+
+        // The signals of the parent are available to the child.
+        let mut registry = SemConvSpecs::default();
+        registry.append_sem_conv_specs(specs);
+        registry.load_from_str(CHILD, "child.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::with_keep_specs()).unwrap();
+        assert!(registry.attribute("http.request.method").is_some());
+        let metric = registry.metric("http.client.request.duration").unwrap();
+        assert!(matches!(metric.instrument, InstrumentSpec::Histogram));
+        assert!(registry.event_body("feature_flag.evaluation").is_some());
+
+        match load_parent_schema("parent.pb") {
+            Err(Error::InvalidResolvedSchema { path_or_url, .. }) => {
+                assert_eq!(path_or_url, "parent.pb")
+            }
+            result => panic!("Expected an unsupported format error, found {:?}", result),
+        }
+    }
+}
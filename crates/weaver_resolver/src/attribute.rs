@@ -3,11 +3,17 @@
 //! Attribute resolution.
 
 use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 use weaver_resolved_schema::attribute;
 use weaver_resolved_schema::attribute::AttributeRef;
-use weaver_resolved_schema::lineage::{FieldId, FieldLineage, GroupLineage, ResolutionMode};
+use weaver_resolved_schema::catalog::Stability;
+use weaver_resolved_schema::lineage::{
+    FieldId, FieldLineage, FieldOverride, GroupLineage, ResolutionMode,
+};
 use weaver_schema::attribute::Attribute;
 use weaver_schema::tags::Tags;
 use weaver_semconv::attribute::{
@@ -21,50 +27,86 @@ use weaver_version::VersionAttributeChanges;
 use crate::{stability, Error};
 
 /// A catalog of deduplicated resolved attributes with their corresponding reference.
+///
+/// Each attribute is stored once: the deduplication index only holds the
+/// references of the attributes, and the root attributes are referenced by
+/// their reference.
 #[derive(Deserialize, Debug, Default, PartialEq)]
 pub struct AttributeCatalog {
-    /// A map of deduplicated resolved attributes with their corresponding reference.
-    attribute_refs: HashMap<attribute::Attribute, AttributeRef>,
+    /// The deduplicated resolved attributes, indexed by their reference.
+    attributes: Vec<attribute::Attribute>,
+    #[serde(skip)]
+    /// The references of the deduplicated attributes indexed by the hash of
+    /// their content (built lazily).
+    attribute_refs: HashMap<u64, Vec<AttributeRef>>,
     #[serde(skip)]
     /// A map of root attributes indexed by their name.
     /// Root attributes are attributes that doesn't inherit from another attribute.
-    root_attributes: HashMap<String, AttributeWithGroupId>,
+    root_attributes: HashMap<String, RootAttribute>,
 }
 
+/// A root attribute of the catalog and the group defining it.
 #[derive(Debug, PartialEq)]
-struct AttributeWithGroupId {
-    pub attribute: attribute::Attribute,
+struct RootAttribute {
+    pub attr_ref: AttributeRef,
     pub group_id: String,
 }
 
+/// Returns the hash of the content of an attribute.
+fn attribute_hash(attr: &attribute::Attribute) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    attr.hash(&mut hasher);
+    hasher.finish()
+}
+
 impl AttributeCatalog {
     /// Returns the reference of the given attribute or creates a new reference if the attribute
     /// does not exist in the catalog.
     pub fn attribute_ref(&mut self, attr: attribute::Attribute) -> AttributeRef {
-        let next_id = self.attribute_refs.len() as u32;
-        *self
+        // The index of a deserialized catalog is rebuilt on first use.
+        if self.attribute_refs.is_empty() {
+            for (index, attr) in self.attributes.iter().enumerate() {
+                self.attribute_refs
+                    .entry(attribute_hash(attr))
+                    .or_default()
+                    .push(AttributeRef(index as u32));
+            }
+        }
+
+        let attr_refs = self
             .attribute_refs
-            .entry(attr)
-            .or_insert_with(|| AttributeRef(next_id))
+            .entry(attribute_hash(&attr))
+            .or_default();
+        if let Some(attr_ref) = attr_refs
+            .iter()
+            .find(|attr_ref| self.attributes[attr_ref.0 as usize] == attr)
+        {
+            return *attr_ref;
+        }
+        let attr_ref = AttributeRef(self.attributes.len() as u32);
+        attr_refs.push(attr_ref);
+        self.attributes.push(attr);
+        attr_ref
     }
 
     /// Returns a list of deduplicated attributes ordered by their references.
     pub fn drain_attributes(self) -> Vec<attribute::Attribute> {
-        let mut attributes: Vec<(attribute::Attribute, AttributeRef)> =
-            self.attribute_refs.into_iter().collect();
-        attributes.sort_by_key(|(_, attr_ref)| attr_ref.0);
-        attributes.into_iter().map(|(attr, _)| attr).collect()
+        self.attributes
     }
 
     /// Tries to resolve the given attribute spec (ref or id) from the catalog.
     /// Returns `None` if the attribute spec is a ref and it does not exist yet
     /// in the catalog.
+    ///
+    /// An attribute without stability (neither defined by the spec nor by the
+    /// referenced attribute) inherits the given group stability (if any).
     pub fn resolve(
         &mut self,
         group_id: &str,
         prefix: &str,
         attr: &AttributeSpec,
-        lineage: Option<&mut GroupLineage>,
+        group_stability: Option<&Stability>,
+        mut lineage: Option<&mut GroupLineage>,
     ) -> Option<AttributeRef> {
         match attr {
             AttributeSpec::Ref {
@@ -77,81 +119,142 @@ impl AttributeCatalog {
                 note,
                 stability,
                 deprecated,
+                ..
             } => {
-                let root_attr = self.root_attributes.get(r#ref);
-                if let Some(root_attr) = root_attr {
+                let root = self.root_attributes.get(r#ref);
+                if let Some(root) = root {
+                    let root_attr = &self.attributes[root.attr_ref.0 as usize];
+                    let root_group_id = root.group_id.clone();
                     let mut inherited_fields = vec![];
+                    let mut group_default_fields = vec![];
+                    let mut overridden_fields = vec![];
 
-                    // Create a fully resolved attribute from an attribute spec
-                    // (ref) and override the root attribute with the new
-                    // values if they are present.
-                    let resolved_attr = attribute::Attribute {
-                        name: r#ref.clone(),
-                        r#type: root_attr.attribute.r#type.clone(),
-                        brief: match brief {
-                            Some(brief) => brief.clone(),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeBrief);
-                                root_attr.attribute.brief.clone()
-                            }
-                        },
-                        examples: match examples {
-                            Some(_) => semconv_to_resolved_examples(examples),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeExamples);
-                                root_attr.attribute.examples.clone()
-                            }
-                        },
-                        tag: match tag {
-                            Some(_) => tag.clone(),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeTag);
-                                root_attr.attribute.tag.clone()
-                            }
-                        },
-                        requirement_level: match requirement_level {
-                            Some(requirement_level) => {
-                                semconv_to_resolved_req_level(requirement_level)
-                            }
-                            None => {
-                                inherited_fields.push(FieldId::AttributeRequirementLevel);
-                                root_attr.attribute.requirement_level.clone()
-                            }
-                        },
-                        sampling_relevant: match sampling_relevant {
-                            Some(_) => *sampling_relevant,
-                            None => {
-                                inherited_fields.push(FieldId::AttributeSamplingRelevant);
-                                root_attr.attribute.sampling_relevant
-                            }
-                        },
-                        note: match note {
-                            Some(note) => note.clone(),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeNote);
-                                root_attr.attribute.note.clone()
-                            }
-                        },
-                        stability: match stability {
-                            Some(_) => stability::resolve_stability(stability),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeStability);
-                                root_attr.attribute.stability.clone()
-                            }
-                        },
-                        deprecated: match deprecated {
-                            Some(_) => deprecated.clone(),
-                            None => {
-                                inherited_fields.push(FieldId::AttributeDeprecated);
-                                root_attr.attribute.deprecated.clone()
-                            }
-                        },
-                        tags: root_attr.attribute.tags.clone(),
-                        value: root_attr.attribute.value.clone(),
-                    };
+                    // A reference without any override resolves to its root
+                    // attribute, which is reused as is.
+                    let inherits_all = brief.is_none()
+                        && examples.is_none()
+                        && tag.is_none()
+                        && requirement_level.is_none()
+                        && sampling_relevant.is_none()
+                        && note.is_none()
+                        && stability.is_none()
+                        && deprecated.is_none()
+                        && (root_attr.stability.is_some() || group_stability.is_none());
 
-                    let group_id = root_attr.group_id.clone();
-                    let attr_ref = self.attribute_ref(resolved_attr);
+                    let attr_ref = if inherits_all {
+                        inherited_fields = vec![
+                            FieldId::AttributeBrief,
+                            FieldId::AttributeExamples,
+                            FieldId::AttributeTag,
+                            FieldId::AttributeRequirementLevel,
+                            FieldId::AttributeSamplingRelevant,
+                            FieldId::AttributeNote,
+                            FieldId::AttributeStability,
+                            FieldId::AttributeDeprecated,
+                        ];
+                        root.attr_ref
+                    } else {
+                        // Create a fully resolved attribute from an attribute spec
+                        // (ref) and override the root attribute with the new
+                        // values if they are present.
+                        let resolved_attr = attribute::Attribute {
+                            name: r#ref.clone(),
+                            r#type: root_attr.r#type.clone(),
+                            brief: match brief {
+                                Some(brief) => {
+                                    if *brief != root_attr.brief {
+                                        overridden_fields.push((
+                                            FieldId::AttributeBrief,
+                                            json_value(&root_attr.brief),
+                                            json_value(brief),
+                                        ));
+                                    }
+                                    brief.clone()
+                                }
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeBrief);
+                                    root_attr.brief.clone()
+                                }
+                            },
+                            examples: match examples {
+                                Some(_) => {
+                                    let examples = semconv_to_resolved_examples(examples);
+                                    if examples != root_attr.examples {
+                                        overridden_fields.push((
+                                            FieldId::AttributeExamples,
+                                            json_value(&root_attr.examples),
+                                            json_value(&examples),
+                                        ));
+                                    }
+                                    examples
+                                }
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeExamples);
+                                    root_attr.examples.clone()
+                                }
+                            },
+                            tag: match tag {
+                                Some(_) => tag.clone(),
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeTag);
+                                    root_attr.tag.clone()
+                                }
+                            },
+                            requirement_level: match requirement_level {
+                                Some(requirement_level) => {
+                                    semconv_to_resolved_req_level(requirement_level)
+                                }
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeRequirementLevel);
+                                    root_attr.requirement_level.clone()
+                                }
+                            },
+                            sampling_relevant: match sampling_relevant {
+                                Some(_) => *sampling_relevant,
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeSamplingRelevant);
+                                    root_attr.sampling_relevant
+                                }
+                            },
+                            note: match note {
+                                Some(note) => {
+                                    if *note != root_attr.note {
+                                        overridden_fields.push((
+                                            FieldId::AttributeNote,
+                                            json_value(&root_attr.note),
+                                            json_value(note),
+                                        ));
+                                    }
+                                    note.clone()
+                                }
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeNote);
+                                    root_attr.note.clone()
+                                }
+                            },
+                            stability: match (stability, &root_attr.stability) {
+                                (Some(_), _) => stability::resolve_stability(stability),
+                                (None, None) if group_stability.is_some() => {
+                                    group_default_fields.push(FieldId::AttributeStability);
+                                    group_stability.cloned()
+                                }
+                                (None, root_stability) => {
+                                    inherited_fields.push(FieldId::AttributeStability);
+                                    root_stability.clone()
+                                }
+                            },
+                            deprecated: match deprecated {
+                                Some(_) => stability::resolve_deprecated(deprecated),
+                                None => {
+                                    inherited_fields.push(FieldId::AttributeDeprecated);
+                                    root_attr.deprecated.clone()
+                                }
+                            },
+                            tags: root_attr.tags.clone(),
+                            value: root_attr.value.clone(),
+                        };
+                        self.attribute_ref(resolved_attr)
+                    };
 
                     // Update the lineage based on the inherited fields.
                     // Note: the lineage is only updated if a group lineage is provided.
@@ -162,7 +265,28 @@ impl AttributeCatalog {
                                 field_id,
                                 FieldLineage {
                                     resolution_mode: ResolutionMode::Reference,
-                                    group_id: group_id.clone(),
+                                    group_id: root_group_id.clone(),
+                                },
+                            );
+                        }
+                        for field_id in group_default_fields {
+                            lineage.add_attribute_field_lineage(
+                                attr_ref,
+                                field_id,
+                                FieldLineage {
+                                    resolution_mode: ResolutionMode::GroupDefault,
+                                    group_id: group_id.to_owned(),
+                                },
+                            );
+                        }
+                        for (field_id, before, after) in overridden_fields {
+                            lineage.add_attribute_field_override(
+                                attr_ref,
+                                field_id,
+                                FieldOverride {
+                                    group_id: root_group_id.clone(),
+                                    before,
+                                    after,
                                 },
                             );
                         }
@@ -184,6 +308,7 @@ impl AttributeCatalog {
                 note,
                 stability,
                 deprecated,
+                ..
             } => {
                 let root_attr_id = if prefix.is_empty() {
                     id.clone()
@@ -195,7 +320,7 @@ impl AttributeCatalog {
                 // and check if it already exists in the catalog.
                 // If it does, return the reference to the existing attribute.
                 // If it does not, add it to the catalog and return a new reference.
-                let attr = attribute::Attribute {
+                let mut attr = attribute::Attribute {
                     name: root_attr_id.clone(),
                     r#type: semconv_to_resolved_attr_type(r#type),
                     brief: brief.clone(),
@@ -205,19 +330,36 @@ impl AttributeCatalog {
                     sampling_relevant: *sampling_relevant,
                     note: note.clone(),
                     stability: stability::resolve_stability(stability),
-                    deprecated: deprecated.clone(),
+                    deprecated: stability::resolve_deprecated(deprecated),
                     tags: None,
                     value: None,
                 };
+                let inherits_stability = attr.stability.is_none() && group_stability.is_some();
+                if inherits_stability {
+                    attr.stability = group_stability.cloned();
+                }
 
+                let attr_ref = self.attribute_ref(attr);
                 self.root_attributes.insert(
                     root_attr_id,
-                    AttributeWithGroupId {
-                        attribute: attr.clone(),
+                    RootAttribute {
+                        attr_ref,
                         group_id: group_id.to_string(),
                     },
                 );
-                Some(self.attribute_ref(attr))
+
+                // Note: the lineage is only updated if a group lineage is provided.
+                if let Some(lineage) = lineage.as_mut().filter(|_| inherits_stability) {
+                    lineage.add_attribute_field_lineage(
+                        attr_ref,
+                        FieldId::AttributeStability,
+                        FieldLineage {
+                            resolution_mode: ResolutionMode::GroupDefault,
+                            group_id: group_id.to_owned(),
+                        },
+                    );
+                }
+                Some(attr_ref)
             }
         }
     }
@@ -235,19 +377,22 @@ impl AttributeCatalog {
 ///
 /// Note: Version changes are used during the resolution process to determine the names of the
 /// attributes.
-pub fn resolve_attributes(
-    attributes: &[Attribute],
-    sem_conv_catalog: &weaver_semconv::SemConvSpecs,
+///
+/// Note: the resolved attributes are indexed by ids borrowed from the catalog and from the given
+/// attributes (only the normalized names of the references are owned).
+pub fn resolve_attributes<'a>(
+    attributes: &'a [Attribute],
+    sem_conv_catalog: &'a weaver_semconv::SemConvSpecs,
     version_changes: impl VersionAttributeChanges,
 ) -> Result<Vec<Attribute>, Error> {
-    let mut resolved_attrs = BTreeMap::new();
+    let mut resolved_attrs: BTreeMap<Cow<'a, str>, Attribute> = BTreeMap::new();
     let mut copy_into_resolved_attrs =
-        |attrs: HashMap<&String, &weaver_semconv::attribute::AttributeSpec>,
+        |attrs: HashMap<&'a String, &'a weaver_semconv::attribute::AttributeSpec>,
          tags: &Option<Tags>| {
             for (attr_id, attr) in attrs {
                 let mut attr: Attribute = attr.into();
                 attr.set_tags(tags);
-                resolved_attrs.insert(attr_id.clone(), attr);
+                resolved_attrs.insert(Cow::Borrowed(attr_id.as_str()), attr);
             }
         };
 
@@ -318,7 +463,7 @@ pub fn resolve_attributes(
                     error: e.to_string(),
                 }
             })?;
-            resolved_attrs.insert(normalized_ref, resolved_attribute);
+            resolved_attrs.insert(Cow::Owned(normalized_ref), resolved_attribute);
         }
     }
 
@@ -326,7 +471,7 @@ pub fn resolve_attributes(
     // Note: any resolved attributes with the same id will be overridden.
     for attribute in attributes.iter() {
         if let Attribute::Id { id, .. } = attribute {
-            resolved_attrs.insert(id.clone(), attribute.clone());
+            resolved_attrs.insert(Cow::Borrowed(id.as_str()), attribute.clone());
         }
     }
 
@@ -414,6 +559,7 @@ pub fn resolve_attribute(
             note,
             stability,
             deprecated,
+            ..
         } => Ok(attribute::Attribute {
             name: id.clone(),
             r#type: semconv_to_resolved_attr_type(r#type),
@@ -424,7 +570,7 @@ pub fn resolve_attribute(
             sampling_relevant: *sampling_relevant,
             note: note.clone(),
             stability: stability::resolve_stability(stability),
-            deprecated: deprecated.clone(),
+            deprecated: stability::resolve_deprecated(deprecated),
             tags: None,
             value: None,
         }),
@@ -475,13 +621,17 @@ fn semconv_to_resolved_attr_type(attr_type: &AttributeTypeSpec) -> attribute::At
                     },
                     brief: member.brief.clone(),
                     note: member.note.clone(),
+                    stability: stability::resolve_stability(&member.stability),
+                    deprecated: stability::resolve_deprecated(&member.deprecated),
                 })
                 .collect(),
         },
     }
 }
 
-fn semconv_to_resolved_examples(examples: &Option<ExamplesSpec>) -> Option<attribute::Example> {
+pub(crate) fn semconv_to_resolved_examples(
+    examples: &Option<ExamplesSpec>,
+) -> Option<attribute::Example> {
     examples.as_ref().map(|examples| match examples {
         ExamplesSpec::Bool(v) => attribute::Example::Bool { value: *v },
         ExamplesSpec::Int(v) => attribute::Example::Int { value: *v },
@@ -494,7 +644,15 @@ fn semconv_to_resolved_examples(examples: &Option<ExamplesSpec>) -> Option<attri
     })
 }
 
-fn semconv_to_resolved_req_level(req_level: &RequirementLevelSpec) -> attribute::RequirementLevel {
+/// Returns the JSON representation of a field value (used to report the
+/// overridden fields).
+fn json_value<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_else(|e| e.to_string())
+}
+
+pub(crate) fn semconv_to_resolved_req_level(
+    req_level: &RequirementLevelSpec,
+) -> attribute::RequirementLevel {
     match req_level {
         RequirementLevelSpec::Basic(level) => match level {
             BasicRequirementLevelSpec::Required => attribute::RequirementLevel::Required,
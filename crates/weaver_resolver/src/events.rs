@@ -2,9 +2,13 @@
 
 //! Resolve events
 
-use crate::attribute::resolve_attributes;
+use crate::attribute::{
+    resolve_attributes, semconv_to_resolved_examples, semconv_to_resolved_req_level,
+};
 use crate::Error;
+use weaver_resolved_schema::signal::{BodyField, BodyFieldType};
 use weaver_schema::schema_spec::SchemaSpec;
+use weaver_semconv::group::{BodyFieldSpec, BodyFieldTypeSpec};
 use weaver_semconv::SemConvSpecs;
 use weaver_version::VersionChanges;
 
@@ -21,6 +25,13 @@ pub fn resolve_events(
             version_changes.log_attribute_changes(),
         )?;
         for event in events.events.iter_mut() {
+            // Without its own body, an event inherits the body of the event
+            // group with the same name.
+            if event.body.is_empty() {
+                if let Some(body) = sem_conv_catalog.event_body(&event.event_name) {
+                    event.body = body.to_vec();
+                }
+            }
             event.attributes = resolve_attributes(
                 event.attributes.as_ref(),
                 sem_conv_catalog,
@@ -30,3 +41,28 @@ pub fn resolve_events(
     }
     Ok(())
 }
+
+/// Resolve the fields of an event body.
+pub fn resolve_body(body: &[BodyFieldSpec]) -> Vec<BodyField> {
+    body.iter()
+        .map(|field| BodyField {
+            id: field.id.clone(),
+            r#type: match field.r#type {
+                BodyFieldTypeSpec::Boolean => BodyFieldType::Boolean,
+                BodyFieldTypeSpec::Int => BodyFieldType::Int,
+                BodyFieldTypeSpec::Double => BodyFieldType::Double,
+                BodyFieldTypeSpec::String => BodyFieldType::String,
+                BodyFieldTypeSpec::Strings => BodyFieldType::Strings,
+                BodyFieldTypeSpec::Ints => BodyFieldType::Ints,
+                BodyFieldTypeSpec::Doubles => BodyFieldType::Doubles,
+                BodyFieldTypeSpec::Booleans => BodyFieldType::Booleans,
+                BodyFieldTypeSpec::Map => BodyFieldType::Map,
+            },
+            brief: field.brief.clone(),
+            note: field.note.clone(),
+            requirement_level: semconv_to_resolved_req_level(&field.requirement_level),
+            examples: semconv_to_resolved_examples(&field.examples),
+            fields: resolve_body(&field.fields),
+        })
+        .collect()
+}
@@ -0,0 +1,477 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Step-by-step explanation of the resolution of a semantic convention group.
+
+use serde::Serialize;
+
+use weaver_resolved_schema::attribute::{Attribute, AttributeRef};
+use weaver_resolved_schema::lineage::{FieldId, ResolutionMode};
+use weaver_resolved_schema::registry::{Group, TypedGroup};
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::SemConvSpecs;
+use weaver_version::VersionChanges;
+
+use crate::attribute::AttributeCatalog;
+use crate::registry::{resolve_registry, unresolved_registry_from_specs};
+use crate::Error;
+
+/// A transformation applied to a group during its resolution.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum ResolutionStep {
+    /// A local attribute id has been expanded with the prefix of the group.
+    PrefixExpansion {
+        /// The attribute id as defined in the group.
+        local_id: String,
+        /// The prefix of the group.
+        prefix: String,
+        /// The fully qualified attribute id.
+        attribute_id: String,
+    },
+    /// An attribute reference has been materialized from the attribute it
+    /// refers to.
+    RefMaterialization {
+        /// The referenced attribute id.
+        attribute_id: String,
+        /// The id of the group defining the referenced attribute (if at least
+        /// one field is inherited from it).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        defined_in: Option<String>,
+        /// The fields inherited from the referenced attribute.
+        inherited_fields: Vec<FieldId>,
+        /// The fields overridden by the reference.
+        overridden_fields: Vec<FieldId>,
+    },
+    /// The attributes of the extended group have been merged into the group.
+    ExtendsMerge {
+        /// The id of the extended group.
+        extends: String,
+        /// The ids of the inherited attributes.
+        attribute_ids: Vec<String>,
+    },
+    /// A name has been renamed by the schema versions.
+    VersionRename {
+        /// The name before the rename.
+        old_name: String,
+        /// The name after the rename.
+        new_name: String,
+    },
+    /// A constraint has been expanded into the resolved group.
+    ConstraintExpansion {
+        /// The attribute ids of the `any_of` constraint.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        any_of: Vec<String>,
+        /// The id of the included group.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        include: Option<String>,
+    },
+}
+
+/// The step-by-step record of the transformations applied to a group to
+/// go from its specification to its resolved form.
+#[derive(Serialize, Debug, Clone)]
+pub struct ResolutionExplanation {
+    /// The id of the explained group.
+    pub group_id: String,
+    /// The provenance (path or URL) of the group.
+    pub provenance: String,
+    /// The transformations in the order they are applied by the resolver.
+    pub steps: Vec<ResolutionStep>,
+}
+
+/// A field of a referenced attribute redefined with a different value by an
+/// attribute reference.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AttributeOverride {
+    /// The id of the group containing the attribute reference.
+    pub group_id: String,
+    /// The referenced attribute id.
+    pub attribute_id: String,
+    /// The overridden field.
+    pub field_id: FieldId,
+    /// The id of the group defining the referenced attribute.
+    pub defined_in: String,
+    /// The value of the field in the referenced attribute (JSON).
+    pub before: String,
+    /// The value of the field in the attribute reference (JSON).
+    pub after: String,
+}
+
+/// Explains the resolution of the group `group_id` of the given semantic
+/// convention registry. The optional version changes are used to report the
+/// renames applied to the resolved names.
+pub fn explain_resolution(
+    specs: &SemConvSpecs,
+    group_id: &str,
+    version_changes: Option<&VersionChanges>,
+) -> Result<ResolutionExplanation, Error> {
+    let group_spec = specs
+        .groups_with_provenance()
+        .find(|group| group.spec.id == group_id)
+        .ok_or_else(|| Error::GroupNotFound {
+            group_id: group_id.to_owned(),
+        })?;
+
+    let mut attr_catalog = AttributeCatalog::default();
+    let registry = resolve_registry(unresolved_registry_from_specs("", specs), &mut attr_catalog)?;
+    let attributes = attr_catalog.drain_attributes();
+    let resolved_group = registry
+        .groups
+        .into_iter()
+        .find(|group| group.id == group_id)
+        .ok_or_else(|| Error::GroupNotFound {
+            group_id: group_id.to_owned(),
+        })?;
+    let attribute = |attr_ref: &AttributeRef| attributes.get(attr_ref.0 as usize);
+
+    let mut steps = vec![];
+    let spec = &group_spec.spec;
+
+    for attr in spec.attributes.iter() {
+        match attr {
+            AttributeSpec::Id { id, .. } if !spec.prefix.is_empty() => {
+                steps.push(ResolutionStep::PrefixExpansion {
+                    local_id: id.clone(),
+                    prefix: spec.prefix.clone(),
+                    attribute_id: format!("{}.{}", spec.prefix, id),
+                });
+            }
+            AttributeSpec::Id { .. } => {}
+            AttributeSpec::Ref { r#ref, .. } => {
+                let attr_ref = resolved_group
+                    .attributes
+                    .iter()
+                    .find(|attr_ref| attribute(attr_ref).map(|attr| &attr.name) == Some(r#ref));
+                let mut defined_in = None;
+                let mut inherited_fields = vec![];
+                if let Some(fields) = attr_ref.and_then(|attr_ref| {
+                    resolved_group
+                        .lineage
+                        .as_ref()
+                        .and_then(|lineage| lineage.attribute_lineage(attr_ref))
+                }) {
+                    for (field_id, field_lineage) in fields {
+                        if field_lineage.resolution_mode == ResolutionMode::Reference {
+                            defined_in = Some(field_lineage.group_id.clone());
+                            inherited_fields.push(field_id.clone());
+                        }
+                    }
+                }
+                steps.push(ResolutionStep::RefMaterialization {
+                    attribute_id: r#ref.clone(),
+                    defined_in,
+                    inherited_fields,
+                    overridden_fields: overridden_fields(attr),
+                });
+            }
+        }
+    }
+
+    if let Some(extends) = &spec.extends {
+        let attribute_ids = resolved_group
+            .attributes
+            .iter()
+            .filter(|attr_ref| {
+                resolved_group
+                    .lineage
+                    .as_ref()
+                    .and_then(|lineage| lineage.attribute_lineage(attr_ref))
+                    .and_then(|fields| fields.get(&FieldId::GroupAttributes))
+                    .is_some_and(|field_lineage| {
+                        field_lineage.resolution_mode == ResolutionMode::Extends
+                    })
+            })
+            .filter_map(|attr_ref| attribute(attr_ref).map(|attr| attr.name.clone()))
+            .collect();
+        steps.push(ResolutionStep::ExtendsMerge {
+            extends: extends.clone(),
+            attribute_ids,
+        });
+    }
+
+    if let Some(version_changes) = version_changes {
+        steps.extend(version_renames(
+            &resolved_group,
+            resolved_group.attributes.iter().filter_map(attribute),
+            version_changes,
+        ));
+    }
+
+    for constraint in spec.constraints.iter() {
+        steps.push(ResolutionStep::ConstraintExpansion {
+            any_of: constraint.any_of.clone(),
+            include: constraint.include.clone(),
+        });
+    }
+
+    Ok(ResolutionExplanation {
+        group_id: group_id.to_owned(),
+        provenance: group_spec.provenance,
+        steps,
+    })
+}
+
+/// Returns the brief, note and examples of the referenced attributes
+/// redefined with a different value by the attribute references of the given
+/// semantic convention registry.
+pub fn attribute_overrides(specs: &SemConvSpecs) -> Result<Vec<AttributeOverride>, Error> {
+    let mut attr_catalog = AttributeCatalog::default();
+    let registry = resolve_registry(unresolved_registry_from_specs("", specs), &mut attr_catalog)?;
+    let attributes = attr_catalog.drain_attributes();
+
+    let mut overrides = vec![];
+    for group in registry.groups.iter() {
+        let Some(lineage) = group.lineage.as_ref() else {
+            continue;
+        };
+        for attr_ref in group.attributes.iter() {
+            let (Some(attr), Some(fields)) = (
+                attributes.get(attr_ref.0 as usize),
+                lineage.attribute_overrides(attr_ref),
+            ) else {
+                continue;
+            };
+            overrides.extend(
+                fields
+                    .iter()
+                    .map(|(field_id, field_override)| AttributeOverride {
+                        group_id: group.id.clone(),
+                        attribute_id: attr.name.clone(),
+                        field_id: field_id.clone(),
+                        defined_in: field_override.group_id.clone(),
+                        before: field_override.before.clone(),
+                        after: field_override.after.clone(),
+                    }),
+            );
+        }
+    }
+    Ok(overrides)
+}
+
+/// Returns the fields explicitly set by an attribute reference.
+pub(crate) fn overridden_fields(attr: &AttributeSpec) -> Vec<FieldId> {
+    let mut fields = vec![];
+    if let AttributeSpec::Ref {
+        brief,
+        examples,
+        tag,
+        requirement_level,
+        sampling_relevant,
+        note,
+        stability,
+        deprecated,
+        ..
+    } = attr
+    {
+        let overrides = [
+            (brief.is_some(), FieldId::AttributeBrief),
+            (examples.is_some(), FieldId::AttributeExamples),
+            (tag.is_some(), FieldId::AttributeTag),
+            (
+                requirement_level.is_some(),
+                FieldId::AttributeRequirementLevel,
+            ),
+            (
+                sampling_relevant.is_some(),
+                FieldId::AttributeSamplingRelevant,
+            ),
+            (note.is_some(), FieldId::AttributeNote),
+            (stability.is_some(), FieldId::AttributeStability),
+            (deprecated.is_some(), FieldId::AttributeDeprecated),
+        ];
+        fields.extend(
+            overrides
+                .into_iter()
+                .filter_map(|(is_set, field_id)| is_set.then_some(field_id)),
+        );
+    }
+    fields
+}
+
+/// Returns the renames the version changes apply to the attributes (and the
+/// metric name) of the given resolved group.
+fn version_renames<'a>(
+    group: &Group,
+    attributes: impl Iterator<Item = &'a Attribute>,
+    version_changes: &VersionChanges,
+) -> Vec<ResolutionStep> {
+    let rename = |old_name: &str, new_name: String| {
+        (old_name != new_name).then(|| ResolutionStep::VersionRename {
+            old_name: old_name.to_owned(),
+            new_name,
+        })
+    };
+
+    let mut steps = vec![];
+    if let TypedGroup::Metric {
+        metric_name: Some(metric_name),
+        ..
+    } = &group.typed_group
+    {
+        steps.extend(rename(
+            metric_name,
+            version_changes.get_metric_name(metric_name),
+        ));
+    }
+    for attr in attributes {
+        let new_name = match group.typed_group {
+            TypedGroup::Span { .. } => version_changes.get_span_attribute_name(&attr.name),
+            TypedGroup::Event { .. } => version_changes.get_log_attribute_name(&attr.name),
+            TypedGroup::Metric { .. } | TypedGroup::MetricGroup {} => {
+                version_changes.get_metric_attribute_name(&attr.name)
+            }
+            TypedGroup::Resource {} => version_changes.get_resource_attribute_name(&attr.name),
+            TypedGroup::AttributeGroup {} | TypedGroup::Scope {} => continue,
+        };
+        steps.extend(rename(&attr.name, new_name));
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolved_schema::lineage::FieldId;
+    use weaver_semconv::SemConvSpecs;
+    use weaver_version::Versions;
+
+    use crate::explain::{attribute_overrides, explain_resolution, ResolutionStep};
+
+    #[test]
+    fn test_explain_resolution() {
+        let mut specs = SemConvSpecs::default();
+        for file in glob::glob("data/registry-test-3-extends/registry/*.yaml").unwrap() {
+            specs.load_from_file(file.unwrap()).unwrap();
+        }
+
+        let explanation = explain_resolution(&specs, "metric.messaging.attributes", None).unwrap();
+        assert_eq!(
+            explanation.steps[0],
+            ResolutionStep::RefMaterialization {
+                attribute_id: "messaging.destination.name".to_owned(),
+                defined_in: Some("registry.messaging".to_owned()),
+                inherited_fields: vec![
+                    FieldId::AttributeBrief,
+                    FieldId::AttributeExamples,
+                    FieldId::AttributeTag,
+                    FieldId::AttributeSamplingRelevant,
+                    FieldId::AttributeNote,
+                    FieldId::AttributeStability,
+                    FieldId::AttributeDeprecated,
+                ],
+                overridden_fields: vec![FieldId::AttributeRequirementLevel],
+            }
+        );
+        match explanation.steps.last() {
+            Some(ResolutionStep::ExtendsMerge {
+                extends,
+                attribute_ids,
+            }) => {
+                assert_eq!(extends, "messaging.attributes.common");
+                assert!(attribute_ids.contains(&"messaging.system".to_owned()));
+            }
+            step => panic!("Expected an extends merge, found {:?}", step),
+        }
+
+        assert!(explain_resolution(&specs, "unknown.group", None).is_err());
+    }
+
+    #[test]
+    fn test_explain_version_renames() {
+        let mut specs = SemConvSpecs::default();
+        for file in glob::glob("data/registry-test-3-extends/registry/*.yaml").unwrap() {
+            specs.load_from_file(file.unwrap()).unwrap();
+        }
+        let versions = Versions::load_from_str(
+            r#"
+versions:
+  1.1.0:
+    metrics:
+      changes:
+        - rename_metrics:
+            messaging.publish.duration: messaging.client.publish.duration
+        - rename_attributes:
+            attribute_map:
+              messaging.destination.name: messaging.destination
+    spans:
+      changes:
+        - rename_attributes:
+            attribute_map:
+              messaging.system: messaging.provider
+  1.0.0:
+"#,
+            "versions.yaml",
+        )
+        .unwrap();
+        let version_changes = versions.version_changes_for(versions.latest_version().unwrap());
+
+        let explanation = explain_resolution(
+            &specs,
+            "metric.messaging.publish.duration",
+            Some(&version_changes),
+        )
+        .unwrap();
+        let renames: Vec<_> = explanation
+            .steps
+            .iter()
+            .filter(|step| matches!(step, ResolutionStep::VersionRename { .. }))
+            .collect();
+        // The span attribute renames don't apply to a metric.
+        assert_eq!(
+            renames,
+            vec![
+                &ResolutionStep::VersionRename {
+                    old_name: "messaging.publish.duration".to_owned(),
+                    new_name: "messaging.client.publish.duration".to_owned(),
+                },
+                &ResolutionStep::VersionRename {
+                    old_name: "messaging.destination.name".to_owned(),
+                    new_name: "messaging.destination".to_owned(),
+                },
+            ]
+        );
+        // The renames follow the `extends` merge.
+        assert!(matches!(
+            explanation.steps.first(),
+            Some(ResolutionStep::ExtendsMerge { .. })
+        ));
+
+        let explanation =
+            explain_resolution(&specs, "metric.messaging.publish.duration", None).unwrap();
+        assert!(!explanation
+            .steps
+            .iter()
+            .any(|step| matches!(step, ResolutionStep::VersionRename { .. })));
+    }
+
+    #[test]
+    fn test_attribute_overrides() {
+        let mut specs = SemConvSpecs::default();
+        for file in glob::glob("data/registry-test-3-extends/registry/*.yaml").unwrap() {
+            specs.load_from_file(file.unwrap()).unwrap();
+        }
+
+        let overrides = attribute_overrides(&specs).unwrap();
+        let url_scheme_examples = overrides
+            .iter()
+            .find(|attr_override| {
+                attr_override.group_id == "attributes.http.client"
+                    && attr_override.attribute_id == "url.scheme"
+            })
+            .expect("Expected an override of `url.scheme`");
+        assert_eq!(url_scheme_examples.field_id, FieldId::AttributeExamples);
+        assert_eq!(url_scheme_examples.defined_in, "registry.url");
+        assert_eq!(
+            url_scheme_examples.before,
+            r#"{"type":"Strings","values":["https","ftp","telnet"]}"#
+        );
+        assert_eq!(
+            url_scheme_examples.after,
+            r#"{"type":"Strings","values":["http","https"]}"#
+        );
+
+        // Overriding the requirement level only is not reported.
+        assert!(!overrides
+            .iter()
+            .any(|attr_override| attr_override.attribute_id == "messaging.destination.name"));
+    }
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolve the instrumentation library (instrumentation scope) of a
+//! telemetry schema.
+
+use weaver_schema::TelemetrySchema;
+
+use crate::Error;
+
+/// Checks that the version of the instrumentation library (if any) matches
+/// the version of the schema (if its schema URL ends with a version), and
+/// sets the schema URL of the instrumentation library to the schema URL of
+/// the schema when not defined (the dependencies keep their own schema URL).
+pub fn resolve_instrumentation_library(schema: &mut TelemetrySchema) -> Result<(), Error> {
+    if let (Some(version), Some(schema_version)) = (
+        schema
+            .instrumentation_library()
+            .and_then(|library| library.version.as_deref()),
+        schema.schema_version(),
+    ) {
+        if version != schema_version {
+            return Err(Error::InstrumentationLibraryVersionMismatch {
+                version: version.to_owned(),
+                schema_version: schema_version.to_owned(),
+            });
+        }
+    }
+
+    let schema_url = schema.schema_url.clone();
+    if let Some(library) = schema
+        .schema
+        .as_mut()
+        .and_then(|schema_spec| schema_spec.instrumentation_library.as_mut())
+    {
+        if library.schema_url.is_none() {
+            library.schema_url = Some(schema_url);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_schema::TelemetrySchema;
+
+    use crate::instrumentation_library::resolve_instrumentation_library;
+    use crate::Error;
+
+    fn telemetry_schema(version: &str) -> TelemetrySchema {
+        serde_yaml::from_str(&format!(
+            r#"
+file_format: 1.2.0
+schema_url: https://mycompany.com/schemas/1.2.0
+schema:
+  instrumentation_library:
+    name: my-service
+    version: {version}
+  dependencies:
+    - name: my-http-client
+      version: 0.4.0
+"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_instrumentation_library() {
+        let mut schema = telemetry_schema("1.2.0");
+        resolve_instrumentation_library(&mut schema).unwrap();
+        let schema_spec = schema.schema.as_ref().unwrap();
+        let library = schema_spec.instrumentation_library.as_ref().unwrap();
+        assert_eq!(
+            library.schema_url.as_deref(),
+            Some("https://mycompany.com/schemas/1.2.0")
+        );
+        assert!(schema_spec.dependencies[0].schema_url.is_none());
+
+        let mut schema = telemetry_schema("1.1.0");
+        assert!(matches!(
+            resolve_instrumentation_library(&mut schema),
+            Err(Error::InstrumentationLibraryVersionMismatch { version, schema_version })
+                if version == "1.1.0" && schema_version == "1.2.0"
+        ));
+    }
+}
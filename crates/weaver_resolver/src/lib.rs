@@ -6,46 +6,71 @@
 #![deny(clippy::print_stdout)]
 #![deny(clippy::print_stderr)]
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::Relaxed;
 use std::time::Instant;
 
+use ignore::gitignore::GitignoreBuilder;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
-use regex::Regex;
-use url::Url;
 use walkdir::DirEntry;
 
 use crate::attribute::AttributeCatalog;
 use weaver_cache::Cache;
 use weaver_logger::Logger;
 use weaver_resolved_schema::catalog::Catalog;
+use weaver_resolved_schema::manifest::RegistryManifest;
 use weaver_resolved_schema::ResolvedTelemetrySchema;
 use weaver_schema::{SemConvImport, TelemetrySchema};
-use weaver_semconv::{ResolverConfig, SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs};
-use weaver_version::VersionChanges;
+use weaver_semconv::{
+    ResolverConfig, ResolverWarning, SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs,
+};
+
+pub use crate::explain::{attribute_overrides, explain_resolution};
+pub use crate::usages::find_usages;
 
 use crate::events::resolve_events;
+use crate::instrumentation_library::resolve_instrumentation_library;
 use crate::metrics::{resolve_metrics, semconv_to_resolved_metric};
+use crate::path::git_provenance;
+pub use crate::pool::init_io_pool;
 use crate::registry::resolve_semconv_registry;
 use crate::resource::resolve_resource;
 use crate::spans::resolve_spans;
+use crate::stability::{filter_resolved_schema, filter_schema_attributes};
 
+mod artifact;
 pub mod attribute;
 mod constraint;
 mod events;
+pub mod explain;
+mod instrumentation_library;
 mod metrics;
+mod path;
+mod pool;
 pub mod registry;
 mod resource;
 mod spans;
 mod stability;
 mod tags;
+pub mod usages;
 
 /// A resolver that can be used to resolve telemetry schemas.
 /// All references to semantic conventions will be resolved.
 pub struct SchemaResolver {}
 
+/// The diagnostics of a lenient resolution (see
+/// [`SchemaResolver::semconv_registry_from_imports_lenient`]).
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    /// The errors skipped by the resolution (semantic convention files that
+    /// couldn't be loaded, resolution errors).
+    pub errors: Vec<Error>,
+    /// The warnings of the resolution.
+    pub warnings: Vec<ResolverWarning>,
+}
+
 /// Different types of unresolved references.
 #[derive(Debug)]
 pub enum UnresolvedReference {
@@ -67,6 +92,15 @@ pub enum UnresolvedReference {
         /// The provenance of the reference (URL or path).
         provenance: String,
     },
+    /// An unresolved `include` constraint reference.
+    IncludeRef {
+        /// The id of the group containing the `include` constraint.
+        group_id: String,
+        /// The unresolved `include` constraint reference.
+        include_ref: String,
+        /// The provenance of the reference (URL or path).
+        provenance: String,
+    },
 }
 
 /// An error that can occur while resolving a telemetry schema.
@@ -80,6 +114,15 @@ pub enum Error {
     #[error("Parent schema error (error: {0:?})")]
     ParentSchemaError(weaver_schema::Error),
 
+    /// An invalid resolved schema artifact (used as a parent schema).
+    #[error("Invalid resolved schema `{path_or_url}`: {error}")]
+    InvalidResolvedSchema {
+        /// The path or URL of the resolved schema.
+        path_or_url: String,
+        /// The error that occurred.
+        error: String,
+    },
+
     /// An invalid URL.
     #[error("Invalid URL `{url:?}`, error: {error:?})")]
     InvalidUrl {
@@ -112,6 +155,22 @@ pub enum Error {
         refs: Vec<UnresolvedReference>,
     },
 
+    /// The `extends` chains of a set of groups form a cycle.
+    #[error("Cyclic `extends` chain: {}", .group_ids.join(" -> "))]
+    CyclicExtends {
+        /// The ids of the groups of the cycle, the first group being repeated
+        /// at the end.
+        group_ids: Vec<String>,
+    },
+
+    /// The `include` constraints of a set of groups form a cycle.
+    #[error("Cyclic `include` constraints: {}", .group_ids.join(" -> "))]
+    CyclicInclude {
+        /// The ids of the groups of the cycle, the first group being repeated
+        /// at the end.
+        group_ids: Vec<String>,
+    },
+
     /// Failed to resolve a metric.
     #[error("Failed to resolve the metric '{r#ref}'")]
     FailToResolveMetric {
@@ -136,8 +195,71 @@ pub enum Error {
         /// The error that occurred.
         message: String,
     },
+
+    /// The single source (no mirrors) of a semantic convention import could
+    /// not be fetched.
+    #[error("Failed to fetch the semantic convention import `{url}`: {error}")]
+    FetchFailed {
+        /// The URL of the import.
+        url: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// None of the sources (primary URL and mirrors) of a semantic convention
+    /// import could be fetched.
+    #[error("Failed to fetch the semantic convention import from all its sources:\n{}", .attempts.join("\n"))]
+    ImportSourcesFailed {
+        /// The attempted sources with their error (`- <source>: <error>`).
+        attempts: Vec<String>,
+    },
+
+    /// A thread pool couldn't be created.
+    #[error("Failed to create the thread pool ({error})")]
+    ThreadPoolError {
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// A group was not found in the semantic convention registry.
+    #[error("Group `{group_id}` not found in the semantic convention registry")]
+    GroupNotFound {
+        /// The id of the group.
+        group_id: String,
+    },
+
+    /// An attribute is not used by any group of the semantic convention
+    /// registry.
+    #[error("Attribute `{attribute_id}` not found in the semantic convention registry")]
+    AttributeNotFound {
+        /// The id of the attribute.
+        attribute_id: String,
+    },
+
+    /// The version of the instrumentation library doesn't match the version
+    /// of the telemetry schema.
+    #[error("The version `{version}` of the instrumentation library doesn't match the version `{schema_version}` of the schema")]
+    InstrumentationLibraryVersionMismatch {
+        /// The version of the instrumentation library.
+        version: String,
+        /// The version of the schema (from its schema URL).
+        schema_version: String,
+    },
+
+    /// The `.weaverignore` file of a local registry is invalid.
+    #[error("Invalid ignore file {path:?}: {error}")]
+    InvalidIgnoreFile {
+        /// The path of the ignore file.
+        path: PathBuf,
+        /// The error that occurred.
+        error: String,
+    },
 }
 
+/// Name of the file listing, with the gitignore syntax, the files of a local
+/// registry directory that must not be loaded.
+pub const WEAVER_IGNORE_FILE: &str = ".weaverignore";
+
 impl SchemaResolver {
     /// Loads a telemetry schema from an URL or a file and returns the resolved
     /// schema.
@@ -147,7 +269,13 @@ impl SchemaResolver {
         log: impl Logger + Clone + Sync,
     ) -> Result<TelemetrySchema, Error> {
         let mut schema = Self::load_schema(schema_url_or_path, log.clone())?;
-        Self::resolve(&mut schema, schema_url_or_path, cache, log)?;
+        Self::resolve(
+            &mut schema,
+            schema_url_or_path,
+            ResolverConfig::default(),
+            cache,
+            log,
+        )?;
 
         Ok(schema)
     }
@@ -157,11 +285,23 @@ impl SchemaResolver {
         schema_path: P,
         cache: &Cache,
         log: impl Logger + Clone + Sync,
+    ) -> Result<TelemetrySchema, Error> {
+        Self::resolve_schema_file_with_config(schema_path, ResolverConfig::default(), cache, log)
+    }
+
+    /// Loads a telemetry schema file and returns the schema resolved with the
+    /// given resolver configuration.
+    pub fn resolve_schema_file_with_config<P: AsRef<Path> + Clone>(
+        schema_path: P,
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
     ) -> Result<TelemetrySchema, Error> {
         let mut schema = Self::load_schema_from_path(schema_path.clone(), log.clone())?;
         Self::resolve(
             &mut schema,
-            schema_path.as_ref().to_str().unwrap(),
+            &schema_path.as_ref().display().to_string(),
+            resolver_config,
             cache,
             log,
         )?;
@@ -173,27 +313,38 @@ impl SchemaResolver {
     fn resolve(
         schema: &mut TelemetrySchema,
         schema_path: &str,
+        resolver_config: ResolverConfig,
         cache: &Cache,
         log: impl Logger + Clone + Sync,
     ) -> Result<(), Error> {
-        let sem_conv_catalog = Self::semconv_registry_from_schema(schema, cache, log.clone())?;
+        let sem_conv_catalog = Self::semconv_registry_from_schema_with_config(
+            schema,
+            resolver_config,
+            cache,
+            log.clone(),
+        )?;
+        Self::resolve_schema_with_registry(schema, schema_path, sem_conv_catalog, log)
+    }
+
+    /// Resolves the given telemetry schema (loaded with its parent schema)
+    /// against an already loaded semantic convention registry, e.g. a registry
+    /// shared by several schemas importing the same semantic conventions (see
+    /// [`SchemaResolver::semconv_registry_from_imports`]).
+    pub fn resolve_schema_with_registry(
+        schema: &mut TelemetrySchema,
+        schema_path: &str,
+        sem_conv_catalog: SemConvSpecs,
+        log: impl Logger + Clone + Sync,
+    ) -> Result<(), Error> {
         let start = Instant::now();
 
         // Merges the versions of the parent schema into the current schema.
         schema.merge_versions();
 
         // Generates version changes
-        let version_changes = schema
-            .versions
-            .as_ref()
-            .map(|versions| {
-                if let Some(latest_version) = versions.latest_version() {
-                    versions.version_changes_for(latest_version)
-                } else {
-                    VersionChanges::default()
-                }
-            })
-            .unwrap_or_default();
+        let version_changes = schema.version_changes();
+
+        resolve_instrumentation_library(schema)?;
 
         // Resolve the references to the semantic conventions.
         log.loading("Solving semantic convention references");
@@ -203,6 +354,8 @@ impl SchemaResolver {
             resolve_events(schema, &sem_conv_catalog, &version_changes)?;
             resolve_spans(schema, &sem_conv_catalog, version_changes)?;
         }
+        filter_schema_attributes(schema, sem_conv_catalog.stability_filter());
+        log.phase("resolve", start.elapsed());
         log.success(&format!(
             "Resolved schema '{}' ({:.2}s)",
             schema_path,
@@ -226,6 +379,7 @@ impl SchemaResolver {
             &[SemConvImport::GitUrl {
                 git_url: registry_git_url,
                 path,
+                mirrors: vec![],
             }],
             ResolverConfig::default(),
             cache,
@@ -244,6 +398,7 @@ impl SchemaResolver {
             &[SemConvImport::GitUrl {
                 git_url: registry_git_url,
                 path,
+                mirrors: vec![],
             }],
             cache,
             log.clone(),
@@ -262,6 +417,7 @@ impl SchemaResolver {
             log.error(&format!("Failed to load schema '{}'", schema_url_or_path));
             Error::TelemetrySchemaError(e)
         })?;
+        log.phase("load", start.elapsed());
         log.success(&format!(
             "Loaded schema '{}' ({:.2}s)",
             schema_url_or_path,
@@ -291,6 +447,7 @@ impl SchemaResolver {
             ));
             Error::TelemetrySchemaError(e)
         })?;
+        log.phase("load", start.elapsed());
         log.success(&format!(
             "Loaded schema '{}' ({:.2}s)",
             schema_path.as_ref().display(),
@@ -308,14 +465,33 @@ impl SchemaResolver {
         cache: &Cache,
         log: impl Logger + Clone + Sync,
     ) -> Result<SemConvSpecs, Error> {
-        Self::semconv_registry_from_imports(
-            &schema.merged_semantic_conventions(),
+        Self::semconv_registry_from_schema_with_config(
+            schema,
             ResolverConfig::default(),
             cache,
             log.clone(),
         )
     }
 
+    /// Loads a semantic convention registry from the given schema with the
+    /// given resolver configuration: the imports of the schema and of its
+    /// parent schemas, and the semantic conventions of its parent schemas
+    /// loaded from resolved schema artifacts.
+    pub fn semconv_registry_from_schema_with_config(
+        schema: &TelemetrySchema,
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
+    ) -> Result<SemConvSpecs, Error> {
+        Self::semconv_registry_from_imports_and_specs(
+            &schema.merged_semantic_conventions(),
+            schema.merged_resolved_semantic_conventions(),
+            resolver_config,
+            cache,
+            log,
+        )
+    }
+
     /// Loads a semantic convention registry from the given semantic convention imports.
     pub fn load_semconv_registry_from_imports(
         imports: &[SemConvImport],
@@ -323,7 +499,8 @@ impl SchemaResolver {
         log: impl Logger + Clone + Sync,
     ) -> Result<SemConvSpecs, Error> {
         let start = Instant::now();
-        let registry = Self::create_semantic_convention_registry(imports, cache, log.clone())?;
+        let registry =
+            Self::create_semantic_convention_registry(imports, false, cache, log.clone())?;
         log.success(&format!(
             "Loaded {} semantic convention files containing the definition of {} attributes and {} metrics ({:.2}s)",
             registry.asset_count(),
@@ -341,14 +518,36 @@ impl SchemaResolver {
         resolver_config: ResolverConfig,
         cache: &Cache,
         log: impl Logger + Clone + Sync,
+    ) -> Result<SemConvSpecs, Error> {
+        Self::semconv_registry_from_imports_and_specs(imports, vec![], resolver_config, cache, log)
+    }
+
+    /// Loads a semantic convention registry from the given semantic convention
+    /// imports and already loaded semantic convention specs.
+    fn semconv_registry_from_imports_and_specs(
+        imports: &[SemConvImport],
+        specs: Vec<SemConvSpecWithProvenance>,
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
     ) -> Result<SemConvSpecs, Error> {
         let start = Instant::now();
-        let mut registry = Self::create_semantic_convention_registry(imports, cache, log.clone())?;
+        let mut registry = Self::create_semantic_convention_registry(
+            imports,
+            resolver_config.build_tools_compat(),
+            cache,
+            log.clone(),
+        )?;
+        for spec in specs {
+            registry.append_sem_conv_spec(spec);
+        }
+        let resolve_start = Instant::now();
         let warnings = registry
             .resolve(resolver_config)
             .map_err(|e| Error::SemConvError {
                 message: e.to_string(),
             })?;
+        log.phase("resolve", resolve_start.elapsed());
         for warning in warnings {
             log.warn("Semantic convention warning")
                 .log(&warning.error.to_string());
@@ -364,6 +563,94 @@ impl SchemaResolver {
         Ok(registry)
     }
 
+    /// Loads a semantic convention registry from the given semantic
+    /// convention imports in lenient mode: the files that can't be loaded are
+    /// skipped and the resolution errors don't fail the resolution (see
+    /// [`SemConvSpecs::resolve_lenient`]). The returned registry contains the
+    /// definitions that could be resolved, the skipped errors being returned
+    /// with the warnings.
+    pub fn semconv_registry_from_imports_lenient(
+        imports: &[SemConvImport],
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
+    ) -> (SemConvSpecs, Diagnostics) {
+        Self::semconv_registry_from_imports_and_specs_lenient(
+            imports,
+            vec![],
+            resolver_config,
+            cache,
+            log,
+        )
+    }
+
+    /// Loads a semantic convention registry from the given semantic
+    /// convention imports and already loaded semantic convention specs in
+    /// lenient mode (see [`SchemaResolver::semconv_registry_from_imports_lenient`]).
+    fn semconv_registry_from_imports_and_specs_lenient(
+        imports: &[SemConvImport],
+        specs: Vec<SemConvSpecWithProvenance>,
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
+    ) -> (SemConvSpecs, Diagnostics) {
+        let start = Instant::now();
+        let (mut registry, mut errors) = Self::load_semantic_convention_specs(
+            imports,
+            resolver_config.build_tools_compat(),
+            cache,
+            log.clone(),
+        );
+        for spec in specs {
+            registry.append_sem_conv_spec(spec);
+        }
+        let resolve_start = Instant::now();
+        let (warnings, resolution_errors) = registry.resolve_lenient(resolver_config);
+        log.phase("resolve", resolve_start.elapsed());
+        errors.extend(resolution_errors.into_iter().map(|e| Error::SemConvError {
+            message: e.to_string(),
+        }));
+        log.success(&format!(
+            "Loaded {} semantic convention files containing the definition of {} attributes and {} metrics, {} error(s) skipped ({:.2}s)",
+            registry.asset_count(),
+            registry.attribute_count(),
+            registry.metric_count(),
+            errors.len(),
+            start.elapsed().as_secs_f32()
+        ));
+
+        (registry, Diagnostics { errors, warnings })
+    }
+
+    /// Resolves the given telemetry schema (loaded with its parent schema) in
+    /// lenient mode: its semantic convention registry is loaded with
+    /// [`SchemaResolver::semconv_registry_from_imports_lenient`], and if the
+    /// signals of the schema can't be resolved, the error is added to the
+    /// diagnostics and the signals are kept as resolved so far.
+    pub fn resolve_schema_lenient(
+        mut schema: TelemetrySchema,
+        schema_path: &str,
+        resolver_config: ResolverConfig,
+        cache: &Cache,
+        log: impl Logger + Clone + Sync,
+    ) -> (TelemetrySchema, Diagnostics) {
+        let (registry, mut diagnostics) = Self::semconv_registry_from_imports_and_specs_lenient(
+            &schema.merged_semantic_conventions(),
+            schema.merged_resolved_semantic_conventions(),
+            resolver_config,
+            cache,
+            log.clone(),
+        );
+        if let Err(e) =
+            Self::resolve_schema_with_registry(&mut schema, schema_path, registry.clone(), log)
+        {
+            diagnostics.errors.push(e);
+            schema.semantic_conventions.clear();
+            schema.set_semantic_convention_catalog(registry);
+        }
+        (schema, diagnostics)
+    }
+
     /// Resolves the given semantic convention registry and returns the
     /// corresponding resolved telemetry schema.
     pub fn resolve_semantic_convention_registry(
@@ -381,7 +668,7 @@ impl SchemaResolver {
             .map(semconv_to_resolved_metric)
             .collect();
 
-        let resolved_schema = ResolvedTelemetrySchema {
+        let mut resolved_schema = ResolvedTelemetrySchema {
             file_format: "1.0.0".to_string(),
             schema_url: "".to_string(),
             registries: vec![resolved_registry],
@@ -393,7 +680,11 @@ impl SchemaResolver {
             instrumentation_library: None,
             dependencies: vec![],
             versions: None, // ToDo LQ: Implement this!
+            registry_manifest: registry.registry_manifest().to_vec(),
+            resolution_environment: None,
         };
+        filter_resolved_schema(&mut resolved_schema, registry.stability_filter());
+        log.phase("resolve", start.elapsed());
 
         log.success(&format!(
             "Resolved {} semantic convention files containing the definition of {} attributes and {} metrics ({:.2}s)",
@@ -406,7 +697,60 @@ impl SchemaResolver {
         Ok(resolved_schema)
     }
 
-    /// Loads the parent telemetry schema if it exists.
+    /// Builds the manifest of the given semantic convention imports, i.e. the
+    /// URL, commit, reference, fetch time and number of files of each imported
+    /// registry.
+    ///
+    /// The loaders of this resolver set this manifest on the registries they
+    /// load (see [`SemConvSpecs::registry_manifest`]), so it is embedded in
+    /// every resolved registry.
+    ///
+    /// Note: the file count is computed from the specs of the registry, so
+    /// the manifest must be built before the resolution of the registry
+    /// (which drops the specs unless [`ResolverConfig::with_keep_specs`] is
+    /// used).
+    pub fn registry_manifest(
+        imports: &[SemConvImport],
+        registry: &SemConvSpecs,
+        cache: &Cache,
+    ) -> Vec<RegistryManifest> {
+        imports
+            .iter()
+            .map(|import| {
+                let (registry_url, info) = match import {
+                    SemConvImport::Url { url, .. } => (url.clone(), None),
+                    SemConvImport::GitUrl {
+                        git_url, mirrors, ..
+                    } => (
+                        git_url.clone(),
+                        // The repo may have been fetched from a mirror.
+                        std::iter::once(git_url)
+                            .chain(mirrors.iter())
+                            .find_map(|source| cache.git_repo_info(source)),
+                    ),
+                    SemConvImport::LocalPath { local_path } => (local_path.clone(), None),
+                };
+                let file_count = registry
+                    .provenances()
+                    .filter(|provenance| provenance.starts_with(registry_url.as_str()))
+                    .count();
+                let info = info.unwrap_or_default();
+
+                RegistryManifest {
+                    registry_url,
+                    commit: info.commit,
+                    git_ref: info.git_ref,
+                    fetched_at: info.fetched_at,
+                    file_count,
+                    weaver_version: env!("CARGO_PKG_VERSION").to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Loads the parent telemetry schema if it exists. The parent schema can
+    /// be a source telemetry schema or a resolved schema artifact (see the
+    /// `artifact` module).
     fn load_parent_schema(
         schema: &TelemetrySchema,
         log: impl Logger,
@@ -415,36 +759,15 @@ impl SchemaResolver {
         // Load the parent schema and merge it into the current schema.
         let parent_schema = if let Some(parent_schema_url) = schema.parent_schema_url.as_ref() {
             log.loading(&format!("Loading parent schema '{}'", parent_schema_url));
-            let url_pattern = Regex::new(r"^(https|http|file):.*")
-                .expect("invalid regex, please report this bug");
-            let parent_schema = if url_pattern.is_match(parent_schema_url) {
-                let url = Url::parse(parent_schema_url).map_err(|e| {
-                    log.error(&format!(
-                        "Failed to parset parent schema url '{}'",
-                        parent_schema_url
-                    ));
-                    Error::InvalidUrl {
-                        url: parent_schema_url.clone(),
-                        error: e.to_string(),
-                    }
-                })?;
-                TelemetrySchema::load_from_url(&url).map_err(|e| {
-                    log.error(&format!(
-                        "Failed to load parent schema '{}'",
-                        parent_schema_url
-                    ));
-                    Error::ParentSchemaError(e)
-                })?
-            } else {
-                TelemetrySchema::load_from_file(parent_schema_url).map_err(|e| {
-                    log.error(&format!(
-                        "Failed to load parent schema '{}'",
-                        parent_schema_url
-                    ));
-                    Error::ParentSchemaError(e)
-                })?
-            };
+            let parent_schema = artifact::load_parent_schema(parent_schema_url).map_err(|e| {
+                log.error(&format!(
+                    "Failed to load parent schema '{}'",
+                    parent_schema_url
+                ));
+                e
+            })?;
 
+            log.phase("load", start.elapsed());
             log.success(&format!(
                 "Loaded parent schema '{}' ({:.2}s)",
                 parent_schema_url,
@@ -461,42 +784,72 @@ impl SchemaResolver {
     /// Creates a semantic convention registry from the given telemetry schema.
     fn create_semantic_convention_registry(
         sem_convs: &[SemConvImport],
+        build_tools_compat: bool,
         cache: &Cache,
         log: impl Logger + Sync,
     ) -> Result<SemConvSpecs, Error> {
+        let (sem_conv_catalog, mut errors) =
+            Self::load_semantic_convention_specs(sem_convs, build_tools_compat, cache, log);
+
+        // All the errors have been logged, the first one is returned so the
+        // caller can report the failure category (e.g. network failure).
+        if !errors.is_empty() {
+            return Err(errors.swap_remove(0));
+        }
+
+        Ok(sem_conv_catalog)
+    }
+
+    /// Loads the semantic convention specifications of the given imports and
+    /// returns them with the errors (logged) of the files that couldn't be
+    /// loaded.
+    fn load_semantic_convention_specs(
+        sem_convs: &[SemConvImport],
+        build_tools_compat: bool,
+        cache: &Cache,
+        log: impl Logger + Sync,
+    ) -> (SemConvSpecs, Vec<Error>) {
         // Load all the semantic convention catalogs.
         let mut sem_conv_catalog = SemConvSpecs::default();
         let total_file_count = sem_convs.len();
         let loaded_files_count = AtomicUsize::new(0);
         let error_count = AtomicUsize::new(0);
 
-        let result: Vec<Result<(String, SemConvSpec), Error>> = sem_convs
-            .par_iter()
-            .flat_map(|sem_conv_import| {
-                let results = Self::import_sem_conv_specs(sem_conv_import, cache);
-                for result in results.iter() {
-                    if result.is_err() {
-                        error_count.fetch_add(1, Relaxed);
-                    }
-                    loaded_files_count.fetch_add(1, Relaxed);
-                    if error_count.load(Relaxed) == 0 {
-                        log.loading(&format!(
-                            "Loaded {}/{} semantic convention files (no error detected)",
-                            loaded_files_count.load(Relaxed),
-                            total_file_count
-                        ));
-                    } else {
-                        log.loading(&format!(
-                            "Loaded {}/{} semantic convention files ({} error(s) detected)",
-                            loaded_files_count.load(Relaxed),
-                            total_file_count,
-                            error_count.load(Relaxed)
-                        ));
+        // The files are loaded in the IO thread pool, if initialized.
+        let result: Vec<Result<(String, SemConvSpec), Error>> = pool::install_io(|| {
+            sem_convs
+                .par_iter()
+                .flat_map(|sem_conv_import| {
+                    let results = Self::import_sem_conv_specs(
+                        sem_conv_import,
+                        build_tools_compat,
+                        cache,
+                        &log,
+                    );
+                    for result in results.iter() {
+                        if result.is_err() {
+                            error_count.fetch_add(1, Relaxed);
+                        }
+                        loaded_files_count.fetch_add(1, Relaxed);
+                        if error_count.load(Relaxed) == 0 {
+                            log.loading(&format!(
+                                "Loaded {}/{} semantic convention files (no error detected)",
+                                loaded_files_count.load(Relaxed),
+                                total_file_count
+                            ));
+                        } else {
+                            log.loading(&format!(
+                                "Loaded {}/{} semantic convention files ({} error(s) detected)",
+                                loaded_files_count.load(Relaxed),
+                                total_file_count,
+                                error_count.load(Relaxed)
+                            ));
+                        }
                     }
-                }
-                results
-            })
-            .collect();
+                    results
+                })
+                .collect()
+        });
 
         let mut errors = vec![];
         result.into_iter().for_each(|result| match result {
@@ -509,10 +862,10 @@ impl SchemaResolver {
                 errors.push(e);
             }
         });
+        let registry_manifest = Self::registry_manifest(sem_convs, &sem_conv_catalog, cache);
+        sem_conv_catalog.set_registry_manifest(registry_manifest);
 
-        // ToDo LQ: Propagate the errors!
-
-        Ok(sem_conv_catalog)
+        (sem_conv_catalog, errors)
     }
 
     /// Imports the semantic convention specifications from the given import declaration.
@@ -520,99 +873,267 @@ impl SchemaResolver {
     /// URL or a git URL (containing potentially multiple semantic convention specifications).
     fn import_sem_conv_specs(
         import_decl: &SemConvImport,
+        build_tools_compat: bool,
         cache: &Cache,
+        log: &impl Logger,
     ) -> Vec<Result<(String, SemConvSpec), Error>> {
         match import_decl {
-            SemConvImport::Url { url } => {
-                let spec = SemConvSpecs::load_sem_conv_spec_from_url(url).map_err(|e| {
-                    Error::SemConvError {
-                        message: e.to_string(),
+            SemConvImport::Url { url, mirrors } => {
+                // Only the sources that can't be fetched fall back to the
+                // mirrors, an invalid semantic convention file is reported
+                // as is.
+                let start = Instant::now();
+                let spec = Self::fetch_with_mirrors(url, mirrors, log, |source| {
+                    match SemConvSpecs::load_sem_conv_spec_from_url(source, build_tools_compat) {
+                        Err(e @ weaver_semconv::Error::CatalogNotFound { .. }) => {
+                            Err(e.to_string())
+                        }
+                        result => Ok(result),
                     }
-                });
+                })
+                .and_then(|spec| {
+                    spec.map_err(|e| Error::SemConvError {
+                        message: e.to_string(),
+                    })
+                })
+                // The provenance is the primary URL whatever the source used.
+                .map(|(_, spec)| (url.clone(), spec));
+                log.phase("load", start.elapsed());
                 vec![spec]
             }
-            SemConvImport::GitUrl { git_url, path } => {
-                fn is_hidden(entry: &DirEntry) -> bool {
-                    entry
-                        .file_name()
-                        .to_str()
-                        .map(|s| s.starts_with('.'))
-                        .unwrap_or(false)
+            SemConvImport::GitUrl {
+                git_url,
+                path,
+                mirrors,
+            } => {
+                let start = Instant::now();
+                let git_repo = match Self::fetch_with_mirrors(git_url, mirrors, log, |source| {
+                    cache
+                        .git_repo(source.to_owned(), path.clone())
+                        .map_err(|e| e.to_string())
+                }) {
+                    Ok(git_repo) => git_repo,
+                    Err(e) => return vec![Err(e)],
+                };
+                log.phase("clone", start.elapsed());
+
+                // Loads the semantic convention specifications from the git repo.
+                // All yaml files are recursively loaded from the given path.
+                let start = Instant::now();
+                let specs = Self::semantic_convention_files(&git_repo)
+                    .into_iter()
+                    .map(|file| {
+                        let file = file?;
+                        let (_, spec) =
+                            SemConvSpecs::load_sem_conv_spec_from_file(&file, build_tools_compat)
+                                .map_err(|e| Error::SemConvError {
+                                message: e.to_string(),
+                            })?;
+                        // Replace the local path with the git URL combined with the relative path
+                        // of the semantic convention file.
+                        Ok((git_provenance(git_url, &git_repo, &file), spec))
+                    })
+                    .collect();
+                log.phase("parse", start.elapsed());
+                specs
+            }
+            SemConvImport::LocalPath { local_path } => {
+                let start = Instant::now();
+                let specs = Self::local_semantic_convention_files(Path::new(local_path), log)
+                    .into_iter()
+                    .map(|file| {
+                        SemConvSpecs::load_sem_conv_spec_from_file(&file?, build_tools_compat)
+                            .map_err(|e| Error::SemConvError {
+                                message: e.to_string(),
+                            })
+                    })
+                    .collect();
+                log.phase("parse", start.elapsed());
+                specs
+            }
+        }
+    }
+
+    /// Fetches the primary `url` of an import with the given `fetch` function,
+    /// then each mirror in order until one of them succeeds. Each failed
+    /// attempt is reported as a warning when mirrors are declared, and the
+    /// returned error lists all the attempted sources.
+    pub fn fetch_with_mirrors<T>(
+        url: &str,
+        mirrors: &[String],
+        log: &impl Logger,
+        mut fetch: impl FnMut(&str) -> Result<T, String>,
+    ) -> Result<T, Error> {
+        let mut attempts = vec![];
+        for source in std::iter::once(url).chain(mirrors.iter().map(String::as_str)) {
+            match fetch(source) {
+                Ok(value) => return Ok(value),
+                Err(error) if mirrors.is_empty() => {
+                    return Err(Error::FetchFailed {
+                        url: url.to_owned(),
+                        error,
+                    })
+                }
+                Err(e) => {
+                    log.warn(&format!("Failed to fetch `{}`: {}", source, e));
+                    attempts.push(format!("- {}: {}", source, e));
                 }
-                fn is_semantic_convention_file(entry: &DirEntry) -> bool {
-                    let path = entry.path();
-                    let extension = path.extension().unwrap_or_else(|| std::ffi::OsStr::new(""));
-                    let file_name = path.file_name().unwrap_or_else(|| std::ffi::OsStr::new(""));
-                    path.is_file()
-                        && (extension == "yaml" || extension == "yml")
-                        && file_name != "schema-next.yaml"
+            }
+        }
+        Err(Error::ImportSourcesFailed { attempts })
+    }
+
+    /// Returns the semantic convention files of the given local directory (or
+    /// the given file itself), excluding the files matched by the
+    /// `.weaverignore` file of the directory (gitignore syntax) if any.
+    /// The ignored files are reported at trace level.
+    pub fn local_semantic_convention_files(
+        root: &Path,
+        log: &impl Logger,
+    ) -> Vec<Result<PathBuf, Error>> {
+        let ignore_file = root.join(WEAVER_IGNORE_FILE);
+        if !ignore_file.is_file() {
+            return Self::semantic_convention_files(root);
+        }
+
+        let invalid_ignore_file = |error: String| Error::InvalidIgnoreFile {
+            path: ignore_file.clone(),
+            error,
+        };
+        let mut builder = GitignoreBuilder::new(root);
+        if let Some(e) = builder.add(&ignore_file) {
+            return vec![Err(invalid_ignore_file(e.to_string()))];
+        }
+        let ignore = match builder.build() {
+            Ok(ignore) => ignore,
+            Err(e) => return vec![Err(invalid_ignore_file(e.to_string()))],
+        };
+
+        Self::semantic_convention_files(root)
+            .into_iter()
+            .filter(|file| match file {
+                Ok(path) if ignore.matched_path_or_any_parents(path, false).is_ignore() => {
+                    log.trace(&format!(
+                        "Ignored semantic convention file `{}` (see {})",
+                        path.display(),
+                        ignore_file.display()
+                    ));
+                    false
                 }
+                _ => true,
+            })
+            .collect()
+    }
 
-                let mut result = vec![];
-                let git_repo = cache.git_repo(git_url.clone(), path.clone()).map_err(|e| {
-                    Error::SemConvError {
-                        message: e.to_string(),
-                    }
-                });
-
-                if let Ok(git_repo) = git_repo {
-                    // Loads the semantic convention specifications from the git repo.
-                    // All yaml files are recursively loaded from the given path.
-                    for entry in walkdir::WalkDir::new(git_repo.clone())
-                        .into_iter()
-                        .filter_entry(|e| !is_hidden(e))
-                    {
-                        match entry {
-                            Ok(entry) => {
-                                if is_semantic_convention_file(&entry) {
-                                    let spec =
-                                        SemConvSpecs::load_sem_conv_spec_from_file(entry.path())
-                                            .map_err(|e| Error::SemConvError {
-                                                message: e.to_string(),
-                                            });
-                                    result.push(match spec {
-                                        Ok((path, spec)) => {
-                                            // Replace the local path with the git URL combined with the relative path
-                                            // of the semantic convention file.
-                                            let prefix = git_repo
-                                                .to_str()
-                                                .map(|s| s.to_string())
-                                                .unwrap_or_default();
-                                            let path = format!(
-                                                "{}/{}",
-                                                git_url,
-                                                &path[prefix.len() + 1..]
-                                            );
-                                            Ok((path, spec))
-                                        }
-                                        Err(e) => Err(e),
-                                    });
-                                }
-                            }
-                            Err(e) => result.push(Err(Error::SemConvError {
-                                message: e.to_string(),
-                            })),
-                        }
+    /// Returns the semantic convention files of the given directory (or the
+    /// given file itself). All the yaml and json files are recursively collected,
+    /// hidden files and directories are skipped.
+    pub fn semantic_convention_files(root: &Path) -> Vec<Result<PathBuf, Error>> {
+        fn is_hidden(entry: &DirEntry) -> bool {
+            entry.depth() > 0
+                && entry
+                    .file_name()
+                    .to_str()
+                    .map(|s| s.starts_with('.'))
+                    .unwrap_or(false)
+        }
+        fn is_semantic_convention_file(entry: &DirEntry) -> bool {
+            let path = entry.path();
+            path.is_file() && weaver_semconv::is_semconv_file(path)
+        }
+
+        let mut files = vec![];
+        for entry in walkdir::WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| !is_hidden(e))
+        {
+            match entry {
+                Ok(entry) => {
+                    if is_semantic_convention_file(&entry) {
+                        files.push(Ok(entry.into_path()));
                     }
                 }
-
-                result
+                Err(e) => files.push(Err(Error::SemConvError {
+                    message: e.to_string(),
+                })),
             }
         }
+        files
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::path::Path;
+
     use weaver_cache::Cache;
     use weaver_logger::{ConsoleLogger, Logger};
+    use weaver_schema::SemConvImport;
+    use weaver_semconv::stability::StabilityFilter;
+    use weaver_semconv::{ResolverConfig, SemConvSpecs};
 
-    use crate::SchemaResolver;
+    use crate::{Error, SchemaResolver};
+
+    #[test]
+    fn fetch_with_mirrors() {
+        let log = ConsoleLogger::new(0);
+        let mirrors = vec!["mirror-1".to_owned(), "mirror-2".to_owned()];
+
+        let mut attempted = vec![];
+        let result = SchemaResolver::fetch_with_mirrors("primary", &mirrors, &log, |source| {
+            attempted.push(source.to_owned());
+            if source == "mirror-2" {
+                Ok(source.to_owned())
+            } else {
+                Err("unreachable".to_owned())
+            }
+        });
+        assert_eq!(result.unwrap(), "mirror-2");
+        assert_eq!(attempted, vec!["primary", "mirror-1", "mirror-2"]);
+
+        let result: Result<(), Error> =
+            SchemaResolver::fetch_with_mirrors("primary", &mirrors, &log, |_| {
+                Err("unreachable".to_owned())
+            });
+        match result {
+            Err(Error::ImportSourcesFailed { attempts }) => assert_eq!(attempts.len(), 3),
+            result => panic!("Expected an aggregated error, found {:?}", result),
+        }
+
+        let result: Result<(), Error> =
+            SchemaResolver::fetch_with_mirrors("primary", &[], &log, |_| {
+                Err("unreachable".to_owned())
+            });
+        match result {
+            Err(Error::FetchFailed { url, .. }) => assert_eq!(url, "primary"),
+            result => panic!("Expected a fetch error, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn local_semantic_convention_files_with_ignore_file() {
+        let log = ConsoleLogger::new(0);
+        let files: Vec<String> = SchemaResolver::local_semantic_convention_files(
+            Path::new("data/weaverignore-registry"),
+            &log,
+        )
+        .into_iter()
+        .map(|file| {
+            file.expect("Failed to list semconv file")
+                .strip_prefix("data/weaverignore-registry")
+                .expect("Unexpected semconv file")
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+        assert_eq!(files, vec!["registry.yaml", "templates/keep.yaml"]);
+    }
 
     #[test]
     fn resolve_schema() {
         let log = ConsoleLogger::new(0);
-        let cache = Cache::try_new().unwrap_or_else(|e| {
+        let cache = Cache::try_new(None).unwrap_or_else(|e| {
             log.error(&e.to_string());
             std::process::exit(1);
         });
@@ -623,4 +1144,65 @@ mod test {
         );
         assert!(schema.is_ok(), "{:#?}", schema.err().unwrap());
     }
+
+    #[test]
+    fn resolve_registry_with_stability_filter() {
+        let log = ConsoleLogger::new(0);
+        let mut registry = SemConvSpecs::default();
+        for entry in glob::glob("data/registry-test-8-stability/registry/*.yaml")
+            .expect("Failed to read glob pattern")
+        {
+            registry
+                .load_from_file(entry.expect("Failed to read semconv file"))
+                .expect("Failed to load semconv file");
+        }
+        _ = registry
+            .resolve(
+                ResolverConfig::with_keep_specs().with_stability_filter(StabilityFilter::Stable),
+            )
+            .expect("Failed to resolve semconv specs");
+
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry, log)
+            .expect("Failed to resolve registry");
+        let groups: Vec<&str> = schema.registries[0]
+            .groups
+            .iter()
+            .map(|group| group.id.as_str())
+            .collect();
+        assert_eq!(groups, vec!["registry.db"]);
+        let attributes: Vec<&str> = schema
+            .catalog
+            .attributes
+            .iter()
+            .map(|attr| attr.name.as_str())
+            .collect();
+        assert_eq!(attributes, vec!["db.system", "db.statement"]);
+        assert_eq!(schema.registries[0].groups[0].attributes.len(), 2);
+    }
+
+    #[test]
+    fn resolve_registry_with_manifest() {
+        let log = ConsoleLogger::new(0);
+        let cache = Cache::try_new(None).expect("Failed to create the cache");
+        let mut registry = SchemaResolver::semconv_registry_from_imports(
+            &[SemConvImport::LocalPath {
+                local_path: "data/registry-test-8-stability/registry".to_owned(),
+            }],
+            ResolverConfig::default(),
+            &cache,
+            log.clone(),
+        )
+        .expect("Failed to load registry");
+
+        let schema = SchemaResolver::resolve_semantic_convention_registry(&mut registry, log)
+            .expect("Failed to resolve registry");
+        assert_eq!(schema.registry_manifest.len(), 1);
+        let manifest = &schema.registry_manifest[0];
+        assert_eq!(
+            manifest.registry_url,
+            "data/registry-test-8-stability/registry"
+        );
+        assert!(manifest.file_count > 0);
+        assert_eq!(manifest.commit, None);
+    }
 }
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Platform-independent handling of the paths of the semantic convention
+//! files, e.g. to build the provenance of a file loaded from a git repo.
+
+use std::path::{Component, Path, PathBuf, Prefix};
+
+/// Returns the provenance of a semantic convention file loaded from a git
+/// repo, i.e. the git URL followed by the path of the file relative to the
+/// repo, `/` separated whatever the platform.
+pub(crate) fn git_provenance(git_url: &str, git_repo: &Path, file: &Path) -> String {
+    let relative_path = relative_path(git_repo, file).unwrap_or_else(|| file.to_path_buf());
+    let relative_path = relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("{}/{}", git_url.trim_end_matches('/'), relative_path)
+}
+
+/// Returns the path relative to `base`, or `None` if the path is not located
+/// under `base`. Unlike [`Path::strip_prefix`], the verbatim (`\\?\`) and the
+/// regular forms of a Windows drive or UNC share are considered equal.
+pub(crate) fn relative_path(base: &Path, path: &Path) -> Option<PathBuf> {
+    if let Ok(relative_path) = path.strip_prefix(base) {
+        return Some(relative_path.to_path_buf());
+    }
+
+    let mut components = path.components();
+    for base_component in base.components() {
+        if component_key(components.next()?) != component_key(base_component) {
+            return None;
+        }
+    }
+    Some(components.as_path().to_path_buf())
+}
+
+/// Returns a key identifying a path component, the Windows drive letters and
+/// UNC shares being compared regardless of their form and case.
+fn component_key(component: Component<'_>) -> String {
+    if let Component::Prefix(prefix) = component {
+        match prefix.kind() {
+            Prefix::VerbatimDisk(disk) | Prefix::Disk(disk) => {
+                return format!("{}:", char::from(disk.to_ascii_uppercase()));
+            }
+            Prefix::VerbatimUNC(server, share) | Prefix::UNC(server, share) => {
+                return format!(
+                    r"\\{}\{}",
+                    server.to_string_lossy().to_lowercase(),
+                    share.to_string_lossy().to_lowercase()
+                );
+            }
+            _ => {}
+        }
+    }
+    component.as_os_str().to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::path::{git_provenance, relative_path};
+
+    #[test]
+    fn test_git_provenance() {
+        let git_repo = Path::new("cache").join("git").join("0123456789abcdef");
+        let file = git_repo.join("model").join("registry").join("http.yaml");
+        assert_eq!(
+            git_provenance("https://github.com/org/semconv.git", &git_repo, &file),
+            "https://github.com/org/semconv.git/model/registry/http.yaml"
+        );
+        assert_eq!(
+            git_provenance("https://github.com/org/semconv/", &git_repo, &file),
+            "https://github.com/org/semconv/model/registry/http.yaml"
+        );
+        assert_eq!(
+            relative_path(&git_repo, Path::new("elsewhere/http.yaml")),
+            None
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_paths() {
+        // Drive letters, verbatim or not.
+        assert_eq!(
+            relative_path(
+                Path::new(r"\\?\C:\Users\weaver\cache\git\repo"),
+                Path::new(r"c:\Users\weaver\cache\git\repo\model\http.yaml")
+            ),
+            Some(Path::new(r"model\http.yaml").to_path_buf())
+        );
+        // UNC shares, verbatim or not.
+        assert_eq!(
+            git_provenance(
+                "https://github.com/org/semconv.git",
+                Path::new(r"\\server\share\cache\git\repo"),
+                Path::new(r"\\?\UNC\SERVER\share\cache\git\repo\model\registry\http.yaml")
+            ),
+            "https://github.com/org/semconv.git/model/registry/http.yaml"
+        );
+        assert_eq!(
+            relative_path(
+                Path::new(r"C:\cache\git\repo"),
+                Path::new(r"D:\cache\git\repo\http.yaml")
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Thread pool loading the semantic convention files.
+//!
+//! Loading a registry is IO-bound (cloning, fetching and parsing the files)
+//! while the resolution and the code generation are CPU-bound (global rayon
+//! pool). A dedicated pool lets the number of concurrent loads be controlled
+//! independently of the CPU-bound work.
+
+use std::sync::OnceLock;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::Error;
+
+/// The thread pool loading the semantic convention files (see
+/// [`init_io_pool`]).
+static IO_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+/// Initializes the thread pool loading the semantic convention files with
+/// the given number of threads. Must be called before any loading, the files
+/// being otherwise loaded by the global rayon pool.
+pub fn init_io_pool(threads: usize) -> Result<(), Error> {
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|index| format!("weaver-io-{}", index))
+        .build()
+        .map_err(|e| Error::ThreadPoolError {
+            error: e.to_string(),
+        })?;
+    IO_POOL.set(pool).map_err(|_| Error::ThreadPoolError {
+        error: "the IO thread pool is already initialized".to_owned(),
+    })
+}
+
+/// Runs the given operation (and its parallel iterators) in the IO thread
+/// pool, or in the global rayon pool if [`init_io_pool`] has not been called.
+pub(crate) fn install_io<OP, R>(op: OP) -> R
+where
+    OP: FnOnce() -> R + Send,
+    R: Send,
+{
+    match IO_POOL.get() {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
@@ -2,23 +2,24 @@
 
 //! Functions to resolve a semantic convention registry.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use weaver_logger::Logger;
 use weaver_resolved_schema::attribute::{AttributeRef, UnresolvedAttribute};
 use weaver_resolved_schema::lineage::{FieldId, FieldLineage, GroupLineage, ResolutionMode};
 use weaver_resolved_schema::registry::{
-    Group, Registry, TypedGroup, UnresolvedGroup, UnresolvedRegistry,
+    Constraint, Group, Registry, TypedGroup, UnresolvedGroup, UnresolvedRegistry,
 };
 use weaver_semconv::attribute::AttributeSpec;
-use weaver_semconv::group::{ConvTypeSpec, GroupSpec};
+use weaver_semconv::group::{extends_cycles, ConvTypeSpec, GroupSpec};
 use weaver_semconv::{GroupSpecWithProvenance, SemConvSpecs};
 
 use crate::attribute::{resolve_attribute, AttributeCatalog};
 use crate::constraint::resolve_constraints;
+use crate::events::resolve_body;
 use crate::metrics::resolve_instrument;
-use crate::spans::resolve_span_kind;
-use crate::stability::resolve_stability;
+use crate::spans::{resolve_span_kind, resolve_span_name};
+use crate::stability::{resolve_deprecated, resolve_stability};
 use crate::{Error, UnresolvedReference};
 
 /// Creates a registry from a set of semantic convention specifications.
@@ -36,6 +37,7 @@ pub fn unresolved_registry_from_specs(url: &str, specs: &SemConvSpecs) -> Unreso
             groups: vec![],
         },
         groups,
+        stability_inheritance: specs.stability_inheritance(),
     }
 }
 
@@ -57,9 +59,11 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
                 ConvTypeSpec::Span => TypedGroup::Span {
                     span_kind: group.spec.span_kind.as_ref().map(resolve_span_kind),
                     events: group.spec.events,
+                    span_name: group.spec.span_name.as_ref().map(resolve_span_name),
                 },
                 ConvTypeSpec::Event => TypedGroup::Event {
                     name: group.spec.name,
+                    body: resolve_body(&group.spec.body),
                 },
                 ConvTypeSpec::Metric => TypedGroup::Metric {
                     metric_name: group.spec.metric_name,
@@ -75,7 +79,7 @@ fn group_from_spec(group: GroupSpecWithProvenance) -> UnresolvedGroup {
             prefix: group.spec.prefix,
             extends: group.spec.extends,
             stability: resolve_stability(&group.spec.stability),
-            deprecated: group.spec.deprecated,
+            deprecated: resolve_deprecated(&group.spec.deprecated),
             constraints: resolve_constraints(&group.spec.constraints),
             attributes: vec![],
             lineage: Some(GroupLineage::new(group.provenance.clone())),
@@ -90,8 +94,16 @@ pub fn resolve_semconv_registry(
     attr_catalog: &mut AttributeCatalog,
     url: &str,
     registry: &SemConvSpecs,
-    _log: impl Logger + Sync + Clone,
+    log: impl Logger + Sync + Clone,
 ) -> Result<Registry, Error> {
+    // The resolution of an attribute defined several times with different
+    // types depends on the load order, so the conflicting definitions are
+    // reported.
+    for warning in registry.attribute_type_conflicts() {
+        log.warn("Attribute type conflict")
+            .log(&warning.error.to_string());
+    }
+
     let groups: Result<Vec<weaver_resolved_schema::registry::Group>, Error> = registry
         .groups()
         .map(|group| semconv_to_resolved_group(registry, attr_catalog, group))
@@ -112,7 +124,18 @@ fn semconv_to_resolved_group(
     let attr_refs: Result<Vec<AttributeRef>, Error> = group
         .attributes
         .iter()
-        .map(|attr| Ok(attr_catalog.attribute_ref(resolve_attribute(registry, attr)?)))
+        .map(|attr_spec| {
+            let mut attr = resolve_attribute(registry, attr_spec)?;
+            // The attributes defined by the group are identified by their
+            // fully qualified id, as the attributes referenced by the group.
+            if matches!(attr_spec, AttributeSpec::Id { .. }) && !group.prefix.is_empty() {
+                attr.name = format!("{}.{}", group.prefix, attr.name);
+            }
+            if attr.stability.is_none() && registry.stability_inheritance() {
+                attr.stability = resolve_stability(&group.stability);
+            }
+            Ok(attr_catalog.attribute_ref(attr))
+        })
         .collect();
 
     Ok(Group {
@@ -122,9 +145,11 @@ fn semconv_to_resolved_group(
             ConvTypeSpec::Span => TypedGroup::Span {
                 span_kind: group.span_kind.as_ref().map(resolve_span_kind),
                 events: group.events.clone(),
+                span_name: group.span_name.as_ref().map(resolve_span_name),
             },
             ConvTypeSpec::Event => TypedGroup::Event {
                 name: group.name.clone(),
+                body: resolve_body(&group.body),
             },
             ConvTypeSpec::Metric => TypedGroup::Metric {
                 metric_name: group.metric_name.clone(),
@@ -140,7 +165,7 @@ fn semconv_to_resolved_group(
         prefix: group.prefix.to_string(),
         extends: group.extends.clone(),
         stability: resolve_stability(&group.stability),
-        deprecated: group.deprecated.clone(),
+        deprecated: resolve_deprecated(&group.deprecated),
         constraints: resolve_constraints(&group.constraints),
         attributes: attr_refs?,
         lineage: None,
@@ -167,16 +192,20 @@ pub fn resolve_attribute_references(
         // Iterate over all groups and resolve the attributes.
         for unresolved_group in ureg.groups.iter_mut() {
             let mut resolved_attr = vec![];
-
-            unresolved_group.attributes = unresolved_group
-                .attributes
+            let group_stability = unresolved_group
+                .group
+                .stability
                 .clone()
+                .filter(|_| ureg.stability_inheritance);
+
+            unresolved_group.attributes = std::mem::take(&mut unresolved_group.attributes)
                 .into_iter()
                 .filter_map(|attr| {
                     let attr_ref = attr_catalog.resolve(
                         &unresolved_group.group.id,
                         &unresolved_group.group.prefix,
                         &attr.spec,
+                        group_stability.as_ref(),
                         unresolved_group.group.lineage.as_mut(),
                     );
                     if let Some(attr_ref) = attr_ref {
@@ -212,8 +241,22 @@ pub fn resolve_attribute_references(
 /// `extends` references are resolved or when no `extends` reference could
 /// be resolved in an iteration.
 ///
-/// Returns true if all the `extends` references could be resolved.
-pub fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> bool {
+/// Returns an error if the `extends` chains form a cycle, or false if an
+/// `extends` reference could not be resolved.
+pub fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> Result<bool, Error> {
+    // A cycle would never be resolved, so it is reported with its full path
+    // instead of as unresolved references.
+    if let Some(group_ids) = extends_cycles(
+        ureg.groups
+            .iter()
+            .map(|group| (group.group.id.as_str(), group.group.extends.as_deref())),
+    )
+    .into_iter()
+    .next()
+    {
+        return Err(Error::CyclicExtends { group_ids });
+    }
+
     loop {
         let mut unresolved_extends_count = 0;
         let mut resolved_extends_count = 0;
@@ -263,16 +306,145 @@ pub fn resolve_extends_references(ureg: &mut UnresolvedRegistry) -> bool {
         // It means that we have an issue with the semantic convention
         // specifications.
         if resolved_extends_count == 0 {
-            return false;
+            return Ok(false);
         }
     }
-    true
+    Ok(true)
+}
+
+/// The attributes and constraints of a group once its `include` constraints
+/// are expanded.
+#[derive(Default, Clone)]
+struct IncludeExpansion {
+    /// The attributes of the group followed by the included attributes.
+    attributes: Vec<AttributeRef>,
+    /// The included attributes with the id of the included group providing
+    /// them.
+    included_attributes: Vec<(AttributeRef, String)>,
+    /// The constraints of the group followed by the included constraints.
+    constraints: Vec<Constraint>,
+}
+
+/// Expands the `include` constraints in the given registry: the attributes
+/// and the constraints of an included group (its own `include` constraints
+/// being expanded first) that are not already defined by the group are
+/// merged into the group. This step must follow the resolution of the
+/// `extends` references, so the included groups come with their inherited
+/// attributes.
+///
+/// Returns an error if the `include` constraints form a cycle, or false if
+/// an included group doesn't exist.
+pub fn resolve_include_constraints(ureg: &mut UnresolvedRegistry) -> Result<bool, Error> {
+    let group_index: HashMap<&str, usize> = ureg
+        .groups
+        .iter()
+        .enumerate()
+        .map(|(index, group)| (group.group.id.as_str(), index))
+        .collect();
+    let mut expansions = HashMap::new();
+    let mut all_includes_resolved = true;
+    for index in 0..ureg.groups.len() {
+        all_includes_resolved &= expand_includes(
+            &ureg.groups,
+            &group_index,
+            index,
+            &mut vec![],
+            &mut expansions,
+        )?;
+    }
+
+    for (index, expansion) in expansions {
+        let group = &mut ureg.groups[index].group;
+        for (attr_ref, include) in expansion.included_attributes {
+            // Note: the lineage is only updated if a group lineage is provided.
+            if let Some(lineage) = group.lineage.as_mut() {
+                lineage.add_attribute_field_lineage(
+                    attr_ref,
+                    FieldId::GroupAttributes,
+                    FieldLineage {
+                        resolution_mode: ResolutionMode::Include,
+                        group_id: include,
+                    },
+                );
+            }
+        }
+        group.attributes = expansion.attributes;
+        group.constraints = expansion.constraints;
+    }
+    Ok(all_includes_resolved)
+}
+
+/// Expands the `include` constraints of the group with the given index (and
+/// of the groups it includes), the groups being visited in depth-first order
+/// to detect the cycles. The groups of the current path are in `stack`.
+///
+/// Returns false if an included group doesn't exist.
+fn expand_includes(
+    groups: &[UnresolvedGroup],
+    group_index: &HashMap<&str, usize>,
+    index: usize,
+    stack: &mut Vec<usize>,
+    expansions: &mut HashMap<usize, IncludeExpansion>,
+) -> Result<bool, Error> {
+    if expansions.contains_key(&index) {
+        return Ok(true);
+    }
+    if let Some(start) = stack.iter().position(|visited| *visited == index) {
+        return Err(Error::CyclicInclude {
+            group_ids: stack[start..]
+                .iter()
+                .chain(std::iter::once(&index))
+                .map(|index| groups[*index].group.id.clone())
+                .collect(),
+        });
+    }
+
+    let group = &groups[index].group;
+    let mut expansion = IncludeExpansion {
+        attributes: group.attributes.clone(),
+        included_attributes: vec![],
+        constraints: group.constraints.clone(),
+    };
+    let mut all_includes_resolved = true;
+    stack.push(index);
+    for include in group
+        .constraints
+        .iter()
+        .filter_map(|constraint| constraint.include.as_ref())
+    {
+        let Some(&included_index) = group_index.get(include.as_str()) else {
+            all_includes_resolved = false;
+            continue;
+        };
+        all_includes_resolved &=
+            expand_includes(groups, group_index, included_index, stack, expansions)?;
+        let Some(included) = expansions.get(&included_index) else {
+            continue;
+        };
+        for attr_ref in included.attributes.iter() {
+            if !expansion.attributes.contains(attr_ref) {
+                expansion.attributes.push(*attr_ref);
+                expansion
+                    .included_attributes
+                    .push((*attr_ref, include.clone()));
+            }
+        }
+        for constraint in included.constraints.iter() {
+            if !expansion.constraints.contains(constraint) {
+                expansion.constraints.push(constraint.clone());
+            }
+        }
+    }
+    _ = stack.pop();
+    _ = expansions.insert(index, expansion);
+    Ok(all_includes_resolved)
 }
 
 /// Resolves the registry by resolving all groups and attributes.
 /// The resolution process consists of the following steps:
 /// - Resolve all attribute references and apply the overrides when needed.
 /// - Resolve all the `extends` references.
+/// - Expand all the `include` constraints.
 #[allow(dead_code)] // ToDo remove this once this function is called from the CLI.
 pub fn resolve_registry(
     mut ureg: UnresolvedRegistry,
@@ -281,12 +453,18 @@ pub fn resolve_registry(
     let mut all_refs_resolved = true;
 
     all_refs_resolved &= resolve_attribute_references(&mut ureg, attr_catalog);
-    all_refs_resolved &= resolve_extends_references(&mut ureg);
+    all_refs_resolved &= resolve_extends_references(&mut ureg)?;
+    all_refs_resolved &= resolve_include_constraints(&mut ureg)?;
 
     if !all_refs_resolved {
         // Process all unresolved references.
         // An Error::UnresolvedReferences is built and returned.
         let mut unresolved_refs = vec![];
+        let group_ids: HashSet<&str> = ureg
+            .groups
+            .iter()
+            .map(|group| group.group.id.as_str())
+            .collect();
         for group in ureg.groups.iter() {
             if let Some(extends) = group.group.extends.as_ref() {
                 unresolved_refs.push(UnresolvedReference::ExtendsRef {
@@ -295,6 +473,19 @@ pub fn resolve_registry(
                     provenance: group.provenance.clone(),
                 });
             }
+            for include in group
+                .group
+                .constraints
+                .iter()
+                .filter_map(|constraint| constraint.include.as_ref())
+                .filter(|include| !group_ids.contains(include.as_str()))
+            {
+                unresolved_refs.push(UnresolvedReference::IncludeRef {
+                    group_id: group.group.id.clone(),
+                    include_ref: include.clone(),
+                    provenance: group.provenance.clone(),
+                });
+            }
             for attr in group.attributes.iter() {
                 if let AttributeSpec::Ref { r#ref, .. } = &attr.spec {
                     unresolved_refs.push(UnresolvedReference::AttributeRef {
@@ -332,11 +523,13 @@ mod tests {
     use glob::glob;
 
     use weaver_resolved_schema::attribute;
+    use weaver_resolved_schema::catalog::Stability;
     use weaver_resolved_schema::registry::Registry;
-    use weaver_semconv::SemConvSpecs;
+    use weaver_semconv::{ResolverConfig, SemConvSpecs};
 
     use crate::attribute::AttributeCatalog;
     use crate::registry::{resolve_registry, unresolved_registry_from_specs};
+    use crate::Error;
 
     /// Test the resolution of semantic convention registries stored in the
     /// data directory.
@@ -426,6 +619,248 @@ mod tests {
             println!("{}", yaml);
         }
     }
+
+    /// Test that the attributes keep their own stability when the stability
+    /// inheritance is disabled.
+    #[test]
+    fn test_registry_resolution_without_stability_inheritance() {
+        let mut sc_specs = SemConvSpecs::default();
+        for sc_entry in glob("data/registry-test-8-stability/registry/*.yaml")
+            .expect("Failed to read glob pattern")
+        {
+            sc_specs
+                .load_from_file(sc_entry.expect("Failed to read semconv file"))
+                .expect("Failed to load semconv file");
+        }
+        _ = sc_specs
+            .resolve(ResolverConfig::with_keep_specs().without_stability_inheritance())
+            .expect("Failed to resolve semconv specs");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_registry(
+            unresolved_registry_from_specs("https://semconv-registry.com", &sc_specs),
+            &mut attr_catalog,
+        )
+        .expect("Failed to resolve registry");
+        let stabilities: Vec<(String, Option<Stability>)> = attr_catalog
+            .drain_attributes()
+            .into_iter()
+            .map(|attr| (attr.name, attr.stability))
+            .collect();
+        assert_eq!(
+            stabilities,
+            vec![
+                ("db.system".to_owned(), None),
+                ("db.name".to_owned(), Some(Stability::Experimental)),
+                ("db.statement".to_owned(), None),
+                ("db.cassandra.table".to_owned(), None),
+                ("db.statement".to_owned(), Some(Stability::Deprecated)),
+            ]
+        );
+        assert!(!serde_json::to_string(&registry)
+            .expect("Failed to serialize registry")
+            .contains("GroupDefault"));
+    }
+
+    /// Test that the `sampling_relevant` flag of the attributes of an event
+    /// (i.e. a log record) is propagated by the resolution, whether it is
+    /// defined by the referenced attribute or by the reference itself.
+    #[test]
+    fn test_event_sampling_relevant() {
+        let mut sc_specs = SemConvSpecs::default();
+        for sc_entry in glob("data/sampling-relevant-registry/registry/*.yaml")
+            .expect("Failed to read glob pattern")
+        {
+            sc_specs
+                .load_from_file(sc_entry.expect("Failed to read semconv file"))
+                .expect("Failed to load semconv file");
+        }
+        _ = sc_specs
+            .resolve(ResolverConfig::with_keep_specs())
+            .expect("Failed to resolve semconv specs");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_registry(
+            unresolved_registry_from_specs("https://semconv-registry.com", &sc_specs),
+            &mut attr_catalog,
+        )
+        .expect("Failed to resolve registry");
+        let catalog = attr_catalog.drain_attributes();
+        let event = registry
+            .groups
+            .iter()
+            .find(|group| group.id == "log-feature_flag")
+            .expect("Event group not found");
+        let mut sampling_relevant: Vec<(&str, Option<bool>)> = event
+            .attributes
+            .iter()
+            .map(|attr_ref| {
+                let attr = &catalog[attr_ref.0 as usize];
+                (attr.name.as_str(), attr.sampling_relevant)
+            })
+            .collect();
+        // The attributes of a resolved group are ordered by reference.
+        sampling_relevant.sort();
+        assert_eq!(
+            sampling_relevant,
+            vec![
+                ("feature_flag.key", Some(true)),
+                ("feature_flag.provider_name", Some(true)),
+            ]
+        );
+    }
+
+    /// Test that the attributes and the constraints of the included groups
+    /// are merged into the groups including them.
+    #[test]
+    fn test_include_constraints() {
+        let mut sc_specs = SemConvSpecs::default();
+        sc_specs
+            .load_from_str(
+                r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+      - id: port
+        type: int
+        brief: "Server port."
+        examples: [80]
+  - id: server
+    type: span
+    brief: "Server span."
+    attributes:
+      - ref: server.address
+    constraints:
+      - any_of:
+          - server.address
+  - id: client
+    type: span
+    brief: "Client span."
+    attributes:
+      - ref: server.port
+    constraints:
+      - include: server
+"#,
+                "include.yaml",
+            )
+            .expect("Failed to load semconv specs");
+        _ = sc_specs
+            .resolve(ResolverConfig::with_keep_specs())
+            .expect("Failed to resolve semconv specs");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let registry = resolve_registry(
+            unresolved_registry_from_specs("https://semconv-registry.com", &sc_specs),
+            &mut attr_catalog,
+        )
+        .expect("Failed to resolve registry");
+        let catalog = attr_catalog.drain_attributes();
+        let client = registry
+            .groups
+            .iter()
+            .find(|group| group.id == "client")
+            .expect("Client group not found");
+        let mut attribute_names: Vec<&str> = client
+            .attributes
+            .iter()
+            .map(|attr_ref| catalog[attr_ref.0 as usize].name.as_str())
+            .collect();
+        attribute_names.sort();
+        assert_eq!(attribute_names, vec!["server.address", "server.port"]);
+        let constraints: Vec<(Vec<String>, Option<&str>)> = client
+            .constraints
+            .iter()
+            .map(|constraint| (constraint.any_of.clone(), constraint.include.as_deref()))
+            .collect();
+        assert_eq!(
+            constraints,
+            vec![
+                (vec![], Some("server")),
+                (vec!["server.address".to_owned()], None),
+            ]
+        );
+        assert!(serde_json::to_string(&client)
+            .expect("Failed to serialize group")
+            .contains("Include"));
+    }
+
+    /// Test that the cyclic `include` constraints are reported.
+    #[test]
+    fn test_cyclic_include_constraints() {
+        let mut sc_specs = SemConvSpecs::default();
+        sc_specs
+            .load_from_str(
+                r#"groups:
+  - id: a
+    type: span
+    brief: "Span a."
+    constraints:
+      - include: b
+  - id: b
+    type: span
+    brief: "Span b."
+    constraints:
+      - include: c
+  - id: c
+    type: span
+    brief: "Span c."
+    constraints:
+      - include: b
+"#,
+                "cycle.yaml",
+            )
+            .expect("Failed to load semconv specs");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_registry(
+            unresolved_registry_from_specs("https://semconv-registry.com", &sc_specs),
+            &mut attr_catalog,
+        );
+        match result {
+            Err(Error::CyclicInclude { group_ids }) => {
+                assert_eq!(group_ids, vec!["b", "c", "b"]);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_cyclic_extends() {
+        let mut sc_specs = SemConvSpecs::default();
+        sc_specs
+            .load_from_str(
+                r#"groups:
+  - id: a
+    type: span
+    brief: "Span a."
+    extends: b
+  - id: b
+    type: span
+    brief: "Span b."
+    extends: a
+"#,
+                "cycle.yaml",
+            )
+            .expect("Failed to load semconv specs");
+
+        let mut attr_catalog = AttributeCatalog::default();
+        let result = resolve_registry(
+            unresolved_registry_from_specs("https://semconv-registry.com", &sc_specs),
+            &mut attr_catalog,
+        );
+        match result {
+            Err(Error::CyclicExtends { group_ids }) => {
+                assert_eq!(group_ids, vec!["a", "b", "a"]);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
 }
 
 // ToDo Remove #[allow(dead_code)] once the corresponding functions are called from the CLI.
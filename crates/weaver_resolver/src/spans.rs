@@ -4,8 +4,9 @@
 
 use crate::attribute::resolve_attributes;
 use crate::Error;
+use weaver_schema::attribute::Attribute;
 use weaver_schema::schema_spec::SchemaSpec;
-use weaver_semconv::group::SpanKindSpec;
+use weaver_semconv::group::{SpanKindSpec, SpanNameSpec};
 use weaver_semconv::SemConvSpecs;
 use weaver_version::VersionChanges;
 
@@ -22,6 +23,16 @@ pub fn resolve_spans(
             version_changes.span_attribute_changes(),
         )?;
         for span in spans.spans.iter_mut() {
+            // Without its own guidance, a span inherits the span name
+            // guidance of the first referenced span group defining one.
+            if span.span_name_guidance.is_none() {
+                span.span_name_guidance = span.attributes.iter().find_map(|attr| match attr {
+                    Attribute::SpanRef { span_ref, .. } => {
+                        sem_conv_catalog.span_name(span_ref).cloned()
+                    }
+                    _ => None,
+                });
+            }
             span.attributes = resolve_attributes(
                 span.attributes.as_ref(),
                 sem_conv_catalog,
@@ -56,3 +67,11 @@ pub fn resolve_span_kind(span_kind: &SpanKindSpec) -> weaver_resolved_schema::si
         SpanKindSpec::Server => weaver_resolved_schema::signal::SpanKind::Server,
     }
 }
+
+/// Resolve a span name guidance.
+pub fn resolve_span_name(span_name: &SpanNameSpec) -> weaver_resolved_schema::signal::SpanName {
+    weaver_resolved_schema::signal::SpanName {
+        format: span_name.format.clone(),
+        examples: span_name.examples.clone(),
+    }
+}
@@ -1,15 +1,175 @@
 // SPDX-License-Identifier: Apache-2.0
 
-//! Functions to resolve a semantic convention stability field.
+//! Functions to resolve the stability and deprecation fields of a semantic
+//! convention and to filter the resolved definitions by stability.
 
-use weaver_semconv::stability::StabilitySpec;
+use std::collections::{HashMap, HashSet};
 
-pub fn resolve_stability(
-    stability: &Option<StabilitySpec>,
-) -> Option<weaver_resolved_schema::catalog::Stability> {
+use weaver_resolved_schema::attribute::AttributeRef;
+use weaver_resolved_schema::catalog::{Deprecated, Stability, StructuredDeprecation};
+use weaver_resolved_schema::registry::TypedGroup;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_schema::attribute::Attribute;
+use weaver_schema::univariate_metric::UnivariateMetric;
+use weaver_schema::TelemetrySchema;
+use weaver_semconv::deprecated::{DeprecatedSpec, StructuredDeprecationSpec};
+use weaver_semconv::stability::{StabilityFilter, StabilitySpec};
+
+pub fn resolve_stability(stability: &Option<StabilitySpec>) -> Option<Stability> {
     stability.as_ref().map(|stability| match stability {
-        StabilitySpec::Deprecated => weaver_resolved_schema::catalog::Stability::Deprecated,
-        StabilitySpec::Experimental => weaver_resolved_schema::catalog::Stability::Experimental,
-        StabilitySpec::Stable => weaver_resolved_schema::catalog::Stability::Stable,
+        StabilitySpec::Deprecated => Stability::Deprecated,
+        StabilitySpec::Experimental => Stability::Experimental,
+        StabilitySpec::ReleaseCandidate => Stability::ReleaseCandidate,
+        StabilitySpec::Stable => Stability::Stable,
+        StabilitySpec::Other(stability) => Stability::Other(stability.clone()),
     })
 }
+
+/// Converts a resolved stability back to a stability specification.
+pub fn stability_spec(stability: &Stability) -> StabilitySpec {
+    match stability {
+        Stability::Deprecated => StabilitySpec::Deprecated,
+        Stability::Experimental => StabilitySpec::Experimental,
+        Stability::ReleaseCandidate => StabilitySpec::ReleaseCandidate,
+        Stability::Stable => StabilitySpec::Stable,
+        Stability::Other(stability) => StabilitySpec::Other(stability.clone()),
+    }
+}
+
+/// Resolves a deprecation specification.
+pub fn resolve_deprecated(deprecated: &Option<DeprecatedSpec>) -> Option<Deprecated> {
+    deprecated.as_ref().map(|deprecated| match deprecated {
+        DeprecatedSpec::Note(note) => Deprecated::Note(note.clone()),
+        DeprecatedSpec::Structured(deprecation) => Deprecated::Structured(StructuredDeprecation {
+            reason: deprecation.reason.clone(),
+            renamed_to: deprecation.renamed_to.clone(),
+            removed_in: deprecation.removed_in.clone(),
+        }),
+    })
+}
+
+/// Converts a resolved deprecation back to a deprecation specification.
+pub fn deprecated_spec(deprecated: &Deprecated) -> DeprecatedSpec {
+    match deprecated {
+        Deprecated::Note(note) => DeprecatedSpec::Note(note.clone()),
+        Deprecated::Structured(deprecation) => {
+            DeprecatedSpec::Structured(StructuredDeprecationSpec {
+                reason: deprecation.reason.clone(),
+                renamed_to: deprecation.renamed_to.clone(),
+                removed_in: deprecation.removed_in.clone(),
+            })
+        }
+    }
+}
+
+/// Returns true if a resolved definition with the given stability is kept by
+/// the filter.
+fn accepts(filter: StabilityFilter, stability: &Option<Stability>) -> bool {
+    let stability = stability.as_ref().map(stability_spec);
+    filter.accepts(stability.as_ref())
+}
+
+/// Removes from a resolved registry the groups and attributes below the
+/// stability of the filter. The catalog only keeps the attributes still
+/// referenced by a group and the metrics still defined by a group.
+pub fn filter_resolved_schema(schema: &mut ResolvedTelemetrySchema, filter: StabilityFilter) {
+    if filter == StabilityFilter::All {
+        return;
+    }
+
+    let attributes = std::mem::take(&mut schema.catalog.attributes);
+    let mut new_refs: HashMap<u32, AttributeRef> = HashMap::new();
+    let mut removed_metrics = HashSet::new();
+    let mut kept_metrics = HashSet::new();
+    for registry in schema.registries.iter_mut() {
+        registry.groups.retain(|group| {
+            let kept = accepts(filter, &group.stability);
+            if let TypedGroup::Metric {
+                metric_name: Some(metric_name),
+                ..
+            } = &group.typed_group
+            {
+                if kept {
+                    _ = kept_metrics.insert(metric_name.clone());
+                } else {
+                    _ = removed_metrics.insert(metric_name.clone());
+                }
+            }
+            kept
+        });
+
+        for group in registry.groups.iter_mut() {
+            group.attributes = group
+                .attributes
+                .iter()
+                .filter(|attr_ref| accepts(filter, &attributes[attr_ref.0 as usize].stability))
+                .map(|attr_ref| {
+                    *new_refs.entry(attr_ref.0).or_insert_with(|| {
+                        schema
+                            .catalog
+                            .attributes
+                            .push(attributes[attr_ref.0 as usize].clone());
+                        AttributeRef((schema.catalog.attributes.len() - 1) as u32)
+                    })
+                })
+                .collect();
+        }
+    }
+
+    schema.catalog.metrics.retain(|metric| {
+        kept_metrics.contains(&metric.name) || !removed_metrics.contains(&metric.name)
+    });
+}
+
+/// Removes from a resolved telemetry schema the attributes below the
+/// stability of the filter.
+/// Note: the metrics, events and spans of a telemetry schema don't define a
+/// stability, so they are all kept.
+pub fn filter_schema_attributes(schema: &mut TelemetrySchema, filter: StabilityFilter) {
+    if filter == StabilityFilter::All {
+        return;
+    }
+    let Some(schema) = schema.schema.as_mut() else {
+        return;
+    };
+
+    let retain = |attributes: &mut Vec<Attribute>| {
+        attributes.retain(|attr| match attr {
+            Attribute::Id { stability, .. } => filter.accepts(stability.as_ref()),
+            _ => true,
+        })
+    };
+    if let Some(resource) = schema.resource.as_mut() {
+        retain(&mut resource.attributes);
+    }
+    if let Some(resource_metrics) = schema.resource_metrics.as_mut() {
+        retain(&mut resource_metrics.attributes);
+        for metric in resource_metrics.metrics.iter_mut() {
+            match metric {
+                UnivariateMetric::Ref { attributes, .. }
+                | UnivariateMetric::Metric { attributes, .. } => retain(attributes),
+            }
+        }
+        for metric_group in resource_metrics.metric_groups.iter_mut() {
+            retain(&mut metric_group.attributes);
+        }
+    }
+    if let Some(resource_events) = schema.resource_events.as_mut() {
+        retain(&mut resource_events.attributes);
+        for event in resource_events.events.iter_mut() {
+            retain(&mut event.attributes);
+        }
+    }
+    if let Some(resource_spans) = schema.resource_spans.as_mut() {
+        retain(&mut resource_spans.attributes);
+        for span in resource_spans.spans.iter_mut() {
+            retain(&mut span.attributes);
+            for event in span.events.iter_mut() {
+                retain(&mut event.attributes);
+            }
+            for link in span.links.iter_mut() {
+                retain(&mut link.attributes);
+            }
+        }
+    }
+}
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Usages of an attribute by the groups of a semantic convention registry and
+//! by the signals of a telemetry schema, e.g. to assess the impact of the
+//! deprecation or the rename of an attribute.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+use weaver_resolved_schema::attribute::RequirementLevel;
+use weaver_resolved_schema::lineage::FieldId;
+use weaver_schema::attribute::Attribute;
+use weaver_schema::metric_group::Metric;
+use weaver_schema::univariate_metric::UnivariateMetric;
+use weaver_schema::TelemetrySchema;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::{GroupSpecWithProvenance, SemConvSpecs};
+
+use crate::attribute::AttributeCatalog;
+use crate::explain::overridden_fields;
+use crate::registry::{resolve_registry, unresolved_registry_from_specs};
+use crate::Error;
+
+/// How an attribute is used by a group or a signal.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageKind {
+    /// The attribute is defined by the group (or the signal) itself.
+    Definition,
+    /// The attribute is referenced with `ref`.
+    Reference,
+    /// The attribute is inherited from the group extended with `extends`.
+    Extends,
+    /// The attribute is used through a reference to a registry group using
+    /// it (`attribute_group_ref`, `span_ref`, `resource_ref`, `event_ref`).
+    GroupReference,
+}
+
+/// A usage of an attribute by a registry group or a schema signal.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct AttributeUsage {
+    /// The type of the registry group, or the schema signal (resource, span,
+    /// span_event, span_link, event, metric or metric_group).
+    pub kind: String,
+    /// The id of the group or the name of the signal (empty for the
+    /// attributes shared by all the signals of a type).
+    pub id: String,
+    /// The provenance (path or URL) of the registry group.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<String>,
+    /// How the attribute is used.
+    pub usage: UsageKind,
+    /// The registry group through which the attribute is used (extended or
+    /// referenced group).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub via: Option<String>,
+    /// The requirement level of the attribute in this usage (none when
+    /// inherited from the registry by a schema signal).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirement_level: Option<String>,
+    /// The fields of the attribute redefined by this usage.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overridden_fields: Vec<FieldId>,
+}
+
+/// The usages of an attribute.
+#[derive(Serialize, Debug, Clone)]
+pub struct AttributeUsages {
+    /// The attribute id.
+    pub attribute_id: String,
+    /// The usages by the groups of the semantic convention registry.
+    pub registry: Vec<AttributeUsage>,
+    /// The usages by the signals of the telemetry schema (if any).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schema: Vec<AttributeUsage>,
+}
+
+/// Returns the usages of the attribute `attribute_id` by the groups of the
+/// given semantic convention registry and, optionally, by the signals of a
+/// telemetry schema (the schema is expected to be unresolved so that its
+/// references are reported as such).
+pub fn find_usages(
+    specs: &SemConvSpecs,
+    attribute_id: &str,
+    schema: Option<&TelemetrySchema>,
+) -> Result<AttributeUsages, Error> {
+    let group_specs: HashMap<String, GroupSpecWithProvenance> = specs
+        .groups_with_provenance()
+        .map(|group| (group.spec.id.clone(), group))
+        .collect();
+    let mut attr_catalog = AttributeCatalog::default();
+    let registry = resolve_registry(unresolved_registry_from_specs("", specs), &mut attr_catalog)?;
+    let attributes = attr_catalog.drain_attributes();
+
+    let mut registry_usages = vec![];
+    for group in registry.groups.iter() {
+        let Some(attr) = group
+            .attributes
+            .iter()
+            .filter_map(|attr_ref| attributes.get(attr_ref.0 as usize))
+            .find(|attr| attr.name == attribute_id)
+        else {
+            continue;
+        };
+        let Some(group_spec) = group_specs.get(&group.id) else {
+            continue;
+        };
+        let spec = &group_spec.spec;
+        let attr_spec = spec.attributes.iter().find(|attr_spec| match attr_spec {
+            AttributeSpec::Ref { r#ref, .. } => r#ref == attribute_id,
+            AttributeSpec::Id { id, .. } if spec.prefix.is_empty() => id == attribute_id,
+            AttributeSpec::Id { id, .. } => format!("{}.{}", spec.prefix, id) == attribute_id,
+        });
+        let (usage, via) = match attr_spec {
+            Some(AttributeSpec::Id { .. }) => (UsageKind::Definition, None),
+            Some(AttributeSpec::Ref { .. }) => (UsageKind::Reference, None),
+            None => (UsageKind::Extends, spec.extends.clone()),
+        };
+
+        registry_usages.push(AttributeUsage {
+            kind: serde_json::to_value(&spec.r#type)
+                .ok()
+                .and_then(|kind| kind.as_str().map(str::to_owned))
+                .unwrap_or_default(),
+            id: group.id.clone(),
+            provenance: Some(group_spec.provenance.clone()),
+            usage,
+            via,
+            requirement_level: Some(requirement_level_text(&attr.requirement_level)),
+            overridden_fields: attr_spec.map(overridden_fields).unwrap_or_default(),
+        });
+    }
+    if registry_usages.is_empty() {
+        return Err(Error::AttributeNotFound {
+            attribute_id: attribute_id.to_owned(),
+        });
+    }
+
+    let using_groups: HashSet<&str> = registry_usages
+        .iter()
+        .map(|usage| usage.id.as_str())
+        .collect();
+    let schema_usages = schema
+        .map(|schema| schema_usages(schema, attribute_id, &using_groups))
+        .unwrap_or_default();
+
+    Ok(AttributeUsages {
+        attribute_id: attribute_id.to_owned(),
+        registry: registry_usages,
+        schema: schema_usages,
+    })
+}
+
+/// Returns the usages of an attribute by the signals of a telemetry schema.
+fn schema_usages(
+    schema: &TelemetrySchema,
+    attribute_id: &str,
+    using_groups: &HashSet<&str>,
+) -> Vec<AttributeUsage> {
+    let mut usages = vec![];
+    let mut signal = |kind: &str, id: &str, attributes: &[Attribute]| {
+        usages.extend(
+            attributes
+                .iter()
+                .filter_map(|attr| signal_usage(kind, id, attr, attribute_id, using_groups)),
+        );
+    };
+    let Some(spec) = schema.schema.as_ref() else {
+        return vec![];
+    };
+
+    if let Some(resource) = spec.resource.as_ref() {
+        signal("resource", "", &resource.attributes);
+    }
+    if let Some(resource_metrics) = spec.resource_metrics.as_ref() {
+        signal("metric", "", &resource_metrics.attributes);
+        for metric in resource_metrics.metrics.iter() {
+            let attributes = match metric {
+                UnivariateMetric::Ref { attributes, .. }
+                | UnivariateMetric::Metric { attributes, .. } => attributes,
+            };
+            signal("metric", &metric.name(), attributes);
+        }
+        for metric_group in resource_metrics.metric_groups.iter() {
+            signal("metric_group", &metric_group.name, &metric_group.attributes);
+            for metric in metric_group.metrics.iter() {
+                if let Metric::Metric { attributes, .. } = metric {
+                    signal("metric_group", &metric_group.name, attributes);
+                }
+            }
+        }
+    }
+    if let Some(resource_events) = spec.resource_events.as_ref() {
+        signal("event", "", &resource_events.attributes);
+        for event in resource_events.events.iter() {
+            signal("event", &event.event_name, &event.attributes);
+        }
+    }
+    if let Some(resource_spans) = spec.resource_spans.as_ref() {
+        signal("span", "", &resource_spans.attributes);
+        for span in resource_spans.spans.iter() {
+            signal("span", &span.span_name, &span.attributes);
+            for event in span.events.iter() {
+                signal("span_event", &event.event_name, &event.attributes);
+            }
+            for link in span.links.iter() {
+                signal("span_link", &link.link_name, &link.attributes);
+            }
+        }
+    }
+    usages
+}
+
+/// Returns the usage of an attribute by an attribute of the schema signal
+/// `kind`/`id`, or `None` if the attribute is not used.
+fn signal_usage(
+    kind: &str,
+    id: &str,
+    attr: &Attribute,
+    attribute_id: &str,
+    using_groups: &HashSet<&str>,
+) -> Option<AttributeUsage> {
+    let usage_of = |usage, via, requirement_level, overridden_fields| AttributeUsage {
+        kind: kind.to_owned(),
+        id: id.to_owned(),
+        provenance: None,
+        usage,
+        via,
+        requirement_level,
+        overridden_fields,
+    };
+    match attr {
+        Attribute::Id {
+            id,
+            requirement_level,
+            ..
+        } if id == attribute_id => Some(usage_of(
+            UsageKind::Definition,
+            None,
+            Some(requirement_level.to_string()),
+            vec![],
+        )),
+        Attribute::Ref {
+            r#ref,
+            brief,
+            examples,
+            tag,
+            requirement_level,
+            sampling_relevant,
+            note,
+            stability,
+            deprecated,
+            ..
+        } if r#ref == attribute_id => {
+            let overrides = [
+                (brief.is_some(), FieldId::AttributeBrief),
+                (examples.is_some(), FieldId::AttributeExamples),
+                (tag.is_some(), FieldId::AttributeTag),
+                (
+                    requirement_level.is_some(),
+                    FieldId::AttributeRequirementLevel,
+                ),
+                (
+                    sampling_relevant.is_some(),
+                    FieldId::AttributeSamplingRelevant,
+                ),
+                (note.is_some(), FieldId::AttributeNote),
+                (stability.is_some(), FieldId::AttributeStability),
+                (deprecated.is_some(), FieldId::AttributeDeprecated),
+            ];
+            Some(usage_of(
+                UsageKind::Reference,
+                None,
+                requirement_level.as_ref().map(ToString::to_string),
+                overrides
+                    .into_iter()
+                    .filter_map(|(is_set, field_id)| is_set.then_some(field_id))
+                    .collect(),
+            ))
+        }
+        Attribute::AttributeGroupRef {
+            attribute_group_ref: group_id,
+            ..
+        }
+        | Attribute::SpanRef {
+            span_ref: group_id, ..
+        }
+        | Attribute::ResourceRef {
+            resource_ref: group_id,
+            ..
+        }
+        | Attribute::EventRef {
+            event_ref: group_id,
+            ..
+        } if using_groups.contains(group_id.as_str()) => Some(usage_of(
+            UsageKind::GroupReference,
+            Some(group_id.clone()),
+            None,
+            vec![],
+        )),
+        _ => None,
+    }
+}
+
+/// Returns a human readable requirement level (same format as the
+/// requirement levels of the semantic convention specs).
+fn requirement_level_text(requirement_level: &RequirementLevel) -> String {
+    match requirement_level {
+        RequirementLevel::Required => "required".to_owned(),
+        RequirementLevel::Recommended { text: None } => "recommended".to_owned(),
+        RequirementLevel::Recommended { text: Some(text) } => format!("recommended ({})", text),
+        RequirementLevel::OptIn => "opt-in".to_owned(),
+        RequirementLevel::ConditionallyRequired { text } => {
+            format!("conditionally required (condition: {})", text)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_resolved_schema::lineage::FieldId;
+    use weaver_semconv::SemConvSpecs;
+
+    use crate::usages::{find_usages, UsageKind};
+    use crate::Error;
+
+    #[test]
+    fn test_find_usages() {
+        let mut specs = SemConvSpecs::default();
+        for file in glob::glob("data/registry-test-3-extends/registry/*.yaml").unwrap() {
+            specs.load_from_file(file.unwrap()).unwrap();
+        }
+
+        let usages = find_usages(&specs, "url.scheme", None).unwrap();
+        let definition = usages
+            .registry
+            .iter()
+            .find(|usage| usage.usage == UsageKind::Definition)
+            .expect("Expected the definition of `url.scheme`");
+        assert_eq!(definition.id, "registry.url");
+        assert_eq!(definition.kind, "attribute_group");
+        let reference = usages
+            .registry
+            .iter()
+            .find(|usage| usage.id == "attributes.http.client")
+            .expect("Expected a reference to `url.scheme`");
+        assert_eq!(reference.usage, UsageKind::Reference);
+        assert!(reference
+            .overridden_fields
+            .contains(&FieldId::AttributeExamples));
+        assert!(usages.schema.is_empty());
+
+        assert!(matches!(
+            find_usages(&specs, "unknown.attribute", None),
+            Err(Error::AttributeNotFound { .. })
+        ));
+    }
+}
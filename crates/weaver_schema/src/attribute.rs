@@ -4,16 +4,18 @@
 
 //! Definition of an attribute in the context of a telemetry schema.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use weaver_semconv::attribute::{AttributeTypeSpec, ExamplesSpec, RequirementLevelSpec, ValueSpec};
+use weaver_semconv::deprecated::DeprecatedSpec;
 use weaver_semconv::stability::StabilitySpec;
 
 use crate::tags::Tags;
 use crate::Error;
 
 /// An attribute specification.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 #[serde(rename_all = "snake_case")]
@@ -52,7 +54,9 @@ pub enum Attribute {
         #[serde(skip_serializing_if = "Option::is_none")]
         requirement_level: Option<RequirementLevelSpec>,
         /// Specifies if the attribute is (especially) relevant for sampling
-        /// and thus should be set at span start. It defaults to false.
+        /// and thus should be set at span start, or when the event or log
+
+        /// record is created. It defaults to false.
         /// Note: this field is experimental.
         #[serde(skip_serializing_if = "Option::is_none")]
         sampling_relevant: Option<bool>,
@@ -68,11 +72,12 @@ pub enum Attribute {
         /// error.
         #[serde(skip_serializing_if = "Option::is_none")]
         stability: Option<StabilitySpec>,
-        /// Specifies if the attribute is deprecated. The string
-        /// provided as <description> MUST specify why it's deprecated and/or what
-        /// to use instead. See also stability.
+        /// Specifies if the attribute is deprecated, either with a note which
+        /// MUST specify why it's deprecated and/or what to use instead, or with
+        /// a structured deprecation (reason, `renamed_to`, `removed_in`). See
+        /// also stability.
         #[serde(skip_serializing_if = "Option::is_none")]
-        deprecated: Option<String>,
+        deprecated: Option<DeprecatedSpec>,
         /// A set of tags for the attribute.
         #[serde(skip_serializing_if = "Option::is_none")]
         tags: Option<Tags>,
@@ -154,7 +159,9 @@ pub enum Attribute {
         #[serde(default)]
         requirement_level: RequirementLevelSpec,
         /// Specifies if the attribute is (especially) relevant for sampling
-        /// and thus should be set at span start. It defaults to false.
+        /// and thus should be set at span start, or when the event or log
+
+        /// record is created. It defaults to false.
         /// Note: this field is experimental.
         #[serde(skip_serializing_if = "Option::is_none")]
         sampling_relevant: Option<bool>,
@@ -169,11 +176,12 @@ pub enum Attribute {
         /// error.
         #[serde(skip_serializing_if = "Option::is_none")]
         stability: Option<StabilitySpec>,
-        /// Specifies if the attribute is deprecated. The string
-        /// provided as <description> MUST specify why it's deprecated and/or what
-        /// to use instead. See also stability.
+        /// Specifies if the attribute is deprecated, either with a note which
+        /// MUST specify why it's deprecated and/or what to use instead, or with
+        /// a structured deprecation (reason, `renamed_to`, `removed_in`). See
+        /// also stability.
         #[serde(skip_serializing_if = "Option::is_none")]
-        deprecated: Option<String>,
+        deprecated: Option<DeprecatedSpec>,
         /// A set of tags for the attribute.
         #[serde(skip_serializing_if = "Option::is_none")]
         tags: Option<Tags>,
@@ -199,6 +207,7 @@ impl From<&weaver_semconv::attribute::AttributeSpec> for Attribute {
                 note,
                 stability,
                 deprecated,
+                ..
             } => Attribute::Ref {
                 r#ref,
                 brief,
@@ -223,6 +232,7 @@ impl From<&weaver_semconv::attribute::AttributeSpec> for Attribute {
                 note,
                 stability,
                 deprecated,
+                ..
             } => Attribute::Id {
                 id,
                 r#type,
@@ -323,6 +333,7 @@ impl Attribute {
                     note,
                     stability,
                     deprecated,
+                    ..
                 }) = sem_conv_attr
                 {
                     let id = id.clone();
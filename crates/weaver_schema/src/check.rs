@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks applied to the metrics defined locally by a telemetry schema (i.e.
+//! not referencing a semantic convention metric). These metrics are checked
+//! with the name, instrument and unit rules applied to the metrics of a
+//! semantic convention registry (see [`weaver_semconv::check`]).
+
+use std::fmt::{Display, Formatter};
+
+use weaver_semconv::check::{check_metric, Severity};
+
+use crate::metric_group::Metric;
+use crate::univariate_metric::UnivariateMetric;
+use crate::TelemetrySchema;
+
+/// A finding reported on a metric locally defined by a telemetry schema.
+#[derive(Debug, Clone)]
+pub struct MetricFinding {
+    /// The id of the rule that reported the finding.
+    pub rule_id: &'static str,
+    /// The severity of the finding.
+    pub severity: Severity,
+    /// The name of the metric concerned by the finding.
+    pub metric_name: String,
+    /// A human readable description of the finding.
+    pub message: String,
+}
+
+impl Display for MetricFinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} [{}] metric `{}`: {}",
+            self.severity, self.rule_id, self.metric_name, self.message
+        )
+    }
+}
+
+impl TelemetrySchema {
+    /// Checks the univariate metrics and the metrics of the metric groups
+    /// locally defined by the schema and returns the findings.
+    /// The schema is expected to be unresolved, as the resolution replaces the
+    /// references to the semantic convention metrics by their definition.
+    pub fn check_metrics(&self) -> Vec<MetricFinding> {
+        let Some(resource_metrics) = self
+            .schema
+            .as_ref()
+            .and_then(|schema| schema.resource_metrics.as_ref())
+        else {
+            return vec![];
+        };
+
+        let local_metrics = resource_metrics
+            .metrics
+            .iter()
+            .filter_map(|metric| match metric {
+                UnivariateMetric::Metric {
+                    name,
+                    instrument,
+                    unit,
+                    ..
+                } => Some((name, instrument, unit)),
+                UnivariateMetric::Ref { .. } => None,
+            })
+            .chain(
+                resource_metrics
+                    .metric_groups
+                    .iter()
+                    .flat_map(|metric_group| metric_group.metrics.iter())
+                    .filter_map(|metric| match metric {
+                        Metric::Metric {
+                            name,
+                            instrument,
+                            unit,
+                            ..
+                        } => Some((name, instrument, unit)),
+                        Metric::Ref { .. } => None,
+                    }),
+            );
+
+        local_metrics
+            .flat_map(|(name, instrument, unit)| {
+                check_metric(name, instrument, unit.as_deref())
+                    .into_iter()
+                    .map(|(rule_id, severity, message)| MetricFinding {
+                        rule_id,
+                        severity,
+                        metric_name: name.clone(),
+                        message,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TelemetrySchema;
+
+    #[test]
+    fn test_check_metrics() {
+        let schema = TelemetrySchema::load_from_file("data/metric-checks.yaml").unwrap();
+        let findings: Vec<(String, &'static str)> = schema
+            .check_metrics()
+            .into_iter()
+            .map(|finding| (finding.metric_name, finding.rule_id))
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                ("jobs.queue.usage".to_owned(), "metric-counter-monotonic"),
+                ("jobs.queue.usage".to_owned(), "metric-unit-count"),
+                ("jobs.duration".to_owned(), "metric-unit-duration"),
+                ("Jobs".to_owned(), "metric-name-namespace"),
+            ]
+        );
+    }
+}
@@ -4,10 +4,12 @@
 
 use crate::attribute::Attribute;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use weaver_semconv::group::BodyFieldSpec;
 
 /// An event specification.
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Event {
     /// The name of the event.
@@ -18,6 +20,12 @@ pub struct Event {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub attributes: Vec<Attribute>,
+    /// The fields of the body of the log record. Without its own body, an
+    /// event inherits the body of the semantic convention event with the
+    /// same name during the resolution.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<BodyFieldSpec>,
     /// Brief description of the event.
     pub brief: Option<String>,
     /// Longer description.
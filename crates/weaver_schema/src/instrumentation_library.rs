@@ -3,20 +3,28 @@
 //! Instrumentation library specification.
 
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-/// An instrumentation library specification.
+/// An instrumentation library specification, i.e. the instrumentation scope
+/// of the tracers and meters created by the generated SDKs.
 /// MUST be used both by applications and libraries.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct InstrumentationLibrary {
     /// An optional name for the instrumentation library.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
-    /// An optional version for the instrumentation library.
+    /// An optional version for the instrumentation library. When the schema
+    /// URL ends with a version, the version of the instrumentation library
+    /// must be the same.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
+    /// An optional schema URL for the instrumentation library. Once
+    /// resolved, it defaults to the schema URL of the telemetry schema.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_url: Option<String>,
     /// A set of tags for the instrumentation library.
     #[serde(skip_serializing_if = "Option::is_none")]
-    tags: Option<Tags>,
+    pub tags: Option<Tags>,
 }
@@ -12,11 +12,12 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use weaver_semconv::SemConvSpecs;
-use weaver_version::Versions;
+use weaver_semconv::{SemConvSpecWithProvenance, SemConvSpecs};
+use weaver_version::{VersionChanges, Versions};
 
 use crate::event::Event;
 use crate::metric_group::MetricGroup;
@@ -24,18 +25,24 @@ use crate::schema_spec::SchemaSpec;
 use crate::span::Span;
 
 pub mod attribute;
+pub mod check;
 pub mod event;
 pub mod instrumentation_library;
 pub mod log;
+pub mod merge;
 pub mod metric_group;
+pub mod requirements;
 pub mod resource;
 pub mod resource_events;
 pub mod resource_metrics;
 pub mod resource_spans;
 pub mod schema_spec;
+pub mod service;
 pub mod span;
 pub mod span_event;
+pub mod span_kind;
 pub mod span_link;
+pub mod stats;
 pub mod tags;
 pub mod univariate_metric;
 
@@ -72,10 +79,36 @@ pub enum Error {
         /// The error that occurred.
         error: String,
     },
+
+    /// The merged telemetry schemas define the same signals or attributes
+    /// differently.
+    #[error("Conflicting definitions in the merged schemas:\n{}", conflicts.join("\n"))]
+    MergeConflicts {
+        /// The conflicts, one per conflicting signal or attribute.
+        conflicts: Vec<String>,
+    },
+
+    /// The span kind rules file is invalid.
+    #[error("Invalid span kind rules {path:?}\n{error:?}")]
+    InvalidSpanKindRules {
+        /// The path of the span kind rules file.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
+
+    /// The service metadata policy file is invalid.
+    #[error("Invalid service metadata policy {path:?}\n{error:?}")]
+    InvalidServicePolicy {
+        /// The path of the service metadata policy file.
+        path: String,
+        /// The error that occurred.
+        error: String,
+    },
 }
 
 /// A telemetry schema.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct TelemetrySchema {
     /// Defines the file format. MUST be set to 1.2.0.
@@ -113,10 +146,16 @@ pub struct TelemetrySchema {
     /// (if resolved).
     #[serde(skip)]
     pub semantic_convention_registry: SemConvSpecs,
+
+    /// The semantic conventions of the schema when it is loaded from a
+    /// resolved schema artifact (i.e. its resolved registry converted back to
+    /// specs). They are used in place of the imports.
+    #[serde(skip)]
+    pub resolved_semantic_conventions: Vec<SemConvSpecWithProvenance>,
 }
 
 /// A semantic convention import.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 pub enum SemConvImport {
@@ -124,6 +163,11 @@ pub enum SemConvImport {
     Url {
         /// The URL of the semantic convention.
         url: String,
+        /// Mirror URLs of the semantic convention, tried in order when the
+        /// primary URL can't be fetched.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        mirrors: Vec<String>,
     },
     /// Variant to import semantic conventions from a git repo.
     GitUrl {
@@ -132,6 +176,18 @@ pub enum SemConvImport {
         /// An optional path to the semantic convention directory containing
         /// the semantic convention files.
         path: Option<String>,
+        /// Mirror git URLs of the semantic convention git repo, tried in order
+        /// when the primary git URL can't be fetched.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        mirrors: Vec<String>,
+    },
+    /// Variant to import semantic conventions from a local directory (or a
+    /// single local file).
+    /// A relative path is relative to the telemetry schema file.
+    LocalPath {
+        /// The local path of the semantic convention directory or file.
+        local_path: String,
     },
 }
 
@@ -158,7 +214,7 @@ impl TelemetrySchema {
             path_or_url: path_buf.as_path().display().to_string(),
             error: e.to_string(),
         })?;
-        let schema: TelemetrySchema = serde_yaml::from_reader(BufReader::new(schema_file))
+        let mut schema: TelemetrySchema = serde_yaml::from_reader(BufReader::new(schema_file))
             .map_err(|e| Error::InvalidSchema {
                 path_or_url: path_buf.as_path().display().to_string(),
                 line: e.location().map(|loc| loc.line()),
@@ -166,6 +222,17 @@ impl TelemetrySchema {
                 error: e.to_string(),
             })?;
 
+        // Relative local imports are relative to the schema file.
+        if let Some(schema_dir) = path_buf.parent() {
+            for import in schema.semantic_conventions.iter_mut() {
+                if let SemConvImport::LocalPath { local_path } = import {
+                    if Path::new(local_path).is_relative() {
+                        *local_path = schema_dir.join(&*local_path).display().to_string();
+                    }
+                }
+            }
+        }
+
         Ok(schema)
     }
 
@@ -174,8 +241,8 @@ impl TelemetrySchema {
         match schema_url.scheme() {
             "http" | "https" => {
                 // Create a content reader from the schema URL
-                let reader = ureq::get(schema_url.as_ref())
-                    .call()
+                let reader = weaver_net::client()
+                    .and_then(|client| client.get(schema_url.as_ref()))
                     .map_err(|e| Error::SchemaNotFound {
                         path_or_url: schema_url.to_string(),
                         error: e.to_string(),
@@ -223,6 +290,18 @@ impl TelemetrySchema {
         result
     }
 
+    /// Returns the semantic conventions of the schema and its parent schemas
+    /// loaded from resolved schema artifacts (see
+    /// [`TelemetrySchema::resolved_semantic_conventions`]).
+    pub fn merged_resolved_semantic_conventions(&self) -> Vec<SemConvSpecWithProvenance> {
+        let mut result = vec![];
+        if let Some(parent_schema) = self.parent_schema.as_ref() {
+            result.extend(parent_schema.merged_resolved_semantic_conventions());
+        }
+        result.extend(self.resolved_semantic_conventions.iter().cloned());
+        result
+    }
+
     /// Merges versions from the parent schema into the current schema.
     pub fn merge_versions(&mut self) {
         if let Some(parent_schema) = &self.parent_schema {
@@ -239,6 +318,20 @@ impl TelemetrySchema {
         }
     }
 
+    /// Returns the changes of the latest version of the schema, including the
+    /// changes of the previous versions (see [`Self::merge_versions`] for the
+    /// versions of the parent schema).
+    pub fn version_changes(&self) -> VersionChanges {
+        self.versions
+            .as_ref()
+            .and_then(|versions| {
+                versions
+                    .latest_version()
+                    .map(|latest_version| versions.version_changes_for(latest_version))
+            })
+            .unwrap_or_default()
+    }
+
     /// Returns the semantic convention catalog used to resolve the schema (if resolved).
     pub fn semantic_convention_catalog(&self) -> &SemConvSpecs {
         &self.semantic_convention_registry
@@ -296,6 +389,26 @@ impl TelemetrySchema {
         self.schema.as_ref().and_then(|schema| schema.resource())
     }
 
+    /// Returns the instrumentation library or None if not defined.
+    pub fn instrumentation_library(
+        &self,
+    ) -> Option<&instrumentation_library::InstrumentationLibrary> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.instrumentation_library.as_ref())
+    }
+
+    /// Returns the version of the schema, i.e. the last segment of the schema
+    /// URL when it starts with a digit (e.g. `1.2.0` for
+    /// `https://mycompany.com/schemas/1.2.0`), or None.
+    pub fn schema_version(&self) -> Option<&str> {
+        self.schema_url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+    }
+
     /// Returns a vector of metrics.
     pub fn metrics(&self) -> Vec<&univariate_metric::UnivariateMetric> {
         self.schema.as_ref().map_or(
@@ -4,10 +4,11 @@
 
 use crate::attribute::Attribute;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A log record specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct LogRecord {
     /// The id of the log record.
@@ -27,7 +28,7 @@ pub struct LogRecord {
 }
 
 /// The type of body of a log record.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum BodyType {
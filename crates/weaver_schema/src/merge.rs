@@ -0,0 +1,464 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Merge of several resolved telemetry schemas (typically the schemas of the
+//! applications of a platform) into a system-level telemetry schema.
+//!
+//! The signals of the merged schema are the union of the signals of the
+//! schemas. A signal defined by several schemas must have the same definition
+//! in each of them, otherwise the merge reports a conflict. Each signal is
+//! tagged with the schema URL of the schemas defining it (see
+//! [`PROVENANCE_TAG`]). The common attributes (resource, spans, events and
+//! metrics) are merged by attribute id with the same conflict detection, and
+//! the `versions` sections are merged, the first schemas taking precedence.
+//! The instrumentation libraries of the schemas become the dependencies of
+//! the merged schema. The service metadata of the applications is not
+//! carried by the merged schema, which doesn't describe a single service.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::attribute::Attribute;
+use crate::event::Event;
+use crate::instrumentation_library::InstrumentationLibrary;
+use crate::metric_group::MetricGroup;
+use crate::resource::Resource;
+use crate::resource_events::ResourceEvents;
+use crate::resource_metrics::ResourceMetrics;
+use crate::resource_spans::ResourceSpans;
+use crate::schema_spec::SchemaSpec;
+use crate::span::Span;
+use crate::tags::Tags;
+use crate::univariate_metric::UnivariateMetric;
+use crate::{Error, SemConvImport, TelemetrySchema};
+
+/// The tag recording, on each signal of a merged schema, the schema URL of
+/// the schemas defining the signal (comma separated).
+pub const PROVENANCE_TAG: &str = "source_schema_url";
+
+/// A signal (span, event, metric or metric group) of a telemetry schema.
+trait Signal: Serialize {
+    /// The kind of signal, as displayed in the conflicts.
+    const KIND: &'static str;
+
+    /// Returns the name identifying the signal.
+    fn name(&self) -> String;
+
+    /// Returns the tags of the signal.
+    fn tags_mut(&mut self) -> &mut Option<Tags>;
+}
+
+impl Signal for Span {
+    const KIND: &'static str = "Span";
+
+    fn name(&self) -> String {
+        self.span_name.clone()
+    }
+
+    fn tags_mut(&mut self) -> &mut Option<Tags> {
+        &mut self.tags
+    }
+}
+
+impl Signal for Event {
+    const KIND: &'static str = "Event";
+
+    fn name(&self) -> String {
+        self.event_name.clone()
+    }
+
+    fn tags_mut(&mut self) -> &mut Option<Tags> {
+        &mut self.tags
+    }
+}
+
+impl Signal for UnivariateMetric {
+    const KIND: &'static str = "Metric";
+
+    fn name(&self) -> String {
+        UnivariateMetric::name(self)
+    }
+
+    fn tags_mut(&mut self) -> &mut Option<Tags> {
+        match self {
+            UnivariateMetric::Ref { tags, .. } | UnivariateMetric::Metric { tags, .. } => tags,
+        }
+    }
+}
+
+impl Signal for MetricGroup {
+    const KIND: &'static str = "Metric group";
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn tags_mut(&mut self) -> &mut Option<Tags> {
+        &mut self.tags
+    }
+}
+
+/// The signals of a kind merged so far.
+struct MergedSignals<T> {
+    /// The merged signals, in the order of their first definition.
+    signals: Vec<T>,
+    /// Signal name -> index of the signal, definition of the signal and
+    /// schema URLs of the schemas defining it.
+    definitions: HashMap<String, (usize, Option<serde_yaml::Value>, Vec<String>)>,
+}
+
+impl<T: Signal> MergedSignals<T> {
+    fn new() -> Self {
+        Self {
+            signals: vec![],
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Adds the signals of a schema, the conflicting definitions being
+    /// appended to `conflicts`.
+    fn add(&mut self, signals: Vec<T>, schema_url: &str, conflicts: &mut Vec<String>) {
+        for signal in signals {
+            let name = signal.name();
+            let definition = serde_yaml::to_value(&signal).ok();
+            match self.definitions.get_mut(&name) {
+                Some((_, first_definition, schema_urls)) => {
+                    if *first_definition != definition {
+                        conflicts.push(format!(
+                            "{} `{}` is defined differently by {} and {}",
+                            T::KIND,
+                            name,
+                            schema_urls.join(", "),
+                            schema_url
+                        ));
+                    } else if !schema_urls.iter().any(|url| url == schema_url) {
+                        schema_urls.push(schema_url.to_owned());
+                    }
+                }
+                None => {
+                    _ = self.definitions.insert(
+                        name,
+                        (self.signals.len(), definition, vec![schema_url.to_owned()]),
+                    );
+                    self.signals.push(signal);
+                }
+            }
+        }
+    }
+
+    /// Returns the merged signals tagged with their provenance.
+    fn into_signals(self) -> Vec<T> {
+        let mut signals = self.signals;
+        for (index, _, schema_urls) in self.definitions.into_values() {
+            let tags = signals[index].tags_mut().get_or_insert_with(|| Tags {
+                tags: BTreeMap::new(),
+            });
+            _ = tags
+                .tags
+                .insert(PROVENANCE_TAG.to_owned(), schema_urls.join(", "));
+        }
+        signals
+    }
+}
+
+/// The common attributes merged so far (by attribute id).
+struct MergedAttributes {
+    /// The kind of attributes, as displayed in the conflicts.
+    kind: &'static str,
+    /// The merged attributes, in the order of their first definition.
+    attributes: Vec<Attribute>,
+    /// Attribute id -> definition and schema URL of the first schema
+    /// defining the attribute.
+    definitions: HashMap<String, (Option<serde_yaml::Value>, String)>,
+}
+
+impl MergedAttributes {
+    fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            attributes: vec![],
+            definitions: HashMap::new(),
+        }
+    }
+
+    /// Adds the attributes of a schema, the conflicting definitions being
+    /// appended to `conflicts`.
+    fn add(&mut self, attributes: Vec<Attribute>, schema_url: &str, conflicts: &mut Vec<String>) {
+        for attribute in attributes {
+            let id = attribute.id();
+            let definition = serde_yaml::to_value(&attribute).ok();
+            match self.definitions.get(&id) {
+                Some((first_definition, first_schema_url)) => {
+                    if *first_definition != definition {
+                        conflicts.push(format!(
+                            "{} attribute `{}` is defined differently by {} and {}",
+                            self.kind, id, first_schema_url, schema_url
+                        ));
+                    }
+                }
+                None => {
+                    _ = self
+                        .definitions
+                        .insert(id, (definition, schema_url.to_owned()));
+                    self.attributes.push(attribute);
+                }
+            }
+        }
+    }
+}
+
+/// Merges the tags of a schema into the tags merged so far (the tags of the
+/// first schemas taking precedence).
+fn merge_tags(merged_tags: &mut Option<Tags>, tags: Option<Tags>) {
+    *merged_tags = match (merged_tags.take(), tags) {
+        (Some(merged_tags), Some(tags)) => Some(merged_tags.merge_with_override(&tags)),
+        (merged_tags, tags) => merged_tags.or(tags),
+    };
+}
+
+impl TelemetrySchema {
+    /// Merges resolved telemetry schemas into a system-level telemetry schema
+    /// published at `schema_url` (see the [module documentation](crate::merge)
+    /// for the merge rules).
+    ///
+    /// All the conflicts are reported at once in a
+    /// [`Error::MergeConflicts`] error.
+    pub fn merge(schema_url: &str, schemas: Vec<TelemetrySchema>) -> Result<Self, Error> {
+        let mut conflicts = vec![];
+        let mut file_format = None;
+        let mut semantic_conventions: Vec<SemConvImport> = vec![];
+        let mut versions: Option<weaver_version::Versions> = None;
+        let mut tags = None;
+        let mut dependencies: Vec<InstrumentationLibrary> = vec![];
+        let mut resource_attributes = MergedAttributes::new("Resource");
+        let mut resource_tags = None;
+        let mut has_resource = false;
+        let mut span_attributes = MergedAttributes::new("Span");
+        let mut spans = MergedSignals::<Span>::new();
+        let mut span_tags = None;
+        let mut event_attributes = MergedAttributes::new("Event");
+        let mut events = MergedSignals::<Event>::new();
+        let mut event_tags = None;
+        let mut metric_attributes = MergedAttributes::new("Metric");
+        let mut metrics = MergedSignals::<UnivariateMetric>::new();
+        let mut metric_groups = MergedSignals::<MetricGroup>::new();
+        let mut metric_tags = None;
+
+        for schema in schemas {
+            let source = schema.schema_url;
+            _ = file_format.get_or_insert(schema.file_format);
+            for import in schema.semantic_conventions {
+                let definition = serde_yaml::to_value(&import).ok();
+                if !semantic_conventions
+                    .iter()
+                    .any(|other| serde_yaml::to_value(other).ok() == definition)
+                {
+                    semantic_conventions.push(import);
+                }
+            }
+            if let Some(schema_versions) = schema.versions {
+                match versions.as_mut() {
+                    Some(versions) => versions.extend(schema_versions),
+                    None => versions = Some(schema_versions),
+                }
+            }
+
+            let Some(spec) = schema.schema else {
+                continue;
+            };
+            merge_tags(&mut tags, spec.tags);
+            for library in spec
+                .instrumentation_library
+                .into_iter()
+                .chain(spec.dependencies)
+            {
+                let definition = serde_yaml::to_value(&library).ok();
+                if !dependencies
+                    .iter()
+                    .any(|other| serde_yaml::to_value(other).ok() == definition)
+                {
+                    dependencies.push(library);
+                }
+            }
+            if let Some(resource) = spec.resource {
+                has_resource = true;
+                resource_attributes.add(resource.attributes, &source, &mut conflicts);
+                merge_tags(&mut resource_tags, resource.tags);
+            }
+            if let Some(resource_spans) = spec.resource_spans {
+                span_attributes.add(resource_spans.attributes, &source, &mut conflicts);
+                spans.add(resource_spans.spans, &source, &mut conflicts);
+                merge_tags(&mut span_tags, resource_spans.tags);
+            }
+            if let Some(resource_events) = spec.resource_events {
+                event_attributes.add(resource_events.attributes, &source, &mut conflicts);
+                events.add(resource_events.events, &source, &mut conflicts);
+                merge_tags(&mut event_tags, resource_events.tags);
+            }
+            if let Some(resource_metrics) = spec.resource_metrics {
+                metric_attributes.add(resource_metrics.attributes, &source, &mut conflicts);
+                metrics.add(resource_metrics.metrics, &source, &mut conflicts);
+                metric_groups.add(resource_metrics.metric_groups, &source, &mut conflicts);
+                merge_tags(&mut metric_tags, resource_metrics.tags);
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(Error::MergeConflicts { conflicts });
+        }
+
+        let spans = spans.into_signals();
+        let events = events.into_signals();
+        let metrics = metrics.into_signals();
+        let metric_groups = metric_groups.into_signals();
+        let schema = SchemaSpec {
+            tags,
+            service: None,
+            resource: has_resource.then_some(Resource {
+                attributes: resource_attributes.attributes,
+                tags: resource_tags,
+            }),
+            instrumentation_library: None,
+            dependencies,
+            resource_metrics: (!metric_attributes.attributes.is_empty()
+                || !metrics.is_empty()
+                || !metric_groups.is_empty())
+            .then_some(ResourceMetrics {
+                attributes: metric_attributes.attributes,
+                metrics,
+                metric_groups,
+                tags: metric_tags,
+            }),
+            resource_events: (!event_attributes.attributes.is_empty() || !events.is_empty())
+                .then_some(ResourceEvents {
+                    attributes: event_attributes.attributes,
+                    events,
+                    tags: event_tags,
+                }),
+            resource_spans: (!span_attributes.attributes.is_empty() || !spans.is_empty())
+                .then_some(ResourceSpans {
+                    attributes: span_attributes.attributes,
+                    spans,
+                    tags: span_tags,
+                }),
+        };
+
+        Ok(TelemetrySchema {
+            file_format: file_format.unwrap_or_else(|| "1.2.0".to_owned()),
+            parent_schema_url: None,
+            schema_url: schema_url.to_owned(),
+            semantic_conventions,
+            schema: Some(schema),
+            versions,
+            parent_schema: None,
+            semantic_convention_registry: Default::default(),
+            resolved_semantic_conventions: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::merge::PROVENANCE_TAG;
+    use crate::{Error, TelemetrySchema};
+
+    fn schema(schema_url: &str, spans: &str) -> TelemetrySchema {
+        serde_yaml::from_str(&format!(
+            r#"
+file_format: 1.2.0
+schema_url: {schema_url}
+schema:
+  instrumentation_library:
+    name: {schema_url}
+  resource_spans:
+    spans:
+{spans}
+versions:
+  1.0.0:
+    spans:
+      changes:
+        - rename_attributes:
+            attribute_map:
+              peer.service: {schema_url}.peer
+"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let checkout = schema(
+            "https://acme.com/schemas/checkout/1.0.0",
+            r#"
+      - span_name: http.request
+        brief: An HTTP request.
+      - span_name: checkout
+        brief: A checkout."#,
+        );
+        let payment = schema(
+            "https://acme.com/schemas/payment/1.0.0",
+            r#"
+      - span_name: http.request
+        brief: An HTTP request.
+      - span_name: payment
+        brief: A payment."#,
+        );
+
+        let merged = TelemetrySchema::merge(
+            "https://acme.com/schemas/system/1.0.0",
+            vec![checkout, payment],
+        )
+        .unwrap();
+        assert_eq!(merged.schema_url, "https://acme.com/schemas/system/1.0.0");
+        let provenances: Vec<(&str, &str)> = merged
+            .spans()
+            .iter()
+            .map(|span| {
+                (
+                    span.span_name.as_str(),
+                    span.tags
+                        .as_ref()
+                        .and_then(|tags| tags.get_tag(PROVENANCE_TAG))
+                        .unwrap()
+                        .as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            provenances,
+            vec![
+                (
+                    "http.request",
+                    "https://acme.com/schemas/checkout/1.0.0, https://acme.com/schemas/payment/1.0.0"
+                ),
+                ("checkout", "https://acme.com/schemas/checkout/1.0.0"),
+                ("payment", "https://acme.com/schemas/payment/1.0.0"),
+            ]
+        );
+        assert_eq!(merged.schema.as_ref().unwrap().dependencies.len(), 2);
+        // The versions of the first schema take precedence.
+        let versions = serde_yaml::to_string(&merged.versions).unwrap();
+        assert!(versions.contains("https://acme.com/schemas/checkout/1.0.0.peer"));
+        assert!(!versions.contains("https://acme.com/schemas/payment/1.0.0.peer"));
+
+        let checkout = schema(
+            "https://acme.com/schemas/checkout/1.0.0",
+            r#"
+      - span_name: http.request
+        brief: An HTTP request."#,
+        );
+        let payment = schema(
+            "https://acme.com/schemas/payment/1.0.0",
+            r#"
+      - span_name: http.request
+        brief: An outgoing HTTP request."#,
+        );
+        match TelemetrySchema::merge("https://acme.com/schemas/system/1.0.0", vec![checkout, payment]) {
+            Err(Error::MergeConflicts { conflicts }) => assert_eq!(
+                conflicts,
+                vec!["Span `http.request` is defined differently by https://acme.com/schemas/checkout/1.0.0 and https://acme.com/schemas/payment/1.0.0"]
+            ),
+            result => panic!("Expected a merge conflict, found {:?}", result.map(|_| ())),
+        }
+    }
+}
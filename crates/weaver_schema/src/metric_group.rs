@@ -2,6 +2,7 @@
 
 //! Multivariate metrics.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::attribute::Attribute;
@@ -9,7 +10,7 @@ use crate::tags::Tags;
 use weaver_semconv::group::InstrumentSpec;
 
 /// The specification of a metric group.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct MetricGroup {
     /// The name of the metric group.
@@ -31,7 +32,7 @@ pub struct MetricGroup {
 }
 
 /// A metric specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 pub enum Metric {
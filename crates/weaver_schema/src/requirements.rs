@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Attribute requirement matrices of the signals of a telemetry schema, i.e.
+//! the attributes of each span, event and metric with their requirement
+//! level, type and examples, rendered as CSV or Markdown.
+
+use serde::Serialize;
+use weaver_semconv::attribute::{ExamplesSpec, RequirementLevelSpec};
+
+use crate::attribute::Attribute;
+use crate::metric_group::Metric;
+use crate::univariate_metric::UnivariateMetric;
+use crate::TelemetrySchema;
+
+/// The attribute requirement matrix of a signal.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RequirementMatrix {
+    /// The signal (span, event, metric or metric_group).
+    pub signal: &'static str,
+    /// The name of the span, event, metric or metric group.
+    pub name: String,
+    /// One row per attribute of the signal (the attributes shared by all the
+    /// signals of this type first).
+    pub rows: Vec<RequirementRow>,
+}
+
+/// A row of an attribute requirement matrix.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RequirementRow {
+    /// The id (or reference) of the attribute.
+    pub attribute: String,
+    /// The requirement level of the attribute.
+    pub requirement_level: String,
+    /// The type of the attribute (empty for an unresolved reference).
+    pub r#type: String,
+    /// The examples of the attribute, comma separated.
+    pub examples: String,
+}
+
+impl TelemetrySchema {
+    /// Returns the attribute requirement matrix of each span, event, metric
+    /// and metric group of the schema.
+    /// The schema is expected to be resolved, otherwise the attribute
+    /// references are reported without type and the references to groups
+    /// (`attribute_group_ref`, `span_ref`, ...) are ignored.
+    pub fn requirement_matrices(&self) -> Vec<RequirementMatrix> {
+        let mut matrices = vec![];
+        let mut matrix = |signal: &'static str, name: String, attributes: Vec<&Attribute>| {
+            matrices.push(RequirementMatrix {
+                signal,
+                name,
+                rows: attributes.into_iter().filter_map(requirement_row).collect(),
+            });
+        };
+        let Some(spec) = self.schema.as_ref() else {
+            return vec![];
+        };
+
+        if let Some(resource_spans) = spec.resource_spans.as_ref() {
+            for span in resource_spans.spans.iter() {
+                matrix(
+                    "span",
+                    span.span_name.clone(),
+                    resource_spans
+                        .attributes
+                        .iter()
+                        .chain(span.attributes.iter())
+                        .collect(),
+                );
+            }
+        }
+
+        if let Some(resource_events) = spec.resource_events.as_ref() {
+            for event in resource_events.events.iter() {
+                matrix(
+                    "event",
+                    event.event_name.clone(),
+                    resource_events
+                        .attributes
+                        .iter()
+                        .chain(event.attributes.iter())
+                        .collect(),
+                );
+            }
+        }
+
+        if let Some(resource_metrics) = spec.resource_metrics.as_ref() {
+            for metric in resource_metrics.metrics.iter() {
+                let attributes = match metric {
+                    UnivariateMetric::Ref { attributes, .. }
+                    | UnivariateMetric::Metric { attributes, .. } => attributes,
+                };
+                matrix(
+                    "metric",
+                    metric.name(),
+                    resource_metrics
+                        .attributes
+                        .iter()
+                        .chain(attributes.iter())
+                        .collect(),
+                );
+            }
+            for metric_group in resource_metrics.metric_groups.iter() {
+                matrix(
+                    "metric_group",
+                    metric_group.name.clone(),
+                    resource_metrics
+                        .attributes
+                        .iter()
+                        .chain(metric_group.attributes.iter())
+                        .chain(metric_group.metrics.iter().flat_map(|metric| match metric {
+                            Metric::Ref { .. } => [].iter(),
+                            Metric::Metric { attributes, .. } => attributes.iter(),
+                        }))
+                        .collect(),
+                );
+            }
+        }
+
+        matrices
+    }
+}
+
+/// Returns the requirement row of an attribute or `None` for a reference to
+/// a group of attributes.
+fn requirement_row(attr: &Attribute) -> Option<RequirementRow> {
+    match attr {
+        Attribute::Id {
+            id,
+            r#type,
+            examples,
+            requirement_level,
+            ..
+        } => Some(RequirementRow {
+            attribute: id.clone(),
+            requirement_level: requirement_level.to_string(),
+            r#type: r#type.to_string(),
+            examples: examples.as_ref().map(examples_text).unwrap_or_default(),
+        }),
+        Attribute::Ref {
+            r#ref,
+            examples,
+            requirement_level,
+            ..
+        } => Some(RequirementRow {
+            attribute: r#ref.clone(),
+            requirement_level: requirement_level
+                .clone()
+                .unwrap_or_else(RequirementLevelSpec::default)
+                .to_string(),
+            r#type: String::new(),
+            examples: examples.as_ref().map(examples_text).unwrap_or_default(),
+        }),
+        _ => None,
+    }
+}
+
+/// Returns the examples as comma separated values.
+fn examples_text(examples: &ExamplesSpec) -> String {
+    fn join<T: ToString>(values: &[T]) -> String {
+        values
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    match examples {
+        ExamplesSpec::Bool(value) => value.to_string(),
+        ExamplesSpec::Int(value) => value.to_string(),
+        ExamplesSpec::Double(value) => value.to_string(),
+        ExamplesSpec::String(value) => value.clone(),
+        ExamplesSpec::Ints(values) => join(values),
+        ExamplesSpec::Doubles(values) => join(values),
+        ExamplesSpec::Bools(values) => join(values),
+        ExamplesSpec::Strings(values) => join(values),
+    }
+}
+
+/// Renders the matrices as a single CSV document with one line per
+/// attribute of each signal.
+pub fn to_csv(matrices: &[RequirementMatrix]) -> String {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_owned()
+        }
+    }
+
+    let mut csv = String::from("signal,name,attribute,requirement_level,type,examples\n");
+    for matrix in matrices {
+        for row in matrix.rows.iter() {
+            let fields: [&str; 6] = [
+                matrix.signal,
+                &matrix.name,
+                &row.attribute,
+                &row.requirement_level,
+                &row.r#type,
+                &row.examples,
+            ];
+            csv.push_str(&fields.map(field).join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Renders the matrices as a Markdown document with one table per signal.
+pub fn to_markdown(matrices: &[RequirementMatrix]) -> String {
+    fn cell(value: &str) -> String {
+        value
+            .trim()
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace('\n', "<br>")
+    }
+
+    let mut markdown = String::from("# Attribute requirement matrices\n");
+    for matrix in matrices {
+        markdown.push_str(&format!("\n## {} `{}`\n\n", matrix.signal, matrix.name));
+        if matrix.rows.is_empty() {
+            markdown.push_str("No attributes.\n");
+            continue;
+        }
+        markdown.push_str("| Attribute | Requirement level | Type | Examples |\n");
+        markdown.push_str("|---|---|---|---|\n");
+        for row in matrix.rows.iter() {
+            markdown.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                row.attribute,
+                cell(&row.requirement_level),
+                cell(&row.r#type),
+                cell(&row.examples)
+            ));
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::requirements::{to_csv, to_markdown};
+    use crate::TelemetrySchema;
+
+    #[test]
+    fn test_requirement_matrices() {
+        let schema =
+            TelemetrySchema::load_from_file("../../data/app-telemetry-schema.yaml").unwrap();
+        let matrices = schema.requirement_matrices();
+        let signals: Vec<(&str, &str, usize)> = matrices
+            .iter()
+            .map(|matrix| (matrix.signal, matrix.name.as_str(), matrix.rows.len()))
+            .collect();
+        assert_eq!(
+            signals,
+            vec![
+                ("span", "http.request", 4),
+                ("event", "http", 4),
+                ("metric", "http.server.request.duration", 5),
+                ("metric_group", "http", 1),
+            ]
+        );
+
+        let environment = &matrices[2].rows[0];
+        assert_eq!(environment.attribute, "environment");
+        assert_eq!(environment.requirement_level, "required");
+        assert_eq!(environment.r#type, "string");
+
+        let csv = to_csv(&matrices);
+        assert!(csv.starts_with("signal,name,attribute,requirement_level,type,examples\n"));
+        assert!(csv.contains("metric,http.server.request.duration,environment,required,string,\n"));
+        assert!(csv.contains("span,http.request,server.address,recommended,,\n"));
+
+        let markdown = to_markdown(&matrices);
+        assert!(markdown.contains("## span `http.request`"));
+        assert!(markdown.contains("| `environment` | required | string |  |"));
+    }
+}
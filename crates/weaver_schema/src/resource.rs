@@ -4,13 +4,14 @@
 
 use crate::attribute::Attribute;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A common resource specification.
 /// All the attributes mentioned in this specification will be inherited by all
 /// the other specialized resource specifications.
 /// Only used when a Client SDK is generated.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct Resource {
     /// The common attributes of the resource.
@@ -18,7 +19,7 @@ pub struct Resource {
     pub attributes: Vec<Attribute>,
     /// A set of tags for the resource.
     #[serde(skip_serializing_if = "Option::is_none")]
-    tags: Option<Tags>,
+    pub tags: Option<Tags>,
 }
 
 impl Resource {
@@ -5,10 +5,11 @@
 use crate::attribute::Attribute;
 use crate::event::Event;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A resource events specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceEvents {
     /// Common attributes shared across events (implemented as log records).
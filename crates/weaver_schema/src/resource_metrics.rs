@@ -6,10 +6,11 @@ use crate::attribute::Attribute;
 use crate::metric_group::MetricGroup;
 use crate::tags::Tags;
 use crate::univariate_metric::UnivariateMetric;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A resource metrics specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct ResourceMetrics {
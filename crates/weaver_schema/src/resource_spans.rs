@@ -5,10 +5,11 @@
 use crate::attribute::Attribute;
 use crate::span::Span;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A resource spans specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceSpans {
     /// Common attributes shared across spans.
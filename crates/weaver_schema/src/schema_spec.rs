@@ -2,6 +2,7 @@
 
 //! A schema specification.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::event::Event;
@@ -11,24 +12,33 @@ use crate::resource::Resource;
 use crate::resource_events::ResourceEvents;
 use crate::resource_metrics::ResourceMetrics;
 use crate::resource_spans::ResourceSpans;
+use crate::service::ServiceMetadata;
 use crate::span::Span;
 use crate::tags::Tags;
 use crate::univariate_metric::UnivariateMetric;
 
 /// Definition of the telemetry schema for an application or a library.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct SchemaSpec {
     /// A set of tags for the schema.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Tags>,
+    /// The service metadata of the application (owner, tier, runbook and
+    /// SLO links).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceMetadata>,
     /// A common resource specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resource: Option<Resource>,
     /// The instrumentation library specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instrumentation_library: Option<InstrumentationLibrary>,
+    /// The instrumentation libraries the application or library depends on.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<InstrumentationLibrary>,
     /// A resource metrics specification.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub resource_metrics: Option<ResourceMetrics>,
@@ -0,0 +1,259 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Service metadata of an application telemetry schema (owning team, tier,
+//! runbook and SLO links), carried into the resolved schema and the exported
+//! artifacts so they can be traced back to their owners.
+//!
+//! The metadata is validated against a policy defined in a YAML file:
+//!
+//! ```yaml
+//! required: [owner, tier, runbook_url]
+//! tiers: [critical, standard, best-effort]
+//! annotations: [cost_center]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{Error, TelemetrySchema};
+
+/// The service metadata of an application.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceMetadata {
+    /// The team owning the application.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// The criticality tier of the application (e.g. `critical`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// The URL of the runbook of the application.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runbook_url: Option<String>,
+    /// The URLs of the SLOs of the application.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub slo_urls: Vec<String>,
+    /// Additional free-form metadata (e.g. the cost center).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+/// The policy the service metadata is validated against (see
+/// [`TelemetrySchema::check_service_metadata`]). The URLs are always checked.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServiceMetadataPolicy {
+    /// The fields the metadata must declare (`owner`, `tier`, `runbook_url`
+    /// or `slo_urls`).
+    pub required: Vec<ServiceField>,
+    /// The allowed tiers (any tier if empty).
+    pub tiers: Vec<String>,
+    /// The annotations the metadata must declare.
+    pub annotations: Vec<String>,
+}
+
+/// The fields of the service metadata.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceField {
+    /// The owning team.
+    Owner,
+    /// The criticality tier.
+    Tier,
+    /// The runbook URL.
+    RunbookUrl,
+    /// The SLO URLs.
+    SloUrls,
+}
+
+impl Display for ServiceField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceField::Owner => write!(f, "owner"),
+            ServiceField::Tier => write!(f, "tier"),
+            ServiceField::RunbookUrl => write!(f, "runbook_url"),
+            ServiceField::SloUrls => write!(f, "slo_urls"),
+        }
+    }
+}
+
+/// A violation of the service metadata policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceMetadataViolation {
+    /// The offending field (or annotation).
+    pub field: String,
+    /// A human readable description of the violation.
+    pub message: String,
+}
+
+impl Display for ServiceMetadataViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "service metadata `{}`: {}", self.field, self.message)
+    }
+}
+
+impl ServiceMetadataPolicy {
+    /// Loads the service metadata policy from a YAML file.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<ServiceMetadataPolicy, Error> {
+        let path = path.as_ref();
+        let invalid = |error: String| Error::InvalidServicePolicy {
+            path: path.display().to_string(),
+            error,
+        };
+        let file = File::open(path).map_err(|e| invalid(e.to_string()))?;
+        serde_yaml::from_reader(BufReader::new(file)).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+impl ServiceMetadata {
+    /// Returns the URLs of the metadata (runbook first) with the name of
+    /// their field.
+    pub fn links(&self) -> impl Iterator<Item = (ServiceField, &str)> {
+        self.runbook_url
+            .iter()
+            .map(|url| (ServiceField::RunbookUrl, url.as_str()))
+            .chain(
+                self.slo_urls
+                    .iter()
+                    .map(|url| (ServiceField::SloUrls, url.as_str())),
+            )
+    }
+
+    /// Returns true if the given field is declared.
+    fn has(&self, field: ServiceField) -> bool {
+        match field {
+            ServiceField::Owner => self.owner.as_ref().is_some_and(|v| !v.trim().is_empty()),
+            ServiceField::Tier => self.tier.as_ref().is_some_and(|v| !v.trim().is_empty()),
+            ServiceField::RunbookUrl => self.runbook_url.is_some(),
+            ServiceField::SloUrls => !self.slo_urls.is_empty(),
+        }
+    }
+
+    /// Checks the metadata against the given policy and returns the
+    /// violations.
+    pub fn validate(&self, policy: &ServiceMetadataPolicy) -> Vec<ServiceMetadataViolation> {
+        let mut violations = vec![];
+        for field in policy.required.iter().filter(|field| !self.has(**field)) {
+            violations.push(ServiceMetadataViolation {
+                field: field.to_string(),
+                message: "the field is required".to_owned(),
+            });
+        }
+        if let Some(tier) = &self.tier {
+            if !policy.tiers.is_empty() && !policy.tiers.contains(tier) {
+                violations.push(ServiceMetadataViolation {
+                    field: ServiceField::Tier.to_string(),
+                    message: format!(
+                        "unknown tier `{}` (allowed: {})",
+                        tier,
+                        policy.tiers.join(", ")
+                    ),
+                });
+            }
+        }
+        for (field, url) in self.links() {
+            let valid = Url::parse(url)
+                .map(|url| url.scheme() == "http" || url.scheme() == "https")
+                .unwrap_or(false);
+            if !valid {
+                violations.push(ServiceMetadataViolation {
+                    field: field.to_string(),
+                    message: format!("`{}` is not an http(s) URL", url),
+                });
+            }
+        }
+        for annotation in policy
+            .annotations
+            .iter()
+            .filter(|annotation| !self.annotations.contains_key(*annotation))
+        {
+            violations.push(ServiceMetadataViolation {
+                field: annotation.clone(),
+                message: "the annotation is required".to_owned(),
+            });
+        }
+        violations
+    }
+}
+
+impl TelemetrySchema {
+    /// Returns the service metadata of the schema, if any.
+    pub fn service_metadata(&self) -> Option<&ServiceMetadata> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.service.as_ref())
+    }
+
+    /// Checks the service metadata of the schema against the given policy. A
+    /// schema without service metadata is checked as empty metadata, i.e. it
+    /// only violates the required fields and annotations.
+    pub fn check_service_metadata(
+        &self,
+        policy: &ServiceMetadataPolicy,
+    ) -> Vec<ServiceMetadataViolation> {
+        match self.service_metadata() {
+            Some(metadata) => metadata.validate(policy),
+            None => ServiceMetadata::default().validate(policy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::service::{ServiceField, ServiceMetadataPolicy};
+    use crate::{Error, TelemetrySchema};
+
+    #[test]
+    fn test_check_service_metadata() {
+        let schema = TelemetrySchema::load_from_file("data/service/schema.yaml").unwrap();
+        let metadata = schema.service_metadata().unwrap();
+        assert_eq!(metadata.owner.as_deref(), Some("team-checkout"));
+        assert_eq!(
+            metadata.annotations.get("cost_center").map(String::as_str),
+            Some("cc-1234")
+        );
+
+        let policy = ServiceMetadataPolicy::load_from_file("data/service/policy.yaml").unwrap();
+        assert_eq!(
+            policy.required,
+            vec![ServiceField::Owner, ServiceField::Tier]
+        );
+        let violations: Vec<String> = schema
+            .check_service_metadata(&policy)
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "service metadata `tier`: unknown tier `gold` (allowed: critical, standard)",
+                "service metadata `slo_urls`: `slo/checkout-latency` is not an http(s) URL",
+                "service metadata `region`: the annotation is required",
+            ]
+        );
+
+        // A schema without service metadata misses all the required fields and
+        // annotations.
+        let schema = TelemetrySchema::load_from_file("data/span-kinds/schema.yaml").unwrap();
+        assert!(schema.service_metadata().is_none());
+        assert_eq!(schema.check_service_metadata(&policy).len(), 4);
+        assert!(schema
+            .check_service_metadata(&ServiceMetadataPolicy::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_invalid_service_policy() {
+        let result = ServiceMetadataPolicy::load_from_file("data/service/schema.yaml");
+        assert!(matches!(result, Err(Error::InvalidServicePolicy { .. })));
+    }
+}
@@ -6,11 +6,12 @@ use crate::attribute::Attribute;
 use crate::span_event::SpanEvent;
 use crate::span_link::SpanLink;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use weaver_semconv::group::SpanKindSpec;
+use weaver_semconv::group::{SpanKindSpec, SpanNameSpec};
 
 /// A span specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
 pub struct Span {
@@ -19,6 +20,11 @@ pub struct Span {
     /// The kind of the span.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub kind: Option<SpanKindSpec>,
+    /// The guidance on the runtime names of the span (format and examples).
+    /// If not set, it's inherited from the first referenced span group
+    /// defining one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span_name_guidance: Option<SpanNameSpec>,
     /// The attributes of the span.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
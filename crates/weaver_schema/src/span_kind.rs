@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rules on the attributes the spans of a given kind must declare (e.g. the
+//! server-side attributes of the server spans).
+//!
+//! The rules are defined in a YAML file mapping a span kind to the list of
+//! its required attributes:
+//!
+//! ```yaml
+//! server:
+//!   - http.request.method
+//!   - url.scheme
+//! client:
+//!   - server.address
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::Deserialize;
+use weaver_semconv::group::SpanKindSpec;
+
+use crate::{Error, TelemetrySchema};
+
+/// The attributes required on the spans of each kind.
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SpanKindRules {
+    /// The attributes required on the internal spans.
+    #[serde(default)]
+    pub internal: Vec<String>,
+    /// The attributes required on the client spans.
+    #[serde(default)]
+    pub client: Vec<String>,
+    /// The attributes required on the server spans.
+    #[serde(default)]
+    pub server: Vec<String>,
+    /// The attributes required on the producer spans.
+    #[serde(default)]
+    pub producer: Vec<String>,
+    /// The attributes required on the consumer spans.
+    #[serde(default)]
+    pub consumer: Vec<String>,
+}
+
+/// A span not declaring all the attributes required by its kind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanKindViolation {
+    /// The name of the span.
+    pub span_name: String,
+    /// The kind of the span.
+    pub kind: &'static str,
+    /// The required attributes missing from the span.
+    pub missing_attributes: Vec<String>,
+}
+
+impl Display for SpanKindViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "span `{}` ({}) doesn't declare the attribute(s) required on {} spans: {}",
+            self.span_name,
+            self.kind,
+            self.kind,
+            self.missing_attributes.join(", ")
+        )
+    }
+}
+
+impl SpanKindRules {
+    /// Loads the span kind rules from a YAML file.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<SpanKindRules, Error> {
+        let path = path.as_ref();
+        let invalid = |error: String| Error::InvalidSpanKindRules {
+            path: path.display().to_string(),
+            error,
+        };
+        let file = File::open(path).map_err(|e| invalid(e.to_string()))?;
+        serde_yaml::from_reader(BufReader::new(file)).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Returns the attributes required on the spans of the given kind.
+    pub fn required_attributes(&self, kind: &SpanKindSpec) -> &[String] {
+        match kind {
+            SpanKindSpec::Internal => &self.internal,
+            SpanKindSpec::Client => &self.client,
+            SpanKindSpec::Server => &self.server,
+            SpanKindSpec::Producer => &self.producer,
+            SpanKindSpec::Consumer => &self.consumer,
+        }
+    }
+}
+
+/// Returns the name of a span kind as written in a telemetry schema.
+fn kind_name(kind: &SpanKindSpec) -> &'static str {
+    match kind {
+        SpanKindSpec::Internal => "internal",
+        SpanKindSpec::Client => "client",
+        SpanKindSpec::Server => "server",
+        SpanKindSpec::Producer => "producer",
+        SpanKindSpec::Consumer => "consumer",
+    }
+}
+
+impl TelemetrySchema {
+    /// Returns the spans not declaring all the attributes required by their
+    /// kind. The schema is expected to be resolved so the attributes imported
+    /// from the semantic convention groups (e.g. via `span_ref`) are taken into
+    /// account. The attributes shared by all the spans (`resource_spans`
+    /// level) are declared by every span. The spans without kind are not
+    /// checked.
+    pub fn check_span_kinds(&self, rules: &SpanKindRules) -> Vec<SpanKindViolation> {
+        let Some(resource_spans) = self
+            .schema
+            .as_ref()
+            .and_then(|schema| schema.resource_spans.as_ref())
+        else {
+            return vec![];
+        };
+
+        let mut violations = vec![];
+        for span in resource_spans.spans.iter() {
+            let Some(kind) = span.kind.as_ref() else {
+                continue;
+            };
+            let declared: Vec<String> = resource_spans
+                .attributes
+                .iter()
+                .chain(span.attributes.iter())
+                .map(|attr| attr.id())
+                .collect();
+            let missing_attributes: Vec<String> = rules
+                .required_attributes(kind)
+                .iter()
+                .filter(|required| !declared.contains(required))
+                .cloned()
+                .collect();
+            if !missing_attributes.is_empty() {
+                violations.push(SpanKindViolation {
+                    span_name: span.span_name.clone(),
+                    kind: kind_name(kind),
+                    missing_attributes,
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::span_kind::{SpanKindRules, SpanKindViolation};
+    use crate::{Error, TelemetrySchema};
+
+    #[test]
+    fn test_check_span_kinds() {
+        let schema = TelemetrySchema::load_from_file("data/span-kinds/schema.yaml").unwrap();
+        let rules = SpanKindRules::load_from_file("data/span-kinds/rules.yaml").unwrap();
+        assert_eq!(
+            schema.check_span_kinds(&rules),
+            vec![
+                SpanKindViolation {
+                    span_name: "GET /orders".to_owned(),
+                    kind: "server",
+                    missing_attributes: vec!["url.path".to_owned()],
+                },
+                SpanKindViolation {
+                    span_name: "fetch inventory".to_owned(),
+                    kind: "client",
+                    missing_attributes: vec!["server.address".to_owned(), "server.port".to_owned()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_span_kind_rules() {
+        let result = SpanKindRules::load_from_file("data/span-kinds/schema.yaml");
+        assert!(matches!(result, Err(Error::InvalidSpanKindRules { .. })));
+    }
+}
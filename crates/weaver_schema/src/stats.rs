@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics on the structure of a telemetry schema.
+
+use serde::Serialize;
+
+use crate::attribute::Attribute;
+use crate::metric_group::Metric;
+use crate::univariate_metric::UnivariateMetric;
+use crate::TelemetrySchema;
+
+/// Statistics on the signals and attributes of a telemetry schema.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct SchemaStats {
+    /// Statistics per signal (resource, span, event, metric, metric group).
+    pub signals: Vec<SignalStats>,
+    /// Number of attributes referencing a semantic convention (`ref`,
+    /// `attribute_group_ref`, `span_ref`, `resource_ref`, `event_ref`).
+    pub attribute_refs: usize,
+    /// Number of attributes locally defined with an `id`.
+    pub local_attributes: usize,
+    /// Ratio of the referenced attributes to all the attributes.
+    pub attribute_reuse_ratio: f64,
+    /// Number of version transformations defined by the schema itself.
+    pub local_transformations: usize,
+    /// Number of version transformations inherited from the parent schema.
+    pub inherited_transformations: usize,
+}
+
+/// Statistics on a signal of a telemetry schema.
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct SignalStats {
+    /// The signal name.
+    pub signal: &'static str,
+    /// Number of definitions of this signal.
+    pub count: usize,
+    /// Number of attributes declared by the definitions of this signal
+    /// (including the attributes shared at the resource level).
+    pub attributes: usize,
+}
+
+impl TelemetrySchema {
+    /// Returns the statistics of the telemetry schema.
+    /// The versions are expected to be unmerged (see
+    /// [`TelemetrySchema::merge_versions`]) to distinguish the local
+    /// transformations from the inherited ones.
+    pub fn stats(&self) -> SchemaStats {
+        let mut stats = SchemaStats::default();
+        let mut signal = |signal: &'static str, count: usize, attributes: Vec<&Attribute>| {
+            for attr in attributes.iter() {
+                match attr {
+                    Attribute::Id { .. } => stats.local_attributes += 1,
+                    _ => stats.attribute_refs += 1,
+                }
+            }
+            stats.signals.push(SignalStats {
+                signal,
+                count,
+                attributes: attributes.len(),
+            });
+        };
+
+        let spec = self.schema.as_ref();
+        let resource = spec.and_then(|spec| spec.resource.as_ref());
+        signal(
+            "resource",
+            usize::from(resource.is_some()),
+            resource
+                .map(|resource| resource.attributes.iter().collect())
+                .unwrap_or_default(),
+        );
+
+        let resource_spans = spec.and_then(|spec| spec.resource_spans.as_ref());
+        signal(
+            "span",
+            spec.map_or(0, |spec| spec.spans_count()),
+            resource_spans
+                .map(|resource_spans| {
+                    resource_spans
+                        .attributes
+                        .iter()
+                        .chain(resource_spans.spans.iter().flat_map(|span| {
+                            span.attributes
+                                .iter()
+                                .chain(span.events.iter().flat_map(|event| event.attributes.iter()))
+                        }))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        );
+
+        let resource_events = spec.and_then(|spec| spec.resource_events.as_ref());
+        signal(
+            "event",
+            spec.map_or(0, |spec| spec.events_count()),
+            resource_events
+                .map(|resource_events| {
+                    resource_events
+                        .attributes
+                        .iter()
+                        .chain(
+                            resource_events
+                                .events
+                                .iter()
+                                .flat_map(|event| event.attributes.iter()),
+                        )
+                        .collect()
+                })
+                .unwrap_or_default(),
+        );
+
+        let resource_metrics = spec.and_then(|spec| spec.resource_metrics.as_ref());
+        signal(
+            "metric",
+            spec.map_or(0, |spec| spec.metrics_count()),
+            resource_metrics
+                .map(|resource_metrics| {
+                    resource_metrics
+                        .attributes
+                        .iter()
+                        .chain(
+                            resource_metrics
+                                .metrics
+                                .iter()
+                                .flat_map(|metric| match metric {
+                                    UnivariateMetric::Ref { attributes, .. }
+                                    | UnivariateMetric::Metric { attributes, .. } => {
+                                        attributes.iter()
+                                    }
+                                }),
+                        )
+                        .collect()
+                })
+                .unwrap_or_default(),
+        );
+        signal(
+            "metric_group",
+            spec.map_or(0, |spec| spec.metric_groups_count()),
+            resource_metrics
+                .map(|resource_metrics| {
+                    resource_metrics
+                        .metric_groups
+                        .iter()
+                        .flat_map(|metric_group| {
+                            metric_group.attributes.iter().chain(
+                                metric_group.metrics.iter().flat_map(|metric| match metric {
+                                    Metric::Ref { .. } => [].iter(),
+                                    Metric::Metric { attributes, .. } => attributes.iter(),
+                                }),
+                            )
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        );
+
+        let attribute_count = stats.attribute_refs + stats.local_attributes;
+        if attribute_count > 0 {
+            stats.attribute_reuse_ratio = stats.attribute_refs as f64 / attribute_count as f64;
+        }
+        stats.local_transformations = self
+            .versions
+            .as_ref()
+            .map_or(0, |versions| versions.transformation_count());
+        stats.inherited_transformations = self
+            .parent_schema
+            .as_ref()
+            .and_then(|parent_schema| parent_schema.versions.as_ref())
+            .map_or(0, |versions| versions.transformation_count());
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TelemetrySchema;
+
+    #[test]
+    fn test_schema_stats() {
+        let schema =
+            TelemetrySchema::load_from_file("../../data/app-telemetry-schema.yaml").unwrap();
+        let stats = schema.stats();
+        let signals: Vec<(&str, usize, usize)> = stats
+            .signals
+            .iter()
+            .map(|signal| (signal.signal, signal.count, signal.attributes))
+            .collect();
+        assert_eq!(
+            signals,
+            vec![
+                ("resource", 1, 2),
+                ("span", 1, 7),
+                ("event", 1, 4),
+                ("metric", 1, 5),
+                ("metric_group", 1, 0),
+            ]
+        );
+        assert_eq!(stats.attribute_refs, 15);
+        assert_eq!(stats.local_attributes, 3);
+        assert_eq!(stats.local_transformations, 0);
+    }
+}
@@ -2,6 +2,7 @@
 
 //! Tags for telemetry schemas.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -15,7 +16,7 @@ use std::collections::BTreeMap;
 /// - semantic_type: first_name
 /// - owner:
 /// - provenance: browser_sensor
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(transparent)]
 #[serde(deny_unknown_fields)]
 pub struct Tags {
@@ -4,11 +4,12 @@
 
 use crate::attribute::Attribute;
 use crate::tags::Tags;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use weaver_semconv::group::InstrumentSpec;
 
 /// A univariate metric specification.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 pub enum UnivariateMetric {
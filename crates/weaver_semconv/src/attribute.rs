@@ -5,13 +5,16 @@
 //! Attribute specification.
 
 use ordered_float::OrderedFloat;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 
+use crate::deprecated::DeprecatedSpec;
 use crate::stability::StabilitySpec;
 
 /// An attribute specification.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(untagged)]
 #[serde(rename_all = "snake_case")]
@@ -45,7 +48,9 @@ pub enum AttributeSpec {
         #[serde(skip_serializing_if = "Option::is_none")]
         requirement_level: Option<RequirementLevelSpec>,
         /// Specifies if the attribute is (especially) relevant for sampling
-        /// and thus should be set at span start. It defaults to false.
+        /// and thus should be set at span start, or when the event or log
+
+        /// record is created. It defaults to false.
         /// Note: this field is experimental.
         #[serde(skip_serializing_if = "Option::is_none")]
         sampling_relevant: Option<bool>,
@@ -61,11 +66,19 @@ pub enum AttributeSpec {
         /// error.
         #[serde(skip_serializing_if = "Option::is_none")]
         stability: Option<StabilitySpec>,
-        /// Specifies if the attribute is deprecated. The string
-        /// provided as <description> MUST specify why it's deprecated and/or what
-        /// to use instead. See also stability.
+        /// Specifies if the attribute is deprecated, either with a note which
+        /// MUST specify why it's deprecated and/or what to use instead, or with
+        /// a structured deprecation (reason, `renamed_to`, `removed_in`). See
+        /// also stability.
         #[serde(skip_serializing_if = "Option::is_none")]
-        deprecated: Option<String>,
+        deprecated: Option<DeprecatedSpec>,
+        /// Extension fields not defined by the weaver syntax. They are
+        /// collected when a registry is loaded in build-tools compatibility
+        /// mode.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+        annotations: BTreeMap<String, serde_yaml::Value>,
     },
     /// Attribute definition.
     Id {
@@ -96,7 +109,9 @@ pub enum AttributeSpec {
         #[serde(default)]
         requirement_level: RequirementLevelSpec,
         /// Specifies if the attribute is (especially) relevant for sampling
-        /// and thus should be set at span start. It defaults to false.
+        /// and thus should be set at span start, or when the event or log
+
+        /// record is created. It defaults to false.
         /// Note: this field is experimental.
         #[serde(skip_serializing_if = "Option::is_none")]
         sampling_relevant: Option<bool>,
@@ -111,11 +126,19 @@ pub enum AttributeSpec {
         /// error.
         #[serde(skip_serializing_if = "Option::is_none")]
         stability: Option<StabilitySpec>,
-        /// Specifies if the attribute is deprecated. The string
-        /// provided as <description> MUST specify why it's deprecated and/or what
-        /// to use instead. See also stability.
+        /// Specifies if the attribute is deprecated, either with a note which
+        /// MUST specify why it's deprecated and/or what to use instead, or with
+        /// a structured deprecation (reason, `renamed_to`, `removed_in`). See
+        /// also stability.
         #[serde(skip_serializing_if = "Option::is_none")]
-        deprecated: Option<String>,
+        deprecated: Option<DeprecatedSpec>,
+        /// Extension fields not defined by the weaver syntax. They are
+        /// collected when a registry is loaded in build-tools compatibility
+        /// mode.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+        annotations: BTreeMap<String, serde_yaml::Value>,
     },
 }
 
@@ -144,6 +167,15 @@ impl AttributeSpec {
         }
     }
 
+    /// Returns the stability of the attribute, if specified.
+    pub fn stability(&self) -> Option<&StabilitySpec> {
+        match self {
+            AttributeSpec::Ref { stability, .. } | AttributeSpec::Id { stability, .. } => {
+                stability.as_ref()
+            }
+        }
+    }
+
     /// Returns the brief of the attribute.
     pub fn brief(&self) -> String {
         match self {
@@ -170,7 +202,7 @@ impl AttributeSpec {
 }
 
 /// The different types of attributes (specification).
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum AttributeTypeSpec {
@@ -207,13 +239,62 @@ impl Display for AttributeTypeSpec {
     }
 }
 
+impl AttributeTypeSpec {
+    /// Returns true if the examples have the type of the attribute (or of
+    /// its items for an array). The integer examples are accepted by the
+    /// double attributes and an enum accepts the types of its members.
+    pub fn accepts_examples(&self, examples: &ExamplesSpec) -> bool {
+        // The accepted example types: boolean, int, double and string.
+        let (boolean, int, double, string) = match self {
+            AttributeTypeSpec::PrimitiveOrArray(r#type) => match r#type {
+                PrimitiveOrArrayTypeSpec::Boolean | PrimitiveOrArrayTypeSpec::Booleans => {
+                    (true, false, false, false)
+                }
+                PrimitiveOrArrayTypeSpec::Int | PrimitiveOrArrayTypeSpec::Ints => {
+                    (false, true, false, false)
+                }
+                PrimitiveOrArrayTypeSpec::Double | PrimitiveOrArrayTypeSpec::Doubles => {
+                    (false, true, true, false)
+                }
+                PrimitiveOrArrayTypeSpec::String | PrimitiveOrArrayTypeSpec::Strings => {
+                    (false, false, false, true)
+                }
+            },
+            AttributeTypeSpec::Template(r#type) => match r#type {
+                TemplateTypeSpec::Boolean | TemplateTypeSpec::Booleans => {
+                    (true, false, false, false)
+                }
+                TemplateTypeSpec::Int | TemplateTypeSpec::Ints => (false, true, false, false),
+                TemplateTypeSpec::Double | TemplateTypeSpec::Doubles => (false, true, true, false),
+                TemplateTypeSpec::String | TemplateTypeSpec::Strings => (false, false, false, true),
+            },
+            AttributeTypeSpec::Enum { members, .. } => {
+                let has = |f: fn(&ValueSpec) -> bool| members.iter().any(|m| f(&m.value));
+                let double = has(|v| matches!(v, ValueSpec::Double(_)));
+                (
+                    false,
+                    double || has(|v| matches!(v, ValueSpec::Int(_))),
+                    double,
+                    has(|v| matches!(v, ValueSpec::String(_))),
+                )
+            }
+        };
+        match examples {
+            ExamplesSpec::Bool(_) | ExamplesSpec::Bools(_) => boolean,
+            ExamplesSpec::Int(_) | ExamplesSpec::Ints(_) => int,
+            ExamplesSpec::Double(_) | ExamplesSpec::Doubles(_) => double,
+            ExamplesSpec::String(_) | ExamplesSpec::Strings(_) => string,
+        }
+    }
+}
+
 /// Specifies the default value for allow_custom_values.
 fn default_as_true() -> bool {
     true
 }
 
 /// Primitive or array types.
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum PrimitiveOrArrayTypeSpec {
     /// A boolean attribute.
@@ -255,7 +336,7 @@ impl Display for PrimitiveOrArrayTypeSpec {
 }
 
 /// Template types.
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum TemplateTypeSpec {
     /// A boolean attribute.
@@ -301,7 +382,7 @@ impl Display for TemplateTypeSpec {
 }
 
 /// Possible enum entries.
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(deny_unknown_fields)]
 pub struct EnumEntriesSpec {
     /// String that uniquely identifies the enum entry.
@@ -314,6 +395,18 @@ pub struct EnumEntriesSpec {
     /// Longer description.
     /// It defaults to an empty string.
     pub note: Option<String>,
+    /// Specifies the stability of the enum entry.
+    /// If deprecated is present and stability differs from deprecated, this
+    /// will result in an error.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stability: Option<StabilitySpec>,
+    /// Specifies if the enum entry is deprecated, either with a note or with
+    /// a structured deprecation (reason, `renamed_to`, `removed_in`). See
+    /// also stability.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<DeprecatedSpec>,
 }
 
 /// Implements a human readable display for EnumEntries.
@@ -324,13 +417,14 @@ impl Display for EnumEntriesSpec {
 }
 
 /// The different types of values.
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum ValueSpec {
     /// A integer value.
     Int(i64),
     /// A double value.
+    #[schemars(with = "f64")]
     Double(OrderedFloat<f64>),
     /// A string value.
     String(String),
@@ -349,7 +443,7 @@ impl Display for ValueSpec {
 }
 
 /// The different types of examples.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum ExamplesSpec {
@@ -358,12 +452,14 @@ pub enum ExamplesSpec {
     /// A integer example.
     Int(i64),
     /// A double example.
+    #[schemars(with = "f64")]
     Double(OrderedFloat<f64>),
     /// A string example.
     String(String),
     /// A array of integers example.
     Ints(Vec<i64>),
     /// A array of doubles example.
+    #[schemars(with = "Vec<f64>")]
     Doubles(Vec<OrderedFloat<f64>>),
     /// A array of bools example.
     Bools(Vec<bool>),
@@ -372,7 +468,7 @@ pub enum ExamplesSpec {
 }
 
 /// The different requirement level specifications.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 #[serde(untagged)]
 pub enum RequirementLevelSpec {
@@ -414,7 +510,7 @@ impl Default for RequirementLevelSpec {
 }
 
 /// The different types of basic requirement levels.
-#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum BasicRequirementLevelSpec {
     /// A required requirement level.
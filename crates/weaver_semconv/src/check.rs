@@ -0,0 +1,584 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Checks applied to a semantic convention registry beyond the syntactic
+//! validation performed while loading it.
+//!
+//! Each check is identified by a stable rule id so the findings can be
+//! reported, filtered and documented individually:
+//! - `metric-counter-monotonic`: a counter can't declare non-monotonic
+//!   semantics (`monotonic: false`, or a `.usage`/`.utilization`/`.limit`
+//!   name that calls for an updowncounter or a gauge).
+//! - `metric-non-counter-monotonic`: only counters can declare a monotonic sum
+//!   (`monotonic: true`).
+//! - `metric-bucket-advice`: explicit bucket boundaries can only be advised for
+//!   histograms and must be strictly increasing.
+//! - `metric-unit-duration`: durations should be measured in seconds (`s`).
+//! - `metric-unit-utilization`: utilizations are dimensionless (`1`).
+//! - `metric-unit-count`: counts should use a curly-brace annotation
+//!   (e.g. `{request}`) instead of `1`.
+//! - `metric-name-charset`: a metric name must start with a letter followed by
+//!   letters, digits, `_`, `.`, `-` or `/`.
+//! - `metric-name-length`: a metric name can't exceed 255 characters.
+//! - `metric-name-trailing-dot`: a metric name can't end with a dot.
+//! - `metric-name-namespace`: a metric name should be prefixed by a namespace
+//!   (e.g. `http.server.`) without empty segments.
+//! - `metric-name-collision`: two metrics of the registry can't have names
+//!   differing only by their case.
+//! - `deprecated-renamed-to`: the `renamed_to` field of a structured
+//!   deprecation should name an attribute (or, for a metric group, a metric)
+//!   of the registry other than the deprecated one.
+//!
+//! The metric rules also apply to the metrics defined outside of the registry,
+//! e.g. locally by a telemetry schema (see [`check_metric`]).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+use crate::attribute::AttributeSpec;
+use crate::group::{ConvTypeSpec, GroupSpec, InstrumentSpec, MetricAdviceSpec};
+use crate::SemConvSpecs;
+
+/// The severity of a finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The registry violates a constraint of the semantic conventions.
+    Error,
+    /// The registry doesn't follow a common practice.
+    Warning,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A finding reported by a check.
+#[derive(Debug, Clone)]
+pub struct Finding {
+    /// The id of the rule that reported the finding.
+    pub rule_id: &'static str,
+    /// The severity of the finding.
+    pub severity: Severity,
+    /// The id of the group concerned by the finding.
+    pub group_id: String,
+    /// The provenance (path or URL) of the group.
+    pub provenance: String,
+    /// The JSON pointer of the offending field in the semantic convention
+    /// document (e.g. `/groups/4/unit`), if known.
+    pub pointer: Option<String>,
+    /// A human readable description of the finding.
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} [{}] group `{}` ({}): {}",
+            self.severity, self.rule_id, self.group_id, self.provenance, self.message
+        )
+    }
+}
+
+/// Name suffixes describing a value that can go up and down.
+const NON_MONOTONIC_SUFFIXES: &[&str] = &[".usage", ".utilization", ".limit"];
+
+/// Maximum length of a metric name (see the OTel instrument name syntax).
+const MAX_METRIC_NAME_LENGTH: usize = 255;
+
+impl SemConvSpecs {
+    /// Checks all the groups of the registry and returns the findings.
+    /// Note: the specs must be kept by the resolver (see
+    /// [`crate::ResolverConfig::with_keep_specs`]).
+    pub fn check(&self) -> Vec<Finding> {
+        let mut findings: Vec<Finding> = self
+            .groups_with_provenance()
+            .flat_map(|group| {
+                check_metric_group(&group.spec).into_iter().map(
+                    move |(rule_id, severity, message)| Finding {
+                        rule_id,
+                        severity,
+                        group_id: group.spec.id.clone(),
+                        provenance: group.provenance.clone(),
+                        pointer: group.spec.pointer.clone(),
+                        message,
+                    },
+                )
+            })
+            .collect();
+        findings.extend(self.check_metric_name_collisions());
+        findings.extend(self.check_renames());
+        findings
+    }
+
+    /// Checks that the structured deprecations of the attributes and the
+    /// metric groups are renamed to an attribute or a metric of the registry.
+    fn check_renames(&self) -> Vec<Finding> {
+        let groups: Vec<_> = self.groups_with_provenance().collect();
+        let qualified_id = |group: &GroupSpec, id: &str| {
+            if group.prefix.is_empty() {
+                id.to_owned()
+            } else {
+                format!("{}.{}", group.prefix, id)
+            }
+        };
+        let attributes: HashSet<String> = groups
+            .iter()
+            .flat_map(|group| {
+                group.spec.attributes.iter().filter_map(|attr| match attr {
+                    AttributeSpec::Id { id, .. } => Some(qualified_id(&group.spec, id)),
+                    AttributeSpec::Ref { .. } => None,
+                })
+            })
+            .collect();
+        let metrics: HashSet<&str> = groups
+            .iter()
+            .filter(|group| group.spec.r#type == ConvTypeSpec::Metric)
+            .filter_map(|group| group.spec.metric_name.as_deref())
+            .collect();
+
+        let mut findings = vec![];
+        for group in groups.iter() {
+            let mut renames = vec![];
+            for (index, attr) in group.spec.attributes.iter().enumerate() {
+                if let AttributeSpec::Id {
+                    id,
+                    deprecated: Some(deprecated),
+                    ..
+                } = attr
+                {
+                    if let Some(renamed_to) = deprecated.renamed_to() {
+                        renames.push((
+                            "attribute",
+                            qualified_id(&group.spec, id),
+                            renamed_to,
+                            format!("attributes/{}/deprecated", index),
+                        ));
+                    }
+                }
+            }
+            if let (ConvTypeSpec::Metric, Some(metric_name), Some(deprecated)) = (
+                &group.spec.r#type,
+                &group.spec.metric_name,
+                &group.spec.deprecated,
+            ) {
+                if let Some(renamed_to) = deprecated.renamed_to() {
+                    renames.push((
+                        "metric",
+                        metric_name.clone(),
+                        renamed_to,
+                        "deprecated".to_owned(),
+                    ));
+                }
+            }
+
+            for (kind, name, renamed_to, field) in renames {
+                let known = match kind {
+                    "metric" => metrics.contains(renamed_to),
+                    _ => attributes.contains(renamed_to),
+                };
+                let message = if name == renamed_to {
+                    format!("the {} `{}` is renamed to itself", kind, name)
+                } else if !known {
+                    format!(
+                        "the {} `{}` is renamed to `{}`, which is not defined by the registry",
+                        kind, name, renamed_to
+                    )
+                } else {
+                    continue;
+                };
+                findings.push(Finding {
+                    rule_id: "deprecated-renamed-to",
+                    severity: Severity::Warning,
+                    group_id: group.spec.id.clone(),
+                    provenance: group.provenance.clone(),
+                    pointer: group.spec.field_pointer(&field),
+                    message,
+                });
+            }
+        }
+        findings
+    }
+
+    /// Checks that the metric names of the registry don't collide once
+    /// lowercased. The finding is reported on the last metric defined.
+    fn check_metric_name_collisions(&self) -> Vec<Finding> {
+        let mut metrics: HashMap<String, (String, String, String)> = HashMap::new();
+        let mut findings = vec![];
+        for group in self.groups_with_provenance() {
+            let Some(metric_name) = &group.spec.metric_name else {
+                continue;
+            };
+            if group.spec.r#type != ConvTypeSpec::Metric {
+                continue;
+            }
+            match metrics.get(&metric_name.to_lowercase()) {
+                Some((name, group_id, provenance)) => findings.push(Finding {
+                    rule_id: "metric-name-collision",
+                    severity: Severity::Error,
+                    group_id: group.spec.id.clone(),
+                    provenance: group.provenance.clone(),
+                    pointer: group.spec.field_pointer("metric_name"),
+                    message: format!(
+                        "the metric name `{}` collides with the metric `{}` of the group `{}` ({})",
+                        metric_name, name, group_id, provenance
+                    ),
+                }),
+                None => {
+                    _ = metrics.insert(
+                        metric_name.to_lowercase(),
+                        (
+                            metric_name.clone(),
+                            group.spec.id.clone(),
+                            group.provenance.clone(),
+                        ),
+                    );
+                }
+            }
+        }
+        findings
+    }
+}
+
+/// Checks the instrument and unit of a metric group. Returns the rule id,
+/// the severity and the message of each finding.
+fn check_metric_group(group: &GroupSpec) -> Vec<(&'static str, Severity, String)> {
+    let (Some(instrument), Some(metric_name)) = (&group.instrument, &group.metric_name) else {
+        return vec![];
+    };
+    if group.r#type != ConvTypeSpec::Metric {
+        return vec![];
+    }
+    check_metric_definition(
+        metric_name,
+        instrument,
+        group.unit.as_deref(),
+        group.monotonic,
+        group.advice.as_ref(),
+    )
+}
+
+/// Checks the name, instrument and unit of a metric defined outside of a
+/// semantic convention group (e.g. locally defined by a telemetry schema) with
+/// the rules applied to the metric groups. Returns the rule id, the severity
+/// and the message of each finding.
+pub fn check_metric(
+    metric_name: &str,
+    instrument: &InstrumentSpec,
+    unit: Option<&str>,
+) -> Vec<(&'static str, Severity, String)> {
+    check_metric_definition(metric_name, instrument, unit, None, None)
+}
+
+/// Checks the name, instrument and unit of a metric, and the `monotonic` and
+/// `advice` fields of its definition.
+fn check_metric_definition(
+    metric_name: &str,
+    instrument: &InstrumentSpec,
+    unit: Option<&str>,
+    monotonic: Option<bool>,
+    advice: Option<&MetricAdviceSpec>,
+) -> Vec<(&'static str, Severity, String)> {
+    let mut findings = vec![];
+    findings.extend(check_metric_name(metric_name));
+
+    let non_monotonic_name = NON_MONOTONIC_SUFFIXES
+        .iter()
+        .find(|suffix| metric_name.ends_with(*suffix));
+    match instrument {
+        InstrumentSpec::Counter => {
+            if monotonic == Some(false) {
+                findings.push((
+                    "metric-counter-monotonic",
+                    Severity::Error,
+                    "a counter is monotonic, use an updowncounter for non-monotonic sums"
+                        .to_owned(),
+                ));
+            } else if let Some(suffix) = non_monotonic_name {
+                findings.push((
+                    "metric-counter-monotonic",
+                    Severity::Error,
+                    format!(
+                        "the `{}` suffix of `{}` implies a non-monotonic value, use an updowncounter or a gauge",
+                        suffix, metric_name
+                    ),
+                ));
+            }
+        }
+        _ => {
+            if monotonic == Some(true) {
+                findings.push((
+                    "metric-non-counter-monotonic",
+                    Severity::Error,
+                    format!(
+                        "a {} can't declare a monotonic sum, use a counter",
+                        instrument
+                    ),
+                ));
+            }
+        }
+    }
+
+    if let Some(boundaries) = advice.and_then(|advice| advice.explicit_bucket_boundaries.as_ref()) {
+        if !matches!(instrument, InstrumentSpec::Histogram) {
+            findings.push((
+                "metric-bucket-advice",
+                Severity::Error,
+                format!("a {} can't advise explicit bucket boundaries", instrument),
+            ));
+        } else if !boundaries.windows(2).all(|w| w[0] < w[1]) {
+            findings.push((
+                "metric-bucket-advice",
+                Severity::Error,
+                "explicit bucket boundaries must be strictly increasing".to_owned(),
+            ));
+        }
+    }
+
+    let unit = unit.unwrap_or_default();
+    if metric_name.ends_with(".duration") && unit != "s" {
+        findings.push((
+            "metric-unit-duration",
+            Severity::Warning,
+            format!(
+                "durations should be measured in seconds (`s`), found `{}`",
+                unit
+            ),
+        ));
+    }
+    if metric_name.ends_with(".utilization") && unit != "1" {
+        findings.push((
+            "metric-unit-utilization",
+            Severity::Warning,
+            format!(
+                "utilizations should be dimensionless (`1`), found `{}`",
+                unit
+            ),
+        ));
+    }
+    if matches!(
+        instrument,
+        InstrumentSpec::Counter | InstrumentSpec::UpDownCounter
+    ) && unit == "1"
+        && !metric_name.ends_with(".utilization")
+    {
+        findings.push((
+            "metric-unit-count",
+            Severity::Warning,
+            "counts should use a curly-brace annotation (e.g. `{request}`) instead of `1`"
+                .to_owned(),
+        ));
+    }
+
+    findings
+}
+
+/// Checks a metric name against the OTel naming rules. Returns the rule id,
+/// the severity and the message of each finding.
+fn check_metric_name(metric_name: &str) -> Vec<(&'static str, Severity, String)> {
+    let mut findings = vec![];
+    let mut chars = metric_name.chars();
+    let valid_charset = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '/'));
+    if !valid_charset {
+        findings.push((
+            "metric-name-charset",
+            Severity::Error,
+            format!(
+                "the metric name `{}` must start with a letter followed by letters, digits, `_`, `.`, `-` or `/`",
+                metric_name
+            ),
+        ));
+    }
+    if metric_name.len() > MAX_METRIC_NAME_LENGTH {
+        findings.push((
+            "metric-name-length",
+            Severity::Error,
+            format!(
+                "the metric name `{}` exceeds {} characters ({})",
+                metric_name,
+                MAX_METRIC_NAME_LENGTH,
+                metric_name.len()
+            ),
+        ));
+    }
+    if metric_name.ends_with('.') {
+        findings.push((
+            "metric-name-trailing-dot",
+            Severity::Error,
+            format!("the metric name `{}` can't end with a dot", metric_name),
+        ));
+    }
+    let namespaces: Vec<&str> = metric_name.trim_end_matches('.').split('.').collect();
+    if namespaces.len() < 2 {
+        findings.push((
+            "metric-name-namespace",
+            Severity::Warning,
+            format!(
+                "the metric name `{}` should be prefixed by a namespace (e.g. `http.server.`)",
+                metric_name
+            ),
+        ));
+    } else if namespaces.iter().any(|namespace| namespace.is_empty()) {
+        findings.push((
+            "metric-name-namespace",
+            Severity::Warning,
+            format!(
+                "the metric name `{}` shouldn't contain empty namespaces",
+                metric_name
+            ),
+        ));
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SemConvSpec;
+
+    #[test]
+    fn test_check_metric_groups() {
+        let spec = SemConvSpec::load_from_file("data/metric-checks.yaml").unwrap();
+        let findings: Vec<(String, &'static str)> = spec
+            .groups
+            .iter()
+            .flat_map(|group| {
+                check_metric_group(group)
+                    .into_iter()
+                    .map(|(rule_id, _, _)| (group.id.clone(), rule_id))
+            })
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "metric.counter.usage".to_owned(),
+                    "metric-counter-monotonic"
+                ),
+                ("metric.counter.usage".to_owned(), "metric-unit-count"),
+                (
+                    "metric.gauge.monotonic".to_owned(),
+                    "metric-non-counter-monotonic"
+                ),
+                ("metric.counter.buckets".to_owned(), "metric-bucket-advice"),
+                (
+                    "metric.histogram.buckets".to_owned(),
+                    "metric-bucket-advice"
+                ),
+                (
+                    "metric.histogram.buckets".to_owned(),
+                    "metric-unit-duration"
+                ),
+                (
+                    "metric.gauge.utilization".to_owned(),
+                    "metric-unit-utilization"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_metric_fields() {
+        // The `monotonic` and `advice` fields are only valid for the metrics.
+        let spec = |r#type: &str, field: &str| {
+            SemConvSpecs::default().load_from_str(
+                &format!(
+                    "groups:\n  - id: group\n    type: {}\n    brief: Group\n    metric_name: group.count\n    instrument: counter\n    unit: \"{{group}}\"\n    {}\n",
+                    r#type, field
+                ),
+                "test",
+            )
+        };
+        assert!(spec("metric", "monotonic: true").is_ok());
+        assert!(spec("metric", "advice: {explicit_bucket_boundaries: [1, 2]}").is_ok());
+        assert!(spec("metric", "advice: {unknown: 1}").is_err());
+        assert!(spec("attribute_group", "monotonic: true").is_err());
+        assert!(spec("attribute_group", "advice: {}").is_err());
+    }
+
+    #[test]
+    fn test_check_metric_names() {
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_file("data/metric-names.yaml").unwrap();
+        let findings: Vec<(String, &'static str)> = catalog
+            .check()
+            .into_iter()
+            .map(|finding| (finding.group_id, finding.rule_id))
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![
+                ("metric.name.charset".to_owned(), "metric-name-charset"),
+                ("metric.name.length".to_owned(), "metric-name-length"),
+                (
+                    "metric.name.trailing_dot".to_owned(),
+                    "metric-name-trailing-dot"
+                ),
+                ("metric.name.namespace".to_owned(), "metric-name-namespace"),
+                (
+                    "metric.name.empty_namespace".to_owned(),
+                    "metric-name-namespace"
+                ),
+                ("metric.name.collision".to_owned(), "metric-name-collision"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_renames() {
+        let mut catalog = SemConvSpecs::default();
+        catalog
+            .load_from_str(
+                r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: "HTTP request method."
+        examples: ["GET"]
+      - id: method
+        type: string
+        brief: "Deprecated, use `http.request.method` instead."
+        examples: ["GET"]
+        stability: deprecated
+        deprecated:
+          reason: "The method is part of the request namespace."
+          renamed_to: http.request.method
+      - id: status_code
+        type: int
+        brief: "Deprecated HTTP status code."
+        examples: [200]
+        stability: deprecated
+        deprecated:
+          reason: "The status code is part of the response namespace."
+          renamed_to: http.response.status_code
+"#,
+                "registry.yaml",
+            )
+            .unwrap();
+        let findings: Vec<(String, &'static str, String)> = catalog
+            .check()
+            .into_iter()
+            .map(|finding| (finding.group_id, finding.rule_id, finding.message))
+            .collect();
+
+        assert_eq!(
+            findings,
+            vec![(
+                "registry.http".to_owned(),
+                "deprecated-renamed-to",
+                "the attribute `http.status_code` is renamed to `http.response.status_code`, which is not defined by the registry".to_owned()
+            )]
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compatibility shim for semantic convention files written for the Python
+//! build-tools.
+//!
+//! The build-tools accept a number of fields that are not part of the weaver
+//! syntax (or that use a legacy syntax). In compatibility mode, the YAML
+//! document is normalized before being deserialized:
+//! - the legacy `required` attribute field is converted into the equivalent
+//!   `requirement_level` field,
+//! - any other unknown group or attribute field is moved into the
+//!   `annotations` map of the corresponding group or attribute instead of
+//!   failing the deserialization.
+
+use serde_yaml::{Mapping, Value};
+
+/// Fields defined by the weaver syntax for a group.
+const GROUP_FIELDS: &[&str] = &[
+    "id",
+    "type",
+    "brief",
+    "note",
+    "prefix",
+    "extends",
+    "stability",
+    "deprecated",
+    "attributes",
+    "constraints",
+    "span_kind",
+    "events",
+    "span_name",
+    "metric_name",
+    "instrument",
+    "unit",
+    "monotonic",
+    "advice",
+    "name",
+    "body",
+    "annotations",
+];
+
+/// Fields defined by the weaver syntax for an attribute (id or ref).
+const ATTRIBUTE_FIELDS: &[&str] = &[
+    "id",
+    "ref",
+    "type",
+    "brief",
+    "examples",
+    "tag",
+    "requirement_level",
+    "sampling_relevant",
+    "note",
+    "stability",
+    "deprecated",
+    "annotations",
+];
+
+/// Normalizes, in place, a semantic convention document written for the
+/// Python build-tools into a document accepted by the weaver syntax.
+pub fn normalize_build_tools_spec(doc: &mut Value) {
+    let groups = doc
+        .as_mapping_mut()
+        .and_then(|doc| doc.get_mut("groups"))
+        .and_then(Value::as_sequence_mut);
+
+    if let Some(groups) = groups {
+        for group in groups.iter_mut().filter_map(Value::as_mapping_mut) {
+            if let Some(attributes) = group.get_mut("attributes").and_then(Value::as_sequence_mut) {
+                for attribute in attributes.iter_mut().filter_map(Value::as_mapping_mut) {
+                    upgrade_required_field(attribute);
+                    move_unknown_fields(attribute, ATTRIBUTE_FIELDS);
+                }
+            }
+            move_unknown_fields(group, GROUP_FIELDS);
+        }
+    }
+}
+
+/// Converts the legacy `required` field (`always` or `conditional: <text>`)
+/// into a `requirement_level` field. An explicit `requirement_level` always
+/// takes precedence over the legacy field.
+fn upgrade_required_field(attribute: &mut Mapping) {
+    let required = match attribute.get("required") {
+        Some(required) if !attribute.contains_key("requirement_level") => required.clone(),
+        _ => return,
+    };
+
+    let requirement_level = match &required {
+        Value::String(level) if level == "always" => Value::String("required".to_owned()),
+        Value::Mapping(conditional) => match conditional.get("conditional") {
+            Some(Value::String(text)) => {
+                let mut level = Mapping::new();
+                _ = level.insert(
+                    Value::String("conditionally_required".to_owned()),
+                    Value::String(text.clone()),
+                );
+                Value::Mapping(level)
+            }
+            _ => return,
+        },
+        _ => return,
+    };
+
+    _ = attribute.remove("required");
+    _ = attribute.insert(
+        Value::String("requirement_level".to_owned()),
+        requirement_level,
+    );
+}
+
+/// Moves every field not listed in `known_fields` into the `annotations`
+/// map of the given node.
+fn move_unknown_fields(node: &mut Mapping, known_fields: &[&str]) {
+    let unknown_keys: Vec<Value> = node
+        .keys()
+        .filter(|key| !matches!(key.as_str(), Some(key) if known_fields.contains(&key)))
+        .cloned()
+        .collect();
+    if unknown_keys.is_empty() {
+        return;
+    }
+
+    let mut annotations = match node.remove("annotations") {
+        Some(Value::Mapping(annotations)) => annotations,
+        _ => Mapping::new(),
+    };
+    for key in unknown_keys {
+        if let Some(value) = node.remove(&key) {
+            _ = annotations.insert(key, value);
+        }
+    }
+    _ = node.insert(
+        Value::String("annotations".to_owned()),
+        Value::Mapping(annotations),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{AttributeSpec, BasicRequirementLevelSpec, RequirementLevelSpec};
+    use crate::SemConvSpec;
+
+    #[test]
+    fn test_build_tools_compat() {
+        // Strict mode rejects the build-tools extensions.
+        assert!(SemConvSpec::load_from_file("data/build-tools-compat.yaml").is_err());
+
+        let spec = SemConvSpec::load_from_file_compat("data/build-tools-compat.yaml").unwrap();
+        let group = &spec.groups[0];
+        assert_eq!(
+            group
+                .annotations
+                .get("display_name")
+                .and_then(|v| v.as_str()),
+            Some("Legacy HTTP")
+        );
+
+        match &group.attributes[0] {
+            AttributeSpec::Id {
+                requirement_level,
+                annotations,
+                ..
+            } => {
+                assert!(matches!(
+                    requirement_level,
+                    RequirementLevelSpec::Basic(BasicRequirementLevelSpec::Required)
+                ));
+                assert!(annotations.contains_key("x-custom"));
+            }
+            _ => panic!("Expected an attribute id"),
+        }
+
+        match &group.attributes[1] {
+            AttributeSpec::Id {
+                requirement_level: RequirementLevelSpec::ConditionallyRequired { text },
+                ..
+            } => assert_eq!(text, "If available."),
+            _ => panic!("Expected a conditionally required attribute id"),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deprecation specification.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The deprecation of an attribute or a group, either a free-form note or a
+/// structured deprecation the resolver and the code generators can follow
+/// without parsing prose.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum DeprecatedSpec {
+    /// A free-form deprecation note. It MUST specify why the definition is
+    /// deprecated and/or what to use instead.
+    Note(String),
+    /// A structured deprecation.
+    Structured(StructuredDeprecationSpec),
+}
+
+/// A structured deprecation.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(deny_unknown_fields)]
+pub struct StructuredDeprecationSpec {
+    /// Why the definition is deprecated.
+    pub reason: String,
+    /// The id of the attribute or the name of the metric replacing the
+    /// deprecated definition, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_to: Option<String>,
+    /// The version in which the deprecated definition is planned to be
+    /// removed, if any.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_in: Option<String>,
+}
+
+impl DeprecatedSpec {
+    /// Returns the reason of the deprecation (the note of a free-form
+    /// deprecation).
+    pub fn reason(&self) -> &str {
+        match self {
+            DeprecatedSpec::Note(note) => note,
+            DeprecatedSpec::Structured(deprecation) => &deprecation.reason,
+        }
+    }
+
+    /// Returns the id or the name replacing the deprecated definition, if
+    /// declared by a structured deprecation.
+    pub fn renamed_to(&self) -> Option<&str> {
+        match self {
+            DeprecatedSpec::Note(_) => None,
+            DeprecatedSpec::Structured(deprecation) => deprecation.renamed_to.as_deref(),
+        }
+    }
+
+    /// Returns the version planned for the removal of the deprecated
+    /// definition, if declared by a structured deprecation.
+    pub fn removed_in(&self) -> Option<&str> {
+        match self {
+            DeprecatedSpec::Note(_) => None,
+            DeprecatedSpec::Structured(deprecation) => deprecation.removed_in.as_deref(),
+        }
+    }
+}
+
+/// Implements a human readable display for the deprecation: the reason
+/// followed by the rename and the removal version, if any.
+impl Display for DeprecatedSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.reason().trim())?;
+        if let Some(renamed_to) = self.renamed_to() {
+            write!(f, " Renamed to `{}`.", renamed_to)?;
+        }
+        if let Some(removed_in) = self.removed_in() {
+            write!(f, " Removed in {}.", removed_in)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_spec() {
+        let note: DeprecatedSpec =
+            serde_yaml::from_str("\"Replaced by `http.request.method`.\"").unwrap();
+        assert_eq!(note.reason(), "Replaced by `http.request.method`.");
+        assert_eq!(note.renamed_to(), None);
+        assert_eq!(note.to_string(), "Replaced by `http.request.method`.");
+
+        let structured: DeprecatedSpec = serde_yaml::from_str(
+            "reason: The method is now part of the request namespace.\nrenamed_to: http.request.method\nremoved_in: 2.0.0",
+        )
+        .unwrap();
+        assert_eq!(structured.renamed_to(), Some("http.request.method"));
+        assert_eq!(structured.removed_in(), Some("2.0.0"));
+        assert_eq!(
+            structured.to_string(),
+            "The method is now part of the request namespace. Renamed to `http.request.method`. Removed in 2.0.0."
+        );
+        // A structured deprecation is serialized back as a map and a note as
+        // a string.
+        assert_eq!(
+            serde_yaml::to_string(&structured).unwrap(),
+            "reason: The method is now part of the request namespace.\nrenamed_to: http.request.method\nremoved_in: 2.0.0\n"
+        );
+        assert_eq!(
+            serde_yaml::to_string(&note).unwrap(),
+            "Replaced by `http.request.method`.\n"
+        );
+
+        assert!(serde_yaml::from_str::<DeprecatedSpec>("renamed_to: http.request.method").is_err());
+        assert!(
+            serde_yaml::from_str::<DeprecatedSpec>("reason: Renamed.\nrenamed: http.method")
+                .is_err()
+        );
+    }
+}
@@ -4,17 +4,23 @@
 
 //! A group specification.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use validator::{Validate, ValidationError};
 
-use crate::attribute::{AttributeSpec, AttributeTypeSpec, PrimitiveOrArrayTypeSpec};
+use crate::attribute::{
+    AttributeSpec, AttributeTypeSpec, ExamplesSpec, PrimitiveOrArrayTypeSpec, RequirementLevelSpec,
+};
+use crate::deprecated::DeprecatedSpec;
 use crate::group::InstrumentSpec::{Counter, Gauge, Histogram, UpDownCounter};
+use crate::position::Position;
 use crate::stability::StabilitySpec;
 
 /// Group Spec contain the list of semantic conventions and it is the root node
 /// of each yaml file.
-#[derive(Serialize, Deserialize, Debug, Validate, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Validate, Clone)]
 #[serde(deny_unknown_fields)]
 #[validate(schema(function = "validate_group"))]
 pub struct GroupSpec {
@@ -28,14 +34,17 @@ pub struct GroupSpec {
     /// A more elaborate description of the semantic convention.
     /// It defaults to an empty string.
     #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub note: String,
     /// Prefix for the attributes for this semantic convention.
     /// It defaults to an empty string.
     #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub prefix: String,
     /// Reference another semantic convention id. It inherits the prefix,
     /// constraints, and all attributes defined in the specified semantic
     /// convention.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub extends: Option<String>,
     /// Specifies the stability of the semantic convention.
     /// Note that, if stability is missing but deprecated is present, it will
@@ -44,29 +53,40 @@ pub struct GroupSpec {
     /// error.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stability: Option<StabilitySpec>,
-    /// Specifies if the semantic convention is deprecated. The string
-    /// provided as <description> MUST specify why it's deprecated and/or what
-    /// to use instead. See also stability.
+    /// Specifies if the semantic convention is deprecated, either with a note
+    /// which MUST specify why it's deprecated and/or what to use instead, or
+    /// with a structured deprecation (reason, `renamed_to`, `removed_in`). See
+    /// also stability.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub deprecated: Option<String>,
+    pub deprecated: Option<DeprecatedSpec>,
     /// List of attributes that belong to the semantic convention.
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub attributes: Vec<AttributeSpec>,
     /// Additional constraints.
     /// Allow to define additional requirements on the semantic convention.
     /// It defaults to an empty list.
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub constraints: Vec<ConstraintSpec>,
     /// Specifies the kind of the span.
     /// Note: only valid if type is span (the default)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub span_kind: Option<SpanKindSpec>,
     /// List of strings that specify the ids of event semantic conventions
     /// associated with this span semantic convention.
     /// Note: only valid if type is span (the default)
     #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub events: Vec<String>,
+    /// Guidance on the name of the spans of the semantic convention: the
+    /// recommended format of the span name and examples.
+    /// Note: only valid if type is span (the default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span_name: Option<SpanNameSpec>,
     /// The metric name as described by the [OpenTelemetry Specification](https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/metrics/data-model.md#timeseries-model).
     /// Note: This field is required if type is metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub metric_name: Option<String>,
     /// The instrument type that should be used to record the metric. Note that
     /// the semantic conventions must be written using the names of the
@@ -74,14 +94,114 @@ pub struct GroupSpec {
     /// histogram).
     /// For more details: [Metrics semantic conventions - Instrument types](https://github.com/open-telemetry/opentelemetry-specification/tree/main/specification/metrics/semantic_conventions#instrument-types).
     /// Note: This field is required if type is metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub instrument: Option<InstrumentSpec>,
     /// The unit in which the metric is measured, which should adhere to the
     /// [guidelines](https://github.com/open-telemetry/opentelemetry-specification/tree/main/specification/metrics/semantic_conventions#instrument-units).
     /// Note: This field is required if type is metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub unit: Option<String>,
+    /// Whether the sum recorded by the metric is monotonic (only counters can
+    /// record a monotonic sum).
+    /// Note: only valid if type is metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monotonic: Option<bool>,
+    /// Advice on the aggregation of the metric by the SDKs.
+    /// Note: only valid if type is metric.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub advice: Option<MetricAdviceSpec>,
     /// The name of the event. If not specified, the prefix is used.
     /// If prefix is empty (or unspecified), name is required.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    /// The fields of the body of the event (structured body).
+    /// Note: only valid if type is event
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub body: Vec<BodyFieldSpec>,
+    /// Extension fields not defined by the weaver syntax (e.g. fields only
+    /// understood by the Python build-tools). They are collected when a
+    /// registry is loaded in build-tools compatibility mode.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    #[schemars(with = "BTreeMap<String, serde_json::Value>")]
+    pub annotations: BTreeMap<String, serde_yaml::Value>,
+    /// The position of the group in its semantic convention file, if known
+    /// (not part of the syntax).
+    #[serde(skip)]
+    pub position: Option<Position>,
+    /// The position of the attributes of the group (by id or reference) in
+    /// the semantic convention file, if known (not part of the syntax).
+    #[serde(skip)]
+    pub attribute_positions: BTreeMap<String, Position>,
+    /// The JSON pointer of the group in its semantic convention document
+    /// (e.g. `/groups/4`), if known (not part of the syntax).
+    #[serde(skip)]
+    pub pointer: Option<String>,
+}
+
+impl GroupSpec {
+    /// Returns the position of an attribute of the group in its semantic
+    /// convention file, if known.
+    pub fn attribute_position(&self, attr: &AttributeSpec) -> Option<Position> {
+        let name = match attr {
+            AttributeSpec::Ref { r#ref, .. } => r#ref,
+            AttributeSpec::Id { id, .. } => id,
+        };
+        self.attribute_positions.get(name).copied()
+    }
+
+    /// Returns the JSON pointer of an attribute of the group in its semantic
+    /// convention document (e.g. `/groups/4/attributes/2`), if known.
+    pub fn attribute_pointer(&self, attr: &AttributeSpec) -> Option<String> {
+        let index = self
+            .attributes
+            .iter()
+            .position(|other| other.id() == attr.id())?;
+        self.field_pointer(&format!("attributes/{}", index))
+    }
+
+    /// Returns the JSON pointer of a field of the group (e.g. `brief` or
+    /// `attributes/2/id`), if the pointer of the group is known.
+    pub fn field_pointer(&self, field: &str) -> Option<String> {
+        self.pointer
+            .as_ref()
+            .map(|pointer| format!("{}/{}", pointer, field))
+    }
+}
+
+/// Returns the cycles of the `extends` chains of the given groups (id and
+/// extended group id, in the order of the registry). Each cycle is reported
+/// once, as the path of the group ids starting and ending with the first
+/// group of the cycle met in the registry.
+pub fn extends_cycles<'a>(
+    groups: impl IntoIterator<Item = (&'a str, Option<&'a str>)>,
+) -> Vec<Vec<String>> {
+    let groups: Vec<(&str, Option<&str>)> = groups.into_iter().collect();
+    let extends: HashMap<&str, Option<&str>> = groups.iter().copied().collect();
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut cycles = vec![];
+
+    for (id, _) in groups {
+        let mut path: Vec<&str> = Vec::new();
+        let mut current = Some(id);
+        while let Some(group_id) = current {
+            if let Some(start) = path.iter().position(|id| *id == group_id) {
+                let mut cycle: Vec<String> =
+                    path[start..].iter().map(|id| id.to_string()).collect();
+                cycle.push(group_id.to_owned());
+                cycles.push(cycle);
+                break;
+            }
+            if visited.contains(group_id) {
+                break;
+            }
+            path.push(group_id);
+            current = extends.get(group_id).copied().flatten();
+        }
+        visited.extend(path);
+    }
+    cycles
 }
 
 /// Validation logic for the group.
@@ -109,6 +229,65 @@ fn validate_group(group: &GroupSpec) -> Result<(), ValidationError> {
                 "This group contains an events field but the type is not set to span.",
             ));
         }
+        if group.span_name.is_some() {
+            return Err(ValidationError::new(
+                "This group contains a span_name field but the type is not set to span.",
+            ));
+        }
+    }
+
+    // Fields monotonic and advice are only valid if type is metric.
+    if group.r#type != ConvTypeSpec::Metric {
+        if group.monotonic.is_some() {
+            return Err(ValidationError::new(
+                "This group contains a monotonic field but the type is not set to metric.",
+            ));
+        }
+        if group.advice.is_some() {
+            return Err(ValidationError::new(
+                "This group contains an advice field but the type is not set to metric.",
+            ));
+        }
+    }
+
+    // The span name examples must follow the span name format.
+    if let Some(span_name) = &group.span_name {
+        let invalid_span_name = |message: String| {
+            let mut error = ValidationError::new("invalid_span_name");
+            error.message = Some(message.into());
+            error
+        };
+        if let Err(e) = span_name.segments() {
+            return Err(invalid_span_name(format!(
+                "The span name format `{}` is invalid: {}.",
+                span_name.format, e
+            )));
+        }
+        if let Some(example) = span_name
+            .examples
+            .iter()
+            .find(|example| !span_name.matches(example))
+        {
+            return Err(invalid_span_name(format!(
+                "The span name example `{}` doesn't follow the format `{}`.",
+                example, span_name.format
+            )));
+        }
+    }
+
+    // The body is only valid if type is event and its fields must be
+    // well-formed.
+    if !group.body.is_empty() {
+        if group.r#type != ConvTypeSpec::Event {
+            return Err(ValidationError::new(
+                "This group contains a body field but the type is not set to event.",
+            ));
+        }
+        if let Err(message) = check_body_fields(&group.body, "") {
+            let mut error = ValidationError::new("invalid_body_field");
+            error.message = Some(message.into());
+            return Err(error);
+        }
     }
 
     // Field name is required if prefix is empty and if type is event.
@@ -161,12 +340,57 @@ fn validate_group(group: &GroupSpec) -> Result<(), ValidationError> {
             }
         }
 
+        // The same rule applies to the enum members.
+        if let AttributeSpec::Id {
+            id,
+            r#type: AttributeTypeSpec::Enum { members, .. },
+            ..
+        } = attribute
+        {
+            for member in members {
+                if member.deprecated.is_some()
+                    && member.stability.is_some()
+                    && member.stability != Some(StabilitySpec::Deprecated)
+                {
+                    let mut error = ValidationError::new("invalid_enum_member_stability");
+                    error.message = Some(
+                        format!(
+                            "The member `{}` of the enum attribute `{}` contains a deprecated field but the stability is not set to deprecated.",
+                            member.id, id
+                        )
+                        .into(),
+                    );
+                    return Err(error);
+                }
+            }
+        }
+
         // Examples are required only for string and string array attributes.
         if let AttributeSpec::Id {
-            r#type, examples, ..
+            id,
+            r#type,
+            examples,
+            ..
         } = attribute
         {
-            if examples.is_some() {
+            // The examples must have the type of the attribute.
+            if let Some(examples) = examples {
+                if !r#type.accepts_examples(examples) {
+                    let attribute_id = if group.prefix.is_empty() {
+                        id.clone()
+                    } else {
+                        format!("{}.{}", group.prefix, id)
+                    };
+                    let mut error = ValidationError::new("invalid_example_type");
+                    error.message = Some(
+                        format!(
+                            "The examples of the attribute `{}` don't match its type `{}`.",
+                            attribute_id, r#type
+                        )
+                        .into(),
+                    );
+                    return Err(error);
+                }
                 continue;
             }
 
@@ -186,8 +410,42 @@ fn validate_group(group: &GroupSpec) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Checks that the ids of the given body fields are unique and that only the
+/// `map` fields declare nested fields. The path is the dot-separated id of
+/// the parent field (empty for the top-level fields).
+fn check_body_fields(fields: &[BodyFieldSpec], path: &str) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for field in fields {
+        let field_path = if path.is_empty() {
+            field.id.clone()
+        } else {
+            format!("{}.{}", path, field.id)
+        };
+        if !ids.insert(field.id.as_str()) {
+            return Err(format!("The body field `{}` is duplicated.", field_path));
+        }
+        match (&field.r#type, field.fields.is_empty()) {
+            (BodyFieldTypeSpec::Map, true) => {
+                return Err(format!(
+                    "The body field `{}` is a map but doesn't declare any field.",
+                    field_path
+                ));
+            }
+            (BodyFieldTypeSpec::Map, false) => check_body_fields(&field.fields, &field_path)?,
+            (_, false) => {
+                return Err(format!(
+                    "The body field `{}` declares fields but its type is not map.",
+                    field_path
+                ));
+            }
+            (_, true) => {}
+        }
+    }
+    Ok(())
+}
+
 /// The different types of groups (specification).
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum ConvTypeSpec {
     /// Attribute group (attribute_group type) defines a set of attributes that
@@ -219,7 +477,7 @@ impl Default for ConvTypeSpec {
 }
 
 /// The span kind.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum SpanKindSpec {
     /// An internal span.
@@ -234,8 +492,196 @@ pub enum SpanKindSpec {
     Consumer,
 }
 
+/// Guidance on the name of the spans of a span semantic convention.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpanNameSpec {
+    /// The recommended format of the span name, the attributes being referred
+    /// to by their id between braces, e.g. `{http.request.method} {http.route}`.
+    pub format: String,
+    /// Examples of span names following the format.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+}
+
+/// A segment of a span name format.
+#[derive(Debug, PartialEq)]
+enum SpanNameSegment<'a> {
+    /// A literal text.
+    Literal(&'a str),
+    /// An attribute id (between braces in the format).
+    Attribute(&'a str),
+}
+
+impl SpanNameSpec {
+    /// Returns the segments of the format, or an error if a brace is not
+    /// balanced or an attribute id is empty.
+    fn segments(&self) -> Result<Vec<SpanNameSegment<'_>>, String> {
+        let mut segments = vec![];
+        let mut rest = self.format.as_str();
+        while !rest.is_empty() {
+            match rest.find(['{', '}']) {
+                Some(index) if rest[index..].starts_with('}') => {
+                    return Err("unexpected `}`".to_owned());
+                }
+                Some(index) => {
+                    if index > 0 {
+                        segments.push(SpanNameSegment::Literal(&rest[..index]));
+                    }
+                    let end = rest[index..]
+                        .find('}')
+                        .map(|end| index + end)
+                        .ok_or_else(|| "unclosed `{`".to_owned())?;
+                    let attribute = rest[index + 1..end].trim();
+                    if attribute.is_empty() || attribute.contains('{') {
+                        return Err(format!("invalid attribute `{}`", &rest[index..=end]));
+                    }
+                    segments.push(SpanNameSegment::Attribute(attribute));
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    segments.push(SpanNameSegment::Literal(rest));
+                    rest = "";
+                }
+            }
+        }
+        Ok(segments)
+    }
+
+    /// Returns the ids of the attributes referred to by the format (empty if
+    /// the format is invalid).
+    pub fn attributes(&self) -> Vec<&str> {
+        self.segments()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|segment| match segment {
+                SpanNameSegment::Attribute(attribute) => Some(attribute),
+                SpanNameSegment::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns true if a span name follows the format, each attribute
+    /// matching a non-empty text.
+    pub fn matches(&self, span_name: &str) -> bool {
+        fn matches(segments: &[SpanNameSegment<'_>], name: &str) -> bool {
+            match segments.split_first() {
+                None => name.is_empty(),
+                Some((SpanNameSegment::Literal(literal), rest)) => name
+                    .strip_prefix(literal)
+                    .is_some_and(|name| matches(rest, name)),
+                Some((SpanNameSegment::Attribute(_), rest)) => name
+                    .char_indices()
+                    .map(|(index, _)| index)
+                    .chain(std::iter::once(name.len()))
+                    .filter(|index| *index > 0)
+                    .any(|index| matches(rest, &name[index..])),
+            }
+        }
+        self.segments()
+            .is_ok_and(|segments| matches(&segments, span_name))
+    }
+
+    /// Returns an (anchored) regular expression matching the span names
+    /// following the format, each attribute matching a non-empty text, or an
+    /// error if the format is invalid.
+    pub fn pattern(&self) -> Result<String, String> {
+        let mut pattern = "^".to_owned();
+        for segment in self.segments()? {
+            match segment {
+                SpanNameSegment::Literal(literal) => {
+                    for c in literal.chars() {
+                        if "\\.+*?()|[]{}^$#&-~".contains(c) {
+                            pattern.push('\\');
+                        }
+                        pattern.push(c);
+                    }
+                }
+                SpanNameSegment::Attribute(_) => pattern.push_str(".+"),
+            }
+        }
+        pattern.push('$');
+        Ok(pattern)
+    }
+}
+
+/// A field of the body of an event semantic convention.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BodyFieldSpec {
+    /// The id of the field, unique among the fields of the same level.
+    pub id: String,
+    /// The type of the field.
+    pub r#type: BodyFieldTypeSpec,
+    /// A brief description of the field.
+    #[serde(default)]
+    pub brief: String,
+    /// A more elaborate description of the field.
+    /// It defaults to an empty string.
+    #[serde(default)]
+    pub note: String,
+    /// Specifies if the field is mandatory. Can be "required",
+    /// "conditionally_required", "recommended", or "opt_in". When omitted,
+    /// the field is "recommended".
+    #[serde(default)]
+    pub requirement_level: RequirementLevelSpec,
+    /// Sequence of example values for the field or single example value.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<ExamplesSpec>,
+    /// The nested fields of a `map` field.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<BodyFieldSpec>,
+}
+
+/// The types of the body fields.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BodyFieldTypeSpec {
+    /// A boolean field.
+    Boolean,
+    /// A integer field (signed 64 bit integer).
+    Int,
+    /// A double field (double precision floating point (IEEE 754-1985)).
+    Double,
+    /// A string field.
+    String,
+    /// An array of strings field.
+    #[serde(rename = "string[]")]
+    Strings,
+    /// An array of integer field.
+    #[serde(rename = "int[]")]
+    Ints,
+    /// An array of double field.
+    #[serde(rename = "double[]")]
+    Doubles,
+    /// An array of boolean field.
+    #[serde(rename = "boolean[]")]
+    Booleans,
+    /// A map of nested fields.
+    Map,
+}
+
+/// Implements a human readable display for BodyFieldTypeSpec.
+impl Display for BodyFieldTypeSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BodyFieldTypeSpec::Boolean => write!(f, "boolean"),
+            BodyFieldTypeSpec::Int => write!(f, "int"),
+            BodyFieldTypeSpec::Double => write!(f, "double"),
+            BodyFieldTypeSpec::String => write!(f, "string"),
+            BodyFieldTypeSpec::Strings => write!(f, "string[]"),
+            BodyFieldTypeSpec::Ints => write!(f, "int[]"),
+            BodyFieldTypeSpec::Doubles => write!(f, "double[]"),
+            BodyFieldTypeSpec::Booleans => write!(f, "boolean[]"),
+            BodyFieldTypeSpec::Map => write!(f, "map"),
+        }
+    }
+}
+
 /// Allow to define additional requirements on the semantic convention.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ConstraintSpec {
     /// any_of accepts a list of sequences. Each sequence contains a list of
@@ -249,8 +695,18 @@ pub struct ConstraintSpec {
     pub include: Option<String>,
 }
 
+/// Advice on the aggregation of a metric by the SDKs.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct MetricAdviceSpec {
+    /// The explicit bucket boundaries of a histogram, a strictly increasing
+    /// list of numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explicit_bucket_boundaries: Option<Vec<f64>>,
+}
+
 /// The type of the metric.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum InstrumentSpec {
     /// An up-down counter metric.
@@ -15,22 +15,38 @@
     unused_results,
     unused_extern_crates
 )]
+// The errors carry the location (line, column and JSON pointer) of the
+// offending definition for the diagnostics.
+#![allow(clippy::result_large_err)]
 
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::BufReader;
-use std::path::Path;
+use std::io::{BufReader, Read};
+use std::path::{Component, Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::attribute::AttributeSpec;
-use crate::group::GroupSpec;
-use crate::metric::MetricSpec;
+use crate::attribute::{AttributeSpec, AttributeTypeSpec};
+use crate::group::{BodyFieldSpec, GroupSpec, SpanNameSpec};
+use crate::manifest::RegistryManifest;
+use crate::metric::{MetricGroupSpec, MetricSpec};
+use crate::position::Position;
+use crate::stability::{StabilityFilter, StabilitySpec};
 
 pub mod attribute;
+pub mod check;
+pub mod compat;
+pub mod deprecated;
 pub mod group;
+pub mod lint;
+pub mod manifest;
 pub mod metric;
+pub mod naming;
+pub mod new_group;
+pub mod normalize;
+pub mod position;
+pub mod refactor;
 pub mod stability;
 
 /// An error that can occur while loading a semantic convention registry.
@@ -54,26 +70,55 @@ pub enum Error {
         line: Option<usize>,
         /// The column where the error occurred.
         column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The error that occurred.
         error: String,
     },
 
     /// The semantic convention asset contains a duplicate attribute id.
-    #[error("Duplicate attribute id `{id}` detected while loading {path_or_url:?}, already defined in {origin_path_or_url:?}")]
+    #[error("Duplicate attribute id `{id}` detected while loading {}, already defined in {origin_path_or_url:?}", location(.path_or_url, .line, .column))]
     DuplicateAttributeId {
         /// The path or URL where the attribute id was defined for the first time.
         origin_path_or_url: String,
         /// The path or URL of the semantic convention asset.
         path_or_url: String,
+        /// The line of the duplicated attribute, if known.
+        line: Option<usize>,
+        /// The column of the duplicated attribute, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The duplicated attribute id.
         id: String,
     },
 
+    /// The same attribute id is defined with different types (or enum
+    /// members) by several groups, possibly in different registries.
+    #[error("Attribute `{id}` is defined with conflicting types:\n- {origin}\n- {definition}")]
+    AttributeTypeConflict {
+        /// The conflicting attribute id.
+        id: String,
+        /// The first definition of the attribute.
+        origin: Box<AttributeDefinition>,
+        /// The conflicting definition of the attribute.
+        definition: Box<AttributeDefinition>,
+    },
+
     /// The semantic convention asset contains a duplicate group id.
-    #[error("Duplicate group id `{id}` detected while loading {path_or_url:?} and already defined in {origin}")]
+    #[error("Duplicate group id `{id}` detected while loading {} and already defined in {origin}", location(.path_or_url, .line, .column))]
     DuplicateGroupId {
         /// The path or URL of the semantic convention asset.
         path_or_url: String,
+        /// The line of the duplicated group, if known.
+        line: Option<usize>,
+        /// The column of the duplicated group, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The duplicated group id.
         id: String,
         /// The asset where the group id was already defined.
@@ -81,19 +126,33 @@ pub enum Error {
     },
 
     /// The semantic convention asset contains a duplicate metric name.
-    #[error("Duplicate metric name `{name}` detected while loading {path_or_url:?}")]
+    #[error("Duplicate metric name `{name}` detected while loading {}", location(.path_or_url, .line, .column))]
     DuplicateMetricName {
         /// The path or URL of the semantic convention asset.
         path_or_url: String,
+        /// The line of the metric group, if known.
+        line: Option<usize>,
+        /// The column of the metric group, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The duplicated metric name.
         name: String,
     },
 
     /// The semantic convention asset contains an invalid attribute definition.
-    #[error("Invalid attribute definition detected while resolving {path_or_url:?}, group_id=`{group_id}`.\n{error}")]
+    #[error("Invalid attribute definition detected while resolving {}, group_id=`{group_id}`.\n{error}", location(.path_or_url, .line, .column))]
     InvalidAttribute {
         /// The path or URL of the semantic convention asset.
         path_or_url: String,
+        /// The line of the attribute, if known.
+        line: Option<usize>,
+        /// The column of the attribute, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The group id of the attribute.
         group_id: String,
         /// The reason of the error.
@@ -108,15 +167,214 @@ pub enum Error {
     },
 
     /// The semantic convention asset contains an invalid metric definition.
-    #[error("Invalid metric definition in {path_or_url:?}.\ngroup_id=`{group_id}`.\n{error}")]
+    #[error("Invalid metric definition in {}.\ngroup_id=`{group_id}`.\n{error}", location(.path_or_url, .line, .column))]
     InvalidMetric {
         /// The path or URL of the semantic convention asset.
         path_or_url: String,
+        /// The line of the metric group, if known.
+        line: Option<usize>,
+        /// The column of the metric group, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
         /// The group id of the metric.
         group_id: String,
         /// The reason of the error.
         error: String,
     },
+
+    /// The semantic convention asset contains a group type not supported yet
+    /// by the resolver.
+    ///
+    /// Not returned anymore, all the group types being implemented. Kept for
+    /// compatibility with the code matching this error.
+    #[error("Group type `{group_type}` not implemented yet (group_id=`{group_id}` in {})", location(.path_or_url, .line, .column))]
+    UnimplementedGroupType {
+        /// The path or URL of the semantic convention asset.
+        path_or_url: String,
+        /// The line of the group, if known.
+        line: Option<usize>,
+        /// The column of the group, if known.
+        column: Option<usize>,
+        /// The id of the group.
+        group_id: String,
+        /// The unsupported group type.
+        group_type: String,
+    },
+
+    /// The semantic convention asset contains a stability level unknown to
+    /// this version of weaver.
+    #[error("Unknown stability `{stability}` (group_id=`{group_id}`{} in {})", .attribute_id.as_ref().map(|id| format!(", attribute_id=`{}`", id)).unwrap_or_default(), location(.path_or_url, .line, .column))]
+    UnknownStability {
+        /// The path or URL of the semantic convention asset.
+        path_or_url: String,
+        /// The line of the group or of the attribute, if known.
+        line: Option<usize>,
+        /// The column of the group or of the attribute, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
+        /// The id of the group.
+        group_id: String,
+        /// The id of the attribute (none for the stability of the group).
+        attribute_id: Option<String>,
+        /// The unknown stability level.
+        stability: String,
+    },
+
+    /// A group doesn't define all the attributes of its `any_of` constraint.
+    #[error("Invalid any_of constraint in {}, group_id=`{group_id}`.\nThe attribute(s) {} are not defined by the group (nor by its extends chain or included groups)", location(.path_or_url, .line, .column), .missing.iter().map(|id| format!("`{}`", id)).collect::<Vec<_>>().join(", "))]
+    AnyOfConstraintViolation {
+        /// The path or URL of the semantic convention asset.
+        path_or_url: String,
+        /// The line of the group, if known.
+        line: Option<usize>,
+        /// The column of the group, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
+        /// The id of the group.
+        group_id: String,
+        /// The attribute combinations of the constraint referencing
+        /// attributes not defined by the group.
+        missing: Vec<String>,
+    },
+
+    /// The `extends` chains of groups form a cycle.
+    #[error("Cyclic `extends` chain detected in {}: {}", location(.path_or_url, .line, .column), .group_ids.join(" -> "))]
+    CyclicExtends {
+        /// The path or URL of the semantic convention asset defining the
+        /// first group of the cycle.
+        path_or_url: String,
+        /// The line of the first group of the cycle, if known.
+        line: Option<usize>,
+        /// The column of the first group of the cycle, if known.
+        column: Option<usize>,
+        /// The JSON pointer of the error in the semantic convention document
+        /// (e.g. `/groups/4/attributes/2`), if known.
+        pointer: Option<String>,
+        /// The ids of the groups of the cycle, the first group being repeated
+        /// at the end.
+        group_ids: Vec<String>,
+    },
+
+    /// The lint configuration (or one of its wordlists) is invalid.
+    #[error("Invalid lint configuration {path:?}\n{error}")]
+    InvalidLintConfig {
+        /// The path of the lint configuration or of the wordlist.
+        path: String,
+        /// The reason of the error.
+        error: String,
+    },
+
+    /// A registry refactoring (groups moved between files) can't be applied.
+    #[error("Invalid refactoring of {path:?}\n{error}")]
+    InvalidRefactoring {
+        /// The path of the semantic convention file.
+        path: String,
+        /// The reason of the error.
+        error: String,
+    },
+
+    /// The canonical form of a registry can't be written (see
+    /// [`SemConvSpecs::save_to_file`]).
+    #[error("Failed to write the semantic convention registry {path:?}\n{error}")]
+    RegistryWriteFailed {
+        /// The path of the file or directory.
+        path: String,
+        /// The reason of the error.
+        error: String,
+    },
+
+    /// Several errors collected by a resolution (see
+    /// [`ResolverConfig::with_collect_errors`]).
+    #[error("{} semantic convention error(s)\n{}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))]
+    CompoundError(Vec<Error>),
+}
+
+impl Error {
+    /// Returns the position in the semantic convention file of the error, if
+    /// known (see [`crate::position`]).
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            Error::InvalidCatalog { line, column, .. }
+            | Error::DuplicateAttributeId { line, column, .. }
+            | Error::DuplicateGroupId { line, column, .. }
+            | Error::DuplicateMetricName { line, column, .. }
+            | Error::InvalidAttribute { line, column, .. }
+            | Error::InvalidMetric { line, column, .. }
+            | Error::UnimplementedGroupType { line, column, .. }
+            | Error::UnknownStability { line, column, .. }
+            | Error::AnyOfConstraintViolation { line, column, .. }
+            | Error::CyclicExtends { line, column, .. } => Some(Position {
+                line: (*line)?,
+                column: column.unwrap_or(1),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the JSON pointer of the error in the semantic convention
+    /// document (e.g. `/groups/4/attributes/2/type`), if known (see
+    /// [`crate::position`]).
+    pub fn pointer(&self) -> Option<&str> {
+        match self {
+            Error::InvalidCatalog { pointer, .. }
+            | Error::DuplicateAttributeId { pointer, .. }
+            | Error::DuplicateGroupId { pointer, .. }
+            | Error::DuplicateMetricName { pointer, .. }
+            | Error::InvalidAttribute { pointer, .. }
+            | Error::InvalidMetric { pointer, .. }
+            | Error::UnknownStability { pointer, .. }
+            | Error::AnyOfConstraintViolation { pointer, .. }
+            | Error::CyclicExtends { pointer, .. } => pointer.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the line and the column of an optional position.
+fn line_column(position: Option<Position>) -> (Option<usize>, Option<usize>) {
+    (
+        position.map(|position| position.line),
+        position.map(|position| position.column),
+    )
+}
+
+/// Returns the quoted path or URL of a semantic convention asset, followed
+/// by the line and the column when they are known (`"path:line:column"`).
+fn location(path_or_url: &str, line: &Option<usize>, column: &Option<usize>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => {
+            format!("{:?}", format!("{}:{}:{}", path_or_url, line, column))
+        }
+        (Some(line), None) => format!("{:?}", format!("{}:{}", path_or_url, line)),
+        _ => format!("{:?}", path_or_url),
+    }
+}
+
+/// The definition of an attribute reported in a diagnostic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeDefinition {
+    /// The id of the group defining the attribute.
+    pub group_id: String,
+    /// The path or URL of the semantic convention asset.
+    pub path_or_url: String,
+    /// The type of the attribute.
+    pub r#type: String,
+}
+
+impl std::fmt::Display for AttributeDefinition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` in group `{}` ({:?})",
+            self.r#type, self.group_id, self.path_or_url
+        )
+    }
 }
 
 /// A semantic convention spec with its provenance (path or URL).
@@ -157,7 +415,7 @@ pub struct MetricSpecWithProvenance {
 
 /// A semantic convention specs is a collection of semantic convention
 /// specifications indexed by group id.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct SemConvSpecs {
     /// The number of semantic convention assets added in the semantic convention registry.
     /// A asset can be a semantic convention loaded from a file or an URL.
@@ -177,6 +435,10 @@ pub struct SemConvSpecs {
     /// This collection contains all the metrics defined in the semantic convention registry.
     all_metrics: HashMap<String, MetricSpecWithProvenance>,
 
+    /// Metric groups indexed by their respective id, with their shared
+    /// attributes and their member metrics (resolved).
+    metric_groups: HashMap<String, MetricGroupSpec>,
+
     /// Collection of attribute ids index by group id and defined in a
     /// `resource` semantic convention group.
     /// Attribute ids are references to of attributes defined in the
@@ -212,10 +474,36 @@ pub struct SemConvSpecs {
     /// Attribute ids are references to of attributes defined in the
     /// all_attributes field.
     metric_group_group_attributes: HashMap<String, GroupIds>,
+
+    /// Collection of attribute ids index by group id and defined in a
+    /// `scope` semantic convention group.
+    /// Attribute ids are references to of attributes defined in the
+    /// all_attributes field.
+    scope_group_attributes: HashMap<String, GroupIds>,
+
+    /// Guidance on the span names indexed by span group id (for the span
+    /// groups defining a `span_name`).
+    span_names: HashMap<String, SpanNameSpec>,
+
+    /// The body fields indexed by event name (for the event groups defining
+    /// a `body`).
+    event_bodies: HashMap<String, Vec<BodyFieldSpec>>,
+
+    /// True if the attributes without `stability` must not inherit the
+    /// stability of their group (see [`ResolverConfig::without_stability_inheritance`]).
+    stability_inheritance_disabled: bool,
+
+    /// The minimum stability of the groups and attributes kept in the
+    /// resolved registries and schemas (see [`ResolverConfig::with_stability_filter`]).
+    stability_filter: StabilityFilter,
+
+    /// The manifest of the registries imported by the loader (see
+    /// [`SemConvSpecs::set_registry_manifest`]).
+    registry_manifest: Vec<RegistryManifest>,
 }
 
 /// Represents a collection of ids (attribute or metric ids).
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct GroupIds {
     /// The semantic convention origin (path or URL) where the group id is
     /// defined. This is used to report errors.
@@ -237,10 +525,15 @@ pub struct SemConvSpec {
 }
 
 /// The configuration of the resolver.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ResolverConfig {
     error_when_attribute_ref_not_found: bool,
     keep_specs: bool,
+    build_tools_compat: bool,
+    disable_stability_inheritance: bool,
+    stability_filter: StabilityFilter,
+    tolerant_stability: bool,
+    collect_errors: bool,
 }
 
 impl ResolverConfig {
@@ -252,6 +545,64 @@ impl ResolverConfig {
             ..Default::default()
         }
     }
+
+    /// Instructs the loader to accept the YAML extensions supported by the
+    /// Python build-tools (see the [`compat`] module).
+    pub fn with_build_tools_compat(mut self) -> Self {
+        self.build_tools_compat = true;
+        self
+    }
+
+    /// Returns true if the build-tools compatibility mode is enabled.
+    pub fn build_tools_compat(&self) -> bool {
+        self.build_tools_compat
+    }
+
+    /// Instructs the resolver to keep the attributes without `stability`
+    /// unstable instead of making them inherit the stability of their group.
+    pub fn without_stability_inheritance(mut self) -> Self {
+        self.disable_stability_inheritance = true;
+        self
+    }
+
+    /// Instructs the resolver to exclude the groups and attributes below the
+    /// given stability from the resolved registries and schemas.
+    pub fn with_stability_filter(mut self, stability_filter: StabilityFilter) -> Self {
+        self.stability_filter = stability_filter;
+        self
+    }
+
+    /// Returns the minimum stability of the groups and attributes kept by the
+    /// resolver.
+    pub fn stability_filter(&self) -> StabilityFilter {
+        self.stability_filter
+    }
+
+    /// Instructs the resolver to fail on the constructs it doesn't implement
+    /// yet instead of reporting them as warnings.
+    ///
+    /// All the group types are implemented since the resolution of the
+    /// `metric_group` groups, so this option has no effect.
+    #[deprecated(note = "all the group types are implemented, this option has no effect")]
+    pub fn with_fail_on_unimplemented(self) -> Self {
+        self
+    }
+
+    /// Instructs the resolver to keep the stability levels it doesn't know
+    /// (e.g. levels introduced by a newer version of the semantic
+    /// conventions) and to report them as warnings instead of errors.
+    pub fn with_tolerant_stability(mut self) -> Self {
+        self.tolerant_stability = true;
+        self
+    }
+
+    /// Instructs the resolver to go on after an error and to return all the
+    /// errors of the registry in an [`Error::CompoundError`] (a single error
+    /// is returned as is), instead of returning the first error.
+    pub fn with_collect_errors(mut self) -> Self {
+        self.collect_errors = true;
+        self
+    }
 }
 
 /// A wrapper for a resolver error that is considered as a warning
@@ -263,21 +614,29 @@ pub struct ResolverWarning {
 }
 
 /// Structure to keep track of the source of the attribute to resolve.
-struct AttributeToResolve {
+/// The fields borrow the specs of the registry, so no string is copied for
+/// the (numerous) references resolved successfully.
+struct AttributeToResolve<'a> {
     /// The provenance of the attribute.
     /// Path or URL of the semantic convention asset.
-    path_or_url: String,
-    /// The group id of the attribute.
-    group_id: String,
+    path_or_url: &'a str,
+    /// The group of the attribute.
+    group: &'a GroupSpec,
+    /// The attribute spec.
+    attr: &'a AttributeSpec,
     /// The attribute reference.
-    r#ref: String,
+    r#ref: &'a str,
+    /// The position of the attribute reference, if known.
+    position: Option<Position>,
 }
 
 /// Structure to keep track of the source of the metric to resolve.
-struct MetricToResolve {
-    path_or_url: String,
-    group_id: String,
-    r#ref: String,
+struct MetricToResolve<'a> {
+    path_or_url: &'a str,
+    group_id: &'a str,
+    r#ref: &'a str,
+    position: Option<Position>,
+    pointer: Option<String>,
 }
 
 impl SemConvSpecs {
@@ -289,6 +648,7 @@ impl SemConvSpecs {
                 path_or_url: path.as_ref().display().to_string(),
                 line: None,
                 column: None,
+                pointer: position::validation_pointer(&spec.groups, &e),
                 error: e.to_string(),
             });
         }
@@ -299,16 +659,103 @@ impl SemConvSpecs {
         Ok(())
     }
 
+    /// Load and add a semantic convention spec from its YAML or JSON content (e.g.
+    /// the content of a file being edited) to the semantic convention
+    /// registry. The provenance identifies the content in the errors.
+    pub fn load_from_str(&mut self, content: &str, provenance: &str) -> Result<(), Error> {
+        let spec = SemConvSpec::load_from_str(content, provenance)?;
+        if let Err(e) = spec.validate() {
+            return Err(Error::InvalidCatalog {
+                path_or_url: provenance.to_owned(),
+                line: None,
+                column: None,
+                pointer: position::validation_pointer(&spec.groups, &e),
+                error: e.to_string(),
+            });
+        }
+        self.specs.push(SemConvSpecWithProvenance {
+            spec,
+            provenance: provenance.to_owned(),
+        });
+        Ok(())
+    }
+
+    /// Loads and adds all the semantic convention files of a directory to the
+    /// semantic convention registry, e.g. the `model` directory of a local
+    /// checkout of the semantic conventions. The `*.yaml` and `*.yml` files
+    /// are recursively collected (see [`is_semconv_file`]) and loaded in file
+    /// name order, hidden files and directories are skipped.
+    ///
+    /// Returns the number of loaded files.
+    pub fn load_from_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<usize, Error> {
+        let dir = dir.as_ref();
+        let mut paths = vec![];
+        for entry in walkdir::WalkDir::new(dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_hidden(entry.file_name()))
+        {
+            let entry = entry.map_err(|e| Error::CatalogNotFound {
+                path_or_url: dir.display().to_string(),
+                error: e.to_string(),
+            })?;
+            if entry.file_type().is_file() && is_semconv_file(entry.path()) {
+                paths.push(entry.into_path());
+            }
+        }
+        self.load_from_paths(paths)
+    }
+
+    /// Loads and adds all the semantic convention files matching a glob
+    /// pattern (e.g. `model/**/*.yaml`) to the semantic convention registry.
+    /// The files not recognized as semantic convention files (see
+    /// [`is_semconv_file`]) are skipped, as well as the hidden files and
+    /// directories not explicitly named by the pattern.
+    ///
+    /// Returns the number of loaded files.
+    pub fn load_from_glob(&mut self, pattern: &str) -> Result<usize, Error> {
+        let options = glob::MatchOptions {
+            require_literal_leading_dot: true,
+            ..glob::MatchOptions::default()
+        };
+        let glob_error = |error: String| Error::CatalogNotFound {
+            path_or_url: pattern.to_owned(),
+            error,
+        };
+        let mut paths = vec![];
+        for path in glob::glob_with(pattern, options).map_err(|e| glob_error(e.to_string()))? {
+            let path = path.map_err(|e| glob_error(e.to_string()))?;
+            if path.is_file() && is_semconv_file(&path) && !is_hidden_match(pattern, &path) {
+                paths.push(path);
+            }
+        }
+        self.load_from_paths(paths)
+    }
+
+    /// Loads and adds the given semantic convention files to the semantic
+    /// convention registry and returns their number.
+    fn load_from_paths(&mut self, paths: Vec<PathBuf>) -> Result<usize, Error> {
+        let count = paths.len();
+        for path in paths {
+            self.load_from_file(path)?;
+        }
+        Ok(count)
+    }
+
     /// Loads and returns the semantic convention spec from a file.
+    /// When `build_tools_compat` is true, the Python build-tools YAML
+    /// extensions are accepted.
     pub fn load_sem_conv_spec_from_file(
         sem_conv_path: &Path,
+        build_tools_compat: bool,
     ) -> Result<(String, SemConvSpec), Error> {
-        let spec = SemConvSpec::load_from_file(sem_conv_path)?;
+        let spec = SemConvSpec::from_file(sem_conv_path, build_tools_compat)?;
         if let Err(e) = spec.validate() {
             return Err(Error::InvalidCatalog {
                 path_or_url: sem_conv_path.display().to_string(),
                 line: None,
                 column: None,
+                pointer: position::validation_pointer(&spec.groups, &e),
                 error: e.to_string(),
             });
         }
@@ -316,13 +763,19 @@ impl SemConvSpecs {
     }
 
     /// Downloads and returns the semantic convention spec from an URL.
-    pub fn load_sem_conv_spec_from_url(sem_conv_url: &str) -> Result<(String, SemConvSpec), Error> {
-        let spec = SemConvSpec::load_from_url(sem_conv_url)?;
+    /// When `build_tools_compat` is true, the Python build-tools YAML
+    /// extensions are accepted.
+    pub fn load_sem_conv_spec_from_url(
+        sem_conv_url: &str,
+        build_tools_compat: bool,
+    ) -> Result<(String, SemConvSpec), Error> {
+        let spec = SemConvSpec::from_url(sem_conv_url, build_tools_compat)?;
         if let Err(e) = spec.validate() {
             return Err(Error::InvalidCatalog {
                 path_or_url: sem_conv_url.to_string(),
                 line: None,
                 column: None,
+                pointer: position::validation_pointer(&spec.groups, &e),
                 error: e.to_string(),
             });
         }
@@ -334,6 +787,32 @@ impl SemConvSpecs {
         self.asset_count
     }
 
+    /// Returns true if the attributes without `stability` inherit the
+    /// stability of their group during the resolution (the default).
+    pub fn stability_inheritance(&self) -> bool {
+        !self.stability_inheritance_disabled
+    }
+
+    /// Returns the minimum stability of the groups and attributes kept in the
+    /// resolved registries and schemas.
+    pub fn stability_filter(&self) -> StabilityFilter {
+        self.stability_filter
+    }
+
+    /// Sets the manifest of the registries from which the semantic convention
+    /// specs have been loaded (URL, commit, reference, fetch time and number
+    /// of files of each imported registry).
+    pub fn set_registry_manifest(&mut self, registry_manifest: Vec<RegistryManifest>) {
+        self.registry_manifest = registry_manifest;
+    }
+
+    /// Returns the manifest of the registries from which the semantic
+    /// convention specs have been loaded (empty if the specs have not been
+    /// loaded from imports).
+    pub fn registry_manifest(&self) -> &[RegistryManifest] {
+        &self.registry_manifest
+    }
+
     /// Append a list of semantic convention specs to the semantic convention registry.
     pub fn append_sem_conv_specs(&mut self, specs: Vec<SemConvSpecWithProvenance>) {
         self.specs.extend(specs);
@@ -353,67 +832,101 @@ impl SemConvSpecs {
     /// setting the `error_when_<...>_ref_not_found` to `false`, in which case
     /// the resolver will record the error in a warning list and continue.
     /// The warning list is returned as a list of warnings in the result.
+    ///
+    /// By default, the resolution stops at the first error. With
+    /// [`ResolverConfig::with_collect_errors`], all the errors are collected
+    /// and returned at the end of the resolution.
     pub fn resolve(&mut self, config: ResolverConfig) -> Result<Vec<ResolverWarning>, Error> {
+        let (warnings, mut errors) = self.resolve_collecting(config)?;
+        match errors.len() {
+            0 => Ok(warnings),
+            1 => Err(errors.swap_remove(0)),
+            _ => Err(Error::CompoundError(errors)),
+        }
+    }
+
+    /// Resolves all the references present in the semantic convention
+    /// registry in lenient mode: all the errors are collected and returned
+    /// with the warnings instead of failing the resolution, the definitions
+    /// that could be resolved remaining available (e.g. to explore a
+    /// partially broken registry while fixing it).
+    pub fn resolve_lenient(
+        &mut self,
+        config: ResolverConfig,
+    ) -> (Vec<ResolverWarning>, Vec<Error>) {
+        self.resolve_collecting(config.with_collect_errors())
+            .unwrap_or_else(|error| (vec![], vec![error]))
+    }
+
+    /// Resolves the registry and returns its warnings and the errors
+    /// collected (see [`ResolverConfig::with_collect_errors`]), or the first
+    /// error if the errors are not collected.
+    fn resolve_collecting(
+        &mut self,
+        config: ResolverConfig,
+    ) -> Result<(Vec<ResolverWarning>, Vec<Error>), Error> {
         let mut warnings = Vec::new();
+        let mut errors = Vec::new();
         let mut attributes_to_resolve = Vec::new();
         let mut metrics_to_resolve = HashMap::new();
+        let mut metric_groups_to_resolve = Vec::new();
+
+        // Conflicting definitions are reported with both definitions before
+        // being rejected as duplicates.
+        for conflict in self.attribute_type_conflicts() {
+            Self::report(&config, &mut errors, conflict.error)?;
+        }
+        for error in self.extends_cycles() {
+            Self::report(&config, &mut errors, error)?;
+        }
 
         // Add all the attributes with an id to the semantic convention registry.
-        for SemConvSpecWithProvenance { spec, provenance } in self.specs.clone().into_iter() {
+        // Note: the specs are borrowed (not cloned), the registry indexes
+        // being updated through their own fields.
+        for SemConvSpecWithProvenance { spec, provenance } in self.specs.iter() {
             for group in spec.groups.iter() {
-                // Process attributes
-                match group.r#type {
-                    group::ConvTypeSpec::AttributeGroup
-                    | group::ConvTypeSpec::Span
-                    | group::ConvTypeSpec::Resource
-                    | group::ConvTypeSpec::Metric
-                    | group::ConvTypeSpec::Event
-                    | group::ConvTypeSpec::MetricGroup => {
-                        let attributes_in_group = self.process_attributes(
-                            provenance.clone(),
-                            group.id.clone(),
-                            group.prefix.clone(),
-                            group.attributes.clone(),
-                            &mut attributes_to_resolve,
-                        )?;
-
-                        let group_attributes = match group.r#type {
-                            group::ConvTypeSpec::AttributeGroup => {
-                                Some(&mut self.attr_grp_group_attributes)
-                            }
-                            group::ConvTypeSpec::Span => Some(&mut self.span_group_attributes),
-                            group::ConvTypeSpec::Resource => {
-                                Some(&mut self.resource_group_attributes)
-                            }
-                            group::ConvTypeSpec::Metric => Some(&mut self.metric_group_attributes),
-                            group::ConvTypeSpec::Event => Some(&mut self.event_group_attributes),
-                            group::ConvTypeSpec::MetricGroup => {
-                                Some(&mut self.metric_group_group_attributes)
-                            }
-                            _ => None,
-                        };
+                let (line, column) = line_column(group.position);
+                Self::check_stabilities(&config, &mut warnings, &mut errors, provenance, group)?;
 
-                        if let Some(group_attributes) = group_attributes {
-                            let prev_group_ids = group_attributes.insert(
-                                group.id.clone(),
-                                GroupIds {
-                                    origin: provenance.clone(),
-                                    ids: attributes_in_group.clone(),
-                                },
-                            );
-                            Self::detect_duplicated_group(
-                                provenance.clone(),
-                                group.id.clone(),
-                                prev_group_ids,
-                            )?;
-                        }
-                    }
-                    _ => {
-                        eprintln!(
-                            "Warning: group type `{:?}` not implemented yet",
-                            group.r#type
-                        );
-                    }
+                // Process attributes
+                let attributes_in_group = Self::process_attributes(
+                    &config,
+                    &mut errors,
+                    &mut self.all_attributes,
+                    provenance,
+                    group,
+                    &mut attributes_to_resolve,
+                )?;
+
+                let group_attributes = match group.r#type {
+                    group::ConvTypeSpec::AttributeGroup => &mut self.attr_grp_group_attributes,
+                    group::ConvTypeSpec::Span => &mut self.span_group_attributes,
+                    group::ConvTypeSpec::Resource => &mut self.resource_group_attributes,
+                    group::ConvTypeSpec::Metric => &mut self.metric_group_attributes,
+                    group::ConvTypeSpec::Event => &mut self.event_group_attributes,
+                    group::ConvTypeSpec::MetricGroup => &mut self.metric_group_group_attributes,
+                    group::ConvTypeSpec::Scope => &mut self.scope_group_attributes,
+                };
+                let prev_group_ids = group_attributes.insert(
+                    group.id.clone(),
+                    GroupIds {
+                        origin: provenance.clone(),
+                        ids: attributes_in_group,
+                    },
+                );
+                if let Err(e) =
+                    Self::detect_duplicated_group(provenance.clone(), group, prev_group_ids)
+                {
+                    Self::report(&config, &mut errors, e)?;
+                }
+                if let Some(span_name) = group.span_name.as_ref() {
+                    _ = self.span_names.insert(group.id.clone(), span_name.clone());
+                }
+                if group.r#type == group::ConvTypeSpec::Event && !group.body.is_empty() {
+                    let event_name = group.name.as_ref().unwrap_or(&group.prefix);
+                    _ = self
+                        .event_bodies
+                        .insert(event_name.clone(), group.body.clone());
                 }
 
                 // Process metrics
@@ -422,20 +935,30 @@ impl SemConvSpecs {
                         let metric_name = if let Some(metric_name) = group.metric_name.as_ref() {
                             metric_name.clone()
                         } else {
-                            return Err(Error::InvalidMetric {
+                            let error = Error::InvalidMetric {
                                 path_or_url: provenance.clone(),
+                                line,
+                                column,
+                                pointer: group.pointer.clone(),
                                 group_id: group.id.clone(),
                                 error: "Metric without name".to_string(),
-                            });
+                            };
+                            Self::report(&config, &mut errors, error)?;
+                            continue;
                         };
                         let instrument = if let Some(instrument) = group.instrument.as_ref() {
                             instrument.clone()
                         } else {
-                            return Err(Error::InvalidMetric {
+                            let error = Error::InvalidMetric {
                                 path_or_url: provenance.clone(),
+                                line,
+                                column,
+                                pointer: group.pointer.clone(),
                                 group_id: group.id.clone(),
                                 error: "Metric without instrument definition".to_string(),
-                            });
+                            };
+                            Self::report(&config, &mut errors, error)?;
+                            continue;
                         };
 
                         let prev_val = self.all_metrics.insert(
@@ -453,31 +976,71 @@ impl SemConvSpecs {
                             },
                         );
                         if prev_val.is_some() {
-                            return Err(Error::DuplicateMetricName {
+                            let error = Error::DuplicateMetricName {
                                 path_or_url: provenance.clone(),
+                                line,
+                                column,
+                                pointer: group.field_pointer("metric_name"),
                                 name: metric_name.clone(),
-                            });
+                            };
+                            Self::report(&config, &mut errors, error)?;
                         }
 
                         if let Some(r#ref) = group.extends.as_ref() {
                             let prev_val = metrics_to_resolve.insert(
                                 metric_name.clone(),
                                 MetricToResolve {
-                                    path_or_url: provenance.clone(),
-                                    group_id: group.id.clone(),
-                                    r#ref: r#ref.clone(),
+                                    path_or_url: provenance,
+                                    group_id: &group.id,
+                                    r#ref,
+                                    position: group.position,
+                                    pointer: group.field_pointer("extends"),
                                 },
                             );
                             if prev_val.is_some() {
-                                return Err(Error::DuplicateMetricName {
+                                let error = Error::DuplicateMetricName {
                                     path_or_url: provenance.clone(),
+                                    line,
+                                    column,
+                                    pointer: group.field_pointer("extends"),
                                     name: r#ref.clone(),
-                                });
+                                };
+                                Self::report(&config, &mut errors, error)?;
                             }
                         }
                     }
                     group::ConvTypeSpec::MetricGroup => {
-                        eprintln!("Warning: group type `metric_group` not implemented yet");
+                        // The metric fields belong to the member metrics.
+                        if group.metric_name.is_some()
+                            || group.instrument.is_some()
+                            || group.unit.is_some()
+                        {
+                            let error = Error::InvalidMetric {
+                                path_or_url: provenance.clone(),
+                                line,
+                                column,
+                                pointer: group.pointer.clone(),
+                                group_id: group.id.clone(),
+                                error: "Metric group with a metric_name, an instrument or a unit (only allowed on the member metrics)".to_string(),
+                            };
+                            Self::report(&config, &mut errors, error)?;
+                        }
+                        _ = self.metric_groups.insert(
+                            group.id.clone(),
+                            MetricGroupSpec {
+                                id: group.id.clone(),
+                                brief: group.brief.clone(),
+                                attributes: vec![],
+                                metrics: vec![],
+                            },
+                        );
+                        metric_groups_to_resolve.push(MetricToResolve {
+                            path_or_url: provenance,
+                            group_id: &group.id,
+                            r#ref: &group.id,
+                            position: group.position,
+                            pointer: group.pointer.clone(),
+                        });
                     }
                     _ => {
                         // No metrics to process
@@ -488,16 +1051,23 @@ impl SemConvSpecs {
 
         // Resolve all the attributes with a reference.
         for attr_to_resolve in attributes_to_resolve.into_iter() {
-            let resolved_attr = self.all_attributes.get(&attr_to_resolve.r#ref);
+            let resolved_attr = self.all_attributes.get(attr_to_resolve.r#ref);
 
             if resolved_attr.is_none() {
+                let (line, column) = line_column(attr_to_resolve.position);
+                let group = attr_to_resolve.group;
                 let err = Error::InvalidAttribute {
-                    path_or_url: attr_to_resolve.path_or_url.clone(),
-                    group_id: attr_to_resolve.group_id.clone(),
+                    path_or_url: attr_to_resolve.path_or_url.to_owned(),
+                    line,
+                    column,
+                    pointer: group
+                        .attribute_pointer(attr_to_resolve.attr)
+                        .map(|pointer| format!("{}/ref", pointer)),
+                    group_id: group.id.clone(),
                     error: format!("Attribute reference '{}' not found", attr_to_resolve.r#ref),
                 };
                 if config.error_when_attribute_ref_not_found {
-                    return Err(err);
+                    Self::report(&config, &mut errors, err)?;
                 } else {
                     warnings.push(ResolverWarning { error: err });
                 }
@@ -506,44 +1076,320 @@ impl SemConvSpecs {
 
         // Resolve all the metrics with an `extends` field.
         for (metric_name, metric_to_resolve) in metrics_to_resolve {
-            let attribute_group = self.attr_grp_group_attributes.get(&metric_to_resolve.r#ref);
+            let (line, column) = line_column(metric_to_resolve.position);
+            // A metric extends an attribute group or a metric group (sharing
+            // its attributes with its member metrics).
+            let attribute_group = self
+                .attr_grp_group_attributes
+                .get(metric_to_resolve.r#ref)
+                .or_else(|| {
+                    self.metric_group_group_attributes
+                        .get(metric_to_resolve.r#ref)
+                });
             if let Some(attr_grp) = attribute_group {
                 if let Some(metric) = self.all_metrics.get_mut(&metric_name) {
-                    let mut inherited_attributes = vec![];
-                    for attr_id in attr_grp.ids.iter() {
-                        if let Some(attr) = self.all_attributes.get(attr_id) {
-                            // Note: we only keep the last attribute definition for attributes that
-                            // are defined multiple times in the group.
-                            inherited_attributes.push(attr.attribute.clone());
-                        }
+                    // Note: we only keep the last attribute definition for attributes that
+                    // are defined multiple times in the group.
+                    metric.metric.attributes.extend(
+                        attr_grp
+                            .ids
+                            .iter()
+                            .filter_map(|attr_id| self.all_attributes.get(attr_id))
+                            .map(|attr| attr.attribute.clone()),
+                    );
+                    if let Some(metric_group) = self.metric_groups.get_mut(metric_to_resolve.r#ref)
+                    {
+                        metric_group.metrics.push(metric_name.clone());
                     }
-                    metric
-                        .metric
-                        .attributes
-                        .extend(inherited_attributes.iter().cloned());
                 } else {
-                    return Err(Error::InvalidMetric {
-                        path_or_url: metric_to_resolve.path_or_url,
-                        group_id: metric_to_resolve.group_id,
+                    let error = Error::InvalidMetric {
+                        path_or_url: metric_to_resolve.path_or_url.to_owned(),
+                        line,
+                        column,
+                        pointer: metric_to_resolve.pointer.clone(),
+                        group_id: metric_to_resolve.group_id.to_owned(),
                         error: format!("The metric '{}' doesn't exist", metric_name),
-                    });
+                    };
+                    Self::report(&config, &mut errors, error)?;
                 }
             } else {
                 warnings.push(ResolverWarning {
                     error: Error::InvalidMetric {
-                        path_or_url: metric_to_resolve.path_or_url,
-                        group_id: metric_to_resolve.group_id,
+                        path_or_url: metric_to_resolve.path_or_url.to_owned(),
+                        line,
+                        column,
+                        pointer: metric_to_resolve.pointer.clone(),
+                        group_id: metric_to_resolve.group_id.to_owned(),
                         error: format!("The reference `{}` specified in the `extends` field of the '{}' metric could not be resolved", metric_to_resolve.r#ref, metric_name),
                     }
                 });
             }
         }
 
+        // Collect the shared attributes of the metric groups, which must all
+        // be defined as the member metrics depend on them.
+        for metric_group_to_resolve in metric_groups_to_resolve {
+            let Some(metric_group) = self.metric_groups.get_mut(metric_group_to_resolve.r#ref)
+            else {
+                continue;
+            };
+            metric_group.metrics.sort();
+            let mut attr_ids: Vec<&String> = self
+                .metric_group_group_attributes
+                .get(metric_group_to_resolve.r#ref)
+                .map(|group_ids| group_ids.ids.iter().collect())
+                .unwrap_or_default();
+            attr_ids.sort();
+            for attr_id in attr_ids {
+                if let Some(attr) = self.all_attributes.get(attr_id) {
+                    metric_group.attributes.push(attr.attribute.clone());
+                } else {
+                    let (line, column) = line_column(metric_group_to_resolve.position);
+                    let error = Error::InvalidMetric {
+                        path_or_url: metric_group_to_resolve.path_or_url.to_owned(),
+                        line,
+                        column,
+                        pointer: metric_group_to_resolve.pointer.clone(),
+                        group_id: metric_group_to_resolve.group_id.to_owned(),
+                        error: format!(
+                            "The shared attribute '{}' of the metric group is not defined",
+                            attr_id
+                        ),
+                    };
+                    Self::report(&config, &mut errors, error)?;
+                }
+            }
+        }
+
+        // Check the `any_of` constraints once all the references are
+        // resolved.
+        for error in self.any_of_violations() {
+            Self::report(&config, &mut errors, error)?;
+        }
+
+        self.stability_inheritance_disabled = config.disable_stability_inheritance;
+        self.stability_filter = config.stability_filter;
         if !config.keep_specs {
             self.specs.clear();
         }
 
-        Ok(warnings)
+        Ok((warnings, errors))
+    }
+
+    /// Reports an error of the resolution: the error is returned, or
+    /// collected to be returned at the end of the resolution when the
+    /// resolver collects the errors.
+    fn report(config: &ResolverConfig, errors: &mut Vec<Error>, error: Error) -> Result<(), Error> {
+        if config.collect_errors {
+            errors.push(error);
+            return Ok(());
+        }
+        Err(error)
+    }
+
+    /// Reports the stability levels of a group, of its attributes and of
+    /// their enum members unknown to the resolver, as errors or as warnings
+    /// in tolerant mode. The stability of an enum member is reported on its
+    /// attribute.
+    fn check_stabilities(
+        config: &ResolverConfig,
+        warnings: &mut Vec<ResolverWarning>,
+        errors: &mut Vec<Error>,
+        path_or_url: &str,
+        group: &GroupSpec,
+    ) -> Result<(), Error> {
+        // The attribute and the index of the enum member (if any) locate the
+        // stability in the group.
+        let group_stability =
+            std::iter::once((None, None, None, group.stability.as_ref(), group.position));
+        let attr_stabilities = group.attributes.iter().flat_map(|attr| {
+            let position = group.attribute_position(attr);
+            let (attribute_id, stability, members) = match attr {
+                AttributeSpec::Ref {
+                    r#ref, stability, ..
+                } => (r#ref, stability.as_ref(), None),
+                AttributeSpec::Id {
+                    id,
+                    r#type: AttributeTypeSpec::Enum { members, .. },
+                    stability,
+                    ..
+                } => (id, stability.as_ref(), Some(members)),
+                AttributeSpec::Id { id, stability, .. } => (id, stability.as_ref(), None),
+            };
+            let member_stabilities =
+                members
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .map(move |(index, member)| {
+                        (
+                            Some(attribute_id),
+                            Some(attr),
+                            Some(index),
+                            member.stability.as_ref(),
+                            position,
+                        )
+                    });
+            std::iter::once((Some(attribute_id), Some(attr), None, stability, position))
+                .chain(member_stabilities)
+        });
+
+        for (attribute_id, attr, member, stability, position) in
+            group_stability.chain(attr_stabilities)
+        {
+            if let Some(StabilitySpec::Other(stability)) = stability {
+                let (line, column) = line_column(position);
+                let pointer = match (attr, member) {
+                    (None, _) => group.field_pointer("stability"),
+                    (Some(attr), None) => group
+                        .attribute_pointer(attr)
+                        .map(|pointer| format!("{}/stability", pointer)),
+                    (Some(attr), Some(index)) => group
+                        .attribute_pointer(attr)
+                        .map(|pointer| format!("{}/type/members/{}/stability", pointer, index)),
+                };
+                let error = Error::UnknownStability {
+                    path_or_url: path_or_url.to_owned(),
+                    line,
+                    column,
+                    pointer,
+                    group_id: group.id.clone(),
+                    attribute_id: attribute_id.cloned(),
+                    stability: stability.clone(),
+                };
+                if !config.tolerant_stability {
+                    Self::report(config, errors, error)?;
+                    continue;
+                }
+                warnings.push(ResolverWarning { error });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the errors of the cycles of the `extends` chains, located at
+    /// the first group of each cycle.
+    fn extends_cycles(&self) -> Vec<Error> {
+        let cycles = group::extends_cycles(
+            self.groups()
+                .map(|group| (group.id.as_str(), group.extends.as_deref())),
+        );
+        cycles
+            .into_iter()
+            .map(|group_ids| {
+                let first_group = self.specs.iter().find_map(|spec| {
+                    spec.spec
+                        .groups
+                        .iter()
+                        .find(|group| group.id == group_ids[0])
+                        .map(|group| {
+                            (
+                                spec.provenance.clone(),
+                                group.position,
+                                group.field_pointer("extends"),
+                            )
+                        })
+                });
+                let (path_or_url, position, pointer) = first_group.unwrap_or_default();
+                let (line, column) = line_column(position);
+                Error::CyclicExtends {
+                    path_or_url,
+                    line,
+                    column,
+                    pointer,
+                    group_ids,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the errors of the groups whose `any_of` constraints reference
+    /// attributes they don't define. The attributes of a group are its own
+    /// attributes and the attributes of the groups of its extends chain and
+    /// of its included groups (transitively).
+    fn any_of_violations(&self) -> Vec<Error> {
+        let groups_by_id: HashMap<&str, &GroupSpec> = self
+            .groups()
+            .map(|group| (group.id.as_str(), group))
+            .collect();
+        let mut violations = vec![];
+
+        for SemConvSpecWithProvenance { spec, provenance } in self.specs.iter() {
+            for group in spec.groups.iter() {
+                if group
+                    .constraints
+                    .iter()
+                    .all(|constraint| constraint.any_of.is_empty())
+                {
+                    continue;
+                }
+                let attr_ids = self.constraint_attribute_ids(&groups_by_id, group);
+                for (index, constraint) in group.constraints.iter().enumerate() {
+                    let missing: Vec<String> = constraint
+                        .any_of
+                        .iter()
+                        .filter(|attr_id| !attr_ids.contains(attr_id.as_str()))
+                        .cloned()
+                        .collect();
+                    if !missing.is_empty() {
+                        let (line, column) = line_column(group.position);
+                        violations.push(Error::AnyOfConstraintViolation {
+                            path_or_url: provenance.clone(),
+                            line,
+                            column,
+                            pointer: group.field_pointer(&format!("constraints/{}/any_of", index)),
+                            group_id: group.id.clone(),
+                            missing,
+                        });
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Returns the ids of the attributes available to the constraints of a
+    /// group, i.e. defined by the group, its extends chain or its included
+    /// groups.
+    fn constraint_attribute_ids<'a>(
+        &'a self,
+        groups_by_id: &HashMap<&str, &'a GroupSpec>,
+        group: &'a GroupSpec,
+    ) -> HashSet<&'a str> {
+        let mut attr_ids = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut pending = vec![group];
+        while let Some(group) = pending.pop() {
+            if !visited.insert(group.id.as_str()) {
+                continue;
+            }
+            if let Some(group_ids) = self.group_ids(&group.id, group.r#type.clone()) {
+                attr_ids.extend(group_ids.ids.iter().map(String::as_str));
+            }
+            let included = group
+                .constraints
+                .iter()
+                .filter_map(|constraint| constraint.include.as_deref());
+            for group_id in group.extends.as_deref().into_iter().chain(included) {
+                if let Some(&group) = groups_by_id.get(group_id) {
+                    pending.push(group);
+                }
+            }
+        }
+        attr_ids
+    }
+
+    /// Returns the attribute ids of a group from the index of its type.
+    fn group_ids(&self, group_id: &str, r#type: group::ConvTypeSpec) -> Option<&GroupIds> {
+        match r#type {
+            group::ConvTypeSpec::AttributeGroup => self.attr_grp_group_attributes.get(group_id),
+            group::ConvTypeSpec::Span => self.span_group_attributes.get(group_id),
+            group::ConvTypeSpec::Event => self.event_group_attributes.get(group_id),
+            group::ConvTypeSpec::Metric => self.metric_group_attributes.get(group_id),
+            group::ConvTypeSpec::MetricGroup => self.metric_group_group_attributes.get(group_id),
+            group::ConvTypeSpec::Resource => self.resource_group_attributes.get(group_id),
+            group::ConvTypeSpec::Scope => self.scope_group_attributes.get(group_id),
+        }
     }
 
     /// Returns the number of unique attributes defined in the semantic convention registry.
@@ -581,16 +1427,7 @@ impl SemConvSpecs {
         r#type: group::ConvTypeSpec,
     ) -> Result<HashMap<&String, &AttributeSpec>, Error> {
         let mut attributes = HashMap::new();
-        let group_ids = match r#type {
-            group::ConvTypeSpec::AttributeGroup => self.attr_grp_group_attributes.get(r#ref),
-            group::ConvTypeSpec::Span => self.span_group_attributes.get(r#ref),
-            group::ConvTypeSpec::Event => self.event_group_attributes.get(r#ref),
-            group::ConvTypeSpec::Metric => self.metric_group_attributes.get(r#ref),
-            group::ConvTypeSpec::MetricGroup => self.metric_group_group_attributes.get(r#ref),
-            group::ConvTypeSpec::Resource => self.resource_group_attributes.get(r#ref),
-            group::ConvTypeSpec::Scope => panic!("Scope not implemented yet"),
-        };
-        if let Some(group_ids) = group_ids {
+        if let Some(group_ids) = self.group_ids(r#ref, r#type) {
             for attr_id in group_ids.ids.iter() {
                 if let Some(attr) = self.all_attributes.get(attr_id) {
                     // Note: we only keep the last attribute definition for attributes that
@@ -606,6 +1443,18 @@ impl SemConvSpecs {
         Ok(attributes)
     }
 
+    /// Returns the guidance on the span names of a span group, if the group
+    /// defines a `span_name`.
+    pub fn span_name(&self, span_group_id: &str) -> Option<&SpanNameSpec> {
+        self.span_names.get(span_group_id)
+    }
+
+    /// Returns the body fields of an event (by event name), if its group
+    /// defines a `body`.
+    pub fn event_body(&self, event_name: &str) -> Option<&[BodyFieldSpec]> {
+        self.event_bodies.get(event_name).map(Vec::as_slice)
+    }
+
     /// Returns an iterator over all the groups defined in the semantic convention registry.
     pub fn groups(&self) -> impl Iterator<Item = &GroupSpec> {
         self.specs
@@ -626,11 +1475,117 @@ impl SemConvSpecs {
             })
     }
 
+    /// Returns the attributes defined several times with different types (or
+    /// different enum members), in the order the specs were loaded. Each
+    /// warning describes the first definition and the conflicting one.
+    /// Note: the specs must be kept by the resolver (see
+    /// [`ResolverConfig::with_keep_specs`]).
+    pub fn attribute_type_conflicts(&self) -> Vec<ResolverWarning> {
+        let mut definitions: HashMap<String, AttributeDefinition> = HashMap::new();
+        let mut conflicts = vec![];
+        for group in self.groups_with_provenance() {
+            for attr in group.spec.attributes.iter() {
+                let AttributeSpec::Id { id, r#type, .. } = attr else {
+                    continue;
+                };
+                let fq_attr_id = if group.spec.prefix.is_empty() {
+                    id.clone()
+                } else {
+                    format!("{}.{}", group.spec.prefix, id)
+                };
+                let definition = AttributeDefinition {
+                    group_id: group.spec.id.clone(),
+                    path_or_url: group.provenance.clone(),
+                    r#type: type_signature(r#type),
+                };
+                match definitions.get(&fq_attr_id) {
+                    Some(origin) if origin.r#type != definition.r#type => {
+                        conflicts.push(ResolverWarning {
+                            error: Error::AttributeTypeConflict {
+                                id: fq_attr_id,
+                                origin: Box::new(origin.clone()),
+                                definition: Box::new(definition),
+                            },
+                        });
+                    }
+                    Some(_) => {}
+                    None => {
+                        _ = definitions.insert(fq_attr_id, definition);
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns an iterator over the provenance (path or URL) of every semantic
+    /// convention spec kept in the semantic convention registry.
+    pub fn provenances(&self) -> impl Iterator<Item = &str> {
+        self.specs.iter().map(|spec| spec.provenance.as_str())
+    }
+
     /// Returns an iterator over all the attributes defined in the semantic convention registry.
     pub fn attributes_iter(&self) -> impl Iterator<Item = &AttributeSpec> {
         self.all_attributes.values().map(|attr| &attr.attribute)
     }
 
+    /// Returns the attributes with the given stability, sorted by id.
+    pub fn attributes_with_stability(&self, stability: &StabilitySpec) -> Vec<&AttributeSpec> {
+        Self::sorted_by_id(
+            self.all_attributes
+                .iter()
+                .filter(|(_, attr)| attr.attribute.stability() == Some(stability)),
+        )
+    }
+
+    /// Returns the attributes of a namespace (e.g. `http` or `http.request`),
+    /// i.e. the attributes whose id starts with the namespace followed by a
+    /// dot, sorted by id.
+    pub fn attributes_in_namespace(&self, namespace: &str) -> Vec<&AttributeSpec> {
+        let prefix = format!("{}.", namespace.trim_end_matches('.'));
+        Self::sorted_by_id(
+            self.all_attributes
+                .iter()
+                .filter(|(id, _)| id.starts_with(&prefix)),
+        )
+    }
+
+    /// Returns the attributes required by a group (whatever its type), sorted
+    /// by id, or `None` if the group does not exist. The requirement level is
+    /// the one declared by the group, a `ref` being able to override the
+    /// requirement level of the referenced attribute.
+    ///
+    /// Note: the groups are only available if the registry has been resolved
+    /// with [`ResolverConfig::with_keep_specs`].
+    pub fn required_attributes_of(&self, group_id: &str) -> Option<Vec<&AttributeSpec>> {
+        let group = self.groups().find(|group| group.id == group_id)?;
+        let required = group
+            .attributes
+            .iter()
+            .filter(|attr| attr.is_required())
+            .filter_map(|attr| {
+                let attr_id = match attr {
+                    AttributeSpec::Ref { r#ref, .. } => r#ref.clone(),
+                    AttributeSpec::Id { id, .. } if group.prefix.is_empty() => id.clone(),
+                    AttributeSpec::Id { id, .. } => format!("{}.{}", group.prefix, id),
+                };
+                self.all_attributes.get_key_value(&attr_id)
+            });
+        Some(Self::sorted_by_id(required))
+    }
+
+    /// Returns the attribute definitions sorted by id.
+    fn sorted_by_id<'a>(
+        attributes: impl Iterator<Item = (&'a String, &'a AttributeSpecWithProvenance)>,
+    ) -> Vec<&'a AttributeSpec> {
+        let mut attributes: Vec<_> = attributes.collect();
+        attributes.sort_by_key(|(id, _)| *id);
+        attributes
+            .into_iter()
+            .map(|(_, attr)| &attr.attribute)
+            .collect()
+    }
+
     /// Returns an iterator over all the metrics defined in the semantic convention registry.
     pub fn metrics_iter(&self) -> impl Iterator<Item = &MetricSpec> {
         self.all_metrics.values().map(|metric| &metric.metric)
@@ -644,6 +1599,18 @@ impl SemConvSpecs {
             .map(|metric| &metric.metric)
     }
 
+    /// Returns a metric group definition (its shared attributes and its
+    /// member metrics) from its id or `None` if the id does not exist.
+    pub fn metric_group(&self, id: &str) -> Option<&MetricGroupSpec> {
+        self.metric_groups.get(id)
+    }
+
+    /// Returns an iterator over all the metric groups defined in the semantic
+    /// convention registry.
+    pub fn metric_groups_iter(&self) -> impl Iterator<Item = &MetricGroupSpec> {
+        self.metric_groups.values()
+    }
+
     /// Returns a metric definition and its provenance from its name
     pub fn metric_with_provenance(&self, metric_name: &str) -> Option<&MetricSpecWithProvenance> {
         self.all_metrics.get(metric_name)
@@ -652,13 +1619,17 @@ impl SemConvSpecs {
     /// Returns an error if prev_group_ids is not `None`.
     fn detect_duplicated_group(
         path_or_url: String,
-        group_id: String,
+        group: &GroupSpec,
         prev_group_ids: Option<GroupIds>,
     ) -> Result<(), Error> {
         if let Some(group_ids) = prev_group_ids.as_ref() {
+            let (line, column) = line_column(group.position);
             return Err(Error::DuplicateGroupId {
                 path_or_url,
-                id: group_id,
+                line,
+                column,
+                pointer: group.field_pointer("id"),
+                id: group.id.clone(),
                 origin: group_ids.origin.clone(),
             });
         }
@@ -666,20 +1637,25 @@ impl SemConvSpecs {
     }
 
     /// Processes a collection of attributes passed as a parameter (`attrs`),
-    /// adds attributes fully defined to the semantic convention registry, adds attributes with
-    /// a reference to the list of attributes to resolve and returns a
-    /// collection of attribute ids defined in the current group.
-    fn process_attributes(
-        &mut self,
-        path_or_url: String,
-        group_id: String,
-        prefix: String,
-        attrs: Vec<AttributeSpec>,
-        attributes_to_resolve: &mut Vec<AttributeToResolve>,
+    /// adds attributes fully defined to the semantic convention registry
+    /// (`all_attributes`), adds attributes with a reference to the list of
+    /// attributes to resolve and returns a collection of attribute ids defined
+    /// in the current group.
+    ///
+    /// Only the attributes fully defined are copied, the references borrow
+    /// the given specs.
+    fn process_attributes<'a>(
+        config: &ResolverConfig,
+        errors: &mut Vec<Error>,
+        all_attributes: &mut HashMap<String, AttributeSpecWithProvenance>,
+        path_or_url: &'a str,
+        group: &'a GroupSpec,
+        attributes_to_resolve: &mut Vec<AttributeToResolve<'a>>,
     ) -> Result<HashSet<String>, Error> {
         let mut attributes_in_group = HashSet::new();
-        for mut attr in attrs.into_iter() {
-            match &attr {
+        let prefix = &group.prefix;
+        for attr in group.attributes.iter() {
+            match attr {
                 AttributeSpec::Id { id, .. } => {
                     // The attribute has an id, so add it to the semantic convention registry
                     // if it does not exist yet, otherwise return an error.
@@ -690,32 +1666,42 @@ impl SemConvSpecs {
                     } else {
                         format!("{}.{}", prefix, id)
                     };
-                    if let AttributeSpec::Id { id, .. } = &mut attr {
+                    let mut fq_attr = attr.clone();
+                    if let AttributeSpec::Id { id, .. } = &mut fq_attr {
                         *id = fq_attr_id.clone();
                     }
-                    let prev_val = self.all_attributes.insert(
+                    let prev_val = all_attributes.insert(
                         fq_attr_id.clone(),
                         AttributeSpecWithProvenance {
-                            attribute: attr,
-                            provenance: path_or_url.clone(),
+                            attribute: fq_attr,
+                            provenance: path_or_url.to_owned(),
                         },
                     );
                     if let Some(prev_val) = prev_val {
-                        return Err(Error::DuplicateAttributeId {
-                            origin_path_or_url: prev_val.provenance.clone(),
-                            path_or_url: path_or_url.clone(),
+                        let (line, column) = line_column(group.attribute_position(attr));
+                        let error = Error::DuplicateAttributeId {
+                            origin_path_or_url: prev_val.provenance,
+                            path_or_url: path_or_url.to_owned(),
+                            line,
+                            column,
+                            pointer: group
+                                .attribute_pointer(attr)
+                                .map(|pointer| format!("{}/id", pointer)),
                             id: fq_attr_id.clone(),
-                        });
+                        };
+                        Self::report(config, errors, error)?;
                     }
-                    let _ = attributes_in_group.insert(fq_attr_id.clone());
+                    let _ = attributes_in_group.insert(fq_attr_id);
                 }
                 AttributeSpec::Ref { r#ref, .. } => {
                     // The attribute has a reference, so add it to the
                     // list of attributes to resolve.
                     attributes_to_resolve.push(AttributeToResolve {
-                        path_or_url: path_or_url.clone(),
-                        group_id: group_id.clone(),
-                        r#ref: r#ref.clone(),
+                        path_or_url,
+                        group,
+                        attr,
+                        r#ref,
+                        position: group.attribute_position(attr),
                     });
                     let _ = attributes_in_group.insert(r#ref.clone());
                 }
@@ -728,6 +1714,34 @@ impl SemConvSpecs {
 impl SemConvSpec {
     /// Load a semantic convention semantic convention registry from a file.
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<SemConvSpec, Error> {
+        Self::from_file(path, false)
+    }
+
+    /// Load a semantic convention semantic convention registry from a file
+    /// written with the Python build-tools YAML extensions.
+    pub fn load_from_file_compat<P: AsRef<Path>>(path: P) -> Result<SemConvSpec, Error> {
+        Self::from_file(path, true)
+    }
+
+    /// Load a semantic convention semantic convention registry from its YAML
+    /// or JSON content, the path or URL being used in the errors and to
+    /// detect the format (see [`is_json`]).
+    pub fn load_from_str(content: &str, path_or_url: &str) -> Result<SemConvSpec, Error> {
+        Self::from_reader(content.as_bytes(), path_or_url, false)
+    }
+
+    /// Load a semantic convention semantic convention registry from a URL.
+    pub fn load_from_url(semconv_url: &str) -> Result<SemConvSpec, Error> {
+        Self::from_url(semconv_url, false)
+    }
+
+    /// Load a semantic convention semantic convention registry from a URL
+    /// written with the Python build-tools YAML extensions.
+    pub fn load_from_url_compat(semconv_url: &str) -> Result<SemConvSpec, Error> {
+        Self::from_url(semconv_url, true)
+    }
+
+    fn from_file<P: AsRef<Path>>(path: P, build_tools_compat: bool) -> Result<SemConvSpec, Error> {
         let path_buf = path.as_ref().to_path_buf();
 
         // Load and deserialize the semantic convention semantic convention registry
@@ -735,39 +1749,219 @@ impl SemConvSpec {
             path_or_url: path_buf.as_path().display().to_string(),
             error: e.to_string(),
         })?;
-        let catalog: SemConvSpec =
-            serde_yaml::from_reader(BufReader::new(catalog_file)).map_err(|e| {
-                Error::InvalidCatalog {
-                    path_or_url: path_buf.as_path().display().to_string(),
-                    line: e.location().map(|loc| loc.line()),
-                    column: e.location().map(|loc| loc.column()),
-                    error: e.to_string(),
-                }
-            })?;
-        Ok(catalog)
+        Self::from_reader(
+            BufReader::new(catalog_file),
+            &path_buf.as_path().display().to_string(),
+            build_tools_compat,
+        )
     }
 
-    /// Load a semantic convention semantic convention registry from a URL.
-    pub fn load_from_url(semconv_url: &str) -> Result<SemConvSpec, Error> {
+    fn from_url(semconv_url: &str, build_tools_compat: bool) -> Result<SemConvSpec, Error> {
         // Create a content reader from the semantic convention URL
-        let reader = ureq::get(semconv_url)
-            .call()
+        let reader = weaver_net::client()
+            .and_then(|client| client.get(semconv_url))
             .map_err(|e| Error::CatalogNotFound {
                 path_or_url: semconv_url.to_string(),
                 error: e.to_string(),
             })?
             .into_reader();
 
-        // Deserialize the telemetry schema from the content reader
-        let catalog: SemConvSpec =
-            serde_yaml::from_reader(reader).map_err(|e| Error::InvalidCatalog {
-                path_or_url: semconv_url.to_string(),
+        Self::from_reader(reader, semconv_url, build_tools_compat)
+    }
+
+    /// Deserializes a semantic convention spec from a content reader, the
+    /// content being YAML or JSON (see [`is_json`]).
+    fn from_reader<R: Read>(
+        mut reader: R,
+        path_or_url: &str,
+        build_tools_compat: bool,
+    ) -> Result<SemConvSpec, Error> {
+        let mut content = vec![];
+        _ = reader
+            .read_to_end(&mut content)
+            .map_err(|e| Error::CatalogNotFound {
+                path_or_url: path_or_url.to_string(),
+                error: e.to_string(),
+            })?;
+        let mut spec = if is_json(path_or_url, &content) {
+            Self::from_json(&content, path_or_url, build_tools_compat)?
+        } else {
+            Self::from_yaml(&content, path_or_url, build_tools_compat)?
+        };
+        position::locate(&mut spec.groups, &String::from_utf8_lossy(&content));
+        Ok(spec)
+    }
+
+    /// Deserializes a semantic convention spec from YAML content.
+    /// In build-tools compatibility mode, the YAML document is first
+    /// normalized (see [`compat::normalize_build_tools_spec`]).
+    ///
+    /// The content can contain several YAML documents separated by `---`,
+    /// their groups being merged in the order of the documents (the empty
+    /// documents are ignored). The errors report the document where they
+    /// occurred.
+    fn from_yaml(
+        content: &[u8],
+        path_or_url: &str,
+        build_tools_compat: bool,
+    ) -> Result<SemConvSpec, Error> {
+        let mut groups = vec![];
+        let mut documents = serde_yaml::Deserializer::from_slice(content).peekable();
+        let mut index = 0;
+        while let Some(document) = documents.next() {
+            index += 1;
+            let multi_doc = index > 1 || documents.peek().is_some();
+            let invalid_catalog = |e: serde_yaml::Error| Error::InvalidCatalog {
+                path_or_url: if multi_doc {
+                    format!("{} (document {})", path_or_url, index)
+                } else {
+                    path_or_url.to_string()
+                },
                 line: e.location().map(|loc| loc.line()),
                 column: e.location().map(|loc| loc.column()),
+                pointer: position::yaml_pointer(&e),
                 error: e.to_string(),
-            })?;
-        Ok(catalog)
+            };
+
+            let spec: Option<SemConvSpec> = if build_tools_compat {
+                let mut doc = serde_yaml::Value::deserialize(document).map_err(invalid_catalog)?;
+                if doc.is_null() {
+                    continue;
+                }
+                compat::normalize_build_tools_spec(&mut doc);
+                Some(serde_yaml::from_value(doc).map_err(invalid_catalog)?)
+            } else {
+                Option::<SemConvSpec>::deserialize(document).map_err(invalid_catalog)?
+            };
+            if let Some(mut spec) = spec {
+                // The pointers are relative to the document of the groups.
+                position::point(&mut spec.groups);
+                groups.extend(spec.groups);
+            }
+        }
+        Ok(SemConvSpec { groups })
+    }
+
+    /// Deserializes a semantic convention spec from JSON content (a single
+    /// document with the same structure as the YAML files). In build-tools
+    /// compatibility mode, the document is first normalized (see
+    /// [`compat::normalize_build_tools_spec`]).
+    fn from_json(
+        content: &[u8],
+        path_or_url: &str,
+        build_tools_compat: bool,
+    ) -> Result<SemConvSpec, Error> {
+        // serde_json reports a line 0 when the location is unknown.
+        let invalid_catalog = |e: serde_json::Error| Error::InvalidCatalog {
+            path_or_url: path_or_url.to_string(),
+            line: (e.line() > 0).then(|| e.line()),
+            column: (e.line() > 0).then(|| e.column()),
+            // serde_json doesn't report the path of the errors.
+            pointer: None,
+            error: e.to_string(),
+        };
+
+        let mut spec: SemConvSpec = if build_tools_compat {
+            let mut doc: serde_yaml::Value =
+                serde_json::from_slice(content).map_err(invalid_catalog)?;
+            compat::normalize_build_tools_spec(&mut doc);
+            serde_yaml::from_value(doc).map_err(|e| Error::InvalidCatalog {
+                path_or_url: path_or_url.to_string(),
+                line: None,
+                column: None,
+                pointer: position::yaml_pointer(&e),
+                error: e.to_string(),
+            })?
+        } else {
+            serde_json::from_slice(content).map_err(invalid_catalog)?
+        };
+        position::point(&mut spec.groups);
+        Ok(spec)
+    }
+}
+
+/// Returns a normalized description of an attribute type. The enum members
+/// (id and value) are sorted so that two enums with the same members have the
+/// same description.
+fn type_signature(attr_type: &AttributeTypeSpec) -> String {
+    match attr_type {
+        AttributeTypeSpec::Enum { members, .. } => {
+            let mut entries: Vec<String> = members
+                .iter()
+                .map(|member| format!("{}={}", member.id, member.value))
+                .collect();
+            entries.sort();
+            format!("enum {{{}}}", entries.join(", "))
+        }
+        _ => attr_type.to_string(),
+    }
+}
+
+/// Returns true if the path is a semantic convention file, i.e. a `*.yaml`,
+/// `*.yml` or `*.json` file other than a `schema-next.yaml` file (the
+/// telemetry schema of the semantic conventions repository).
+pub fn is_semconv_file(path: &Path) -> bool {
+    let is_yaml_or_json = path
+        .extension()
+        .is_some_and(|extension| extension == "yaml" || extension == "yml" || extension == "json");
+    is_yaml_or_json
+        && path
+            .file_name()
+            .is_some_and(|name| name != "schema-next.yaml")
+}
+
+/// Returns true if a semantic convention content is JSON: the path or URL
+/// has a `.json` extension, or has no YAML extension and the content is a
+/// JSON object. A YAML file is always parsed as YAML (JSON being a subset of
+/// YAML).
+fn is_json(path_or_url: &str, content: &[u8]) -> bool {
+    let path = path_or_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if path.ends_with(".json") {
+        return true;
+    }
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        return false;
     }
+    content
+        .iter()
+        .find(|byte| !byte.is_ascii_whitespace())
+        .is_some_and(|&byte| byte == b'{')
+}
+
+/// Returns true if the file name is the one of a hidden file or directory.
+fn is_hidden(file_name: &std::ffi::OsStr) -> bool {
+    file_name
+        .to_str()
+        .is_some_and(|file_name| file_name.starts_with('.'))
+}
+
+/// Returns true if the path matched by a glob pattern goes through a hidden
+/// file or directory not explicitly named by the pattern (the `**` wildcard
+/// matches the hidden directories).
+fn is_hidden_match(pattern: &str, path: &Path) -> bool {
+    let pattern = Path::new(pattern);
+    let literal_prefix: PathBuf = pattern
+        .components()
+        .take_while(|component| {
+            !component
+                .as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '['])
+        })
+        .collect();
+    path.strip_prefix(&literal_prefix)
+        .unwrap_or(path)
+        .components()
+        .any(|component| match component {
+            Component::Normal(name) => {
+                is_hidden(name) && !pattern.components().any(|c| c.as_os_str() == name)
+            }
+            _ => false,
+        })
 }
 
 #[cfg(test)]
@@ -776,6 +1970,103 @@ mod tests {
 
     use super::*;
 
+    /// Load the semantic convention files of a directory, skipping the hidden
+    /// directories and the files that are not semantic convention files.
+    #[test]
+    fn test_load_from_dir_and_glob() {
+        let mut catalog = SemConvSpecs::default();
+        assert_eq!(catalog.load_from_dir("data/registry-dir").unwrap(), 3);
+        let group_ids: Vec<&str> = catalog.groups().map(|group| group.id.as_str()).collect();
+        assert_eq!(
+            group_ids,
+            vec!["http.server", "registry.server", "registry.url"]
+        );
+        assert!(catalog.resolve(ResolverConfig::default()).is_ok());
+
+        let mut catalog = SemConvSpecs::default();
+        assert_eq!(catalog.load_from_glob("data/registry-dir/**/*").unwrap(), 3);
+
+        let mut catalog = SemConvSpecs::default();
+        assert!(catalog.load_from_dir("data/not-found").is_err());
+        assert!(catalog.load_from_glob("data/[registry-dir").is_err());
+    }
+
+    #[test]
+    fn test_collect_errors() {
+        let content = "\
+groups:
+  - id: registry.server
+    type: attribute_group
+    prefix: server
+    brief: Server attributes.
+    attributes:
+      - id: port
+        type: int
+        brief: Server port number.
+        examples: [80]
+  - id: metric.server.duration
+    type: metric
+    metric_name: server.duration
+    brief: Duration.
+    instrument: histogram
+    unit: s
+    attributes:
+      - ref: server.address
+";
+        let load = |catalog: &mut SemConvSpecs| {
+            catalog.load_from_str(content, "a.yaml").unwrap();
+            catalog.load_from_str(content, "b.yaml").unwrap();
+        };
+
+        // The first error stops the resolution.
+        let mut catalog = SemConvSpecs::default();
+        load(&mut catalog);
+        assert!(matches!(
+            catalog.resolve(ResolverConfig::default()),
+            Err(Error::DuplicateAttributeId { .. })
+        ));
+
+        // All the errors of the second file are collected: the duplicate
+        // attribute, the duplicate groups and the duplicate metric.
+        let mut catalog = SemConvSpecs::default();
+        load(&mut catalog);
+        match catalog.resolve(ResolverConfig::default().with_collect_errors()) {
+            Err(Error::CompoundError(errors)) => {
+                assert_eq!(errors.len(), 4, "{:?}", errors);
+                assert!(matches!(errors[0], Error::DuplicateAttributeId { .. }));
+                assert!(matches!(errors[1], Error::DuplicateGroupId { .. }));
+                assert!(matches!(errors[2], Error::DuplicateGroupId { .. }));
+                assert!(matches!(errors[3], Error::DuplicateMetricName { .. }));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_load_json() {
+        let spec = SemConvSpec::load_from_file("data/registry-dir/url.json").unwrap();
+        assert_eq!(spec.groups[0].id, "registry.url");
+        assert_eq!(spec.groups[0].attributes[0].id(), "scheme");
+
+        // Detected from the content when the path has no extension.
+        let content = std::fs::read_to_string("data/registry-dir/url.json").unwrap();
+        let spec = SemConvSpec::load_from_str(&content, "https://example.com/url").unwrap();
+        assert_eq!(spec.groups[0].id, "registry.url");
+
+        let error = SemConvSpec::load_from_str(
+            "{\n  \"groups\": [\n    {\"id\": \"url\", \"type\": 12}\n  ]\n}",
+            "url.json",
+        )
+        .unwrap_err();
+        match error {
+            Error::InvalidCatalog { line, column, .. } => {
+                assert_eq!(line, Some(3));
+                assert!(column.is_some());
+            }
+            _ => panic!("unexpected error: {:?}", error),
+        }
+    }
+
     /// Load multiple semantic convention files in the semantic convention registry.
     /// No error should be emitted.
     #[test]
@@ -851,4 +2142,734 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_attribute_type_conflicts() {
+        let mut catalog = SemConvSpecs::default();
+        for yaml in [
+            "data/attribute-conflicts/upstream.yaml",
+            "data/attribute-conflicts/local.yaml",
+        ] {
+            catalog.load_from_file(yaml).unwrap();
+        }
+
+        let conflicts: Vec<(String, String, String)> = catalog
+            .attribute_type_conflicts()
+            .into_iter()
+            .map(|warning| match warning.error {
+                Error::AttributeTypeConflict {
+                    id,
+                    origin,
+                    definition,
+                } => (id, origin.group_id, definition.group_id),
+                e => panic!("Unexpected error {:?}", e),
+            })
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                (
+                    "http.request.resend_count".to_owned(),
+                    "registry.http".to_owned(),
+                    "local.http".to_owned()
+                ),
+                (
+                    "http.request.method".to_owned(),
+                    "registry.http".to_owned(),
+                    "local.http.custom".to_owned()
+                ),
+            ]
+        );
+
+        assert!(matches!(
+            catalog.resolve(ResolverConfig::default()),
+            Err(Error::AttributeTypeConflict { .. })
+        ));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_fail_on_unimplemented() {
+        // The group types once reported as not implemented are resolved, with
+        // or without the deprecated option.
+        let mut catalog = SemConvSpecs::default();
+        catalog
+            .load_from_file("data/unimplemented-groups.yaml")
+            .unwrap();
+        let warnings = catalog
+            .resolve(ResolverConfig::default().with_fail_on_unimplemented())
+            .unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert!(catalog.metric_group("metric_group.http.server").is_some());
+    }
+
+    #[test]
+    fn test_metric_groups() {
+        let content = r#"groups:
+  - id: metric_group.http.server
+    type: metric_group
+    brief: "HTTP server metrics."
+    attributes:
+      - id: http.server.route
+        type: string
+        brief: "The matched route."
+        examples: ['/users/:userID?']
+  - id: metric.http.server.duration
+    type: metric
+    metric_name: http.server.duration
+    brief: "Duration of the HTTP server requests."
+    instrument: histogram
+    unit: "s"
+    extends: metric_group.http.server
+  - id: metric.http.server.active_requests
+    type: metric
+    metric_name: http.server.active_requests
+    brief: "Number of active HTTP server requests."
+    instrument: updowncounter
+    unit: "{request}"
+    extends: metric_group.http.server
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "http.yaml").unwrap();
+        let warnings = catalog.resolve(ResolverConfig::default()).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let metric_group = catalog.metric_group("metric_group.http.server").unwrap();
+        assert_eq!(
+            metric_group.metrics,
+            vec!["http.server.active_requests", "http.server.duration"]
+        );
+        assert_eq!(metric_group.attributes.len(), 1);
+        assert_eq!(metric_group.attributes[0].id(), "http.server.route");
+        assert_eq!(catalog.metric_groups_iter().count(), 1);
+        let duration = catalog.metric("http.server.duration").unwrap();
+        assert_eq!(duration.attributes.len(), 1);
+
+        let content = r#"groups:
+  - id: metric_group.http.client
+    type: metric_group
+    brief: "HTTP client metrics."
+    unit: "s"
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "http.yaml").unwrap();
+        assert!(matches!(
+            catalog.resolve(ResolverConfig::default()),
+            Err(Error::InvalidMetric { group_id, .. }) if group_id == "metric_group.http.client"
+        ));
+    }
+
+    #[test]
+    fn test_any_of_constraints() {
+        let content = r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+      - id: port
+        type: int
+        brief: "Server port."
+        examples: [80]
+  - id: client
+    type: span
+    brief: "Client span."
+    attributes:
+      - ref: server.port
+    constraints:
+      - include: registry.server
+  - id: client.http
+    type: span
+    brief: "HTTP client span."
+    extends: client
+    constraints:
+      - any_of:
+          - server.address
+          - server.port
+  - id: client.grpc
+    type: span
+    brief: "gRPC client span."
+    attributes:
+      - ref: server.port
+    constraints:
+      - any_of:
+          - server.port
+          - server.socket.address
+          - server.socket.port
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "client.yaml").unwrap();
+        let result = catalog.resolve(ResolverConfig::default());
+        match result {
+            Err(Error::AnyOfConstraintViolation {
+                group_id,
+                line,
+                missing,
+                ..
+            }) => {
+                assert_eq!(group_id, "client.grpc");
+                assert_eq!(line, Some(30));
+                assert_eq!(missing, vec!["server.socket.address", "server.socket.port"]);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_resolve_lenient() {
+        let content = r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+  - id: client
+    type: span
+    brief: "Client span."
+    attributes:
+      - ref: server.address
+      - ref: server.socket.port
+  - id: registry.server.legacy
+    type: attribute_group
+    brief: "Legacy server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "client.yaml").unwrap();
+        assert!(catalog.clone().resolve(ResolverConfig::default()).is_err());
+
+        // The dangling reference is a warning, the duplicated attribute an
+        // error.
+        let (warnings, errors) = catalog.resolve_lenient(ResolverConfig::default());
+        match &warnings[..] {
+            [ResolverWarning {
+                error:
+                    Error::InvalidAttribute {
+                        group_id, error, ..
+                    },
+            }] => {
+                assert_eq!(group_id, "client");
+                assert!(error.contains("server.socket.port"), "{}", error);
+            }
+            warnings => panic!("Unexpected warnings {:?}", warnings),
+        }
+        match &errors[..] {
+            [Error::DuplicateAttributeId { id, .. }] => assert_eq!(id, "server.address"),
+            errors => panic!("Unexpected errors {:?}", errors),
+        }
+        // The definitions that could be resolved remain available.
+        assert!(catalog.attribute("server.address").is_some());
+    }
+
+    #[test]
+    fn test_cyclic_extends() {
+        let content = r#"groups:
+  - id: base
+    type: span
+    brief: "Base span."
+  - id: a
+    type: span
+    brief: "Span A."
+    extends: b
+  - id: b
+    type: span
+    brief: "Span B."
+    extends: c
+  - id: c
+    type: span
+    brief: "Span C."
+    extends: a
+  - id: d
+    type: span
+    brief: "Span D."
+    extends: a
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "cycle.yaml").unwrap();
+        let result = catalog.resolve(ResolverConfig::default());
+        match result {
+            Err(Error::CyclicExtends {
+                group_ids, line, ..
+            }) => {
+                assert_eq!(group_ids, vec!["a", "b", "c", "a"]);
+                assert_eq!(line, Some(5));
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        let cycles = group::extends_cycles([
+            ("a", Some("a")),
+            ("b", Some("a")),
+            ("c", Some("d")),
+            ("d", Some("c")),
+            ("e", Some("unknown")),
+        ]);
+        assert_eq!(
+            cycles,
+            vec![
+                vec!["a".to_owned(), "a".to_owned()],
+                vec!["c".to_owned(), "d".to_owned(), "c".to_owned()]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_example_type() {
+        let content = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: response.status_code
+        type: int
+        brief: "HTTP response status code."
+        examples: [200, 404]
+      - id: request.resend_count
+        type: double
+        brief: "Ordinal number of request resending attempt."
+        examples: 3
+      - id: request.method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: "GET"
+        brief: "HTTP request method."
+        examples: ["GET"]
+      - id: route
+        type: string
+        brief: "The matched route."
+        examples: [42]
+"#;
+        let mut catalog = SemConvSpecs::default();
+        match catalog.load_from_str(content, "http.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(error.contains("`http.route`"), "{}", error);
+                assert!(error.contains("type `string`"), "{}", error);
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        let valid = content.replace("examples: [42]", "examples: [\"/users/:id\"]");
+        catalog.load_from_str(&valid, "http.yaml").unwrap();
+    }
+
+    #[test]
+    fn test_span_name() {
+        let content = r#"groups:
+  - id: span.http.client
+    type: span
+    span_kind: client
+    brief: "HTTP client span."
+    span_name:
+      format: "{http.request.method} {http.route}"
+      examples: ["GET /users/:id"]
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "http.yaml").unwrap();
+        _ = catalog.resolve(ResolverConfig::default()).unwrap();
+        let span_name = catalog.span_name("span.http.client").unwrap();
+        assert_eq!(
+            span_name.attributes(),
+            vec!["http.request.method", "http.route"]
+        );
+        assert!(span_name.matches("POST /users"));
+        assert!(!span_name.matches("POST"));
+        assert_eq!(span_name.pattern().unwrap(), "^.+ .+$");
+
+        let invalid = content.replace("[\"GET /users/:id\"]", "[\"GET\"]");
+        match SemConvSpecs::default().load_from_str(&invalid, "http.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(error.contains("example `GET`"), "{}", error)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let invalid = content.replace("{http.route}\"", "{http.route\"");
+        match SemConvSpecs::default().load_from_str(&invalid, "http.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(error.contains("unclosed `{`"), "{}", error)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let invalid = content.replace("type: span\n    span_kind: client", "type: attribute_group");
+        assert!(SemConvSpecs::default()
+            .load_from_str(&invalid, "http.yaml")
+            .is_err());
+    }
+
+    #[test]
+    fn test_enum_member_stability() {
+        let content = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: request.method
+        type:
+          allow_custom_values: true
+          members:
+            - id: get
+              value: GET
+              stability: stable
+            - id: connect
+              value: CONNECT
+              stability: deprecated
+              deprecated:
+                reason: "Tunnels are not traced."
+                removed_in: 2.0.0
+        brief: "HTTP request method."
+        examples: ["GET"]
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "http.yaml").unwrap();
+        _ = catalog.resolve(ResolverConfig::default()).unwrap();
+        let Some(AttributeSpec::Id {
+            r#type: AttributeTypeSpec::Enum { members, .. },
+            ..
+        }) = catalog.attribute("http.request.method")
+        else {
+            panic!("Enum attribute expected");
+        };
+        assert_eq!(members[0].stability, Some(StabilitySpec::Stable));
+        assert_eq!(members[0].deprecated, None);
+        assert_eq!(members[1].stability, Some(StabilitySpec::Deprecated));
+        assert_eq!(
+            members[1].deprecated.as_ref().and_then(|d| d.removed_in()),
+            Some("2.0.0")
+        );
+
+        let invalid = content.replace(
+            "value: CONNECT\n              stability: deprecated",
+            "value: CONNECT\n              stability: stable",
+        );
+        match SemConvSpecs::default().load_from_str(&invalid, "http.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(error.contains("member `connect`"), "{}", error)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        let unknown = content.replace("stability: stable", "stability: alpha");
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(&unknown, "http.yaml").unwrap();
+        assert!(matches!(
+            catalog.resolve(ResolverConfig::default()),
+            Err(Error::UnknownStability { attribute_id: Some(attribute_id), stability, .. })
+                if attribute_id == "request.method" && stability == "alpha"
+        ));
+    }
+
+    #[test]
+    fn test_event_body() {
+        let content = r#"groups:
+  - id: event.feature_flag
+    type: event
+    name: feature_flag.evaluation
+    brief: "A feature flag evaluation."
+    body:
+      - id: flag
+        type: string
+        brief: "The key of the flag."
+        requirement_level: required
+        examples: ["new-checkout"]
+      - id: result
+        type: map
+        brief: "The result of the evaluation."
+        fields:
+          - id: variant
+            type: string
+            examples: ["on"]
+          - id: reason
+            type: string
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "events.yaml").unwrap();
+        _ = catalog.resolve(ResolverConfig::default()).unwrap();
+        let body = catalog.event_body("feature_flag.evaluation").unwrap();
+        assert_eq!(body.len(), 2);
+        assert_eq!(body[1].r#type.to_string(), "map");
+        assert_eq!(body[1].fields[0].id, "variant");
+        assert!(catalog.event_body("event.feature_flag").is_none());
+
+        let invalid = content.replace("id: reason", "id: variant");
+        match SemConvSpecs::default().load_from_str(&invalid, "events.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(
+                    error.contains("`result.variant` is duplicated"),
+                    "{}",
+                    error
+                )
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let invalid = content.replace("type: map", "type: string");
+        match SemConvSpecs::default().load_from_str(&invalid, "events.yaml") {
+            Err(Error::InvalidCatalog { error, .. }) => {
+                assert!(error.contains("its type is not map"), "{}", error)
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let invalid = content.replace(
+            "type: event\n    name: feature_flag.evaluation",
+            "type: span",
+        );
+        assert!(SemConvSpecs::default()
+            .load_from_str(&invalid, "events.yaml")
+            .is_err());
+    }
+
+    #[test]
+    fn test_scope_groups() {
+        let content = r#"groups:
+  - id: scope.instrumentation
+    type: scope
+    brief: "Instrumentation scope."
+    prefix: otel.scope
+    attributes:
+      - id: name
+        type: string
+        brief: "The name of the instrumentation scope."
+        examples: ["io.opentelemetry.contrib.mongodb"]
+  - id: http.server
+    type: span
+    brief: "HTTP server span."
+    attributes:
+      - ref: otel.scope.name
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "scope.yaml").unwrap();
+        let warnings = catalog.resolve(ResolverConfig::default()).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let attributes = catalog
+            .attributes("scope.instrumentation", group::ConvTypeSpec::Scope)
+            .unwrap();
+        assert_eq!(attributes.len(), 1);
+        assert!(attributes.keys().any(|id| *id == "otel.scope.name"));
+        assert!(catalog
+            .attributes("scope.instrumentation", group::ConvTypeSpec::AttributeGroup)
+            .is_err());
+    }
+
+    #[test]
+    fn test_catalog_queries() {
+        let content = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: request.method
+        type: string
+        brief: "The HTTP request method."
+        examples: ["GET"]
+        stability: stable
+      - id: route
+        type: string
+        brief: "The matched route."
+        examples: ["/users/:id"]
+        stability: experimental
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "The server address."
+        examples: ["example.com"]
+        stability: stable
+  - id: http.server
+    type: span
+    brief: "HTTP server span."
+    attributes:
+      - ref: http.request.method
+        requirement_level: required
+      - ref: http.route
+      - ref: server.address
+        requirement_level: required
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "http.yaml").unwrap();
+        let warnings = catalog.resolve(ResolverConfig::with_keep_specs()).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let ids = |attributes: Vec<&AttributeSpec>| -> Vec<String> {
+            attributes.iter().map(|attr| attr.id()).collect()
+        };
+        assert_eq!(
+            ids(catalog.attributes_with_stability(&StabilitySpec::Stable)),
+            vec!["http.request.method", "server.address"]
+        );
+        assert_eq!(
+            ids(catalog.attributes_in_namespace("http")),
+            vec!["http.request.method", "http.route"]
+        );
+        assert!(catalog
+            .attributes_in_namespace("http.request.method")
+            .is_empty());
+        assert_eq!(
+            ids(catalog.required_attributes_of("http.server").unwrap()),
+            vec!["http.request.method", "server.address"]
+        );
+        assert!(catalog.required_attributes_of("unknown").is_none());
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let content = r#"groups:
+  - id: server
+    type: span
+    brief: "Server span."
+    attributes:
+      - ref: server.address
+      - ref: server.port
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "server.yaml").unwrap();
+        // The unresolved references are reported as warnings.
+        let warnings = catalog.resolve(ResolverConfig::default()).unwrap();
+        let errors: Vec<_> = warnings.into_iter().map(|warning| warning.error).collect();
+        let positions: Vec<_> = errors.iter().map(Error::position).collect();
+        assert_eq!(
+            positions,
+            vec![
+                Some(Position { line: 6, column: 9 }),
+                Some(Position { line: 7, column: 9 })
+            ]
+        );
+        assert!(errors[0].to_string().contains("\"server.yaml:6:9\""));
+    }
+
+    #[test]
+    fn test_error_pointers() {
+        let content = r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+        stability: alpha
+---
+groups:
+  - id: server
+    type: span
+    brief: "Server span."
+    attributes:
+      - ref: server.address
+      - ref: server.port
+"#;
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_str(content, "server.yaml").unwrap();
+        let warnings = catalog
+            .resolve(ResolverConfig::default().with_tolerant_stability())
+            .unwrap();
+        let pointers: Vec<_> = warnings
+            .iter()
+            .map(|warning| warning.error.pointer())
+            .collect();
+        // The pointers are relative to the document of the group.
+        assert_eq!(
+            pointers,
+            vec![
+                Some("/groups/0/attributes/0/stability"),
+                Some("/groups/0/attributes/1/ref"),
+            ]
+        );
+
+        // The deserialization errors are located by their path.
+        let content = r#"groups:
+  - id: server
+    type: span
+    brief: "Server span."
+    span_kind: serveur
+"#;
+        let error = SemConvSpec::load_from_str(content, "server.yaml").unwrap_err();
+        assert_eq!(error.pointer(), Some("/groups/0/span_kind"));
+
+        // The validation errors of a group are located at the group.
+        let content = r#"groups:
+  - id: server
+    type: span
+    brief: "Server span."
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    span_kind: server
+"#;
+        let error = SemConvSpecs::default()
+            .load_from_str(content, "server.yaml")
+            .unwrap_err();
+        assert_eq!(error.pointer(), Some("/groups/1"));
+    }
+
+    #[test]
+    fn test_unknown_stability() {
+        let mut catalog = SemConvSpecs::default();
+        catalog
+            .load_from_file("data/unknown-stability.yaml")
+            .unwrap();
+
+        assert!(matches!(
+            catalog.clone().resolve(ResolverConfig::default()),
+            Err(Error::UnknownStability { stability, .. }) if stability == "alpha"
+        ));
+
+        let warnings = catalog
+            .resolve(ResolverConfig::default().with_tolerant_stability())
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            &warnings[0].error,
+            Error::UnknownStability { attribute_id: Some(attribute_id), stability, .. }
+                if attribute_id == "request.resend_count" && stability == "alpha"
+        ));
+        let attribute = catalog.attribute("http.request.resend_count").unwrap();
+        assert!(matches!(
+            attribute,
+            AttributeSpec::Id { stability: Some(StabilitySpec::Other(stability)), .. } if stability == "alpha"
+        ));
+    }
+
+    #[test]
+    fn test_multi_doc_file() {
+        let spec = SemConvSpec::load_from_file("data/multi-doc.yaml").unwrap();
+        assert_eq!(
+            spec.groups
+                .iter()
+                .map(|group| group.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["registry.session", "registry.session.previous"]
+        );
+
+        let mut catalog = SemConvSpecs::default();
+        catalog.load_from_file("data/multi-doc.yaml").unwrap();
+        let _ = catalog.resolve(ResolverConfig::default()).unwrap();
+        assert!(catalog.attribute("session.previous.id").is_some());
+
+        assert!(matches!(
+            SemConvSpec::load_from_file("data/multi-doc-invalid.yaml"),
+            Err(Error::InvalidCatalog { path_or_url, .. })
+                if path_or_url == "data/multi-doc-invalid.yaml (document 2)"
+        ));
+    }
 }
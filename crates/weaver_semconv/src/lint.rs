@@ -0,0 +1,455 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional lint pass over the briefs and notes of a semantic convention
+//! registry, reporting the misspelled words and the terminology violations.
+//!
+//! The lint pass is configured by a YAML file:
+//! - `wordlists`: files listing the accepted words (one word per line, `#`
+//!   starting a comment), relative to the configuration file,
+//! - `words`: additional accepted words,
+//! - `terminology`: the terms to `prefer` (any other capitalization is
+//!   reported, e.g. `HTTP`) or to `avoid` (e.g. `Todo`), with an optional
+//!   `message`.
+//!
+//! The spelling is only checked when at least one word is accepted. Code spans
+//! (between backticks), identifiers (e.g. `http.request.method`), URLs,
+//! acronyms and camel-cased words are not spell-checked.
+//!
+//! Rule ids:
+//! - `spelling`: a word of a brief or note is not in the wordlists.
+//! - `terminology`: a term is avoided or doesn't have its preferred
+//!   capitalization.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::check::{Finding, Severity};
+use crate::{Error, SemConvSpecs};
+
+/// The configuration of the lint pass.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    /// Files listing the accepted words.
+    #[serde(default)]
+    pub wordlists: Vec<PathBuf>,
+    /// Additional accepted words.
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// Terminology rules.
+    #[serde(default)]
+    pub terminology: Vec<TerminologyRule>,
+}
+
+/// A terminology rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TerminologyRule {
+    /// The term (word or phrase) to prefer, any other capitalization of this
+    /// term is reported.
+    #[serde(default)]
+    pub prefer: Option<String>,
+    /// The term (word or phrase) to avoid, whatever its capitalization.
+    #[serde(default)]
+    pub avoid: Option<String>,
+    /// An optional explanation added to the findings.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// A linter built from a [`LintConfig`].
+#[derive(Debug, Default)]
+pub struct Linter {
+    /// The accepted words (lowercased).
+    words: HashSet<String>,
+    /// The terminology rules.
+    terminology: Vec<TerminologyRule>,
+}
+
+/// A word of a text.
+struct Word<'a> {
+    /// The byte offset of the word in the text.
+    offset: usize,
+    /// The word.
+    text: &'a str,
+    /// True if the word is part of the prose (i.e. not part of a code span,
+    /// an identifier or a URL).
+    prose: bool,
+}
+
+impl Linter {
+    /// Creates a linter from the given lint configuration file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let invalid_config = |error: String| Error::InvalidLintConfig {
+            path: path.display().to_string(),
+            error,
+        };
+
+        let file = File::open(path).map_err(|e| invalid_config(e.to_string()))?;
+        let config: LintConfig = serde_yaml::from_reader(BufReader::new(file))
+            .map_err(|e| invalid_config(e.to_string()))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::try_new(config, base_dir)
+    }
+
+    /// Creates a linter from the given lint configuration. The wordlists are
+    /// relative to `base_dir`.
+    pub fn try_new(config: LintConfig, base_dir: &Path) -> Result<Self, Error> {
+        let mut words: HashSet<String> = config
+            .words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect();
+        for wordlist in &config.wordlists {
+            let path = base_dir.join(wordlist);
+            let invalid_wordlist = |error: String| Error::InvalidLintConfig {
+                path: path.display().to_string(),
+                error,
+            };
+            let file = File::open(&path).map_err(|e| invalid_wordlist(e.to_string()))?;
+            for line in BufReader::new(file).lines() {
+                let line = line.map_err(|e| invalid_wordlist(e.to_string()))?;
+                let word = line.split('#').next().unwrap_or_default().trim();
+                if !word.is_empty() {
+                    _ = words.insert(word.to_lowercase());
+                }
+            }
+        }
+
+        for rule in &config.terminology {
+            if rule.prefer.is_some() == rule.avoid.is_some() {
+                return Err(Error::InvalidLintConfig {
+                    path: base_dir.display().to_string(),
+                    error: "a terminology rule must define either `prefer` or `avoid`".to_owned(),
+                });
+            }
+        }
+
+        Ok(Self {
+            words,
+            terminology: config.terminology,
+        })
+    }
+
+    /// Lints a text and returns the rule id and the message of each finding.
+    /// The location of each finding is given by its line and column in the
+    /// text.
+    fn lint_text(&self, text: &str) -> Vec<(&'static str, String)> {
+        let mut findings = self.lint_words(text);
+        findings.sort_by_key(|(offset, _, _)| *offset);
+        findings
+            .into_iter()
+            .map(|(_, rule_id, message)| (rule_id, message))
+            .collect()
+    }
+
+    /// Lints the words of a text and returns the offset, the rule id and the
+    /// message of each finding.
+    fn lint_words(&self, text: &str) -> Vec<(usize, &'static str, String)> {
+        let words: Vec<Word<'_>> = words(text).filter(|word| word.prose).collect();
+        let location = |offset: usize| {
+            let line = text[..offset].matches('\n').count() + 1;
+            let column = text[..offset]
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count()
+                + 1;
+            format!("{}:{}", line, column)
+        };
+        let mut findings = vec![];
+
+        if !self.words.is_empty() {
+            for word in &words {
+                let text = word.text.strip_suffix("'s").unwrap_or(word.text);
+                let is_acronym_or_camel_case = text.chars().skip(1).any(char::is_uppercase);
+                if text.chars().count() < 2
+                    || is_acronym_or_camel_case
+                    || self.words.contains(&text.to_lowercase())
+                {
+                    continue;
+                }
+                findings.push((
+                    word.offset,
+                    "spelling",
+                    format!("unknown word `{}` at {}", word.text, location(word.offset)),
+                ));
+            }
+        }
+
+        for rule in &self.terminology {
+            let term = rule
+                .prefer
+                .as_deref()
+                .or(rule.avoid.as_deref())
+                .unwrap_or_default();
+            let term_words: Vec<&str> = words_of(term).collect();
+            if term_words.is_empty() {
+                continue;
+            }
+            for window in words.windows(term_words.len()) {
+                let matches = window
+                    .iter()
+                    .zip(&term_words)
+                    .all(|(word, term_word)| word.text.eq_ignore_ascii_case(term_word));
+                if !matches {
+                    continue;
+                }
+                // A term can be wrapped over several lines, its words are
+                // compared one by one and reported on a single line.
+                let found = text[window[0].offset
+                    ..window[window.len() - 1].offset + window[window.len() - 1].text.len()]
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let preferred = window
+                    .iter()
+                    .zip(&term_words)
+                    .all(|(word, term_word)| word.text == *term_word);
+                let message = match &rule.prefer {
+                    Some(prefer) if !preferred => {
+                        format!("use `{}` instead of `{}`", prefer, found)
+                    }
+                    Some(_) => continue,
+                    None => format!("avoid `{}`", found),
+                };
+                let message = match &rule.message {
+                    Some(explanation) => format!("{} ({})", message, explanation),
+                    None => message,
+                };
+                findings.push((
+                    window[0].offset,
+                    "terminology",
+                    format!("{} at {}", message, location(window[0].offset)),
+                ));
+            }
+        }
+
+        findings
+    }
+}
+
+/// Returns the words of a term.
+fn words_of(term: &str) -> impl Iterator<Item = &str> {
+    term.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+}
+
+/// Returns the words of a text. The whitespace-separated chunks of the text
+/// that are code spans, or that contain characters other than letters,
+/// apostrophes and hyphens once their surrounding punctuation is removed
+/// (e.g. identifiers, numbers or URLs), are not prose.
+fn words(text: &str) -> impl Iterator<Item = Word<'_>> {
+    let mut in_code_span = false;
+    let mut chunks = vec![];
+    let mut start = None;
+    for (offset, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        if c == '`' {
+            if let Some(chunk_start) = start.take() {
+                chunks.push((chunk_start, &text[chunk_start..offset], !in_code_span));
+            }
+            in_code_span = !in_code_span;
+        } else if c.is_whitespace() {
+            if let Some(chunk_start) = start.take() {
+                chunks.push((chunk_start, &text[chunk_start..offset], !in_code_span));
+            }
+        } else if start.is_none() {
+            start = Some(offset);
+        }
+    }
+
+    chunks.into_iter().flat_map(|(offset, chunk, prose)| {
+        let trimmed = chunk.trim_matches(|c: char| !c.is_alphanumeric());
+        let trimmed_offset = offset + chunk.find(trimmed).unwrap_or_default();
+        let prose = prose
+            && trimmed
+                .chars()
+                .all(|c| c.is_alphabetic() || c == '\'' || c == '-');
+        trimmed
+            .split('-')
+            .scan(trimmed_offset, move |offset, word| {
+                let word_offset = *offset;
+                *offset += word.len() + 1;
+                Some(Word {
+                    offset: word_offset,
+                    text: word,
+                    prose,
+                })
+            })
+            .filter(|word| !word.text.is_empty())
+    })
+}
+
+impl SemConvSpecs {
+    /// Lints the briefs and notes of the groups and attributes of the
+    /// registry and returns the findings (reported as warnings).
+    /// Note: the specs must be kept by the resolver (see
+    /// [`crate::ResolverConfig::with_keep_specs`]).
+    pub fn lint(&self, linter: &Linter) -> Vec<Finding> {
+        let mut findings = vec![];
+        for group in self.groups_with_provenance() {
+            // The description of each text, its pointer and the text.
+            let mut texts = vec![
+                (
+                    "brief".to_owned(),
+                    "brief".to_owned(),
+                    group.spec.brief.clone(),
+                ),
+                (
+                    "note".to_owned(),
+                    "note".to_owned(),
+                    group.spec.note.clone(),
+                ),
+            ];
+            for (index, attr) in group.spec.attributes.iter().enumerate() {
+                texts.push((
+                    format!("brief of attribute `{}`", attr.id()),
+                    format!("attributes/{}/brief", index),
+                    attr.brief(),
+                ));
+                texts.push((
+                    format!("note of attribute `{}`", attr.id()),
+                    format!("attributes/{}/note", index),
+                    attr.note(),
+                ));
+            }
+
+            for (field, pointer, text) in texts {
+                for (rule_id, message) in linter.lint_text(&text) {
+                    findings.push(Finding {
+                        rule_id,
+                        severity: Severity::Warning,
+                        group_id: group.spec.id.clone(),
+                        provenance: group.provenance.clone(),
+                        pointer: group.spec.field_pointer(&pointer),
+                        message: format!("{} of the {}", message, field),
+                    });
+                }
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linter() -> Linter {
+        Linter::from_file("data/lint/lint.yaml").unwrap()
+    }
+
+    #[test]
+    fn test_lint_text() {
+        let findings = linter().lint_text(
+            "The http reqeust `reqeust.id` sent to\nhttps://exemple.com (see RFC-9110). Todo: fix the Http client's e.g. OpenTelemetry.",
+        );
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "terminology",
+                    "use `HTTP` instead of `http` at 1:5".to_owned()
+                ),
+                ("spelling", "unknown word `reqeust` at 1:10".to_owned()),
+                (
+                    "terminology",
+                    "avoid `Todo` (use an issue instead) at 2:37".to_owned()
+                ),
+                (
+                    "terminology",
+                    "use `HTTP` instead of `Http` at 2:51".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_wrapped_term() {
+        let terminology = |text: &str| {
+            linter()
+                .lint_text(text)
+                .into_iter()
+                .filter(|(rule, _)| *rule == "terminology")
+                .collect::<Vec<_>>()
+        };
+
+        // A preferred term wrapped over several lines is not reported.
+        assert!(terminology("Sent to the OpenTelemetry\n  Collector.").is_empty());
+        assert_eq!(
+            terminology("Sent to the opentelemetry\n  collector."),
+            vec![(
+                "terminology",
+                "use `OpenTelemetry Collector` instead of `opentelemetry collector` at 1:13"
+                    .to_owned()
+            )]
+        );
+        assert_eq!(
+            terminology("Nothing to\ndo."),
+            vec![("terminology", "avoid `to do` at 1:9".to_owned())]
+        );
+    }
+
+    #[test]
+    fn test_lint_registry() {
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_file("data/lint/registry.yaml").unwrap();
+        let findings: Vec<(String, &'static str, String)> = registry
+            .lint(&linter())
+            .into_iter()
+            .map(|finding| (finding.group_id, finding.rule_id, finding.message))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "registry.http".to_owned(),
+                    "spelling",
+                    "unknown word `atributes` at 1:6 of the brief".to_owned()
+                ),
+                (
+                    "registry.http".to_owned(),
+                    "terminology",
+                    "use `HTTP` instead of `http` at 1:48 of the brief of attribute `method`"
+                        .to_owned()
+                ),
+                (
+                    "registry.http".to_owned(),
+                    "terminology",
+                    "avoid `To do` at 1:1 of the note of attribute `method`".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let result = Linter::try_new(
+            LintConfig {
+                terminology: vec![TerminologyRule {
+                    prefer: None,
+                    avoid: None,
+                    message: None,
+                }],
+                ..Default::default()
+            },
+            Path::new("data/lint"),
+        );
+        assert!(matches!(result, Err(Error::InvalidLintConfig { .. })));
+
+        let result = Linter::try_new(
+            LintConfig {
+                wordlists: vec![PathBuf::from("missing.txt")],
+                ..Default::default()
+            },
+            Path::new("data/lint"),
+        );
+        assert!(matches!(result, Err(Error::InvalidLintConfig { .. })));
+    }
+}
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Define the registry manifest, i.e. the exact provenance of the semantic
+//! convention registries used to produce a resolved telemetry schema.
+
+use serde::{Deserialize, Serialize};
+
+/// Describes a semantic convention registry imported during the resolution.
+/// This information makes a resolved telemetry schema reproducible.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryManifest {
+    /// The URL (or git URL) of the imported registry.
+    pub registry_url: String,
+    /// The commit id of the registry (only for git registries).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// The git reference (e.g. branch or tag) of the registry (only for git
+    /// registries).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_ref: Option<String>,
+    /// The time at which the registry was fetched, in seconds since the Unix
+    /// epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<u64>,
+    /// The number of semantic convention files loaded from the registry.
+    pub file_count: usize,
+    /// The version of weaver used to resolve the registry.
+    pub weaver_version: String,
+}
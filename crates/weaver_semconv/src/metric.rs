@@ -4,10 +4,11 @@
 
 use crate::attribute::AttributeSpec;
 use crate::group::InstrumentSpec;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// A metric specification.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct MetricSpec {
     /// Metric name.
@@ -46,3 +47,18 @@ impl MetricSpec {
         self.unit.as_deref()
     }
 }
+
+/// A resolved metric group, i.e. a `metric_group` semantic convention group
+/// defining the attributes shared by its member metrics (the metrics
+/// extending the group).
+#[derive(Serialize, Debug, Clone)]
+pub struct MetricGroupSpec {
+    /// Id of the metric group.
+    pub id: String,
+    /// Brief description of the metric group.
+    pub brief: String,
+    /// The attributes shared by the member metrics.
+    pub attributes: Vec<AttributeSpec>,
+    /// The names of the member metrics (sorted).
+    pub metrics: Vec<String>,
+}
@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Naming policy of the group and attribute ids of a semantic convention
+//! registry (see [`SemConvSpecs::validate_names`]).
+//!
+//! Each rule of the policy can be disabled and is identified by a stable
+//! rule id:
+//! - `name-lowercase`: an id (or a prefix) must be made of dot-separated
+//!   segments, each one starting with a lowercase letter followed by
+//!   lowercase letters, digits or underscores.
+//! - `name-trailing-dot`: an id (or a prefix) can't end with a dot.
+//! - `name-prefix`: the prefix of a group must be a namespace of its id (e.g.
+//!   the prefix `http` of the group `registry.http`).
+//! - `name-namespace-collision`: an attribute id can't be the namespace of
+//!   other attributes (e.g. `http.request` with `http.request.method`).
+//!
+//! The attribute ids are checked fully qualified (i.e. prefixed by the prefix
+//! of their group).
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::attribute::AttributeSpec;
+use crate::new_group::check_name;
+use crate::{GroupSpecWithProvenance, SemConvSpecs};
+
+/// The naming policy enforced by [`SemConvSpecs::validate_names`]. All the
+/// rules are enabled by default.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingPolicy {
+    /// Check that the ids are lowercase dot-separated names.
+    pub lowercase: bool,
+    /// Check that the ids don't end with a dot.
+    pub trailing_dot: bool,
+    /// Check that the prefix of a group is a namespace of its id.
+    pub prefix: bool,
+    /// Check that no attribute id is the namespace of other attributes.
+    pub namespace_collision: bool,
+    /// Ids (of groups or attributes) exempted from the policy, e.g. legacy
+    /// names kept for compatibility.
+    pub allowed: Vec<String>,
+}
+
+impl Default for NamingPolicy {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            trailing_dot: true,
+            prefix: true,
+            namespace_collision: true,
+            allowed: vec![],
+        }
+    }
+}
+
+/// The rules of the naming policy.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingRule {
+    /// The id is not a lowercase dot-separated name.
+    Lowercase,
+    /// The id ends with a dot.
+    TrailingDot,
+    /// The prefix of the group is not a namespace of its id.
+    Prefix,
+    /// The attribute id is the namespace of other attributes.
+    NamespaceCollision,
+}
+
+impl NamingRule {
+    /// Returns the stable id of the rule.
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            NamingRule::Lowercase => "name-lowercase",
+            NamingRule::TrailingDot => "name-trailing-dot",
+            NamingRule::Prefix => "name-prefix",
+            NamingRule::NamespaceCollision => "name-namespace-collision",
+        }
+    }
+}
+
+/// The kinds of names checked by the naming policy.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NameKind {
+    /// The id of a group.
+    Group,
+    /// The prefix of a group.
+    Prefix,
+    /// The (fully qualified) id of an attribute.
+    Attribute,
+}
+
+impl Display for NameKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameKind::Group => write!(f, "group id"),
+            NameKind::Prefix => write!(f, "prefix"),
+            NameKind::Attribute => write!(f, "attribute id"),
+        }
+    }
+}
+
+/// A violation of the naming policy.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct NameViolation {
+    /// The violated rule.
+    pub rule: NamingRule,
+    /// The kind of the offending name.
+    pub kind: NameKind,
+    /// The offending name.
+    pub name: String,
+    /// The id of the group defining the name.
+    pub group_id: String,
+    /// The provenance (path or URL) of the group.
+    pub provenance: String,
+    /// The JSON pointer of the offending name in the semantic convention
+    /// document (e.g. `/groups/4/attributes/2/id`), if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    /// A human readable description of the violation.
+    pub message: String,
+}
+
+impl Display for NameViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} `{}` of the group `{}` ({}): {}",
+            self.rule.rule_id(),
+            self.kind,
+            self.name,
+            self.group_id,
+            self.provenance,
+            self.message
+        )
+    }
+}
+
+impl SemConvSpecs {
+    /// Checks the group ids, prefixes and attribute ids of the registry
+    /// against the given naming policy and returns the violations, in the
+    /// order of the groups.
+    /// Note: the specs must be kept by the resolver (see
+    /// [`crate::ResolverConfig::with_keep_specs`]).
+    pub fn validate_names(&self, policy: &NamingPolicy) -> Vec<NameViolation> {
+        let allowed: HashSet<&str> = policy.allowed.iter().map(String::as_str).collect();
+        let groups: Vec<GroupSpecWithProvenance> = self.groups_with_provenance().collect();
+        let mut names = vec![];
+        for group in groups.iter() {
+            let spec = &group.spec;
+            names.push((
+                NameKind::Group,
+                spec.id.clone(),
+                group,
+                spec.field_pointer("id"),
+            ));
+            if !spec.prefix.is_empty() {
+                names.push((
+                    NameKind::Prefix,
+                    spec.prefix.clone(),
+                    group,
+                    spec.field_pointer("prefix"),
+                ));
+            }
+            for (index, attr) in spec.attributes.iter().enumerate() {
+                if let AttributeSpec::Id { id, .. } = attr {
+                    let attr_id = if spec.prefix.is_empty() {
+                        id.clone()
+                    } else {
+                        format!("{}.{}", spec.prefix, id)
+                    };
+                    names.push((
+                        NameKind::Attribute,
+                        attr_id,
+                        group,
+                        spec.field_pointer(&format!("attributes/{}/id", index)),
+                    ));
+                }
+            }
+        }
+
+        // The namespaces of the attributes, i.e. the proper prefixes of
+        // their ids.
+        let namespaces: BTreeSet<&str> = names
+            .iter()
+            .filter(|(kind, ..)| *kind == NameKind::Attribute)
+            .flat_map(|(_, name, ..)| {
+                name.match_indices('.')
+                    .map(move |(index, _)| &name[..index])
+            })
+            .collect();
+
+        let mut violations = vec![];
+        for (kind, name, group, pointer) in names.iter() {
+            if allowed.contains(name.as_str()) {
+                continue;
+            }
+            let mut violation = |rule: NamingRule, message: String| {
+                violations.push(NameViolation {
+                    rule,
+                    kind: *kind,
+                    name: name.clone(),
+                    group_id: group.spec.id.clone(),
+                    provenance: group.provenance.clone(),
+                    pointer: pointer.clone(),
+                    message,
+                })
+            };
+
+            if policy.lowercase {
+                if let Err(message) = check_name(name.trim_end_matches('.')) {
+                    violation(NamingRule::Lowercase, message);
+                }
+            }
+            if policy.trailing_dot && name.ends_with('.') {
+                violation(
+                    NamingRule::TrailingDot,
+                    "the name can't end with a dot".to_owned(),
+                );
+            }
+            if policy.prefix
+                && *kind == NameKind::Prefix
+                && !format!(".{}.", group.spec.id).contains(&format!(".{}.", name))
+            {
+                violation(
+                    NamingRule::Prefix,
+                    format!(
+                        "the prefix is not a namespace of the group id `{}`",
+                        group.spec.id
+                    ),
+                );
+            }
+            if policy.namespace_collision
+                && *kind == NameKind::Attribute
+                && namespaces.contains(name.as_str())
+            {
+                violation(
+                    NamingRule::NamespaceCollision,
+                    "the attribute id is also the namespace of other attributes".to_owned(),
+                );
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: request
+        type: string
+        brief: "HTTP request."
+        examples: ["GET /"]
+      - id: request.method
+        type: string
+        brief: "HTTP request method."
+        examples: ["GET"]
+      - id: Route
+        type: string
+        brief: "The matched route."
+        examples: ["/users/:id"]
+  - id: registry.network
+    type: attribute_group
+    brief: "Network attributes."
+    prefix: net.
+    attributes:
+      - id: peer..name
+        type: string
+        brief: "Peer name."
+        examples: ["example.com"]
+"#;
+
+    #[test]
+    fn test_validate_names() {
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(REGISTRY, "registry.yaml").unwrap();
+        let violations: Vec<(&'static str, NameKind, String)> = registry
+            .validate_names(&NamingPolicy::default())
+            .into_iter()
+            .map(|violation| (violation.rule.rule_id(), violation.kind, violation.name))
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                (
+                    "name-namespace-collision",
+                    NameKind::Attribute,
+                    "http.request".to_owned()
+                ),
+                (
+                    "name-lowercase",
+                    NameKind::Attribute,
+                    "http.Route".to_owned()
+                ),
+                ("name-trailing-dot", NameKind::Prefix, "net.".to_owned()),
+                ("name-prefix", NameKind::Prefix, "net.".to_owned()),
+                (
+                    "name-lowercase",
+                    NameKind::Attribute,
+                    "net..peer..name".to_owned()
+                ),
+            ]
+        );
+
+        let policy = NamingPolicy {
+            namespace_collision: false,
+            prefix: false,
+            allowed: vec!["http.Route".to_owned()],
+            ..Default::default()
+        };
+        let violations = registry.validate_names(&policy);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].pointer.as_deref(), Some("/groups/1/prefix"));
+        assert!(violations[0]
+            .to_string()
+            .starts_with("[name-trailing-dot] prefix `net.` of the group `registry.network`"));
+    }
+}
@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! A new semantic convention group described by an author (see the
+//! `registry new-group` wizard). The ids are validated against the registry
+//! as they are entered and the group is rendered as a formatted YAML
+//! document, ready to be written in the registry.
+//!
+//! The group and attribute ids follow the naming rules of the semantic
+//! conventions: dot-separated namespaces of lowercase letters, digits and
+//! underscores, each starting with a letter.
+
+use serde::Serialize;
+
+use crate::attribute::{BasicRequirementLevelSpec, PrimitiveOrArrayTypeSpec};
+use crate::group::{ConvTypeSpec, InstrumentSpec};
+use crate::SemConvSpecs;
+
+/// A new semantic convention group.
+#[derive(Debug, Clone)]
+pub struct NewGroup {
+    /// The type of the group.
+    pub r#type: ConvTypeSpec,
+    /// The id of the group.
+    pub id: String,
+    /// A brief description of the group.
+    pub brief: String,
+    /// The prefix of the attributes of the group.
+    pub prefix: Option<String>,
+    /// The metric of a `metric` group.
+    pub metric: Option<NewMetric>,
+    /// The attributes of the group, in the order they were entered.
+    pub attributes: Vec<NewAttribute>,
+}
+
+/// The metric of a new `metric` group.
+#[derive(Debug, Clone)]
+pub struct NewMetric {
+    /// The name of the metric.
+    pub metric_name: String,
+    /// The instrument of the metric.
+    pub instrument: InstrumentSpec,
+    /// The UCUM unit of the metric.
+    pub unit: String,
+}
+
+/// An attribute of a new group.
+#[derive(Debug, Clone)]
+pub struct NewAttribute {
+    /// The id of the attribute (without the prefix of the group).
+    pub id: String,
+    /// The type of the attribute.
+    pub r#type: NewAttributeType,
+    /// A brief description of the attribute.
+    pub brief: String,
+    /// The requirement level of the attribute.
+    pub requirement_level: BasicRequirementLevelSpec,
+    /// The example values of the attribute.
+    pub examples: Vec<String>,
+}
+
+/// The type of an attribute of a new group.
+#[derive(Debug, Clone)]
+pub enum NewAttributeType {
+    /// A primitive or array type.
+    PrimitiveOrArray(PrimitiveOrArrayTypeSpec),
+    /// An enum whose members are identified by their (string) value.
+    Enum {
+        /// Set to false to not accept values other than the members.
+        allow_custom_values: bool,
+        /// The ids of the members (also used as their values).
+        members: Vec<String>,
+    },
+}
+
+/// Checks that a name (group id, attribute id, prefix or metric name)
+/// follows the naming rules of the semantic conventions.
+pub fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("the name can't be empty".to_owned());
+    }
+    for segment in name.split('.') {
+        let valid = segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase())
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+        if !valid {
+            return Err(format!(
+                "`{}` is not a valid name, each dot-separated segment must start with a lowercase letter followed by lowercase letters, digits or underscores",
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl NewGroup {
+    /// Checks that the given group id follows the naming rules and is not
+    /// already used by the registry.
+    pub fn check_id(registry: &SemConvSpecs, id: &str) -> Result<(), String> {
+        check_name(id)?;
+        if registry.groups().any(|group| group.id == id) {
+            return Err(format!("the group id `{}` is already used", id));
+        }
+        Ok(())
+    }
+
+    /// Checks that the given metric name follows the naming rules and is not
+    /// already used by the registry.
+    pub fn check_metric_name(registry: &SemConvSpecs, metric_name: &str) -> Result<(), String> {
+        check_name(metric_name)?;
+        if registry.metric(metric_name).is_some() {
+            return Err(format!("the metric name `{}` is already used", metric_name));
+        }
+        Ok(())
+    }
+
+    /// Returns the fully qualified id of an attribute of the group.
+    pub fn attribute_id(&self, id: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}.{}", prefix, id),
+            None => id.to_owned(),
+        }
+    }
+
+    /// Checks that the given attribute id follows the naming rules and that
+    /// its fully qualified id is not already used by the registry or by
+    /// another attribute of the group.
+    pub fn check_attribute_id(&self, registry: &SemConvSpecs, id: &str) -> Result<(), String> {
+        check_name(id)?;
+        let attribute_id = self.attribute_id(id);
+        if registry.attribute(&attribute_id).is_some()
+            || self.attributes.iter().any(|attr| attr.id == id)
+        {
+            return Err(format!(
+                "the attribute id `{}` is already used",
+                attribute_id
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the file of the registry receiving the group,
+    /// i.e. the root namespace of its prefix (or of its id without the
+    /// `registry.` namespace) followed by `.yaml`.
+    pub fn file_name(&self) -> String {
+        let name = self
+            .prefix
+            .as_deref()
+            .unwrap_or_else(|| self.id.strip_prefix("registry.").unwrap_or(&self.id));
+        format!("{}.yaml", name.split('.').next().unwrap_or(name))
+    }
+
+    /// Renders the group as a semantic convention document (with a `groups`
+    /// section).
+    pub fn to_yaml(&self) -> String {
+        let mut lines = vec![
+            "groups:".to_owned(),
+            format!("  - id: {}", yaml_scalar(&self.id)),
+            format!("    type: {}", yaml_scalar(&self.r#type)),
+            format!("    brief: {}", yaml_scalar(&self.brief)),
+        ];
+        if let Some(prefix) = &self.prefix {
+            lines.push(format!("    prefix: {}", yaml_scalar(prefix)));
+        }
+        if let Some(metric) = &self.metric {
+            lines.push(format!(
+                "    metric_name: {}",
+                yaml_scalar(&metric.metric_name)
+            ));
+            lines.push(format!(
+                "    instrument: {}",
+                yaml_scalar(&metric.instrument)
+            ));
+            lines.push(format!("    unit: {}", yaml_scalar(&metric.unit)));
+        }
+        if !self.attributes.is_empty() {
+            lines.push("    attributes:".to_owned());
+        }
+        for attr in self.attributes.iter() {
+            lines.push(format!("      - id: {}", yaml_scalar(&attr.id)));
+            match &attr.r#type {
+                NewAttributeType::PrimitiveOrArray(r#type) => {
+                    lines.push(format!("        type: {}", yaml_scalar(r#type)));
+                }
+                NewAttributeType::Enum {
+                    allow_custom_values,
+                    members,
+                } => {
+                    lines.push("        type:".to_owned());
+                    lines.push(format!(
+                        "          allow_custom_values: {}",
+                        allow_custom_values
+                    ));
+                    lines.push("          members:".to_owned());
+                    for member in members {
+                        lines.push(format!("            - id: {}", yaml_scalar(member)));
+                        lines.push(format!("              value: {}", json_string(member)));
+                    }
+                }
+            }
+            lines.push(format!("        brief: {}", yaml_scalar(&attr.brief)));
+            lines.push(format!(
+                "        requirement_level: {}",
+                yaml_scalar(&attr.requirement_level)
+            ));
+            if !attr.examples.is_empty() {
+                let examples: Vec<String> = attr
+                    .examples
+                    .iter()
+                    .map(String::as_str)
+                    .map(json_string)
+                    .collect();
+                lines.push(format!("        examples: [{}]", examples.join(", ")));
+            }
+        }
+        let mut yaml = lines.join("\n");
+        yaml.push('\n');
+        yaml
+    }
+}
+
+/// Returns a value serialized as a YAML scalar (quoted only when needed).
+fn yaml_scalar<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_yaml::to_string(value)
+        .map(|yaml| yaml.trim_end().to_owned())
+        .unwrap_or_default()
+}
+
+/// Returns a string as a double-quoted scalar, so the values looking like
+/// numbers or booleans stay strings.
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::{BasicRequirementLevelSpec, PrimitiveOrArrayTypeSpec};
+    use crate::group::ConvTypeSpec;
+    use crate::new_group::{check_name, NewAttribute, NewAttributeType, NewGroup};
+    use crate::{ResolverConfig, SemConvSpec, SemConvSpecs};
+
+    #[test]
+    fn test_check_name() {
+        assert!(check_name("http.request.method").is_ok());
+        assert!(check_name("k8s.pod_name").is_ok());
+        assert!(check_name("").is_err());
+        assert!(check_name("Http.method").is_err());
+        assert!(check_name("http..method").is_err());
+        assert!(check_name("http.2xx").is_err());
+        assert!(check_name("http-method").is_err());
+    }
+
+    const SERVER: &str = r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address
+        type: string
+        brief: "Server address."
+        examples: ["example.com"]
+"#;
+
+    #[test]
+    fn test_new_group() {
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(SERVER, "server.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::with_keep_specs()).unwrap();
+
+        assert!(NewGroup::check_id(&registry, "registry.server").is_err());
+        assert!(NewGroup::check_id(&registry, "registry.server.socket").is_ok());
+
+        let mut group = NewGroup {
+            r#type: ConvTypeSpec::AttributeGroup,
+            id: "registry.server.socket".to_owned(),
+            brief: "Server socket attributes: 'yes'".to_owned(),
+            prefix: Some("server".to_owned()),
+            metric: None,
+            attributes: vec![],
+        };
+        assert!(group.check_attribute_id(&registry, "address").is_err());
+        assert!(group.check_attribute_id(&registry, "socket.port").is_ok());
+        group.attributes.push(NewAttribute {
+            id: "socket.port".to_owned(),
+            r#type: NewAttributeType::PrimitiveOrArray(PrimitiveOrArrayTypeSpec::Int),
+            brief: "Server socket port.".to_owned(),
+            requirement_level: BasicRequirementLevelSpec::OptIn,
+            examples: vec![],
+        });
+        group.attributes.push(NewAttribute {
+            id: "socket.family".to_owned(),
+            r#type: NewAttributeType::Enum {
+                allow_custom_values: false,
+                members: vec!["inet".to_owned(), "inet6".to_owned()],
+            },
+            brief: "Socket family.".to_owned(),
+            requirement_level: BasicRequirementLevelSpec::Required,
+            examples: vec!["inet".to_owned()],
+        });
+        assert!(group.check_attribute_id(&registry, "socket.port").is_err());
+        assert_eq!(group.file_name(), "server.yaml");
+
+        let yaml = group.to_yaml();
+        let spec = SemConvSpec::load_from_str(&yaml, "new.yaml").unwrap();
+        let new_group = &spec.groups[0];
+        assert_eq!(new_group.id, "registry.server.socket");
+        assert_eq!(new_group.brief, "Server socket attributes: 'yes'");
+        assert_eq!(new_group.attributes.len(), 2);
+        assert!(yaml.contains("requirement_level: opt_in"));
+        assert!(yaml.contains("value: \"inet6\""));
+
+        // The new group resolves with the registry.
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(SERVER, "server.yaml").unwrap();
+        registry.load_from_str(&yaml, "new.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::with_keep_specs()).unwrap();
+        assert!(registry.attribute("server.socket.family").is_some());
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Canonical form of a resolved semantic convention registry, re-emitted as
+//! semantic convention YAML (a single file or one file per group).
+//!
+//! The canonical form of a group doesn't use a `prefix`: the ids of its
+//! attributes are fully qualified, and the name of an event defaulting to
+//! its prefix is made explicit. The groups are sorted by id, the attributes
+//! keep the order of their definition. The comments and the formatting of
+//! the original files are not preserved (see [`crate::refactor`] for the
+//! refactorings editing the files in place).
+
+use std::path::Path;
+
+use crate::attribute::AttributeSpec;
+use crate::group::{ConvTypeSpec, GroupSpec};
+use crate::{Error, SemConvSpec, SemConvSpecs};
+
+impl SemConvSpecs {
+    /// Returns the canonical form of the registry as a single semantic
+    /// convention spec (see the [module documentation](crate::normalize)).
+    /// Note: the specs must be kept by the resolver (see
+    /// [`crate::ResolverConfig::with_keep_specs`]), otherwise the spec has no
+    /// groups.
+    pub fn to_spec(&self) -> SemConvSpec {
+        let mut groups: Vec<GroupSpec> = self.groups().map(normalize_group).collect();
+        groups.sort_by(|a, b| a.id.cmp(&b.id));
+        SemConvSpec { groups }
+    }
+
+    /// Writes the canonical form of the registry in a single semantic
+    /// convention file (see [`SemConvSpecs::to_spec`]).
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        write_spec(path.as_ref(), &self.to_spec())
+    }
+
+    /// Writes the canonical form of the registry in a directory, one
+    /// semantic convention file per group named after the id of the group
+    /// (e.g. `registry.http.yaml`). The directory is created if missing.
+    /// Returns the number of files written.
+    pub fn save_to_dir<P: AsRef<Path>>(&self, dir: P) -> Result<usize, Error> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(|e| Error::RegistryWriteFailed {
+            path: dir.display().to_string(),
+            error: e.to_string(),
+        })?;
+        let spec = self.to_spec();
+        for group in spec.groups.iter() {
+            write_spec(
+                &dir.join(format!("{}.yaml", group.id)),
+                &SemConvSpec {
+                    groups: vec![group.clone()],
+                },
+            )?;
+        }
+        Ok(spec.groups.len())
+    }
+}
+
+/// Returns the canonical form of a group: the attribute ids are prefixed
+/// with the prefix of the group, which is removed.
+fn normalize_group(group: &GroupSpec) -> GroupSpec {
+    let mut group = group.clone();
+    if group.prefix.is_empty() {
+        return group;
+    }
+    let prefix = std::mem::take(&mut group.prefix);
+    for attr in group.attributes.iter_mut() {
+        if let AttributeSpec::Id { id, .. } = attr {
+            *id = format!("{}.{}", prefix, id);
+        }
+    }
+    if group.r#type == ConvTypeSpec::Event && group.name.is_none() {
+        group.name = Some(prefix);
+    }
+    group
+}
+
+/// Writes a semantic convention spec as YAML.
+fn write_spec(path: &Path, spec: &SemConvSpec) -> Result<(), Error> {
+    let error = |error: String| Error::RegistryWriteFailed {
+        path: path.display().to_string(),
+        error,
+    };
+    let yaml = serde_yaml::to_string(spec).map_err(|e| error(e.to_string()))?;
+    std::fs::write(path, yaml).map_err(|e| error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::attribute::AttributeSpec;
+    use crate::{ResolverConfig, SemConvSpec, SemConvSpecs};
+
+    #[test]
+    fn test_to_spec() {
+        let content = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: "HTTP attributes."
+    prefix: http
+    attributes:
+      - id: route
+        type: string
+        brief: "The matched route."
+        examples: ["/users/:id"]
+  - id: exception
+    type: event
+    brief: "An exception."
+    prefix: exception
+    attributes:
+      - id: message
+        type: string
+        brief: "The exception message."
+        examples: ["Division by zero"]
+  - id: http.server
+    type: span
+    brief: "HTTP server span."
+    attributes:
+      - ref: http.route
+"#;
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(content, "http.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::with_keep_specs()).unwrap();
+
+        let spec = registry.to_spec();
+        let ids: Vec<&str> = spec.groups.iter().map(|group| group.id.as_str()).collect();
+        assert_eq!(ids, vec!["exception", "http.server", "registry.http"]);
+        assert!(spec.groups.iter().all(|group| group.prefix.is_empty()));
+        assert_eq!(spec.groups[0].name.as_deref(), Some("exception"));
+        assert_eq!(spec.groups[0].attributes[0].id(), "exception.message");
+        assert_eq!(spec.groups[2].attributes[0].id(), "http.route");
+
+        // The canonical form is a valid registry resolving to the same
+        // attributes, and is stable.
+        let yaml = serde_yaml::to_string(&spec).unwrap();
+        assert!(!yaml.contains("prefix"));
+        let mut normalized = SemConvSpecs::default();
+        normalized.load_from_str(&yaml, "normalized.yaml").unwrap();
+        _ = normalized
+            .resolve(ResolverConfig::with_keep_specs())
+            .unwrap();
+        assert_eq!(normalized.attribute_count(), registry.attribute_count());
+        assert!(matches!(
+            normalized.attribute("http.route"),
+            Some(AttributeSpec::Id { .. })
+        ));
+        assert_eq!(serde_yaml::to_string(&normalized.to_spec()).unwrap(), yaml);
+
+        // Without the specs, the canonical form is empty.
+        let mut registry = SemConvSpecs::default();
+        registry.load_from_str(content, "http.yaml").unwrap();
+        _ = registry.resolve(ResolverConfig::default()).unwrap();
+        let SemConvSpec { groups } = registry.to_spec();
+        assert!(groups.is_empty());
+    }
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Position (line and column) of the groups and attributes in their
+//! semantic convention file, reported by the errors so the editors and the CI
+//! annotations can point at the offending line.
+//!
+//! serde_yaml doesn't expose the location of the deserialized values, so the
+//! positions are recovered by scanning the content once deserialized: the
+//! `id` line of each group (in the order of the groups), then the `id` or
+//! `ref` line of each attribute between the group and the next one. The
+//! positions are a best effort, a group or an attribute whose line can't be
+//! found (e.g. a JSON document on a single line) has no position.
+//!
+//! The diagnostics also carry a JSON pointer into their document (e.g.
+//! `/groups/4/attributes/2/type`), stable whatever the formatting of the file,
+//! so the tools editing the YAML programmatically can apply precise fixes.
+//! The pointer of each group is recorded when its document is deserialized
+//! (the indexes are relative to the document of a multi-document file), the
+//! deserialization errors being located with the path reported by serde_yaml.
+
+use std::fmt::{Display, Formatter};
+
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::attribute::AttributeSpec;
+use crate::group::GroupSpec;
+
+/// A position in a semantic convention file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    /// The line (starting at 1).
+    pub line: usize,
+    /// The column (starting at 1).
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Records the position of the given groups and of their attributes, the
+/// groups being deserialized from the given content.
+pub(crate) fn locate(groups: &mut [GroupSpec], content: &str) {
+    let lines: Vec<&str> = content.lines().collect();
+
+    // The `id` line of each group (none if not found).
+    let mut cursor = 0;
+    let group_lines: Vec<Option<usize>> = groups
+        .iter()
+        .map(|group| {
+            let index = find(&lines, cursor..lines.len(), "id", &group.id)?;
+            cursor = index + 1;
+            Some(index)
+        })
+        .collect();
+
+    for (i, group) in groups.iter_mut().enumerate() {
+        let Some(start) = group_lines[i] else {
+            continue;
+        };
+        let end = group_lines[i + 1..]
+            .iter()
+            .find_map(|line| *line)
+            .unwrap_or(lines.len());
+        group.position = position(lines[start], start);
+
+        let mut cursor = start + 1;
+        for attr in group.attributes.iter() {
+            let (key, name) = match attr {
+                AttributeSpec::Ref { r#ref, .. } => ("ref", r#ref),
+                AttributeSpec::Id { id, .. } => ("id", id),
+            };
+            if let Some(index) = find(&lines, cursor..end, key, name) {
+                if let Some(position) = position(lines[index], index) {
+                    _ = group.attribute_positions.insert(name.clone(), position);
+                }
+                cursor = index + 1;
+            }
+        }
+    }
+}
+
+/// Records the JSON pointer of the groups of a document (`/groups/<index>`).
+pub(crate) fn point(groups: &mut [GroupSpec]) {
+    for (index, group) in groups.iter_mut().enumerate() {
+        group.pointer = Some(format!("/groups/{}", index));
+    }
+}
+
+/// Returns the JSON pointer of a deserialization error from the path
+/// prefixing its message (e.g. `groups[4].attributes[2].type: unknown
+/// variant ...`), or `None` if the error has no path.
+pub(crate) fn yaml_pointer(error: &serde_yaml::Error) -> Option<String> {
+    let message = error.to_string();
+    let (path, _) = message.split_once(": ")?;
+    if path.is_empty() || path.contains(char::is_whitespace) {
+        return None;
+    }
+    let mut pointer = String::new();
+    for segment in path.split('.') {
+        // The unknown parts of a path are reported as `?`.
+        if segment.starts_with('?') {
+            break;
+        }
+        let (key, indexes) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+        if !key.is_empty() {
+            pointer.push('/');
+            pointer.push_str(&escape(key));
+        }
+        for index in indexes.split_terminator(']') {
+            let index = index.strip_prefix('[')?;
+            if !index.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            pointer.push('/');
+            pointer.push_str(index);
+        }
+    }
+    (!pointer.is_empty()).then_some(pointer)
+}
+
+/// Returns the JSON pointer of the first validation error of the given
+/// groups (the group pointer followed by the path of the invalid field).
+pub(crate) fn validation_pointer(
+    groups: &[GroupSpec],
+    errors: &ValidationErrors,
+) -> Option<String> {
+    let Some(ValidationErrorsKind::List(groups_errors)) = errors.errors().get("groups") else {
+        return None;
+    };
+    let (index, group_errors) = groups_errors.iter().next()?;
+    let mut pointer = groups.get(*index)?.pointer.clone()?;
+    let mut errors: &ValidationErrors = group_errors;
+    loop {
+        // The errors of a struct (e.g. of the group itself) are reported
+        // under `__all__`.
+        let Some((field, kind)) = errors
+            .errors()
+            .iter()
+            .filter(|(field, _)| **field != "__all__")
+            .min_by_key(|(field, _)| **field)
+        else {
+            return Some(pointer);
+        };
+        pointer.push('/');
+        pointer.push_str(&escape(field));
+        match kind {
+            ValidationErrorsKind::Struct(inner) => errors = inner,
+            ValidationErrorsKind::List(items) => {
+                let Some((index, inner)) = items.iter().next() else {
+                    return Some(pointer);
+                };
+                pointer.push_str(&format!("/{}", index));
+                errors = inner;
+            }
+            ValidationErrorsKind::Field(_) => return Some(pointer),
+        }
+    }
+}
+
+/// Escapes a key of a JSON pointer (`~` and `/`, see RFC 6901).
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// Returns the index of the first line of the range defining the given key
+/// with the given value.
+fn find(lines: &[&str], range: std::ops::Range<usize>, key: &str, value: &str) -> Option<usize> {
+    range
+        .into_iter()
+        .find(|index| key_value(lines[*index]).is_some_and(|(k, _, v)| k == key && v == value))
+}
+
+/// Returns the position of the key defined on the line with the given index.
+fn position(line: &str, index: usize) -> Option<Position> {
+    key_value(line).map(|(_, column, _)| Position {
+        line: index + 1,
+        column,
+    })
+}
+
+/// Returns the key, the column of the key and the scalar value of a YAML
+/// (`- id: value`) or JSON (`"id": "value",`) line.
+fn key_value(line: &str) -> Option<(&str, usize, &str)> {
+    let rest = line.trim_start_matches(|c: char| c.is_whitespace() || c == '-' || c == '{');
+    let column = line[..line.len() - rest.len()].chars().count() + 1;
+    let (key, value) = rest.split_once(':')?;
+    let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+    let value = value.trim_start();
+    // A quoted value can be followed by the other fields of a JSON object.
+    let value = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+        _ => value
+            .split(" #")
+            .next()
+            .unwrap_or_default()
+            .trim_end()
+            .trim_end_matches(','),
+    };
+    Some((key, column, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+    use crate::SemConvSpec;
+
+    #[test]
+    fn test_locate_yaml() {
+        let content = r#"groups:
+  - id: registry.server
+    type: attribute_group
+    brief: "Server attributes."
+    prefix: server
+    attributes:
+      - id: address # The server address.
+        type: string
+        brief: Server address.
+        examples: ["example.com"]
+      - id: port
+        type: int
+        brief: Server port.
+        examples: [80]
+  - id: server
+    type: span
+    brief: "Server span."
+    attributes:
+      - ref: server.address
+      - ref: 'server.port'
+"#;
+        let spec = SemConvSpec::load_from_str(content, "server.yaml").unwrap();
+        let registry = &spec.groups[0];
+        assert_eq!(registry.position, Some(Position { line: 2, column: 5 }));
+        assert_eq!(
+            registry.attribute_positions.get("address"),
+            Some(&Position { line: 7, column: 9 })
+        );
+        assert_eq!(
+            registry.attribute_positions.get("port"),
+            Some(&Position {
+                line: 11,
+                column: 9
+            })
+        );
+        let server = &spec.groups[1];
+        assert_eq!(
+            server.position,
+            Some(Position {
+                line: 15,
+                column: 5
+            })
+        );
+        assert_eq!(
+            server.attribute_positions.get("server.port"),
+            Some(&Position {
+                line: 20,
+                column: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_locate_json() {
+        let content = r#"{
+  "groups": [
+    {
+      "id": "registry.url",
+      "type": "attribute_group",
+      "brief": "URL attributes.",
+      "attributes": [
+        {"id": "url.full", "type": "string", "brief": "URL.", "examples": ["https://example.com"]}
+      ]
+    }
+  ]
+}"#;
+        let spec = SemConvSpec::load_from_str(content, "url.json").unwrap();
+        let group = &spec.groups[0];
+        assert_eq!(group.position, Some(Position { line: 4, column: 7 }));
+        // The attribute is defined on a single line with its other fields.
+        assert_eq!(
+            group.attribute_positions.get("url.full"),
+            Some(&Position {
+                line: 8,
+                column: 10
+            })
+        );
+    }
+}
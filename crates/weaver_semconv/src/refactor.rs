@@ -0,0 +1,691 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Refactorings moving groups between the files of a semantic convention
+//! registry (e.g. splitting a large file or merging several files), or
+//! removing groups and attributes (e.g. pruning the deprecated ones).
+//!
+//! The files are edited as text so the comments and the formatting of the
+//! moved groups and of the rest of the files are preserved. A group is moved
+//! with the comment lines directly preceding it and is re-indented to the
+//! indentation of the groups of the target file.
+
+use std::ops::Range;
+use std::path::Path;
+
+use crate::Error;
+
+/// A semantic convention file split into the text blocks of its groups.
+#[derive(Debug, Clone)]
+pub struct RegistryFile {
+    /// The path of the file.
+    path: String,
+    /// The lines of the file.
+    lines: Vec<String>,
+    /// The indentation of the items of the `groups` sequence.
+    item_indent: usize,
+    /// The groups of the file, in the order of the file.
+    groups: Vec<GroupBlock>,
+    /// The index of the line following the `groups` section.
+    groups_end: usize,
+}
+
+/// The lines of a group in a semantic convention file.
+#[derive(Debug, Clone)]
+struct GroupBlock {
+    /// The id of the group.
+    id: String,
+    /// The index of the first line of the group (including its leading
+    /// comments).
+    start: usize,
+    /// The index of the line following the group.
+    end: usize,
+}
+
+/// The new content of the files concerned by a refactoring.
+#[derive(Debug)]
+pub struct Refactoring {
+    /// The new content of the source file or None if all its groups were
+    /// moved (i.e. the file can be removed).
+    pub source: Option<String>,
+    /// The new content of the target file.
+    pub target: String,
+    /// The ids of the moved groups.
+    pub moved_groups: Vec<String>,
+}
+
+impl RegistryFile {
+    /// Loads a semantic convention file.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<RegistryFile, Error> {
+        let path = path.as_ref().display().to_string();
+        let content = std::fs::read_to_string(&path).map_err(|e| Error::CatalogNotFound {
+            path_or_url: path.clone(),
+            error: e.to_string(),
+        })?;
+        RegistryFile::parse(&path, &content)
+    }
+
+    /// Returns a semantic convention file without groups, its groups being
+    /// indented like the groups of the given file.
+    pub fn empty_like(path: &str, file: &RegistryFile) -> RegistryFile {
+        RegistryFile {
+            path: path.to_owned(),
+            lines: vec!["groups:".to_owned()],
+            item_indent: file.item_indent,
+            groups: vec![],
+            groups_end: 1,
+        }
+    }
+
+    /// Parses the content of a semantic convention file.
+    pub fn parse(path: &str, content: &str) -> Result<RegistryFile, Error> {
+        let invalid = |error: String| Error::InvalidRefactoring {
+            path: path.to_owned(),
+            error,
+        };
+        let lines: Vec<String> = content.lines().map(str::to_owned).collect();
+        let groups_line = lines
+            .iter()
+            .position(|line| {
+                line.strip_prefix("groups:")
+                    .is_some_and(|rest| rest.trim().is_empty() || rest.trim().starts_with('#'))
+            })
+            .ok_or_else(|| invalid("no block sequence `groups:` found".to_owned()))?;
+
+        let mut item_indent = None;
+        let mut item_starts = vec![];
+        let mut groups_end = lines.len();
+        for (index, line) in lines.iter().enumerate().skip(groups_line + 1) {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - trimmed.len();
+            let is_item = trimmed == "-" || trimmed.starts_with("- ");
+            let expected_indent = *item_indent.get_or_insert(indent);
+            if indent < expected_indent || (indent == expected_indent && !is_item) {
+                groups_end = index;
+                break;
+            }
+            if indent == expected_indent {
+                item_starts.push(index);
+            }
+        }
+        // The comments and blank lines preceding the next top-level key
+        // don't belong to the last group.
+        while groups_end > groups_line + 1 && is_blank_or_comment(&lines[groups_end - 1]) {
+            groups_end -= 1;
+        }
+        let item_indent = item_indent.unwrap_or(2);
+
+        let mut groups: Vec<GroupBlock> = vec![];
+        for (i, &item_start) in item_starts.iter().enumerate() {
+            // Comment lines directly preceding a group belong to it.
+            let mut start = item_start;
+            while start > groups_line + 1 && is_comment(&lines[start - 1], item_indent) {
+                start -= 1;
+            }
+            if let Some(previous) = groups.last_mut() {
+                previous.end = start;
+            }
+            let end = item_starts.get(i + 1).copied().unwrap_or(groups_end);
+            let text = indent_lines(&lines[item_start..end], item_indent, 0).join("\n");
+            let item: serde_yaml::Value = serde_yaml::from_str(&text)
+                .map_err(|e| invalid(format!("line {}: {}", item_start + 1, e)))?;
+            let id = item
+                .as_sequence()
+                .and_then(|items| items.first())
+                .and_then(|group| group.get("id"))
+                .and_then(serde_yaml::Value::as_str)
+                .ok_or_else(|| invalid(format!("line {}: group without id", item_start + 1)))?;
+            groups.push(GroupBlock {
+                id: id.to_owned(),
+                start,
+                end,
+            });
+        }
+        if let Some(last) = groups.last_mut() {
+            last.end = groups_end;
+        }
+
+        Ok(RegistryFile {
+            path: path.to_owned(),
+            lines,
+            item_indent,
+            groups,
+            groups_end,
+        })
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns the ids of the groups of the file, in the order of the file.
+    pub fn group_ids(&self) -> Vec<&str> {
+        self.groups.iter().map(|group| group.id.as_str()).collect()
+    }
+
+    /// Returns the content of the file.
+    pub fn content(&self) -> String {
+        let mut content = self.lines.join("\n");
+        content.push('\n');
+        content
+    }
+
+    /// Returns true if the groups of the file are separated by blank lines.
+    fn blank_line_separated(&self) -> bool {
+        self.groups
+            .iter()
+            .rev()
+            .skip(1)
+            .any(|group| self.lines[group.end - 1].trim().is_empty())
+    }
+
+    /// Moves the given groups (or all the groups if `group_ids` is None) of
+    /// this file at the end of the groups of the target file and returns the
+    /// new content of both files. The moved groups keep their order.
+    pub fn move_groups(
+        &self,
+        target: &RegistryFile,
+        group_ids: Option<&[String]>,
+    ) -> Result<Refactoring, Error> {
+        let invalid = |path: &str, error: String| Error::InvalidRefactoring {
+            path: path.to_owned(),
+            error,
+        };
+        if let Some(group_ids) = group_ids {
+            let unknown: Vec<&str> = group_ids
+                .iter()
+                .filter(|id| !self.groups.iter().any(|group| &group.id == *id))
+                .map(String::as_str)
+                .collect();
+            if !unknown.is_empty() {
+                return Err(invalid(
+                    &self.path,
+                    format!("unknown group(s) {}", unknown.join(", ")),
+                ));
+            }
+        }
+        let (moved, kept): (Vec<&GroupBlock>, Vec<&GroupBlock>) = self
+            .groups
+            .iter()
+            .partition(|group| group_ids.map_or(true, |group_ids| group_ids.contains(&group.id)));
+        let duplicates: Vec<&str> = moved
+            .iter()
+            .filter(|group| target.groups.iter().any(|other| other.id == group.id))
+            .map(|group| group.id.as_str())
+            .collect();
+        if !duplicates.is_empty() {
+            return Err(invalid(
+                &target.path,
+                format!("group(s) {} already defined", duplicates.join(", ")),
+            ));
+        }
+
+        // New content of the source file.
+        let source = if kept.is_empty() {
+            None
+        } else {
+            let mut lines: Vec<String> = self
+                .lines
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !moved.iter().any(|g| g.start <= *index && *index < g.end))
+                .map(|(_, line)| line.clone())
+                .collect();
+            // The blank lines separating a moved group from the previous one
+            // are not kept at the end of the file.
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                _ = lines.pop();
+            }
+            Some(lines)
+        };
+
+        // New content of the target file (the moved groups are inserted after
+        // the last non-blank line of its groups).
+        let separated = if target.groups.is_empty() {
+            self.blank_line_separated()
+        } else {
+            target.blank_line_separated()
+        };
+        let mut insert_at = target.groups_end;
+        while insert_at > 0 && target.lines[insert_at - 1].trim().is_empty() {
+            insert_at -= 1;
+        }
+        let mut inserted: Vec<String> = vec![];
+        for (i, group) in moved.iter().enumerate() {
+            let mut block = &self.lines[group.start..group.end];
+            while block.last().is_some_and(|line| line.trim().is_empty()) {
+                block = &block[..block.len() - 1];
+            }
+            if separated && (i > 0 || !target.groups.is_empty()) {
+                inserted.push(String::new());
+            }
+            inserted.extend(indent_lines(block, self.item_indent, target.item_indent));
+        }
+        let mut target_lines = target.lines.clone();
+        _ = target_lines.splice(insert_at..insert_at, inserted);
+
+        // Sanity check of the new content of both files (valid YAML defining
+        // the expected groups).
+        let moved_groups: Vec<String> = moved.iter().map(|group| group.id.clone()).collect();
+        let target_content = join_lines(&target_lines);
+        let expected: Vec<&str> = target
+            .group_ids()
+            .into_iter()
+            .chain(moved_groups.iter().map(String::as_str))
+            .collect();
+        check_content(&target.path, &target_content, &expected)?;
+        let source_content = match source {
+            Some(lines) => {
+                let content = join_lines(&lines);
+                let expected: Vec<&str> = kept.iter().map(|group| group.id.as_str()).collect();
+                check_content(&self.path, &content, &expected)?;
+                Some(content)
+            }
+            None => None,
+        };
+
+        Ok(Refactoring {
+            source: source_content,
+            target: target_content,
+            moved_groups,
+        })
+    }
+
+    /// Removes the given groups and the given attributes of the other groups
+    /// of this file, and returns the new content of the file or None if no
+    /// group is left (i.e. the file can be removed). The attributes are
+    /// identified by the id of their group and by their `id` (definitions) or
+    /// `ref` (references) as written in the file, e.g.
+    /// `("registry.http", "method")`. A group left without attributes loses
+    /// its `attributes` section.
+    pub fn remove(
+        &self,
+        group_ids: &[String],
+        attributes: &[(String, String)],
+    ) -> Result<Option<String>, Error> {
+        let unknown: Vec<&str> = group_ids
+            .iter()
+            .chain(attributes.iter().map(|(group_id, _)| group_id))
+            .filter(|id| !self.groups.iter().any(|group| &group.id == *id))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(Error::InvalidRefactoring {
+                path: self.path.clone(),
+                error: format!("unknown group(s) {}", unknown.join(", ")),
+            });
+        }
+
+        let mut removed: Vec<Range<usize>> = vec![];
+        let mut kept: Vec<&str> = vec![];
+        for group in self.groups.iter() {
+            if group_ids.contains(&group.id) {
+                removed.push(group.start..group.end);
+                continue;
+            }
+            kept.push(&group.id);
+            let names: Vec<&str> = attributes
+                .iter()
+                .filter(|(group_id, _)| group_id == &group.id)
+                .map(|(_, name)| name.as_str())
+                .collect();
+            if !names.is_empty() {
+                removed.extend(self.attribute_blocks(group, &names)?);
+            }
+        }
+        if kept.is_empty() {
+            return Ok(None);
+        }
+
+        let mut lines: Vec<String> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !removed.iter().any(|range| range.contains(index)))
+            .map(|(_, line)| line.clone())
+            .collect();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            _ = lines.pop();
+        }
+        let content = join_lines(&lines);
+        check_content(&self.path, &content, &kept)?;
+        Ok(Some(content))
+    }
+
+    /// Returns the line ranges of the attributes of a group with the given
+    /// `id` or `ref` (with their leading comments), or the range of the whole
+    /// `attributes` section if all the attributes of the group are removed.
+    fn attribute_blocks(
+        &self,
+        group: &GroupBlock,
+        names: &[&str],
+    ) -> Result<Vec<Range<usize>>, Error> {
+        let invalid = |error: String| Error::InvalidRefactoring {
+            path: self.path.clone(),
+            error,
+        };
+        let lines = &self.lines;
+        let item_line = (group.start..group.end)
+            .find(|&index| !is_blank_or_comment(&lines[index]))
+            .unwrap_or(group.start);
+        // The keys of the group are aligned with the first key of the item.
+        let item = lines[item_line].trim_start();
+        let key_indent = indentation(&lines[item_line]) + 1 + indentation(&item[1..]);
+        let Some(attributes_line) = (item_line + 1..group.end).find(|&index| {
+            indentation(&lines[index]) == key_indent
+                && lines[index].trim_start().starts_with("attributes:")
+        }) else {
+            return Err(invalid(format!("group {} has no attributes", group.id)));
+        };
+
+        let mut item_indent = None;
+        let mut item_starts = vec![];
+        let mut section_end = group.end;
+        for (index, line) in lines
+            .iter()
+            .enumerate()
+            .take(group.end)
+            .skip(attributes_line + 1)
+        {
+            if is_blank_or_comment(line) {
+                continue;
+            }
+            let indent = indentation(line);
+            let trimmed = line.trim_start();
+            let is_item = trimmed == "-" || trimmed.starts_with("- ");
+            let expected_indent = *item_indent.get_or_insert(indent);
+            if indent < key_indent
+                || indent < expected_indent
+                || (indent == expected_indent && !is_item)
+            {
+                section_end = index;
+                break;
+            }
+            if indent == expected_indent {
+                item_starts.push(index);
+            }
+        }
+        while section_end > attributes_line + 1 && is_blank_or_comment(&lines[section_end - 1]) {
+            section_end -= 1;
+        }
+        let item_indent = item_indent.unwrap_or(key_indent);
+
+        // Comment lines directly preceding an attribute belong to it.
+        let block_starts: Vec<usize> = item_starts
+            .iter()
+            .map(|&item_start| {
+                let mut start = item_start;
+                while start > attributes_line + 1 && is_comment(&lines[start - 1], item_indent) {
+                    start -= 1;
+                }
+                start
+            })
+            .collect();
+        let mut blocks = vec![];
+        let mut found: Vec<&str> = vec![];
+        for (i, &item_start) in item_starts.iter().enumerate() {
+            let end = block_starts.get(i + 1).copied().unwrap_or(section_end);
+            let text = indent_lines(&lines[item_start..end], item_indent, 0).join("\n");
+            let item: serde_yaml::Value = serde_yaml::from_str(&text)
+                .map_err(|e| invalid(format!("line {}: {}", item_start + 1, e)))?;
+            let attribute = item.as_sequence().and_then(|items| items.first());
+            let name = ["id", "ref"].iter().find_map(|key| {
+                attribute
+                    .and_then(|attribute| attribute.get(key))
+                    .and_then(serde_yaml::Value::as_str)
+            });
+            if let Some(name) = name.and_then(|name| names.iter().find(|n| **n == name)) {
+                blocks.push(block_starts[i]..end);
+                found.push(name);
+            }
+        }
+
+        let missing: Vec<&str> = names
+            .iter()
+            .filter(|name| !found.contains(name))
+            .copied()
+            .collect();
+        if !missing.is_empty() {
+            return Err(invalid(format!(
+                "attribute(s) {} not found in group {}",
+                missing.join(", "),
+                group.id
+            )));
+        }
+        if blocks.len() == item_starts.len() {
+            return Ok(std::iter::once(attributes_line..section_end).collect());
+        }
+        Ok(blocks)
+    }
+}
+
+/// Returns true if the line is a comment indented at most like the groups.
+fn is_comment(line: &str, item_indent: usize) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with('#') && line.len() - trimmed.len() <= item_indent
+}
+
+/// Returns the indentation of a line.
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Returns true if the line is blank or a comment.
+fn is_blank_or_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Re-indents lines indented from `from` to be indented from `to`.
+fn indent_lines(lines: &[String], from: usize, to: usize) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return String::new();
+            }
+            let indent = line.len() - line.trim_start().len();
+            let stripped = &line[indent.min(from)..];
+            format!("{}{}", " ".repeat(to), stripped)
+        })
+        .collect()
+}
+
+/// Joins lines into the content of a file.
+fn join_lines(lines: &[String]) -> String {
+    let mut content = lines.join("\n");
+    content.push('\n');
+    content
+}
+
+/// Checks the content of a file is valid and defines the expected groups.
+fn check_content(path: &str, content: &str, expected: &[&str]) -> Result<(), Error> {
+    let file = RegistryFile::parse(path, content)?;
+    if file.group_ids() != expected {
+        return Err(Error::InvalidRefactoring {
+            path: path.to_owned(),
+            error: format!(
+                "unexpected groups after refactoring ({} instead of {})",
+                file.group_ids().join(", "),
+                expected.join(", ")
+            ),
+        });
+    }
+    let _: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| Error::InvalidRefactoring {
+            path: path.to_owned(),
+            error: e.to_string(),
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::refactor::RegistryFile;
+    use crate::{Error, SemConvSpec};
+
+    const SOURCE: &str = "\
+# Header comment.
+groups:
+  # The URL attributes.
+  - id: url
+    brief: Attributes describing URL.
+    type: attribute_group
+    prefix: url
+    attributes:
+      - id: scheme
+        type: string
+        brief: >
+          The URI scheme.
+
+          # Not a comment.
+        examples: ['https']
+
+  - id: server
+    type: attribute_group
+    prefix: server
+    brief: Server attributes. # Trailing comment.
+    attributes:
+      - id: address
+        type: string
+        brief: Server address.
+        examples: ['example.com']
+
+  - id: client
+    type: attribute_group
+    prefix: client
+    brief: Client attributes.
+    attributes:
+      - id: port
+        type: int
+        brief: Client port.
+        examples: [65123]
+";
+
+    const TARGET: &str = "\
+groups:
+- id: network
+  type: attribute_group
+  prefix: network
+  brief: Network attributes.
+  attributes:
+  - id: type
+    type: string
+    brief: Network type.
+    examples: ['ipv4']
+";
+
+    #[test]
+    fn test_parse() {
+        let file = RegistryFile::parse("source.yaml", SOURCE).unwrap();
+        assert_eq!(file.group_ids(), vec!["url", "server", "client"]);
+        assert_eq!(file.content(), SOURCE);
+
+        let file = RegistryFile::parse("target.yaml", TARGET).unwrap();
+        assert_eq!(file.group_ids(), vec!["network"]);
+        assert!(matches!(
+            RegistryFile::parse("invalid.yaml", "attributes: []\n"),
+            Err(Error::InvalidRefactoring { .. })
+        ));
+    }
+
+    #[test]
+    fn test_split() {
+        let source = RegistryFile::parse("source.yaml", SOURCE).unwrap();
+        let target = RegistryFile::empty_like("url.yaml", &source);
+        let refactoring = source
+            .move_groups(&target, Some(&["url".to_owned(), "client".to_owned()]))
+            .unwrap();
+
+        assert_eq!(refactoring.moved_groups, vec!["url", "client"]);
+        let source_content = refactoring.source.unwrap();
+        assert!(source_content.starts_with("# Header comment.\ngroups:\n  - id: server\n"));
+        assert!(source_content.contains("brief: Server attributes. # Trailing comment.\n"));
+        assert!(!source_content.contains("url"));
+        assert!(refactoring
+            .target
+            .starts_with("groups:\n  # The URL attributes.\n  - id: url\n"));
+        assert!(refactoring
+            .target
+            .contains("\n\n          # Not a comment.\n"));
+        assert!(refactoring
+            .target
+            .contains("examples: ['https']\n\n  - id: client\n"));
+
+        let spec: SemConvSpec = serde_yaml::from_str(&refactoring.target).unwrap();
+        assert_eq!(spec.groups.len(), 2);
+        let spec: SemConvSpec = serde_yaml::from_str(&source_content).unwrap();
+        assert_eq!(spec.groups.len(), 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let source = RegistryFile::parse("source.yaml", SOURCE).unwrap();
+        let target = RegistryFile::parse("target.yaml", TARGET).unwrap();
+        let refactoring = source.move_groups(&target, None).unwrap();
+
+        assert!(refactoring.source.is_none());
+        let target = RegistryFile::parse("target.yaml", &refactoring.target).unwrap();
+        assert_eq!(
+            target.group_ids(),
+            vec!["network", "url", "server", "client"]
+        );
+        // The moved groups are re-indented like the groups of the target.
+        assert!(refactoring
+            .target
+            .contains("examples: ['ipv4']\n# The URL attributes.\n- id: url\n"));
+        assert!(refactoring
+            .target
+            .contains("\n  attributes:\n    - id: address\n"));
+
+        // Groups already defined by the target are not moved.
+        let source = RegistryFile::parse("source.yaml", &refactoring.target).unwrap();
+        assert!(matches!(
+            source.move_groups(&target, Some(&["url".to_owned()])),
+            Err(Error::InvalidRefactoring { .. })
+        ));
+        assert!(matches!(
+            source.move_groups(&target, Some(&["unknown".to_owned()])),
+            Err(Error::InvalidRefactoring { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remove() {
+        let source = RegistryFile::parse("source.yaml", SOURCE).unwrap();
+        let content = source
+            .remove(
+                &["client".to_owned()],
+                &[("url".to_owned(), "scheme".to_owned())],
+            )
+            .unwrap()
+            .unwrap();
+
+        let file = RegistryFile::parse("source.yaml", &content).unwrap();
+        assert_eq!(file.group_ids(), vec!["url", "server"]);
+        assert!(!content.contains("scheme"));
+        assert!(!content.contains("client"));
+        // The group left without attributes loses its `attributes` section.
+        assert!(content.contains("    prefix: url\n\n  - id: server\n"));
+        assert!(content.ends_with("examples: ['example.com']\n"));
+        let spec: SemConvSpec = serde_yaml::from_str(&content).unwrap();
+        assert!(spec.groups[0].attributes.is_empty());
+        assert_eq!(spec.groups[1].attributes.len(), 1);
+
+        // A file without groups can be removed.
+        let ids: Vec<String> = source.group_ids().iter().map(|id| id.to_string()).collect();
+        assert!(source.remove(&ids, &[]).unwrap().is_none());
+
+        assert!(matches!(
+            source.remove(&[], &[("server".to_owned(), "port".to_owned())]),
+            Err(Error::InvalidRefactoring { .. })
+        ));
+        assert!(matches!(
+            source.remove(&["unknown".to_owned()], &[]),
+            Err(Error::InvalidRefactoring { .. })
+        ));
+    }
+}
@@ -2,19 +2,53 @@
 
 //! Stability specification.
 
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
 /// The level of stability for a definition.
+///
+/// Unknown stability levels (e.g. levels introduced by a newer version of the
+/// semantic conventions) are preserved as [`StabilitySpec::Other`] instead of
+/// failing the deserialization. The resolver reports them as errors, or as
+/// warnings in tolerant mode (see `ResolverConfig::with_tolerant_stability`).
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Hash)]
-#[serde(rename_all = "snake_case")]
+#[serde(from = "String", into = "String")]
 pub enum StabilitySpec {
     /// A deprecated definition.
     Deprecated,
     /// An experimental definition.
     Experimental,
+    /// A release candidate definition, i.e. a definition about to become
+    /// stable.
+    ReleaseCandidate,
     /// A stable definition.
     Stable,
+    /// A stability level unknown to this version of weaver.
+    Other(String),
+}
+
+impl From<String> for StabilitySpec {
+    fn from(stability: String) -> Self {
+        match stability.as_str() {
+            "deprecated" => StabilitySpec::Deprecated,
+            "experimental" => StabilitySpec::Experimental,
+            "release_candidate" => StabilitySpec::ReleaseCandidate,
+            "stable" => StabilitySpec::Stable,
+            _ => StabilitySpec::Other(stability),
+        }
+    }
+}
+
+impl From<StabilitySpec> for String {
+    fn from(stability: StabilitySpec) -> Self {
+        match stability {
+            StabilitySpec::Other(stability) => stability,
+            stability => stability.to_string(),
+        }
+    }
 }
 
 /// Implements a human readable display for the stability.
@@ -23,7 +57,108 @@ impl Display for StabilitySpec {
         match self {
             StabilitySpec::Deprecated => write!(f, "deprecated"),
             StabilitySpec::Experimental => write!(f, "experimental"),
+            StabilitySpec::ReleaseCandidate => write!(f, "release_candidate"),
             StabilitySpec::Stable => write!(f, "stable"),
+            StabilitySpec::Other(stability) => write!(f, "{}", stability),
         }
     }
 }
+
+/// The stability is described as a string, the known levels being listed in
+/// the description (the other levels are accepted in tolerant mode).
+impl JsonSchema for StabilitySpec {
+    fn schema_name() -> String {
+        "StabilitySpec".to_owned()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let mut schema = String::json_schema(generator).into_object();
+        schema.metadata().description = Some(
+            "The level of stability for a definition: `deprecated`, `experimental`, `release_candidate` or `stable`."
+                .to_owned(),
+        );
+        schema.into()
+    }
+}
+
+/// The minimum stability of the definitions kept by the resolver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StabilityFilter {
+    /// Only the stable definitions.
+    Stable,
+    /// The stable and experimental definitions (the deprecated definitions
+    /// are excluded).
+    Experimental,
+    /// All the definitions.
+    #[default]
+    All,
+}
+
+impl StabilityFilter {
+    /// Returns true if a definition with the given stability is kept. A
+    /// definition without stability is considered as experimental.
+    pub fn accepts(&self, stability: Option<&StabilitySpec>) -> bool {
+        match self {
+            StabilityFilter::Stable => stability == Some(&StabilitySpec::Stable),
+            StabilityFilter::Experimental => stability != Some(&StabilitySpec::Deprecated),
+            StabilityFilter::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stability_filter() {
+        let stabilities = [
+            None,
+            Some(StabilitySpec::Deprecated),
+            Some(StabilitySpec::Experimental),
+            Some(StabilitySpec::ReleaseCandidate),
+            Some(StabilitySpec::Stable),
+            Some(StabilitySpec::Other("alpha".to_owned())),
+        ];
+        let accepted = |filter: StabilityFilter| -> Vec<bool> {
+            stabilities
+                .iter()
+                .map(|stability| filter.accepts(stability.as_ref()))
+                .collect()
+        };
+
+        assert_eq!(
+            accepted(StabilityFilter::Stable),
+            [false, false, false, false, true, false]
+        );
+        assert_eq!(
+            accepted(StabilityFilter::Experimental),
+            [true, false, true, true, true, true]
+        );
+        assert_eq!(
+            accepted(StabilityFilter::All),
+            [true, true, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_stability_serde() {
+        let stabilities: Vec<StabilitySpec> =
+            serde_yaml::from_str("[deprecated, experimental, release_candidate, stable, alpha]")
+                .unwrap();
+        assert_eq!(
+            stabilities,
+            vec![
+                StabilitySpec::Deprecated,
+                StabilitySpec::Experimental,
+                StabilitySpec::ReleaseCandidate,
+                StabilitySpec::Stable,
+                StabilitySpec::Other("alpha".to_owned()),
+            ]
+        );
+        assert_eq!(
+            serde_yaml::to_string(&stabilities).unwrap(),
+            "- deprecated\n- experimental\n- release_candidate\n- stable\n- alpha\n"
+        );
+    }
+}
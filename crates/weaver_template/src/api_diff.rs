@@ -0,0 +1,326 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Summarized diff of the public API of two generated client SDKs (e.g.
+//! generated from two versions of a semantic convention registry).
+//!
+//! The API items are extracted line by line from the generated files with
+//! the `api_items` regular expressions of the language config, the `name`
+//! capture group (or the whole match) naming the item. An item is identified
+//! by its file and its name, its signature being its trimmed line. Without
+//! `api_items`, only the added, removed and changed files are reported.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Serialize;
+
+/// The regular expressions extracting the API items of the generated files.
+#[derive(Debug, Default)]
+pub(crate) struct ApiPatterns {
+    patterns: Vec<Regex>,
+}
+
+/// An item of the public API of a generated client SDK.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ApiItem {
+    /// The generated file defining the item (relative to the output
+    /// directory).
+    pub file: String,
+    /// The name of the item.
+    pub name: String,
+    /// The signature of the item.
+    pub signature: String,
+}
+
+/// An API item whose signature changed.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ApiChange {
+    /// The generated file defining the item (relative to the output
+    /// directory).
+    pub file: String,
+    /// The name of the item.
+    pub name: String,
+    /// The signature of the item in the first client SDK.
+    pub from: String,
+    /// The signature of the item in the second client SDK.
+    pub to: String,
+}
+
+/// The differences between the public APIs of two generated client SDKs.
+#[derive(Serialize, Debug, Default)]
+pub struct ApiDiff {
+    /// The files only generated in the second client SDK.
+    pub added_files: Vec<String>,
+    /// The files only generated in the first client SDK.
+    pub removed_files: Vec<String>,
+    /// The files generated in both client SDKs with a different content.
+    pub changed_files: Vec<String>,
+    /// The API items only defined by the second client SDK.
+    pub added: Vec<ApiItem>,
+    /// The API items only defined by the first client SDK.
+    pub removed: Vec<ApiItem>,
+    /// The API items whose signature changed.
+    pub changed: Vec<ApiChange>,
+}
+
+/// The API items of a client SDK: (file, name) -> signatures, in the order
+/// of the file.
+type ApiItems = BTreeMap<(String, String), Vec<String>>;
+
+impl ApiPatterns {
+    /// Compiles the `api_items` regular expressions of a language config.
+    pub(crate) fn try_new(patterns: &[String]) -> Result<Self, String> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| format!("invalid `api_items` pattern: {}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { patterns })
+    }
+
+    /// Returns the API items (name and signature) of the content of a
+    /// generated file.
+    fn items<'a>(&'a self, content: &'a str) -> impl Iterator<Item = (String, String)> + 'a {
+        content.lines().filter_map(|line| {
+            self.patterns.iter().find_map(|pattern| {
+                let captures = pattern.captures(line)?;
+                let name = captures.name("name").or_else(|| captures.get(0))?;
+                let signature = line.trim().trim_end_matches('{').trim_end();
+                Some((name.as_str().to_owned(), signature.to_owned()))
+            })
+        })
+    }
+
+    /// Returns the differences between the client SDKs generated in the
+    /// directories `from_dir` and `to_dir`.
+    pub(crate) fn diff_dirs(&self, from_dir: &Path, to_dir: &Path) -> Result<ApiDiff, String> {
+        let from_files = generated_files(from_dir)?;
+        let to_files = generated_files(to_dir)?;
+        let mut diff = ApiDiff::default();
+        let mut from_items = ApiItems::new();
+        let mut to_items = ApiItems::new();
+
+        for (file, content) in from_files.iter() {
+            match to_files.get(file) {
+                None => diff.removed_files.push(file.clone()),
+                Some(to_content) if to_content != content => diff.changed_files.push(file.clone()),
+                Some(_) => {}
+            }
+            self.collect_items(&mut from_items, file, content);
+        }
+        for (file, content) in to_files.iter() {
+            if !from_files.contains_key(file) {
+                diff.added_files.push(file.clone());
+            }
+            self.collect_items(&mut to_items, file, content);
+        }
+
+        for ((file, name), from_signatures) in from_items.iter() {
+            let to_signatures = to_items
+                .get(&(file.clone(), name.clone()))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let removed: Vec<&String> = from_signatures
+                .iter()
+                .filter(|signature| !to_signatures.contains(signature))
+                .collect();
+            let added: Vec<&String> = to_signatures
+                .iter()
+                .filter(|signature| !from_signatures.contains(signature))
+                .collect();
+            // The signatures of an item are paired in the order of the file,
+            // the signatures without counterpart being removed or added.
+            for (from, to) in removed.iter().zip(added.iter()) {
+                diff.changed.push(ApiChange {
+                    file: file.clone(),
+                    name: name.clone(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+            }
+            let item = |signature: &&String| ApiItem {
+                file: file.clone(),
+                name: name.clone(),
+                signature: signature.to_string(),
+            };
+            diff.removed
+                .extend(removed.iter().skip(added.len()).map(item));
+            diff.added
+                .extend(added.iter().skip(removed.len()).map(item));
+        }
+        for ((file, name), to_signatures) in to_items.iter() {
+            if !from_items.contains_key(&(file.clone(), name.clone())) {
+                diff.added
+                    .extend(to_signatures.iter().map(|signature| ApiItem {
+                        file: file.clone(),
+                        name: name.clone(),
+                        signature: signature.clone(),
+                    }));
+            }
+        }
+        Ok(diff)
+    }
+
+    /// Adds the API items of a generated file (ignored if not UTF-8).
+    fn collect_items(&self, items: &mut ApiItems, file: &str, content: &[u8]) {
+        let Ok(content) = std::str::from_utf8(content) else {
+            return;
+        };
+        for (name, signature) in self.items(content) {
+            items
+                .entry((file.to_owned(), name))
+                .or_default()
+                .push(signature);
+        }
+    }
+}
+
+impl ApiDiff {
+    /// Returns true if the client SDKs are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added_files.is_empty()
+            && self.removed_files.is_empty()
+            && self.changed_files.is_empty()
+    }
+}
+
+impl Display for ApiDiff {
+    /// Formats the diff as a human readable summary, followed by the added
+    /// (`+`), removed (`-`) and changed (`~`) API items.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "API items: {} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )?;
+        writeln!(
+            f,
+            "Files: {} added, {} removed, {} changed",
+            self.added_files.len(),
+            self.removed_files.len(),
+            self.changed_files.len()
+        )?;
+        for file in self.added_files.iter() {
+            writeln!(f, "+ {}", file)?;
+        }
+        for file in self.removed_files.iter() {
+            writeln!(f, "- {}", file)?;
+        }
+        for item in self.added.iter() {
+            writeln!(f, "+ {}: {}", item.file, item.signature)?;
+        }
+        for item in self.removed.iter() {
+            writeln!(f, "- {}: {}", item.file, item.signature)?;
+        }
+        for change in self.changed.iter() {
+            writeln!(f, "~ {}: {}", change.file, change.name)?;
+            writeln!(f, "    - {}", change.from)?;
+            writeln!(f, "    + {}", change.to)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the content of the files of a directory (recursively), indexed
+/// by their path relative to the directory (with `/` separators).
+fn generated_files(dir: &Path) -> Result<BTreeMap<String, Vec<u8>>, String> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries =
+            fs::read_dir(&current).map_err(|e| format!("{}: {}", current.display(), e))?;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| format!("{}: {}", current.display(), e))?
+                .path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let content = fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let relative_path = path
+                .strip_prefix(dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            _ = files.insert(relative_path, content);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{ApiChange, ApiPatterns};
+
+    #[test]
+    fn test_diff_dirs() {
+        let from_dir = tempdir::TempDir::new("from").unwrap();
+        let to_dir = tempdir::TempDir::new("to").unwrap();
+        fs::create_dir_all(from_dir.path().join("meter")).unwrap();
+        fs::create_dir_all(to_dir.path().join("meter")).unwrap();
+        fs::write(
+            from_dir.path().join("attributes.rs"),
+            "pub const HTTP_METHOD: &str = \"http.method\";\npub const NET_PEER_NAME: &str = \"net.peer.name\";\n",
+        )
+        .unwrap();
+        fs::write(
+            to_dir.path().join("attributes.rs"),
+            "pub const HTTP_METHOD: &str = \"http.request.method\";\npub const SERVER_ADDRESS: &str = \"server.address\";\n",
+        )
+        .unwrap();
+        fs::write(
+            from_dir.path().join("meter/mod.rs"),
+            "pub fn duration() -> Histogram {\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            to_dir.path().join("meter/mod.rs"),
+            "pub fn duration() -> Histogram {\n}\n",
+        )
+        .unwrap();
+        fs::write(to_dir.path().join("README.md"), "# Client SDK\n").unwrap();
+
+        let patterns =
+            ApiPatterns::try_new(&[r"^\s*pub\s+(?:const|fn)\s+(?P<name>\w+)".to_owned()]).unwrap();
+        let diff = patterns.diff_dirs(from_dir.path(), to_dir.path()).unwrap();
+        assert_eq!(diff.added_files, vec!["README.md"]);
+        assert!(diff.removed_files.is_empty());
+        assert_eq!(diff.changed_files, vec!["attributes.rs"]);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "SERVER_ADDRESS");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "NET_PEER_NAME");
+        assert_eq!(
+            diff.changed,
+            vec![ApiChange {
+                file: "attributes.rs".to_owned(),
+                name: "HTTP_METHOD".to_owned(),
+                from: "pub const HTTP_METHOD: &str = \"http.method\";".to_owned(),
+                to: "pub const HTTP_METHOD: &str = \"http.request.method\";".to_owned(),
+            }]
+        );
+        assert!(diff
+            .to_string()
+            .starts_with("API items: 1 added, 1 removed, 1 changed\n"));
+
+        // Without patterns, only the files are compared.
+        let diff = ApiPatterns::default()
+            .diff_dirs(from_dir.path(), to_dir.path())
+            .unwrap();
+        assert_eq!(diff.changed_files, vec!["attributes.rs"]);
+        assert!(diff.added.is_empty() && diff.changed.is_empty());
+
+        assert!(ApiPatterns::try_new(&["(".to_owned()]).is_err());
+    }
+}
@@ -10,6 +10,8 @@ use convert_case::{Case, Casing};
 use serde::Deserialize;
 use thread_local::ThreadLocal;
 
+use crate::deprecated::DeprecatedPolicy;
+use crate::formatter::FormatterConfig;
 use crate::Error;
 use crate::Error::InvalidConfigFile;
 
@@ -56,6 +58,21 @@ pub struct LanguageConfig {
     /// Type mapping for language specific types (OTel types -> Target language types).
     #[serde(default)]
     pub type_mapping: HashMap<String, String>,
+    /// Unit mapping for language specific unit suffixes (UCUM unit -> suffix),
+    /// overriding the suffixes of the built-in conversion table.
+    #[serde(default)]
+    pub unit_mapping: HashMap<String, String>,
+    /// How the deprecated definitions appear in the generated code.
+    #[serde(default)]
+    pub deprecated: DeprecatedPolicy,
+    /// Formatter applied to the generated files (none by default).
+    #[serde(default)]
+    pub formatter: Option<FormatterConfig>,
+    /// Regular expressions extracting the public API items (constants,
+    /// functions, types, ...) of the generated files, compared by `gen diff`
+    /// (see [`crate::api_diff`]).
+    #[serde(default)]
+    pub api_items: Vec<String>,
 }
 
 /// Dynamic global configuration.
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON schemas of the contexts passed to each kind of template. The schemas
+//! are generated from the Rust types used to build these contexts, so they
+//! can't drift from what the templates actually receive.
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, RootSchema, Schema, SchemaObject};
+use schemars::JsonSchema;
+
+use weaver_schema::event::Event;
+use weaver_schema::instrumentation_library::InstrumentationLibrary;
+use weaver_schema::metric_group::MetricGroup;
+use weaver_schema::resource::Resource;
+use weaver_schema::service::ServiceMetadata;
+use weaver_schema::span::Span;
+use weaver_schema::univariate_metric::UnivariateMetric;
+use weaver_schema::TelemetrySchema;
+
+use crate::namespaces::AttributeNamespace;
+use crate::sdkgen::{CompatContext, ReadmeContext, RegistryContext};
+
+/// The kinds of template, a template being recognized by its file name (see
+/// the `gen-client` command).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TemplateKind {
+    /// `span` templates, rendered once per span.
+    Span,
+    /// `span_test` templates, rendered once per span to generate test
+    /// helpers asserting that a captured span conforms to its definition.
+    SpanTest,
+    /// `event` templates, rendered once per event.
+    Event,
+    /// `metric` templates, rendered once per univariate metric.
+    Metric,
+    /// `metric_group` templates, rendered once per multivariate metric.
+    MetricGroup,
+    /// `resource` templates, rendered once with the resource of the schema.
+    Resource,
+    /// `compat` templates, rendered once per previous version of the schema.
+    Compat,
+    /// `readme` templates, rendered last with a summary of the generated files.
+    Readme,
+    /// Any other template, rendered once with the whole telemetry schema.
+    Other,
+}
+
+impl TemplateKind {
+    /// All the kinds of template.
+    pub const ALL: [TemplateKind; 9] = [
+        TemplateKind::Span,
+        TemplateKind::SpanTest,
+        TemplateKind::Event,
+        TemplateKind::Metric,
+        TemplateKind::MetricGroup,
+        TemplateKind::Resource,
+        TemplateKind::Compat,
+        TemplateKind::Readme,
+        TemplateKind::Other,
+    ];
+
+    /// Returns the name of the kind of template (i.e. the file stem of the
+    /// templates of this kind, `other` for the other templates).
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateKind::Span => "span",
+            TemplateKind::SpanTest => "span_test",
+            TemplateKind::Event => "event",
+            TemplateKind::Metric => "metric",
+            TemplateKind::MetricGroup => "metric_group",
+            TemplateKind::Resource => "resource",
+            TemplateKind::Compat => "compat",
+            TemplateKind::Readme => "readme",
+            TemplateKind::Other => "other",
+        }
+    }
+
+    /// Returns the JSON schema (draft 7) of the context passed to the
+    /// templates of this kind, `registry`, `resource` and
+    /// `instrumentation_library` objects included.
+    pub fn context_schema(&self) -> RootSchema {
+        match self {
+            TemplateKind::Span | TemplateKind::SpanTest => {
+                context_schema::<Span>(self, attribute_namespaces)
+            }
+            TemplateKind::Event => context_schema::<Event>(self, attribute_namespaces),
+            TemplateKind::Metric => context_schema::<UnivariateMetric>(self, attribute_namespaces),
+            TemplateKind::MetricGroup => context_schema::<MetricGroup>(self, attribute_namespaces),
+            TemplateKind::Resource => context_schema::<Resource>(self, |generator| {
+                vec![("schema_url", generator.subschema_for::<String>())]
+            }),
+            TemplateKind::Compat => context_schema::<CompatContext<'_>>(self, |_| vec![]),
+            TemplateKind::Readme => context_schema::<ReadmeContext<'_>>(self, |_| vec![]),
+            TemplateKind::Other => context_schema::<TelemetrySchema>(self, |_| vec![]),
+        }
+    }
+}
+
+/// Builds the schema of a context made of the serialized object `T`, the
+/// given additional properties and the `resource`, `instrumentation_library`
+/// and `service` objects. The object returned by the `registry` function is
+/// described by the `RegistryContext` definition.
+fn context_schema<T: JsonSchema>(
+    kind: &TemplateKind,
+    properties: impl FnOnce(&mut SchemaGenerator) -> Vec<(&'static str, Schema)>,
+) -> RootSchema {
+    let mut generator = SchemaSettings::draft07().into_generator();
+    let mut schema = T::json_schema(&mut generator).into_object();
+    let mut properties = properties(&mut generator);
+    _ = generator.subschema_for::<RegistryContext<'_>>();
+    properties.push(("resource", generator.subschema_for::<Option<Resource>>()));
+    properties.push((
+        "instrumentation_library",
+        generator.subschema_for::<Option<InstrumentationLibrary>>(),
+    ));
+    properties.push((
+        "service",
+        generator.subschema_for::<Option<ServiceMetadata>>(),
+    ));
+    add_properties(&mut schema, &properties);
+    schema.metadata().title = Some(format!("Context of the `{}` templates", kind.name()));
+
+    RootSchema {
+        meta_schema: generator.settings().meta_schema.clone(),
+        schema,
+        definitions: generator.take_definitions(),
+    }
+}
+
+/// Returns the `attribute_namespaces` property of the signal contexts.
+fn attribute_namespaces(generator: &mut SchemaGenerator) -> Vec<(&'static str, Schema)> {
+    vec![(
+        "attribute_namespaces",
+        generator.subschema_for::<Vec<AttributeNamespace<'_>>>(),
+    )]
+}
+
+/// Adds the given required properties to an object schema, or to each object
+/// variant of an enum schema. The properties are added to the schema itself
+/// (and not combined with `allOf`) as the objects don't accept unknown fields.
+fn add_properties(schema: &mut SchemaObject, properties: &[(&'static str, Schema)]) {
+    if let Some(subschemas) = schema.subschemas.as_mut() {
+        for variant in subschemas
+            .any_of
+            .iter_mut()
+            .chain(subschemas.one_of.iter_mut())
+            .flatten()
+        {
+            if let Schema::Object(variant) = variant {
+                add_properties(variant, properties);
+            }
+        }
+    }
+    if schema.has_type(InstanceType::Object) {
+        let object = schema.object();
+        for (name, property) in properties {
+            _ = object
+                .properties
+                .insert((*name).to_owned(), property.clone());
+            _ = object.required.insert((*name).to_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::context_schema::TemplateKind;
+
+    #[test]
+    fn test_context_schemas() {
+        for kind in TemplateKind::ALL {
+            let schema = serde_json::to_value(kind.context_schema()).unwrap();
+            assert_eq!(
+                schema["title"],
+                format!("Context of the `{}` templates", kind.name())
+            );
+            assert!(schema["definitions"]["GroupSpec"]["properties"]["attributes"].is_object());
+        }
+
+        let span = serde_json::to_value(TemplateKind::Span.context_schema()).unwrap();
+        assert!(span["properties"]["span_name"].is_object());
+        assert_eq!(span["additionalProperties"], false);
+
+        // The registry is returned by the `registry` function, it is not a
+        // property of the contexts.
+        assert!(span["properties"]["registry"].is_null());
+        assert!(span["definitions"]["RegistryContext"]["properties"]["attributes"].is_object());
+
+        // Each variant of the metric gets the `resource` object.
+        let metric = serde_json::to_value(TemplateKind::Metric.context_schema()).unwrap();
+        for variant in metric["anyOf"].as_array().unwrap() {
+            assert!(variant["properties"]["resource"].is_object());
+        }
+
+        let resource = serde_json::to_value(TemplateKind::Resource.context_schema()).unwrap();
+        assert_eq!(resource["properties"]["schema_url"]["type"], "string");
+
+        assert_eq!(span["properties"]["attribute_namespaces"]["type"], "array");
+        assert!(metric["definitions"]["AttributeNamespace"]["properties"]["count"].is_object());
+
+        // The resolved resource is given to the signal templates.
+        let event = serde_json::to_value(TemplateKind::Event.context_schema()).unwrap();
+        assert!(event["properties"]["resource"].is_object());
+        assert!(event["properties"]["instrumentation_library"].is_object());
+        assert!(event["properties"]["service"].is_object());
+    }
+}
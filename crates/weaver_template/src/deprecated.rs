@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Policy controlling how the deprecated attributes, metrics and groups
+//! appear in the generated code.
+//!
+//! A definition is deprecated when it has a `deprecated` field or when its
+//! stability is `deprecated`.
+
+use serde::Deserialize;
+use tera::Value;
+
+/// How the deprecated definitions appear in the generated code.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecatedPolicy {
+    /// The deprecated definitions are removed from the contexts of the
+    /// templates.
+    Omit,
+    /// The deprecated definitions are kept with their `deprecated` field so
+    /// the templates can emit language-native deprecation annotations (see
+    /// the `deprecated` tester).
+    #[default]
+    Annotate,
+    /// The deprecated definitions are kept without their `deprecated` field,
+    /// i.e. without deprecation annotations.
+    Include,
+}
+
+impl DeprecatedPolicy {
+    /// Applies the policy to a serialized context.
+    pub(crate) fn apply(&self, value: &mut Value) {
+        match self {
+            DeprecatedPolicy::Omit => omit(value),
+            DeprecatedPolicy::Annotate => {}
+            DeprecatedPolicy::Include => strip(value),
+        }
+    }
+}
+
+/// Returns true if the value is a deprecated definition.
+pub(crate) fn is_deprecated(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => {
+            !matches!(map.get("deprecated"), None | Some(Value::Null))
+                || matches!(map.get("stability"), Some(Value::String(stability)) if stability == "deprecated")
+        }
+        _ => false,
+    }
+}
+
+/// Removes recursively the deprecated definitions of the arrays and objects.
+fn omit(value: &mut Value) {
+    match value {
+        Value::Array(values) => {
+            values.retain(|value| !is_deprecated(value));
+            values.iter_mut().for_each(omit);
+        }
+        Value::Object(map) => {
+            map.retain(|_, value| !is_deprecated(value));
+            map.values_mut().for_each(omit);
+        }
+        _ => {}
+    }
+}
+
+/// Removes recursively the `deprecated` field of the definitions.
+fn strip(value: &mut Value) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(strip),
+        Value::Object(map) => {
+            _ = map.remove("deprecated");
+            map.values_mut().for_each(strip);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::deprecated::DeprecatedPolicy;
+
+    #[test]
+    fn test_deprecated_policy() {
+        let context = json!({
+            "span_name": "http.request",
+            "attributes": [
+                {"id": "http.method", "deprecated": "Replaced by `http.request.method`."},
+                {"id": "http.request.method"},
+                {"id": "net.peer.name", "stability": "deprecated"},
+            ],
+            "registry": {
+                "groups": {
+                    "registry.http": {"id": "registry.http", "deprecated": null},
+                    "registry.net": {"id": "registry.net", "stability": "deprecated"},
+                },
+            },
+        });
+
+        let mut omitted = context.clone();
+        DeprecatedPolicy::Omit.apply(&mut omitted);
+        assert_eq!(
+            omitted,
+            json!({
+                "span_name": "http.request",
+                "attributes": [{"id": "http.request.method"}],
+                "registry": {
+                    "groups": {
+                        "registry.http": {"id": "registry.http", "deprecated": null},
+                    },
+                },
+            })
+        );
+
+        let mut annotated = context.clone();
+        DeprecatedPolicy::Annotate.apply(&mut annotated);
+        assert_eq!(annotated, context);
+
+        let mut included = context.clone();
+        DeprecatedPolicy::Include.apply(&mut included);
+        assert_eq!(included["attributes"][0], json!({"id": "http.method"}));
+        assert_eq!(included["attributes"].as_array().unwrap().len(), 3);
+    }
+}
@@ -2,12 +2,15 @@
 
 //! Custom Tera filters
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use tera::{try_get_value, Filter, Result, Value};
 use textwrap::{wrap, Options};
+use weaver_semconv::deprecated::DeprecatedSpec;
+use weaver_semconv::group::SpanNameSpec;
 
 use crate::config::CaseConvention;
+use crate::units;
 
 /// Case converter filter.
 pub struct CaseConverter {
@@ -268,6 +271,70 @@ pub fn without_enum(value: &Value, _: &HashMap<String, Value>) -> Result<Value>
     Ok(Value::Array(without_enums))
 }
 
+/// Filter to combine the attributes of a signal with the attributes of the
+/// resource (parameter `resource`, the `resource` object of the context or a
+/// list of attributes). An attribute defined by both keeps the definition of
+/// the signal, unless the parameter `precedence` is set to `resource`.
+///
+/// The result is the list of the signal attributes followed by the resource
+/// attributes not defined by the signal.
+pub fn merge_with_resource(value: &Value, ctx: &HashMap<String, Value>) -> Result<Value> {
+    let Value::Array(signal_attrs) = value else {
+        return Err(tera::Error::msg(format!(
+            "Filter merge_with_resource: expected a list of attributes, got {:?}",
+            value
+        )));
+    };
+    let resource_attrs = match ctx.get("resource") {
+        None | Some(Value::Null) => vec![],
+        Some(Value::Array(attrs)) => attrs.clone(),
+        Some(Value::Object(resource)) => match resource.get("attributes") {
+            Some(Value::Array(attrs)) => attrs.clone(),
+            _ => vec![],
+        },
+        Some(resource) => {
+            return Err(tera::Error::msg(format!(
+                "Filter merge_with_resource: expected a resource or a list of attributes for the resource parameter, got {:?}",
+                resource
+            )))
+        }
+    };
+    let resource_first = match ctx.get("precedence") {
+        None => false,
+        Some(Value::String(precedence)) if precedence == "signal" => false,
+        Some(Value::String(precedence)) if precedence == "resource" => true,
+        Some(precedence) => {
+            return Err(tera::Error::msg(format!(
+                "Filter merge_with_resource: expected `signal` or `resource` for the precedence parameter, got {:?}",
+                precedence
+            )))
+        }
+    };
+
+    fn attr_id(attr: &Value) -> Option<&str> {
+        attr.get("id").and_then(Value::as_str)
+    }
+    let signal_ids: HashSet<&str> = signal_attrs.iter().filter_map(attr_id).collect();
+    let resource_by_id: HashMap<&str, &Value> = resource_attrs
+        .iter()
+        .filter_map(|attr| attr_id(attr).map(|id| (id, attr)))
+        .collect();
+
+    let mut merged = vec![];
+    for attr in signal_attrs {
+        match attr_id(attr).and_then(|id| resource_by_id.get(id)) {
+            Some(resource_attr) if resource_first => merged.push((*resource_attr).clone()),
+            _ => merged.push(attr.clone()),
+        }
+    }
+    for attr in resource_attrs.iter() {
+        if !attr_id(attr).is_some_and(|id| signal_ids.contains(id)) {
+            merged.push(attr.clone());
+        }
+    }
+    Ok(Value::Array(merged))
+}
+
 /// Filter to map an OTel type to a language type.
 pub struct TypeMapping {
     pub type_mapping: HashMap<String, String>,
@@ -299,6 +366,137 @@ impl Filter for TypeMapping {
     }
 }
 
+/// Filter rendering the requirement level of an attribute (or the
+/// requirement level itself) as a sentence, keeping the condition of a
+/// conditionally required attribute and the text of a recommended attribute,
+/// e.g. `Conditionally required: If the request has a body.`.
+///
+/// Both the requirement levels of the semantic conventions (`required`,
+/// `{conditionally_required: <text>}`, ...) and the ones of the resolved
+/// schema (`{type: ConditionallyRequired, text: <text>}`, ...) are supported.
+/// An attribute without requirement level is recommended.
+pub fn requirement_doc(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+    let level = match value {
+        Value::Object(map) if map.contains_key("id") || map.contains_key("name") => {
+            map.get("requirement_level").unwrap_or(&Value::Null)
+        }
+        _ => value,
+    };
+    let (kind, text) = match level {
+        Value::Null => ("recommended", None),
+        Value::String(kind) => (kind.as_str(), None),
+        Value::Object(map) => match map.get("type") {
+            Some(Value::String(kind)) => (kind.as_str(), map.get("text")),
+            _ => match map.iter().next() {
+                Some((kind, text)) if map.len() == 1 => (kind.as_str(), Some(text)),
+                _ => ("", None),
+            },
+        },
+        _ => ("", None),
+    };
+    let sentence = match kind {
+        "required" | "Required" => "Required",
+        "recommended" | "Recommended" => "Recommended",
+        "opt_in" | "OptIn" => "Opt-in",
+        "conditionally_required" | "ConditionallyRequired" => "Conditionally required",
+        _ => {
+            return Err(tera::Error::msg(format!(
+                "Filter requirement_doc: expected an attribute or a requirement level, got {:?}",
+                value
+            )))
+        }
+    };
+    let sentence = match text.and_then(Value::as_str).map(str::trim) {
+        Some(text) if !text.is_empty() => {
+            let mut sentence = format!("{}: {}", sentence, text);
+            if !sentence.ends_with(['.', '!', '?']) {
+                sentence.push('.');
+            }
+            sentence
+        }
+        _ => format!("{}.", sentence),
+    };
+    Ok(Value::String(sentence))
+}
+
+/// Filter naming a UCUM unit in the generated code (see the [`units`]
+/// module), e.g. `milliseconds` for `ms` or `bytes_per_second` for `By/s`,
+/// the dimensionless units (`1` and the annotations such as `{request}`)
+/// having an empty suffix. The `unit_mapping` of the language configuration
+/// overrides the suffix of a unit.
+///
+/// An unknown unit is an error, so a metric helper can't be generated with a
+/// unit it doesn't document.
+pub struct UnitSuffix {
+    pub unit_mapping: HashMap<String, String>,
+}
+
+impl Filter for UnitSuffix {
+    /// Map a UCUM unit to its suffix.
+    fn filter(&self, value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+        let unit = try_get_value!("unit_suffix", "value", String, value);
+        if let Some(suffix) = self.unit_mapping.get(&unit) {
+            return Ok(Value::String(suffix.clone()));
+        }
+        match units::resolve(&unit) {
+            Some(unit) => Ok(Value::String(unit.suffix)),
+            None => Err(tera::Error::msg(format!("Filter unit_suffix: unknown unit `{}`. To resolve this, create or extend the unit_mapping in the config.yaml file.", unit))),
+        }
+    }
+}
+
+/// Filter returning the factor converting a value expressed in a UCUM unit
+/// into the base unit of its dimension (see the [`units`] module), e.g.
+/// `0.001` for `ms` (the base unit being `s`) or `1024` for `KiBy`. An
+/// unknown unit is an error.
+pub fn to_base_unit_factor(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+    let unit = try_get_value!("to_base_unit_factor", "value", String, value);
+    match units::resolve(&unit) {
+        Some(unit) => Ok(Value::from(unit.factor)),
+        None => Err(tera::Error::msg(format!(
+            "Filter to_base_unit_factor: unknown unit `{}`",
+            unit
+        ))),
+    }
+}
+
+/// Filter converting a span name format (e.g. `{http.request.method}
+/// {http.route}`) into an anchored regular expression matching the span names
+/// following it, e.g. `^.+ .+$`. An invalid format is an error.
+pub fn span_name_pattern(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+    let format = try_get_value!("span_name_pattern", "value", String, value);
+    let span_name = SpanNameSpec {
+        format,
+        examples: vec![],
+    };
+    span_name
+        .pattern()
+        .map(Value::String)
+        .map_err(|e| tera::Error::msg(format!("Filter span_name_pattern: {}", e)))
+}
+
+/// Filter normalizing a `deprecated` field, either a note or a structured
+/// deprecation, into an object with the fields `reason`, `renamed_to` and
+/// `removed_in` (null if not declared) and `note`, a sentence combining
+/// them (e.g. for a language-native deprecation annotation).
+///
+/// A null value stays null, so the filter can be applied to the
+/// non-deprecated definitions.
+pub fn deprecation(value: &Value, _: &HashMap<String, Value>) -> Result<Value> {
+    if value.is_null() {
+        return Ok(Value::Null);
+    }
+    let deprecated: DeprecatedSpec = serde_json::from_value(value.clone()).map_err(|e| {
+        tera::Error::msg(format!("Filter deprecation: invalid deprecation ({})", e))
+    })?;
+    Ok(serde_json::json!({
+        "reason": deprecated.reason(),
+        "renamed_to": deprecated.renamed_to(),
+        "removed_in": deprecated.removed_in(),
+        "note": deprecated.to_string(),
+    }))
+}
+
 /// Creates a multiline comment from a string.
 /// The `value` parameter is a string.
 /// The `prefix` parameter is a string.
@@ -345,3 +543,152 @@ pub fn comment(value: &Value, ctx: &HashMap<String, Value>) -> Result<Value> {
     }
     Ok(Value::String(comments))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+    use tera::{Filter, Value};
+
+    use crate::filters::{
+        deprecation, merge_with_resource, requirement_doc, span_name_pattern, to_base_unit_factor,
+        UnitSuffix,
+    };
+
+    #[test]
+    fn test_merge_with_resource() {
+        let signal_attrs = json!([
+            {"id": "http.method", "brief": "signal"},
+            {"id": "service.name", "brief": "signal"},
+        ]);
+        let resource = json!({"attributes": [
+            {"id": "service.name", "brief": "resource"},
+            {"id": "host.name", "brief": "resource"},
+        ]});
+
+        let mut args = HashMap::from([("resource".to_owned(), resource)]);
+        let merged = merge_with_resource(&signal_attrs, &args).unwrap();
+        assert_eq!(
+            merged,
+            json!([
+                {"id": "http.method", "brief": "signal"},
+                {"id": "service.name", "brief": "signal"},
+                {"id": "host.name", "brief": "resource"},
+            ])
+        );
+
+        _ = args.insert("precedence".to_owned(), json!("resource"));
+        let merged = merge_with_resource(&signal_attrs, &args).unwrap();
+        assert_eq!(
+            merged[1],
+            json!({"id": "service.name", "brief": "resource"})
+        );
+        assert_eq!(merged.as_array().unwrap().len(), 3);
+
+        // No resource in the schema.
+        _ = args.insert("resource".to_owned(), json!(null));
+        assert_eq!(
+            merge_with_resource(&signal_attrs, &args).unwrap(),
+            signal_attrs
+        );
+
+        _ = args.insert("precedence".to_owned(), json!("other"));
+        assert!(merge_with_resource(&signal_attrs, &args).is_err());
+    }
+
+    #[test]
+    fn test_requirement_doc() {
+        let args = HashMap::new();
+        let doc = |value: tera::Value| requirement_doc(&value, &args).unwrap();
+
+        assert_eq!(doc(json!("required")), json!("Required."));
+        assert_eq!(doc(json!("opt_in")), json!("Opt-in."));
+        assert_eq!(
+            doc(json!({"conditionally_required": "If the request has a body"})),
+            json!("Conditionally required: If the request has a body.")
+        );
+        assert_eq!(
+            doc(json!({"recommended": "When available."})),
+            json!("Recommended: When available.")
+        );
+
+        // Requirement levels of the resolved schema.
+        assert_eq!(doc(json!({"type": "Recommended"})), json!("Recommended."));
+        assert_eq!(
+            doc(json!({"type": "ConditionallyRequired", "text": "If set"})),
+            json!("Conditionally required: If set.")
+        );
+
+        // Attributes.
+        assert_eq!(
+            doc(
+                json!({"id": "http.route", "requirement_level": {"conditionally_required": "If available"}})
+            ),
+            json!("Conditionally required: If available.")
+        );
+        assert_eq!(doc(json!({"id": "http.method"})), json!("Recommended."));
+
+        assert!(requirement_doc(&json!("mandatory"), &args).is_err());
+    }
+
+    #[test]
+    fn test_units() {
+        let args = HashMap::new();
+        let unit_suffix = UnitSuffix {
+            unit_mapping: HashMap::from([("s".to_owned(), "secs".to_owned())]),
+        };
+        let suffix = |unit: &str| unit_suffix.filter(&json!(unit), &args);
+
+        assert_eq!(suffix("ms").unwrap(), json!("milliseconds"));
+        assert_eq!(suffix("By/s").unwrap(), json!("bytes_per_second"));
+        assert_eq!(suffix("{request}").unwrap(), json!(""));
+        // The language mapping overrides the suffix of the table.
+        assert_eq!(suffix("s").unwrap(), json!("secs"));
+        assert!(suffix("sec").is_err());
+
+        let factor = |unit: &str| to_base_unit_factor(&json!(unit), &args);
+        assert_eq!(factor("ms").unwrap(), json!(0.001));
+        assert_eq!(factor("s").unwrap(), json!(1.0));
+        assert_eq!(factor("MiBy").unwrap(), json!(1048576.0));
+        assert!(factor("sec").is_err());
+        assert!(factor("").is_err());
+    }
+
+    #[test]
+    fn test_span_name_pattern() {
+        let args = HashMap::new();
+        let pattern = |format: &str| span_name_pattern(&json!(format), &args);
+
+        assert_eq!(
+            pattern("{http.request.method} {http.route}").unwrap(),
+            json!("^.+ .+$")
+        );
+        assert_eq!(pattern("GET /users").unwrap(), json!("^GET /users$"));
+        assert!(pattern("{http.request.method").is_err());
+    }
+
+    #[test]
+    fn test_deprecation() {
+        let args = HashMap::new();
+
+        let note = deprecation(&json!("Replaced by `http.request.method`."), &args).unwrap();
+        assert_eq!(note["reason"], json!("Replaced by `http.request.method`."));
+        assert_eq!(note["renamed_to"], Value::Null);
+
+        let structured = deprecation(
+            &json!({"reason": "Moved to the request namespace.", "renamed_to": "http.request.method"}),
+            &args,
+        )
+        .unwrap();
+        assert_eq!(structured["renamed_to"], json!("http.request.method"));
+        assert_eq!(structured["removed_in"], Value::Null);
+        assert_eq!(
+            structured["note"],
+            json!("Moved to the request namespace. Renamed to `http.request.method`.")
+        );
+
+        assert_eq!(deprecation(&Value::Null, &args).unwrap(), Value::Null);
+        assert!(deprecation(&json!(42), &args).is_err());
+    }
+}
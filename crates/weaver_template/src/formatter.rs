@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Formatting of the generated files, declared by the `formatter` field of
+//! the language config.
+//!
+//! The formatter is either a built-in formatter (an external tool with
+//! predefined arguments, e.g. `rustfmt`, or the `whitespace` formatter
+//! implemented by weaver) or a custom command. The generated files are
+//! formatted in place once all of them have been generated.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use serde::Deserialize;
+
+/// The formatter of the generated files.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FormatterConfig {
+    /// A built-in formatter.
+    Builtin(BuiltinFormatter),
+    /// A custom command.
+    Command(CommandFormatter),
+}
+
+/// The built-in formatters.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BuiltinFormatter {
+    /// `rustfmt` applied to the `.rs` files.
+    Rustfmt,
+    /// `gofmt -w` applied to the `.go` files.
+    Gofmt,
+    /// `black` applied to the `.py` files.
+    Black,
+    /// `clang-format -i` applied to the C and C++ files.
+    ClangFormat,
+    /// Removes the trailing whitespaces and the consecutive blank lines, and
+    /// ends the files with a single newline (no external tool required).
+    Whitespace,
+}
+
+/// A formatter command, executed once per generated file with the path of
+/// the file as last argument. The file is expected to be formatted in place.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CommandFormatter {
+    /// The program to execute.
+    pub command: String,
+    /// The arguments passed before the path of the file.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// The extensions (without dot) of the files to format. All the
+    /// generated files are formatted if empty.
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+/// A generated file that couldn't be formatted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatFailure {
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The error message (output of the formatter if any).
+    pub error: String,
+}
+
+/// The result of the formatting of the generated files.
+#[derive(Debug, Default)]
+pub struct FormatReport {
+    /// The number of formatted files.
+    pub formatted: usize,
+    /// The files that couldn't be formatted.
+    pub failures: Vec<FormatFailure>,
+    /// The formatter program, if it is not installed (nothing is formatted).
+    pub missing_program: Option<String>,
+}
+
+impl BuiltinFormatter {
+    /// Returns the command of the built-in formatter, or `None` if the
+    /// formatter is implemented by weaver.
+    fn command(&self) -> Option<CommandFormatter> {
+        let command = |command: &str, args: &[&str], extensions: &[&str]| CommandFormatter {
+            command: command.to_owned(),
+            args: args.iter().map(|arg| (*arg).to_owned()).collect(),
+            extensions: extensions.iter().map(|ext| (*ext).to_owned()).collect(),
+        };
+        match self {
+            BuiltinFormatter::Rustfmt => Some(command("rustfmt", &["--edition", "2021"], &["rs"])),
+            BuiltinFormatter::Gofmt => Some(command("gofmt", &["-w"], &["go"])),
+            BuiltinFormatter::Black => Some(command("black", &["--quiet"], &["py"])),
+            BuiltinFormatter::ClangFormat => Some(command(
+                "clang-format",
+                &["-i"],
+                &["c", "h", "cc", "cpp", "cxx", "hh", "hpp"],
+            )),
+            BuiltinFormatter::Whitespace => None,
+        }
+    }
+}
+
+impl FormatterConfig {
+    /// Formats in place the given files. The files are formatted in
+    /// parallel, the failures being reported per file.
+    pub fn format(&self, files: &[PathBuf]) -> FormatReport {
+        let command = match self {
+            FormatterConfig::Builtin(builtin) => builtin.command(),
+            FormatterConfig::Command(command) => Some(command.clone()),
+        };
+        let Some(command) = command else {
+            return collect(
+                files
+                    .par_iter()
+                    .map(|file| format_whitespace(file))
+                    .collect(),
+            );
+        };
+
+        let files: Vec<&PathBuf> = files
+            .iter()
+            .filter(|file| command.applies_to(file))
+            .collect();
+        let Some((first_file, other_files)) = files.split_first() else {
+            return FormatReport::default();
+        };
+        // The first file is formatted alone so that a missing program is
+        // reported once instead of once per file.
+        let first_result = command.run(first_file);
+        if let Err(CommandError {
+            kind: ErrorKind::NotFound,
+            ..
+        }) = first_result
+        {
+            return FormatReport {
+                missing_program: Some(command.command.clone()),
+                ..FormatReport::default()
+            };
+        }
+        let mut results = vec![first_result.map_err(|e| e.into_failure(first_file))];
+        results.extend(
+            other_files
+                .par_iter()
+                .map(|file| command.format(file))
+                .collect::<Vec<_>>(),
+        );
+        collect(results)
+    }
+}
+
+/// The error of a formatter command.
+struct CommandError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl CommandError {
+    fn into_failure(self, path: &Path) -> FormatFailure {
+        FormatFailure {
+            path: path.to_path_buf(),
+            error: self.message,
+        }
+    }
+}
+
+impl CommandFormatter {
+    /// Returns true if the command applies to the file.
+    fn applies_to(&self, file: &Path) -> bool {
+        self.extensions.is_empty()
+            || file
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+
+    /// Formats the file.
+    fn format(&self, file: &Path) -> Result<(), FormatFailure> {
+        self.run(file).map_err(|e| e.into_failure(file))
+    }
+
+    /// Runs the command on the file.
+    fn run(&self, file: &Path) -> Result<(), CommandError> {
+        let output = Command::new(&self.command)
+            .args(&self.args)
+            .arg(file)
+            .output()
+            .map_err(|e| CommandError {
+                kind: e.kind(),
+                message: format!("failed to execute `{}`: {}", self.command, e),
+            })?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = [stderr.trim(), stdout.trim()]
+            .into_iter()
+            .find(|output| !output.is_empty())
+            .unwrap_or_default();
+        Err(CommandError {
+            kind: ErrorKind::Other,
+            message: format!("`{}` failed ({}): {}", self.command, output.status, message),
+        })
+    }
+}
+
+/// Collects the results of the formatting of the files.
+fn collect(results: Vec<Result<(), FormatFailure>>) -> FormatReport {
+    let mut report = FormatReport::default();
+    for result in results {
+        match result {
+            Ok(()) => report.formatted += 1,
+            Err(failure) => report.failures.push(failure),
+        }
+    }
+    report
+}
+
+/// Formats the file with the `whitespace` formatter.
+fn format_whitespace(file: &Path) -> Result<(), FormatFailure> {
+    let failure = |e: std::io::Error| FormatFailure {
+        path: file.to_path_buf(),
+        error: e.to_string(),
+    };
+    let content = std::fs::read_to_string(file).map_err(failure)?;
+    let formatted = normalize_whitespace(&content);
+    if formatted != content {
+        std::fs::write(file, formatted).map_err(failure)?;
+    }
+    Ok(())
+}
+
+/// Removes the trailing whitespaces of the lines, the leading blank lines
+/// and the consecutive blank lines, and ends the text with a single newline.
+fn normalize_whitespace(content: &str) -> String {
+    let mut formatted = String::with_capacity(content.len());
+    let mut blank_lines = 0;
+    for line in content.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+        if blank_lines > 0 && !formatted.is_empty() {
+            formatted.push('\n');
+        }
+        blank_lines = 0;
+        formatted.push_str(line);
+        formatted.push('\n');
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::formatter::{
+        normalize_whitespace, BuiltinFormatter, CommandFormatter, FormatterConfig,
+    };
+
+    #[test]
+    fn test_formatter_config() {
+        let config: FormatterConfig = serde_yaml::from_str("rustfmt").unwrap();
+        assert_eq!(config, FormatterConfig::Builtin(BuiltinFormatter::Rustfmt));
+        let config: FormatterConfig = serde_yaml::from_str("clang-format").unwrap();
+        assert_eq!(
+            config,
+            FormatterConfig::Builtin(BuiltinFormatter::ClangFormat)
+        );
+        let config: FormatterConfig =
+            serde_yaml::from_str("command: ktlint\nargs: [\"-F\"]\nextensions: [kt]").unwrap();
+        assert_eq!(
+            config,
+            FormatterConfig::Command(CommandFormatter {
+                command: "ktlint".to_owned(),
+                args: vec!["-F".to_owned()],
+                extensions: vec!["kt".to_owned()],
+            })
+        );
+        assert!(serde_yaml::from_str::<FormatterConfig>("unknown-formatter").is_err());
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        assert_eq!(
+            normalize_whitespace("\n\nfn main() {  \n\n\n    foo();\t\n}\n\n\n"),
+            "fn main() {\n\n    foo();\n}\n"
+        );
+        assert_eq!(normalize_whitespace(""), "");
+    }
+
+    #[test]
+    fn test_format() {
+        let dir = tempdir::TempDir::new("weaver-formatter").unwrap();
+        let file = dir.path().join("file.txt");
+        std::fs::write(&file, "a  \n\n\nb").unwrap();
+
+        let report = FormatterConfig::Builtin(BuiltinFormatter::Whitespace)
+            .format(std::slice::from_ref(&file));
+        assert_eq!(report.formatted, 1);
+        assert!(report.failures.is_empty());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "a\n\nb\n");
+
+        // Files not matching the extensions of the formatter are skipped.
+        let report =
+            FormatterConfig::Builtin(BuiltinFormatter::Rustfmt).format(std::slice::from_ref(&file));
+        assert_eq!(report.formatted, 0);
+        assert!(report.failures.is_empty());
+
+        let missing = FormatterConfig::Command(CommandFormatter {
+            command: "weaver-missing-formatter".to_owned(),
+            args: vec![],
+            extensions: vec![],
+        })
+        .format(&[file, PathBuf::from(Path::new("other.txt"))]);
+        assert_eq!(
+            missing.missing_program.as_deref(),
+            Some("weaver-missing-formatter")
+        );
+        assert_eq!(missing.formatted, 0);
+    }
+}
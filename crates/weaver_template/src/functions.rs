@@ -3,7 +3,7 @@
 //! Custom Tera functions
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
 use tera::Result;
 use tera::{Function, Value};
@@ -24,7 +24,10 @@ impl FunctionConfig {
 impl Function for FunctionConfig {
     fn call(&self, args: &HashMap<String, Value>) -> Result<Value> {
         if let Some(file_name) = args.get("file_name") {
-            self.config.set(file_name.as_str().unwrap());
+            let file_name = file_name
+                .as_str()
+                .ok_or_else(|| tera::Error::msg("`file_name` must be a string"))?;
+            self.config.set(file_name);
         }
         Ok(Value::Null)
     }
@@ -33,3 +36,59 @@ impl Function for FunctionConfig {
         false
     }
 }
+
+/// The registry returned by the `registry` function, serialized once per
+/// generation and shared by all its renderings.
+#[derive(Debug, Default)]
+pub struct SharedRegistry {
+    registry: RwLock<Arc<Value>>,
+}
+
+impl SharedRegistry {
+    /// Sets the registry of the current generation.
+    pub fn set(&self, registry: Value) {
+        *self.registry.write().expect("registry lock failed") = Arc::new(registry);
+    }
+
+    /// Returns the registry of the current generation.
+    pub fn get(&self) -> Arc<Value> {
+        self.registry.read().expect("registry lock failed").clone()
+    }
+}
+
+/// Returns the registry of the current generation. The registry is only
+/// copied into the renderings calling this function.
+#[derive(Debug)]
+pub struct FunctionRegistry {
+    registry: Arc<SharedRegistry>,
+}
+
+impl FunctionRegistry {
+    pub fn new(registry: Arc<SharedRegistry>) -> Self {
+        FunctionRegistry { registry }
+    }
+}
+
+impl Function for FunctionRegistry {
+    fn call(&self, _args: &HashMap<String, Value>) -> Result<Value> {
+        Ok(self.registry.get().as_ref().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let registry = Arc::new(SharedRegistry::default());
+        let function = FunctionRegistry::new(registry.clone());
+        assert_eq!(function.call(&HashMap::new()).unwrap(), Value::Null);
+
+        registry.set(serde_json::json!({"groups": {}}));
+        assert_eq!(
+            function.call(&HashMap::new()).unwrap(),
+            serde_json::json!({"groups": {}})
+        );
+    }
+}
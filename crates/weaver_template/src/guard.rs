@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Guards limiting the resources used to render a template, so a broken
+//! template can't hang or exhaust the memory of the generator.
+//!
+//! Tera doesn't expose hooks on its loops, so the limits are enforced at each
+//! step of the rendering: each call to the filters, functions and testers
+//! registered by weaver, and each write of rendered text (the body of a
+//! `{% for %}` loop writes its text at each iteration). A step exceeding a
+//! limit fails the rendering, which stops the rendering thread.
+//!
+//! The limits are best-effort for the steps not observable by weaver: a loop
+//! that neither writes text nor calls a weaver filter, function or tester
+//! (e.g. an empty nested loop) can't be interrupted. The generator then stops
+//! waiting for the rendering after the maximum render time, but the rendering
+//! thread keeps running until the loop completes.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use tera::{Context, Filter, Function, Tera, Test, Value};
+
+thread_local! {
+    /// The state of the guard of the template rendered by the current
+    /// thread (`None` if the current thread is not rendering).
+    static GUARD: RefCell<Option<RenderGuard>> = const { RefCell::new(None) };
+}
+
+/// The limits applied to the rendering of each template.
+#[derive(Debug, Clone)]
+pub struct RenderLimits {
+    /// Maximum duration of the rendering of a template.
+    pub max_render_time: Duration,
+    /// Maximum size (in bytes) of the code generated by a template.
+    pub max_output_size: usize,
+    /// Maximum number of steps (calls to the weaver filters, functions and
+    /// testers, and writes of rendered text) during the rendering of a
+    /// template.
+    pub max_iterations: usize,
+}
+
+impl Default for RenderLimits {
+    /// Limits large enough for the templates of a full semantic convention
+    /// registry.
+    fn default() -> Self {
+        Self {
+            max_render_time: Duration::from_secs(30),
+            max_output_size: 10 * 1024 * 1024,
+            max_iterations: 10_000_000,
+        }
+    }
+}
+
+/// A limit exceeded by the rendering of a template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExceededLimit {
+    /// The maximum render time.
+    RenderTime,
+    /// The maximum output size.
+    OutputSize,
+    /// The maximum number of iterations.
+    Iterations,
+}
+
+impl ExceededLimit {
+    /// Returns the description of the exceeded limit (e.g. `maximum output
+    /// size (1024 bytes)`).
+    pub(crate) fn describe(&self, limits: &RenderLimits) -> String {
+        match self {
+            ExceededLimit::RenderTime => format!(
+                "maximum render time ({:.1}s)",
+                limits.max_render_time.as_secs_f32()
+            ),
+            ExceededLimit::OutputSize => {
+                format!("maximum output size ({} bytes)", limits.max_output_size)
+            }
+            ExceededLimit::Iterations => {
+                format!("maximum number of iterations ({})", limits.max_iterations)
+            }
+        }
+    }
+}
+
+impl Display for ExceededLimit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExceededLimit::RenderTime => write!(f, "Render time exceeded"),
+            ExceededLimit::OutputSize => write!(f, "Output size exceeded"),
+            ExceededLimit::Iterations => write!(f, "Iteration cap exceeded"),
+        }
+    }
+}
+
+/// The state of the guard of a rendering.
+struct RenderGuard {
+    iterations_left: usize,
+    deadline: Instant,
+    exceeded: Option<ExceededLimit>,
+}
+
+/// Renders a template within the given limits on the current thread. Returns
+/// the result of the rendering and the exceeded limit, if any (the rendering
+/// then fails).
+pub(crate) fn render(
+    tera: &Tera,
+    template: &str,
+    context: &Context,
+    limits: &RenderLimits,
+) -> (tera::Result<String>, Option<ExceededLimit>) {
+    GUARD.with(|guard| {
+        *guard.borrow_mut() = Some(RenderGuard {
+            iterations_left: limits.max_iterations,
+            deadline: Instant::now() + limits.max_render_time,
+            exceeded: None,
+        })
+    });
+    let mut output = LimitedWriter {
+        buffer: Vec::with_capacity(2000),
+        max_size: limits.max_output_size,
+    };
+    let result = tera
+        .render_to(template, context, &mut output)
+        .and_then(|_| String::from_utf8(output.buffer).map_err(tera::Error::msg));
+    let exceeded = GUARD.with(|guard| guard.borrow_mut().take().and_then(|guard| guard.exceeded));
+    (result, exceeded)
+}
+
+/// Counts one step of the rendering, or returns the exceeded limit (recorded
+/// in the guard of the rendering).
+fn step() -> Result<(), ExceededLimit> {
+    GUARD.with(|guard| {
+        let mut guard = guard.borrow_mut();
+        let Some(guard) = guard.as_mut() else {
+            return Ok(());
+        };
+        if let Some(exceeded) = guard.exceeded {
+            return Err(exceeded);
+        }
+        let exceeded = if guard.iterations_left == 0 {
+            ExceededLimit::Iterations
+        } else if Instant::now() > guard.deadline {
+            ExceededLimit::RenderTime
+        } else {
+            guard.iterations_left -= 1;
+            return Ok(());
+        };
+        guard.exceeded = Some(exceeded);
+        Err(exceeded)
+    })
+}
+
+/// Records the given exceeded limit in the guard of the rendering.
+fn exceed(limit: ExceededLimit) -> ExceededLimit {
+    GUARD.with(|guard| {
+        if let Some(guard) = guard.borrow_mut().as_mut() {
+            guard.exceeded = Some(limit);
+        }
+    });
+    limit
+}
+
+/// The output of a rendering, failing the rendering once its maximum size is
+/// exceeded.
+struct LimitedWriter {
+    buffer: Vec<u8>,
+    max_size: usize,
+}
+
+impl Write for LimitedWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let limit_error = |limit: ExceededLimit| {
+            std::io::Error::new(std::io::ErrorKind::Other, limit.to_string())
+        };
+        step().map_err(limit_error)?;
+        if self.buffer.len() + buf.len() > self.max_size {
+            return Err(limit_error(exceed(ExceededLimit::OutputSize)));
+        }
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A filter, function or tester whose calls are counted as steps of the
+/// rendering.
+pub(crate) struct Guarded<T>(pub T);
+
+/// Counts one step of the rendering, as a Tera error.
+fn guarded_step() -> tera::Result<()> {
+    step().map_err(|limit| tera::Error::msg(limit.to_string()))
+}
+
+impl<F: Filter> Filter for Guarded<F> {
+    fn filter(&self, value: &Value, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        guarded_step()?;
+        self.0.filter(value, args)
+    }
+
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
+impl<F: Function> Function for Guarded<F> {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        guarded_step()?;
+        self.0.call(args)
+    }
+
+    fn is_safe(&self) -> bool {
+        self.0.is_safe()
+    }
+}
+
+impl<T: Test> Test for Guarded<T> {
+    fn test(&self, value: Option<&Value>, args: &[Value]) -> tera::Result<bool> {
+        guarded_step()?;
+        self.0.test(value, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use tera::{Context, Tera};
+
+    use super::*;
+
+    fn new_tera(template: &str) -> Tera {
+        let mut tera = Tera::default();
+        tera.add_raw_template("test.tera", template).unwrap();
+        tera.register_filter(
+            "same",
+            Guarded(|value: &Value, _: &HashMap<String, Value>| Ok(value.clone())),
+        );
+        tera
+    }
+
+    fn limits() -> RenderLimits {
+        RenderLimits {
+            max_render_time: Duration::from_secs(30),
+            max_output_size: 1024 * 1024,
+            max_iterations: 10_000,
+        }
+    }
+
+    #[test]
+    fn test_render_within_limits() {
+        let tera = new_tera("{% for i in range(end=3) %}{{ 'x' | same }}{% endfor %}");
+        let (result, exceeded) = render(&tera, "test.tera", &Context::new(), &limits());
+        assert_eq!(result.unwrap(), "xxx");
+        assert_eq!(exceeded, None);
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        // A plain loop without any weaver filter is capped.
+        let tera = new_tera("{% for i in range(end=1000000) %}{{ i }}{% endfor %}");
+        let (result, exceeded) = render(&tera, "test.tera", &Context::new(), &limits());
+        assert!(result.is_err());
+        assert_eq!(exceeded, Some(ExceededLimit::Iterations));
+
+        // The calls to the weaver filters are counted too.
+        let tera =
+            new_tera("{% for i in range(end=1000000) %}{% set x = 'x' | same %}{% endfor %}");
+        let (result, exceeded) = render(&tera, "test.tera", &Context::new(), &limits());
+        assert!(result.is_err());
+        assert_eq!(exceeded, Some(ExceededLimit::Iterations));
+    }
+
+    #[test]
+    fn test_render_time_limit() {
+        let tera = new_tera(
+            "{% for i in range(end=100000) %}{% for j in range(end=100000) %}.{% endfor %}{% endfor %}",
+        );
+        let limits = RenderLimits {
+            max_render_time: Duration::from_millis(100),
+            max_output_size: usize::MAX,
+            max_iterations: usize::MAX,
+        };
+        let start = Instant::now();
+        let (result, exceeded) = render(&tera, "test.tera", &Context::new(), &limits);
+        // The rendering stops by itself shortly after the deadline.
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(result.is_err());
+        assert_eq!(exceeded, Some(ExceededLimit::RenderTime));
+    }
+
+    #[test]
+    fn test_output_size_limit() {
+        let tera = new_tera("{% for i in range(end=1000) %}0123456789{% endfor %}");
+        let limits = RenderLimits {
+            max_output_size: 100,
+            ..limits()
+        };
+        let (result, exceeded) = render(&tera, "test.tera", &Context::new(), &limits);
+        assert!(result.is_err());
+        assert_eq!(exceeded, Some(ExceededLimit::OutputSize));
+        assert_eq!(
+            ExceededLimit::OutputSize.describe(&limits),
+            "maximum output size (100 bytes)"
+        );
+    }
+}
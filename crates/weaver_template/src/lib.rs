@@ -1,10 +1,24 @@
 use std::path::PathBuf;
 
+use weaver_semconv::stability::StabilityFilter;
+
+use crate::deprecated::DeprecatedPolicy;
+use crate::guard::RenderLimits;
+
+pub mod api_diff;
 mod config;
+pub mod context_schema;
+pub mod deprecated;
 mod filters;
+pub mod formatter;
 mod functions;
+pub mod guard;
+mod namespaces;
+mod pack;
+pub mod scaffold;
 pub mod sdkgen;
 mod testers;
+mod units;
 
 /// An error that can occur while generating a client SDK.
 #[derive(thiserror::Error, Debug)]
@@ -50,6 +64,24 @@ pub enum Error {
         error: String,
     },
 
+    /// Resolution of the telemetry schema failed.
+    #[error("Resolution of the telemetry schema {schema} failed: {error}")]
+    SchemaResolutionFailed {
+        /// Schema file.
+        schema: PathBuf,
+        /// Resolver error.
+        error: weaver_resolver::Error,
+    },
+
+    /// Template rendering failed.
+    #[error("Rendering of the template `{template}` failed: {error}")]
+    TemplateRenderFailed {
+        /// Template path.
+        template: PathBuf,
+        /// Error message, followed by its causes.
+        error: String,
+    },
+
     /// Write generated code failed.
     #[error("Writing of the generated code {template} failed: {error}")]
     WriteGeneratedCodeFailed {
@@ -69,11 +101,47 @@ pub enum Error {
         /// Template path.
         template: PathBuf,
     },
+
+    /// Render limit exceeded.
+    #[error("Template `{template}` exceeded the {limit}")]
+    RenderLimitExceeded {
+        /// Template path.
+        template: PathBuf,
+        /// Description of the exceeded limit.
+        limit: String,
+    },
+
+    /// Language pack already existing.
+    #[error("The language pack `{0}` already exists")]
+    LanguagePackAlreadyExists(PathBuf),
+
+    /// Scaffolding of a language pack failed.
+    #[error("Scaffolding of the file {path} failed: {error}")]
+    ScaffoldFailed {
+        /// Path of the scaffolded file.
+        path: PathBuf,
+        /// Error message.
+        error: String,
+    },
+
+    /// Comparison of two generated client SDKs failed.
+    #[error("API diff failed: {0}")]
+    ApiDiffFailed(String),
 }
 
 /// General configuration for the generator.
 pub struct GeneratorConfig {
     template_dir: PathBuf,
+    override_template_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    refresh_cache: bool,
+    render_limits: RenderLimits,
+    generate_tests: bool,
+    compat_versions: usize,
+    stability_filter: StabilityFilter,
+    build_tools_compat: bool,
+    deprecated_policy: Option<DeprecatedPolicy>,
+    format: bool,
 }
 
 impl Default for GeneratorConfig {
@@ -81,6 +149,99 @@ impl Default for GeneratorConfig {
     fn default() -> Self {
         Self {
             template_dir: PathBuf::from("templates"),
+            override_template_dir: None,
+            cache_dir: None,
+            refresh_cache: false,
+            render_limits: RenderLimits::default(),
+            generate_tests: false,
+            compat_versions: 0,
+            stability_filter: StabilityFilter::All,
+            build_tools_compat: false,
+            deprecated_policy: None,
+            format: true,
         }
     }
 }
+
+impl GeneratorConfig {
+    /// Sets the directory containing the templates of each language
+    /// (`templates` by default).
+    pub fn with_template_dir(mut self, template_dir: PathBuf) -> Self {
+        self.template_dir = template_dir;
+        self
+    }
+
+    /// Sets a directory of templates layered over the template directory:
+    /// the templates of a language found in this directory override the
+    /// templates with the same relative path, the other templates (and the
+    /// language config if not overridden) being taken from the template
+    /// directory.
+    pub fn with_override_template_dir(mut self, override_template_dir: Option<PathBuf>) -> Self {
+        self.override_template_dir = override_template_dir;
+        self
+    }
+
+    /// Sets the cache directory used to fetch the semantic convention
+    /// registries. If `None`, the default cache directory is used.
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
+    /// Forces the git registries to be cloned again instead of reusing the
+    /// clones persisted in the cache directory.
+    pub fn with_refresh_cache(mut self, refresh_cache: bool) -> Self {
+        self.refresh_cache = refresh_cache;
+        self
+    }
+
+    /// Sets the limits applied to the rendering of each template.
+    pub fn with_render_limits(mut self, render_limits: RenderLimits) -> Self {
+        self.render_limits = render_limits;
+        self
+    }
+
+    /// Enables the rendering of the templates of the `tests/` directory of the
+    /// language, generating a test scaffold exercising the generated code.
+    pub fn with_tests(mut self, generate_tests: bool) -> Self {
+        self.generate_tests = generate_tests;
+        self
+    }
+
+    /// Sets the number of versions preceding the latest version of the schema
+    /// for which the `compat` templates generate compatibility modules.
+    pub fn with_compat_versions(mut self, compat_versions: usize) -> Self {
+        self.compat_versions = compat_versions;
+        self
+    }
+
+    /// Sets the minimum stability of the attributes used to generate the
+    /// client SDK.
+    pub fn with_stability_filter(mut self, stability_filter: StabilityFilter) -> Self {
+        self.stability_filter = stability_filter;
+        self
+    }
+
+    /// Enables the build-tools compatibility mode of the semantic convention
+    /// loader, accepting the YAML extensions supported by the Python
+    /// build-tools.
+    pub fn with_build_tools_compat(mut self, build_tools_compat: bool) -> Self {
+        self.build_tools_compat = build_tools_compat;
+        self
+    }
+
+    /// Sets how the deprecated definitions appear in the generated code,
+    /// overriding the `deprecated` field of the language config. If `None`,
+    /// the policy of the language config is used.
+    pub fn with_deprecated_policy(mut self, deprecated_policy: Option<DeprecatedPolicy>) -> Self {
+        self.deprecated_policy = deprecated_policy;
+        self
+    }
+
+    /// Enables (default) or disables the formatting of the generated files
+    /// with the `formatter` of the language config.
+    pub fn with_formatting(mut self, format: bool) -> Self {
+        self.format = format;
+        self
+    }
+}
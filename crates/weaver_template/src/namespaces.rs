@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Attributes of a signal grouped by namespace, computed once for the
+//! templates rendering a table per namespace (e.g. in the generated
+//! documentation) instead of re-implementing the grouping in Tera, which is
+//! slow on the large groups.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use weaver_schema::attribute::Attribute;
+
+/// The attributes of a signal sharing a namespace.
+#[derive(Serialize, JsonSchema, Debug)]
+pub(crate) struct AttributeNamespace<'a> {
+    /// The namespace, i.e. the name of the attributes without its last
+    /// segment (e.g. `http.request` for `http.request.method`), empty for the
+    /// attributes without namespace.
+    pub(crate) namespace: String,
+    /// The number of attributes in the namespace.
+    pub(crate) count: usize,
+    /// The attributes of the namespace, sorted by name.
+    pub(crate) attributes: Vec<&'a Attribute>,
+}
+
+/// Groups the given attributes by namespace, the namespaces being sorted by
+/// name.
+pub(crate) fn attribute_namespaces(attributes: &[Attribute]) -> Vec<AttributeNamespace<'_>> {
+    let mut namespaces: BTreeMap<String, Vec<(String, &Attribute)>> = BTreeMap::new();
+    for attribute in attributes {
+        let id = attribute.id();
+        let namespace = id
+            .rsplit_once('.')
+            .map(|(namespace, _)| namespace.to_owned())
+            .unwrap_or_default();
+        namespaces
+            .entry(namespace)
+            .or_default()
+            .push((id, attribute));
+    }
+    namespaces
+        .into_iter()
+        .map(|(namespace, mut attributes)| {
+            attributes.sort_by(|(a, _), (b, _)| a.cmp(b));
+            AttributeNamespace {
+                namespace,
+                count: attributes.len(),
+                attributes: attributes
+                    .into_iter()
+                    .map(|(_, attribute)| attribute)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use weaver_schema::attribute::Attribute;
+
+    use super::attribute_namespaces;
+
+    #[test]
+    fn test_attribute_namespaces() {
+        let attributes: Vec<Attribute> = serde_yaml::from_str(
+            r#"
+- ref: http.response.status_code
+- ref: http.request.method
+- ref: error.type
+- ref: http.request.body.size
+- ref: http.request.header
+- ref: custom
+"#,
+        )
+        .unwrap();
+        let namespaces = attribute_namespaces(&attributes);
+        let summary: Vec<(&str, usize, Vec<String>)> = namespaces
+            .iter()
+            .map(|namespace| {
+                (
+                    namespace.namespace.as_str(),
+                    namespace.count,
+                    namespace
+                        .attributes
+                        .iter()
+                        .map(|attribute| attribute.id())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("", 1, vec!["custom".to_owned()]),
+                ("error", 1, vec!["error.type".to_owned()]),
+                (
+                    "http.request",
+                    2,
+                    vec![
+                        "http.request.header".to_owned(),
+                        "http.request.method".to_owned()
+                    ]
+                ),
+                (
+                    "http.request.body",
+                    1,
+                    vec!["http.request.body.size".to_owned()]
+                ),
+                (
+                    "http.response",
+                    1,
+                    vec!["http.response.status_code".to_owned()]
+                ),
+            ]
+        );
+    }
+}
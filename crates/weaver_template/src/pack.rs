@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Language pack made of layered template directories: the templates of a
+//! local directory override the templates with the same relative path of the
+//! installed pack, the missing ones falling back to the installed pack. A
+//! single template can then be customized without copying the whole pack.
+
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+use glob::glob;
+
+use crate::config::LanguageConfig;
+use crate::Error;
+use crate::Error::{InvalidTemplateDirectory, LanguageNotSupported};
+
+/// The templates of a language, from its layered template directories.
+#[derive(Debug)]
+pub(crate) struct LanguagePack {
+    /// The directories of the language, from the lowest to the highest
+    /// priority (existing directories only).
+    lang_paths: Vec<PathBuf>,
+    /// Template name (path relative to the language directory, `/`
+    /// separated) -> template file of the highest priority layer.
+    templates: BTreeMap<String, PathBuf>,
+}
+
+impl LanguagePack {
+    /// Loads the templates of the language from the given template
+    /// directories, sorted from the lowest to the highest priority.
+    pub(crate) fn try_new(template_dirs: &[PathBuf], language: &str) -> Result<Self, Error> {
+        let lang_paths: Vec<PathBuf> = template_dirs
+            .iter()
+            .map(|template_dir| template_dir.join(language))
+            .filter(|lang_path| lang_path.exists())
+            .collect();
+        if lang_paths.is_empty() {
+            return Err(LanguageNotSupported(language.to_owned()));
+        }
+
+        let mut templates = BTreeMap::new();
+        for lang_path in lang_paths.iter() {
+            let pattern = lang_path
+                .to_str()
+                .map(|dir| format!("{}/**/*.tera", glob::Pattern::escape(dir)))
+                .ok_or_else(|| InvalidTemplateDirectory(lang_path.clone()))?;
+            let paths = glob(&pattern).map_err(|_| InvalidTemplateDirectory(lang_path.clone()))?;
+            for path in paths {
+                let path = path.map_err(|_| InvalidTemplateDirectory(lang_path.clone()))?;
+                if path.is_dir() {
+                    continue;
+                }
+                if let Some(name) = template_name(lang_path, &path) {
+                    _ = templates.insert(name, path);
+                }
+            }
+        }
+
+        Ok(Self {
+            lang_paths,
+            templates,
+        })
+    }
+
+    /// Returns the language config of the highest priority layer defining
+    /// one (default config if none).
+    pub(crate) fn config(&self) -> Result<LanguageConfig, Error> {
+        match self
+            .lang_paths
+            .iter()
+            .rev()
+            .find(|lang_path| lang_path.join("config.yaml").exists())
+        {
+            Some(lang_path) => LanguageConfig::try_new(lang_path),
+            None => Ok(LanguageConfig::default()),
+        }
+    }
+
+    /// Returns the directory of the highest priority layer.
+    pub(crate) fn lang_path(&self) -> &Path {
+        self.lang_paths
+            .last()
+            .expect("a language pack has at least one directory")
+    }
+
+    /// Returns the templates (name -> file) of the language.
+    pub(crate) fn templates(&self) -> &BTreeMap<String, PathBuf> {
+        &self.templates
+    }
+}
+
+/// Returns the name of a template, i.e. its path relative to the language
+/// directory with `/` separators.
+fn template_name(lang_path: &Path, path: &Path) -> Option<String> {
+    let relative_path = path.strip_prefix(lang_path).ok()?;
+    let components = relative_path
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(components.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::pack::LanguagePack;
+    use crate::Error;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_layered_templates() {
+        let installed = tempdir::TempDir::new("installed").unwrap();
+        let local = tempdir::TempDir::new("local").unwrap();
+        write(
+            &installed.path().join("go/config.yaml"),
+            "file_name: snake_case",
+        );
+        write(
+            &installed.path().join("go/otel/span.tera"),
+            "installed span",
+        );
+        write(
+            &installed.path().join("go/otel/metric.tera"),
+            "installed metric",
+        );
+        write(&local.path().join("go/otel/span.tera"), "local span");
+        write(&local.path().join("go/extra.tera"), "local extra");
+
+        let pack = LanguagePack::try_new(
+            &[installed.path().to_path_buf(), local.path().to_path_buf()],
+            "go",
+        )
+        .unwrap();
+        let templates = pack.templates();
+        assert_eq!(
+            templates.keys().collect::<Vec<_>>(),
+            vec!["extra.tera", "otel/metric.tera", "otel/span.tera"]
+        );
+        assert_eq!(
+            templates["otel/span.tera"],
+            local.path().join("go/otel/span.tera")
+        );
+        assert_eq!(
+            templates["otel/metric.tera"],
+            installed.path().join("go/otel/metric.tera")
+        );
+        assert_eq!(pack.lang_path(), local.path().join("go"));
+        // The config of the installed pack is used as the local directory
+        // doesn't define one.
+        assert!(pack.config().is_ok());
+
+        assert!(matches!(
+            LanguagePack::try_new(&[installed.path().to_path_buf()], "rust"),
+            Err(Error::LanguageNotSupported(_))
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Scaffolding of a new language pack, i.e. a template directory containing
+//! a documented `config.yaml`, starter templates for the spans, metrics and
+//! events, a macro file and a smoke test rendered with a fixture schema.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::Error;
+use crate::Error::{LanguagePackAlreadyExists, ScaffoldFailed};
+
+/// Placeholder replaced by the name of the language in the scaffolded files.
+const LANGUAGE_PLACEHOLDER: &str = "__LANGUAGE__";
+
+/// The files of the scaffold (path relative to the language directory and
+/// content).
+const SCAFFOLD_FILES: &[(&str, &str)] = &[
+    ("config.yaml", include_str!("../scaffold/config.yaml")),
+    (
+        "attrs.macro.tera",
+        include_str!("../scaffold/attrs.macro.tera"),
+    ),
+    ("otel/span.tera", include_str!("../scaffold/otel/span.tera")),
+    (
+        "otel/metric.tera",
+        include_str!("../scaffold/otel/metric.tera"),
+    ),
+    (
+        "otel/event.tera",
+        include_str!("../scaffold/otel/event.tera"),
+    ),
+    (
+        "tests/smoke.txt.tera",
+        include_str!("../scaffold/tests/smoke.txt.tera"),
+    ),
+    (
+        "tests/fixture.yaml",
+        include_str!("../scaffold/tests/fixture.yaml"),
+    ),
+];
+
+/// Scaffolds the language pack `language` in the template directory and
+/// returns the paths of the created files.
+/// An existing language pack is never overwritten.
+pub fn init_language_pack(template_dir: &Path, language: &str) -> Result<Vec<PathBuf>, Error> {
+    let lang_path = template_dir.join(language);
+    if lang_path.exists() {
+        return Err(LanguagePackAlreadyExists(lang_path));
+    }
+
+    let mut files = vec![];
+    for (relative_path, content) in SCAFFOLD_FILES {
+        let path = lang_path.join(relative_path);
+        let scaffold_failed = |e: std::io::Error| ScaffoldFailed {
+            path: path.clone(),
+            error: e.to_string(),
+        };
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir).map_err(scaffold_failed)?;
+        }
+        fs::write(&path, content.replace(LANGUAGE_PLACEHOLDER, language))
+            .map_err(scaffold_failed)?;
+        files.push(path);
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use weaver_logger::NullLogger;
+
+    use crate::scaffold::init_language_pack;
+    use crate::sdkgen::ClientSdkGenerator;
+    use crate::{Error, GeneratorConfig};
+
+    #[test]
+    fn test_init_language_pack() {
+        let template_dir = tempdir::TempDir::new("templates").unwrap();
+        let output_dir = tempdir::TempDir::new("output").unwrap();
+
+        let files = init_language_pack(template_dir.path(), "mylang").unwrap();
+        assert_eq!(files.len(), 7);
+        assert!(
+            fs::read_to_string(template_dir.path().join("mylang/config.yaml"))
+                .unwrap()
+                .contains("`mylang` language pack")
+        );
+        assert!(matches!(
+            init_language_pack(template_dir.path(), "mylang"),
+            Err(Error::LanguagePackAlreadyExists(_))
+        ));
+
+        // The scaffold renders the fixture schema, test scaffold included.
+        let config = GeneratorConfig::default()
+            .with_template_dir(template_dir.path().to_path_buf())
+            .with_tests(true);
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        generator
+            .generate(
+                NullLogger::new(),
+                template_dir.path().join("mylang/tests/fixture.yaml"),
+                output_dir.path().to_path_buf(),
+            )
+            .unwrap();
+
+        let span =
+            fs::read_to_string(output_dir.path().join("otel/tracer/http_request.txt")).unwrap();
+        assert!(span.contains("struct HttpRequestSpanAttrs {"));
+        assert!(span.contains("http_request_method: string,"));
+        assert!(span.contains("server_port: optional int,"));
+        let event =
+            fs::read_to_string(output_dir.path().join("otel/eventer/app/exception.txt")).unwrap();
+        assert!(event.contains("enum ExceptionType {"));
+        assert!(output_dir
+            .path()
+            .join("otel/meter/http_client_request_duration.txt")
+            .exists());
+        assert!(output_dir.path().join("tests/smoke.txt").exists());
+    }
+}
@@ -2,42 +2,95 @@
 
 //! Client SDK generator
 
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
-use std::{fs, process};
+use std::sync::{mpsc, Arc};
+use std::time::Instant;
+use std::{fs, thread};
 
-use glob::{glob, Paths};
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
+use schemars::JsonSchema;
+use serde::Serialize;
 use tera::{Context, Tera};
 use weaver_cache::Cache;
 
 use weaver_logger::Logger;
 use weaver_resolver::SchemaResolver;
+use weaver_schema::attribute::Attribute;
 use weaver_schema::event::Event;
 use weaver_schema::metric_group::MetricGroup;
+use weaver_schema::resource::Resource;
 use weaver_schema::span::Span;
 use weaver_schema::univariate_metric::UnivariateMetric;
-use weaver_schema::TelemetrySchema;
+use weaver_schema::{SemConvImport, TelemetrySchema};
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::GroupSpec;
+use weaver_semconv::metric::MetricSpec;
+use weaver_semconv::stability::StabilityFilter;
+use weaver_semconv::{ResolverConfig, SemConvSpecs};
+use weaver_version::{Rename, RenameKind, Versions};
 
-use crate::config::{DynamicGlobalConfig, LanguageConfig};
+use crate::api_diff::{ApiDiff, ApiPatterns};
+use crate::config::DynamicGlobalConfig;
+use crate::deprecated::DeprecatedPolicy;
+use crate::formatter::FormatterConfig;
+use crate::functions::SharedRegistry;
+use crate::guard::{ExceededLimit, Guarded, RenderLimits};
+use crate::namespaces::attribute_namespaces;
+use crate::pack::LanguagePack;
 use crate::Error::{
-    InternalError, InvalidTelemetrySchema, InvalidTemplate, InvalidTemplateDirectory,
-    InvalidTemplateFile, LanguageNotSupported, TemplateFileNameUndefined, WriteGeneratedCodeFailed,
+    ApiDiffFailed, InternalError, InvalidConfigFile, InvalidTelemetrySchema, InvalidTemplate,
+    RenderLimitExceeded, SchemaResolutionFailed, TemplateFileNameUndefined, TemplateRenderFailed,
+    WriteGeneratedCodeFailed,
 };
-use crate::{filters, functions, testers, GeneratorConfig};
+use crate::{filters, functions, guard, testers, GeneratorConfig};
+
+/// Directory of the language containing the templates of the test scaffold.
+const TESTS_DIR: &str = "tests";
 
 /// Client SDK generator
 pub struct ClientSdkGenerator {
-    /// Language path
+    /// Language path (of the override template directory if it defines
+    /// the language)
     lang_path: PathBuf,
 
+    /// Template name -> template file, the templates of the override
+    /// template directory taking precedence
+    templates: BTreeMap<String, PathBuf>,
+
     /// Tera template engine
-    tera: Tera,
+    tera: Arc<Tera>,
+
+    /// Limits applied to the rendering of each template
+    render_limits: RenderLimits,
 
     /// Global configuration
     config: Arc<DynamicGlobalConfig>,
+
+    /// Registry returned by the `registry` function
+    registry: Arc<SharedRegistry>,
+
+    /// Cache directory (default cache directory if `None`)
+    cache_dir: Option<PathBuf>,
+    /// Whether the cached git registries must be cloned again.
+    refresh_cache: bool,
+    /// Whether the templates of the test scaffold are rendered.
+    generate_tests: bool,
+    /// Number of previous versions for which the `compat` templates are
+    /// rendered.
+    compat_versions: usize,
+    /// Minimum stability of the attributes used to generate the client SDK.
+    stability_filter: StabilityFilter,
+    /// Whether the build-tools compatibility mode of the loader is enabled.
+    build_tools_compat: bool,
+    /// How the deprecated definitions appear in the generated code.
+    deprecated_policy: DeprecatedPolicy,
+    /// Formatter applied to the generated files (none if `None`).
+    formatter: Option<FormatterConfig>,
+    /// Patterns extracting the API items of the generated files.
+    api_patterns: ApiPatterns,
 }
 
 /// A pair {template, object} to generate code for.
@@ -58,11 +111,140 @@ enum TemplateObjectPair<'a> {
         template: String,
         span: &'a Span,
     },
+    SpanTest {
+        template: String,
+        span: &'a Span,
+    },
+    Resource {
+        template: String,
+        resource: &'a Resource,
+    },
     Other {
         template: String,
         relative_path: PathBuf,
         object: &'a TelemetrySchema,
     },
+    Readme {
+        template: String,
+        relative_path: PathBuf,
+    },
+    Compat {
+        template: String,
+        versions: &'a Versions,
+        version: &'a semver::Version,
+    },
+}
+
+/// A file generated from a template, as listed in the generated README.
+#[derive(Serialize, JsonSchema, Debug, Clone)]
+struct GeneratedFile {
+    /// The kind of object the file has been generated for (e.g. `span`).
+    kind: &'static str,
+    /// The name of the object the file has been generated for.
+    name: String,
+    /// The path of the generated file relative to the output directory.
+    path: String,
+}
+
+impl GeneratedFile {
+    fn new(kind: &'static str, name: &str, relative_path: &Path) -> Self {
+        Self {
+            kind,
+            name: name.to_owned(),
+            path: relative_path
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"),
+        }
+    }
+}
+
+/// The number of signals defined in the schema.
+#[derive(Serialize, JsonSchema, Debug)]
+struct SignalCounts {
+    /// The number of univariate metrics.
+    metrics: usize,
+    /// The number of multivariate metrics.
+    metric_groups: usize,
+    /// The number of events.
+    events: usize,
+    /// The number of spans.
+    spans: usize,
+}
+
+/// The context used to render a `readme` template.
+#[derive(Serialize, JsonSchema, Debug)]
+pub(crate) struct ReadmeContext<'a> {
+    /// The language of the generated client SDK.
+    language: String,
+    /// The URL of the telemetry schema.
+    schema_url: &'a str,
+    /// The latest version defined by the telemetry schema (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// The semantic convention registries imported by the schema.
+    semantic_conventions: Vec<SemConvImport>,
+    /// The number of signals defined in the schema.
+    counts: SignalCounts,
+    /// The files generated by the other templates, sorted by path.
+    files: Vec<GeneratedFile>,
+    /// The command to run to regenerate the client SDK.
+    regenerate_command: String,
+}
+
+/// The `registry` object added to the context of every template, giving a
+/// read-only access to the whole semantic convention registry of the schema.
+#[derive(Serialize, JsonSchema, Debug)]
+pub(crate) struct RegistryContext<'a> {
+    /// The groups indexed by id.
+    groups: BTreeMap<&'a str, &'a GroupSpec>,
+    /// The attributes indexed by fully qualified name.
+    attributes: BTreeMap<String, &'a AttributeSpec>,
+    /// The metrics indexed by name.
+    metrics: BTreeMap<&'a str, &'a MetricSpec>,
+}
+
+impl<'a> RegistryContext<'a> {
+    /// Builds the registry context of the given registry, keeping only the
+    /// groups and attributes accepted by the stability filter.
+    fn new(registry: &'a SemConvSpecs, stability_filter: StabilityFilter) -> Self {
+        Self {
+            groups: registry
+                .groups()
+                .filter(|group| stability_filter.accepts(group.stability.as_ref()))
+                .map(|group| (group.id.as_str(), group))
+                .collect(),
+            attributes: registry
+                .attributes_iter()
+                .filter(|attr| {
+                    let stability = match attr {
+                        AttributeSpec::Ref { stability, .. }
+                        | AttributeSpec::Id { stability, .. } => stability,
+                    };
+                    stability_filter.accepts(stability.as_ref())
+                })
+                .map(|attr| (attr.id(), attr))
+                .collect(),
+            metrics: registry
+                .metrics_iter()
+                .map(|metric| (metric.name.as_str(), metric))
+                .collect(),
+        }
+    }
+}
+
+/// The context used to render a `compat` template.
+#[derive(Serialize, JsonSchema, Debug)]
+pub(crate) struct CompatContext<'a> {
+    /// The previous version the compatibility module is generated for.
+    version: &'a semver::Version,
+    /// The latest version defined by the telemetry schema.
+    current_version: Option<&'a semver::Version>,
+    /// The attributes renamed since the previous version.
+    attributes: Vec<Rename>,
+    /// The metrics renamed since the previous version.
+    metrics: Vec<Rename>,
 }
 
 impl ClientSdkGenerator {
@@ -70,85 +252,146 @@ impl ClientSdkGenerator {
     /// or return an error if the language is not supported.
     pub fn try_new(language: &str, config: GeneratorConfig) -> Result<Self, crate::Error> {
         // Check if the language is supported
-        // A language is supported if a template directory exists for it.
-        let lang_path = config.template_dir.join(language);
+        // A language is supported if a template directory exists for it,
+        // in the template directory or in the override template directory.
+        let template_dirs: Vec<PathBuf> = std::iter::once(config.template_dir.clone())
+            .chain(config.override_template_dir.clone())
+            .collect();
+        let pack = LanguagePack::try_new(&template_dirs, language)?;
+        let lang_path = pack.lang_path().to_path_buf();
+        let templates = pack.templates().clone();
 
-        if !lang_path.exists() {
-            return Err(LanguageNotSupported(language.to_string()));
+        let mut tera = Tera::default();
+        if let Err(e) = tera.add_template_files(
+            templates
+                .iter()
+                .map(|(name, path)| (path.clone(), Some(name.clone()))),
+        ) {
+            return Err(InvalidTemplate {
+                template: lang_path,
+                error: format!("{}", e),
+            });
         }
 
-        let lang_dir_tree = match lang_path.to_str() {
-            None => {
-                return Err(InvalidTemplateDirectory(lang_path));
-            }
-            Some(dir) => {
-                format!("{}/**/*.tera", dir)
-            }
-        };
-
-        let mut tera = match Tera::new(&lang_dir_tree) {
-            Ok(tera) => tera,
-            Err(e) => {
-                return Err(InvalidTemplate {
-                    template: lang_path,
-                    error: format!("{}", e),
-                });
-            }
-        };
-
-        let lang_config = LanguageConfig::try_new(&lang_path)?;
+        let lang_config = pack.config()?;
+        let cache_dir = config.cache_dir;
+        let refresh_cache = config.refresh_cache;
+        let render_limits = config.render_limits;
+        let generate_tests = config.generate_tests;
+        let compat_versions = config.compat_versions;
+        let stability_filter = config.stability_filter;
+        let build_tools_compat = config.build_tools_compat;
+        let deprecated_policy = config.deprecated_policy.unwrap_or(lang_config.deprecated);
+        let formatter = lang_config.formatter.filter(|_| config.format);
+        let api_patterns =
+            ApiPatterns::try_new(&lang_config.api_items).map_err(|error| InvalidConfigFile {
+                config_file: lang_path.join("config.yaml"),
+                error,
+            })?;
 
         let config = Arc::new(DynamicGlobalConfig::default());
 
         // Register custom filters
         tera.register_filter(
             "file_name",
-            filters::CaseConverter::new(lang_config.file_name, "file_name"),
+            Guarded(filters::CaseConverter::new(
+                lang_config.file_name,
+                "file_name",
+            )),
         );
         tera.register_filter(
             "function_name",
-            filters::CaseConverter::new(lang_config.function_name, "function_name"),
+            Guarded(filters::CaseConverter::new(
+                lang_config.function_name,
+                "function_name",
+            )),
         );
         tera.register_filter(
             "arg_name",
-            filters::CaseConverter::new(lang_config.arg_name, "arg_name"),
+            Guarded(filters::CaseConverter::new(
+                lang_config.arg_name,
+                "arg_name",
+            )),
         );
         tera.register_filter(
             "struct_name",
-            filters::CaseConverter::new(lang_config.struct_name, "struct_name"),
+            Guarded(filters::CaseConverter::new(
+                lang_config.struct_name,
+                "struct_name",
+            )),
         );
         tera.register_filter(
             "field_name",
-            filters::CaseConverter::new(lang_config.field_name, "field_name"),
+            Guarded(filters::CaseConverter::new(
+                lang_config.field_name,
+                "field_name",
+            )),
         );
-        tera.register_filter("unique_attributes", filters::unique_attributes);
-        tera.register_filter("instrument", filters::instrument);
-        tera.register_filter("required", filters::required);
-        tera.register_filter("not_required", filters::not_required);
-        tera.register_filter("value", filters::value);
-        tera.register_filter("with_value", filters::with_value);
-        tera.register_filter("without_value", filters::without_value);
-        tera.register_filter("with_enum", filters::with_enum);
-        tera.register_filter("without_enum", filters::without_enum);
-        tera.register_filter("comment", filters::comment);
+        tera.register_filter("unique_attributes", Guarded(filters::unique_attributes));
+        tera.register_filter("instrument", Guarded(filters::instrument));
+        tera.register_filter("required", Guarded(filters::required));
+        tera.register_filter("not_required", Guarded(filters::not_required));
+        tera.register_filter("value", Guarded(filters::value));
+        tera.register_filter("with_value", Guarded(filters::with_value));
+        tera.register_filter("without_value", Guarded(filters::without_value));
+        tera.register_filter("with_enum", Guarded(filters::with_enum));
+        tera.register_filter("without_enum", Guarded(filters::without_enum));
+        tera.register_filter("comment", Guarded(filters::comment));
+        tera.register_filter("merge_with_resource", Guarded(filters::merge_with_resource));
+        tera.register_filter("requirement_doc", Guarded(filters::requirement_doc));
         tera.register_filter(
             "type_mapping",
-            filters::TypeMapping {
+            Guarded(filters::TypeMapping {
                 type_mapping: lang_config.type_mapping,
-            },
+            }),
+        );
+        tera.register_filter(
+            "unit_suffix",
+            Guarded(filters::UnitSuffix {
+                unit_mapping: lang_config.unit_mapping,
+            }),
         );
+        tera.register_filter("to_base_unit_factor", Guarded(filters::to_base_unit_factor));
+        tera.register_filter("span_name_pattern", Guarded(filters::span_name_pattern));
+        tera.register_filter("deprecation", Guarded(filters::deprecation));
 
         // Register custom functions
-        tera.register_function("config", functions::FunctionConfig::new(config.clone()));
+        tera.register_function(
+            "config",
+            Guarded(functions::FunctionConfig::new(config.clone())),
+        );
+        let registry = Arc::new(SharedRegistry::default());
+        tera.register_function(
+            "registry",
+            Guarded(functions::FunctionRegistry::new(registry.clone())),
+        );
 
         // Register custom testers
-        tera.register_tester("required", testers::is_required);
-        tera.register_tester("not_required", testers::is_not_required);
+        tera.register_tester("required", Guarded(testers::is_required));
+        tera.register_tester("not_required", Guarded(testers::is_not_required));
+        tera.register_tester(
+            "deprecated",
+            Guarded(testers::IsDeprecated {
+                policy: deprecated_policy,
+            }),
+        );
 
         Ok(Self {
             lang_path,
-            tera,
+            templates,
+            tera: Arc::new(tera),
+            render_limits,
             config,
+            registry,
+            cache_dir,
+            refresh_cache,
+            generate_tests,
+            compat_versions,
+            stability_filter,
+            build_tools_compat,
+            deprecated_policy,
+            formatter,
+            api_patterns,
         })
     }
 
@@ -159,32 +402,108 @@ impl ClientSdkGenerator {
         schema_path: PathBuf,
         output_dir: PathBuf,
     ) -> Result<(), crate::Error> {
-        let cache = Cache::try_new().unwrap_or_else(|e| {
-            log.error(&e.to_string());
-            std::process::exit(1);
-        });
+        let cache = Cache::try_new(self.cache_dir.clone())
+            .map(|cache| cache.with_refresh(self.refresh_cache))
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                std::process::exit(1);
+            });
+
+        let schema = SchemaResolver::resolve_schema_file_with_config(
+            schema_path.clone(),
+            self.resolver_config(),
+            &cache,
+            log.clone(),
+        )
+        .map_err(|error| SchemaResolutionFailed {
+            schema: schema_path.clone(),
+            error,
+        })?;
 
-        let schema = SchemaResolver::resolve_schema_file(schema_path.clone(), &cache, log.clone())
-            .map_err(|e| InvalidTelemetrySchema {
+        self.generate_from_schema(log, &schema, schema_path, output_dir)
+    }
+
+    /// Returns the differences between the public APIs of two client SDKs
+    /// generated for the language of the generator, the API items being
+    /// extracted with the `api_items` patterns of the language config.
+    pub fn api_diff(&self, from_dir: &Path, to_dir: &Path) -> Result<ApiDiff, crate::Error> {
+        self.api_patterns
+            .diff_dirs(from_dir, to_dir)
+            .map_err(ApiDiffFailed)
+    }
+
+    /// Returns the configuration used by the generator to resolve a schema.
+    /// The group specs are kept for the `registry` object of the contexts.
+    pub fn resolver_config(&self) -> ResolverConfig {
+        let resolver_config =
+            ResolverConfig::with_keep_specs().with_stability_filter(self.stability_filter);
+        if self.build_tools_compat {
+            resolver_config.with_build_tools_compat()
+        } else {
+            resolver_config
+        }
+    }
+
+    /// Generate a client SDK for the given schema, already resolved with the
+    /// configuration of the generator (see [`ClientSdkGenerator::resolver_config`]).
+    /// The generations of a generator must not run concurrently (they share
+    /// the registry returned by the `registry` function).
+    pub fn generate_from_schema(
+        &self,
+        log: impl Logger + Clone + Sync,
+        schema: &TelemetrySchema,
+        schema_path: PathBuf,
+        output_dir: PathBuf,
+    ) -> Result<(), crate::Error> {
+        // The registry is serialized once and returned by the `registry`
+        // function, so it is only copied into the renderings calling it. The
+        // resource, the instrumentation library and the service metadata are
+        // small enough to be added to every context.
+        let mut registry = tera::to_value(RegistryContext::new(
+            schema.semantic_convention_catalog(),
+            self.stability_filter,
+        ))
+        .map_err(|e| InvalidTelemetrySchema {
+            schema: schema_path.clone(),
+            error: format!("{}", e),
+        })?;
+        self.deprecated_policy.apply(&mut registry);
+        let mut resource =
+            tera::to_value(schema.resource()).map_err(|e| InvalidTelemetrySchema {
                 schema: schema_path.clone(),
                 error: format!("{}", e),
             })?;
-
-        // Process recursively all files in the template directory
-        let mut lang_path = self.lang_path.to_str().unwrap_or_default().to_string();
-        let paths = if lang_path.is_empty() {
-            glob("**/*.tera").map_err(|e| InternalError(e.to_string()))?
-        } else {
-            lang_path.push_str("/**/*.tera");
-            glob(lang_path.as_str()).map_err(|e| InternalError(e.to_string()))?
-        };
+        self.deprecated_policy.apply(&mut resource);
+        let instrumentation_library =
+            tera::to_value(schema.instrumentation_library()).map_err(|e| {
+                InvalidTelemetrySchema {
+                    schema: schema_path.clone(),
+                    error: format!("{}", e),
+                }
+            })?;
+        let service =
+            tera::to_value(schema.service_metadata()).map_err(|e| InvalidTelemetrySchema {
+                schema: schema_path.clone(),
+                error: format!("{}", e),
+            })?;
+        self.registry.set(registry);
+        let mut globals = Context::new();
+        globals.insert("resource", &resource);
+        globals.insert("instrumentation_library", &instrumentation_library);
+        globals.insert("service", &service);
 
         // Build the list of all {template, object} pairs to generate code for
         // and process them in parallel.
         // All pairs are independent from each other so we can process them in parallel.
-        self.list_all_templates(&schema, paths)?
+        // The README templates are processed last as they list the generated files.
+        let (readme_pairs, pairs): (Vec<_>, Vec<_>) = self
+            .list_all_templates(schema)?
+            .into_iter()
+            .partition(|pair| matches!(pair, TemplateObjectPair::Readme { .. }));
+
+        let mut generated_files: Vec<GeneratedFile> = pairs
             .into_par_iter()
-            .try_for_each(|pair| {
+            .map(|pair| {
                 match pair {
                     TemplateObjectPair::Metric { template, metric } => self.process_metric(
                         log.clone(),
@@ -192,23 +511,64 @@ impl ClientSdkGenerator {
                         &schema_path,
                         metric,
                         &output_dir,
+                        &globals,
                     ),
                     TemplateObjectPair::MetricGroup {
                         template,
                         metric_group,
-                    } => self.process_metric_group(
-                        log.clone(),
-                        &template,
-                        &schema_path,
-                        metric_group,
-                        &output_dir,
-                    ),
-                    TemplateObjectPair::Event { template, event } => {
-                        self.process_event(log.clone(), &template, &schema_path, event, &output_dir)
-                    }
-                    TemplateObjectPair::Span { template, span } => {
-                        self.process_span(log.clone(), &template, &schema_path, span, &output_dir)
-                    }
+                    } => self
+                        .process_metric_group(
+                            log.clone(),
+                            &template,
+                            &schema_path,
+                            metric_group,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
+                    TemplateObjectPair::Event { template, event } => self
+                        .process_event(
+                            log.clone(),
+                            &template,
+                            &schema_path,
+                            event,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
+                    TemplateObjectPair::Span { template, span } => self
+                        .process_span(
+                            log.clone(),
+                            "span",
+                            &template,
+                            &schema_path,
+                            span,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
+                    TemplateObjectPair::SpanTest { template, span } => self
+                        .process_span(
+                            log.clone(),
+                            "span_test",
+                            &template,
+                            &schema_path,
+                            span,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
+                    TemplateObjectPair::Resource { template, resource } => self
+                        .process_resource(
+                            log.clone(),
+                            &template,
+                            &schema_path,
+                            &schema.schema_url,
+                            resource,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
                     TemplateObjectPair::Other {
                         template,
                         relative_path,
@@ -223,100 +583,225 @@ impl ClientSdkGenerator {
                         })?;
 
                         log.loading(&format!("Generating file {}", template));
-                        let generated_code = self.generate_code(log.clone(), &template, context)?;
-                        let relative_path = relative_path.to_path_buf();
-                        let generated_file =
-                            Self::save_generated_code(&output_dir, relative_path, generated_code)?;
+                        let generated_code =
+                            self.generate_code(log.clone(), &template, context, &globals)?;
+                        let file = GeneratedFile::new(
+                            "file",
+                            &relative_path.to_string_lossy(),
+                            &relative_path,
+                        );
+                        let generated_file = Self::save_generated_code(
+                            &log,
+                            &output_dir,
+                            relative_path,
+                            generated_code,
+                        )?;
                         log.success(&format!("Generated file {:?}", generated_file));
-                        Ok(())
+                        Ok(Some(file))
                     }
+                    TemplateObjectPair::Compat {
+                        template,
+                        versions,
+                        version,
+                    } => self
+                        .process_compat(
+                            log.clone(),
+                            &template,
+                            &schema_path,
+                            versions,
+                            version,
+                            &output_dir,
+                            &globals,
+                        )
+                        .map(Some),
+                    TemplateObjectPair::Readme { .. } => Ok(None),
                 }
-            })?;
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        generated_files.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut generated_paths: Vec<PathBuf> = generated_files
+            .iter()
+            .map(|file| output_dir.join(&file.path))
+            .collect();
+
+        if !readme_pairs.is_empty() {
+            let context = self.readme_context(schema, &schema_path, &output_dir, generated_files);
+            for pair in readme_pairs {
+                if let TemplateObjectPair::Readme {
+                    template,
+                    relative_path,
+                } = pair
+                {
+                    generated_paths.push(self.process_readme(
+                        log.clone(),
+                        &template,
+                        &schema_path,
+                        &context,
+                        relative_path,
+                        &output_dir,
+                        &globals,
+                    )?);
+                }
+            }
+        }
+
+        if let Some(formatter) = &self.formatter {
+            self.format_generated_files(log, formatter, &generated_paths);
+        }
 
         Ok(())
     }
 
+    /// Formats the generated files, reporting the files that couldn't be
+    /// formatted as warnings (the unformatted files are kept).
+    fn format_generated_files(
+        &self,
+        log: impl Logger,
+        formatter: &FormatterConfig,
+        files: &[PathBuf],
+    ) {
+        log.loading("Formatting the generated files");
+        let start = Instant::now();
+        let report = formatter.format(files);
+        log.phase("format", start.elapsed());
+        if let Some(program) = report.missing_program {
+            log.warn(&format!(
+                "Formatter `{}` not found, the generated files are not formatted",
+                program
+            ));
+            return;
+        }
+        for failure in report.failures.iter() {
+            log.warn(&format!(
+                "Failed to format {}: {}",
+                failure.path.display(),
+                failure.error
+            ));
+        }
+        log.success(&format!(
+            "Formatted {} generated file(s) ({} failure(s))",
+            report.formatted,
+            report.failures.len()
+        ));
+    }
+
     /// Lists all {template, object} pairs derived from a template directory and a given
     /// schema specification.
     fn list_all_templates<'a>(
         &self,
         schema: &'a TelemetrySchema,
-        paths: Paths,
     ) -> Result<Vec<TemplateObjectPair<'a>>, crate::Error> {
         let mut templates = Vec::new();
         if let Some(schema_spec) = &schema.schema {
-            for entry in paths {
-                if let Ok(tmpl_file_path) = entry {
-                    if tmpl_file_path.is_dir() {
-                        continue;
-                    }
-                    let relative_path = tmpl_file_path.strip_prefix(&self.lang_path).unwrap();
-                    let tmpl_file = relative_path
-                        .to_str()
-                        .ok_or(InvalidTemplateFile(tmpl_file_path.clone()))?;
-
-                    if tmpl_file.ends_with(".macro.tera") {
-                        // Macro files are not templates.
-                        // They are included in other templates.
-                        // So we skip them.
-                        continue;
-                    }
+            for (tmpl_file, tmpl_file_path) in self.templates.iter() {
+                let tmpl_file = tmpl_file.as_str();
+                let relative_path = Path::new(tmpl_file);
 
-                    match tmpl_file_path.file_stem().and_then(|s| s.to_str()) {
-                        Some("metric") => {
-                            if let Some(resource_metrics) = schema_spec.resource_metrics.as_ref() {
-                                for metric in resource_metrics.metrics.iter() {
-                                    templates.push(TemplateObjectPair::Metric {
-                                        template: tmpl_file.into(),
-                                        metric,
-                                    })
-                                }
+                if tmpl_file.ends_with(".macro.tera") {
+                    // Macro files are not templates.
+                    // They are included in other templates.
+                    // So we skip them.
+                    continue;
+                }
+                if !self.generate_tests && relative_path.starts_with(TESTS_DIR) {
+                    // The test scaffold is only generated on demand.
+                    continue;
+                }
+
+                match tmpl_file_path.file_stem().and_then(|s| s.to_str()) {
+                    Some("metric") => {
+                        if let Some(resource_metrics) = schema_spec.resource_metrics.as_ref() {
+                            for metric in resource_metrics.metrics.iter() {
+                                templates.push(TemplateObjectPair::Metric {
+                                    template: tmpl_file.into(),
+                                    metric,
+                                })
                             }
                         }
-                        Some("metric_group") => {
-                            if let Some(resource_metrics) = schema_spec.resource_metrics.as_ref() {
-                                for metric_group in resource_metrics.metric_groups.iter() {
-                                    templates.push(TemplateObjectPair::MetricGroup {
-                                        template: tmpl_file.into(),
-                                        metric_group,
-                                    })
-                                }
+                    }
+                    Some("metric_group") => {
+                        if let Some(resource_metrics) = schema_spec.resource_metrics.as_ref() {
+                            for metric_group in resource_metrics.metric_groups.iter() {
+                                templates.push(TemplateObjectPair::MetricGroup {
+                                    template: tmpl_file.into(),
+                                    metric_group,
+                                })
                             }
                         }
-                        Some("event") => {
-                            if let Some(events) = schema_spec.resource_events.as_ref() {
-                                for event in events.events.iter() {
-                                    templates.push(TemplateObjectPair::Event {
-                                        template: tmpl_file.into(),
-                                        event,
-                                    })
-                                }
+                    }
+                    Some("event") => {
+                        if let Some(events) = schema_spec.resource_events.as_ref() {
+                            for event in events.events.iter() {
+                                templates.push(TemplateObjectPair::Event {
+                                    template: tmpl_file.into(),
+                                    event,
+                                })
                             }
                         }
-                        Some("span") => {
-                            if let Some(spans) = schema_spec.resource_spans.as_ref() {
-                                for span in spans.spans.iter() {
-                                    templates.push(TemplateObjectPair::Span {
-                                        template: tmpl_file.into(),
-                                        span,
-                                    })
-                                }
+                    }
+                    Some("span") => {
+                        if let Some(spans) = schema_spec.resource_spans.as_ref() {
+                            for span in spans.spans.iter() {
+                                templates.push(TemplateObjectPair::Span {
+                                    template: tmpl_file.into(),
+                                    span,
+                                })
                             }
                         }
-                        _ => {
-                            // Remove the `tera` extension from the relative path
-                            let mut relative_path = relative_path.to_path_buf();
-                            relative_path.set_extension("");
-
-                            templates.push(TemplateObjectPair::Other {
+                    }
+                    Some("span_test") => {
+                        if let Some(spans) = schema_spec.resource_spans.as_ref() {
+                            for span in spans.spans.iter() {
+                                templates.push(TemplateObjectPair::SpanTest {
+                                    template: tmpl_file.into(),
+                                    span,
+                                })
+                            }
+                        }
+                    }
+                    Some("resource") => {
+                        if let Some(resource) = schema_spec.resource.as_ref() {
+                            templates.push(TemplateObjectPair::Resource {
                                 template: tmpl_file.into(),
-                                relative_path,
-                                object: schema,
+                                resource,
                             })
                         }
                     }
-                } else {
-                    return Err(InvalidTemplateDirectory(self.lang_path.clone()));
+                    Some("compat") => {
+                        if let Some(versions) = schema.versions.as_ref() {
+                            // The latest version doesn't need a compatibility module.
+                            for (version, _) in versions
+                                .versions_desc()
+                                .into_iter()
+                                .skip(1)
+                                .take(self.compat_versions)
+                            {
+                                templates.push(TemplateObjectPair::Compat {
+                                    template: tmpl_file.into(),
+                                    versions,
+                                    version,
+                                })
+                            }
+                        }
+                    }
+                    Some("readme") => templates.push(TemplateObjectPair::Readme {
+                        template: tmpl_file.into(),
+                        relative_path: relative_path.with_file_name("README.md"),
+                    }),
+                    _ => {
+                        // Remove the `tera` extension from the relative path
+                        let mut relative_path = relative_path.to_path_buf();
+                        relative_path.set_extension("");
+
+                        templates.push(TemplateObjectPair::Other {
+                            template: tmpl_file.into(),
+                            relative_path,
+                            object: schema,
+                        })
+                    }
                 }
             }
         }
@@ -324,32 +809,92 @@ impl ClientSdkGenerator {
     }
 
     /// Generate code.
+    ///
+    /// The template is rendered in a dedicated thread within the render limits
+    /// (see [`guard`]). The rendering stops by itself when it exceeds a limit;
+    /// the thread is abandoned if it doesn't reach a guarded step before the
+    /// maximum render time. The file name set by the template (see
+    /// [`DynamicGlobalConfig`]) is transferred to the calling thread. The
+    /// objects shared by all the renderings (`resource`,
+    /// `instrumentation_library` and `service`) are added to the given
+    /// context.
     fn generate_code(
         &self,
         log: impl Logger,
         tmpl_file: &str,
         context: &Context,
+        globals: &Context,
     ) -> Result<String, crate::Error> {
-        let generated_code = self.tera.render(tmpl_file, context).unwrap_or_else(|err| {
-            log.newline(1);
-            log.error(&format!("{}", err));
+        let template_path = || {
+            self.templates
+                .get(tmpl_file)
+                .cloned()
+                .unwrap_or_else(|| self.lang_path.join(tmpl_file))
+        };
+        let limit_exceeded = |limit: String| RenderLimitExceeded {
+            template: template_path(),
+            limit,
+        };
+
+        let start = Instant::now();
+        let (sender, receiver) = mpsc::channel();
+        let tera = self.tera.clone();
+        let config = self.config.clone();
+        let template = tmpl_file.to_owned();
+        let mut context = context.clone();
+        if self.deprecated_policy != DeprecatedPolicy::Annotate {
+            let mut value = context.into_json();
+            self.deprecated_policy.apply(&mut value);
+            context = Context::from_value(value).map_err(|e| InternalError(e.to_string()))?;
+        }
+        context.extend(globals.clone());
+        let render_limits = self.render_limits.clone();
+        _ = thread::Builder::new()
+            .name(format!("render {}", tmpl_file))
+            .spawn(move || {
+                config.reset();
+                let (result, exceeded) = guard::render(&tera, &template, &context, &render_limits);
+                // The receiver is gone if the render time has been exceeded.
+                _ = sender.send((result, exceeded, config.get()));
+            })
+            .map_err(|e| InternalError(e.to_string()))?;
+
+        let (result, exceeded, file_name) = receiver
+            .recv_timeout(self.render_limits.max_render_time)
+            .map_err(|_| limit_exceeded(ExceededLimit::RenderTime.describe(&self.render_limits)))?;
+        if let Some(exceeded) = exceeded {
+            return Err(limit_exceeded(exceeded.describe(&self.render_limits)));
+        }
+        self.config.reset();
+        if let Some(file_name) = file_name {
+            self.config.set(&file_name);
+        }
+
+        let generated_code = result.map_err(|err| {
+            let mut error = err.to_string();
             let mut cause = err.source();
             while let Some(e) = cause {
-                log.error(&format!("- caused by: {}", e));
+                error.push_str(&format!("\n- caused by: {}", e));
                 cause = e.source();
             }
-            process::exit(1);
-        });
+            TemplateRenderFailed {
+                template: template_path(),
+                error,
+            }
+        })?;
+        log.phase("render", start.elapsed());
 
         Ok(generated_code)
     }
 
     /// Save the generated code to the output directory.
     fn save_generated_code(
+        log: &impl Logger,
         output_dir: &Path,
         relative_path: PathBuf,
         generated_code: String,
     ) -> Result<PathBuf, crate::Error> {
+        let start = Instant::now();
         // Create all intermediary directories if they don't exist
         let output_file_path = output_dir.join(relative_path);
         if let Some(parent_dir) = output_file_path.parent() {
@@ -368,10 +913,26 @@ impl ClientSdkGenerator {
                 error: format!("{}", e),
             }
         })?;
+        log.phase("write", start.elapsed());
 
         Ok(output_file_path)
     }
 
+    /// Builds the context of a signal template: the serialized signal and
+    /// its attributes grouped by namespace (`attribute_namespaces`).
+    fn signal_context(
+        signal: &impl Serialize,
+        attributes: &[Attribute],
+        schema_path: &Path,
+    ) -> Result<Context, crate::Error> {
+        let mut context = Context::from_serialize(signal).map_err(|e| InvalidTelemetrySchema {
+            schema: schema_path.to_path_buf(),
+            error: format!("{}", e),
+        })?;
+        context.insert("attribute_namespaces", &attribute_namespaces(attributes));
+        Ok(context)
+    }
+
     /// Process an univariate metric.
     fn process_metric(
         &self,
@@ -380,18 +941,19 @@ impl ClientSdkGenerator {
         schema_path: &Path,
         metric: &UnivariateMetric,
         output_dir: &Path,
-    ) -> Result<(), crate::Error> {
-        if let UnivariateMetric::Metric { name, .. } = metric {
-            let context = &Context::from_serialize(metric).map_err(|e| InvalidTelemetrySchema {
-                schema: schema_path.to_path_buf(),
-                error: format!("{}", e),
-            })?;
+        globals: &Context,
+    ) -> Result<Option<GeneratedFile>, crate::Error> {
+        if let UnivariateMetric::Metric {
+            name, attributes, ..
+        } = metric
+        {
+            let context = &Self::signal_context(metric, attributes, schema_path)?;
 
             // Reset the config
             self.config.reset();
 
             log.loading(&format!("Generating code for univariate metric `{}`", name));
-            let generated_code = self.generate_code(log.clone(), tmpl_file, context)?;
+            let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
 
             // Retrieve the file name from the config
             let relative_path = {
@@ -406,12 +968,14 @@ impl ClientSdkGenerator {
             };
 
             // Save the generated code to the output directory
+            let file = GeneratedFile::new("metric", name, &relative_path);
             let generated_file =
-                Self::save_generated_code(output_dir, relative_path, generated_code)?;
+                Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
             log.success(&format!("Generated file {:?}", generated_file));
+            return Ok(Some(file));
         }
 
-        Ok(())
+        Ok(None)
     }
 
     /// Process a metric group (multivariate).
@@ -422,11 +986,9 @@ impl ClientSdkGenerator {
         schema_path: &Path,
         metric: &MetricGroup,
         output_dir: &Path,
-    ) -> Result<(), crate::Error> {
-        let context = &Context::from_serialize(metric).map_err(|e| InvalidTelemetrySchema {
-            schema: schema_path.to_path_buf(),
-            error: format!("{}", e),
-        })?;
+        globals: &Context,
+    ) -> Result<GeneratedFile, crate::Error> {
+        let context = &Self::signal_context(metric, &metric.attributes, schema_path)?;
 
         // Reset the config
         self.config.reset();
@@ -435,7 +997,7 @@ impl ClientSdkGenerator {
             "Generating code for multivariate metric `{}`",
             metric.name
         ));
-        let generated_code = self.generate_code(log.clone(), tmpl_file, context)?;
+        let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
 
         // Retrieve the file name from the config
         let relative_path = {
@@ -450,10 +1012,12 @@ impl ClientSdkGenerator {
         };
 
         // Save the generated code to the output directory
-        let generated_file = Self::save_generated_code(output_dir, relative_path, generated_code)?;
+        let file = GeneratedFile::new("metric_group", &metric.name, &relative_path);
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
         log.success(&format!("Generated file {:?}", generated_file));
 
-        Ok(())
+        Ok(file)
     }
 
     /// Process an event.
@@ -464,17 +1028,15 @@ impl ClientSdkGenerator {
         schema_path: &Path,
         event: &Event,
         output_dir: &Path,
-    ) -> Result<(), crate::Error> {
-        let context = &Context::from_serialize(event).map_err(|e| InvalidTelemetrySchema {
-            schema: schema_path.to_path_buf(),
-            error: format!("{}", e),
-        })?;
+        globals: &Context,
+    ) -> Result<GeneratedFile, crate::Error> {
+        let context = &Self::signal_context(event, &event.attributes, schema_path)?;
 
         // Reset the config
         self.config.reset();
 
-        log.loading(&format!("Generating code for log `{}`", event.event_name));
-        let generated_code = self.generate_code(log.clone(), tmpl_file, context)?;
+        log.loading(&format!("Generating code for event `{}`", event.event_name));
+        let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
 
         // Retrieve the file name from the config
         let relative_path = {
@@ -489,22 +1051,129 @@ impl ClientSdkGenerator {
         };
 
         // Save the generated code to the output directory
-        let generated_file = Self::save_generated_code(output_dir, relative_path, generated_code)?;
+        let file = GeneratedFile::new("event", &event.event_name, &relative_path);
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
         log.success(&format!("Generated file {:?}", generated_file));
 
-        Ok(())
+        Ok(file)
     }
 
-    /// Process a span.
+    /// Process a span (`span` and `span_test` templates).
+    #[allow(clippy::too_many_arguments)]
     fn process_span(
         &self,
         log: impl Logger + Clone,
+        kind: &'static str,
         tmpl_file: &str,
         schema_path: &Path,
         span: &Span,
         output_dir: &Path,
-    ) -> Result<(), crate::Error> {
-        let context = &Context::from_serialize(span).map_err(|e| InvalidTelemetrySchema {
+        globals: &Context,
+    ) -> Result<GeneratedFile, crate::Error> {
+        let context = &Self::signal_context(span, &span.attributes, schema_path)?;
+
+        // Reset the config
+        self.config.reset();
+
+        log.loading(&format!(
+            "Generating code for {} `{}`",
+            kind.replace('_', " "),
+            span.span_name
+        ));
+        let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
+
+        // Retrieve the file name from the config
+        let relative_path = {
+            match self.config.get() {
+                None => {
+                    return Err(TemplateFileNameUndefined {
+                        template: PathBuf::from(tmpl_file),
+                    });
+                }
+                Some(file_name) => PathBuf::from(file_name.clone()),
+            }
+        };
+
+        // Save the generated code to the output directory
+        let file = GeneratedFile::new(kind, &span.span_name, &relative_path);
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
+        log.success(&format!("Generated file {:?}", generated_file));
+
+        Ok(file)
+    }
+
+    /// Process the common resource.
+    /// The schema URL is added to the context as `schema_url`.
+    #[allow(clippy::too_many_arguments)]
+    fn process_resource(
+        &self,
+        log: impl Logger + Clone,
+        tmpl_file: &str,
+        schema_path: &Path,
+        schema_url: &str,
+        resource: &Resource,
+        output_dir: &Path,
+        globals: &Context,
+    ) -> Result<GeneratedFile, crate::Error> {
+        let mut context =
+            Context::from_serialize(resource).map_err(|e| InvalidTelemetrySchema {
+                schema: schema_path.to_path_buf(),
+                error: format!("{}", e),
+            })?;
+        context.insert("schema_url", schema_url);
+
+        // Reset the config
+        self.config.reset();
+
+        log.loading("Generating code for the resource");
+        let generated_code = self.generate_code(log.clone(), tmpl_file, &context, globals)?;
+
+        // Retrieve the file name from the config
+        let relative_path = {
+            match self.config.get() {
+                None => {
+                    return Err(TemplateFileNameUndefined {
+                        template: PathBuf::from(tmpl_file),
+                    });
+                }
+                Some(file_name) => PathBuf::from(file_name.clone()),
+            }
+        };
+
+        // Save the generated code to the output directory
+        let file = GeneratedFile::new("resource", "resource", &relative_path);
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
+        log.success(&format!("Generated file {:?}", generated_file));
+
+        Ok(file)
+    }
+
+    /// Process the compatibility module of a previous version.
+    #[allow(clippy::too_many_arguments)]
+    fn process_compat(
+        &self,
+        log: impl Logger + Clone,
+        tmpl_file: &str,
+        schema_path: &Path,
+        versions: &Versions,
+        version: &semver::Version,
+        output_dir: &Path,
+        globals: &Context,
+    ) -> Result<GeneratedFile, crate::Error> {
+        let (attributes, metrics) = versions
+            .renames_since(version)
+            .into_iter()
+            .partition(|rename| rename.kind == RenameKind::Attribute);
+        let compat = CompatContext {
+            version,
+            current_version: versions.latest_version(),
+            attributes,
+            metrics,
+        };
+        let context = &Context::from_serialize(compat).map_err(|e| InvalidTelemetrySchema {
             schema: schema_path.to_path_buf(),
             error: format!("{}", e),
         })?;
@@ -512,8 +1181,11 @@ impl ClientSdkGenerator {
         // Reset the config
         self.config.reset();
 
-        log.loading(&format!("Generating code for span `{}`", span.span_name));
-        let generated_code = self.generate_code(log.clone(), tmpl_file, context)?;
+        log.loading(&format!(
+            "Generating compatibility module for version {}",
+            version
+        ));
+        let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
 
         // Retrieve the file name from the config
         let relative_path = {
@@ -528,9 +1200,321 @@ impl ClientSdkGenerator {
         };
 
         // Save the generated code to the output directory
-        let generated_file = Self::save_generated_code(output_dir, relative_path, generated_code)?;
+        let file = GeneratedFile::new("compat", &version.to_string(), &relative_path);
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
         log.success(&format!("Generated file {:?}", generated_file));
 
-        Ok(())
+        Ok(file)
+    }
+
+    /// Builds the context of the `readme` templates.
+    fn readme_context<'a>(
+        &self,
+        schema: &'a TelemetrySchema,
+        schema_path: &Path,
+        output_dir: &Path,
+        files: Vec<GeneratedFile>,
+    ) -> ReadmeContext<'a> {
+        let language = self
+            .lang_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        ReadmeContext {
+            regenerate_command: format!(
+                "weaver gen-client --schema {} --language {} --output-dir {}",
+                schema_path.display(),
+                language,
+                output_dir.display()
+            ),
+            language,
+            schema_url: &schema.schema_url,
+            version: schema
+                .versions
+                .as_ref()
+                .and_then(|versions| versions.latest_version())
+                .map(|version| version.to_string()),
+            semantic_conventions: schema.merged_semantic_conventions(),
+            counts: SignalCounts {
+                metrics: schema.metrics_count(),
+                metric_groups: schema.metric_groups_count(),
+                events: schema.events_count(),
+                spans: schema.spans_count(),
+            },
+            files,
+        }
+    }
+
+    /// Process a README summarizing the generated client SDK.
+    #[allow(clippy::too_many_arguments)]
+    fn process_readme(
+        &self,
+        log: impl Logger + Clone,
+        tmpl_file: &str,
+        schema_path: &Path,
+        readme: &ReadmeContext<'_>,
+        relative_path: PathBuf,
+        output_dir: &Path,
+        globals: &Context,
+    ) -> Result<PathBuf, crate::Error> {
+        let context = &Context::from_serialize(readme).map_err(|e| InvalidTelemetrySchema {
+            schema: schema_path.to_path_buf(),
+            error: format!("{}", e),
+        })?;
+
+        log.loading(&format!("Generating README {}", tmpl_file));
+        let generated_code = self.generate_code(log.clone(), tmpl_file, context, globals)?;
+
+        // Save the generated code to the output directory
+        let generated_file =
+            Self::save_generated_code(&log, output_dir, relative_path, generated_code)?;
+        log.success(&format!("Generated file {:?}", generated_file));
+
+        Ok(generated_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use weaver_logger::NullLogger;
+
+    use crate::scaffold::init_language_pack;
+    use crate::sdkgen::ClientSdkGenerator;
+    use crate::{Error, GeneratorConfig};
+
+    #[test]
+    fn test_resolver_config_build_tools_compat() {
+        let template_dir = tempdir::TempDir::new("templates").unwrap();
+        _ = init_language_pack(template_dir.path(), "mylang").unwrap();
+
+        let config =
+            GeneratorConfig::default().with_template_dir(template_dir.path().to_path_buf());
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        assert!(!generator.resolver_config().build_tools_compat());
+
+        let config = GeneratorConfig::default()
+            .with_template_dir(template_dir.path().to_path_buf())
+            .with_build_tools_compat(true);
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        assert!(generator.resolver_config().build_tools_compat());
+    }
+
+    #[test]
+    fn test_readme() {
+        let template_dir = tempdir::TempDir::new("templates").unwrap();
+        let output_dir = tempdir::TempDir::new("output").unwrap();
+        _ = init_language_pack(template_dir.path(), "mylang").unwrap();
+        fs::write(
+            template_dir.path().join("mylang/readme.tera"),
+            include_str!("../../../templates/go/readme.tera"),
+        )
+        .unwrap();
+
+        let config =
+            GeneratorConfig::default().with_template_dir(template_dir.path().to_path_buf());
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        let schema_path = template_dir.path().join("mylang/tests/fixture.yaml");
+        generator
+            .generate(
+                NullLogger::new(),
+                schema_path.clone(),
+                output_dir.path().to_path_buf(),
+            )
+            .unwrap();
+
+        // The README is rendered after the other templates and lists the
+        // files they generated.
+        let readme = fs::read_to_string(output_dir.path().join("README.md")).unwrap();
+        assert!(readme.starts_with("# Generated OpenTelemetry client SDK (mylang)"));
+        assert!(readme.contains("from the telemetry schema `https://example.com/schemas/1.0.0`."));
+        assert!(readme.contains("| Metrics       | 1 |"));
+        assert!(readme.contains("| Metric groups | 0 |"));
+        assert!(readme.contains("| Events        | 1 |"));
+        assert!(readme.contains("| Spans         | 1 |"));
+        assert!(readme.contains("### Spans\n\n- [`http.request`](otel/tracer/http_request.txt)"));
+        assert!(readme.contains("### Events\n\n- [`exception`](otel/eventer/app/exception.txt)"));
+        assert!(readme.contains(
+            "### Metrics\n\n- [`http.client.request.duration`](otel/meter/http_client_request_duration.txt)"
+        ));
+        assert!(!readme.contains("### Metric groups"));
+        assert!(readme.contains(&format!(
+            "weaver gen-client --schema {} --language mylang --output-dir {}",
+            schema_path.display(),
+            output_dir.path().display()
+        )));
+    }
+
+    #[test]
+    fn test_template_kinds() {
+        let template_dir = tempdir::TempDir::new("templates").unwrap();
+        let output_dir = tempdir::TempDir::new("output").unwrap();
+        let lang_dir = template_dir.path().join("mylang");
+        fs::create_dir_all(lang_dir.join("otel")).unwrap();
+        fs::write(
+            lang_dir.join("otel/resource.tera"),
+            r#"{{- config(file_name="otel/resource.txt") -}}
+{{ schema_url }}
+{% for attr in attributes %}{{ attr.id }}
+{% endfor %}{% set registry = registry() %}{{ registry.groups | length }}"#,
+        )
+        .unwrap();
+        fs::write(
+            lang_dir.join("otel/metric_group.tera"),
+            r#"{{- config(file_name="otel/meter/" ~ name ~ ".txt") -}}
+{% for attr in attributes %}{{ attr.id }}
+{% endfor %}{% for metric in metrics %}{{ metric.name }}
+{% endfor %}"#,
+        )
+        .unwrap();
+        fs::write(
+            lang_dir.join("otel/event.tera"),
+            r#"{{- config(file_name="otel/eventer/" ~ domain ~ "/" ~ event_name ~ ".txt") -}}
+{% for attr in attributes %}{{ attr.id }}
+{% endfor %}"#,
+        )
+        .unwrap();
+        let schema_path = template_dir.path().join("schema.yaml");
+        fs::write(
+            &schema_path,
+            r#"file_format: 1.2.0
+schema_url: https://example.com/schemas/1.0.0
+schema:
+  resource:
+    attributes:
+      - id: service.name
+        type: string
+        brief: The name of the service.
+        examples: ["checkout"]
+  resource_metrics:
+    metric_groups:
+      - name: jobs
+        attributes:
+          - id: job.queue
+            type: string
+            brief: The queue of the jobs.
+            examples: ["default"]
+        metrics:
+          - name: jobs.duration
+            brief: The duration of the jobs.
+            note: ""
+            instrument: histogram
+            unit: ms
+          - name: jobs.count
+            brief: The number of jobs.
+            note: ""
+            instrument: gauge
+            unit: "{job}"
+  resource_events:
+    events:
+      - event_name: exception
+        domain: app
+        attributes:
+          - id: exception.message
+            type: string
+            brief: The message of the exception.
+            examples: ["Connection reset"]
+"#,
+        )
+        .unwrap();
+
+        let config =
+            GeneratorConfig::default().with_template_dir(template_dir.path().to_path_buf());
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        generator
+            .generate(
+                NullLogger::new(),
+                schema_path,
+                output_dir.path().to_path_buf(),
+            )
+            .unwrap();
+
+        let read = |path: &str| fs::read_to_string(output_dir.path().join(path)).unwrap();
+        // The resource template is rendered once, with the schema URL.
+        assert_eq!(
+            read("otel/resource.txt"),
+            "https://example.com/schemas/1.0.0\nservice.name\n0"
+        );
+        // The metric group template is rendered once per metric group, with
+        // the attributes and metrics of the group.
+        assert_eq!(
+            read("otel/meter/jobs.txt"),
+            "job.queue\njobs.duration\njobs.count\n"
+        );
+        // The event template is rendered once per event.
+        assert_eq!(
+            read("otel/eventer/app/exception.txt"),
+            "exception.message\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_errors() {
+        let template_dir = tempdir::TempDir::new("templates").unwrap();
+        let output_dir = tempdir::TempDir::new("output").unwrap();
+        let lang_dir = template_dir.path().join("mylang");
+        fs::create_dir_all(lang_dir.join("otel")).unwrap();
+        fs::write(
+            lang_dir.join("otel/resource.tera"),
+            r#"{{- config(file_name="otel/resource.txt") -}}
+{{ unknown.field }}"#,
+        )
+        .unwrap();
+        let schema_path = template_dir.path().join("schema.yaml");
+        let write_schema = |attribute: &str| {
+            fs::write(
+                &schema_path,
+                format!(
+                    r#"file_format: 1.2.0
+schema_url: https://example.com/schemas/1.0.0
+schema:
+  resource:
+    attributes:
+      - {}
+"#,
+                    attribute
+                ),
+            )
+            .unwrap();
+        };
+        let config =
+            GeneratorConfig::default().with_template_dir(template_dir.path().to_path_buf());
+        let generator = ClientSdkGenerator::try_new("mylang", config).unwrap();
+        let generate = || {
+            generator.generate(
+                NullLogger::new(),
+                schema_path.clone(),
+                output_dir.path().to_path_buf(),
+            )
+        };
+
+        // The resolver error is kept to be classified by the caller.
+        write_schema("ref: unknown.attribute");
+        assert!(matches!(
+            generate(),
+            Err(Error::SchemaResolutionFailed {
+                error: weaver_resolver::Error::FailToResolveAttributes { .. }
+                    | weaver_resolver::Error::UnresolvedReferences { .. },
+                ..
+            })
+        ));
+
+        // A render failure is returned instead of exiting the process.
+        write_schema(
+            r#"id: service.name
+        type: string
+        brief: The name of the service.
+        examples: ["checkout"]"#,
+        );
+        match generate() {
+            Err(Error::TemplateRenderFailed { template, error }) => {
+                assert!(template.ends_with("otel/resource.tera"));
+                assert!(error.contains("unknown"), "{}", error);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
     }
 }
@@ -2,7 +2,9 @@
 
 //! Custom testers
 
-use tera::Value;
+use tera::{Test, Value};
+
+use crate::deprecated::{is_deprecated, DeprecatedPolicy};
 
 pub fn is_required(value: Option<&Value>, _args: &[Value]) -> tera::Result<bool> {
     if let Some(Value::Object(map)) = value {
@@ -25,3 +27,16 @@ pub fn is_not_required(value: Option<&Value>, _args: &[Value]) -> tera::Result<b
     }
     Ok(true)
 }
+
+/// Tester returning true if the value is a deprecated definition (always false
+/// with the `include` deprecated policy).
+pub struct IsDeprecated {
+    /// The deprecated policy of the generator.
+    pub policy: DeprecatedPolicy,
+}
+
+impl Test for IsDeprecated {
+    fn test(&self, value: Option<&Value>, _args: &[Value]) -> tera::Result<bool> {
+        Ok(self.policy != DeprecatedPolicy::Include && value.is_some_and(is_deprecated))
+    }
+}
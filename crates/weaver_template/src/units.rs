@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion table of the UCUM units used by the semantic conventions, for
+//! the code generation: the suffix naming a unit in the generated code (e.g.
+//! `milliseconds` for `ms`) and the factor converting a value to the base
+//! unit of its dimension (e.g. `0.001` from `ms` to `s`), so the generated
+//! metric helpers can't mix up milliseconds and seconds.
+//!
+//! The rates (e.g. `By/s`) are combinations of two units of the table. The
+//! annotations (e.g. `{request}`) and the unit `1` are dimensionless, without
+//! suffix.
+
+/// A unit of the conversion table.
+struct UnitDefinition {
+    /// The UCUM code of the unit.
+    code: &'static str,
+    /// The suffix naming the unit in the generated code.
+    suffix: &'static str,
+    /// The UCUM code of the base unit of the dimension.
+    base: &'static str,
+    /// The factor converting a value to the base unit.
+    factor: f64,
+}
+
+/// The units of the conversion table.
+const UNITS: &[UnitDefinition] = &[
+    unit("ns", "nanoseconds", "s", 1e-9),
+    unit("us", "microseconds", "s", 1e-6),
+    unit("ms", "milliseconds", "s", 1e-3),
+    unit("s", "seconds", "s", 1.0),
+    unit("min", "minutes", "s", 60.0),
+    unit("h", "hours", "s", 3600.0),
+    unit("d", "days", "s", 86400.0),
+    unit("bit", "bits", "By", 0.125),
+    unit("By", "bytes", "By", 1.0),
+    unit("kBy", "kilobytes", "By", 1e3),
+    unit("KBy", "kilobytes", "By", 1e3),
+    unit("MBy", "megabytes", "By", 1e6),
+    unit("GBy", "gigabytes", "By", 1e9),
+    unit("TBy", "terabytes", "By", 1e12),
+    unit("KiBy", "kibibytes", "By", 1024.0),
+    unit("MiBy", "mebibytes", "By", 1048576.0),
+    unit("GiBy", "gibibytes", "By", 1073741824.0),
+    unit("TiBy", "tebibytes", "By", 1099511627776.0),
+    unit("m", "meters", "m", 1.0),
+    unit("g", "grams", "g", 1.0),
+    unit("Hz", "hertz", "Hz", 1.0),
+    unit("V", "volts", "V", 1.0),
+    unit("A", "amperes", "A", 1.0),
+    unit("W", "watts", "W", 1.0),
+    unit("J", "joules", "J", 1.0),
+    unit("Cel", "celsius", "Cel", 1.0),
+    unit("%", "percent", "1", 0.01),
+    unit("1", "", "1", 1.0),
+];
+
+/// Returns a unit of the conversion table.
+const fn unit(
+    code: &'static str,
+    suffix: &'static str,
+    base: &'static str,
+    factor: f64,
+) -> UnitDefinition {
+    UnitDefinition {
+        code,
+        suffix,
+        base,
+        factor,
+    }
+}
+
+/// A unit resolved with the conversion table.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Unit {
+    /// The suffix naming the unit in the generated code (empty for the
+    /// dimensionless units).
+    pub(crate) suffix: String,
+    /// The UCUM code of the base unit of the dimension.
+    pub(crate) base: String,
+    /// The factor converting a value to the base unit.
+    pub(crate) factor: f64,
+}
+
+/// Resolves a UCUM unit (a unit of the table, an annotation or a rate of
+/// these units), or returns `None` if the unit is unknown.
+pub(crate) fn resolve(code: &str) -> Option<Unit> {
+    let code = code.trim();
+    if let Some((numerator, denominator)) = code.split_once('/') {
+        let numerator = resolve(numerator)?;
+        let denominator = resolve(denominator)?;
+        // The denominator of a rate is singular (`per_second`).
+        let per = denominator
+            .suffix
+            .strip_suffix('s')
+            .filter(|_| !denominator.suffix.ends_with("us"))
+            .unwrap_or(&denominator.suffix);
+        let suffix = match (numerator.suffix.is_empty(), per.is_empty()) {
+            (_, true) => numerator.suffix,
+            (true, false) => format!("per_{}", per),
+            (false, false) => format!("{}_per_{}", numerator.suffix, per),
+        };
+        return Some(Unit {
+            suffix,
+            base: format!("{}/{}", numerator.base, denominator.base),
+            factor: numerator.factor / denominator.factor,
+        });
+    }
+    if code.starts_with('{') && code.ends_with('}') {
+        return Some(Unit {
+            suffix: String::new(),
+            base: "1".to_owned(),
+            factor: 1.0,
+        });
+    }
+    UNITS
+        .iter()
+        .find(|unit| unit.code == code)
+        .map(|unit| Unit {
+            suffix: unit.suffix.to_owned(),
+            base: unit.base.to_owned(),
+            factor: unit.factor,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve;
+
+    #[test]
+    fn test_resolve() {
+        let ms = resolve("ms").unwrap();
+        assert_eq!(ms.suffix, "milliseconds");
+        assert_eq!(ms.base, "s");
+        assert_eq!(ms.factor, 0.001);
+        assert_eq!(resolve("KiBy").unwrap().factor, 1024.0);
+        assert_eq!(resolve("{request}").unwrap().suffix, "");
+        assert_eq!(resolve("1").unwrap().suffix, "");
+
+        let rate = resolve("KiBy/ms").unwrap();
+        assert_eq!(rate.suffix, "kibibytes_per_millisecond");
+        assert_eq!(rate.base, "By/s");
+        assert!((rate.factor - 1024000.0).abs() < 1e-6);
+        assert_eq!(resolve("{packet}/s").unwrap().suffix, "per_second");
+
+        assert!(resolve("sec").is_none());
+        assert!(resolve("By/parsec").is_none());
+    }
+}
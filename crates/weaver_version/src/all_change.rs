@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Changes to apply to all the signals for a specific version.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Changes to apply to all the signals (resources, metrics, logs and spans)
+/// for a specific version.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AllChange {
+    /// Changes to apply to the attributes of all the signals for a specific version.
+    pub rename_attributes: RenameAttributes,
+}
+
+/// Changes to apply to the attributes of all the signals for a specific version.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RenameAttributes {
+    /// A collection of rename operations to apply to the attributes of all the signals.
+    pub attribute_map: HashMap<String, String>,
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Changes to apply to all the signals for a specific version.
+
+use crate::all_change::AllChange;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Changes to apply to all the signals for a specific version.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AllVersion {
+    /// Changes to apply to all the signals for a specific version.
+    pub changes: Vec<AllChange>,
+}
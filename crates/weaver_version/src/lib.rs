@@ -6,13 +6,18 @@
 #![deny(clippy::print_stdout)]
 #![deny(clippy::print_stderr)]
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::BufReader;
+use std::ops::Bound;
 use std::path::Path;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::all_change::AllChange;
+use crate::all_version::AllVersion;
 use crate::logs_change::LogsChange;
 use crate::logs_version::LogsVersion;
 use crate::metrics_change::MetricsChange;
@@ -22,6 +27,8 @@ use crate::resource_version::ResourceVersion;
 use crate::spans_change::SpansChange;
 use crate::spans_version::SpansVersion;
 
+pub mod all_change;
+pub mod all_version;
 pub mod logs_change;
 pub mod logs_version;
 pub mod metrics_change;
@@ -57,17 +64,28 @@ pub enum Error {
     },
 }
 
+/// Versions has a transparent serde representation so we need to define a top-level
+/// struct to deserialize the `versions` file.
+#[derive(Serialize, Deserialize, Debug)]
+struct VersionsFile {
+    versions: Versions,
+}
+
 /// List of versions with their changes.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(transparent)]
 pub struct Versions {
     versions: BTreeMap<semver::Version, VersionSpec>,
 }
 
 /// An history of changes to apply to the schema for different versions.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VersionSpec {
+    /// The changes to apply to all the signals for a specific version.
+    /// The changes specific to a signal take precedence over these changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all: Option<AllVersion>,
     /// The changes to apply to the metrics specification for a specific version.
     pub metrics: Option<MetricsVersion>,
     /// The changes to apply to the logs specification for a specific version.
@@ -89,6 +107,62 @@ pub struct VersionChanges {
     span_old_to_new_attributes: HashMap<String, String>,
 }
 
+/// The kind of object renamed by a version.
+#[derive(Serialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameKind {
+    /// An attribute (of a resource, metric, log or span).
+    Attribute,
+    /// A metric.
+    Metric,
+}
+
+/// A rename of an attribute or a metric between a version and the latest
+/// version.
+#[derive(Serialize, JsonSchema, Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    /// The kind of object renamed.
+    pub kind: RenameKind,
+    /// The name of the object in the initial version.
+    pub old_name: String,
+    /// The name of the object in the latest version.
+    pub new_name: String,
+    /// The version introducing the latest name.
+    pub version: semver::Version,
+}
+
+/// A divergence of a version history from the published version history it
+/// must extend (see [`Versions::history_divergences`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryDivergence {
+    /// A published version missing from the version history.
+    MissingVersion(semver::Version),
+    /// A published version whose changes were modified.
+    ModifiedVersion(semver::Version),
+    /// A version inserted before the latest published version.
+    InsertedVersion(semver::Version),
+}
+
+impl Display for HistoryDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryDivergence::MissingVersion(version) => {
+                write!(f, "The published version {} is missing", version)
+            }
+            HistoryDivergence::ModifiedVersion(version) => write!(
+                f,
+                "The changes of the published version {} were modified",
+                version
+            ),
+            HistoryDivergence::InsertedVersion(version) => write!(
+                f,
+                "The version {} is inserted before the latest published version",
+                version
+            ),
+        }
+    }
+}
+
 /// A trait to get the new name of an attribute of a resource, log or span.
 pub trait VersionAttributeChanges {
     /// Returns the new name of the given attribute or the given name if the attribute
@@ -100,13 +174,6 @@ impl Versions {
     /// Loads a `versions` file and returns an instance of `Versions` if successful
     /// or an error if the file could not be loaded or deserialized.
     pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Versions, Error> {
-        /// Versions has a transparent serde representation so we need to define a top-level
-        /// struct to deserialize the `versions` file.
-        #[derive(Serialize, Deserialize, Debug)]
-        struct TopLevel {
-            versions: Versions,
-        }
-
         let path_buf = path.as_ref().to_path_buf();
 
         // Load and deserialize the telemetry schema
@@ -114,14 +181,26 @@ impl Versions {
             path_or_url: path_buf.as_path().display().to_string(),
             error: e.to_string(),
         })?;
-        let top_level: TopLevel =
-            serde_yaml::from_reader(BufReader::new(versions_file)).map_err(|e| {
-                Error::InvalidVersions {
-                    path_or_url: path_buf.as_path().display().to_string(),
-                    line: e.location().map(|loc| loc.line()),
-                    column: e.location().map(|loc| loc.column()),
-                    error: e.to_string(),
-                }
+        let top_level: VersionsFile = serde_yaml::from_reader(BufReader::new(versions_file))
+            .map_err(|e| Error::InvalidVersions {
+                path_or_url: path_buf.as_path().display().to_string(),
+                line: e.location().map(|loc| loc.line()),
+                column: e.location().map(|loc| loc.column()),
+                error: e.to_string(),
+            })?;
+        Ok(top_level.versions)
+    }
+
+    /// Loads the `versions` section of the content of a `versions` file or of
+    /// a schema file (e.g. a published schema file fetched from its schema
+    /// URL), the path or URL being used in the errors.
+    pub fn load_from_str(content: &str, path_or_url: &str) -> Result<Versions, Error> {
+        let top_level: VersionsFile =
+            serde_yaml::from_str(content).map_err(|e| Error::InvalidVersions {
+                path_or_url: path_or_url.to_owned(),
+                line: e.location().map(|loc| loc.line()),
+                column: e.location().map(|loc| loc.column()),
+                error: e.to_string(),
             })?;
         Ok(top_level.versions)
     }
@@ -161,11 +240,58 @@ impl Versions {
         self.versions.range(..=version.clone()).rev().collect()
     }
 
+    /// Returns the number of transformations (attribute and metric renames)
+    /// defined by all the versions.
+    pub fn transformation_count(&self) -> usize {
+        self.versions
+            .values()
+            .map(|spec| {
+                let metrics: usize = spec
+                    .metrics
+                    .iter()
+                    .flat_map(|metrics| metrics.changes.iter())
+                    .map(|change| {
+                        change.rename_attributes.attribute_map.len() + change.rename_metrics.len()
+                    })
+                    .sum();
+                let logs: usize = spec
+                    .logs
+                    .iter()
+                    .flat_map(|logs| logs.changes.iter())
+                    .map(|change| change.rename_attributes.attribute_map.len())
+                    .sum();
+                let spans: usize = spec
+                    .spans
+                    .iter()
+                    .flat_map(|spans| spans.changes.iter())
+                    .map(|change| change.rename_attributes.attribute_map.len())
+                    .sum();
+                let resources: usize = spec
+                    .resources
+                    .iter()
+                    .flat_map(|resources| resources.changes.iter())
+                    .map(|change| change.rename_attributes.attribute_map.len())
+                    .sum();
+                let all: usize = spec
+                    .all
+                    .iter()
+                    .flat_map(|all| all.changes.iter())
+                    .map(|change| change.rename_attributes.attribute_map.len())
+                    .sum();
+                metrics + logs + spans + resources + all
+            })
+            .sum()
+    }
+
     /// Returns the changes to apply for the given version including the changes
     /// of the previous versions.
     /// The current supported changes are:
     /// - Renaming of attributes (for resources, logs and spans)
     /// - Renaming of metrics
+    ///
+    /// The attribute renames of the `all` section apply to the resources, metrics,
+    /// logs and spans, unless the section of the signal renames the same attribute
+    /// in the same version.
     pub fn version_changes_for(&self, version: &semver::Version) -> VersionChanges {
         let mut resource_old_to_new_attributes: HashMap<String, String> = HashMap::new();
         let mut metric_old_to_new_names: HashMap<String, String> = HashMap::new();
@@ -243,6 +369,26 @@ impl Versions {
                         }
                     });
             }
+
+            // Completes the maps of old to new attribute names of each signal with the
+            // attributes that have been renamed in the different versions of all the signals.
+            if let Some(all) = spec.all.as_ref() {
+                all.changes
+                    .iter()
+                    .flat_map(|change| change.rename_attributes.attribute_map.iter())
+                    .for_each(|(old_name, new_name)| {
+                        for old_to_new_attributes in [
+                            &mut resource_old_to_new_attributes,
+                            &mut metric_old_to_new_attributes,
+                            &mut log_old_to_new_attributes,
+                            &mut span_old_to_new_attributes,
+                        ] {
+                            if !old_to_new_attributes.contains_key(old_name) {
+                                old_to_new_attributes.insert(old_name.clone(), new_name.clone());
+                            }
+                        }
+                    });
+            }
         }
 
         VersionChanges {
@@ -254,6 +400,76 @@ impl Versions {
         }
     }
 
+    /// Returns the renames of attributes and metrics introduced by the versions
+    /// following the given version, ordered by kind and old name.
+    /// Successive renames are chained, so the old name of a rename is the name
+    /// in the given version and the new name is the name in the latest version.
+    /// The attribute renames of the different signals are merged.
+    pub fn renames_since(&self, version: &semver::Version) -> Vec<Rename> {
+        let mut renames: BTreeMap<(RenameKind, String), (String, semver::Version)> =
+            BTreeMap::new();
+        let mut rename =
+            |kind: RenameKind, old_name: &String, new_name: &String, at: &semver::Version| {
+                let mut chained = false;
+                for ((renamed_kind, _), (name, renamed_at)) in renames.iter_mut() {
+                    if *renamed_kind == kind && name == old_name {
+                        name.clone_from(new_name);
+                        *renamed_at = at.clone();
+                        chained = true;
+                    }
+                }
+                // A name introduced by a previous rename doesn't exist in the
+                // given version.
+                if !chained {
+                    _ = renames
+                        .entry((kind, old_name.clone()))
+                        .or_insert_with(|| (new_name.clone(), at.clone()));
+                }
+            };
+
+        for (at, spec) in self
+            .versions
+            .range((Bound::Excluded(version), Bound::Unbounded))
+        {
+            for (kind, old_name, new_name) in spec.renames() {
+                rename(kind, old_name, new_name, at);
+            }
+        }
+
+        renames
+            .into_iter()
+            // A name renamed back to its initial value is not a rename.
+            .filter(|((_, old_name), (new_name, _))| old_name != new_name)
+            .map(|((kind, old_name), (new_name, version))| Rename {
+                kind,
+                old_name,
+                new_name,
+                version,
+            })
+            .collect()
+    }
+
+    /// Returns every rename of attributes and metrics declared by the
+    /// versions, ordered by version. Unlike [`Versions::renames_since`], the
+    /// renames are not chained: the new name of a rename is the name
+    /// introduced by its version and the version is the one declaring it.
+    pub fn renames_by_version(&self) -> Vec<Rename> {
+        self.versions
+            .iter()
+            .flat_map(|(version, spec)| {
+                spec.renames()
+                    .into_iter()
+                    .filter(|(_, old_name, new_name)| old_name != new_name)
+                    .map(move |(kind, old_name, new_name)| Rename {
+                        kind,
+                        old_name: old_name.clone(),
+                        new_name: new_name.clone(),
+                        version: version.clone(),
+                    })
+            })
+            .collect()
+    }
+
     /// Update the current `Versions` to include the transformations of the parent `Versions`.
     /// Transformations of the current `Versions` take precedence over the parent `Versions`.
     pub fn extend(&mut self, parent_versions: Versions) {
@@ -269,6 +485,39 @@ impl Versions {
         }
     }
 
+    /// Returns the divergences of these versions from a published version
+    /// history (e.g. the `versions` section of the schema file published at
+    /// the schema URL), which must be a prefix of these versions: the
+    /// published versions are kept unchanged and the new versions come after
+    /// the latest published version. An empty list means the published
+    /// history was not rewritten.
+    pub fn history_divergences(&self, published: &Versions) -> Vec<HistoryDivergence> {
+        let mut divergences = vec![];
+        for (version, published_spec) in published.versions.iter() {
+            match self.versions.get(version) {
+                None => divergences.push(HistoryDivergence::MissingVersion(version.clone())),
+                Some(spec) => {
+                    // The version specs are compared through their serialized form.
+                    if serde_yaml::to_value(spec).ok() != serde_yaml::to_value(published_spec).ok()
+                    {
+                        divergences.push(HistoryDivergence::ModifiedVersion(version.clone()));
+                    }
+                }
+            }
+        }
+        if let Some(latest_published) = published.latest_version() {
+            divergences.extend(
+                self.versions
+                    .keys()
+                    .filter(|version| {
+                        *version < latest_published && !published.versions.contains_key(version)
+                    })
+                    .map(|version| HistoryDivergence::InsertedVersion(version.clone())),
+            );
+        }
+        divergences
+    }
+
     /// Returns true if the `Versions` is empty.
     pub fn is_empty(&self) -> bool {
         self.versions.is_empty()
@@ -281,14 +530,102 @@ impl Versions {
 }
 
 impl VersionSpec {
+    /// Returns the renames (kind, old name, new name) declared by the
+    /// changes of this version. The attribute renames of the different
+    /// signals are merged.
+    fn renames(&self) -> Vec<(RenameKind, &String, &String)> {
+        let mut renames = vec![];
+        let attribute_maps = self
+            .all
+            .iter()
+            .flat_map(|a| a.changes.iter().map(|c| &c.rename_attributes.attribute_map))
+            .chain(
+                self.resources
+                    .iter()
+                    .flat_map(|r| r.changes.iter().map(|c| &c.rename_attributes.attribute_map)),
+            )
+            .chain(
+                self.metrics
+                    .iter()
+                    .flat_map(|m| m.changes.iter().map(|c| &c.rename_attributes.attribute_map)),
+            )
+            .chain(
+                self.logs
+                    .iter()
+                    .flat_map(|l| l.changes.iter().map(|c| &c.rename_attributes.attribute_map)),
+            )
+            .chain(
+                self.spans
+                    .iter()
+                    .flat_map(|s| s.changes.iter().map(|c| &c.rename_attributes.attribute_map)),
+            );
+        for attribute_map in attribute_maps {
+            for (old_name, new_name) in attribute_map.iter() {
+                renames.push((RenameKind::Attribute, old_name, new_name));
+            }
+        }
+        for change in self.metrics.iter().flat_map(|m| m.changes.iter()) {
+            for (old_name, new_name) in change.rename_metrics.iter() {
+                renames.push((RenameKind::Metric, old_name, new_name));
+            }
+        }
+        renames
+    }
+
     /// Update the current `VersionSpec` to include the transformations of the parent `VersionSpec`.
     /// Transformations of the current `VersionSpec` take precedence over the parent `VersionSpec`.
+    /// The attributes renamed by the local `all` section are not renamed by the
+    /// sections of the parent `VersionSpec`.
     pub fn extend(&mut self, parent_spec: VersionSpec) {
+        let local_all_renames: HashSet<String> = self
+            .all
+            .iter()
+            .flat_map(|all| all.changes.iter())
+            .flat_map(|change| change.rename_attributes.attribute_map.keys().cloned())
+            .collect();
+
+        // Process all signals
+        if let Some(all) = parent_spec.all {
+            let mut all_change = AllChange::default();
+            for change in all.changes {
+                for (old, new) in change.rename_attributes.attribute_map {
+                    if local_all_renames.contains(&old) {
+                        // renaming already present in local changes, skip it
+                        continue;
+                    }
+                    // renaming not found in local changes, add it
+                    all_change.rename_attributes.attribute_map.insert(old, new);
+                }
+            }
+            if !all_change.rename_attributes.attribute_map.is_empty() {
+                if self
+                    .all
+                    .get_or_insert_with(AllVersion::default)
+                    .changes
+                    .is_empty()
+                {
+                    self.all
+                        .get_or_insert_with(AllVersion::default)
+                        .changes
+                        .push(all_change);
+                } else {
+                    self.all.get_or_insert_with(AllVersion::default).changes[0]
+                        .rename_attributes
+                        .attribute_map
+                        .extend(all_change.rename_attributes.attribute_map);
+                }
+            }
+        }
+
         // Process resources
         if let Some(resources) = parent_spec.resources {
             let mut resource_change = ResourceChange::default();
             for change in resources.changes {
                 'next_parent_renaming: for (old, new) in change.rename_attributes.attribute_map {
+                    if local_all_renames.contains(&old) {
+                        // renaming overridden by the local `all` section, skip it
+                        continue;
+                    }
                     for local_change in self
                         .resources
                         .get_or_insert_with(ResourceVersion::default)
@@ -379,6 +716,10 @@ impl VersionSpec {
             let mut logs_change = LogsChange::default();
             for change in logs.changes {
                 'next_parent_renaming: for (old, new) in change.rename_attributes.attribute_map {
+                    if local_all_renames.contains(&old) {
+                        // renaming overridden by the local `all` section, skip it
+                        continue;
+                    }
                     for local_change in self
                         .logs
                         .get_or_insert_with(LogsVersion::default)
@@ -423,6 +764,10 @@ impl VersionSpec {
             let mut spans_change = SpansChange::default();
             for change in spans.changes {
                 'next_parent_renaming: for (old, new) in change.rename_attributes.attribute_map {
+                    if local_all_renames.contains(&old) {
+                        // renaming overridden by the local `all` section, skip it
+                        continue;
+                    }
                     for local_change in self
                         .spans
                         .get_or_insert_with(SpansVersion::default)
@@ -601,7 +946,7 @@ impl VersionChanges {
 
 #[cfg(test)]
 mod tests {
-    use crate::Versions;
+    use crate::{HistoryDivergence, RenameKind, Versions};
 
     #[test]
     fn test_ordering() {
@@ -609,8 +954,8 @@ mod tests {
         let mut version = None;
 
         for (v, _) in versions.versions_asc() {
-            if version.is_some() {
-                assert!(v > version.unwrap());
+            if let Some(version) = version {
+                assert!(v > version);
             }
             version = Some(v);
         }
@@ -618,8 +963,8 @@ mod tests {
         let mut version = None;
 
         for (v, _) in versions.versions_desc() {
-            if version.is_some() {
-                assert!(v < version.unwrap());
+            if let Some(version) = version {
+                assert!(v < version);
             }
             version = Some(v);
         }
@@ -672,6 +1017,73 @@ mod tests {
         assert_eq!("metric_2", changes.get_metric_name("m2"));
     }
 
+    #[test]
+    fn test_renames_since() {
+        let versions: Versions = Versions::load_from_file("data/parent_versions.yaml").unwrap();
+        let renames = versions.renames_since(&semver::Version::parse("1.16.0").unwrap());
+
+        let rename = |old_name: &str| {
+            renames
+                .iter()
+                .find(|rename| rename.kind == RenameKind::Attribute && rename.old_name == old_name)
+                .map(|rename| (rename.new_name.as_str(), rename.version.to_string()))
+        };
+        // Renamed in 1.17.0, 1.20.0 and then 1.21.0.
+        assert_eq!(
+            rename("messaging.protocol"),
+            Some(("network.protocol.name", "1.21.0".to_owned()))
+        );
+        // Intermediate names, introduced after 1.16.0.
+        assert_eq!(rename("net.app.protocol.name"), None);
+        assert_eq!(rename("net.protocol.name"), None);
+        assert_eq!(
+            rename("http.method"),
+            Some(("http.request.method", "1.21.0".to_owned()))
+        );
+        // Renamed by the versions preceding 1.16.0.
+        assert_eq!(rename("db.hbase.namespace"), None);
+        assert!(!renames.iter().any(|rename| rename.old_name == "m1"));
+        assert!(renames
+            .iter()
+            .any(|rename| rename.kind == RenameKind::Metric
+                && rename.old_name == "process.runtime.jvm.cpu.utilization"
+                && rename.new_name == "process.runtime.jvm.cpu.recent_utilization"));
+
+        assert!(versions
+            .renames_since(versions.latest_version().unwrap())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_renames_by_version() {
+        let versions: Versions = Versions::load_from_file("data/parent_versions.yaml").unwrap();
+        let renames = versions.renames_by_version();
+
+        let rename = |old_name: &str| {
+            renames
+                .iter()
+                .find(|rename| rename.kind == RenameKind::Attribute && rename.old_name == old_name)
+                .map(|rename| (rename.new_name.as_str(), rename.version.to_string()))
+        };
+        // Successive renames are not chained.
+        assert_eq!(
+            rename("messaging.protocol"),
+            Some(("net.app.protocol.name", "1.17.0".to_owned()))
+        );
+        assert_eq!(
+            rename("net.app.protocol.name"),
+            Some(("net.protocol.name", "1.20.0".to_owned()))
+        );
+        assert_eq!(
+            rename("net.protocol.name"),
+            Some(("network.protocol.name", "1.21.0".to_owned()))
+        );
+        // Ordered by version.
+        assert!(renames
+            .windows(2)
+            .all(|pair| pair[0].version <= pair[1].version));
+    }
+
     #[test]
     fn test_override() {
         let parent_versions = Versions::load_from_file("data/parent_versions.yaml").unwrap();
@@ -763,4 +1175,239 @@ mod tests {
         assert_eq!("metric_1", changes.get_metric_name("m1"));
         assert_eq!("metric2", changes.get_metric_name("m2"));
     }
+
+    /// Checks the renames of the `all` sections of a schema file published by
+    /// the semantic conventions. Marked as ignore because it fetches the file
+    /// from opentelemetry.io.
+    #[test]
+    #[ignore]
+    fn test_published_all_section() {
+        let url = "https://opentelemetry.io/schemas/1.27.0";
+        let content = weaver_net::client()
+            .unwrap()
+            .get(url)
+            .unwrap()
+            .into_string()
+            .unwrap();
+        let versions = Versions::load_from_str(&content, url).unwrap();
+
+        let mut renames = 0;
+        for (version, spec) in versions.versions_asc() {
+            let Some(all) = &spec.all else {
+                continue;
+            };
+            let changes = versions.version_changes_for(version);
+            let renamed_by_signal = |old_name: &str| {
+                spec.resources.iter().any(|resources| {
+                    resources.changes.iter().any(|change| {
+                        change
+                            .rename_attributes
+                            .attribute_map
+                            .contains_key(old_name)
+                    })
+                }) || spec.metrics.iter().any(|metrics| {
+                    metrics.changes.iter().any(|change| {
+                        change
+                            .rename_attributes
+                            .attribute_map
+                            .contains_key(old_name)
+                    })
+                }) || spec.logs.iter().any(|logs| {
+                    logs.changes.iter().any(|change| {
+                        change
+                            .rename_attributes
+                            .attribute_map
+                            .contains_key(old_name)
+                    })
+                }) || spec.spans.iter().any(|spans| {
+                    spans.changes.iter().any(|change| {
+                        change
+                            .rename_attributes
+                            .attribute_map
+                            .contains_key(old_name)
+                    })
+                })
+            };
+            for change in all.changes.iter() {
+                for (old_name, new_name) in change.rename_attributes.attribute_map.iter() {
+                    if renamed_by_signal(old_name) {
+                        continue;
+                    }
+                    // Renames of the `all` section apply to all the signals.
+                    assert_eq!(new_name, &changes.get_resource_attribute_name(old_name));
+                    assert_eq!(new_name, &changes.get_metric_attribute_name(old_name));
+                    assert_eq!(new_name, &changes.get_log_attribute_name(old_name));
+                    assert_eq!(new_name, &changes.get_span_attribute_name(old_name));
+                    renames += 1;
+                }
+            }
+        }
+        assert!(renames > 0, "no `all` section in {}", url);
+    }
+
+    #[test]
+    fn test_all_version_changes_for() {
+        let versions: Versions = Versions::load_from_file("data/all_versions.yaml").unwrap();
+        assert_eq!(versions.transformation_count(), 11);
+
+        let changes = versions.version_changes_for(versions.latest_version().unwrap());
+        // Renames of the `all` section apply to all the signals
+        assert_eq!(
+            "db.namespace",
+            changes.get_resource_attribute_name("db.name")
+        );
+        assert_eq!("db.namespace", changes.get_metric_attribute_name("db.name"));
+        assert_eq!("db.namespace", changes.get_log_attribute_name("db.name"));
+        assert_eq!("db.namespace", changes.get_span_attribute_name("db.name"));
+        assert_eq!(
+            "db.collection.name",
+            changes.get_log_attribute_name("db.mongodb.collection")
+        );
+        assert_eq!(
+            "messaging.client.id",
+            changes.get_metric_attribute_name("messaging.client_id")
+        );
+        // Renames of the signal sections are still applied
+        assert_eq!(
+            "messaging.client_id",
+            changes.get_span_attribute_name("messaging.kafka.client_id")
+        );
+        assert_eq!(
+            "messaging.kafka.client_id",
+            changes.get_log_attribute_name("messaging.kafka.client_id")
+        );
+        assert_eq!(
+            "jvm.memory.used",
+            changes.get_metric_name("jvm.memory.usage")
+        );
+
+        // Renames introduced by the later versions are not applied
+        let changes = versions.version_changes_for(&semver::Version::parse("1.25.0").unwrap());
+        assert_eq!("db.name", changes.get_span_attribute_name("db.name"));
+        assert_eq!(
+            "messaging.client.id",
+            changes.get_span_attribute_name("messaging.client_id")
+        );
+
+        let renames = versions.renames_since(&semver::Version::parse("1.20.0").unwrap());
+        let rename = |old_name: &str| {
+            renames
+                .iter()
+                .find(|rename| rename.kind == RenameKind::Attribute && rename.old_name == old_name)
+                .map(|rename| (rename.new_name.as_str(), rename.version.to_string()))
+        };
+        // Renamed in 1.21.0 (spans) and then 1.25.0 (all signals).
+        assert_eq!(
+            rename("messaging.kafka.client_id"),
+            Some(("messaging.client.id", "1.25.0".to_owned()))
+        );
+        assert_eq!(
+            rename("db.statement"),
+            Some(("db.query.text", "1.26.0".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_all_override() {
+        let parent_versions = Versions::load_from_file("data/all_versions.yaml").unwrap();
+        let mut app_versions = Versions::load_from_file("data/app_all_versions.yaml").unwrap();
+
+        // Update `app_version` to extend `parent_versions`
+        app_versions.extend(parent_versions);
+        let changes = app_versions.version_changes_for(app_versions.latest_version().unwrap());
+
+        // The local `all` section overrides the parent `all` section
+        assert_eq!(
+            "db.query",
+            changes.get_resource_attribute_name("db.statement")
+        );
+        assert_eq!("db.query", changes.get_span_attribute_name("db.statement"));
+        // The local spans section overrides the parent `all` section for the
+        // spans only
+        assert_eq!("database.name", changes.get_span_attribute_name("db.name"));
+        assert_eq!("db.namespace", changes.get_log_attribute_name("db.name"));
+        // Transformations of the parent `all` section not overridden
+        assert_eq!(
+            "db.collection.name",
+            changes.get_metric_attribute_name("db.sql.table")
+        );
+        assert_eq!(
+            "messaging.client.id",
+            changes.get_span_attribute_name("messaging.client_id")
+        );
+        assert_eq!(
+            "jvm.memory.used",
+            changes.get_metric_name("jvm.memory.usage")
+        );
+
+        // The local `all` section overrides the parent spans section
+        let changes = app_versions.version_changes_for(&semver::Version::parse("1.21.0").unwrap());
+        assert_eq!(
+            "messaging.kafka.client.id",
+            changes.get_span_attribute_name("messaging.kafka.client_id")
+        );
+        assert_eq!(
+            "messaging.client_id",
+            changes.get_span_attribute_name("messaging.rocketmq.client_id")
+        );
+    }
+
+    #[test]
+    fn test_history_divergences() {
+        let published = Versions::load_from_str(
+            r#"file_format: 1.1.0
+schema_url: https://example.com/schemas/1.1.0
+versions:
+  1.1.0:
+    metrics:
+      changes:
+        - rename_metrics:
+            http.server.duration: http.server.request.duration
+  1.0.0:
+"#,
+            "https://example.com/schemas/1.1.0",
+        )
+        .unwrap();
+
+        // New versions appended after the latest published version.
+        let local: Versions = serde_yaml::from_str(
+            r#"
+1.2.0:
+  spans:
+    changes:
+      - rename_attributes:
+          attribute_map:
+            http.method: http.request.method
+1.1.0:
+  metrics:
+    changes:
+      - rename_metrics:
+          http.server.duration: http.server.request.duration
+1.0.0:
+"#,
+        )
+        .unwrap();
+        assert!(local.history_divergences(&published).is_empty());
+
+        // Rewritten history.
+        let local: Versions = serde_yaml::from_str(
+            r#"
+1.1.0:
+  metrics:
+    changes:
+      - rename_metrics:
+          http.server.duration: http.server.latency
+1.0.1:
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            local.history_divergences(&published),
+            vec![
+                HistoryDivergence::MissingVersion(semver::Version::new(1, 0, 0)),
+                HistoryDivergence::ModifiedVersion(semver::Version::new(1, 1, 0)),
+                HistoryDivergence::InsertedVersion(semver::Version::new(1, 0, 1)),
+            ]
+        );
+    }
 }
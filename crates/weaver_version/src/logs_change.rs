@@ -2,11 +2,12 @@
 
 //! Changes to apply to the logs for a specific version.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Changes to apply to the logs for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct LogsChange {
     /// A collection of rename operations to apply to the log attributes.
@@ -14,7 +15,7 @@ pub struct LogsChange {
 }
 
 /// A collection of rename operations to apply to the log attributes.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RenameAttributes {
     /// A collection of rename operations to apply to the log attributes.
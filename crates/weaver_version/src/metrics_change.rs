@@ -2,11 +2,12 @@
 
 //! Metrics change definitions.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Changes to apply to the metrics for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct MetricsChange {
     /// A collection of rename operations to apply to the metric attributes.
@@ -18,8 +19,9 @@ pub struct MetricsChange {
 }
 
 /// A collection of rename operations to apply to the metric attributes.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
+#[schemars(rename = "MetricsRenameAttributes")]
 pub struct RenameAttributes {
     /// A collection of rename operations to apply to the metric attributes.
     pub attribute_map: HashMap<String, String>,
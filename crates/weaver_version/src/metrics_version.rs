@@ -3,10 +3,11 @@
 //! Metrics version.
 
 use crate::metrics_change::MetricsChange;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Changes to apply to the metrics for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct MetricsVersion {
     /// Changes to apply to the metrics for a specific version.
@@ -2,11 +2,12 @@
 
 //! Changes to apply to the resources for a specific version.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Changes to apply to the resources for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceChange {
     /// Changes to apply to the resource attributes for a specific version.
@@ -14,7 +15,7 @@ pub struct ResourceChange {
 }
 
 /// Changes to apply to the resource attributes for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RenameAttributes {
     /// A collection of rename operations to apply to the resource attributes.
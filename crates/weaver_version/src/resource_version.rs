@@ -3,10 +3,11 @@
 //! Resource version.
 
 use crate::resource_change::ResourceChange;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Changes to apply to the resource for a specific version.
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceVersion {
     /// Changes to apply to the resource for a specific version.
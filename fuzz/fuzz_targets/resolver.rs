@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured fuzzer of the resolver: builds random registries of groups
+//! referencing each other (attribute refs, `extends`, constraint includes,
+//! possibly dangling or cyclic) and resolves them.
+
+#![no_main]
+
+use libfuzzer_sys::arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use validator::Validate;
+
+use weaver_logger::NullLogger;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::{ResolverConfig, SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs};
+
+/// The group types, only the types that the resolver implements.
+const GROUP_TYPES: [&str; 5] = ["attribute_group", "span", "event", "metric", "resource"];
+
+/// A random registry made of a small number of groups and attributes, so
+/// the references often hit an existing group or attribute.
+#[derive(Arbitrary, Debug)]
+struct Registry {
+    groups: Vec<Group>,
+}
+
+#[derive(Arbitrary, Debug)]
+struct Group {
+    r#type: u8,
+    extends: Option<u8>,
+    attributes: Vec<Attribute>,
+    includes: Vec<u8>,
+    any_of: Vec<(u8, u8)>,
+}
+
+#[derive(Arbitrary, Debug)]
+enum Attribute {
+    /// An attribute defined by the group.
+    Id(u8),
+    /// A reference to an attribute (group, attribute).
+    Ref(u8, u8),
+}
+
+/// Returns the id of a group.
+fn group_id(index: u8) -> String {
+    format!("group.g{}", index % 8)
+}
+
+/// Returns the id of an attribute, the prefix of a group being its index.
+fn attribute_id(group: u8, attribute: u8) -> String {
+    format!("g{}.a{}", group % 8, attribute % 4)
+}
+
+impl Registry {
+    /// Returns the registry as a semantic convention YAML file.
+    fn to_yaml(&self) -> String {
+        let mut yaml = String::from("groups:\n");
+        for (index, group) in self.groups.iter().take(8).enumerate() {
+            let index = index as u8;
+            let r#type = GROUP_TYPES[group.r#type as usize % GROUP_TYPES.len()];
+            yaml.push_str(&format!(
+                "  - id: {}\n    type: {}\n    brief: fuzz\n    prefix: g{}\n",
+                group_id(index),
+                r#type,
+                index
+            ));
+            if r#type == "metric" {
+                yaml.push_str(&format!(
+                    "    metric_name: metric.g{}\n    instrument: counter\n    unit: \"1\"\n",
+                    index
+                ));
+            }
+            if let Some(extends) = group.extends {
+                yaml.push_str(&format!("    extends: {}\n", group_id(extends)));
+            }
+            if !group.attributes.is_empty() {
+                yaml.push_str("    attributes:\n");
+                for attribute in group.attributes.iter().take(8) {
+                    match attribute {
+                        Attribute::Id(id) => yaml.push_str(&format!(
+                            "      - id: a{}\n        type: int\n        brief: fuzz\n",
+                            id % 4
+                        )),
+                        Attribute::Ref(group, id) => {
+                            yaml.push_str(&format!("      - ref: {}\n", attribute_id(*group, *id)))
+                        }
+                    }
+                }
+            }
+            if !group.includes.is_empty() || !group.any_of.is_empty() {
+                yaml.push_str("    constraints:\n");
+                for include in group.includes.iter().take(4) {
+                    yaml.push_str(&format!("      - include: {}\n", group_id(*include)));
+                }
+                for (group, id) in group.any_of.iter().take(4) {
+                    yaml.push_str(&format!(
+                        "      - any_of: [{}]\n",
+                        attribute_id(*group, *id)
+                    ));
+                }
+            }
+        }
+        yaml
+    }
+}
+
+fuzz_target!(|registry: Registry| {
+    let Ok(spec) = serde_yaml::from_str::<SemConvSpec>(&registry.to_yaml()) else {
+        return;
+    };
+    if spec.validate().is_err() {
+        return;
+    }
+    let mut registry = SemConvSpecs::default();
+    registry.append_sem_conv_spec(SemConvSpecWithProvenance {
+        spec,
+        provenance: "fuzz.yaml".to_owned(),
+    });
+    if registry.resolve(ResolverConfig::with_keep_specs()).is_err() {
+        return;
+    }
+    _ = SchemaResolver::resolve_semantic_convention_registry(&mut registry, NullLogger::new());
+});
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzzes the deserialization, the validation and the resolution of a
+//! semantic convention file.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use validator::Validate;
+
+use weaver_semconv::{ResolverConfig, SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(spec) = serde_yaml::from_slice::<SemConvSpec>(data) else {
+        return;
+    };
+    if spec.validate().is_err() {
+        return;
+    }
+    let mut registry = SemConvSpecs::default();
+    registry.append_sem_conv_spec(SemConvSpecWithProvenance {
+        spec,
+        provenance: "fuzz.yaml".to_owned(),
+    });
+    _ = registry.resolve(ResolverConfig::with_keep_specs());
+});
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzzes the deserialization of a telemetry schema.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use weaver_schema::TelemetrySchema;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut schema) = serde_yaml::from_slice::<TelemetrySchema>(data) else {
+        return;
+    };
+    _ = schema.schema_version();
+    _ = schema.metrics();
+    _ = schema.events();
+    _ = schema.spans();
+    schema.merge_versions();
+});
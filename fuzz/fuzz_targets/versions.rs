@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzzes the deserialization of the `versions` section of a telemetry
+//! schema and the computation of the changes up to its latest version.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use serde::Deserialize;
+
+use weaver_version::Versions;
+
+/// The top-level structure of a versions file (or of a telemetry schema).
+#[derive(Deserialize)]
+struct TopLevel {
+    versions: Versions,
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(TopLevel { versions }) = serde_yaml::from_slice::<TopLevel>(data) else {
+        return;
+    };
+    if let Some(latest_version) = versions.latest_version() {
+        _ = versions.version_changes_for(latest_version);
+    }
+});
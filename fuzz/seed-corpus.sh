@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# SPDX-License-Identifier: Apache-2.0
+#
+# Seeds the corpus of the fuzz targets with the YAML files of the data
+# directories of the repository. Run from the root of the repository:
+#   ./fuzz/seed-corpus.sh && cargo +nightly fuzz run semconv_spec
+
+set -euo pipefail
+
+cd "$(dirname "$0")"
+
+seed() {
+    local target=$1
+    shift
+    mkdir -p "corpus/$target"
+    for file in "$@"; do
+        cp "$file" "corpus/$target/$(echo "$file" | tr '/' '_')"
+    done
+}
+
+seed semconv_spec ../crates/weaver_semconv/data/*.yaml ../crates/weaver_resolver/data/*/registry/*.yaml
+seed telemetry_schema ../data/app-telemetry-schema*.yaml ../data/open-telemetry-schema*.yaml ../demo/app-telemetry-schema.yaml
+seed versions ../crates/weaver_version/data/*.yaml
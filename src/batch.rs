@@ -0,0 +1,492 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to generate the client SDKs of several telemetry schemas listed in
+//! a manifest file.
+//!
+//! The semantic convention registries are resolved once per distinct set of
+//! imports (and resolver configuration) and shared by all the schemas
+//! importing them. The client SDKs are then generated in parallel and a
+//! consolidated report is displayed (and optionally written as JSON).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::time::{Duration, Instant};
+
+use clap::Args;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use serde::{Deserialize, Serialize};
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::TelemetrySchema;
+use weaver_semconv::SemConvSpecs;
+use weaver_template::guard::RenderLimits;
+use weaver_template::sdkgen::ClientSdkGenerator;
+use weaver_template::GeneratorConfig;
+
+use crate::cli::{CacheArgs, DeprecatedArg, StabilityArg};
+use crate::exit_code::{ExitCode, FAILURE};
+
+/// Parameters for the `batch` command
+#[derive(Args)]
+pub struct BatchCommand {
+    /// Manifest file listing the schemas to generate client SDKs for
+    #[arg(short, long, value_name = "FILE")]
+    pub manifest: PathBuf,
+
+    /// File where the consolidated report is written (JSON)
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+}
+
+/// A batch manifest.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct BatchManifest {
+    /// The schemas to generate client SDKs for.
+    targets: Vec<BatchTarget>,
+}
+
+/// A schema to generate client SDKs for.
+/// The relative paths are relative to the manifest file.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct BatchTarget {
+    /// The telemetry schema file.
+    schema: PathBuf,
+    /// The languages of the client SDKs to generate.
+    languages: Vec<String>,
+    /// The output directory, the client SDK of each language being generated
+    /// in a sub-directory named after the language.
+    output_dir: PathBuf,
+    /// The generation parameters (see the `gen-client` command).
+    #[serde(default)]
+    params: BatchParams,
+}
+
+/// The generation parameters of a target, with the same defaults as the
+/// options of the `gen-client` command.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+struct BatchParams {
+    /// Maximum time (in seconds) to render a template.
+    max_render_time: u64,
+    /// Maximum size (in bytes) of the code generated by a template.
+    max_output_size: usize,
+    /// Maximum number of steps (filter, function and tester calls, writes of
+    /// rendered text) while rendering a template.
+    max_iterations: usize,
+    /// Whether the test scaffold of the language is generated.
+    with_tests: bool,
+    /// Number of versions preceding the latest version of the schema for
+    /// which compatibility modules are generated.
+    compat_versions: usize,
+    /// Minimum stability of the attributes used to generate the client SDK.
+    stability: StabilityArg,
+    /// Whether the YAML extensions supported by the Python build-tools are
+    /// accepted.
+    build_tools_compat: bool,
+    /// How the deprecated attributes and groups appear in the generated code
+    /// (the policy of the language config if `None`).
+    deprecated: Option<DeprecatedArg>,
+    /// Directory of local templates layered over the installed language
+    /// packs.
+    template_dir: Option<PathBuf>,
+}
+
+impl Default for BatchParams {
+    fn default() -> Self {
+        Self {
+            max_render_time: 30,
+            max_output_size: 10 * 1024 * 1024,
+            max_iterations: 10_000_000,
+            with_tests: false,
+            compat_versions: 0,
+            stability: StabilityArg::All,
+            build_tools_compat: false,
+            deprecated: None,
+            template_dir: None,
+        }
+    }
+}
+
+/// The result of the generation of a client SDK, as listed in the report.
+#[derive(Serialize, Debug)]
+struct GenerationReport {
+    /// The telemetry schema file.
+    schema: PathBuf,
+    /// The language of the client SDK.
+    language: String,
+    /// The directory of the generated client SDK.
+    output_dir: PathBuf,
+    /// The error that occurred (if any).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// The generation time in seconds (excluding the resolution).
+    duration: f32,
+}
+
+/// The consolidated report of a batch.
+#[derive(Serialize, Debug)]
+struct BatchReport {
+    /// The number of distinct semantic convention registries resolved.
+    registries: usize,
+    /// The number of client SDKs generated.
+    generated: usize,
+    /// The number of client SDKs whose generation failed.
+    failed: usize,
+    /// The result of each generation, in the order of the manifest.
+    generations: Vec<GenerationReport>,
+}
+
+/// A client SDK to generate from a resolved schema.
+struct Generation<'a> {
+    generator: ClientSdkGenerator,
+    schema: &'a TelemetrySchema,
+    schema_path: PathBuf,
+    output_dir: PathBuf,
+}
+
+/// Generate the client SDKs of the schemas listed in a manifest
+pub fn command_batch(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &BatchCommand,
+) {
+    let manifest: BatchManifest = std::fs::File::open(&command.manifest)
+        .map_err(|e| e.to_string())
+        .and_then(|file| serde_yaml::from_reader(file).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            log.error(&format!(
+                "Invalid batch manifest `{}`: {}",
+                command.manifest.display(),
+                e
+            ));
+            exit(FAILURE);
+        });
+    let manifest_dir = command
+        .manifest
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let cache = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+
+    let report = run_batch(log.clone(), cache_args, &cache, &manifest, manifest_dir);
+    print_report(&log, &report);
+    if let Some(report_path) = &command.report {
+        let written = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(report_path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            log.error(&format!(
+                "Failed to write the report `{}`: {}",
+                report_path.display(),
+                e
+            ));
+            exit(FAILURE);
+        }
+    }
+    if report.failed > 0 {
+        exit(FAILURE);
+    }
+}
+
+/// Resolves the schemas of the given manifest and generates their client
+/// SDKs. The failure of a generation doesn't stop the other ones, it is listed
+/// in the returned report.
+fn run_batch(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    cache: &Cache,
+    manifest: &BatchManifest,
+    manifest_dir: &Path,
+) -> BatchReport {
+    // Resolve the schemas, sharing the registries, then list the client SDKs
+    // to generate (or the reason why they can't be generated).
+    let mut registries: HashMap<String, SemConvSpecs> = HashMap::new();
+    let mut schemas: Vec<TelemetrySchema> = vec![];
+    let mut pending = vec![];
+    let mut failures: HashMap<(usize, usize), String> = HashMap::new();
+    for (target_index, target) in manifest.targets.iter().enumerate() {
+        let schema_path = manifest_dir.join(&target.schema);
+        let mut generators = vec![];
+        for (language_index, language) in target.languages.iter().enumerate() {
+            match ClientSdkGenerator::try_new(
+                language,
+                generator_config(cache_args, manifest_dir, &target.params),
+            ) {
+                Ok(generator) => generators.push((language_index, generator)),
+                Err(e) => _ = failures.insert((target_index, language_index), e.to_string()),
+            }
+        }
+        let Some((_, generator)) = generators.first() else {
+            continue;
+        };
+
+        match resolve_schema(&log, cache, &mut registries, &schema_path, generator) {
+            Ok(schema) => {
+                for (language_index, generator) in generators {
+                    pending.push((target_index, language_index, schemas.len(), generator));
+                }
+                schemas.push(schema);
+            }
+            Err(e) => {
+                for (language_index, _) in generators {
+                    _ = failures.insert((target_index, language_index), e.clone());
+                }
+            }
+        }
+    }
+
+    let generations: Vec<_> = pending
+        .into_iter()
+        .map(|(target_index, language_index, schema_index, generator)| {
+            let target = &manifest.targets[target_index];
+            let language = &target.languages[language_index];
+            (
+                (target_index, language_index),
+                Generation {
+                    generator,
+                    schema: &schemas[schema_index],
+                    schema_path: manifest_dir.join(&target.schema),
+                    output_dir: manifest_dir.join(&target.output_dir).join(language),
+                },
+            )
+        })
+        .collect();
+
+    // Generate all the client SDKs in parallel.
+    let results: HashMap<(usize, usize), (Option<String>, Duration)> = generations
+        .into_par_iter()
+        .map(|(key, generation)| {
+            let start = Instant::now();
+            let result = generation
+                .generator
+                .generate_from_schema(
+                    log.clone(),
+                    generation.schema,
+                    generation.schema_path,
+                    generation.output_dir,
+                )
+                .err()
+                .map(|e| e.to_string());
+            (key, (result, start.elapsed()))
+        })
+        .collect();
+
+    let mut report = BatchReport {
+        registries: registries.len(),
+        generated: 0,
+        failed: 0,
+        generations: vec![],
+    };
+    for (target_index, target) in manifest.targets.iter().enumerate() {
+        for (language_index, language) in target.languages.iter().enumerate() {
+            let key = (target_index, language_index);
+            let (error, duration) = match (failures.remove(&key), results.get(&key)) {
+                (Some(error), _) => (Some(error), Duration::ZERO),
+                (None, Some((error, duration))) => (error.clone(), *duration),
+                (None, None) => (Some("not generated".to_owned()), Duration::ZERO),
+            };
+            if error.is_some() {
+                report.failed += 1;
+            } else {
+                report.generated += 1;
+            }
+            report.generations.push(GenerationReport {
+                schema: manifest_dir.join(&target.schema),
+                language: language.clone(),
+                output_dir: manifest_dir.join(&target.output_dir).join(language),
+                error,
+                duration: duration.as_secs_f32(),
+            });
+        }
+    }
+    report
+}
+
+/// Builds the configuration of the generators of a target.
+fn generator_config(
+    cache_args: &CacheArgs,
+    manifest_dir: &Path,
+    params: &BatchParams,
+) -> GeneratorConfig {
+    GeneratorConfig::default()
+        .with_override_template_dir(
+            params
+                .template_dir
+                .as_ref()
+                .map(|dir| manifest_dir.join(dir)),
+        )
+        .with_cache_dir(cache_args.cache_dir.clone())
+        .with_refresh_cache(cache_args.refresh)
+        .with_render_limits(RenderLimits {
+            max_render_time: Duration::from_secs(params.max_render_time),
+            max_output_size: params.max_output_size,
+            max_iterations: params.max_iterations,
+        })
+        .with_tests(params.with_tests)
+        .with_compat_versions(params.compat_versions)
+        .with_stability_filter(params.stability.into())
+        .with_build_tools_compat(params.build_tools_compat)
+        .with_deprecated_policy(params.deprecated.map(Into::into))
+}
+
+/// Loads and resolves a schema with the configuration of the given generator.
+/// The registry imported by the schema is only resolved if no previous schema
+/// imported the same registry with the same configuration.
+fn resolve_schema(
+    log: &(impl Logger + Sync + Clone),
+    cache: &Cache,
+    registries: &mut HashMap<String, SemConvSpecs>,
+    schema_path: &Path,
+    generator: &ClientSdkGenerator,
+) -> Result<TelemetrySchema, String> {
+    let mut schema = SchemaResolver::load_schema_from_path(schema_path, log.clone())
+        .map_err(|e| e.to_string())?;
+    let imports = schema.merged_semantic_conventions();
+    // The parent schemas loaded from resolved schema artifacts are identified
+    // by their provenance.
+    let resolved_parents: Vec<String> = schema
+        .merged_resolved_semantic_conventions()
+        .into_iter()
+        .map(|spec| spec.provenance)
+        .collect();
+    let resolver_config = generator.resolver_config();
+    let key = format!(
+        "{}{:?}{:?}",
+        serde_yaml::to_string(&imports).map_err(|e| e.to_string())?,
+        resolved_parents,
+        resolver_config
+    );
+    let registry = match registries.get(&key) {
+        Some(registry) => registry.clone(),
+        None => {
+            let registry = SchemaResolver::semconv_registry_from_schema_with_config(
+                &schema,
+                resolver_config,
+                cache,
+                log.clone(),
+            )
+            .map_err(|e| e.to_string())?;
+            _ = registries.insert(key, registry.clone());
+            registry
+        }
+    };
+    SchemaResolver::resolve_schema_with_registry(
+        &mut schema,
+        &schema_path.display().to_string(),
+        registry,
+        log.clone(),
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(schema)
+}
+
+/// Displays the consolidated report.
+fn print_report(log: &impl Logger, report: &BatchReport) {
+    for generation in report.generations.iter() {
+        match &generation.error {
+            None => log.success(&format!(
+                "{} ({}) -> {} ({:.2}s)",
+                generation.schema.display(),
+                generation.language,
+                generation.output_dir.display(),
+                generation.duration
+            )),
+            Some(error) => log.error(&format!(
+                "{} ({}): {}",
+                generation.schema.display(),
+                generation.language,
+                error
+            )),
+        };
+    }
+    let summary = format!(
+        "{} client SDK(s) generated, {} failed ({} registr{} resolved)",
+        report.generated,
+        report.failed,
+        report.registries,
+        if report.registries == 1 { "y" } else { "ies" }
+    );
+    if report.failed > 0 {
+        log.error(&summary);
+    } else {
+        log.success(&summary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempdir::TempDir;
+    use weaver_logger::NullLogger;
+
+    use super::*;
+
+    #[test]
+    fn test_run_batch() {
+        let dir = TempDir::new("weaver-batch").unwrap();
+        std::fs::write(
+            dir.path().join("schema.yaml"),
+            r#"file_format: 1.2.0
+schema_url: https://example.com/schemas/1.0.0
+schema:
+  instrumentation_library:
+    name: checkout
+    version: 1.0.0
+  resource:
+    attributes:
+      - id: service.name
+        type: string
+        brief: The name of the service.
+        examples: ["checkout"]
+"#,
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.path().join("broken/go")).unwrap();
+        std::fs::write(
+            dir.path().join("broken/go/readme.tera"),
+            "{{ unknown.field }}",
+        )
+        .unwrap();
+        let manifest: BatchManifest = serde_yaml::from_str(
+            r#"targets:
+  - schema: schema.yaml
+    languages: [go]
+    output_dir: broken
+    params:
+      template_dir: broken
+  - schema: schema.yaml
+    languages: [go]
+    output_dir: valid
+"#,
+        )
+        .unwrap();
+        let cache_args = CacheArgs {
+            cache_dir: Some(dir.path().join("cache")),
+            refresh: false,
+        };
+        let cache = cache_args.cache().unwrap();
+
+        // A template failing to render fails its own generation only.
+        let report = run_batch(
+            NullLogger::new(),
+            &cache_args,
+            &cache,
+            &manifest,
+            dir.path(),
+        );
+        assert_eq!(report.generated, 1);
+        assert_eq!(report.failed, 1);
+        let error = report.generations[0].error.as_deref().unwrap();
+        assert!(error.contains("readme.tera"), "{}", error);
+        assert!(report.generations[1].error.is_none());
+        assert!(dir.path().join("valid/go/README.md").exists());
+    }
+}
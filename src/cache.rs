@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to manage the cache directory.
+
+use clap::{Args, Subcommand};
+use std::process::exit;
+use weaver_cache::verify::EntryStatus;
+use weaver_logger::Logger;
+
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE};
+
+/// Specify the `cache` command
+#[derive(Args)]
+pub struct CacheCommand {
+    /// Define the sub-commands for the `cache` command
+    #[clap(subcommand)]
+    pub command: CacheSubCommand,
+}
+
+/// Sub-commands for the `cache` command
+#[derive(Subcommand)]
+pub enum CacheSubCommand {
+    /// Check the integrity of the git repos and lock files of the cache
+    Verify(CacheVerify),
+}
+
+/// Parameters for the `cache verify` sub-command
+#[derive(Args)]
+pub struct CacheVerify {
+    /// Repair the corrupt entries (by cloning them again) and remove the
+    /// orphaned ones
+    #[arg(long)]
+    pub repair: bool,
+}
+
+/// Manage the cache directory
+pub fn command_cache(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &CacheCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+
+    match command.command {
+        CacheSubCommand::Verify(ref command) => {
+            let entries = cache.verify();
+            let mut problems = 0;
+
+            for entry in entries.iter() {
+                let path = entry.path.display();
+                let message = match &entry.status {
+                    EntryStatus::Valid => continue,
+                    EntryStatus::Corrupt { message } => format!("Corrupt `{}`: {}", path, message),
+                    EntryStatus::Orphaned { message } => {
+                        format!("Orphaned `{}`: {}", path, message)
+                    }
+                };
+                if !command.repair {
+                    log.warn(&message);
+                    problems += 1;
+                    continue;
+                }
+                match cache.repair(entry) {
+                    Ok(_) => {
+                        log.success(&format!("{} (repaired)", message));
+                    }
+                    Err(e) => {
+                        log.error(&format!("{} ({})", message, e));
+                        problems += 1;
+                    }
+                }
+            }
+
+            if problems > 0 {
+                log.error(&format!(
+                    "{} problem(s) found in the cache `{}`",
+                    problems,
+                    cache.path().display()
+                ));
+                exit(FAILURE);
+            }
+            log.success(&format!(
+                "Cache `{}` verified ({} entries)",
+                cache.path().display(),
+                entries.len()
+            ));
+        }
+    }
+}
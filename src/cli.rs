@@ -2,11 +2,31 @@
 
 //! Manage command line arguments
 
+use crate::batch::BatchCommand;
+use crate::cache::CacheCommand;
+use crate::completions::CompletionsCommand;
+use crate::conformance::ConformanceCommand;
+use crate::export::ExportCommand;
 use crate::gen_client::GenClientCommand;
+use crate::gen_diff::GenCommand;
 use crate::languages::LanguagesParams;
-use crate::resolve::ResolveCommand;
+use crate::lsp::LspCommand;
+use crate::registry::{RegistryCommand, RegistrySubCommand};
+use crate::resolve::{ResolveCommand, ResolveSubCommand};
+use crate::schema::SchemaCommand;
 use crate::search::SearchCommand;
-use clap::{Parser, Subcommand};
+use crate::serve::ServeCommand;
+use crate::template::TemplateCommand;
+use crate::version::VersionCommand;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+use std::io::IsTerminal;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use weaver_cache::Cache;
+use weaver_net::NetworkConfig;
+use weaver_semconv::stability::StabilityFilter;
+use weaver_template::deprecated::DeprecatedPolicy;
 
 /// Command line arguments.
 #[derive(Parser)]
@@ -16,6 +36,23 @@ pub struct Cli {
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub debug: u8,
 
+    /// When to use colors in the console output (`auto` disables them when
+    /// `NO_COLOR` is set or when the output is not a terminal)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorArg::Auto)]
+    pub color: ColorArg,
+
+    /// Cache options
+    #[command(flatten)]
+    pub cache: CacheArgs,
+
+    /// Network options
+    #[command(flatten)]
+    pub network: NetworkArgs,
+
+    /// Concurrency options
+    #[command(flatten)]
+    pub concurrency: ConcurrencyArgs,
+
     /// List of supported commands
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -28,8 +65,298 @@ pub enum Commands {
     Resolve(ResolveCommand),
     /// Generate a client SDK or client API
     GenClient(GenClientCommand),
+    /// Generate and compare client SDKs
+    Gen(GenCommand),
     /// List all supported languages
     Languages(LanguagesParams),
     /// Search in a semantic convention registry or a telemetry schema
     Search(SearchCommand),
+    /// Export a semantic convention registry or a telemetry schema into other
+    /// formats
+    Export(ExportCommand),
+    /// Manage a semantic convention registry
+    Registry(RegistryCommand),
+    /// Inspect a telemetry schema
+    Schema(SchemaCommand),
+    /// Check (and repair) the cache directory
+    Cache(CacheCommand),
+    /// Serve the resolved registries and schemas of several projects over HTTP
+    Serve(ServeCommand),
+    /// Start a language server (LSP over stdio) validating the semantic
+    /// convention files as they are edited
+    Lsp(LspCommand),
+    /// Generate the client SDKs of several telemetry schemas listed in a
+    /// manifest file
+    Batch(BatchCommand),
+    /// Manage the language packs (templates) used to generate the client SDKs
+    Template(TemplateCommand),
+    /// Run a conformance suite (counts, spot checks, references, output
+    /// determinism) against the official semantic convention registry
+    Conformance(ConformanceCommand),
+    /// Display the version of weaver (and its build information with
+    /// `--verbose`)
+    Version(VersionCommand),
+    /// Print the shell completion script of weaver (completing the attribute
+    /// ids, group ids and metric names of the last resolved registry)
+    Completions(CompletionsCommand),
+}
+
+impl Commands {
+    /// Returns the format of the timing breakdown requested with the
+    /// `--timings` option of the command, if any.
+    pub fn timings(&self) -> Option<TimingsFormat> {
+        let timings = match self {
+            Commands::Resolve(command) => match &command.command {
+                ResolveSubCommand::Registry(args) => &args.timings,
+                ResolveSubCommand::Schema(args) => &args.timings,
+            },
+            Commands::GenClient(command) => &command.timings,
+            Commands::Registry(command) => match &command.command {
+                RegistrySubCommand::Check(args) => &args.timings,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        timings.timings
+    }
+}
+
+/// Options controlling the cache used to store the fetched registries.
+#[derive(Args, Clone, Default)]
+pub struct CacheArgs {
+    /// Cache directory (defaults to `WEAVER_CACHE_DIR`, `$XDG_CACHE_HOME/otel-weaver`
+    /// or the platform-specific cache location)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Clone the git registries again instead of reusing the cached clones
+    #[arg(long, global = true)]
+    pub refresh: bool,
+}
+
+impl CacheArgs {
+    /// Creates the cache described by these options.
+    pub fn cache(&self) -> Result<Cache, weaver_cache::Error> {
+        Cache::try_new(self.cache_dir.clone()).map(|cache| cache.with_refresh(self.refresh))
+    }
+}
+
+/// Options controlling the proxy and the certificate authorities used by the
+/// network operations.
+#[derive(Args, Clone, Default)]
+pub struct NetworkArgs {
+    /// Network configuration file (defaults to `WEAVER_NETWORK_CONFIG`)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub network_config: Option<PathBuf>,
+
+    /// HTTP(S) proxy URL (defaults to `HTTPS_PROXY`, `ALL_PROXY` or `HTTP_PROXY`)
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// Comma-separated list of hosts reached without the proxy (defaults to
+    /// `NO_PROXY`)
+    #[arg(long, global = true, value_name = "HOSTS")]
+    pub no_proxy: Option<String>,
+
+    /// PEM file of the certificate authorities to trust in addition to the
+    /// built-in ones (defaults to `WEAVER_CA_BUNDLE` or `SSL_CERT_FILE`)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub ca_bundle: Option<PathBuf>,
+
+    /// Timeout of the connections to the servers, in seconds (defaults to 30)
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub connect_timeout: Option<u64>,
+
+    /// Timeout of each read from the servers, in seconds (defaults to 60)
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub read_timeout: Option<u64>,
+}
+
+impl NetworkArgs {
+    /// Builds the network configuration described by these options. The
+    /// options override the configuration file, which overrides the
+    /// environment variables.
+    pub fn network_config(&self) -> Result<NetworkConfig, weaver_net::Error> {
+        let mut config = NetworkConfig::from_env();
+        if let Some(config_file) = self.config_file() {
+            config = config.merge(NetworkConfig::from_file(config_file)?);
+        }
+        Ok(config.merge(NetworkConfig {
+            proxy: self.proxy.clone(),
+            no_proxy: self
+                .no_proxy
+                .as_deref()
+                .map(weaver_net::split_hosts)
+                .unwrap_or_default(),
+            ca_bundle: self.ca_bundle.clone(),
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+        }))
+    }
+
+    /// Returns the network configuration file, if any (the option or the
+    /// `WEAVER_NETWORK_CONFIG` environment variable).
+    pub fn config_file(&self) -> Option<PathBuf> {
+        self.network_config.clone().or_else(|| {
+            std::env::var_os(weaver_net::NETWORK_CONFIG_ENV_VAR)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from)
+        })
+    }
+}
+
+/// The environment variable limiting the number of threads of the CPU-bound
+/// work.
+pub const JOBS_ENV_VAR: &str = "WEAVER_JOBS";
+
+/// The environment variable limiting the number of threads loading the
+/// registries.
+pub const IO_JOBS_ENV_VAR: &str = "WEAVER_IO_JOBS";
+
+/// Options controlling the number of threads used by the commands, e.g. to
+/// keep weaver from saturating a CI runner shared with other jobs.
+#[derive(Args, Clone, Default)]
+pub struct ConcurrencyArgs {
+    /// Maximum number of threads of the CPU-bound work (resolution, code
+    /// generation, formatting), defaults to `WEAVER_JOBS` or the number of
+    /// CPUs
+    #[arg(long, global = true, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Maximum number of threads loading the registries (cloning, fetching
+    /// and parsing the files), defaults to `WEAVER_IO_JOBS` or the number of
+    /// threads of the CPU-bound work
+    #[arg(long, global = true, value_name = "N")]
+    pub io_jobs: Option<NonZeroUsize>,
+}
+
+impl ConcurrencyArgs {
+    /// Initializes the thread pools of the process. The options override the
+    /// environment variables; without option nor environment variable, the
+    /// pools keep their default size.
+    pub fn init(&self) -> Result<(), String> {
+        let jobs = Self::threads(self.jobs, JOBS_ENV_VAR)?;
+        let io_jobs = Self::threads(self.io_jobs, IO_JOBS_ENV_VAR)?.or(jobs);
+        if let Some(jobs) = jobs {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs)
+                .build_global()
+                .map_err(|e| format!("Failed to create the thread pool ({})", e))?;
+        }
+        if let Some(io_jobs) = io_jobs {
+            weaver_resolver::init_io_pool(io_jobs).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of threads given by an option or, by default, by
+    /// an environment variable (a positive integer).
+    fn threads(option: Option<NonZeroUsize>, env_var: &str) -> Result<Option<usize>, String> {
+        if let Some(threads) = option {
+            return Ok(Some(threads.get()));
+        }
+        match std::env::var(env_var) {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse::<NonZeroUsize>()
+                .map(|threads| Some(threads.get()))
+                .map_err(|_| {
+                    format!(
+                        "Invalid `{}` environment variable `{}` (a positive integer is expected)",
+                        env_var, value
+                    )
+                }),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Option printing the timing breakdown of a command.
+#[derive(Args, Clone, Default)]
+pub struct TimingsArgs {
+    /// Print at the end of the command the time spent in each phase (load,
+    /// clone, parse, resolve, render, write), as text (default) or JSON (on
+    /// stderr)
+    #[arg(long, value_enum, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "text")]
+    pub timings: Option<TimingsFormat>,
+}
+
+/// Formats of the timing breakdown.
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum TimingsFormat {
+    /// A human-readable table
+    Text,
+    /// A JSON document
+    Json,
+}
+
+/// When to use colors in the console output.
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum ColorArg {
+    /// Colors unless `NO_COLOR` is set or the output is not a terminal
+    Auto,
+    /// Always colors, even when `NO_COLOR` is set
+    Always,
+    /// Never colors
+    Never,
+}
+
+impl ColorArg {
+    /// Returns true if the console output is colored.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorArg::Auto => {
+                std::env::var_os("NO_COLOR").map_or(true, |no_color| no_color.is_empty())
+                    && std::io::stdout().is_terminal()
+            }
+            ColorArg::Always => true,
+            ColorArg::Never => false,
+        }
+    }
+}
+
+/// Minimum stability of the groups and attributes kept in the resolved
+/// registries and schemas.
+#[derive(Clone, Copy, ValueEnum, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityArg {
+    /// Only the stable definitions
+    Stable,
+    /// The stable and experimental definitions (deprecated ones are excluded)
+    Experimental,
+    /// All the definitions
+    All,
+}
+
+impl From<StabilityArg> for StabilityFilter {
+    fn from(stability: StabilityArg) -> Self {
+        match stability {
+            StabilityArg::Stable => StabilityFilter::Stable,
+            StabilityArg::Experimental => StabilityFilter::Experimental,
+            StabilityArg::All => StabilityFilter::All,
+        }
+    }
+}
+
+/// How the deprecated definitions appear in the generated code.
+#[derive(Clone, Copy, ValueEnum, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeprecatedArg {
+    /// Remove the deprecated definitions
+    Omit,
+    /// Keep the deprecated definitions with language-native deprecation
+    /// annotations
+    Annotate,
+    /// Keep the deprecated definitions without deprecation annotations
+    Include,
+}
+
+impl From<DeprecatedArg> for DeprecatedPolicy {
+    fn from(deprecated: DeprecatedArg) -> Self {
+        match deprecated {
+            DeprecatedArg::Omit => DeprecatedPolicy::Omit,
+            DeprecatedArg::Annotate => DeprecatedPolicy::Annotate,
+            DeprecatedArg::Include => DeprecatedPolicy::Include,
+        }
+    }
 }
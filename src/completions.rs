@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to generate the shell completion scripts of weaver.
+//!
+//! The scripts delegate the completion to weaver itself (hidden `--complete`
+//! option), which walks the command line definition to propose the
+//! sub-commands, the options and their values. The options and arguments
+//! taking an attribute id, a group id or a metric name (identified by their
+//! `ATTRIBUTE_ID`, `GROUP_ID` and `METRIC_NAME` value names) are completed
+//! with the names of the last resolved registry, recorded in a small index of
+//! the cache directory (`<cache>/completions.json`) by `resolve registry` and
+//! `registry check`.
+
+use std::fs;
+use std::process::exit;
+
+use clap::{Arg, ArgAction, Args, Command, CommandFactory, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_semconv::SemConvSpecs;
+
+use crate::cli::{CacheArgs, Cli};
+use crate::exit_code::FAILURE;
+
+/// The file of the cache containing the names of the last resolved registry.
+const INDEX_FILE: &str = "completions.json";
+
+/// Parameters for the `completions` command
+#[derive(Args)]
+pub struct CompletionsCommand {
+    /// Shell to generate the completion script for
+    #[arg(value_enum, required_unless_present = "complete")]
+    pub shell: Option<Shell>,
+
+    /// Print the candidates completing the word at the given index of the
+    /// command line following `--` (used by the completion scripts)
+    #[arg(long, value_name = "INDEX", hide = true)]
+    pub complete: Option<usize>,
+
+    /// The words of the command line to complete
+    #[arg(last = true, hide = true)]
+    pub words: Vec<String>,
+}
+
+/// Supported shells
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Shell {
+    /// Bash
+    Bash,
+    /// Zsh
+    Zsh,
+    /// Fish
+    Fish,
+}
+
+/// The names of the last resolved registry, completing the attribute ids,
+/// the group ids and the metric names.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CompletionIndex {
+    /// The ids of the attributes.
+    pub attributes: Vec<String>,
+    /// The ids of the groups.
+    pub groups: Vec<String>,
+    /// The names of the metrics.
+    pub metrics: Vec<String>,
+}
+
+impl CompletionIndex {
+    /// Creates the index of the names of a resolved registry.
+    pub fn from_registry(registry: &SemConvSpecs) -> Self {
+        let sorted = |mut names: Vec<String>| {
+            names.sort();
+            names.dedup();
+            names
+        };
+        Self {
+            attributes: sorted(registry.attributes_iter().map(|attr| attr.id()).collect()),
+            groups: sorted(registry.groups().map(|group| group.id.clone()).collect()),
+            metrics: sorted(
+                registry
+                    .metrics_iter()
+                    .map(|metric| metric.name.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Loads the index of the cache (empty if the index is missing or
+    /// invalid).
+    pub fn load(cache: &Cache) -> Self {
+        fs::read_to_string(cache.path().join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Saves the index in the cache. The index is written in a temporary
+    /// file then renamed, so a concurrent completion never reads a partial
+    /// index.
+    pub fn save(&self, cache: &Cache) -> Result<(), String> {
+        let path = cache.path().join(INDEX_FILE);
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        let content = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(&tmp_path, content)
+            .and_then(|_| fs::rename(&tmp_path, &path))
+            .map_err(|e| {
+                _ = fs::remove_file(&tmp_path);
+                format!("{}: {}", path.display(), e)
+            })
+    }
+
+    /// Returns the names completing the values of the given kind
+    /// (`ATTRIBUTE_ID`, `GROUP_ID` or `METRIC_NAME`), if any.
+    fn names(&self, value_name: &str) -> Option<&[String]> {
+        match value_name {
+            "ATTRIBUTE_ID" => Some(&self.attributes),
+            "GROUP_ID" => Some(&self.groups),
+            "METRIC_NAME" => Some(&self.metrics),
+            _ => None,
+        }
+    }
+}
+
+/// Records the names of a resolved registry for the shell completions. A
+/// failure is only traced, the completions being a convenience.
+pub fn record_names(log: &impl Logger, cache: &Cache, registry: &SemConvSpecs) {
+    if let Err(e) = CompletionIndex::from_registry(registry).save(cache) {
+        log.trace(&format!("Completion index not saved: {}", e));
+    }
+}
+
+/// Print a shell completion script or the candidates completing a command
+/// line
+pub fn command_completions(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &CompletionsCommand,
+) {
+    if let Some(index) = command.complete {
+        // A completion never fails, the shell falling back on the files.
+        let index_names = cache_args
+            .cache()
+            .map(|cache| CompletionIndex::load(&cache))
+            .unwrap_or_default();
+        for candidate in complete(&command.words, index, &index_names) {
+            println!("{}", candidate);
+        }
+        return;
+    }
+
+    match command.shell {
+        Some(shell) => log.log(script(shell).trim_end()),
+        None => {
+            log.error("A shell is required");
+            exit(FAILURE);
+        }
+    };
+}
+
+/// Returns the completion script of a shell.
+fn script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => {
+            r#"_weaver() {
+    local IFS=$'\n'
+    COMPREPLY=($(weaver completions --complete "$COMP_CWORD" -- "${COMP_WORDS[@]}" 2>/dev/null))
+}
+complete -o default -F _weaver weaver
+"#
+        }
+        Shell::Zsh => {
+            r#"#compdef weaver
+_weaver() {
+    local -a candidates
+    candidates=("${(@f)$(weaver completions --complete $((CURRENT - 1)) -- "${words[@]}" 2>/dev/null)}")
+    if [[ -n "${candidates[1]}" ]]; then
+        compadd -a candidates
+    else
+        _files
+    fi
+}
+compdef _weaver weaver
+"#
+        }
+        Shell::Fish => {
+            r#"function __weaver_complete
+    set -l words (commandline -opc) (commandline -ct)
+    set -l candidates (weaver completions --complete (math (count $words) - 1) -- $words 2>/dev/null)
+    if test (count $candidates) -eq 0
+        __fish_complete_path (commandline -ct)
+    else
+        printf '%s\n' $candidates
+    end
+end
+complete -c weaver -f -a '(__weaver_complete)'
+"#
+        }
+    }
+}
+
+/// Returns the candidates completing the word at the given index of a
+/// command line (the first word being the program).
+fn complete(words: &[String], index: usize, index_names: &CompletionIndex) -> Vec<String> {
+    let mut cli = Cli::command();
+    cli.build();
+
+    // Walks the words preceding the completed word to find the current
+    // sub-command, the option waiting for a value or the current positional
+    // argument.
+    let mut command = &cli;
+    let mut pending: Option<&Arg> = None;
+    let mut positional = 0;
+    let mut options_ended = false;
+    for word in words.iter().take(index).skip(1) {
+        if pending.take().is_some() {
+            continue;
+        }
+        if !options_ended && word == "--" {
+            options_ended = true;
+        } else if !options_ended && word.starts_with("--") {
+            let (name, value) = match word[2..].split_once('=') {
+                Some((name, _)) => (name, true),
+                None => (&word[2..], false),
+            };
+            pending = command
+                .get_arguments()
+                .find(|arg| arg.get_long() == Some(name))
+                .filter(|arg| !value && arg.get_action().takes_values());
+        } else if !options_ended && word.starts_with('-') && word.len() > 1 {
+            let mut shorts = word[1..].chars();
+            let short = shorts.next();
+            pending = command
+                .get_arguments()
+                .find(|arg| arg.get_short().is_some() && arg.get_short() == short)
+                .filter(|arg| shorts.next().is_none() && arg.get_action().takes_values());
+        } else if let Some(subcommand) = command
+            .find_subcommand(word)
+            .filter(|_| positional == 0 && !options_ended)
+        {
+            command = subcommand;
+        } else {
+            positional += 1;
+        }
+    }
+
+    let current = words.get(index).map(String::as_str).unwrap_or_default();
+    let mut candidates = match pending {
+        Some(arg) => values(arg, index_names),
+        None if current.starts_with('-') && !options_ended => options(command),
+        None => {
+            let mut candidates: Vec<String> = if positional == 0 {
+                command
+                    .get_subcommands()
+                    .filter(|subcommand| !subcommand.is_hide_set())
+                    .map(|subcommand| subcommand.get_name().to_owned())
+                    .collect()
+            } else {
+                vec![]
+            };
+            // The arguments following a variadic argument are its values.
+            let arg = command.get_positionals().nth(positional).or_else(|| {
+                command
+                    .get_positionals()
+                    .last()
+                    .filter(|arg| matches!(arg.get_action(), ArgAction::Append))
+            });
+            if let Some(arg) = arg {
+                candidates.extend(values(arg, index_names));
+            }
+            candidates
+        }
+    };
+    candidates.retain(|candidate| candidate.starts_with(current));
+    candidates
+}
+
+/// Returns the options of a command (their long name, or their short name
+/// without long name).
+fn options(command: &Command) -> Vec<String> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_positional() && !arg.is_hide_set())
+        .filter_map(|arg| match (arg.get_long(), arg.get_short()) {
+            (Some(long), _) => Some(format!("--{}", long)),
+            (None, Some(short)) => Some(format!("-{}", short)),
+            (None, None) => None,
+        })
+        .collect()
+}
+
+/// Returns the values of an option or an argument: the names of the last
+/// resolved registry, the possible values of an enum, or nothing (the shell
+/// then completes the files).
+fn values(arg: &Arg, index_names: &CompletionIndex) -> Vec<String> {
+    let names = arg
+        .get_value_names()
+        .and_then(|value_names| value_names.first())
+        .and_then(|value_name| index_names.names(value_name.as_str()));
+    match names {
+        Some(names) => names.to_vec(),
+        None => arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_owned())
+            .collect(),
+    }
+}
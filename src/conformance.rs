@@ -0,0 +1,446 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command running a conformance suite against a semantic convention
+//! registry (the official registry by default), to detect early the upstream
+//! changes breaking weaver (e.g. in a nightly job).
+//!
+//! The suite resolves the registry and checks:
+//! - the number of groups, attributes and metrics (minimum counts),
+//! - the presence of well-known attributes and metrics (spot checks),
+//! - the absence of unresolved references,
+//! - the determinism of the resolution (two resolutions of the registry
+//!   serialized to the same output, whose hash is reported).
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use weaver_logger::Logger;
+use weaver_resolved_schema::registry::TypedGroup;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_resolver::{Error, SchemaResolver, UnresolvedReference};
+use weaver_schema::SemConvImport;
+use weaver_semconv::ResolverConfig;
+
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE, VALIDATION_FAILURE};
+
+/// The git URL of the official semantic convention registry.
+const OFFICIAL_REGISTRY: &str = "https://github.com/open-telemetry/semantic-conventions.git";
+
+/// Parameters for the `conformance` command
+#[derive(Args)]
+pub struct ConformanceCommand {
+    /// Git URL of the semantic convention registry (the official registry by
+    /// default)
+    #[arg(long, default_value = OFFICIAL_REGISTRY)]
+    pub registry: String,
+
+    /// Path in the git repository where the semantic convention registry is
+    /// located
+    #[arg(long, default_value = "model")]
+    pub path: String,
+
+    /// Expectations file overriding the minimum counts, the spot checked
+    /// attributes and metrics, and pinning the expected output hash
+    #[arg(long, value_name = "FILE")]
+    pub expectations: Option<PathBuf>,
+
+    /// Format of the report
+    #[arg(short, long, value_enum, default_value = "text")]
+    pub format: ReportFormat,
+
+    /// Output file to write the report to
+    /// If not specified, the report is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Formats of the conformance report
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ReportFormat {
+    /// One line per check
+    Text,
+    /// A JSON document
+    Json,
+}
+
+/// The expectations of the conformance suite.
+#[derive(Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Expectations {
+    /// The minimum number of groups.
+    pub min_groups: usize,
+    /// The minimum number of attributes.
+    pub min_attributes: usize,
+    /// The minimum number of metrics.
+    pub min_metrics: usize,
+    /// The attributes (fully qualified ids) the registry must define.
+    pub attributes: Vec<String>,
+    /// The metrics the registry must define.
+    pub metrics: Vec<String>,
+    /// The expected hash of the resolved registry, if pinned.
+    pub output_hash: Option<String>,
+}
+
+impl Default for Expectations {
+    fn default() -> Self {
+        Self {
+            min_groups: 1,
+            min_attributes: 1,
+            min_metrics: 1,
+            attributes: [
+                "error.type",
+                "http.request.method",
+                "http.response.status_code",
+                "server.address",
+                "server.port",
+                "url.full",
+            ]
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+            metrics: vec!["http.server.request.duration".to_owned()],
+            output_hash: None,
+        }
+    }
+}
+
+impl Expectations {
+    /// Loads the expectations from a YAML file.
+    fn load_from_file(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        serde_yaml::from_reader(BufReader::new(file))
+            .map_err(|e| format!("Invalid expectations {}: {}", path.display(), e))
+    }
+}
+
+/// The report of the conformance suite.
+#[derive(Serialize, Debug)]
+struct ConformanceReport {
+    /// The git URL of the registry.
+    registry: String,
+    /// The path of the registry in the git repository.
+    path: String,
+    /// The commit of the registry, if known.
+    commit: Option<String>,
+    /// The version of weaver.
+    weaver_version: String,
+    /// The number of groups of the resolved registry.
+    groups: usize,
+    /// The number of attributes of the resolved registry.
+    attributes: usize,
+    /// The number of metrics of the resolved registry.
+    metrics: usize,
+    /// The SHA-256 hash of the resolved registry (JSON), if resolved.
+    output_hash: Option<String>,
+    /// The checks, in the order of execution.
+    checks: Vec<Check>,
+}
+
+/// A check of the conformance suite.
+#[derive(Serialize, Debug)]
+struct Check {
+    /// The name of the check.
+    name: String,
+    /// True if the check passed.
+    passed: bool,
+    /// A human readable description of the outcome.
+    detail: String,
+}
+
+impl ConformanceReport {
+    /// Records the outcome of a check.
+    fn check(&mut self, name: &str, passed: bool, detail: String) {
+        self.checks.push(Check {
+            name: name.to_owned(),
+            passed,
+            detail,
+        });
+    }
+
+    /// Returns the number of failed checks.
+    fn failures(&self) -> usize {
+        self.checks.iter().filter(|check| !check.passed).count()
+    }
+}
+
+/// Run the conformance suite against a semantic convention registry
+pub fn command_conformance(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &ConformanceCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+    let expectations = match &command.expectations {
+        Some(path) => Expectations::load_from_file(path).unwrap_or_else(|e| {
+            log.error(&e);
+            exit(FAILURE);
+        }),
+        None => Expectations::default(),
+    };
+
+    let imports = [SemConvImport::GitUrl {
+        git_url: command.registry.clone(),
+        path: Some(command.path.clone()),
+        mirrors: vec![],
+    }];
+    let registry = SchemaResolver::semconv_registry_from_imports(
+        &imports,
+        ResolverConfig::default(),
+        &cache,
+        log.clone(),
+    )
+    .unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+
+    let mut report = ConformanceReport {
+        registry: command.registry.clone(),
+        path: command.path.clone(),
+        commit: cache
+            .git_repo_info(&command.registry)
+            .and_then(|info| info.commit),
+        weaver_version: env!("CARGO_PKG_VERSION").to_owned(),
+        groups: 0,
+        attributes: 0,
+        metrics: 0,
+        output_hash: None,
+        checks: vec![],
+    };
+
+    // The registry is resolved twice, from two copies of the loaded specs,
+    // to check the determinism of the resolution.
+    let resolutions = [registry.clone(), registry].map(|mut registry| {
+        SchemaResolver::resolve_semantic_convention_registry(&mut registry, log.clone())
+    });
+    let [first, second] = resolutions;
+    match (first, second) {
+        (Ok(first), Ok(second)) => {
+            report.check(
+                "unresolved_references",
+                true,
+                "all the references are resolved".to_owned(),
+            );
+            check_counts(&mut report, &first, &expectations);
+            check_spot_checks(&mut report, &first, &expectations);
+            check_determinism(&mut report, &first, &second, &expectations);
+        }
+        (Err(Error::UnresolvedReferences { refs }), _) => {
+            let refs: Vec<String> = refs.iter().map(unresolved_reference).collect();
+            report.check(
+                "unresolved_references",
+                false,
+                format!(
+                    "{} unresolved reference(s): {}",
+                    refs.len(),
+                    refs.join(", ")
+                ),
+            );
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        }
+    }
+
+    let content = match command.format {
+        ReportFormat::Text => text_report(&report),
+        ReportFormat::Json => serde_json::to_string_pretty(&report).unwrap_or_else(|e| {
+            log.error(&e.to_string());
+            exit(FAILURE);
+        }),
+    };
+    match &command.output {
+        Some(output) => {
+            if let Err(e) = std::fs::write(output, &content) {
+                log.error(&format!("Failed to write to {}: {}", output.display(), e));
+                exit(FAILURE);
+            }
+            log.success(&format!("Saved report to '{}'", output.display()));
+        }
+        None => {
+            log.log(&content);
+        }
+    }
+
+    let failures = report.failures();
+    if failures > 0 {
+        log.error(&format!(
+            "Conformance suite failed ({} of {} check(s) failed)",
+            failures,
+            report.checks.len()
+        ));
+        exit(VALIDATION_FAILURE);
+    }
+    log.success(&format!(
+        "Conformance suite passed ({} check(s))",
+        report.checks.len()
+    ));
+}
+
+/// Checks the number of groups, attributes and metrics of the resolved
+/// registry.
+fn check_counts(
+    report: &mut ConformanceReport,
+    schema: &ResolvedTelemetrySchema,
+    expectations: &Expectations,
+) {
+    report.groups = schema
+        .registries
+        .iter()
+        .map(|registry| registry.groups.len())
+        .sum();
+    report.attributes = schema.catalog.attributes.len();
+    report.metrics = schema.catalog.metrics.len();
+
+    for (name, count, min) in [
+        ("group_count", report.groups, expectations.min_groups),
+        (
+            "attribute_count",
+            report.attributes,
+            expectations.min_attributes,
+        ),
+        ("metric_count", report.metrics, expectations.min_metrics),
+    ] {
+        report.check(name, count >= min, format!("{} (minimum {})", count, min));
+    }
+}
+
+/// Checks that the expected attributes and metrics are defined by the
+/// resolved registry.
+fn check_spot_checks(
+    report: &mut ConformanceReport,
+    schema: &ResolvedTelemetrySchema,
+    expectations: &Expectations,
+) {
+    let attributes: HashSet<&str> = schema
+        .catalog
+        .attributes
+        .iter()
+        .map(|attr| attr.name.as_str())
+        .collect();
+    let metrics: HashSet<&str> = schema
+        .registries
+        .iter()
+        .flat_map(|registry| registry.groups.iter())
+        .filter_map(|group| match &group.typed_group {
+            TypedGroup::Metric { metric_name, .. } => metric_name.as_deref(),
+            _ => None,
+        })
+        .chain(
+            schema
+                .catalog
+                .metrics
+                .iter()
+                .map(|metric| metric.name.as_str()),
+        )
+        .collect();
+
+    for attribute in &expectations.attributes {
+        let found = attributes.contains(attribute.as_str());
+        report.check(
+            &format!("attribute `{}`", attribute),
+            found,
+            if found { "defined" } else { "not defined" }.to_owned(),
+        );
+    }
+    for metric in &expectations.metrics {
+        let found = metrics.contains(metric.as_str());
+        report.check(
+            &format!("metric `{}`", metric),
+            found,
+            if found { "defined" } else { "not defined" }.to_owned(),
+        );
+    }
+}
+
+/// Checks that the two resolutions of the registry are serialized to the
+/// same output, and that its hash is the expected one if pinned.
+fn check_determinism(
+    report: &mut ConformanceReport,
+    first: &ResolvedTelemetrySchema,
+    second: &ResolvedTelemetrySchema,
+    expectations: &Expectations,
+) {
+    let hash = |schema: &ResolvedTelemetrySchema| {
+        serde_json::to_vec(schema)
+            .map(|content| format!("{:x}", Sha256::digest(content)))
+            .unwrap_or_else(|e| e.to_string())
+    };
+    let (first, second) = (hash(first), hash(second));
+    report.check(
+        "deterministic_output",
+        first == second,
+        if first == second {
+            format!("sha256 {}", first)
+        } else {
+            format!("sha256 {} then {}", first, second)
+        },
+    );
+    if let Some(expected) = &expectations.output_hash {
+        report.check(
+            "output_hash",
+            *expected == first,
+            format!("sha256 {} (expected {})", first, expected),
+        );
+    }
+    report.output_hash = Some(first);
+}
+
+/// Returns a human readable description of an unresolved reference.
+fn unresolved_reference(reference: &UnresolvedReference) -> String {
+    let (kind, target, group_id, provenance) = match reference {
+        UnresolvedReference::AttributeRef {
+            group_id,
+            attribute_ref,
+            provenance,
+        } => ("attribute", attribute_ref, group_id, provenance),
+        UnresolvedReference::ExtendsRef {
+            group_id,
+            extends_ref,
+            provenance,
+        } => ("extends", extends_ref, group_id, provenance),
+        UnresolvedReference::IncludeRef {
+            group_id,
+            include_ref,
+            provenance,
+        } => ("include", include_ref, group_id, provenance),
+    };
+    format!(
+        "{} `{}` of the group `{}` ({})",
+        kind, target, group_id, provenance
+    )
+}
+
+/// Returns the text report: a summary of the registry followed by one line
+/// per check.
+fn text_report(report: &ConformanceReport) -> String {
+    let mut lines = vec![format!(
+        "Registry {} ({}, commit {}), weaver {}",
+        report.registry,
+        report.path,
+        report.commit.as_deref().unwrap_or("unknown"),
+        report.weaver_version
+    )];
+    lines.extend(report.checks.iter().map(|check| {
+        format!(
+            "[{}] {}: {}",
+            if check.passed { "PASS" } else { "FAIL" },
+            check.name,
+            check.detail
+        )
+    }));
+    lines.join("\n")
+}
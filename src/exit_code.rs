@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Process exit codes of the weaver commands. Each failure category has its
+//! own exit code so CI pipelines can branch on the failure type without
+//! parsing the logs:
+//!
+//! | Code | Failure category                                                  |
+//! |------|-------------------------------------------------------------------|
+//! | 0    | Success                                                           |
+//! | 1    | Other failure (I/O error, invalid argument, internal error, ...)  |
+//! | 2    | Validation errors (invalid registry or schema, failed checks)     |
+//! | 3    | Unresolved references (attribute refs, extends, metric refs)      |
+//! | 4    | Network failure (registry clone, remote file, network config)     |
+//! | 5    | Template error (invalid language pack, rendering failure)         |
+
+/// Other failure (I/O error, invalid argument, internal error, ...).
+pub const FAILURE: i32 = 1;
+
+/// Validation errors: invalid semantic convention registry or telemetry
+/// schema, or failed checks (`registry check`, `schema lint`, ...).
+pub const VALIDATION_FAILURE: i32 = 2;
+
+/// References that could not be resolved (attribute refs, extends, metric
+/// refs).
+pub const UNRESOLVED_REFERENCES: i32 = 3;
+
+/// Network failure (clone of a registry, fetch of a remote file, invalid
+/// network configuration).
+pub const NETWORK_FAILURE: i32 = 4;
+
+/// Template error (invalid language pack, template rendering failure).
+pub const TEMPLATE_FAILURE: i32 = 5;
+
+/// The exit code of the failure category of an error.
+pub trait ExitCode {
+    /// Returns the process exit code of the error.
+    fn exit_code(&self) -> i32;
+}
+
+impl ExitCode for weaver_resolver::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_resolver::Error::*;
+
+        match self {
+            TelemetrySchemaError(e) | ParentSchemaError(e) => e.exit_code(),
+            InvalidUrl { .. }
+            | InvalidResolvedSchema { .. }
+            | SemConvError { .. }
+            | IncompatibleMetricAttributes { .. }
+            | InstrumentationLibraryVersionMismatch { .. }
+            | InvalidIgnoreFile { .. }
+            | CyclicExtends { .. }
+            | CyclicInclude { .. } => VALIDATION_FAILURE,
+            FailToResolveAttributes { .. }
+            | UnresolvedReferences { .. }
+            | FailToResolveMetric { .. } => UNRESOLVED_REFERENCES,
+            FetchFailed { .. } | ImportSourcesFailed { .. } => NETWORK_FAILURE,
+            ConversionError { .. }
+            | GroupNotFound { .. }
+            | AttributeNotFound { .. }
+            | ThreadPoolError { .. } => FAILURE,
+        }
+    }
+}
+
+impl ExitCode for weaver_semconv::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_semconv::Error::*;
+
+        match self {
+            CatalogNotFound { .. }
+            | InvalidLintConfig { .. }
+            | InvalidRefactoring { .. }
+            | RegistryWriteFailed { .. } => FAILURE,
+            AttributeNotFound { .. } => UNRESOLVED_REFERENCES,
+            InvalidCatalog { .. }
+            | DuplicateAttributeId { .. }
+            | AttributeTypeConflict { .. }
+            | DuplicateGroupId { .. }
+            | DuplicateMetricName { .. }
+            | InvalidAttribute { .. }
+            | InvalidMetric { .. }
+            | UnimplementedGroupType { .. }
+            | UnknownStability { .. }
+            | AnyOfConstraintViolation { .. }
+            | CyclicExtends { .. } => VALIDATION_FAILURE,
+            // The category shared by all the errors, if any.
+            CompoundError(errors) => {
+                let mut exit_codes = errors.iter().map(ExitCode::exit_code);
+                let first = exit_codes.next().unwrap_or(VALIDATION_FAILURE);
+                if exit_codes.all(|exit_code| exit_code == first) {
+                    first
+                } else {
+                    VALIDATION_FAILURE
+                }
+            }
+        }
+    }
+}
+
+impl ExitCode for weaver_schema::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_schema::Error::*;
+
+        match self {
+            SchemaNotFound { .. } => FAILURE,
+            InvalidSchema { .. }
+            | InvalidAttribute { .. }
+            | InvalidSpanKindRules { .. }
+            | InvalidServicePolicy { .. }
+            | MergeConflicts { .. } => VALIDATION_FAILURE,
+        }
+    }
+}
+
+impl ExitCode for weaver_template::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_template::Error::*;
+
+        match self {
+            InvalidTelemetrySchema { .. } => VALIDATION_FAILURE,
+            SchemaResolutionFailed { error, .. } => error.exit_code(),
+            WriteGeneratedCodeFailed { .. }
+            | InternalError(_)
+            | LanguagePackAlreadyExists(_)
+            | ApiDiffFailed(_) => FAILURE,
+            InvalidConfigFile { .. }
+            | LanguageNotSupported(_)
+            | InvalidTemplateDirectory(_)
+            | InvalidTemplateFile(_)
+            | InvalidTemplate { .. }
+            | TemplateFileNameUndefined { .. }
+            | RenderLimitExceeded { .. }
+            | TemplateRenderFailed { .. }
+            | ScaffoldFailed { .. } => TEMPLATE_FAILURE,
+        }
+    }
+}
+
+impl ExitCode for weaver_cache::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_cache::Error::*;
+
+        match self {
+            GitRepoNotCreated { .. } | GitError { .. } => NETWORK_FAILURE,
+            HomeDirNotFound
+            | CacheDirNotCreated { .. }
+            | LockNotAcquired { .. }
+            | RepairFailed { .. } => FAILURE,
+        }
+    }
+}
+
+impl ExitCode for weaver_version::Error {
+    fn exit_code(&self) -> i32 {
+        use weaver_version::Error::*;
+
+        match self {
+            VersionsNotFound { .. } => FAILURE,
+            InvalidVersions { .. } => VALIDATION_FAILURE,
+        }
+    }
+}
+
+impl ExitCode for weaver_net::Error {
+    fn exit_code(&self) -> i32 {
+        NETWORK_FAILURE
+    }
+}
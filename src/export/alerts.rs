@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Prometheus alerting rule scaffolds of the metrics of a resolved semantic
+//! convention registry: one rule group per metric namespace (first segment
+//! of the metric name) and one rule per metric, aggregated by the labels of
+//! the required attributes of the metric. The thresholds are placeholders to
+//! replace before loading the rules.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use weaver_resolved_schema::metric::Instrument;
+
+use crate::export::prometheus::PromMetric;
+
+/// The placeholder of the thresholds. It is not a valid PromQL expression so
+/// that a rule whose threshold hasn't been set is rejected by Prometheus (and
+/// `promtool check rules`).
+const THRESHOLD_PLACEHOLDER: &str = "<THRESHOLD>";
+
+/// A Prometheus rule file.
+#[derive(Serialize, Debug)]
+pub struct RuleFile {
+    groups: Vec<RuleGroup>,
+}
+
+/// A group of rules.
+#[derive(Serialize, Debug)]
+struct RuleGroup {
+    name: String,
+    rules: Vec<AlertingRule>,
+}
+
+/// An alerting rule.
+#[derive(Serialize, Debug)]
+struct AlertingRule {
+    alert: String,
+    expr: String,
+    r#for: String,
+    labels: BTreeMap<String, String>,
+    annotations: BTreeMap<String, String>,
+}
+
+/// Returns the alerting rule scaffolds of the given metrics.
+pub fn alerting_rules(metrics: &[PromMetric]) -> RuleFile {
+    let mut groups: BTreeMap<&str, Vec<AlertingRule>> = BTreeMap::new();
+    for metric in metrics {
+        let namespace = metric.name.split('.').next().unwrap_or(&metric.name);
+        groups
+            .entry(namespace)
+            .or_default()
+            .push(alerting_rule(metric));
+    }
+    RuleFile {
+        groups: groups
+            .into_iter()
+            .map(|(namespace, rules)| RuleGroup {
+                name: namespace.to_owned(),
+                rules,
+            })
+            .collect(),
+    }
+}
+
+/// Returns the alerting rule scaffold of a metric.
+fn alerting_rule(metric: &PromMetric) -> AlertingRule {
+    let required_attributes: Vec<_> = metric
+        .key_attributes
+        .iter()
+        .filter(|attr| attr.required)
+        .collect();
+    let by_labels = required_attributes
+        .iter()
+        .map(|attr| attr.label.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let (alert_suffix, expr, condition) = match metric.instrument {
+        Instrument::Counter => (
+            "RateHigh",
+            format!(
+                "sum by ({}) (rate({}[5m])) > {}",
+                by_labels, metric.prom_name, THRESHOLD_PLACEHOLDER
+            ),
+            "rate",
+        ),
+        Instrument::Histogram => (
+            "P95High",
+            format!(
+                "histogram_quantile(0.95, sum by ({}) (rate({}_bucket[5m]))) > {}",
+                [by_labels.as_str(), "le"]
+                    .into_iter()
+                    .filter(|labels| !labels.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                metric.prom_name,
+                THRESHOLD_PLACEHOLDER
+            ),
+            "95th percentile",
+        ),
+        Instrument::UpDownCounter | Instrument::Gauge => (
+            "High",
+            format!(
+                "max by ({}) ({}) > {}",
+                by_labels, metric.prom_name, THRESHOLD_PLACEHOLDER
+            ),
+            "value",
+        ),
+    };
+
+    let unit = metric
+        .unit
+        .as_deref()
+        .filter(|unit| !unit.is_empty() && *unit != "1")
+        .map(|unit| format!(" (unit `{}`)", unit))
+        .unwrap_or_default();
+    let context = required_attributes
+        .iter()
+        .map(|attr| format!("{}={{{{ $labels.{} }}}}", attr.name, attr.label))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut summary = format!("High {} of {}", condition, metric.name);
+    if !context.is_empty() {
+        summary.push_str(&format!(" ({})", context));
+    }
+
+    AlertingRule {
+        alert: format!("{}{}", pascal_case(&metric.name), alert_suffix),
+        expr,
+        r#for: "5m".to_owned(),
+        labels: BTreeMap::from([("severity".to_owned(), "warning".to_owned())]),
+        annotations: BTreeMap::from([
+            ("summary".to_owned(), summary),
+            (
+                "description".to_owned(),
+                format!(
+                    "{}{}. The {} is {{{{ $value }}}}, above the threshold {}.",
+                    metric.brief.trim_end_matches('.'),
+                    unit,
+                    condition,
+                    THRESHOLD_PLACEHOLDER
+                ),
+            ),
+        ]),
+    }
+}
+
+/// Converts a metric name (e.g. `http.server.request.duration`) into an
+/// alert name prefix (e.g. `HttpServerRequestDuration`).
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Starter Grafana dashboard of the metrics of a resolved semantic convention
+//! registry: one time series panel per metric, querying a Prometheus data
+//! source, and one templated variable per key attribute (required and
+//! conditionally required attributes) filtering the panels. The service
+//! metadata of a telemetry schema, if given, is carried by the description,
+//! the tags and the links of the dashboard.
+
+use std::collections::BTreeSet;
+
+use serde_json::{json, Value};
+
+use weaver_resolved_schema::metric::Instrument;
+use weaver_schema::service::ServiceMetadata;
+
+use crate::export::prometheus::PromMetric;
+
+/// The width of a panel (half of the 24 columns of the grid).
+const PANEL_WIDTH: usize = 12;
+
+/// The height of a panel.
+const PANEL_HEIGHT: usize = 8;
+
+/// Returns the Grafana dashboard (JSON model) of the given metrics.
+pub fn dashboard(title: &str, metrics: &[PromMetric], service: Option<&ServiceMetadata>) -> Value {
+    let labels: BTreeSet<&str> = metrics
+        .iter()
+        .flat_map(|metric| metric.key_attributes.iter())
+        .map(|attr| attr.label.as_str())
+        .collect();
+
+    let mut variables = vec![json!({
+        "name": "datasource",
+        "label": "Data source",
+        "type": "datasource",
+        "query": "prometheus",
+    })];
+    variables.extend(labels.into_iter().map(|label| {
+        json!({
+            "name": label,
+            "label": label,
+            "type": "query",
+            "datasource": { "type": "prometheus", "uid": "${datasource}" },
+            "query": { "query": format!("label_values({})", label), "refId": "variable" },
+            "definition": format!("label_values({})", label),
+            "refresh": 2,
+            "includeAll": true,
+            "multi": true,
+            "allValue": ".*",
+            "current": { "text": "All", "value": "$__all" },
+        })
+    }));
+
+    let panels: Vec<Value> = metrics
+        .iter()
+        .enumerate()
+        .map(|(i, metric)| panel(i, metric))
+        .collect();
+
+    let mut tags = vec!["opentelemetry".to_owned(), "weaver".to_owned()];
+    let mut description = vec![];
+    let mut links = vec![];
+    if let Some(service) = service {
+        if let Some(owner) = &service.owner {
+            tags.push(format!("team:{}", owner));
+            description.push(format!("Owner: {}", owner));
+        }
+        if let Some(tier) = &service.tier {
+            tags.push(format!("tier:{}", tier));
+            description.push(format!("Tier: {}", tier));
+        }
+        if let Some(runbook_url) = &service.runbook_url {
+            links.push(link("Runbook", runbook_url));
+        }
+        links.extend(
+            service
+                .slo_urls
+                .iter()
+                .enumerate()
+                .map(|(i, url)| link(&format!("SLO {}", i + 1), url)),
+        );
+    }
+
+    json!({
+        "title": title,
+        "uid": null,
+        "editable": true,
+        "schemaVersion": 39,
+        "time": { "from": "now-6h", "to": "now" },
+        "tags": tags,
+        "description": description.join(", "),
+        "links": links,
+        "templating": { "list": variables },
+        "panels": panels,
+    })
+}
+
+/// Returns a dashboard link opening the given URL in a new tab.
+fn link(title: &str, url: &str) -> Value {
+    json!({
+        "title": title,
+        "type": "link",
+        "url": url,
+        "targetBlank": true,
+    })
+}
+
+/// Returns the time series panel of a metric.
+fn panel(index: usize, metric: &PromMetric) -> Value {
+    let filters = metric
+        .key_attributes
+        .iter()
+        .map(|attr| format!("{}=~\"${}\"", attr.label, attr.label))
+        .collect::<Vec<_>>()
+        .join(",");
+    let (expr, unit) = match metric.instrument {
+        Instrument::Counter => (
+            format!(
+                "sum(rate({}{{{}}}[$__rate_interval]))",
+                metric.prom_name, filters
+            ),
+            // A rate is expressed per second.
+            match metric.unit.as_deref().map(str::trim) {
+                Some("By") => Some("Bps".to_owned()),
+                _ => Some("suffix:/s".to_owned()),
+            },
+        ),
+        Instrument::Histogram => (
+            format!(
+                "histogram_quantile(0.95, sum by (le) (rate({}_bucket{{{}}}[$__rate_interval])))",
+                metric.prom_name, filters
+            ),
+            grafana_unit(metric.unit.as_deref()),
+        ),
+        Instrument::UpDownCounter => (
+            format!("sum({}{{{}}})", metric.prom_name, filters),
+            grafana_unit(metric.unit.as_deref()),
+        ),
+        Instrument::Gauge => (
+            format!("avg({}{{{}}})", metric.prom_name, filters),
+            // A gauge with the unit `1` is a ratio (e.g. a utilization).
+            match metric.unit.as_deref().map(str::trim) {
+                Some("1") => Some("percentunit".to_owned()),
+                unit => grafana_unit(unit),
+            },
+        ),
+    };
+    let legend = match metric.instrument {
+        Instrument::Histogram => "p95",
+        _ => metric.name.as_str(),
+    };
+
+    json!({
+        "id": index + 1,
+        "type": "timeseries",
+        "title": metric.name,
+        "description": metric.brief,
+        "datasource": { "type": "prometheus", "uid": "${datasource}" },
+        "gridPos": {
+            "h": PANEL_HEIGHT,
+            "w": PANEL_WIDTH,
+            "x": (index % 2) * PANEL_WIDTH,
+            "y": (index / 2) * PANEL_HEIGHT,
+        },
+        "fieldConfig": {
+            "defaults": { "unit": unit.unwrap_or_else(|| "short".to_owned()) },
+            "overrides": [],
+        },
+        "targets": [{
+            "refId": "A",
+            "expr": expr,
+            "legendFormat": legend,
+        }],
+    })
+}
+
+/// Returns the Grafana unit of a UCUM unit, or `None` if there is no
+/// equivalent Grafana unit.
+fn grafana_unit(unit: Option<&str>) -> Option<String> {
+    let unit = match unit?.trim() {
+        "ns" => "ns",
+        "us" => "µs",
+        "ms" => "ms",
+        "s" => "s",
+        "min" => "m",
+        "h" => "h",
+        "d" => "d",
+        "By" => "bytes",
+        "KiBy" => "kbytes",
+        "MiBy" => "mbytes",
+        "GiBy" => "gbytes",
+        "By/s" => "Bps",
+        "%" => "percent",
+        "Hz" => "hertz",
+        "Cel" => "celsius",
+        "V" => "volt",
+        "A" => "amp",
+        "W" => "watt",
+        "J" => "joule",
+        _ => return None,
+    };
+    Some(unit.to_owned())
+}
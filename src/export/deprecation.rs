@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deprecation map of a semantic convention registry: for each deprecated
+//! attribute and metric name, the name replacing it (in the latest version),
+//! the version deprecating it and the version targeted for its removal. The
+//! map is built from the `deprecated` fields of the registry (following the
+//! `renamed_to` and `removed_in` fields of the structured deprecations) and
+//! from the renames declared by the `versions` section of a telemetry
+//! schema, so the
+//! ingestion pipelines of the telemetry backends can normalize the telemetry
+//! emitted with old names.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::deprecated::DeprecatedSpec;
+use weaver_semconv::group::ConvTypeSpec;
+use weaver_semconv::SemConvSpecs;
+use weaver_version::{RenameKind, Versions};
+
+/// The deprecation map.
+#[derive(Serialize, Debug, Default)]
+pub struct DeprecationMap {
+    /// Deprecated attribute name -> deprecation.
+    pub attributes: BTreeMap<String, Deprecation>,
+    /// Deprecated metric name -> deprecation.
+    pub metrics: BTreeMap<String, Deprecation>,
+}
+
+/// The deprecation of an attribute or a metric name.
+#[derive(Serialize, Debug, Default)]
+pub struct Deprecation {
+    /// The name replacing the deprecated name in the latest version (`None`
+    /// if the name is removed without replacement or if the replacement is
+    /// unknown).
+    pub new_name: Option<String>,
+    /// The version deprecating the name (`None` if unknown, i.e. the name
+    /// is only deprecated by the registry).
+    pub since_version: Option<String>,
+    /// The version targeted for the removal of the name (the `removed_in`
+    /// field of a structured deprecation, or the removal target of the
+    /// export; `None` if not planned).
+    pub removal_target: Option<String>,
+    /// The deprecation note of the registry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Returns the deprecation map of the given registry and versions. The
+/// removal target (if any) applies to all the deprecated names without a
+/// `removed_in` version.
+pub fn deprecation_map(
+    registry: &SemConvSpecs,
+    versions: Option<&Versions>,
+    removal_target: Option<&str>,
+) -> DeprecationMap {
+    let mut map = DeprecationMap::default();
+
+    // The renames of the versions, each old name being mapped to the name
+    // introduced by the version renaming it.
+    for rename in versions
+        .iter()
+        .flat_map(|versions| versions.renames_by_version())
+    {
+        let deprecations = match rename.kind {
+            RenameKind::Attribute => &mut map.attributes,
+            RenameKind::Metric => &mut map.metrics,
+        };
+        // The first version renaming a name deprecates it.
+        _ = deprecations
+            .entry(rename.old_name)
+            .or_insert_with(|| Deprecation {
+                new_name: Some(rename.new_name),
+                since_version: Some(rename.version.to_string()),
+                ..Deprecation::default()
+            });
+    }
+
+    // The names deprecated by the registry.
+    for group in registry.groups() {
+        for attr in group.attributes.iter() {
+            if let AttributeSpec::Id { id, deprecated, .. } = attr {
+                let Some(deprecated) = deprecated else {
+                    continue;
+                };
+                let name = if group.prefix.is_empty() {
+                    id.clone()
+                } else {
+                    format!("{}.{}", group.prefix, id)
+                };
+                add_deprecated(&mut map.attributes, name, deprecated);
+            }
+        }
+        if let (ConvTypeSpec::Metric, Some(metric_name), Some(deprecated)) =
+            (&group.r#type, &group.metric_name, &group.deprecated)
+        {
+            add_deprecated(&mut map.metrics, metric_name.clone(), deprecated);
+        }
+    }
+
+    for deprecations in [&mut map.attributes, &mut map.metrics] {
+        chain_renames(deprecations);
+        for deprecation in deprecations.values_mut() {
+            if deprecation.removal_target.is_none() {
+                deprecation.removal_target = removal_target.map(str::to_owned);
+            }
+        }
+    }
+    map
+}
+
+/// Adds the deprecation of the registry to the deprecation of a name. When
+/// the versions don't rename the name, the replacement is the `renamed_to`
+/// field of a structured deprecation, or the replacement mentioned by a note.
+fn add_deprecated(
+    deprecations: &mut BTreeMap<String, Deprecation>,
+    name: String,
+    deprecated: &DeprecatedSpec,
+) {
+    let deprecation = deprecations.entry(name).or_default();
+    if deprecation.new_name.is_none() {
+        deprecation.new_name = match deprecated {
+            DeprecatedSpec::Note(note) => replacement(note),
+            DeprecatedSpec::Structured(_) => deprecated.renamed_to().map(str::to_owned),
+        };
+    }
+    deprecation.removal_target = deprecated.removed_in().map(str::to_owned);
+    deprecation.note = Some(deprecated.reason().trim().to_owned());
+}
+
+/// Replaces the new name of each deprecation by the name in the latest
+/// version, following the successive renames (a name renamed back to a
+/// previous name stops the chain).
+fn chain_renames(deprecations: &mut BTreeMap<String, Deprecation>) {
+    let next_names: BTreeMap<String, String> = deprecations
+        .iter()
+        .filter_map(|(name, deprecation)| {
+            deprecation
+                .new_name
+                .clone()
+                .map(|new_name| (name.clone(), new_name))
+        })
+        .collect();
+    for (name, deprecation) in deprecations.iter_mut() {
+        let Some(mut new_name) = deprecation.new_name.clone() else {
+            continue;
+        };
+        let mut visited = HashSet::from([name.clone()]);
+        while let Some(next_name) = next_names.get(&new_name) {
+            if !visited.insert(new_name.clone()) {
+                break;
+            }
+            new_name = next_name.clone();
+        }
+        deprecation.new_name = Some(new_name);
+    }
+}
+
+/// Returns the replacement mentioned by a deprecation note, i.e. the first
+/// name quoted with backticks after `Replaced by` or `Use`.
+fn replacement(note: &str) -> Option<String> {
+    let lowercase_note = note.to_ascii_lowercase();
+    let start = ["replaced by", "use `"]
+        .iter()
+        .filter_map(|marker| lowercase_note.find(marker))
+        .min()?;
+    let quoted = &note[start..];
+    let open = quoted.find('`')? + 1;
+    let close = quoted[open..].find('`')? + open;
+    let name = quoted[open..close].trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
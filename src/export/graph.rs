@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Group graph of a semantic convention registry (extends, attribute refs,
+//! include constraints and span events) rendered as Graphviz DOT or Mermaid,
+//! or as the dependency tree of a group.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::SemConvSpecs;
+
+/// The kind of relationship between two groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EdgeKind {
+    /// The source group extends the target group.
+    Extends,
+    /// The source group references an attribute defined in the target group.
+    AttributeRef,
+    /// The source group includes the target group via a constraint.
+    Include,
+    /// The source span group declares the target event group.
+    Event,
+}
+
+impl EdgeKind {
+    fn label(&self) -> &'static str {
+        match self {
+            EdgeKind::Extends => "extends",
+            EdgeKind::AttributeRef => "ref",
+            EdgeKind::Include => "include",
+            EdgeKind::Event => "event",
+        }
+    }
+}
+
+/// A node of the dependency tree of a group.
+#[derive(Serialize, Debug)]
+pub struct DependencyNode {
+    /// The id of the group.
+    pub group_id: String,
+    /// The type of the group (`None` if the group is not defined in the
+    /// registry).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    /// The relationship with the parent node (`None` for the root).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<&'static str>,
+    /// True if the group is one of its own ancestors in the tree, i.e. the
+    /// dependency closes a cycle. The dependencies of the group are not
+    /// expanded again.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+    /// True if the dependencies of the group are already expanded in
+    /// another branch of the tree (and not expanded again).
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    /// The dependencies of the group.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyNode>,
+}
+
+/// The transitive dependencies of a group.
+#[derive(Serialize, Debug)]
+pub struct DependencyTree {
+    /// The root of the tree (the group itself).
+    pub root: DependencyNode,
+    /// The cycles found in the dependencies, each one starting and ending
+    /// with the same group.
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// A graph of groups where nodes are group ids and edges are relationships
+/// between groups.
+#[derive(Debug, Default)]
+pub struct GroupGraph {
+    /// Group id -> group type.
+    nodes: BTreeMap<String, String>,
+    /// (source group id, target group id, kind)
+    edges: BTreeSet<(String, String, EdgeKind)>,
+}
+
+impl GroupGraph {
+    /// Builds the group graph of the given (unresolved) registry.
+    pub fn from_registry(registry: &SemConvSpecs) -> Self {
+        let mut graph = GroupGraph::default();
+
+        // Index the group defining each attribute.
+        let mut attribute_groups = BTreeMap::new();
+        for group in registry.groups() {
+            for attr in group.attributes.iter() {
+                if let AttributeSpec::Id { id, .. } = attr {
+                    let fq_id = if group.prefix.is_empty() {
+                        id.clone()
+                    } else {
+                        format!("{}.{}", group.prefix, id)
+                    };
+                    _ = attribute_groups.insert(fq_id, group.id.clone());
+                }
+            }
+        }
+
+        for group in registry.groups() {
+            _ = graph
+                .nodes
+                .insert(group.id.clone(), format!("{:?}", group.r#type));
+
+            if let Some(extends) = &group.extends {
+                _ = graph
+                    .edges
+                    .insert((group.id.clone(), extends.clone(), EdgeKind::Extends));
+            }
+            for attr in group.attributes.iter() {
+                if let AttributeSpec::Ref { r#ref, .. } = attr {
+                    if let Some(target) = attribute_groups.get(r#ref) {
+                        if target != &group.id {
+                            _ = graph.edges.insert((
+                                group.id.clone(),
+                                target.clone(),
+                                EdgeKind::AttributeRef,
+                            ));
+                        }
+                    }
+                }
+            }
+            for constraint in group.constraints.iter() {
+                if let Some(include) = &constraint.include {
+                    _ = graph
+                        .edges
+                        .insert((group.id.clone(), include.clone(), EdgeKind::Include));
+                }
+            }
+            for event in group.events.iter() {
+                _ = graph
+                    .edges
+                    .insert((group.id.clone(), event.clone(), EdgeKind::Event));
+            }
+        }
+
+        graph
+    }
+
+    /// Returns the transitive dependencies of a group (extended groups,
+    /// groups defining the referenced attributes, included groups and
+    /// events), or `None` if the group is not defined in the registry.
+    pub fn dependency_tree(&self, group_id: &str) -> Option<DependencyTree> {
+        let r#type = self.nodes.get(group_id)?;
+        let mut cycles = vec![];
+        let mut expanded = BTreeSet::new();
+        let mut path = vec![group_id.to_owned()];
+        let root = DependencyNode {
+            group_id: group_id.to_owned(),
+            r#type: Some(r#type.clone()),
+            kind: None,
+            cycle: false,
+            repeated: false,
+            dependencies: self.dependencies(&mut path, &mut expanded, &mut cycles),
+        };
+        Some(DependencyTree { root, cycles })
+    }
+
+    /// Returns the dependency nodes of the last group of the path (the
+    /// path of the group from the root of the tree).
+    fn dependencies(
+        &self,
+        path: &mut Vec<String>,
+        expanded: &mut BTreeSet<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) -> Vec<DependencyNode> {
+        let group_id = path.last().cloned().unwrap_or_default();
+        _ = expanded.insert(group_id.clone());
+
+        let mut dependencies = vec![];
+        for (_, target, kind) in self.edges.iter().filter(|(s, _, _)| *s == group_id) {
+            let mut node = DependencyNode {
+                group_id: target.clone(),
+                r#type: self.nodes.get(target).cloned(),
+                kind: Some(kind.label()),
+                cycle: false,
+                repeated: false,
+                dependencies: vec![],
+            };
+            if let Some(start) = path.iter().position(|id| id == target) {
+                node.cycle = true;
+                let mut cycle = path[start..].to_vec();
+                cycle.push(target.clone());
+                cycles.push(cycle);
+            } else if expanded.contains(target) {
+                node.repeated = self.edges.iter().any(|(s, _, _)| s == target);
+            } else {
+                path.push(target.clone());
+                node.dependencies = self.dependencies(path, expanded, cycles);
+                _ = path.pop();
+            }
+            dependencies.push(node);
+        }
+        dependencies
+    }
+
+    /// Renders the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph registry {\n  rankdir=LR;\n  node [shape=box];\n");
+        for (id, r#type) in self.nodes.iter() {
+            dot.push_str(&format!(
+                "  \"{}\" [label=\"{}\\n({})\"];\n",
+                id, id, r#type
+            ));
+        }
+        for (source, target, kind) in self.edges.iter() {
+            let style = match kind {
+                EdgeKind::Extends => "solid",
+                EdgeKind::AttributeRef => "dashed",
+                EdgeKind::Include => "dotted",
+                EdgeKind::Event => "bold",
+            };
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{}\", style={}];\n",
+                source,
+                target,
+                kind.label(),
+                style
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        // Mermaid node ids can't contain dots, so every group (including the
+        // targets not defined in the registry) gets a synthetic id.
+        let mut node_ids = BTreeMap::new();
+        for id in self
+            .nodes
+            .keys()
+            .chain(self.edges.iter().map(|(_, t, _)| t))
+        {
+            let next_id = format!("g{}", node_ids.len());
+            _ = node_ids.entry(id.clone()).or_insert(next_id);
+        }
+
+        let mut mermaid = String::from("graph LR\n");
+        for (id, node_id) in node_ids.iter() {
+            mermaid.push_str(&format!("  {}[\"{}\"]\n", node_id, id));
+        }
+        for (source, target, kind) in self.edges.iter() {
+            let arrow = match kind {
+                EdgeKind::Extends => "-->",
+                EdgeKind::AttributeRef => "-.->",
+                EdgeKind::Include => "==>",
+                EdgeKind::Event => "--o",
+            };
+            mermaid.push_str(&format!(
+                "  {} {}|{}| {}\n",
+                node_ids[source],
+                arrow,
+                kind.label(),
+                node_ids[target]
+            ));
+        }
+        mermaid
+    }
+}
+
+impl DependencyTree {
+    /// Renders the tree as text, one group per line. The dependencies
+    /// closing a cycle are flagged with `(cycle)` and the groups whose
+    /// dependencies are listed in another branch with `(*)`.
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{}\n", Self::node_label(&self.root));
+        Self::append_children(&self.root, "", &mut text);
+        text
+    }
+
+    fn append_children(node: &DependencyNode, prefix: &str, text: &mut String) {
+        for (i, child) in node.dependencies.iter().enumerate() {
+            let last = i + 1 == node.dependencies.len();
+            text.push_str(prefix);
+            text.push_str(if last { "└── " } else { "├── " });
+            text.push_str(&Self::node_label(child));
+            text.push('\n');
+            let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            Self::append_children(child, &child_prefix, text);
+        }
+    }
+
+    fn node_label(node: &DependencyNode) -> String {
+        let mut label = match node.kind {
+            Some(kind) => format!("{} {}", kind, node.group_id),
+            None => node.group_id.clone(),
+        };
+        match &node.r#type {
+            Some(r#type) => label.push_str(&format!(" ({})", r#type)),
+            None => label.push_str(" (undefined)"),
+        }
+        if node.cycle {
+            label.push_str(" (cycle)");
+        } else if node.repeated {
+            label.push_str(" (*)");
+        }
+        label
+    }
+}
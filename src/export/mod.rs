@@ -0,0 +1,380 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to export a semantic convention registry or a telemetry schema into
+//! other formats.
+
+use std::path::PathBuf;
+use std::process::exit;
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::{requirements, SemConvImport};
+use weaver_semconv::ResolverConfig;
+
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE};
+
+mod alerts;
+mod dashboard;
+mod deprecation;
+pub(crate) mod graph;
+mod prometheus;
+
+/// Parameters for the `export` command
+#[derive(Args)]
+pub struct ExportCommand {
+    /// Define the sub-commands for the `export` command
+    #[clap(subcommand)]
+    pub command: ExportSubCommand,
+}
+
+/// Sub-commands for the `export` command
+#[derive(Subcommand)]
+pub enum ExportSubCommand {
+    /// Export the group graph of a semantic convention registry
+    Graph(ExportGraph),
+    /// Export the attribute requirement matrix of each span, event and metric
+    /// of a telemetry schema
+    Requirements(ExportRequirements),
+    /// Export a starter Grafana dashboard of the metrics of a semantic
+    /// convention registry
+    Dashboard(ExportDashboard),
+    /// Export Prometheus alerting rule scaffolds for the metrics of a
+    /// semantic convention registry
+    Alerts(ExportAlerts),
+    /// Export the mapping of the deprecated attribute and metric names to
+    /// their new names (JSON), for the telemetry backends normalizing the
+    /// telemetry emitted with old names
+    DeprecationMap(ExportDeprecationMap),
+    /// Export the catalog of the resource entities (attribute sets and
+    /// identities of the resource groups) of a semantic convention registry
+    /// (JSON), for the entity modeling of the telemetry backends
+    Entities(ExportEntities),
+}
+
+/// Supported graph formats
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT format
+    Dot,
+    /// Mermaid flowchart format
+    Mermaid,
+}
+
+/// Supported requirement matrix formats
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum RequirementsFormat {
+    /// One CSV line per attribute of each signal
+    Csv,
+    /// One Markdown table per signal
+    Markdown,
+}
+
+/// Parameters for the `export graph` sub-command
+#[derive(Args)]
+pub struct ExportGraph {
+    /// Git URL of the semantic convention registry
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Format of the exported graph
+    #[arg(short, long, value_enum, default_value = "dot")]
+    pub format: GraphFormat,
+
+    /// Output file to write the graph to
+    /// If not specified, the graph is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Parameters for the `export requirements` sub-command
+#[derive(Args)]
+pub struct ExportRequirements {
+    /// Telemetry schema file
+    pub schema: PathBuf,
+
+    /// Format of the exported matrices
+    #[arg(short, long, value_enum, default_value = "csv")]
+    pub format: RequirementsFormat,
+
+    /// Output file to write the matrices to
+    /// If not specified, the matrices are printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Parameters for the `export dashboard` sub-command
+#[derive(Args)]
+pub struct ExportDashboard {
+    /// Git URL of the semantic convention registry
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Title of the dashboard
+    #[arg(short, long, default_value = "Semantic convention metrics")]
+    pub title: String,
+
+    /// Telemetry schema file whose service metadata (owner, tier, runbook
+    /// and SLO links) is carried by the dashboard
+    #[arg(long, value_name = "FILE")]
+    pub schema: Option<PathBuf>,
+
+    /// Output file to write the dashboard to
+    /// If not specified, the dashboard is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Parameters for the `export alerts` sub-command
+#[derive(Args)]
+pub struct ExportAlerts {
+    /// Git URL of the semantic convention registry
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Output file to write the rules to
+    /// If not specified, the rules are printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Parameters for the `export deprecation-map` sub-command
+#[derive(Args)]
+pub struct ExportDeprecationMap {
+    /// Git URL of the semantic convention registry
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Telemetry schema whose `versions` section (including the versions of
+    /// its parent schema) declares the renames
+    #[arg(short, long)]
+    pub schema: Option<PathBuf>,
+
+    /// Version targeted for the removal of the deprecated names
+    #[arg(long, value_name = "VERSION")]
+    pub removal_target: Option<String>,
+
+    /// Output file to write the mapping to
+    /// If not specified, the mapping is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Parameters for the `export entities` sub-command
+#[derive(Args)]
+pub struct ExportEntities {
+    /// Git URL of the semantic convention registry
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Output file to write the catalog to
+    /// If not specified, the catalog is printed to stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Export a semantic convention registry
+pub fn command_export(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &ExportCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+
+    match command.command {
+        ExportSubCommand::Graph(ref command) => {
+            let registry = SchemaResolver::load_semconv_registry(
+                command.registry.clone(),
+                command.path.clone(),
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+
+            let graph = graph::GroupGraph::from_registry(&registry);
+            let content = match command.format {
+                GraphFormat::Dot => graph.to_dot(),
+                GraphFormat::Mermaid => graph.to_mermaid(),
+            };
+            write_output(log, command.output.as_ref(), &content);
+        }
+        ExportSubCommand::Requirements(ref command) => {
+            let schema = SchemaResolver::resolve_schema_file(&command.schema, &cache, log.clone())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+
+            let matrices = schema.requirement_matrices();
+            let content = match command.format {
+                RequirementsFormat::Csv => requirements::to_csv(&matrices),
+                RequirementsFormat::Markdown => requirements::to_markdown(&matrices),
+            };
+            write_output(log, command.output.as_ref(), &content);
+        }
+        ExportSubCommand::Dashboard(ref command) => {
+            let metrics = registry_metrics(log.clone(), &cache, &command.registry, &command.path);
+            let schema = command.schema.as_ref().map(|schema| {
+                SchemaResolver::load_schema_from_path(schema, log.clone()).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                })
+            });
+            let dashboard = dashboard::dashboard(
+                &command.title,
+                &metrics,
+                schema.as_ref().and_then(|schema| schema.service_metadata()),
+            );
+            match serde_json::to_string_pretty(&dashboard) {
+                Ok(content) => write_output(log, command.output.as_ref(), &content),
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(FAILURE);
+                }
+            }
+        }
+        ExportSubCommand::Alerts(ref command) => {
+            let metrics = registry_metrics(log.clone(), &cache, &command.registry, &command.path);
+            let rules = alerts::alerting_rules(&metrics);
+            match serde_yaml::to_string(&rules) {
+                Ok(content) => write_output(log, command.output.as_ref(), &content),
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(FAILURE);
+                }
+            }
+        }
+        ExportSubCommand::DeprecationMap(ref command) => {
+            let registry = SchemaResolver::load_semconv_registry(
+                command.registry.clone(),
+                command.path.clone(),
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            let schema = command.schema.as_ref().map(|schema| {
+                SchemaResolver::resolve_schema_file(schema, &cache, log.clone()).unwrap_or_else(
+                    |e| {
+                        log.error(&e.to_string());
+                        exit(e.exit_code());
+                    },
+                )
+            });
+
+            let map = deprecation::deprecation_map(
+                &registry,
+                schema.as_ref().and_then(|schema| schema.versions.as_ref()),
+                command.removal_target.as_deref(),
+            );
+            match serde_json::to_string_pretty(&map) {
+                Ok(content) => write_output(log, command.output.as_ref(), &content),
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(FAILURE);
+                }
+            }
+        }
+        ExportSubCommand::Entities(ref command) => {
+            let schema = resolve_registry(log.clone(), &cache, &command.registry, &command.path);
+            let catalog = schema.resource_entities();
+            if catalog.entities.is_empty() {
+                log.warn("No resource groups defined in the registry");
+            }
+            match serde_json::to_string_pretty(&catalog) {
+                Ok(content) => write_output(log, command.output.as_ref(), &content),
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(FAILURE);
+                }
+            }
+        }
+    }
+}
+
+/// Resolves a semantic convention registry and returns its metrics with
+/// their Prometheus names.
+fn registry_metrics(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    registry: &str,
+    path: &Option<String>,
+) -> Vec<prometheus::PromMetric> {
+    let schema = resolve_registry(log.clone(), cache, registry, path);
+    let metrics = prometheus::metrics(&schema);
+    if metrics.is_empty() {
+        log.warn("No metrics defined in the registry");
+    }
+    metrics
+}
+
+/// Loads and resolves a semantic convention registry.
+fn resolve_registry(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    registry: &str,
+    path: &Option<String>,
+) -> ResolvedTelemetrySchema {
+    let imports = [SemConvImport::GitUrl {
+        git_url: registry.to_owned(),
+        path: path.clone(),
+        mirrors: vec![],
+    }];
+    let mut registry = SchemaResolver::semconv_registry_from_imports(
+        &imports,
+        ResolverConfig::default(),
+        cache,
+        log.clone(),
+    )
+    .unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+    SchemaResolver::resolve_semantic_convention_registry(&mut registry, log.clone()).unwrap_or_else(
+        |e| {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        },
+    )
+}
+
+/// Writes the content to the output file or to stdout if no output file
+/// is specified.
+fn write_output(log: impl Logger, output: Option<&PathBuf>, content: &str) {
+    if let Some(output) = output {
+        if let Err(e) = std::fs::write(output, content) {
+            log.error(&format!("Failed to write to {}: {}", output.display(), e));
+            exit(FAILURE)
+        }
+        log.success(&format!("Saved export to '{}'", output.display()));
+    } else {
+        log.log(content);
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metrics of a resolved semantic convention registry with their Prometheus
+//! names, following the translation of the OTLP metrics by the Prometheus
+//! exporters (dots replaced by underscores, unit suffix, `_total` suffix for
+//! the counters).
+
+use weaver_resolved_schema::attribute::RequirementLevel;
+use weaver_resolved_schema::metric::Instrument;
+use weaver_resolved_schema::registry::TypedGroup;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+
+/// A metric of the registry with its Prometheus name and labels.
+#[derive(Debug, Clone)]
+pub struct PromMetric {
+    /// The OTel metric name.
+    pub name: String,
+    /// The Prometheus metric name (without the `_bucket`, `_sum` and
+    /// `_count` suffixes of the histograms).
+    pub prom_name: String,
+    /// The brief of the metric.
+    pub brief: String,
+    /// The unit of the metric.
+    pub unit: Option<String>,
+    /// The instrument of the metric.
+    pub instrument: Instrument,
+    /// The key attributes of the metric (required and conditionally
+    /// required attributes).
+    pub key_attributes: Vec<KeyAttribute>,
+}
+
+/// A key attribute of a metric.
+#[derive(Debug, Clone)]
+pub struct KeyAttribute {
+    /// The OTel attribute name.
+    pub name: String,
+    /// The Prometheus label name.
+    pub label: String,
+    /// Whether the attribute is required (otherwise conditionally required).
+    pub required: bool,
+}
+
+/// Returns the metrics of the resolved registries, sorted by name.
+pub fn metrics(schema: &ResolvedTelemetrySchema) -> Vec<PromMetric> {
+    let mut metrics: Vec<PromMetric> = schema
+        .registries
+        .iter()
+        .flat_map(|registry| registry.groups.iter())
+        .filter_map(|group| {
+            let TypedGroup::Metric {
+                metric_name: Some(metric_name),
+                instrument: Some(instrument),
+                unit,
+            } = &group.typed_group
+            else {
+                return None;
+            };
+            let key_attributes = group
+                .attributes
+                .iter()
+                .filter_map(|attr_ref| schema.catalog.attributes.get(attr_ref.0 as usize))
+                .filter(|attr| {
+                    matches!(
+                        attr.requirement_level,
+                        RequirementLevel::Required | RequirementLevel::ConditionallyRequired { .. }
+                    )
+                })
+                .map(|attr| KeyAttribute {
+                    name: attr.name.clone(),
+                    label: label_name(&attr.name),
+                    required: attr.requirement_level == RequirementLevel::Required,
+                })
+                .collect();
+            Some(PromMetric {
+                name: metric_name.clone(),
+                prom_name: metric_name_with_suffixes(metric_name, unit.as_deref(), instrument),
+                brief: group.brief.trim().to_owned(),
+                unit: unit.clone(),
+                instrument: instrument.clone(),
+                key_attributes,
+            })
+        })
+        .collect();
+    metrics.sort_by(|a, b| a.name.cmp(&b.name));
+    metrics.dedup_by(|a, b| a.name == b.name);
+    metrics
+}
+
+/// Returns the Prometheus label name of an attribute.
+pub fn label_name(attribute: &str) -> String {
+    sanitize(attribute)
+}
+
+/// Returns the Prometheus name of a metric, i.e. the sanitized OTel name
+/// followed by the unit suffix and, for the counters, by `_total`.
+fn metric_name_with_suffixes(name: &str, unit: Option<&str>, instrument: &Instrument) -> String {
+    let mut prom_name = sanitize(name);
+    let unit_suffix = unit.and_then(|unit| unit_suffix(unit, instrument));
+    if let Some(unit_suffix) = unit_suffix {
+        if !prom_name.ends_with(&format!("_{}", unit_suffix)) {
+            prom_name.push('_');
+            prom_name.push_str(&unit_suffix);
+        }
+    }
+    if *instrument == Instrument::Counter && !prom_name.ends_with("_total") {
+        prom_name.push_str("_total");
+    }
+    prom_name
+}
+
+/// Returns the Prometheus suffix of a UCUM unit, or `None` if the unit has
+/// no suffix (annotations such as `{request}`).
+fn unit_suffix(unit: &str, instrument: &Instrument) -> Option<String> {
+    fn word(unit: &str) -> Option<&str> {
+        Some(match unit {
+            "d" => "days",
+            "h" => "hours",
+            "min" => "minutes",
+            "s" => "seconds",
+            "ms" => "milliseconds",
+            "us" => "microseconds",
+            "ns" => "nanoseconds",
+            "By" => "bytes",
+            "KiBy" => "kibibytes",
+            "MiBy" => "mebibytes",
+            "GiBy" => "gibibytes",
+            "KBy" => "kilobytes",
+            "MBy" => "megabytes",
+            "GBy" => "gigabytes",
+            "m" => "meters",
+            "V" => "volts",
+            "A" => "amperes",
+            "J" => "joules",
+            "W" => "watts",
+            "g" => "grams",
+            "Cel" => "celsius",
+            "Hz" => "hertz",
+            "%" => "percent",
+            _ if unit.starts_with('{') || unit.is_empty() => return None,
+            _ => unit,
+        })
+    }
+    let unit = unit.trim();
+    if unit == "1" {
+        return (*instrument == Instrument::Gauge).then(|| "ratio".to_owned());
+    }
+    match unit.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator = match denominator {
+                "s" => Some("second"),
+                "min" => Some("minute"),
+                "h" => Some("hour"),
+                "d" => Some("day"),
+                _ => word(denominator),
+            };
+            match (word(numerator), denominator) {
+                (Some(numerator), Some(denominator)) => {
+                    Some(sanitize(&format!("{}_per_{}", numerator, denominator)))
+                }
+                (None, Some(denominator)) => Some(sanitize(&format!("per_{}", denominator))),
+                (numerator, None) => numerator.map(sanitize),
+            }
+        }
+        None => word(unit).map(sanitize),
+    }
+}
+
+/// Replaces the characters not allowed in the Prometheus names by `_`.
+fn sanitize(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
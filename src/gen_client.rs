@@ -3,10 +3,15 @@
 //! Command to generate a client SDK.
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::Parser;
 
 use weaver_logger::Logger;
+
+use crate::cli::{CacheArgs, DeprecatedArg, StabilityArg, TimingsArgs};
+use crate::exit_code::ExitCode;
+use weaver_template::guard::RenderLimits;
 use weaver_template::sdkgen::ClientSdkGenerator;
 use weaver_template::GeneratorConfig;
 
@@ -21,23 +26,94 @@ pub struct GenClientCommand {
     #[arg(short, long)]
     language: String,
 
+    /// Directory of local templates layered over the installed language
+    /// packs: its templates override the templates with the same relative
+    /// path, the missing ones falling back to the installed pack
+    #[arg(long, value_name = "DIR")]
+    template_dir: Option<PathBuf>,
+
     /// Output directory where the client API will be generated
     #[arg(short, long, value_name = "DIR")]
     output_dir: PathBuf,
+
+    /// Maximum time (in seconds) to render a template
+    #[arg(long, value_name = "SECS", default_value = "30")]
+    max_render_time: u64,
+
+    /// Maximum size (in bytes) of the code generated by a template
+    #[arg(long, value_name = "BYTES", default_value = "10485760")]
+    max_output_size: usize,
+
+    /// Maximum number of steps (filter, function and tester calls, writes of
+    /// rendered text) while rendering a template
+    #[arg(long, value_name = "N", default_value = "10000000")]
+    max_iterations: usize,
+
+    /// Also generate the test scaffold of the language (templates of its
+    /// `tests/` directory)
+    #[arg(long)]
+    with_tests: bool,
+
+    /// Number of versions preceding the latest version of the schema for which
+    /// compatibility modules are generated (`compat` templates)
+    #[arg(long, value_name = "N", default_value = "0")]
+    compat_versions: usize,
+
+    /// Minimum stability of the attributes used to generate the client SDK
+    #[arg(long, value_enum, default_value = "all")]
+    stability: StabilityArg,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    build_tools_compat: bool,
+
+    /// How the deprecated attributes and groups appear in the generated code
+    /// (defaults to the `deprecated` field of the language config, itself
+    /// `annotate` by default)
+    #[arg(long, value_enum)]
+    deprecated: Option<DeprecatedArg>,
+
+    /// Don't format the generated files with the `formatter` of the language
+    /// config
+    #[arg(long)]
+    no_format: bool,
+
+    /// Timing breakdown option
+    #[command(flatten)]
+    pub timings: TimingsArgs,
 }
 
 /// Generate a client SDK (application)
-pub fn command_gen_client(log: impl Logger + Sync + Clone, params: &GenClientCommand) {
+pub fn command_gen_client(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    params: &GenClientCommand,
+) {
     log.loading(&format!(
         "Generating client SDK for language {}",
         params.language
     ));
-    let generator = match ClientSdkGenerator::try_new(&params.language, GeneratorConfig::default())
-    {
+    let config = GeneratorConfig::default()
+        .with_override_template_dir(params.template_dir.clone())
+        .with_cache_dir(cache_args.cache_dir.clone())
+        .with_refresh_cache(cache_args.refresh)
+        .with_render_limits(RenderLimits {
+            max_render_time: Duration::from_secs(params.max_render_time),
+            max_output_size: params.max_output_size,
+            max_iterations: params.max_iterations,
+        })
+        .with_tests(params.with_tests)
+        .with_compat_versions(params.compat_versions)
+        .with_stability_filter(params.stability.into())
+        .with_build_tools_compat(params.build_tools_compat)
+        .with_deprecated_policy(params.deprecated.map(Into::into))
+        .with_formatting(!params.no_format);
+    let generator = match ClientSdkGenerator::try_new(&params.language, config) {
         Ok(gen) => gen,
         Err(e) => {
             log.error(&format!("{}", e));
-            std::process::exit(1);
+            std::process::exit(e.exit_code());
         }
     };
 
@@ -49,7 +125,7 @@ pub fn command_gen_client(log: impl Logger + Sync + Clone, params: &GenClientCom
         )
         .map_err(|e| {
             log.error(&format!("{}", e));
-            std::process::exit(1);
+            std::process::exit(e.exit_code());
         })
         .unwrap();
 
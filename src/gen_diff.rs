@@ -0,0 +1,344 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to compare the client SDKs generated from two versions of a
+//! semantic convention registry.
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use clap::{Args, Subcommand, ValueEnum};
+
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::SemConvImport;
+use weaver_template::sdkgen::ClientSdkGenerator;
+use weaver_template::GeneratorConfig;
+
+use crate::cli::{CacheArgs, DeprecatedArg, StabilityArg};
+use crate::exit_code::{ExitCode, FAILURE};
+
+/// Specify the `gen` command
+#[derive(Args)]
+pub struct GenCommand {
+    /// Define the sub-commands for the `gen` command
+    #[clap(subcommand)]
+    pub command: GenSubCommand,
+}
+
+/// Sub-commands for the `gen` command
+#[derive(Subcommand)]
+pub enum GenSubCommand {
+    /// Generate the client SDK of a telemetry schema against two versions of
+    /// a semantic convention registry and summarize the differences of their
+    /// public API (added, removed and changed items)
+    Diff(GenDiff),
+}
+
+/// Parameters for the `gen diff` sub-command
+#[derive(Args)]
+pub struct GenDiff {
+    /// Telemetry schema file to generate the client SDKs for (its imported
+    /// semantic conventions are replaced by the compared registries)
+    #[arg(short, long, value_name = "FILE")]
+    pub schema: PathBuf,
+
+    /// Language to generate the client SDKs for
+    #[arg(short, long)]
+    pub language: String,
+
+    /// Registry of the first client SDK (local directory or git URL)
+    #[arg(long, value_name = "REGISTRY")]
+    pub from: String,
+
+    /// Git reference (branch or tag) of the first registry, the default
+    /// branch if not set
+    #[arg(long, value_name = "REF")]
+    pub from_ref: Option<String>,
+
+    /// Registry of the second client SDK (local directory or git URL)
+    #[arg(long, value_name = "REGISTRY")]
+    pub to: String,
+
+    /// Git reference (branch or tag) of the second registry, the default
+    /// branch if not set
+    #[arg(long, value_name = "REF")]
+    pub to_ref: Option<String>,
+
+    /// Optional path in the git repositories where the semantic convention
+    /// registries are located
+    #[arg(long)]
+    pub path: Option<String>,
+
+    /// Directory of local templates layered over the installed language
+    /// packs
+    #[arg(long, value_name = "DIR")]
+    pub template_dir: Option<PathBuf>,
+
+    /// Minimum stability of the attributes used to generate the client SDKs
+    #[arg(long, value_enum, default_value = "all")]
+    pub stability: StabilityArg,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+
+    /// How the deprecated attributes and groups appear in the generated code
+    /// (defaults to the `deprecated` field of the language config)
+    #[arg(long, value_enum)]
+    pub deprecated: Option<DeprecatedArg>,
+
+    /// Output format of the diff
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: DiffFormat,
+}
+
+/// Output formats of the `gen diff` sub-command
+#[derive(Clone, ValueEnum)]
+pub enum DiffFormat {
+    /// A human readable summary followed by the added, removed and changed
+    /// API items
+    Text,
+    /// A JSON document
+    Json,
+}
+
+/// Generate and compare client SDKs
+pub fn command_gen(log: impl Logger + Sync + Clone, cache_args: &CacheArgs, command: &GenCommand) {
+    match command.command {
+        GenSubCommand::Diff(ref command) => {
+            let cache = cache_args.cache().unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            let config = GeneratorConfig::default()
+                .with_override_template_dir(command.template_dir.clone())
+                .with_cache_dir(cache_args.cache_dir.clone())
+                .with_refresh_cache(cache_args.refresh)
+                .with_stability_filter(command.stability.into())
+                .with_build_tools_compat(command.build_tools_compat)
+                .with_deprecated_policy(command.deprecated.map(Into::into));
+            let generator =
+                ClientSdkGenerator::try_new(&command.language, config).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+
+            let from = Registry {
+                location: &command.from,
+                git_ref: command.from_ref.as_deref(),
+            };
+            let to = Registry {
+                location: &command.to,
+                git_ref: command.to_ref.as_deref(),
+            };
+            let from_dir = generate(&log, &cache, &generator, command, &from);
+            let to_dir = generate(&log, &cache, &generator, command, &to);
+            let diff = generator
+                .api_diff(from_dir.path(), to_dir.path())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+
+            match command.format {
+                DiffFormat::Text => {
+                    log.log(diff.to_string().trim_end());
+                }
+                DiffFormat::Json => match serde_json::to_string_pretty(&diff) {
+                    Ok(json) => {
+                        log.log(&json);
+                    }
+                    Err(e) => {
+                        log.error(&e.to_string());
+                        exit(FAILURE);
+                    }
+                },
+            }
+            if diff.is_empty() {
+                log.success(&format!(
+                    "No differences between the {} client SDKs of {} and {}",
+                    command.language, from, to
+                ));
+            }
+        }
+    }
+}
+
+/// A compared registry.
+struct Registry<'a> {
+    /// The local directory or git URL of the registry.
+    location: &'a str,
+    /// The git reference checked out (default branch if `None`).
+    git_ref: Option<&'a str>,
+}
+
+impl Display for Registry<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.git_ref {
+            Some(git_ref) => write!(f, "{} ({})", self.location, git_ref),
+            None => write!(f, "{}", self.location),
+        }
+    }
+}
+
+/// Generates the client SDK of the schema against the given registry in a
+/// temporary directory (deleted when dropped).
+fn generate(
+    log: &(impl Logger + Sync + Clone),
+    cache: &Cache,
+    generator: &ClientSdkGenerator,
+    command: &GenDiff,
+    registry: &Registry<'_>,
+) -> tempdir::TempDir {
+    log.loading(&format!(
+        "Generating the {} client SDK of {}",
+        command.language, registry
+    ));
+    let mut schema = SchemaResolver::load_schema_from_path(&command.schema, log.clone())
+        .unwrap_or_else(|e| {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        });
+    let imports = [
+        registry_import(cache, registry, command.path.clone()).unwrap_or_else(|e| {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        }),
+    ];
+    let sem_conv_catalog = SchemaResolver::semconv_registry_from_imports(
+        &imports,
+        generator.resolver_config(),
+        cache,
+        log.clone(),
+    )
+    .unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+    SchemaResolver::resolve_schema_with_registry(
+        &mut schema,
+        &command.schema.display().to_string(),
+        sem_conv_catalog,
+        log.clone(),
+    )
+    .unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+
+    let output_dir = tempdir::TempDir::new("weaver-gen-diff").unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(FAILURE);
+    });
+    generator
+        .generate_from_schema(
+            log.clone(),
+            &schema,
+            command.schema.clone(),
+            output_dir.path().to_path_buf(),
+        )
+        .unwrap_or_else(|e| {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        });
+    output_dir
+}
+
+/// Returns the import of a registry given as a local directory (made
+/// absolute, the local imports being relative to the schema) or a git URL.
+/// The git reference of a registry, if any, is checked out in the cache and
+/// imported as a local directory.
+fn registry_import(
+    cache: &Cache,
+    registry: &Registry<'_>,
+    path: Option<String>,
+) -> Result<SemConvImport, weaver_cache::Error> {
+    if let Some(git_ref) = registry.git_ref {
+        let local_path =
+            cache.git_repo_at(registry.location.to_owned(), Some(git_ref.to_owned()), path)?;
+        return Ok(SemConvImport::LocalPath {
+            local_path: local_path.display().to_string(),
+        });
+    }
+    Ok(match Path::new(registry.location).canonicalize() {
+        Ok(local_path) => SemConvImport::LocalPath {
+            local_path: local_path.display().to_string(),
+        },
+        Err(_) => SemConvImport::GitUrl {
+            git_url: registry.location.to_owned(),
+            path,
+            mirrors: vec![],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_registry_import() {
+        let git = |dir: &Path, args: &[&str]| {
+            let status = Command::new("git")
+                .args([
+                    "-c",
+                    "user.name=weaver",
+                    "-c",
+                    "user.email=weaver@example.com",
+                ])
+                .args(args)
+                .current_dir(dir)
+                .status();
+            assert!(
+                status.is_ok_and(|status| status.success()),
+                "git {:?}",
+                args
+            );
+        };
+        let remote = TempDir::new("weaver-registry").unwrap();
+        git(remote.path(), &["init", "-q", "-b", "main"]);
+        std::fs::create_dir_all(remote.path().join("model")).unwrap();
+        for version in ["v1", "v2"] {
+            std::fs::write(remote.path().join("model/version"), version).unwrap();
+            git(remote.path(), &["add", "model"]);
+            git(remote.path(), &["commit", "-q", "-m", version]);
+            git(remote.path(), &["tag", version]);
+        }
+        let url = format!("file://{}", remote.path().display());
+        let cache_dir = TempDir::new("weaver-cache").unwrap();
+        let cache = Cache::try_new(Some(cache_dir.path().to_path_buf())).unwrap();
+
+        // Two references of the same repo are checked out separately.
+        let version = |git_ref: &str| {
+            let registry = Registry {
+                location: &url,
+                git_ref: Some(git_ref),
+            };
+            match registry_import(&cache, &registry, Some("model".to_owned())).unwrap() {
+                SemConvImport::LocalPath { local_path } => {
+                    std::fs::read_to_string(Path::new(&local_path).join("version")).unwrap()
+                }
+                import => panic!("unexpected import: {:?}", import),
+            }
+        };
+        assert_eq!(version("v1"), "v1");
+        assert_eq!(version("v2"), "v2");
+
+        // Without reference, the git URL is imported as is.
+        let registry = Registry {
+            location: &url,
+            git_ref: None,
+        };
+        assert!(matches!(
+            registry_import(&cache, &registry, None).unwrap(),
+            SemConvImport::GitUrl { .. }
+        ));
+    }
+}
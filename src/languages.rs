@@ -7,6 +7,8 @@ use std::path::PathBuf;
 
 use weaver_logger::Logger;
 
+use crate::exit_code::FAILURE;
+
 /// Parameters for the `languages` command
 #[derive(Parser)]
 pub struct LanguagesParams {
@@ -23,18 +25,18 @@ pub fn command_languages(log: impl Logger + Sync + Clone, params: &LanguagesPara
         Ok(dir) => dir,
         Err(e) => {
             log.error(&format!("Failed to read templates directory: {}", e));
-            std::process::exit(1);
+            std::process::exit(FAILURE);
         }
     };
     for entry in template_dir {
         if let Ok(entry) = entry {
             if entry.file_type().is_ok() {
                 log.indent(1);
-                log.log(&format!("- {}", entry.file_name().to_str().unwrap()));
+                log.log(&format!("- {}", entry.file_name().to_string_lossy()));
             }
         } else {
             log.error("Failed to read template directory entry");
-            std::process::exit(1);
+            std::process::exit(FAILURE);
         }
     }
 }
@@ -0,0 +1,790 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to run a minimal language server (LSP over stdio) for the
+//! authoring of semantic convention YAML files:
+//! - diagnostics as-you-type: YAML syntax, unknown fields, invalid
+//!   definitions, duplicate ids and attribute references not found in the
+//!   registry,
+//! - go-to-definition of the attribute references,
+//! - hover of the attribute ids and references (brief, type and requirement
+//!   level).
+//!
+//! The registry is made of the semantic convention files of the `--registry`
+//! directory (the root of the workspace by default). The content of the open
+//! files replaces their content on disk. The registry is resolved again after
+//! each change (to compute the diagnostics of the changed document) and the
+//! resolved registry is reused by the following requests until the next
+//! change.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use clap::Args;
+use serde_json::{json, Value};
+
+use weaver_logger::Logger;
+use weaver_resolver::SchemaResolver;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::{Error, ResolverConfig, SemConvSpec, SemConvSpecWithProvenance, SemConvSpecs};
+
+use crate::exit_code::FAILURE;
+
+/// Parameters for the `lsp` command
+#[derive(Args)]
+pub struct LspCommand {
+    /// Directory of the semantic convention registry against which the
+    /// references are resolved (defaults to the root of the workspace)
+    #[arg(short, long, value_name = "DIR")]
+    pub registry: Option<PathBuf>,
+}
+
+/// LSP error code of an unknown method.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// LSP diagnostic severities.
+const ERROR: u8 = 1;
+const WARNING: u8 = 2;
+
+/// The state of the language server.
+struct Server {
+    /// The directory of the registry (set at initialization if not given).
+    registry_dir: Option<PathBuf>,
+    /// Path -> spec of the registry files, the open files being replaced by
+    /// their last valid content.
+    registry: BTreeMap<String, SemConvSpec>,
+    /// The resolved registry (as resolved as possible), `None` once the
+    /// registry has changed.
+    resolved: Option<SemConvSpecs>,
+    /// URI -> content of the open files.
+    documents: HashMap<String, String>,
+    /// True once the client has requested the shutdown.
+    shutdown: bool,
+}
+
+/// Runs the language server until the client exits.
+pub fn command_lsp(log: impl Logger, command: &LspCommand) {
+    let mut server = Server {
+        registry_dir: command.registry.clone(),
+        registry: BTreeMap::new(),
+        resolved: None,
+        documents: HashMap::new(),
+        shutdown: false,
+    };
+    let mut reader = std::io::stdin().lock();
+    let mut writer = std::io::stdout().lock();
+
+    loop {
+        let message = match read_message(&mut reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => exit(FAILURE),
+            Err(e) => {
+                log.error(&format!("Invalid LSP message: {}", e));
+                exit(FAILURE);
+            }
+        };
+        if message.get("method").and_then(Value::as_str) == Some("exit") {
+            exit(if server.shutdown { 0 } else { FAILURE });
+        }
+        for response in server.handle(&message) {
+            if let Err(e) = write_message(&mut writer, &response) {
+                log.error(&format!("Failed to write an LSP message: {}", e));
+                exit(FAILURE);
+            }
+        }
+    }
+}
+
+/// Reads a JSON-RPC message (`Content-Length` header, empty line, content).
+/// Returns `None` at the end of the input.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, String> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).map_err(|e| e.to_string())? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(value.trim().parse::<usize>().map_err(|e| e.to_string())?);
+            }
+        }
+    }
+    let content_length = content_length.ok_or("Missing Content-Length header")?;
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content).map_err(|e| e.to_string())?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+/// Writes a JSON-RPC message.
+fn write_message(writer: &mut impl Write, message: &Value) -> std::io::Result<()> {
+    let content = message.to_string();
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()
+}
+
+impl Server {
+    /// Handles a message and returns the messages to send back (response
+    /// and notifications).
+    fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let id = message.get("id").cloned();
+        let params = message.get("params").unwrap_or(&Value::Null);
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        let result = match method {
+            "initialize" => {
+                if self.registry_dir.is_none() {
+                    self.registry_dir = params["rootUri"]
+                        .as_str()
+                        .map(|uri| PathBuf::from(uri_to_path(uri)))
+                        .or_else(|| params["rootPath"].as_str().map(PathBuf::from));
+                }
+                self.load_registry();
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "definitionProvider": true,
+                        "hoverProvider": true,
+                    },
+                    "serverInfo": {
+                        "name": "weaver",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                })
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Value::Null
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                return self.update_document(uri, text.to_owned());
+            }
+            "textDocument/didChange" => {
+                // Full synchronization: the last change is the whole content.
+                let Some(text) = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str())
+                else {
+                    return vec![];
+                };
+                return self.update_document(uri, text.to_owned());
+            }
+            "textDocument/didClose" => {
+                _ = self.documents.remove(uri);
+                let path = uri_to_path(uri);
+                match SemConvSpecs::load_sem_conv_spec_from_file(Path::new(&path), false) {
+                    Ok((provenance, spec)) => _ = self.registry.insert(provenance, spec),
+                    Err(_) => _ = self.registry.remove(&path),
+                }
+                self.resolved = None;
+                return vec![publish_diagnostics(uri, vec![])];
+            }
+            "textDocument/definition" => self
+                .definition(uri, &params["position"])
+                .unwrap_or(Value::Null),
+            "textDocument/hover" => self.hover(uri, &params["position"]).unwrap_or(Value::Null),
+            _ => {
+                // Unknown notifications are ignored.
+                return match id {
+                    Some(id) => vec![json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("Method `{}` not supported", method),
+                        },
+                    })],
+                    None => vec![],
+                };
+            }
+        };
+
+        match id {
+            Some(id) => vec![json!({"jsonrpc": "2.0", "id": id, "result": result})],
+            None => vec![],
+        }
+    }
+
+    /// Loads the semantic convention files of the registry directory (the
+    /// invalid files are skipped, they are reported once open).
+    fn load_registry(&mut self) {
+        let Some(registry_dir) = self.registry_dir.as_ref() else {
+            return;
+        };
+        let registry_dir = registry_dir
+            .canonicalize()
+            .unwrap_or_else(|_| registry_dir.clone());
+        for path in SchemaResolver::semantic_convention_files(&registry_dir)
+            .into_iter()
+            .flatten()
+        {
+            if let Ok((provenance, spec)) = SemConvSpecs::load_sem_conv_spec_from_file(&path, false)
+            {
+                _ = self.registry.insert(provenance, spec);
+            }
+        }
+        self.resolved = None;
+    }
+
+    /// Updates the content of an open document and returns its diagnostics.
+    fn update_document(&mut self, uri: &str, text: String) -> Vec<Value> {
+        let path = uri_to_path(uri);
+        if let Ok(spec) = SemConvSpec::load_from_str(&text, &path) {
+            _ = self.registry.insert(path.clone(), spec);
+            self.resolved = None;
+        }
+        let diagnostics = self.diagnostics(&path, &text);
+        _ = self.documents.insert(uri.to_owned(), text);
+        vec![publish_diagnostics(uri, diagnostics)]
+    }
+
+    /// Returns the diagnostics of a document: the loading errors of the
+    /// document, then the resolution errors and warnings concerning it. The
+    /// registry resolved with a valid document is kept for the following
+    /// requests.
+    fn diagnostics(&mut self, path: &str, text: &str) -> Vec<Value> {
+        let mut specs = SemConvSpecs::default();
+        for (provenance, spec) in self.registry.iter() {
+            if provenance != path {
+                specs.append_sem_conv_spec(SemConvSpecWithProvenance {
+                    spec: spec.clone(),
+                    provenance: provenance.clone(),
+                });
+            }
+        }
+        if let Err(e) = specs.load_from_str(text, path) {
+            return vec![diagnostic(text, &e, ERROR)];
+        }
+
+        let result = specs.resolve(ResolverConfig::default().with_collect_errors());
+        self.resolved = Some(specs);
+        let errors = match result {
+            Ok(warnings) => {
+                return warnings
+                    .iter()
+                    .filter(|warning| error_provenance(&warning.error) == Some(path))
+                    .map(|warning| {
+                        // The references not found are errors for `registry check`.
+                        let severity = match warning.error {
+                            Error::InvalidAttribute { .. } => ERROR,
+                            _ => WARNING,
+                        };
+                        diagnostic(text, &warning.error, severity)
+                    })
+                    .collect();
+            }
+            Err(Error::CompoundError(errors)) => errors,
+            Err(e) => vec![e],
+        };
+        let (errors, other_errors): (Vec<Error>, Vec<Error>) = errors
+            .into_iter()
+            .partition(|e| error_provenance(e) == Some(path));
+        let mut diagnostics: Vec<Value> =
+            errors.iter().map(|e| diagnostic(text, e, ERROR)).collect();
+        if let Some(e) = other_errors.first() {
+            diagnostics.push(json!({
+                "range": line_range(text, 0, 0),
+                "severity": WARNING,
+                "source": "weaver",
+                "message": format!(
+                    "The registry can't be resolved ({} error(s) in other files): {}",
+                    other_errors.len(),
+                    e
+                ),
+            }));
+        }
+        diagnostics
+    }
+
+    /// Returns the resolved registry (as resolved as possible), resolving it
+    /// only if it has changed since the last resolution.
+    fn resolved_registry(&mut self) -> &SemConvSpecs {
+        let registry = &self.registry;
+        self.resolved.get_or_insert_with(|| {
+            let mut specs = SemConvSpecs::default();
+            specs.append_sem_conv_specs(
+                registry
+                    .iter()
+                    .map(|(provenance, spec)| SemConvSpecWithProvenance {
+                        spec: spec.clone(),
+                        provenance: provenance.clone(),
+                    })
+                    .collect(),
+            );
+            _ = specs.resolve(ResolverConfig::default().with_collect_errors());
+            specs
+        })
+    }
+
+    /// Returns the id of the attribute referenced or defined at the given
+    /// position of a document.
+    fn attribute_at(&self, uri: &str, position: &Value) -> Option<String> {
+        let text = self.documents.get(uri)?;
+        let line_index = position["line"].as_u64()? as usize;
+        let lines: Vec<&str> = text.lines().collect();
+        let line = lines.get(line_index)?;
+        if let Some(r#ref) = yaml_value(line, "ref") {
+            return Some(r#ref.to_owned());
+        }
+        let id = yaml_value(line, "id")?;
+        let prefix = attribute_prefix(&lines, line_index)?;
+        Some(qualified_id(prefix, id))
+    }
+
+    /// Returns the location of the definition of the attribute referenced at
+    /// the given position.
+    fn definition(&mut self, uri: &str, position: &Value) -> Option<Value> {
+        let attribute_id = self.attribute_at(uri, position)?;
+        let provenance = self
+            .resolved_registry()
+            .attribute_with_provenance(&attribute_id)?
+            .provenance
+            .clone();
+        let target_uri = path_to_uri(&provenance);
+        let text = match self.documents.get(&target_uri) {
+            Some(text) => text.clone(),
+            None => std::fs::read_to_string(&provenance).ok()?,
+        };
+        let line = attribute_line(&text, &attribute_id).unwrap_or(0);
+        Some(json!({"uri": target_uri, "range": line_range(&text, line, 0)}))
+    }
+
+    /// Returns the hover (brief, type and requirement level) of the attribute
+    /// referenced or defined at the given position.
+    fn hover(&mut self, uri: &str, position: &Value) -> Option<Value> {
+        let attribute_id = self.attribute_at(uri, position)?;
+        let registry = self.resolved_registry();
+        let AttributeSpec::Id {
+            r#type,
+            brief,
+            requirement_level,
+            deprecated,
+            ..
+        } = registry.attribute(&attribute_id)?
+        else {
+            return None;
+        };
+        let mut value = format!(
+            "**`{}`** (`{}`, {})\n\n{}",
+            attribute_id,
+            r#type,
+            requirement_level,
+            brief.trim()
+        );
+        if let Some(deprecated) = deprecated {
+            value.push_str(&format!("\n\n**Deprecated**: {}", deprecated));
+        }
+        Some(json!({"contents": {"kind": "markdown", "value": value}}))
+    }
+}
+
+/// Returns the `textDocument/publishDiagnostics` notification of a document.
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
+
+/// Returns the diagnostic of an error, located at the position reported by
+/// the error or on the line concerned by the error (the first line if
+/// unknown).
+fn diagnostic(text: &str, error: &Error, severity: u8) -> Value {
+    let (line, column) = match (error.position(), error) {
+        (Some(position), _) => (
+            position.line.saturating_sub(1),
+            position.column.saturating_sub(1),
+        ),
+        (
+            None,
+            Error::DuplicateAttributeId { id, .. } | Error::AttributeTypeConflict { id, .. },
+        ) => (attribute_line(text, id).unwrap_or(0), 0),
+        (
+            None,
+            Error::InvalidAttribute {
+                group_id, error, ..
+            },
+        ) => {
+            // The reference not found is quoted in the error.
+            let r#ref = error.split('\'').nth(1).unwrap_or_default();
+            let line = text
+                .lines()
+                .position(|line| yaml_value(line, "ref") == Some(r#ref))
+                .or_else(|| group_line(text, group_id));
+            (line.unwrap_or(0), 0)
+        }
+        (
+            None,
+            Error::DuplicateGroupId { id: group_id, .. }
+            | Error::InvalidMetric { group_id, .. }
+            | Error::UnknownStability { group_id, .. }
+            | Error::AnyOfConstraintViolation { group_id, .. },
+        ) => (group_line(text, group_id).unwrap_or(0), 0),
+        (None, Error::CyclicExtends { group_ids, .. }) => (
+            group_ids
+                .first()
+                .and_then(|group_id| group_line(text, group_id))
+                .unwrap_or(0),
+            0,
+        ),
+        _ => (0, 0),
+    };
+    let mut diagnostic = json!({
+        "range": line_range(text, line, column),
+        "severity": severity,
+        "source": "weaver",
+        "message": error.to_string(),
+    });
+    if let Some(pointer) = error.pointer() {
+        diagnostic["data"] = json!({ "pointer": pointer });
+    }
+    diagnostic
+}
+
+/// Returns the path or URL of the semantic convention asset concerned by an
+/// error (without the document index of the multi-document files).
+fn error_provenance(error: &Error) -> Option<&str> {
+    let path_or_url = match error {
+        Error::CatalogNotFound { path_or_url, .. }
+        | Error::InvalidCatalog { path_or_url, .. }
+        | Error::DuplicateAttributeId { path_or_url, .. }
+        | Error::DuplicateGroupId { path_or_url, .. }
+        | Error::DuplicateMetricName { path_or_url, .. }
+        | Error::InvalidAttribute { path_or_url, .. }
+        | Error::InvalidMetric { path_or_url, .. }
+        | Error::UnknownStability { path_or_url, .. }
+        | Error::AnyOfConstraintViolation { path_or_url, .. }
+        | Error::CyclicExtends { path_or_url, .. } => path_or_url.as_str(),
+        Error::AttributeTypeConflict { definition, .. } => definition.path_or_url.as_str(),
+        _ => return None,
+    };
+    Some(
+        path_or_url
+            .split_once(" (document ")
+            .map_or(path_or_url, |(path_or_url, _)| path_or_url),
+    )
+}
+
+/// Returns the range from the given column to the end of a line.
+fn line_range(text: &str, line: usize, column: usize) -> Value {
+    let end = text
+        .lines()
+        .nth(line)
+        .map_or(column, |line| line.chars().count().max(column));
+    json!({
+        "start": {"line": line, "character": column},
+        "end": {"line": line, "character": end},
+    })
+}
+
+/// Returns the value of a `key: value` YAML line (with or without list item
+/// marker, quotes removed).
+fn yaml_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let line = line.trim_start();
+    let line = line.strip_prefix("- ").unwrap_or(line).trim_start();
+    let value = line.strip_prefix(key)?.strip_prefix(':')?.trim();
+    let value = value.trim_matches(|c| c == '"' || c == '\'');
+    (!value.is_empty()).then_some(value)
+}
+
+/// Returns the indentation of a line.
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Returns the index of the line defining a group.
+fn group_line(text: &str, group_id: &str) -> Option<usize> {
+    let lines: Vec<&str> = text.lines().collect();
+    (0..lines.len()).find(|&index| {
+        yaml_value(lines[index], "id") == Some(group_id)
+            && attribute_prefix(&lines, index).is_none()
+    })
+}
+
+/// Returns the prefix of the group of the attribute defined at the given
+/// line (empty if the group has no prefix), or `None` if the line is not
+/// inside a group (i.e. it defines a group).
+fn attribute_prefix<'a>(lines: &[&'a str], line_index: usize) -> Option<&'a str> {
+    let attribute_indentation = indentation(lines[line_index]);
+    let group_index = (0..line_index).rev().find(|&index| {
+        yaml_value(lines[index], "id").is_some()
+            && lines[index].trim_start().starts_with("- ")
+            && indentation(lines[index]) < attribute_indentation
+    })?;
+    let group_indentation = indentation(lines[group_index]);
+    let prefix = lines[group_index..]
+        .iter()
+        .skip(1)
+        .take_while(|line| line.trim().is_empty() || indentation(line) > group_indentation)
+        .filter(|line| indentation(line) == group_indentation + 2)
+        .find_map(|line| yaml_value(line, "prefix"));
+    Some(prefix.unwrap_or_default())
+}
+
+/// Returns the index of the line defining an attribute from its (prefixed)
+/// id.
+fn attribute_line(text: &str, attribute_id: &str) -> Option<usize> {
+    let lines: Vec<&str> = text.lines().collect();
+    (0..lines.len()).find(|&index| {
+        yaml_value(lines[index], "id").is_some_and(|id| {
+            attribute_prefix(&lines, index)
+                .is_some_and(|prefix| qualified_id(prefix, id) == attribute_id)
+        })
+    })
+}
+
+/// Returns the id of an attribute prefixed by the prefix of its group.
+fn qualified_id(prefix: &str, id: &str) -> String {
+    if prefix.is_empty() {
+        id.to_owned()
+    } else {
+        format!("{}.{}", prefix, id)
+    }
+}
+
+/// Returns the path of a `file://` URI.
+fn uri_to_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    let mut bytes = vec![];
+    let mut chars = path.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex: Vec<u8> = chars.by_ref().take(2).collect();
+            match std::str::from_utf8(&hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(decoded) => bytes.push(decoded),
+                None => {
+                    bytes.push(byte);
+                    bytes.extend(hex);
+                }
+            }
+        } else {
+            bytes.push(byte);
+        }
+    }
+    let path = String::from_utf8_lossy(&bytes).into_owned();
+    // Windows paths (`/C:/...`)
+    match path.as_bytes() {
+        [b'/', _, b':', ..] => path[1..].to_owned(),
+        _ => path,
+    }
+}
+
+/// Returns the `file://` URI of a path.
+fn path_to_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::from(if path.starts_with('/') {
+        "file://"
+    } else {
+        "file:///"
+    });
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = r#"groups:
+  - id: registry.http
+    type: attribute_group
+    brief: HTTP attributes.
+    prefix: http
+    attributes:
+      - id: method
+        type: string
+        brief: The HTTP method.
+        examples: ["GET"]
+"#;
+
+    const SPAN: &str = r#"groups:
+  - id: span.http
+    type: span
+    brief: HTTP span.
+    attributes:
+      - ref: http.method
+      - ref: http.unknown
+"#;
+
+    /// Returns a server with the registry file `/registry/http.yaml`.
+    fn server() -> Server {
+        let mut server = Server {
+            registry_dir: Some(PathBuf::from("/registry")),
+            registry: BTreeMap::new(),
+            resolved: None,
+            documents: HashMap::new(),
+            shutdown: false,
+        };
+        _ = server.registry.insert(
+            "/registry/http.yaml".to_owned(),
+            SemConvSpec::load_from_str(REGISTRY, "/registry/http.yaml").unwrap(),
+        );
+        _ = server
+            .documents
+            .insert("file:///registry/http.yaml".to_owned(), REGISTRY.to_owned());
+        server
+    }
+
+    #[test]
+    fn test_read_message() {
+        let content = r#"{"jsonrpc":"2.0","id":1,"method":"shutdown"}"#;
+        let input = format!(
+            "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{}",
+            content.len(),
+            content
+        );
+        let mut reader = input.as_bytes();
+        let message = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(message["method"], "shutdown");
+        assert!(read_message(&mut reader).unwrap().is_none());
+
+        // A message written by `write_message` is read back.
+        let mut output = vec![];
+        write_message(&mut output, &message).unwrap();
+        assert_eq!(read_message(&mut output.as_slice()).unwrap(), Some(message));
+
+        assert!(read_message(&mut "\r\n{}".as_bytes()).is_err());
+        assert!(read_message(&mut "Content-Length: x\r\n\r\n".as_bytes()).is_err());
+        assert!(read_message(&mut "Content-Length: 3\r\n\r\n{]}".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///registry/http.yaml"),
+            "/registry/http.yaml"
+        );
+        assert_eq!(
+            uri_to_path("file:///my%20registry/http%2Eyaml"),
+            "/my registry/http.yaml"
+        );
+        assert_eq!(
+            uri_to_path("file:///C:/registry/http.yaml"),
+            "C:/registry/http.yaml"
+        );
+        assert_eq!(uri_to_path("file:///registry/100%"), "/registry/100%");
+
+        for path in ["/my registry/http.yaml", "C:/registry/http.yaml"] {
+            assert_eq!(uri_to_path(&path_to_uri(path)), path);
+        }
+    }
+
+    #[test]
+    fn test_attribute_at() {
+        let mut server = server();
+        _ = server
+            .documents
+            .insert("file:///registry/span.yaml".to_owned(), SPAN.to_owned());
+        let attribute_at =
+            |uri: &str, line: u64| server.attribute_at(uri, &json!({"line": line, "character": 8}));
+
+        // Definition of a prefixed attribute.
+        assert_eq!(
+            attribute_at("file:///registry/http.yaml", 6).as_deref(),
+            Some("http.method")
+        );
+        // Reference.
+        assert_eq!(
+            attribute_at("file:///registry/span.yaml", 5).as_deref(),
+            Some("http.method")
+        );
+        // Group definition, other line, line out of range and unknown document.
+        assert_eq!(attribute_at("file:///registry/http.yaml", 1), None);
+        assert_eq!(attribute_at("file:///registry/http.yaml", 7), None);
+        assert_eq!(attribute_at("file:///registry/http.yaml", 100), None);
+        assert_eq!(attribute_at("file:///registry/other.yaml", 6), None);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut server = server();
+
+        let diagnostics = server.diagnostics("/registry/span.yaml", SPAN);
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert_eq!(diagnostics[0]["severity"], ERROR);
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 6);
+        assert!(diagnostics[0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("http.unknown"));
+
+        // The valid document has no diagnostics.
+        assert!(server
+            .diagnostics("/registry/http.yaml", REGISTRY)
+            .is_empty());
+
+        // Loading error.
+        let diagnostics = server.diagnostics("/registry/span.yaml", "groups: [");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], ERROR);
+    }
+
+    #[test]
+    fn test_resolved_registry_cache() {
+        let mut server = server();
+        let position = json!({"line": 6, "character": 8});
+        let hover = server
+            .hover("file:///registry/http.yaml", &position)
+            .unwrap();
+        assert!(hover["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains("The HTTP method."));
+        assert!(server.resolved.is_some());
+
+        // A change resolves the registry with the new content, which is then
+        // reused by the following requests.
+        let text = REGISTRY.replace("The HTTP method.", "The HTTP request method.");
+        _ = server.update_document("file:///registry/http.yaml", text);
+        assert!(server
+            .resolved
+            .as_ref()
+            .and_then(|registry| registry.attribute("http.method"))
+            .is_some());
+        let hover = server
+            .hover("file:///registry/http.yaml", &position)
+            .unwrap();
+        assert!(hover["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains("The HTTP request method."));
+
+        // An invalid change keeps the last valid content in the registry.
+        _ = server.update_document("file:///registry/http.yaml", "groups: [".to_owned());
+        _ = server.update_document("file:///registry/span.yaml", SPAN.to_owned());
+        let hover = server.hover("file:///registry/span.yaml", &json!({"line": 5}));
+        assert!(hover.unwrap()["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains("The HTTP request method."));
+    }
+}
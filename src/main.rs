@@ -1,34 +1,128 @@
+// The semantic convention errors carry the location (line, column and JSON
+// pointer) of the offending definition for the diagnostics.
+#![allow(clippy::result_large_err)]
+
+use std::time::Instant;
+
 use clap::Parser;
 
-use weaver_logger::ConsoleLogger;
+use weaver_logger::{ConsoleLogger, Logger};
 
+use crate::batch::command_batch;
+use crate::cache::command_cache;
 use crate::cli::{Cli, Commands};
+use crate::completions::command_completions;
+use crate::conformance::command_conformance;
+use crate::exit_code::ExitCode;
+use crate::export::command_export;
 use crate::gen_client::command_gen_client;
+use crate::gen_diff::command_gen;
+use crate::lsp::command_lsp;
+use crate::registry::command_registry;
 use crate::resolve::command_resolve;
+use crate::schema::command_schema;
+use crate::serve::command_serve;
+use crate::template::command_template;
+use crate::version::command_version;
 
+mod batch;
+mod cache;
 mod cli;
+mod completions;
+mod conformance;
+mod exit_code;
+mod export;
 mod gen_client;
+mod gen_diff;
 mod languages;
+mod lsp;
+mod registry;
 mod resolve;
+mod schema;
 mod search;
+mod serve;
+mod template;
+mod timings;
+mod version;
+mod wizard;
 
 fn main() {
     let cli = Cli::parse();
-    let log = ConsoleLogger::new(cli.debug);
+    let color = cli.color.enabled();
+    let log = ConsoleLogger::new(cli.debug).with_color(color);
+
+    if let Err(e) = cli
+        .network
+        .network_config()
+        .and_then(|config| weaver_net::init(&config))
+    {
+        log.error(&e.to_string());
+        std::process::exit(e.exit_code());
+    }
+
+    if let Err(e) = cli.concurrency.init() {
+        log.error(&e);
+        std::process::exit(exit_code::FAILURE);
+    }
+
+    let timings = cli.command.as_ref().and_then(Commands::timings);
+    let phase_timings = log.timings();
+    let report_log = log.clone();
+    let start = Instant::now();
 
     match &cli.command {
         Some(Commands::Resolve(params)) => {
-            command_resolve(log, params);
+            command_resolve(log, &cli.cache, &cli.network, params);
         }
         Some(Commands::GenClient(params)) => {
-            command_gen_client(log, params);
+            command_gen_client(log, &cli.cache, params);
+        }
+        Some(Commands::Gen(params)) => {
+            command_gen(log, &cli.cache, params);
         }
         Some(Commands::Languages(params)) => {
             languages::command_languages(log, params);
         }
         Some(Commands::Search(params)) => {
-            search::command_search(log, params);
+            search::command_search(log, &cli.cache, color, params);
+        }
+        Some(Commands::Export(params)) => {
+            command_export(log, &cli.cache, params);
+        }
+        Some(Commands::Registry(params)) => {
+            command_registry(log, &cli.cache, params);
+        }
+        Some(Commands::Schema(params)) => {
+            command_schema(log, &cli.cache, params);
+        }
+        Some(Commands::Cache(params)) => {
+            command_cache(log, &cli.cache, params);
+        }
+        Some(Commands::Serve(params)) => {
+            command_serve(log, &cli.cache, params);
+        }
+        Some(Commands::Lsp(params)) => {
+            command_lsp(log, params);
+        }
+        Some(Commands::Batch(params)) => {
+            command_batch(log, &cli.cache, params);
+        }
+        Some(Commands::Template(params)) => {
+            command_template(log, params);
+        }
+        Some(Commands::Conformance(params)) => {
+            command_conformance(log, &cli.cache, params);
+        }
+        Some(Commands::Version(params)) => {
+            command_version(log, params);
+        }
+        Some(Commands::Completions(params)) => {
+            command_completions(log, &cli.cache, params);
         }
         None => {}
     }
+
+    if let Some(format) = timings {
+        timings::print_timings(&report_log, &phase_timings, start.elapsed(), format);
+    }
 }
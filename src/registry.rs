@@ -0,0 +1,1128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to manage a semantic convention registry.
+
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::process::exit;
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_resolver::{find_usages, SchemaResolver};
+use weaver_schema::SemConvImport;
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::check::Severity;
+use weaver_semconv::group::ConvTypeSpec;
+use weaver_semconv::lint::Linter;
+use weaver_semconv::refactor::RegistryFile;
+use weaver_semconv::{GroupSpecWithProvenance, ResolverConfig, SemConvSpecs};
+use weaver_version::{Rename, RenameKind, Versions};
+
+use crate::cli::{CacheArgs, TimingsArgs};
+use crate::completions::record_names;
+use crate::exit_code::{ExitCode, FAILURE, VALIDATION_FAILURE};
+use crate::export::graph::GroupGraph;
+use crate::wizard::{new_group, Wizard};
+
+/// Specify the `registry` command
+#[derive(Args)]
+pub struct RegistryCommand {
+    /// Define the sub-commands for the `registry` command
+    #[clap(subcommand)]
+    pub command: RegistrySubCommand,
+}
+
+/// Sub-commands for the `registry` command
+#[derive(Subcommand)]
+pub enum RegistrySubCommand {
+    /// Check a semantic convention registry
+    Check(RegistryCheck),
+    /// Copy the registries imported by a telemetry schema into the project
+    /// tree and rewrite the schema to import these local copies
+    Vendor(RegistryVendor),
+    /// Move groups between the files of a semantic convention registry,
+    /// preserving their comments and formatting
+    Refactor(RegistryRefactor),
+    /// List the groups of a semantic convention registry (and the signals
+    /// of a telemetry schema) using an attribute
+    FindUsages(RegistryFindUsages),
+    /// Print the transitive dependencies of a group (extended groups,
+    /// groups defining the referenced attributes, included groups and
+    /// events)
+    GraphDeps(RegistryGraphDeps),
+    /// Remove the groups and attributes deprecated before a version from the
+    /// files of a local semantic convention registry (prints the edit plan
+    /// unless `--apply` is given)
+    PruneDeprecated(RegistryPruneDeprecated),
+    /// Create a new group in a local semantic convention registry with an
+    /// interactive wizard validating the entries against the registry
+    NewGroup(RegistryNewGroup),
+    /// Write the canonical form of a semantic convention registry (groups
+    /// sorted by id, fully qualified attribute ids, no prefix) in a single
+    /// file or in one file per group
+    Normalize(RegistryNormalize),
+}
+
+/// Parameters for the `registry check` sub-command
+#[derive(Args)]
+pub struct RegistryCheck {
+    /// Registry to check
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+
+    /// Deprecated, has no effect: all the group types are implemented by the
+    /// resolver
+    #[arg(long)]
+    pub fail_on_unimplemented: bool,
+
+    /// Keep the stability levels unknown to weaver (e.g. introduced by a
+    /// newer version of the semantic conventions) and report them as
+    /// warnings instead of failing
+    #[arg(long)]
+    pub tolerant_stability: bool,
+
+    /// Lint configuration file (wordlists and terminology rules) used to
+    /// spell-check the briefs and notes of the registry
+    #[arg(long, value_name = "FILE")]
+    pub lint: Option<PathBuf>,
+
+    /// Timing breakdown option
+    #[command(flatten)]
+    pub timings: TimingsArgs,
+}
+
+/// Parameters for the `registry find-usages` sub-command
+#[derive(Args)]
+pub struct RegistryFindUsages {
+    /// Id of the attribute to look for
+    #[arg(value_name = "ATTRIBUTE_ID")]
+    pub attribute_id: String,
+
+    /// Registry to search
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Telemetry schema whose signals are also searched
+    #[arg(short, long)]
+    pub schema: Option<PathBuf>,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+}
+
+/// Parameters for the `registry graph-deps` sub-command
+#[derive(Args)]
+pub struct RegistryGraphDeps {
+    /// Id of the group whose dependencies are printed
+    #[arg(short, long, value_name = "GROUP_ID")]
+    pub group: String,
+
+    /// Registry containing the group
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = DepsFormat::Text)]
+    pub format: DepsFormat,
+}
+
+/// Parameters for the `registry prune-deprecated` sub-command
+#[derive(Args)]
+pub struct RegistryPruneDeprecated {
+    /// Local directory of the semantic convention registry to prune
+    pub registry: PathBuf,
+
+    /// Telemetry schema whose `versions` section (including the versions of
+    /// its parent schema) dates the deprecations
+    #[arg(short, long)]
+    pub schema: PathBuf,
+
+    /// Remove the names deprecated by a version older than this version
+    #[arg(long, value_name = "VERSION")]
+    pub before: semver::Version,
+
+    /// Edit the files of the registry instead of only printing the edit plan
+    #[arg(long)]
+    pub apply: bool,
+}
+
+/// Parameters for the `registry new-group` sub-command
+#[derive(Args)]
+pub struct RegistryNewGroup {
+    /// Local directory of the semantic convention registry receiving the
+    /// group
+    pub registry: PathBuf,
+
+    /// Semantic convention file receiving the group (created if missing,
+    /// `<registry>/<root namespace>.yaml` by default)
+    #[arg(long, value_name = "FILE")]
+    pub file: Option<PathBuf>,
+}
+
+/// Parameters for the `registry normalize` sub-command
+#[derive(Args)]
+pub struct RegistryNormalize {
+    /// Registry to normalize
+    pub registry: String,
+
+    /// Optional path in the git repository where the semantic convention
+    /// registry is located
+    pub path: Option<String>,
+
+    /// Output file (or directory with `--split`)
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Write one semantic convention file per group in the output directory
+    #[arg(long)]
+    pub split: bool,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+}
+
+/// Supported formats of the dependencies of a group
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum DepsFormat {
+    /// A text tree
+    Text,
+    /// A JSON document
+    Json,
+}
+
+/// Parameters for the `registry vendor` sub-command
+#[derive(Args)]
+pub struct RegistryVendor {
+    /// Telemetry schema whose imports are vendored (rewritten in place)
+    #[arg(short, long)]
+    pub schema: PathBuf,
+
+    /// Directory where the imported registries are copied
+    #[arg(short, long, default_value = "third_party/semconv")]
+    pub output: PathBuf,
+}
+
+/// Parameters for the `registry refactor` sub-command
+#[derive(Args)]
+pub struct RegistryRefactor {
+    /// Define the refactorings
+    #[clap(subcommand)]
+    pub command: RefactorSubCommand,
+}
+
+/// Refactorings of the `registry refactor` sub-command
+#[derive(Subcommand)]
+pub enum RefactorSubCommand {
+    /// Move groups of a semantic convention file into a new file
+    Split(RefactorSplit),
+    /// Move the groups of semantic convention files into another file,
+    /// removing the files left without groups
+    Merge(RefactorMerge),
+}
+
+/// Parameters for the `registry refactor split` sub-command
+#[derive(Args)]
+pub struct RefactorSplit {
+    /// Semantic convention file to split
+    pub file: PathBuf,
+
+    /// Id of a group to move (repeatable)
+    #[arg(short, long = "group", value_name = "GROUP_ID", required = true)]
+    pub groups: Vec<String>,
+
+    /// New semantic convention file receiving the groups
+    #[arg(long, value_name = "FILE")]
+    pub into: PathBuf,
+
+    /// Telemetry schema whose `local_path` imports are updated (repeatable)
+    #[arg(short, long = "schema", value_name = "FILE")]
+    pub schemas: Vec<PathBuf>,
+}
+
+/// Parameters for the `registry refactor merge` sub-command
+#[derive(Args)]
+pub struct RefactorMerge {
+    /// Semantic convention files to merge
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+
+    /// Semantic convention file receiving the groups (created if missing)
+    #[arg(long, value_name = "FILE")]
+    pub into: PathBuf,
+
+    /// Id of a group to move (repeatable, all the groups by default)
+    #[arg(short, long = "group", value_name = "GROUP_ID")]
+    pub groups: Vec<String>,
+
+    /// Telemetry schema whose `local_path` imports are updated (repeatable)
+    #[arg(short, long = "schema", value_name = "FILE")]
+    pub schemas: Vec<PathBuf>,
+}
+
+/// Description of a vendored registry, stored in the `.vendor.yaml` file of
+/// the vendored directory.
+#[derive(Serialize)]
+struct VendorInfo {
+    /// The imported URL or git URL.
+    source: String,
+    /// The path of the registry in the git repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// The commit id of the vendored files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit: Option<String>,
+    /// The git reference of the vendored files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_ref: Option<String>,
+    /// The number of vendored files.
+    file_count: usize,
+}
+
+/// The edit plan of the `registry prune-deprecated` sub-command.
+#[derive(Serialize, Default)]
+struct PrunePlan {
+    /// The removals, in the order of the files.
+    removals: Vec<PruneRemoval>,
+    /// The version changes renaming the pruned names, to be retained in the
+    /// `versions` section so the telemetry using these names can still be
+    /// upgraded.
+    retained_version_changes: Vec<Rename>,
+    /// The deprecated names not renamed by any version, i.e. whose
+    /// deprecation can't be dated (they are kept).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    undated: Vec<String>,
+}
+
+/// A removal of the edit plan.
+#[derive(Serialize)]
+struct PruneRemoval {
+    /// The file containing the removed group or attribute.
+    file: String,
+    /// The id of the removed group or of the group of the removed attribute.
+    group: String,
+    /// The kind of removal.
+    kind: PruneKind,
+    /// The name of the removed attribute (or metric of a removed group).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// The version deprecating the removed name (`None` for the references
+    /// to a removed attribute).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated_since: Option<String>,
+    /// The `id` or `ref` of the removed attribute as written in the file.
+    #[serde(skip)]
+    key: String,
+}
+
+/// The kinds of removal of the edit plan.
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum PruneKind {
+    /// A deprecated (metric) group.
+    Group,
+    /// The definition of a deprecated attribute.
+    Attribute,
+    /// A reference to a removed attribute.
+    AttributeRef,
+}
+
+/// Manage a semantic convention registry
+pub fn command_registry(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &RegistryCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+    match command.command {
+        RegistrySubCommand::Check(ref command) => {
+            let mut resolver_config = ResolverConfig::with_keep_specs().with_collect_errors();
+            if command.build_tools_compat {
+                resolver_config = resolver_config.with_build_tools_compat();
+            }
+            if command.fail_on_unimplemented {
+                log.warn("Deprecated option").log(
+                    "--fail-on-unimplemented has no effect, all the group types are implemented",
+                );
+            }
+            if command.tolerant_stability {
+                resolver_config = resolver_config.with_tolerant_stability();
+            }
+            let imports = [SemConvImport::GitUrl {
+                git_url: command.registry.clone(),
+                path: command.path.clone(),
+                mirrors: vec![],
+            }];
+            let registry = SchemaResolver::semconv_registry_from_imports(
+                &imports,
+                resolver_config,
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            record_names(&log, &cache, &registry);
+
+            let mut findings = registry.check();
+            if let Some(lint_config) = &command.lint {
+                let linter = Linter::from_file(lint_config).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+                findings.extend(registry.lint(&linter));
+            }
+            let error_count = findings
+                .iter()
+                .filter(|finding| finding.severity == Severity::Error)
+                .count();
+            for finding in &findings {
+                match finding.severity {
+                    Severity::Error => log.error(&finding.to_string()),
+                    Severity::Warning => log.warn(&finding.to_string()),
+                };
+            }
+
+            if error_count > 0 {
+                log.error(&format!(
+                    "Registry check failed ({} error(s), {} warning(s))",
+                    error_count,
+                    findings.len() - error_count
+                ));
+                exit(VALIDATION_FAILURE);
+            }
+            log.success(&format!(
+                "Registry check passed ({} warning(s))",
+                findings.len()
+            ));
+        }
+        RegistrySubCommand::Vendor(ref command) => {
+            vendor(&log, &cache, command).unwrap_or_else(|e| {
+                log.error(&e);
+                exit(FAILURE);
+            });
+        }
+        RegistrySubCommand::Refactor(ref command) => {
+            let result = match &command.command {
+                RefactorSubCommand::Split(split) => {
+                    if split.into.exists() {
+                        log.error(&format!(
+                            "{} already exists (use `registry refactor merge` to move groups into an existing file)",
+                            split.into.display()
+                        ));
+                        exit(FAILURE);
+                    }
+                    refactor(
+                        &log,
+                        std::slice::from_ref(&split.file),
+                        &split.into,
+                        Some(&split.groups),
+                        &split.schemas,
+                    )
+                }
+                RefactorSubCommand::Merge(merge) => refactor(
+                    &log,
+                    &merge.files,
+                    &merge.into,
+                    (!merge.groups.is_empty()).then_some(&merge.groups),
+                    &merge.schemas,
+                ),
+            };
+            result.unwrap_or_else(|e| {
+                log.error(&e);
+                exit(FAILURE);
+            });
+        }
+        RegistrySubCommand::FindUsages(ref command) => {
+            let mut resolver_config = ResolverConfig::with_keep_specs();
+            if command.build_tools_compat {
+                resolver_config = resolver_config.with_build_tools_compat();
+            }
+            let imports = [SemConvImport::GitUrl {
+                git_url: command.registry.clone(),
+                path: command.path.clone(),
+                mirrors: vec![],
+            }];
+            let registry = SchemaResolver::semconv_registry_from_imports(
+                &imports,
+                resolver_config,
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            let schema = command.schema.as_ref().map(|schema| {
+                SchemaResolver::load_schema_from_path(schema, log.clone()).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                })
+            });
+
+            let usages = find_usages(&registry, &command.attribute_id, schema.as_ref())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            match serde_yaml::to_string(&usages) {
+                Ok(yaml) => {
+                    log.log(&yaml);
+                }
+                Err(e) => {
+                    log.error(&format!("{}", e));
+                    exit(FAILURE)
+                }
+            }
+        }
+        RegistrySubCommand::GraphDeps(ref command) => {
+            let registry = SchemaResolver::load_semconv_registry(
+                command.registry.clone(),
+                command.path.clone(),
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+
+            let graph = GroupGraph::from_registry(&registry);
+            let Some(tree) = graph.dependency_tree(&command.group) else {
+                log.error(&format!(
+                    "Group `{}` not found in the semantic convention registry",
+                    command.group
+                ));
+                exit(FAILURE);
+            };
+            match command.format {
+                DepsFormat::Text => {
+                    log.log(&tree.to_text());
+                    for cycle in tree.cycles.iter() {
+                        log.warn(&format!("Dependency cycle: {}", cycle.join(" -> ")));
+                    }
+                }
+                DepsFormat::Json => match serde_json::to_string_pretty(&tree) {
+                    Ok(json) => {
+                        log.log(&json);
+                    }
+                    Err(e) => {
+                        log.error(&e.to_string());
+                        exit(FAILURE);
+                    }
+                },
+            }
+        }
+        RegistrySubCommand::PruneDeprecated(ref command) => {
+            let mut registry = SemConvSpecs::default();
+            _ = registry
+                .load_from_dir(&command.registry)
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            let schema = SchemaResolver::resolve_schema_file(&command.schema, &cache, log.clone())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            let versions = schema.versions.unwrap_or_default();
+
+            let plan = prune_plan(&registry, &versions, &command.before);
+            match serde_yaml::to_string(&plan) {
+                Ok(yaml) => {
+                    log.log(&yaml);
+                }
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(FAILURE);
+                }
+            }
+            if command.apply {
+                apply_prune_plan(&log, &plan).unwrap_or_else(|e| {
+                    log.error(&e);
+                    exit(FAILURE);
+                });
+            }
+        }
+        RegistrySubCommand::NewGroup(ref command) => {
+            let mut registry = SemConvSpecs::default();
+            _ = registry
+                .load_from_dir(&command.registry)
+                .and_then(|_| registry.resolve(ResolverConfig::with_keep_specs()))
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            let stdin = std::io::stdin();
+            let mut wizard = Wizard::new(stdin.lock(), std::io::stdout());
+            new_group(&log, &mut wizard, &registry, command).unwrap_or_else(|e| {
+                log.error(&e);
+                exit(FAILURE);
+            });
+        }
+        RegistrySubCommand::Normalize(ref command) => {
+            let mut resolver_config = ResolverConfig::with_keep_specs();
+            if command.build_tools_compat {
+                resolver_config = resolver_config.with_build_tools_compat();
+            }
+            let imports = [SemConvImport::GitUrl {
+                git_url: command.registry.clone(),
+                path: command.path.clone(),
+                mirrors: vec![],
+            }];
+            let registry = SchemaResolver::semconv_registry_from_imports(
+                &imports,
+                resolver_config,
+                &cache,
+                log.clone(),
+            )
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+
+            let result = if command.split {
+                registry.save_to_dir(&command.output)
+            } else {
+                registry.save_to_file(&command.output).map(|_| 1)
+            };
+            match result {
+                Ok(file_count) => {
+                    log.success(&format!(
+                        "Normalized registry written to {} ({} file(s))",
+                        command.output.display(),
+                        file_count
+                    ));
+                }
+                Err(e) => {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                }
+            }
+        }
+    }
+}
+
+/// Returns the plan removing the groups and attributes of the registry
+/// deprecated by a version older than the given version, and the
+/// references to the removed attributes. A deprecation is dated by the first
+/// version renaming the deprecated name.
+fn prune_plan(registry: &SemConvSpecs, versions: &Versions, before: &semver::Version) -> PrunePlan {
+    let renames = versions.renames_by_version();
+    let deprecation_version = |kind: RenameKind, name: &str| {
+        renames
+            .iter()
+            .find(|rename| rename.kind == kind && rename.old_name == name)
+            .map(|rename| &rename.version)
+    };
+    let mut plan = PrunePlan::default();
+    let mut pruned_names: BTreeSet<(RenameKind, String)> = BTreeSet::new();
+
+    let groups: Vec<GroupSpecWithProvenance> = registry.groups_with_provenance().collect();
+    let mut pruned_groups: HashSet<&str> = HashSet::new();
+    for GroupSpecWithProvenance {
+        spec: group,
+        provenance,
+    } in groups.iter()
+    {
+        if let (ConvTypeSpec::Metric, Some(metric_name), Some(_)) =
+            (&group.r#type, &group.metric_name, &group.deprecated)
+        {
+            match deprecation_version(RenameKind::Metric, metric_name) {
+                Some(version) if version < before => {
+                    _ = pruned_groups.insert(group.id.as_str());
+                    _ = pruned_names.insert((RenameKind::Metric, metric_name.clone()));
+                    plan.removals.push(PruneRemoval {
+                        file: provenance.clone(),
+                        group: group.id.clone(),
+                        kind: PruneKind::Group,
+                        name: Some(metric_name.clone()),
+                        deprecated_since: Some(version.to_string()),
+                        key: group.id.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => plan.undated.push(metric_name.clone()),
+            }
+        }
+    }
+    for GroupSpecWithProvenance {
+        spec: group,
+        provenance,
+    } in groups.iter()
+    {
+        if pruned_groups.contains(group.id.as_str()) {
+            continue;
+        }
+        for attr in group.attributes.iter() {
+            let AttributeSpec::Id {
+                id,
+                deprecated: Some(_),
+                ..
+            } = attr
+            else {
+                continue;
+            };
+            let name = if group.prefix.is_empty() {
+                id.clone()
+            } else {
+                format!("{}.{}", group.prefix, id)
+            };
+            match deprecation_version(RenameKind::Attribute, &name) {
+                Some(version) if version < before => {
+                    _ = pruned_names.insert((RenameKind::Attribute, name.clone()));
+                    plan.removals.push(PruneRemoval {
+                        file: provenance.clone(),
+                        group: group.id.clone(),
+                        kind: PruneKind::Attribute,
+                        name: Some(name),
+                        deprecated_since: Some(version.to_string()),
+                        key: id.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => plan.undated.push(name),
+            }
+        }
+    }
+    for GroupSpecWithProvenance {
+        spec: group,
+        provenance,
+    } in groups.iter()
+    {
+        if pruned_groups.contains(group.id.as_str()) {
+            continue;
+        }
+        for attr in group.attributes.iter() {
+            if let AttributeSpec::Ref { r#ref, .. } = attr {
+                if pruned_names.contains(&(RenameKind::Attribute, r#ref.clone())) {
+                    plan.removals.push(PruneRemoval {
+                        file: provenance.clone(),
+                        group: group.id.clone(),
+                        kind: PruneKind::AttributeRef,
+                        name: Some(r#ref.clone()),
+                        deprecated_since: None,
+                        key: r#ref.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    plan.removals
+        .sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.group.cmp(&b.group)));
+    plan.retained_version_changes = renames
+        .into_iter()
+        .filter(|rename| pruned_names.contains(&(rename.kind, rename.old_name.clone())))
+        .collect();
+    plan
+}
+
+/// The ids of the groups and the (group id, attribute name) pairs removed from
+/// a registry file.
+type FileRemovals = (Vec<String>, Vec<(String, String)>);
+
+/// Applies the removals of a prune plan to the files of the registry, the
+/// files left without groups being removed. Nothing is written if one of the
+/// files can't be edited.
+fn apply_prune_plan(log: &impl Logger, plan: &PrunePlan) -> Result<(), String> {
+    let mut edits: BTreeMap<&str, FileRemovals> = BTreeMap::new();
+    for removal in plan.removals.iter() {
+        let (groups, attributes) = edits.entry(removal.file.as_str()).or_default();
+        match removal.kind {
+            PruneKind::Group => groups.push(removal.key.clone()),
+            PruneKind::Attribute | PruneKind::AttributeRef => {
+                attributes.push((removal.group.clone(), removal.key.clone()))
+            }
+        }
+    }
+
+    let mut contents = vec![];
+    for (file, (groups, attributes)) in edits {
+        let content = RegistryFile::load_from_file(file)
+            .and_then(|registry_file| registry_file.remove(&groups, &attributes))
+            .map_err(|e| e.to_string())?;
+        contents.push((file, content));
+    }
+    for (file, content) in contents.iter() {
+        match content {
+            Some(content) => {
+                std::fs::write(file, content).map_err(|e| format!("{}: {}", file, e))?;
+            }
+            None => {
+                std::fs::remove_file(file).map_err(|e| format!("{}: {}", file, e))?;
+                log.info(&format!("Removed {} (no groups left)", file));
+            }
+        }
+    }
+    log.success(&format!(
+        "Pruned {} deprecated group(s) and attribute(s) from {} file(s)",
+        plan.removals.len(),
+        contents.len()
+    ));
+    Ok(())
+}
+
+/// Copies the files of the remote imports of the schema into the output
+/// directory and replaces these imports with `local_path` imports.
+fn vendor(log: &impl Logger, cache: &Cache, command: &RegistryVendor) -> Result<(), String> {
+    let schema_path = &command.schema;
+    let error = |e: &dyn std::fmt::Display| format!("{}: {}", schema_path.display(), e);
+    let content = std::fs::read_to_string(schema_path).map_err(|e| error(&e))?;
+    let mut schema: serde_yaml::Value = serde_yaml::from_str(&content).map_err(|e| error(&e))?;
+    let Some(imports) = schema
+        .get_mut("semantic_conventions")
+        .and_then(serde_yaml::Value::as_sequence_mut)
+    else {
+        log.warn("No semantic convention imports to vendor");
+        return Ok(());
+    };
+
+    let schema_dir = schema_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let mut dir_names: Vec<String> = vec![];
+    for import in imports.iter_mut() {
+        let import_decl: SemConvImport =
+            serde_yaml::from_value(import.clone()).map_err(|e| error(&e))?;
+        let (source, path) = match &import_decl {
+            SemConvImport::Url { url, .. } => (url.clone(), None),
+            SemConvImport::GitUrl { git_url, path, .. } => (git_url.clone(), path.clone()),
+            SemConvImport::LocalPath { .. } => continue,
+        };
+
+        let mut dir_name = vendor_dir_name(&source, path.as_deref());
+        if dir_names.contains(&dir_name) {
+            dir_name = format!("{}-{}", dir_name, dir_names.len());
+        }
+        dir_names.push(dir_name.clone());
+        let output_dir = command.output.join(&dir_name);
+        if output_dir.exists() {
+            std::fs::remove_dir_all(&output_dir).map_err(|e| error(&e))?;
+        }
+        std::fs::create_dir_all(&output_dir).map_err(|e| error(&e))?;
+
+        let mut info = VendorInfo {
+            source: source.clone(),
+            path: path.clone(),
+            commit: None,
+            git_ref: None,
+            file_count: 0,
+        };
+        match &import_decl {
+            SemConvImport::Url { url, mirrors } => {
+                let file_name = url
+                    .rsplit('/')
+                    .next()
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or("registry.yaml");
+                let content = SchemaResolver::fetch_with_mirrors(url, mirrors, log, |source| {
+                    weaver_net::client()
+                        .and_then(|client| client.get(source))
+                        .map_err(|e| e.to_string())
+                        .and_then(|response| response.into_string().map_err(|e| e.to_string()))
+                })
+                .map_err(|e| format!("{}: {}", url, e))?;
+                std::fs::write(output_dir.join(file_name), content).map_err(|e| error(&e))?;
+                info.file_count = 1;
+            }
+            SemConvImport::GitUrl {
+                git_url,
+                path,
+                mirrors,
+            } => {
+                let (repo_dir, repo_url) =
+                    SchemaResolver::fetch_with_mirrors(git_url, mirrors, log, |source| {
+                        cache
+                            .git_repo(source.to_owned(), path.clone())
+                            .map(|repo_dir| (repo_dir, source.to_owned()))
+                            .map_err(|e| e.to_string())
+                    })
+                    .map_err(|e| e.to_string())?;
+                for file in SchemaResolver::semantic_convention_files(&repo_dir) {
+                    let file = file.map_err(|e| e.to_string())?;
+                    let target = output_dir.join(file.strip_prefix(&repo_dir).unwrap_or(&file));
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent).map_err(|e| error(&e))?;
+                    }
+                    _ = std::fs::copy(&file, &target).map_err(|e| error(&e))?;
+                    info.file_count += 1;
+                }
+                let repo_info = cache.git_repo_info(&repo_url).unwrap_or_default();
+                info.commit = repo_info.commit;
+                info.git_ref = repo_info.git_ref;
+            }
+            SemConvImport::LocalPath { .. } => {}
+        }
+
+        let info_yaml = serde_yaml::to_string(&info).map_err(|e| error(&e))?;
+        std::fs::write(output_dir.join(".vendor.yaml"), info_yaml).map_err(|e| error(&e))?;
+        log.success(&format!(
+            "Vendored {} file(s) from {} into {}",
+            info.file_count,
+            source,
+            output_dir.display()
+        ));
+
+        let local_path = relative_path(&output_dir, schema_dir).map_err(|e| error(&e))?;
+        let mut local_import = serde_yaml::Mapping::new();
+        _ = local_import.insert("local_path".into(), local_path.display().to_string().into());
+        *import = serde_yaml::Value::Mapping(local_import);
+    }
+
+    let content = serde_yaml::to_string(&schema).map_err(|e| error(&e))?;
+    std::fs::write(schema_path, content).map_err(|e| error(&e))?;
+    log.success(&format!(
+        "Telemetry schema {} updated to import the vendored registries",
+        schema_path.display()
+    ));
+    Ok(())
+}
+
+/// Moves the given groups (or all the groups) of the source files into the
+/// target file, removes the source files left without groups and updates the
+/// `local_path` imports of the given schemas. Nothing is written if one of the
+/// moves is invalid.
+fn refactor(
+    log: &impl Logger,
+    sources: &[PathBuf],
+    target: &Path,
+    group_ids: Option<&Vec<String>>,
+    schemas: &[PathBuf],
+) -> Result<(), String> {
+    let target_path = target.display().to_string();
+    let mut target_file: Option<RegistryFile> = if target.exists() {
+        Some(RegistryFile::load_from_file(target).map_err(|e| e.to_string())?)
+    } else {
+        None
+    };
+    let mut moved_ids: Vec<String> = vec![];
+    let mut updated_sources: Vec<(PathBuf, Option<String>)> = vec![];
+    for source in sources {
+        let source_file = RegistryFile::load_from_file(source).map_err(|e| e.to_string())?;
+        let ids: Option<Vec<String>> = group_ids.map(|ids| {
+            ids.iter()
+                .filter(|id| source_file.group_ids().contains(&id.as_str()))
+                .cloned()
+                .collect()
+        });
+        if ids.as_ref().is_some_and(Vec::is_empty) {
+            continue;
+        }
+        let current_target = target_file
+            .take()
+            .unwrap_or_else(|| RegistryFile::empty_like(&target_path, &source_file));
+        let refactoring = source_file
+            .move_groups(&current_target, ids.as_deref())
+            .map_err(|e| e.to_string())?;
+        target_file = Some(
+            RegistryFile::parse(&target_path, &refactoring.target).map_err(|e| e.to_string())?,
+        );
+        log.info(&format!(
+            "Moving {} group(s) from {} into {}",
+            refactoring.moved_groups.len(),
+            source.display(),
+            target.display()
+        ));
+        moved_ids.extend(refactoring.moved_groups);
+        updated_sources.push((source.clone(), refactoring.source));
+    }
+    if let Some(group_ids) = group_ids {
+        let unknown: Vec<&str> = group_ids
+            .iter()
+            .filter(|id| !moved_ids.contains(id))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            return Err(format!("Unknown group(s) {}", unknown.join(", ")));
+        }
+    }
+    let Some(target_file) = target_file else {
+        log.warn("No groups to move");
+        return Ok(());
+    };
+
+    // The source files are identified before the removal of the files left
+    // without groups.
+    let moves: Vec<(PathBuf, bool)> = updated_sources
+        .iter()
+        .map(|(source, content)| {
+            source
+                .canonicalize()
+                .map(|source| (source, content.is_none()))
+        })
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    if let Some(parent) = target.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+    }
+    std::fs::write(target, target_file.content()).map_err(|e| format!("{}: {}", target_path, e))?;
+    for (source, content) in updated_sources.iter() {
+        if let Some(content) = content {
+            std::fs::write(source, content).map_err(|e| format!("{}: {}", source.display(), e))?;
+        }
+    }
+    for schema in schemas {
+        update_schema_imports(log, schema, &moves, target)?;
+    }
+    for (source, content) in updated_sources.iter() {
+        if content.is_none() {
+            std::fs::remove_file(source).map_err(|e| format!("{}: {}", source.display(), e))?;
+            log.info(&format!("Removed {} (no groups left)", source.display()));
+        }
+    }
+    log.success(&format!(
+        "Moved {} group(s) into {}",
+        moved_ids.len(),
+        target.display()
+    ));
+    Ok(())
+}
+
+/// Updates the `local_path` imports of a telemetry schema importing the
+/// source files of a refactoring so the moved groups are still imported: the
+/// target file is imported next to a source file (or instead of a removed
+/// source file) unless it is already imported (directly or via its directory).
+/// The schema is edited as text to preserve its comments and formatting.
+fn update_schema_imports(
+    log: &impl Logger,
+    schema_path: &Path,
+    moves: &[(PathBuf, bool)],
+    target: &Path,
+) -> Result<(), String> {
+    let error = |e: &dyn std::fmt::Display| format!("{}: {}", schema_path.display(), e);
+    let content = std::fs::read_to_string(schema_path).map_err(|e| error(&e))?;
+    let schema_dir = schema_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let target = target.canonicalize().map_err(|e| error(&e))?;
+    let target_import = relative_path(&target, schema_dir)
+        .map_err(|e| error(&e))?
+        .display()
+        .to_string();
+
+    let mut lines: Vec<String> = content.lines().map(str::to_owned).collect();
+    let imports: Vec<(usize, PathBuf)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let (_, value) = line.split_once("local_path:")?;
+            let value = value.split(" #").next().unwrap_or(value).trim();
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            let path = schema_dir.join(value).canonicalize().ok()?;
+            Some((index, path))
+        })
+        .collect();
+    let mut target_imported = imports.iter().any(|(_, path)| target.starts_with(path));
+
+    let mut updated = false;
+    for (index, path) in imports.iter().rev() {
+        let Some((_, removed)) = moves.iter().find(|(source, _)| source == path) else {
+            continue;
+        };
+        let line = &lines[*index];
+        let Some(prefix_len) = line
+            .trim_start()
+            .starts_with("- local_path:")
+            .then(|| line.find("local_path:").map(|i| i + "local_path:".len()))
+            .flatten()
+        else {
+            log.warn(&format!(
+                "{}:{}: import to update manually",
+                schema_path.display(),
+                index + 1
+            ));
+            continue;
+        };
+        let import = format!("{} {}", &line[..prefix_len], target_import);
+        match (removed, target_imported) {
+            (true, true) => _ = lines.remove(*index),
+            (true, false) => lines[*index] = import,
+            (false, true) => continue,
+            (false, false) => lines.insert(index + 1, import),
+        }
+        target_imported = true;
+        updated = true;
+    }
+
+    if updated {
+        let mut content = lines.join("\n");
+        content.push('\n');
+        std::fs::write(schema_path, content).map_err(|e| error(&e))?;
+        log.success(&format!(
+            "Telemetry schema {} updated to import {}",
+            schema_path.display(),
+            target_import
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the name of the directory receiving a vendored registry, derived
+/// from its URL and its path in the repository.
+fn vendor_dir_name(url: &str, path: Option<&str>) -> String {
+    let url = url.trim_end_matches('/');
+    let repo_name = url
+        .rsplit('/')
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches(".git");
+    let name = match path {
+        Some(path) if !path.trim_matches('/').is_empty() => {
+            format!("{}-{}", repo_name, path.trim_matches('/'))
+        }
+        _ => repo_name.to_owned(),
+    };
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Returns the path of `path` relative to the directory `base`.
+fn relative_path(path: &Path, base: &Path) -> std::io::Result<PathBuf> {
+    let path = path.canonicalize()?;
+    let base = base.canonicalize()?;
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut relative: PathBuf = base
+        .components()
+        .skip(common)
+        .map(|_| Component::ParentDir)
+        .collect();
+    relative.extend(path.components().skip(common));
+    Ok(relative)
+}
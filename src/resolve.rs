@@ -2,16 +2,22 @@
 
 //! Command to resolve a schema file, then output and display the results on the console.
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 use std::process::exit;
-use weaver_cache::Cache;
-
+use std::time::Instant;
 use weaver_logger::Logger;
-use weaver_resolver::SchemaResolver;
+use weaver_resolved_schema::ResolvedTelemetrySchema;
+use weaver_resolver::{attribute_overrides, explain_resolution, SchemaResolver};
 use weaver_schema::SemConvImport;
 use weaver_semconv::ResolverConfig;
 
+use crate::cli::{CacheArgs, NetworkArgs, StabilityArg, TimingsArgs};
+use crate::completions::record_names;
+use crate::exit_code::{ExitCode, FAILURE};
+use crate::version::resolution_environment;
+
 /// Specify the `resolve` command
 #[derive(Args)]
 pub struct ResolveCommand {
@@ -43,6 +49,84 @@ pub struct ResolveRegistry {
     /// If not specified, the resolved schema is printed to stdout
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// Output format of the resolved registry
+    #[arg(long, value_enum, default_value = "yaml")]
+    pub output_format: OutputFormat,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+
+    /// Deprecated, has no effect: all the group types are implemented by the
+    /// resolver
+    #[arg(long)]
+    pub fail_on_unimplemented: bool,
+
+    /// Keep the stability levels unknown to weaver (e.g. introduced by a
+    /// newer version of the semantic conventions) and report them as
+    /// warnings instead of failing
+    #[arg(long)]
+    pub tolerant_stability: bool,
+
+    /// Keep the attributes without `stability` unstable instead of making
+    /// them inherit the stability of their group
+    #[arg(long)]
+    pub no_stability_inheritance: bool,
+
+    /// Minimum stability of the groups and attributes kept in the resolved
+    /// registry
+    #[arg(long, value_enum, default_value = "all")]
+    pub stability: StabilityArg,
+
+    /// Print the step-by-step resolution of the given group instead of the
+    /// resolved registry
+    #[arg(long, value_name = "GROUP_ID")]
+    pub explain: Option<String>,
+
+    /// Telemetry schema (path or URL) whose versions (merged with the
+    /// versions of its parent schema) report the renames applied to the
+    /// resolved names in the `--explain` output
+    #[arg(long, value_name = "SCHEMA", requires = "explain")]
+    pub schema: Option<String>,
+
+    /// Report a warning for each brief, note or examples of a referenced
+    /// attribute redefined with a different value by an attribute reference
+    #[arg(long)]
+    pub warn_overrides: bool,
+
+    /// Materialize the attributes of each group inline instead of referencing
+    /// them in the catalog (not supported with the `ndjson` output format)
+    #[arg(long)]
+    pub flatten: bool,
+
+    /// Record the environment of the resolution (command line, environment
+    /// variables consulted by weaver, configuration files and weaver build)
+    /// in the resolved registry
+    #[arg(long)]
+    pub record_environment: bool,
+
+    /// Also record the values of the environment variables with
+    /// `--record-environment` (only their names are recorded otherwise)
+    #[arg(long, requires = "record_environment")]
+    pub record_env_values: bool,
+
+    /// Timing breakdown option
+    #[command(flatten)]
+    pub timings: TimingsArgs,
+}
+
+/// Output formats of the `resolve registry` sub-command
+#[derive(Clone, ValueEnum)]
+pub enum OutputFormat {
+    /// A YAML document
+    Yaml,
+    /// A JSON document
+    Json,
+    /// One JSON document per line and per resolved entity (attribute, group,
+    /// metric), tagged with an `entity` field
+    Ndjson,
 }
 
 /// Parameters for the `resolve schema` sub-command
@@ -55,38 +139,151 @@ pub struct ResolveSchema {
     /// If not specified, the resolved schema is printed to stdout
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// Minimum stability of the attributes kept in the resolved schema
+    #[arg(long, value_enum, default_value = "all")]
+    pub stability: StabilityArg,
+
+    /// Accept the YAML extensions supported by the Python build-tools
+    /// instead of failing on unknown fields
+    #[arg(long)]
+    pub build_tools_compat: bool,
+
+    /// Timing breakdown option
+    #[command(flatten)]
+    pub timings: TimingsArgs,
 }
 
 /// Resolve a schema file and print the result
-pub fn command_resolve(log: impl Logger + Sync + Clone, command: &ResolveCommand) {
-    let cache = Cache::try_new().unwrap_or_else(|e| {
+pub fn command_resolve(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    network_args: &NetworkArgs,
+    command: &ResolveCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
         log.error(&e.to_string());
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     });
     match command.command {
         ResolveSubCommand::Registry(ref command) => {
+            let mut resolver_config = ResolverConfig::with_keep_specs();
+            if command.build_tools_compat {
+                resolver_config = resolver_config.with_build_tools_compat();
+            }
+            if command.fail_on_unimplemented {
+                log.warn("Deprecated option").log(
+                    "--fail-on-unimplemented has no effect, all the group types are implemented",
+                );
+            }
+            if command.tolerant_stability {
+                resolver_config = resolver_config.with_tolerant_stability();
+            }
+            if command.no_stability_inheritance {
+                resolver_config = resolver_config.without_stability_inheritance();
+            }
+            resolver_config = resolver_config.with_stability_filter(command.stability.into());
+            let imports = [SemConvImport::GitUrl {
+                git_url: command.registry.clone(),
+                path: command.path.clone(),
+                mirrors: vec![],
+            }];
             let mut registry = SchemaResolver::semconv_registry_from_imports(
-                &[SemConvImport::GitUrl {
-                    git_url: command.registry.clone(),
-                    path: command.path.clone(),
-                }],
-                ResolverConfig::with_keep_specs(),
+                &imports,
+                resolver_config,
                 &cache,
                 log.clone(),
             )
             .unwrap_or_else(|e| {
                 log.error(&e.to_string());
-                exit(1);
+                exit(e.exit_code());
             });
+            record_names(&log, &cache, &registry);
+
+            if let Some(group_id) = &command.explain {
+                let version_changes = command.schema.as_ref().map(|schema| {
+                    let mut schema = SchemaResolver::load_schema(schema, log.clone())
+                        .unwrap_or_else(|e| {
+                            log.error(&e.to_string());
+                            exit(e.exit_code());
+                        });
+                    schema.merge_versions();
+                    schema.version_changes()
+                });
+                let explanation = explain_resolution(&registry, group_id, version_changes.as_ref())
+                    .unwrap_or_else(|e| {
+                        log.error(&e.to_string());
+                        exit(e.exit_code());
+                    });
+                match serde_yaml::to_string(&explanation) {
+                    Ok(yaml) => {
+                        log.log(&yaml);
+                    }
+                    Err(e) => {
+                        log.error(&format!("{}", e));
+                        exit(FAILURE)
+                    }
+                }
+                return;
+            }
 
-            let resolved_schema =
+            if command.warn_overrides {
+                let overrides = attribute_overrides(&registry).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+                for attr_override in overrides {
+                    log.warn("Attribute override").log(&format!(
+                        "Group `{}` redefines the field {:?} of the attribute `{}` (defined in `{}`): {} -> {}",
+                        attr_override.group_id,
+                        attr_override.field_id,
+                        attr_override.attribute_id,
+                        attr_override.defined_in,
+                        attr_override.before,
+                        attr_override.after
+                    ));
+                }
+            }
+
+            let mut resolved_schema =
                 SchemaResolver::resolve_semantic_convention_registry(&mut registry, log.clone())
                     .unwrap_or_else(|e| {
                         log.error(&e.to_string());
-                        exit(1);
+                        exit(e.exit_code());
                     });
-            match serde_yaml::to_string(&resolved_schema) {
-                Ok(yaml) => {
+            if command.record_environment {
+                resolved_schema.resolution_environment = Some(resolution_environment(
+                    network_args,
+                    command.record_env_values,
+                ));
+            }
+            let start = Instant::now();
+            if let OutputFormat::Ndjson = command.output_format {
+                if command.flatten {
+                    log.error(
+                        "The --flatten option is not supported with the ndjson output format",
+                    );
+                    exit(FAILURE)
+                }
+                write_ndjson(&log, &resolved_schema, command.output.as_ref());
+                log.phase("write", start.elapsed());
+                return;
+            }
+            let serialized = match (&command.output_format, command.flatten) {
+                (OutputFormat::Json, false) => {
+                    serde_json::to_string_pretty(&resolved_schema).map_err(|e| e.to_string())
+                }
+                (OutputFormat::Json, true) => {
+                    serde_json::to_string_pretty(&resolved_schema.flatten())
+                        .map_err(|e| e.to_string())
+                }
+                (_, false) => serde_yaml::to_string(&resolved_schema).map_err(|e| e.to_string()),
+                (_, true) => {
+                    serde_yaml::to_string(&resolved_schema.flatten()).map_err(|e| e.to_string())
+                }
+            };
+            match serialized {
+                Ok(content) => {
                     if let Some(output) = &command.output {
                         log.loading(&format!(
                             "Saving resolved registry to {}",
@@ -94,13 +291,9 @@ pub fn command_resolve(log: impl Logger + Sync + Clone, command: &ResolveCommand
                                 .to_str()
                                 .unwrap_or("<unrepresentable-filename-not-utf8>")
                         ));
-                        if let Err(e) = std::fs::write(output, &yaml) {
-                            log.error(&format!(
-                                "Failed to write to {}: {}",
-                                output.to_str().unwrap(),
-                                e
-                            ));
-                            exit(1)
+                        if let Err(e) = std::fs::write(output, &content) {
+                            log.error(&format!("Failed to write to {}: {}", output.display(), e));
+                            exit(FAILURE)
                         }
                         log.success(&format!(
                             "Saved resolved registry to '{}'",
@@ -109,19 +302,31 @@ pub fn command_resolve(log: impl Logger + Sync + Clone, command: &ResolveCommand
                                 .unwrap_or("<unrepresentable-filename-not-utf8>")
                         ));
                     } else {
-                        log.log(&yaml);
+                        log.log(&content);
                     }
+                    log.phase("write", start.elapsed());
                 }
                 Err(e) => {
-                    log.error(&format!("{}", e));
-                    exit(1)
+                    log.error(&e);
+                    exit(FAILURE)
                 }
             }
         }
         ResolveSubCommand::Schema(ref command) => {
             let schema = command.schema.clone();
-            let schema = SchemaResolver::resolve_schema_file(schema, &cache, log.clone());
+            let mut resolver_config =
+                ResolverConfig::default().with_stability_filter(command.stability.into());
+            if command.build_tools_compat {
+                resolver_config = resolver_config.with_build_tools_compat();
+            }
+            let schema = SchemaResolver::resolve_schema_file_with_config(
+                schema,
+                resolver_config,
+                &cache,
+                log.clone(),
+            );
 
+            let start = Instant::now();
             match schema {
                 Ok(schema) => match serde_yaml::to_string(&schema) {
                     Ok(yaml) => {
@@ -135,10 +340,10 @@ pub fn command_resolve(log: impl Logger + Sync + Clone, command: &ResolveCommand
                             if let Err(e) = std::fs::write(output, &yaml) {
                                 log.error(&format!(
                                     "Failed to write to {}: {}",
-                                    output.to_str().unwrap(),
+                                    output.display(),
                                     e
                                 ));
-                                exit(1)
+                                exit(FAILURE)
                             }
                             log.success(&format!(
                                 "Saved resolved schema to '{}'",
@@ -149,17 +354,53 @@ pub fn command_resolve(log: impl Logger + Sync + Clone, command: &ResolveCommand
                         } else {
                             log.log(&yaml);
                         }
+                        log.phase("write", start.elapsed());
                     }
                     Err(e) => {
                         log.error(&format!("{}", e));
-                        exit(1)
+                        exit(FAILURE)
                     }
                 },
                 Err(e) => {
                     log.error(&format!("{}", e));
-                    exit(1)
+                    exit(e.exit_code())
                 }
             }
         }
     }
 }
+
+/// Streams the entities of the resolved registry as NDJSON to the given
+/// output file (or to stdout), one entity per line.
+fn write_ndjson(
+    log: &impl Logger,
+    resolved_schema: &ResolvedTelemetrySchema,
+    output: Option<&PathBuf>,
+) {
+    let writer: Box<dyn Write> = match output {
+        Some(output) => match std::fs::File::create(output) {
+            Ok(file) => Box::new(file),
+            Err(e) => {
+                log.error(&format!("Failed to write to {}: {}", output.display(), e));
+                exit(FAILURE)
+            }
+        },
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let mut writer = BufWriter::new(writer);
+
+    let result = resolved_schema.entities().try_for_each(|entity| {
+        serde_json::to_writer(&mut writer, &entity)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)
+    });
+    if let Err(e) = result.and_then(|_| writer.flush().map_err(serde_json::Error::io)) {
+        log.error(&format!("Failed to write the resolved registry: {}", e));
+        exit(FAILURE)
+    }
+    if let Some(output) = output {
+        log.success(&format!(
+            "Saved resolved registry to '{}'",
+            output.display()
+        ));
+    }
+}
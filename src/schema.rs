@@ -0,0 +1,317 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to inspect a telemetry schema.
+
+use clap::{Args, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::exit;
+use weaver_logger::Logger;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::service::ServiceMetadataPolicy;
+use weaver_schema::span_kind::SpanKindRules;
+use weaver_schema::stats::SchemaStats;
+use weaver_schema::TelemetrySchema;
+use weaver_semconv::check::Severity;
+use weaver_version::Versions;
+
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE, NETWORK_FAILURE, VALIDATION_FAILURE};
+
+/// Specify the `schema` command
+#[derive(Args)]
+pub struct SchemaCommand {
+    /// Define the sub-commands for the `schema` command
+    #[clap(subcommand)]
+    pub command: SchemaSubCommand,
+}
+
+/// Sub-commands for the `schema` command
+#[derive(Subcommand)]
+pub enum SchemaSubCommand {
+    /// Display statistics on the signals and attributes of a telemetry schema
+    Stats(SchemaStatsParams),
+    /// Check the metrics locally defined by a telemetry schema and the
+    /// attributes required on its spans by their kind
+    Lint(SchemaLintParams),
+    /// Merge the telemetry schemas of several applications into a
+    /// system-level telemetry schema
+    Merge(SchemaMergeParams),
+}
+
+/// Parameters for the `schema stats` sub-command
+#[derive(Args)]
+pub struct SchemaStatsParams {
+    /// Telemetry schema file
+    pub schema: PathBuf,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "table")]
+    pub format: StatsFormat,
+}
+
+/// Parameters for the `schema lint` sub-command
+#[derive(Args)]
+pub struct SchemaLintParams {
+    /// Telemetry schema file
+    pub schema: PathBuf,
+
+    /// Rules file mapping each span kind to the attributes required on the
+    /// spans of this kind
+    #[arg(long, value_name = "FILE")]
+    pub span_kind_rules: Option<PathBuf>,
+
+    /// Policy file defining the required fields and the allowed tiers of the
+    /// service metadata of the schema
+    #[arg(long, value_name = "FILE")]
+    pub service_policy: Option<PathBuf>,
+
+    /// Fetch the schema file published at the `schema_url` of the schema and
+    /// check that its version history is a prefix of the local `versions`
+    /// section (no rewritten history)
+    #[arg(long)]
+    pub schema_url_check: bool,
+
+    /// Skip the checks requiring the network (`--schema-url-check`)
+    #[arg(long)]
+    pub offline: bool,
+}
+
+/// Parameters for the `schema merge` sub-command
+#[derive(Args)]
+pub struct SchemaMergeParams {
+    /// Telemetry schema files to merge (the first schemas take precedence
+    /// for the `versions` section)
+    #[arg(long, required = true, num_args = 1.., value_name = "FILE")]
+    pub inputs: Vec<PathBuf>,
+
+    /// Schema URL of the merged schema (defaults to the schema URL of the
+    /// first schema)
+    #[arg(long, value_name = "URL")]
+    pub schema_url: Option<String>,
+
+    /// Output file to write the merged schema (stdout by default)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Output formats of the `schema stats` sub-command
+#[derive(Clone, ValueEnum)]
+pub enum StatsFormat {
+    /// A human readable table
+    Table,
+    /// A JSON document
+    Json,
+}
+
+/// Inspect a telemetry schema
+pub fn command_schema(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    command: &SchemaCommand,
+) {
+    match command.command {
+        SchemaSubCommand::Stats(ref command) => {
+            let schema = SchemaResolver::load_schema_from_path(&command.schema, log.clone())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            let stats = schema.stats();
+
+            match command.format {
+                StatsFormat::Table => print_table(&log, &stats),
+                StatsFormat::Json => match serde_json::to_string_pretty(&stats) {
+                    Ok(json) => {
+                        log.log(&json);
+                    }
+                    Err(e) => {
+                        log.error(&e.to_string());
+                        exit(FAILURE);
+                    }
+                },
+            }
+        }
+        SchemaSubCommand::Lint(ref command) => {
+            let mut schema = SchemaResolver::load_schema_from_path(&command.schema, log.clone())
+                .unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            let metric_findings = schema.check_metrics();
+            for finding in &metric_findings {
+                match finding.severity {
+                    Severity::Error => log.error(&finding.to_string()),
+                    Severity::Warning => log.warn(&finding.to_string()),
+                };
+            }
+            let error_count = metric_findings
+                .iter()
+                .filter(|finding| finding.severity == Severity::Error)
+                .count();
+            let warning_count = metric_findings.len() - error_count;
+
+            let mut violation_count = 0;
+            if let Some(span_kind_rules) = &command.span_kind_rules {
+                let rules = SpanKindRules::load_from_file(span_kind_rules).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+                let cache = cache_args.cache().unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+                let schema =
+                    SchemaResolver::resolve_schema_file(&command.schema, &cache, log.clone())
+                        .unwrap_or_else(|e| {
+                            log.error(&e.to_string());
+                            exit(e.exit_code());
+                        });
+
+                let violations = schema.check_span_kinds(&rules);
+                for violation in &violations {
+                    log.error(&violation.to_string());
+                }
+                violation_count = violations.len();
+            }
+
+            let mut service_violation_count = 0;
+            if let Some(service_policy) = &command.service_policy {
+                let policy =
+                    ServiceMetadataPolicy::load_from_file(service_policy).unwrap_or_else(|e| {
+                        log.error(&e.to_string());
+                        exit(e.exit_code());
+                    });
+                let violations = schema.check_service_metadata(&policy);
+                for violation in &violations {
+                    log.error(&violation.to_string());
+                }
+                service_violation_count = violations.len();
+            }
+
+            let mut divergence_count = 0;
+            if command.schema_url_check {
+                if command.offline {
+                    log.warn("Schema URL check skipped (offline mode)");
+                } else if schema.schema_url.is_empty() {
+                    log.warn("Schema URL check skipped (no `schema_url` declared)");
+                } else {
+                    schema.merge_versions();
+                    let published = published_versions(&log, &schema.schema_url);
+                    let divergences = schema
+                        .versions
+                        .unwrap_or_default()
+                        .history_divergences(&published);
+                    for divergence in &divergences {
+                        log.error(&format!("{} ({})", divergence, schema.schema_url));
+                    }
+                    divergence_count = divergences.len();
+                }
+            }
+
+            if error_count > 0
+                || violation_count > 0
+                || service_violation_count > 0
+                || divergence_count > 0
+            {
+                log.error(&format!(
+                    "Schema lint failed ({} metric error(s), {} span(s) missing required attributes, {} service metadata violation(s), {} version history divergence(s), {} warning(s))",
+                    error_count, violation_count, service_violation_count, divergence_count, warning_count
+                ));
+                exit(VALIDATION_FAILURE);
+            }
+            log.success(&format!(
+                "Schema lint passed ({} warning(s))",
+                warning_count
+            ));
+        }
+        SchemaSubCommand::Merge(ref command) => {
+            let cache = cache_args.cache().unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            let schemas: Vec<TelemetrySchema> = command
+                .inputs
+                .iter()
+                .map(|input| {
+                    SchemaResolver::resolve_schema_file(input, &cache, log.clone()).unwrap_or_else(
+                        |e| {
+                            log.error(&e.to_string());
+                            exit(e.exit_code());
+                        },
+                    )
+                })
+                .collect();
+            let schema_url = command
+                .schema_url
+                .clone()
+                .or_else(|| schemas.first().map(|schema| schema.schema_url.clone()))
+                .unwrap_or_default();
+            let schema_count = schemas.len();
+            let schema = TelemetrySchema::merge(&schema_url, schemas).unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+            let yaml = serde_yaml::to_string(&schema).unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(FAILURE);
+            });
+
+            if let Some(output) = &command.output {
+                if let Err(e) = std::fs::write(output, &yaml) {
+                    log.error(&format!("Failed to write to {}: {}", output.display(), e));
+                    exit(FAILURE);
+                }
+                log.success(&format!(
+                    "Merged {} schemas into '{}'",
+                    schema_count,
+                    output.display()
+                ));
+            } else {
+                log.log(&yaml);
+            }
+        }
+    }
+}
+
+/// Fetches the schema file published at the given schema URL and returns its
+/// `versions` section.
+fn published_versions(log: &impl Logger, schema_url: &str) -> Versions {
+    let response = weaver_net::client()
+        .and_then(|client| client.get(schema_url))
+        .unwrap_or_else(|e| {
+            log.error(&e.to_string());
+            exit(e.exit_code());
+        });
+    let content = response.into_string().unwrap_or_else(|e| {
+        log.error(&format!("Failed to read {}: {}", schema_url, e));
+        exit(NETWORK_FAILURE);
+    });
+    Versions::load_from_str(&content, schema_url).unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    })
+}
+
+/// Displays the statistics as a table.
+fn print_table(log: &impl Logger, stats: &SchemaStats) {
+    log.log(&format!(
+        "{:<14}{:>8}{:>12}",
+        "Signal", "Count", "Attributes"
+    ));
+    for signal in stats.signals.iter() {
+        log.log(&format!(
+            "{:<14}{:>8}{:>12}",
+            signal.signal, signal.count, signal.attributes
+        ));
+    }
+    log.log(&format!(
+        "Attribute refs: {}, local attributes: {} (reuse ratio: {:.1}%)",
+        stats.attribute_refs,
+        stats.local_attributes,
+        stats.attribute_reuse_ratio * 100.0
+    ));
+    log.log(&format!(
+        "Version transformations: {} local, {} inherited",
+        stats.local_transformations, stats.inherited_transformations
+    ));
+}
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Search index of a telemetry schema, persisted in the cache directory.
+//!
+//! The index is stored in `<cache>/search-index/<hash>` where `<hash>` is the
+//! hash of the resolved schema (semantic convention registry included), so a
+//! schema already indexed is loaded (memory-mapped) instead of being indexed
+//! again, and any change of the schema or of its registry produces a new
+//! index.
+
+use std::fs;
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use tantivy::schema::{Schema, STORED, TEXT};
+use tantivy::{Index, IndexWriter};
+
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_schema::TelemetrySchema;
+
+use crate::search::schema::{attribute, metric, metric_group, resource, span};
+use crate::search::{schema, DocFields, Result};
+
+/// The directory of the cache containing the search indexes.
+const INDEX_CACHE_DIR: &str = "search-index";
+
+/// The version of the index layout, part of the hash of the indexes so that
+/// a change of the indexed fields invalidates the persisted indexes.
+const INDEX_VERSION: &str = "1";
+
+/// Returns the search index of the schema and its fields. The index is
+/// loaded from the cache if the schema has already been indexed, otherwise
+/// it is built and persisted in the cache.
+pub fn search_index(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    schema: &TelemetrySchema,
+) -> Result<(Index, DocFields)> {
+    let index_dir = cache
+        .path()
+        .join(INDEX_CACHE_DIR)
+        .join(schema_hash(schema)?);
+
+    if index_dir.join("meta.json").exists() {
+        match open_index(&index_dir) {
+            Ok(index) => return Ok(index),
+            Err(e) => {
+                log.warn(&format!(
+                    "Invalid search index {}, rebuilding it: {}",
+                    index_dir.display(),
+                    e
+                ));
+                fs::remove_dir_all(&index_dir)?;
+            }
+        }
+    }
+
+    // The index is built in a temporary directory then renamed, so an
+    // interrupted build never leaves a partial index behind.
+    let build_dir = index_dir.with_extension(format!("{}.tmp", std::process::id()));
+    if build_dir.exists() {
+        fs::remove_dir_all(&build_dir)?;
+    }
+    fs::create_dir_all(&build_dir)?;
+    let build_result = build_index(&build_dir, schema).and_then(|_| {
+        fs::rename(&build_dir, &index_dir).or_else(|e| {
+            // Another process may have persisted the same index meanwhile.
+            if index_dir.join("meta.json").exists() {
+                Ok(())
+            } else {
+                Err(e.into())
+            }
+        })
+    });
+    if build_dir.exists() {
+        _ = fs::remove_dir_all(&build_dir);
+    }
+    build_result?;
+
+    open_index(&index_dir)
+}
+
+/// Returns the hash (SHA-256, hex encoded) of the content indexed for the
+/// schema. The content is serialized via `serde_json::Value`, whose objects
+/// have sorted keys, so the hash doesn't depend on the order of the maps.
+fn schema_hash(schema: &TelemetrySchema) -> Result<String> {
+    let catalog = schema.semantic_convention_catalog();
+    let mut entries = catalog
+        .attributes_iter()
+        .map(serde_json::to_value)
+        .chain(catalog.metrics_iter().map(serde_json::to_value))
+        .map(|value| value.map(|value| value.to_string()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(INDEX_VERSION);
+    hasher.update(serde_json::to_value(schema)?.to_string());
+    for entry in entries {
+        hasher.update("\n");
+        hasher.update(entry);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Returns the schema of the index and its fields.
+fn index_schema() -> (Schema, DocFields) {
+    let mut schema_builder = Schema::builder();
+    let fields = DocFields {
+        path: schema_builder.add_text_field("path", TEXT | STORED),
+        brief: schema_builder.add_text_field("brief", TEXT | STORED),
+        note: schema_builder.add_text_field("note", TEXT),
+        tag: schema_builder.add_text_field("tag", TEXT),
+    };
+    (schema_builder.build(), fields)
+}
+
+/// Builds the index of the schema in the given directory.
+fn build_index(dir: &Path, schema: &TelemetrySchema) -> Result<()> {
+    let semconv_registry = schema.semantic_convention_catalog();
+    let (index_schema, fields) = index_schema();
+    let index = Index::create_in_dir(dir, index_schema)?;
+    let mut index_writer: IndexWriter = index.writer(15_000_000)?;
+
+    attribute::index_semconv_attributes(
+        semconv_registry.attributes_iter(),
+        "semconv",
+        &fields,
+        &mut index_writer,
+    );
+    metric::index_semconv_metrics(
+        semconv_registry.metrics_iter(),
+        "semconv",
+        &fields,
+        &mut index_writer,
+    );
+    resource::index(schema, &fields, &mut index_writer);
+    metric::index_schema_metrics(schema, &fields, &mut index_writer);
+    metric_group::index(schema, &fields, &mut index_writer);
+    schema::event::index(schema, &fields, &mut index_writer);
+    span::index(schema, &fields, &mut index_writer);
+
+    _ = index_writer.commit()?;
+    index_writer.wait_merging_threads()?;
+    Ok(())
+}
+
+/// Opens (memory-maps) the index persisted in the given directory.
+fn open_index(dir: &Path) -> Result<(Index, DocFields)> {
+    let index = Index::open_in_dir(dir)?;
+    let schema = index.schema();
+    let fields = DocFields {
+        path: schema.get_field("path")?,
+        brief: schema.get_field("brief")?,
+        note: schema.get_field("note")?,
+        tag: schema.get_field("tag")?,
+    };
+    Ok((index, fields))
+}
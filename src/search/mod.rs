@@ -9,34 +9,40 @@ use clap::{Args, Subcommand};
 use crossterm::event::DisableMouseCapture;
 use crossterm::event::EnableMouseCapture;
 use crossterm::{
-    event::{self, KeyCode, KeyEventKind},
+    event::{self, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::prelude::{CrosstermBackend, Span, Terminal};
-use ratatui::style::{Color, Style, Stylize};
+use ratatui::style::{Style, Stylize};
 use ratatui::text::Line;
 use ratatui::widgets::Cell;
 use ratatui::widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap};
 use ratatui::Frame;
 use tantivy::collector::TopDocs;
 use tantivy::query::QueryParser;
-use tantivy::schema::{Field, Schema, STORED, TEXT};
-use tantivy::{Index, IndexWriter, ReloadPolicy};
+use tantivy::schema::Field;
+use tantivy::ReloadPolicy;
 use tui_textarea::TextArea;
 
 use theme::ThemeConfig;
 use weaver_cache::Cache;
-use weaver_logger::Logger;
+use weaver_logger::{Logger, NullLogger};
 use weaver_resolver::attribute::AttributeCatalog;
 use weaver_resolver::registry::{resolve_registry, unresolved_registry_from_specs};
-use weaver_resolver::SchemaResolver;
+use weaver_resolver::{Diagnostics, SchemaResolver};
 use weaver_schema::attribute::Attribute;
-use weaver_schema::TelemetrySchema;
+use weaver_schema::{SemConvImport, TelemetrySchema};
+use weaver_semconv::check::Severity;
+use weaver_semconv::ResolverConfig;
 
-use crate::search::schema::{attribute, metric, metric_group, resource, span};
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE};
+use crate::search::index::search_index;
+use crate::search::schema::{attribute, metric, metric_group, span};
 
+mod index;
 mod schema;
 mod semconv;
 mod theme;
@@ -44,6 +50,10 @@ mod theme;
 type Err = Box<dyn std::error::Error>;
 type Result<T> = std::result::Result<T, Err>;
 
+/// A telemetry schema resolved in lenient mode, with the diagnostics of its
+/// resolution.
+type Loaded = (TelemetrySchema, Diagnostics);
+
 /// Parameters for the `search` command
 #[derive(Debug, Args)]
 pub struct SearchCommand {
@@ -104,12 +114,21 @@ pub struct SearchApp<'a> {
     schema: TelemetrySchema,
     search_area: TextArea<'a>,
 
-    results: StatefulResults,
+    results: StatefulResults<ResultItem>,
 
     searcher: tantivy::Searcher,
     query_parser: QueryParser,
     current_query: Option<String>,
 
+    /// The errors and warnings of the resolution of the schema
+    diagnostics: StatefulResults<DiagnosticItem>,
+    /// Whether the diagnostics panel replaces the search results
+    show_diagnostics: bool,
+    /// Whether the schema must be loaded and resolved again
+    should_reload: bool,
+    /// The outcome of the last reload
+    status: Option<String>,
+
     should_quit: bool,
 
     theme: ThemeConfig,
@@ -121,11 +140,17 @@ pub struct ResultItem {
     brief: String,
 }
 
+/// A diagnostic (error or warning) of the resolution of the schema
+pub struct DiagnosticItem {
+    severity: Severity,
+    message: String,
+}
+
 /// A stateful list of items
-pub struct StatefulResults {
+pub struct StatefulResults<T> {
     state: TableState,
     // ListState,
-    items: Vec<ResultItem>,
+    items: Vec<T>,
 }
 
 /// A struct representing all the fields in an indexed document.
@@ -136,9 +161,9 @@ pub struct DocFields {
     tag: Field,
 }
 
-impl StatefulResults {
+impl<T> StatefulResults<T> {
     /// Creates a new stateful list of items
-    fn new() -> StatefulResults {
+    fn new() -> StatefulResults<T> {
         StatefulResults {
             state: TableState::default(), // ListState::default(),
             items: vec![],
@@ -194,22 +219,28 @@ impl StatefulResults {
 }
 
 /// Search for attributes and metrics in a schema file
-pub fn command_search(log: impl Logger + Sync + Clone, command: &SearchCommand) {
-    let cache = Cache::try_new().unwrap_or_else(|e| {
+pub fn command_search(
+    log: impl Logger + Sync + Clone,
+    cache_args: &CacheArgs,
+    color: bool,
+    command: &SearchCommand,
+) {
+    let cache = cache_args.cache().unwrap_or_else(|e| {
         log.error(&e.to_string());
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     });
+    let theme = ThemeConfig::new(color);
 
     match &command.command {
-        SearchSubCommand::Registry(args) => search_registry_command(log, &cache, args),
+        SearchSubCommand::Registry(args) => search_registry_command(log, &cache, theme, args),
         SearchSubCommand::Registry2(args) => search_registry_command2(log, &cache, args),
-        SearchSubCommand::Schema(args) => search_schema_command(log, &cache, args),
+        SearchSubCommand::Schema(args) => search_schema_command(log, &cache, theme, args),
     }
 }
 
 /// Search semantic convention registry command [todo, WIP].
 fn search_registry_command2(
-    log: impl Logger + Sync + Clone + Sized,
+    log: impl Logger + Sync + Clone,
     cache: &Cache,
     registry_args: &SearchRegistry2,
 ) {
@@ -221,7 +252,7 @@ fn search_registry_command2(
     )
     .unwrap_or_else(|e| {
         log.error(&format!("{}", e));
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     });
 
     let mut attr_catalog = AttributeCatalog::default();
@@ -231,7 +262,7 @@ fn search_registry_command2(
     )
     .unwrap_or_else(|e| {
         log.error(&format!("{}", e));
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     });
 
     dbg!(resolved_registry);
@@ -263,27 +294,54 @@ fn search_registry_command2(
 
 /// Search semantic convention registry command.
 fn search_registry_command(
-    log: impl Logger + Sync + Clone + Sized,
+    log: impl Logger + Sync + Clone,
     cache: &Cache,
+    theme: ThemeConfig,
     registry_args: &SearchRegistry,
 ) {
-    let semconv_registry = SchemaResolver::resolve_semconv_registry(
-        registry_args.registry.clone(),
-        registry_args.path.clone(),
-        cache,
-        log.clone(),
-    )
-    .unwrap_or_else(|e| {
+    let loaded = load_registry(log.clone(), cache, registry_args).unwrap_or_else(|e| {
         log.error(&format!("{}", e));
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     });
 
-    let schema = if let Some(schema) = &registry_args.schema {
-        let mut schema =
-            SchemaResolver::resolve_schema(schema, cache, log.clone()).unwrap_or_else(|e| {
-                log.error(&format!("{}", e));
-                std::process::exit(1);
-            });
+    search_schema_tui(log, cache, theme, loaded, || {
+        Ok(load_registry(NullLogger::new(), cache, registry_args)?)
+    });
+}
+
+/// Loads and resolves in lenient mode the registry to search and the
+/// optional telemetry schema containing its versions. Only a schema that
+/// can't be loaded is an error, the errors of the resolution being returned
+/// as diagnostics.
+fn load_registry(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    registry_args: &SearchRegistry,
+) -> std::result::Result<Loaded, weaver_resolver::Error> {
+    // The specs are kept to render the extends chains and the constraints of
+    // the groups.
+    let (semconv_registry, mut diagnostics) = SchemaResolver::semconv_registry_from_imports_lenient(
+        &[SemConvImport::GitUrl {
+            git_url: registry_args.registry.clone(),
+            path: registry_args.path.clone(),
+            mirrors: vec![],
+        }],
+        ResolverConfig::with_keep_specs(),
+        cache,
+        log.clone(),
+    );
+
+    let schema = if let Some(schema_url_or_path) = &registry_args.schema {
+        let schema = SchemaResolver::load_schema(schema_url_or_path, log.clone())?;
+        let (mut schema, schema_diagnostics) = SchemaResolver::resolve_schema_lenient(
+            schema,
+            schema_url_or_path,
+            ResolverConfig::default(),
+            cache,
+            log,
+        );
+        diagnostics.errors.extend(schema_diagnostics.errors);
+        diagnostics.warnings.extend(schema_diagnostics.warnings);
         schema.semantic_convention_registry = semconv_registry;
         schema
     } else {
@@ -296,86 +354,62 @@ fn search_registry_command(
             versions: None,
             parent_schema: None,
             semantic_convention_registry: semconv_registry,
+            resolved_semantic_conventions: vec![],
         }
     };
 
-    search_schema_tui(log, schema);
+    Ok((schema, diagnostics))
 }
 
 /// Search schema command.
 fn search_schema_command(
-    log: impl Logger + Sync + Clone + Sized,
+    log: impl Logger + Sync + Clone,
     cache: &Cache,
+    theme: ThemeConfig,
     schema_args: &SearchSchema,
 ) {
-    let schema =
-        SchemaResolver::resolve_schema_file(schema_args.schema.clone(), cache, log.clone())
-            .unwrap_or_else(|e| {
-                log.error(&format!("{}", e));
-                std::process::exit(1);
-            });
+    let loaded = load_schema(log.clone(), cache, schema_args).unwrap_or_else(|e| {
+        log.error(&format!("{}", e));
+        std::process::exit(e.exit_code());
+    });
 
-    search_schema_tui(log, schema);
+    search_schema_tui(log, cache, theme, loaded, || {
+        Ok(load_schema(NullLogger::new(), cache, schema_args)?)
+    });
 }
 
-fn search_schema_tui(log: impl Logger + Sync + Clone + Sized + Sized, schema: TelemetrySchema) {
-    let semconv_registry = schema.semantic_convention_catalog();
-
-    let mut schema_builder = Schema::builder();
-    let fields = DocFields {
-        path: schema_builder.add_text_field("path", TEXT | STORED),
-        brief: schema_builder.add_text_field("brief", TEXT | STORED),
-        note: schema_builder.add_text_field("note", TEXT),
-        tag: schema_builder.add_text_field("tag", TEXT),
-    };
-
-    let index_schema = schema_builder.build();
-    let index = Index::create_in_ram(index_schema.clone());
-    let mut index_writer: IndexWriter = index
-        .writer(15_000_000)
-        .expect("Failed to create index writer");
-
-    attribute::index_semconv_attributes(
-        semconv_registry.attributes_iter(),
-        "semconv",
-        &fields,
-        &mut index_writer,
-    );
-    metric::index_semconv_metrics(
-        semconv_registry.metrics_iter(),
-        "semconv",
-        &fields,
-        &mut index_writer,
-    );
-    resource::index(&schema, &fields, &mut index_writer);
-    metric::index_schema_metrics(&schema, &fields, &mut index_writer);
-    metric_group::index(&schema, &fields, &mut index_writer);
-    schema::event::index(&schema, &fields, &mut index_writer);
-    span::index(&schema, &fields, &mut index_writer);
-
-    index_writer
-        .commit()
-        .expect("Failed to commit index writer");
-    let reader = index
-        .reader_builder()
-        .reload_policy(ReloadPolicy::OnCommit)
-        .try_into()
-        .expect("Failed to create reader");
-    let searcher = reader.searcher();
-    let DocFields {
-        path,
-        brief,
-        note,
-        tag,
-    } = fields;
-    let query_parser = QueryParser::for_index(&index, vec![path, brief, note, tag]);
+/// Loads the telemetry schema to search and resolves it in lenient mode.
+/// Only a schema that can't be loaded is an error, the errors of the
+/// resolution being returned as diagnostics.
+fn load_schema(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    schema_args: &SearchSchema,
+) -> std::result::Result<Loaded, weaver_resolver::Error> {
+    let schema = SchemaResolver::load_schema_from_path(schema_args.schema.clone(), log.clone())?;
+    Ok(SchemaResolver::resolve_schema_lenient(
+        schema,
+        &schema_args.schema.display().to_string(),
+        ResolverConfig::with_keep_specs(),
+        cache,
+        log,
+    ))
+}
 
-    let theme = ThemeConfig {
-        title: Color::Rgb(238, 238, 238),
-        border: Color::Rgb(85, 109, 89),
-        label: Color::Rgb(128, 208, 163),
-        value: Color::Rgb(204, 204, 204),
-    };
+/// Explores the loaded schema, `reload` loading and resolving it again when
+/// requested (e.g. once the reported errors are fixed).
+fn search_schema_tui(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    theme: ThemeConfig,
+    loaded: Loaded,
+    reload: impl Fn() -> Result<Loaded>,
+) {
+    let (searcher, query_parser) =
+        open_search_index(log.clone(), cache, &loaded.0).unwrap_or_else(|e| {
+            log.error(&format!("Failed to build the search index: {}", e));
+            std::process::exit(FAILURE);
+        });
 
     let mut search_area = TextArea::default();
     search_area.set_cursor_line_style(Style::default());
@@ -384,11 +418,12 @@ fn search_schema_tui(log: impl Logger + Sync + Clone + Sized + Sized, schema: Te
         Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(theme.border))
-            .title("Search (press `Esc` or `Ctrl-C` to stop running) ")
+            .title("Search (press `Esc` or `Ctrl-C` to stop running, `Tab` to toggle the diagnostics, `F5` or `Ctrl-R` to reload) ")
             .title_style(Style::default().fg(theme.title)),
     );
 
     // application state
+    let (schema, diagnostics) = loaded;
     let mut app = SearchApp {
         schema,
         search_area,
@@ -396,17 +431,91 @@ fn search_schema_tui(log: impl Logger + Sync + Clone + Sized + Sized, schema: Te
         searcher,
         query_parser,
         current_query: None,
+        diagnostics: StatefulResults::new(),
+        show_diagnostics: false,
+        should_reload: false,
+        status: None,
         should_quit: false,
         theme,
     };
-
-    search_tui(&mut app).unwrap_or_else(|e| {
+    app.set_diagnostics(diagnostics);
+
+    search_tui(&mut app, &|app: &mut SearchApp<'_>| {
+        let (schema, diagnostics) = reload()?;
+        let (searcher, query_parser) = open_search_index(NullLogger::new(), cache, &schema)?;
+        app.schema = schema;
+        app.searcher = searcher;
+        app.query_parser = query_parser;
+        app.set_diagnostics(diagnostics);
+        // The current query is searched again in the reloaded index.
+        app.current_query = None;
+        Ok(())
+    })
+    .unwrap_or_else(|e| {
         log.error(&format!("{}", e));
-        std::process::exit(1);
+        std::process::exit(FAILURE);
     });
 }
 
-fn search_tui(app: &mut SearchApp<'_>) -> Result<()> {
+/// Returns the searcher and the query parser of the search index of the
+/// schema.
+fn open_search_index(
+    log: impl Logger + Sync + Clone,
+    cache: &Cache,
+    schema: &TelemetrySchema,
+) -> Result<(tantivy::Searcher, QueryParser)> {
+    let (index, fields) = search_index(log, cache, schema)?;
+    let reader: tantivy::IndexReader = index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()?;
+    let DocFields {
+        path,
+        brief,
+        note,
+        tag,
+    } = fields;
+    let query_parser = QueryParser::for_index(&index, vec![path, brief, note, tag]);
+    Ok((reader.searcher(), query_parser))
+}
+
+impl SearchApp<'_> {
+    /// Replaces the diagnostics, the panel being shown if there are errors.
+    fn set_diagnostics(&mut self, diagnostics: Diagnostics) {
+        self.diagnostics.clear();
+        self.diagnostics.items.extend(
+            diagnostics
+                .errors
+                .iter()
+                .map(|error| DiagnosticItem {
+                    severity: Severity::Error,
+                    message: error.to_string(),
+                })
+                .chain(diagnostics.warnings.iter().map(|warning| DiagnosticItem {
+                    severity: Severity::Warning,
+                    message: warning.error.to_string(),
+                })),
+        );
+        self.diagnostics.next();
+        self.show_diagnostics = !diagnostics.errors.is_empty();
+    }
+
+    /// Returns the number of errors and warnings of the diagnostics.
+    fn diagnostic_counts(&self) -> (usize, usize) {
+        let errors = self
+            .diagnostics
+            .items
+            .iter()
+            .filter(|item| item.severity == Severity::Error)
+            .count();
+        (errors, self.diagnostics.items.len() - errors)
+    }
+}
+
+fn search_tui(
+    app: &mut SearchApp<'_>,
+    reload: &dyn Fn(&mut SearchApp<'_>) -> Result<()>,
+) -> Result<()> {
     // Startup
     let stdout = io::stdout();
     let mut stdout = stdout.lock();
@@ -416,7 +525,7 @@ fn search_tui(app: &mut SearchApp<'_>) -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut term = Terminal::new(backend)?;
 
-    let status = run(app);
+    let status = run(app, reload);
 
     // Shutdown
     disable_raw_mode()?;
@@ -469,9 +578,7 @@ fn ui(app: &mut SearchApp, frame: &mut Frame<'_>) {
         }
     });
 
-    let selected_style = Style::default()
-        .bg(Color::Rgb(106, 47, 47))
-        .fg(app.theme.title);
+    let selected_style = app.theme.selected;
     let normal_style = Style::default();
     let header_cells = ["Path:", "Brief:"]
         .iter()
@@ -516,6 +623,16 @@ fn ui(app: &mut SearchApp, frame: &mut Frame<'_>) {
         .highlight_style(selected_style)
         .highlight_symbol(">> ");
 
+    if app.show_diagnostics {
+        frame.render_stateful_widget(
+            diagnostics_table(app),
+            inner_layout[1],
+            &mut app.diagnostics.state,
+        );
+        frame.render_widget(diagnostic_area(app), inner_layout[0]);
+        frame.render_widget(app.search_area.widget(), outer_layout[1]);
+        return;
+    }
     frame.render_stateful_widget(content, inner_layout[1], &mut app.results.state);
 
     // Detail area
@@ -534,6 +651,7 @@ fn ui(app: &mut SearchApp, frame: &mut Frame<'_>) {
 fn summary_area<'a>(app: &'a SearchApp<'a>) -> Paragraph<'a> {
     let area_title = "Summary";
     let semconv_catalog = app.schema.semantic_convention_catalog();
+    let (error_count, warning_count) = app.diagnostic_counts();
     let text = vec![
         Line::from(""),
         Line::from("Telemetry schema:"),
@@ -558,7 +676,10 @@ fn summary_area<'a>(app: &'a SearchApp<'a>) -> Paragraph<'a> {
             Span::raw(format!("- {} metrics.", semconv_catalog.metric_count())),
         ]),
         Line::from(""),
-        Line::from(""),
+        Line::from(vec![
+            Span::raw(format!("Diagnostics: {} error(s), {} warning(s) (press `Tab` to list them, `F5` to reload).", error_count, warning_count)),
+        ]),
+        Line::from(app.status.as_deref().unwrap_or_default()),
         Line::from(">> Enter search terms, operators, or use path:, brief:, tag:, or note: prefixes to target specific fields."),
     ];
 
@@ -576,6 +697,87 @@ fn summary_area<'a>(app: &'a SearchApp<'a>) -> Paragraph<'a> {
         .wrap(Wrap { trim: true })
 }
 
+/// Returns the table of the diagnostics (severity and first line of the
+/// message).
+fn diagnostics_table<'a>(app: &SearchApp<'a>) -> Table<'a> {
+    let (error_count, warning_count) = app.diagnostic_counts();
+    let header_cells = ["Severity:", "Message:"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(app.theme.title)));
+    let header = Row::new(header_cells).height(1).bottom_margin(0);
+    let rows: Vec<Row> = app
+        .diagnostics
+        .items
+        .iter()
+        .map(|item| {
+            let color = match item.severity {
+                Severity::Error => app.theme.error,
+                Severity::Warning => app.theme.warning,
+            };
+            let first_line = item.message.lines().next().unwrap_or_default();
+            Row::new(vec![
+                Cell::from(item.severity.to_string()).fg(color),
+                Cell::from(first_line.to_owned()).fg(app.theme.value),
+            ])
+            .height(1)
+            .bottom_margin(0)
+        })
+        .collect();
+
+    Table::new(rows, [Constraint::Max(10), Constraint::Min(10)])
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border))
+                .title(format!(
+                    "Diagnostics: {} error(s), {} warning(s) (`Tab` to search) ",
+                    error_count, warning_count
+                ))
+                .title_style(Style::default().fg(app.theme.value)),
+        )
+        .highlight_style(app.theme.selected)
+        .highlight_symbol(">> ")
+}
+
+/// Returns the full message of the selected diagnostic.
+fn diagnostic_area<'a>(app: &'a SearchApp<'a>) -> Paragraph<'a> {
+    let item = app
+        .diagnostics
+        .state
+        .selected()
+        .and_then(|i| app.diagnostics.items.get(i));
+    let mut text = vec![];
+    if let Some(status) = &app.status {
+        text.push(Line::from(status.as_str()));
+        text.push(Line::from(""));
+    }
+    match item {
+        Some(item) => {
+            let color = match item.severity {
+                Severity::Error => app.theme.error,
+                Severity::Warning => app.theme.warning,
+            };
+            text.push(Line::from(Span::raw(item.severity.to_string()).fg(color)));
+            text.extend(item.message.lines().map(Line::from));
+        }
+        None => text.push(Line::from(
+            "No diagnostics, the schema and its registry resolved without error.",
+        )),
+    }
+
+    Paragraph::new(text)
+        .style(Style::default().fg(app.theme.value))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.border))
+                .title("Diagnostic ")
+                .title_style(Style::default().fg(app.theme.title)),
+        )
+        .wrap(Wrap { trim: false })
+}
+
 fn detail_area<'a>(app: &'a SearchApp<'a>, item: Option<&'a ResultItem>) -> Paragraph<'a> {
     let mut area_title = "Details";
     let paragraph = if let Some(item) = item {
@@ -588,6 +790,7 @@ fn detail_area<'a>(app: &'a SearchApp<'a>, item: Option<&'a ResultItem>) -> Para
                     app.schema
                         .semantic_convention_catalog()
                         .attribute_with_provenance(id),
+                    app.schema.semantic_convention_catalog(),
                     &app.theme,
                 )
             }
@@ -597,6 +800,7 @@ fn detail_area<'a>(app: &'a SearchApp<'a>, item: Option<&'a ResultItem>) -> Para
                     app.schema
                         .semantic_convention_catalog()
                         .metric_with_provenance(id),
+                    app.schema.semantic_convention_catalog(),
                     &app.theme,
                 )
             }
@@ -727,6 +931,13 @@ fn update(app: &mut SearchApp) -> Result<()> {
                         app.should_quit = true;
                         return Ok(());
                     }
+                    KeyCode::Tab => app.show_diagnostics = !app.show_diagnostics,
+                    KeyCode::F(5) => app.should_reload = true,
+                    KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.should_reload = true
+                    }
+                    KeyCode::Up if app.show_diagnostics => app.diagnostics.previous(),
+                    KeyCode::Down if app.show_diagnostics => app.diagnostics.next(),
                     KeyCode::Up => app.results.previous(),
                     KeyCode::Down => app.results.next(),
                     KeyCode::Enter => {}
@@ -742,7 +953,7 @@ fn update(app: &mut SearchApp) -> Result<()> {
     Ok(())
 }
 
-fn run(app: &mut SearchApp<'_>) -> Result<()> {
+fn run(app: &mut SearchApp<'_>, reload: &dyn Fn(&mut SearchApp<'_>) -> Result<()>) -> Result<()> {
     // ratatui terminal
     let mut t = Terminal::new(CrosstermBackend::new(std::io::stderr()))?;
 
@@ -755,6 +966,16 @@ fn run(app: &mut SearchApp<'_>) -> Result<()> {
         // application update
         update(app)?;
 
+        // the schema is reloaded in place, the previous one being kept if it
+        // can't be loaded anymore
+        if app.should_reload {
+            app.should_reload = false;
+            app.status = Some(match reload(app) {
+                Ok(()) => "Schema reloaded.".to_owned(),
+                Err(e) => format!("Reload failed: {}", e),
+            });
+        }
+
         // application exit
         if app.should_quit {
             break;
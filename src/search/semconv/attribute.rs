@@ -7,12 +7,13 @@ use ratatui::widgets::Paragraph;
 
 use crate::search::theme::ThemeConfig;
 use weaver_semconv::attribute::AttributeSpec;
-use weaver_semconv::AttributeSpecWithProvenance;
+use weaver_semconv::{AttributeSpecWithProvenance, SemConvSpecs};
 
-use crate::search::semconv::examples;
+use crate::search::semconv::{examples, obligations};
 
 pub fn widget<'a>(
     attribute: Option<&'a AttributeSpecWithProvenance>,
+    registry: &'a SemConvSpecs,
     theme: &'a ThemeConfig,
 ) -> Paragraph<'a> {
     match attribute.as_ref() {
@@ -29,6 +30,7 @@ pub fn widget<'a>(
                     note,
                     stability,
                     deprecated,
+                    ..
                 },
             provenance,
         }) => {
@@ -103,6 +105,8 @@ pub fn widget<'a>(
                 examples::append_lines(examples, &mut text, theme);
             }
 
+            obligations::append_attribute_lines(registry, id, &mut text, theme);
+
             // Provenance
             text.push(Line::from(""));
             text.push(Line::from(Span::styled(
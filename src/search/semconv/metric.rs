@@ -6,12 +6,13 @@ use ratatui::prelude::{Line, Span, Style};
 use ratatui::widgets::Paragraph;
 
 use crate::search::theme::ThemeConfig;
-use weaver_semconv::MetricSpecWithProvenance;
+use weaver_semconv::{MetricSpecWithProvenance, SemConvSpecs};
 
-use crate::search::semconv::attributes;
+use crate::search::semconv::{attributes, obligations};
 
 pub fn widget<'a>(
     metric: Option<&'a MetricSpecWithProvenance>,
+    registry: &'a SemConvSpecs,
     theme: &'a ThemeConfig,
 ) -> Paragraph<'a> {
     match metric {
@@ -53,6 +54,13 @@ pub fn widget<'a>(
 
             attributes::append_lines(metric.attributes.as_slice(), &mut text, theme);
 
+            if let Some(group) = registry
+                .groups()
+                .find(|group| group.metric_name.as_deref() == Some(metric.name.as_str()))
+            {
+                obligations::append_group_lines(registry, &group.id, &mut text, theme);
+            }
+
             // Provenance
             text.push(Line::from(""));
             text.push(Line::from(vec![
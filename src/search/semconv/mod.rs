@@ -6,3 +6,4 @@ pub mod attribute;
 pub mod attributes;
 pub mod examples;
 pub mod metric;
+pub mod obligations;
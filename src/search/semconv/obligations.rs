@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Render the obligations of semantic convention groups, i.e. their resolved
+//! extends chain and their `any_of` constraint sets (own constraints,
+//! constraints inherited from the extended groups and constraints included
+//! with an `include` constraint).
+//!
+//! Note: the specs must be kept by the resolver (see
+//! [`weaver_semconv::ResolverConfig::with_keep_specs`]), otherwise no group
+//! is found and nothing is rendered.
+
+use std::collections::HashSet;
+
+use ratatui::prelude::{Line, Span, Style};
+
+use weaver_semconv::attribute::AttributeSpec;
+use weaver_semconv::group::GroupSpec;
+use weaver_semconv::SemConvSpecs;
+
+use crate::search::theme::ThemeConfig;
+
+/// An `any_of` constraint set with the group declaring it.
+struct AnyOfSet<'a> {
+    /// The id of the group declaring the constraint.
+    group_id: &'a str,
+    /// The attribute ids of the constraint (at least one must be set).
+    attribute_ids: &'a [String],
+}
+
+/// Appends the extends chain and the `any_of` constraint sets of a group.
+pub fn append_group_lines<'a>(
+    registry: &SemConvSpecs,
+    group_id: &str,
+    text: &mut Vec<Line<'a>>,
+    theme: &ThemeConfig,
+) {
+    if find_group(registry, group_id).is_none() {
+        return;
+    }
+
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("Group     : ", Style::default().fg(theme.label)),
+        Span::raw(group_id.to_owned()),
+    ]));
+    append_extends_chain(registry, group_id, text, theme);
+
+    let sets = any_of_sets(registry, group_id);
+    if !sets.is_empty() {
+        text.push(Line::from(Span::styled(
+            "Constraints (any_of): ",
+            Style::default().fg(theme.label),
+        )));
+        for set in sets {
+            text.push(any_of_line(&set, group_id));
+        }
+    }
+}
+
+/// Appends the groups defining an attribute (with their extends chain) and
+/// the `any_of` constraint sets referencing the attribute.
+pub fn append_attribute_lines<'a>(
+    registry: &SemConvSpecs,
+    attribute_id: &str,
+    text: &mut Vec<Line<'a>>,
+    theme: &ThemeConfig,
+) {
+    let defining_groups: Vec<&GroupSpec> = registry
+        .groups()
+        .filter(|group| {
+            group.attributes.iter().any(|attr| match attr {
+                AttributeSpec::Id { id, .. } if group.prefix.is_empty() => id == attribute_id,
+                AttributeSpec::Id { id, .. } => format!("{}.{}", group.prefix, id) == attribute_id,
+                AttributeSpec::Ref { .. } => false,
+            })
+        })
+        .collect();
+    for group in defining_groups {
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("Defined in: ", Style::default().fg(theme.label)),
+            Span::raw(group.id.clone()),
+        ]));
+        append_extends_chain(registry, &group.id, text, theme);
+    }
+
+    let mut sets: Vec<AnyOfSet<'_>> = vec![];
+    for group in registry.groups() {
+        sets.extend(
+            any_of_sets(registry, &group.id)
+                .into_iter()
+                .filter(|set| set.attribute_ids.iter().any(|id| id == attribute_id))
+                .map(|set| AnyOfSet {
+                    // The group to which the constraint applies.
+                    group_id: group.id.as_str(),
+                    attribute_ids: set.attribute_ids,
+                }),
+        );
+    }
+    if !sets.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Constraints (any_of): ",
+            Style::default().fg(theme.label),
+        )));
+        for set in sets {
+            text.push(Line::from(format!(
+                "- {}: one of [{}]",
+                set.group_id,
+                set.attribute_ids.join(", ")
+            )));
+        }
+    }
+}
+
+/// Appends the extends chain of a group (nothing if the group doesn't
+/// extend another group).
+fn append_extends_chain<'a>(
+    registry: &SemConvSpecs,
+    group_id: &str,
+    text: &mut Vec<Line<'a>>,
+    theme: &ThemeConfig,
+) {
+    let (chain, cycle) = extends_chain(registry, group_id);
+    if chain.len() <= 1 {
+        return;
+    }
+    let mut chain = chain.join(" -> ");
+    if cycle {
+        chain.push_str(" -> (cycle)");
+    }
+    text.push(Line::from(vec![
+        Span::styled("Extends   : ", Style::default().fg(theme.label)),
+        Span::raw(chain),
+    ]));
+}
+
+/// Returns the extends chain of a group, starting with the group itself,
+/// and whether the chain is a cycle. The chain stops at the first group not
+/// found in the registry.
+fn extends_chain<'a>(registry: &'a SemConvSpecs, group_id: &'a str) -> (Vec<&'a str>, bool) {
+    let mut chain = vec![group_id];
+    let mut visited = HashSet::from([group_id]);
+    let mut current = find_group(registry, group_id);
+    while let Some(extends) = current.and_then(|group| group.extends.as_deref()) {
+        if !visited.insert(extends) {
+            return (chain, true);
+        }
+        chain.push(extends);
+        current = find_group(registry, extends);
+    }
+    (chain, false)
+}
+
+/// Returns the `any_of` constraint sets applying to a group: its own sets,
+/// the sets of the groups of its extends chain and the sets of the included
+/// groups (transitively).
+fn any_of_sets<'a>(registry: &'a SemConvSpecs, group_id: &'a str) -> Vec<AnyOfSet<'a>> {
+    let mut sets = vec![];
+    let mut visited = HashSet::new();
+    let mut pending = vec![group_id];
+    while let Some(group_id) = pending.pop() {
+        if !visited.insert(group_id) {
+            continue;
+        }
+        let Some(group) = find_group(registry, group_id) else {
+            continue;
+        };
+        for constraint in group.constraints.iter() {
+            if !constraint.any_of.is_empty() {
+                sets.push(AnyOfSet {
+                    group_id: group.id.as_str(),
+                    attribute_ids: constraint.any_of.as_slice(),
+                });
+            }
+            if let Some(include) = constraint.include.as_deref() {
+                pending.push(include);
+            }
+        }
+        if let Some(extends) = group.extends.as_deref() {
+            pending.push(extends);
+        }
+    }
+    sets
+}
+
+/// Returns the line of an `any_of` constraint set, mentioning the group
+/// declaring it when it is not the rendered group.
+fn any_of_line<'a>(set: &AnyOfSet<'_>, group_id: &str) -> Line<'a> {
+    let mut line = format!("- one of [{}]", set.attribute_ids.join(", "));
+    if set.group_id != group_id {
+        line.push_str(&format!(" (from {})", set.group_id));
+    }
+    Line::from(line)
+}
+
+/// Returns the group with the given id.
+fn find_group<'a>(registry: &'a SemConvSpecs, group_id: &str) -> Option<&'a GroupSpec> {
+    registry.groups().find(|group| group.id == group_id)
+}
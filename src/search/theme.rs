@@ -1,4 +1,4 @@
-use ratatui::prelude::Color;
+use ratatui::prelude::{Color, Modifier, Style};
 
 /// Theme configurations
 pub struct ThemeConfig {
@@ -10,4 +10,39 @@ pub struct ThemeConfig {
     pub label: Color,
     /// Color of the values (i.e. field values)
     pub value: Color,
+    /// Style of the selected result
+    pub selected: Style,
+    /// Color of the errors of the diagnostics panel
+    pub error: Color,
+    /// Color of the warnings of the diagnostics panel
+    pub warning: Color,
+}
+
+impl ThemeConfig {
+    /// Creates the theme, with colors or with the default colors of the
+    /// terminal only (the selected result being reversed).
+    pub fn new(color: bool) -> Self {
+        if !color {
+            return ThemeConfig {
+                title: Color::Reset,
+                border: Color::Reset,
+                label: Color::Reset,
+                value: Color::Reset,
+                selected: Style::default().add_modifier(Modifier::REVERSED),
+                error: Color::Reset,
+                warning: Color::Reset,
+            };
+        }
+        ThemeConfig {
+            title: Color::Rgb(238, 238, 238),
+            border: Color::Rgb(85, 109, 89),
+            label: Color::Rgb(128, 208, 163),
+            value: Color::Rgb(204, 204, 204),
+            selected: Style::default()
+                .bg(Color::Rgb(106, 47, 47))
+                .fg(Color::Rgb(238, 238, 238)),
+            error: Color::Rgb(224, 108, 117),
+            warning: Color::Rgb(229, 192, 123),
+        }
+    }
 }
@@ -0,0 +1,542 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Command to serve the resolved registries and schemas of several projects
+//! over HTTP.
+//!
+//! Each project is described in a configuration file and is isolated from the
+//! others: it has its own cache directory and its own resolution state. The
+//! following endpoints are exposed:
+//! - `GET /projects`: the list of the projects,
+//! - `GET /projects/{name}/registry`: the resolved registry of a project,
+//! - `GET /projects/{name}/schema`: the resolved telemetry schema of a project,
+//! - `POST /projects/{name}/reload`: discards the resolution state of a project
+//!   and fetches its git repos again on the next request.
+//!
+//! The network operations of the resolutions (git clones, fetched schemas)
+//! are bounded by the connect and read timeouts of the network configuration,
+//! and a client not sending its request or not reading the response within
+//! the client timeout is disconnected. The connections beyond the maximum
+//! number of concurrent connections are rejected.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::exit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use weaver_cache::Cache;
+use weaver_logger::Logger;
+use weaver_resolver::SchemaResolver;
+use weaver_schema::SemConvImport;
+use weaver_semconv::ResolverConfig;
+
+use crate::cli::CacheArgs;
+use crate::exit_code::{ExitCode, FAILURE};
+
+/// Parameters for the `serve` command
+#[derive(Args)]
+pub struct ServeCommand {
+    /// Configuration file listing the served projects
+    #[arg(short, long)]
+    pub config: PathBuf,
+
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:8080")]
+    pub address: String,
+
+    /// Timeout of the reads and writes of the client connections, in seconds
+    /// (0 disables it)
+    #[arg(long, default_value = "30", value_name = "SECONDS")]
+    pub client_timeout: u64,
+
+    /// Maximum number of connections handled concurrently, the following
+    /// ones are rejected with a 503 response
+    #[arg(long, default_value = "64")]
+    pub max_connections: usize,
+}
+
+/// Configuration of the `serve` command.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ServeConfig {
+    /// The served projects.
+    projects: Vec<ProjectConfig>,
+}
+
+/// Configuration of a served project.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct ProjectConfig {
+    /// The name of the project (used in the endpoint paths).
+    name: String,
+    /// The git URL of the semantic convention registry of the project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registry: Option<String>,
+    /// The path of the registry in the git repository.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Accept the YAML extensions supported by the Python build-tools.
+    #[serde(default)]
+    build_tools_compat: bool,
+    /// The telemetry schema of the project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<PathBuf>,
+    /// The cache directory of the project (defaults to a `projects/<name>`
+    /// sub-directory of the cache directory).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_dir: Option<PathBuf>,
+}
+
+/// A served project and its resolution state.
+struct Project {
+    config: ProjectConfig,
+    cache: Cache,
+    /// The resolved registry (YAML), once requested.
+    registry: Mutex<Option<String>>,
+    /// The resolved telemetry schema (YAML), once requested.
+    schema: Mutex<Option<String>>,
+}
+
+/// An HTTP response.
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: message.into(),
+        }
+    }
+}
+
+/// Serve the projects defined in the configuration file
+pub fn command_serve(
+    log: impl Logger + Sync + Send + Clone,
+    cache_args: &CacheArgs,
+    command: &ServeCommand,
+) {
+    let config: ServeConfig = std::fs::File::open(&command.config)
+        .map_err(|e| e.to_string())
+        .and_then(|file| serde_yaml::from_reader(file).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            log.error(&format!(
+                "Invalid serve configuration `{}`: {}",
+                command.config.display(),
+                e
+            ));
+            exit(FAILURE);
+        });
+
+    let cache_root = cache_args.cache().unwrap_or_else(|e| {
+        log.error(&e.to_string());
+        exit(e.exit_code());
+    });
+    let mut projects: Vec<Project> = vec![];
+    for project in config.projects {
+        if projects.iter().any(|p| p.config.name == project.name) {
+            log.error(&format!("Project `{}` defined twice", project.name));
+            exit(FAILURE);
+        }
+        let cache_dir = project.cache_dir.clone().unwrap_or_else(|| {
+            cache_root
+                .path()
+                .join("projects")
+                .join(sanitize(&project.name))
+        });
+        let cache = Cache::try_new(Some(cache_dir))
+            .map(|cache| cache.with_refresh(cache_args.refresh))
+            .unwrap_or_else(|e| {
+                log.error(&e.to_string());
+                exit(e.exit_code());
+            });
+        projects.push(Project {
+            config: project,
+            cache,
+            registry: Mutex::new(None),
+            schema: Mutex::new(None),
+        });
+    }
+
+    let listener = TcpListener::bind(&command.address).unwrap_or_else(|e| {
+        log.error(&format!("Failed to listen on {}: {}", command.address, e));
+        exit(FAILURE);
+    });
+    log.success(&format!(
+        "Serving {} project(s) on http://{}",
+        projects.len(),
+        command.address
+    ));
+
+    let client_timeout = Duration::from_secs(command.client_timeout);
+    let connections = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let Some(slot) = ConnectionSlot::acquire(&connections, command.max_connections)
+                    else {
+                        reject_connection(&log, stream, client_timeout);
+                        continue;
+                    };
+                    let log = log.clone();
+                    let projects = &projects;
+                    _ = scope.spawn(move || {
+                        handle_connection(log, projects, stream, client_timeout);
+                        drop(slot);
+                    });
+                }
+                Err(e) => {
+                    log.warn(&format!("Connection failed: {}", e));
+                }
+            }
+        }
+    });
+}
+
+/// A connection counted in the number of concurrent connections, released
+/// when dropped.
+struct ConnectionSlot<'a> {
+    connections: &'a AtomicUsize,
+}
+
+impl<'a> ConnectionSlot<'a> {
+    /// Counts a new connection, or returns `None` if the maximum number of
+    /// concurrent connections is reached.
+    fn acquire(connections: &'a AtomicUsize, max: usize) -> Option<Self> {
+        connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < max).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self { connections })
+    }
+}
+
+impl Drop for ConnectionSlot<'_> {
+    fn drop(&mut self) {
+        _ = self.connections.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Rejects a connection exceeding the maximum number of concurrent
+/// connections.
+fn reject_connection(log: &impl Logger, stream: TcpStream, timeout: Duration) {
+    log.warn("Maximum number of connections reached, connection rejected");
+    let timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+    if stream.set_write_timeout(timeout).is_ok() {
+        write_response(log, stream, &Response::error(503, "Too many connections"));
+    }
+}
+
+/// Reads a request from the given stream and writes the response. The
+/// connection is closed if a read or a write takes longer than the given
+/// timeout.
+fn handle_connection(
+    log: impl Logger + Sync + Send + Clone,
+    projects: &[Project],
+    stream: TcpStream,
+    timeout: Duration,
+) {
+    let timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+    if let Err(e) = stream
+        .set_read_timeout(timeout)
+        .and_then(|_| stream.set_write_timeout(timeout))
+    {
+        log.warn(&format!("Failed to set the connection timeout: {}", e));
+        return;
+    }
+
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // The headers are not used, they are only consumed.
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|n| n > 0) && header.trim() != "" {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => route(log.clone(), projects, method, path),
+        _ => Response::error(400, "Bad request"),
+    };
+    write_response(&log, stream, &response);
+}
+
+/// Writes the given response to the given stream.
+fn write_response(log: &impl Logger, mut stream: TcpStream, response: &Response) {
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let result = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/yaml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason,
+        response.body.len(),
+        response.body
+    );
+    if let Err(e) = result {
+        log.warn(&format!("Failed to write the response: {}", e));
+    }
+}
+
+/// Dispatches a request to the corresponding endpoint.
+fn route(
+    log: impl Logger + Sync + Clone,
+    projects: &[Project],
+    method: &str,
+    path: &str,
+) -> Response {
+    let segments: Vec<&str> = path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["projects"]) => {
+            let configs: Vec<&ProjectConfig> = projects.iter().map(|p| &p.config).collect();
+            to_yaml(&configs)
+        }
+        (method, ["projects", name, endpoint]) => {
+            let Some(project) = projects.iter().find(|p| p.config.name == *name) else {
+                return Response::error(404, format!("Project `{}` not found", name));
+            };
+            match (method, *endpoint) {
+                ("GET", "registry") => project.resolved_registry(log),
+                ("GET", "schema") => project.resolved_schema(log),
+                ("POST", "reload") => {
+                    *project.registry.lock().expect("registry lock failed") = None;
+                    *project.schema.lock().expect("schema lock failed") = None;
+                    project.cache.evict_git_repos();
+                    Response::ok(format!("Project `{}` reloaded", name))
+                }
+                (_, "registry" | "schema" | "reload") => Response::error(405, "Method not allowed"),
+                _ => Response::error(404, format!("Endpoint `{}` not found", path)),
+            }
+        }
+        _ => Response::error(404, format!("Endpoint `{}` not found", path)),
+    }
+}
+
+impl Project {
+    /// Returns the resolved registry of the project, resolving it on the
+    /// first call.
+    fn resolved_registry(&self, log: impl Logger + Sync + Clone) -> Response {
+        let Some(registry_url) = &self.config.registry else {
+            return Response::error(
+                404,
+                format!("Project `{}` has no registry", self.config.name),
+            );
+        };
+
+        let mut resolved = self.registry.lock().expect("registry lock failed");
+        if let Some(yaml) = resolved.as_ref() {
+            return Response::ok(yaml.clone());
+        }
+
+        let mut resolver_config = ResolverConfig::with_keep_specs();
+        if self.config.build_tools_compat {
+            resolver_config = resolver_config.with_build_tools_compat();
+        }
+        let imports = [SemConvImport::GitUrl {
+            git_url: registry_url.clone(),
+            path: self.config.path.clone(),
+            mirrors: vec![],
+        }];
+        let result = SchemaResolver::semconv_registry_from_imports(
+            &imports,
+            resolver_config,
+            &self.cache,
+            log.clone(),
+        )
+        .and_then(|mut registry| {
+            SchemaResolver::resolve_semantic_convention_registry(&mut registry, log)
+        });
+
+        match result {
+            Ok(schema) => {
+                let response = to_yaml(&schema);
+                if response.status == 200 {
+                    *resolved = Some(response.body.clone());
+                }
+                response
+            }
+            Err(e) => Response::error(500, e.to_string()),
+        }
+    }
+
+    /// Returns the resolved telemetry schema of the project, resolving it on
+    /// the first call.
+    fn resolved_schema(&self, log: impl Logger + Sync + Clone) -> Response {
+        let Some(schema_path) = &self.config.schema else {
+            return Response::error(404, format!("Project `{}` has no schema", self.config.name));
+        };
+
+        let mut resolved = self.schema.lock().expect("schema lock failed");
+        if let Some(yaml) = resolved.as_ref() {
+            return Response::ok(yaml.clone());
+        }
+
+        match SchemaResolver::resolve_schema_file(schema_path.clone(), &self.cache, log) {
+            Ok(schema) => {
+                let response = to_yaml(&schema);
+                if response.status == 200 {
+                    *resolved = Some(response.body.clone());
+                }
+                response
+            }
+            Err(e) => Response::error(500, e.to_string()),
+        }
+    }
+}
+
+/// Serializes the given value into a YAML response.
+fn to_yaml<T: Serialize>(value: &T) -> Response {
+    match serde_yaml::to_string(value) {
+        Ok(yaml) => Response::ok(yaml),
+        Err(e) => Response::error(500, e.to_string()),
+    }
+}
+
+/// Returns a project name usable as a directory name.
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::time::Instant;
+
+    use tempdir::TempDir;
+    use weaver_logger::NullLogger;
+
+    use super::*;
+
+    fn project(name: &str, cache_dir: &TempDir) -> Project {
+        Project {
+            config: ProjectConfig {
+                name: name.to_owned(),
+                registry: Some(
+                    "https://github.com/open-telemetry/semantic-conventions.git".to_owned(),
+                ),
+                path: None,
+                build_tools_compat: false,
+                schema: None,
+                cache_dir: None,
+            },
+            cache: Cache::try_new(Some(cache_dir.path().join(name))).unwrap(),
+            registry: Mutex::new(Some("groups: []\n".to_owned())),
+            schema: Mutex::new(None),
+        }
+    }
+
+    #[test]
+    fn test_route() {
+        let cache_dir = TempDir::new("weaver-serve").unwrap();
+        let projects = [project("team-a", &cache_dir), project("team-b", &cache_dir)];
+        let route = |method: &str, path: &str| route(NullLogger::new(), &projects, method, path);
+
+        let response = route("GET", "/projects");
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("name: team-a"));
+        assert!(response.body.contains("name: team-b"));
+
+        // The resolution state of a project is served as is.
+        let response = route("GET", "/projects/team-a/registry?format=yaml");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "groups: []\n");
+
+        assert_eq!(route("GET", "/projects/team-c/registry").status, 404);
+        assert_eq!(route("GET", "/projects/team-a/unknown").status, 404);
+        assert_eq!(route("GET", "/projects/team-a/schema").status, 404);
+        assert_eq!(route("DELETE", "/projects/team-a/registry").status, 405);
+
+        // Reloading a project only discards its own resolution state.
+        assert_eq!(route("POST", "/projects/team-a/reload").status, 200);
+        assert!(projects[0].registry.lock().unwrap().is_none());
+        assert!(projects[1].registry.lock().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_handle_connection() {
+        let cache_dir = TempDir::new("weaver-serve").unwrap();
+        let projects = [project("team-a", &cache_dir)];
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // A complete request is answered.
+        let mut client = TcpStream::connect(address).unwrap();
+        client
+            .write_all(b"GET /projects HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        handle_connection(
+            NullLogger::new(),
+            &projects,
+            stream,
+            Duration::from_secs(10),
+        );
+        let mut response = String::new();
+        _ = client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("name: team-a\n"));
+
+        // A client not sending its request is disconnected after the timeout.
+        let mut client = TcpStream::connect(address).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        let start = Instant::now();
+        handle_connection(
+            NullLogger::new(),
+            &projects,
+            stream,
+            Duration::from_millis(100),
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+        let mut response = String::new();
+        _ = client.read_to_string(&mut response).unwrap();
+        assert!(response.is_empty());
+    }
+
+    #[test]
+    fn test_connection_slot() {
+        let connections = AtomicUsize::new(0);
+        let slot_1 = ConnectionSlot::acquire(&connections, 2).unwrap();
+        let slot_2 = ConnectionSlot::acquire(&connections, 2).unwrap();
+        assert!(ConnectionSlot::acquire(&connections, 2).is_none());
+        drop(slot_1);
+        let _slot_3 = ConnectionSlot::acquire(&connections, 2).unwrap();
+        drop(slot_2);
+        assert_eq!(connections.load(Ordering::Acquire), 1);
+    }
+}
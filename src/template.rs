@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Commands to manage the language packs (templates).
+
+use clap::{Args, Subcommand, ValueEnum};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+
+use weaver_logger::Logger;
+use weaver_template::context_schema::TemplateKind;
+use weaver_template::scaffold::init_language_pack;
+
+use crate::exit_code::{ExitCode, FAILURE};
+
+/// Specify the `template` command
+#[derive(Args)]
+pub struct TemplateCommand {
+    /// Define the sub-commands for the `template` command
+    #[clap(subcommand)]
+    pub command: TemplateSubCommand,
+}
+
+/// Sub-commands for the `template` command
+#[derive(Subcommand)]
+pub enum TemplateSubCommand {
+    /// Scaffold a new language pack (config.yaml, starter templates, macro
+    /// file and smoke test with its fixture schema)
+    Init(TemplateInit),
+    /// Generate the JSON schemas of the contexts passed to each kind of
+    /// template
+    ContextSchema(TemplateContextSchema),
+}
+
+/// Parameters for the `template init` sub-command
+#[derive(Args)]
+pub struct TemplateInit {
+    /// Name of the language pack to create
+    pub language: String,
+
+    /// Template root directory
+    #[arg(short, long, default_value = "templates")]
+    pub templates: PathBuf,
+}
+
+/// Parameters for the `template context-schema` sub-command
+#[derive(Args)]
+pub struct TemplateContextSchema {
+    /// Kind of template (all the kinds by default)
+    #[arg(short, long, value_enum)]
+    pub kind: Option<TemplateKindArg>,
+
+    /// Directory where a `<kind>.schema.json` file is written per kind of
+    /// template (stdout by default)
+    #[arg(short, long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+}
+
+/// The kinds of template, a template being recognized by its file name.
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum TemplateKindArg {
+    /// Templates named `span`, rendered once per span
+    Span,
+    /// Templates named `span_test`, rendered once per span to generate test
+    /// helpers
+    SpanTest,
+    /// Templates named `event`, rendered once per event
+    Event,
+    /// Templates named `metric`, rendered once per univariate metric
+    Metric,
+    /// Templates named `metric_group`, rendered once per multivariate metric
+    MetricGroup,
+    /// Templates named `resource`, rendered once with the resource
+    Resource,
+    /// Templates named `compat`, rendered once per previous version
+    Compat,
+    /// Templates named `readme`, rendered last with the generated files
+    Readme,
+    /// The other templates, rendered once with the whole telemetry schema
+    Other,
+}
+
+impl From<TemplateKindArg> for TemplateKind {
+    fn from(kind: TemplateKindArg) -> Self {
+        match kind {
+            TemplateKindArg::Span => TemplateKind::Span,
+            TemplateKindArg::SpanTest => TemplateKind::SpanTest,
+            TemplateKindArg::Event => TemplateKind::Event,
+            TemplateKindArg::Metric => TemplateKind::Metric,
+            TemplateKindArg::MetricGroup => TemplateKind::MetricGroup,
+            TemplateKindArg::Resource => TemplateKind::Resource,
+            TemplateKindArg::Compat => TemplateKind::Compat,
+            TemplateKindArg::Readme => TemplateKind::Readme,
+            TemplateKindArg::Other => TemplateKind::Other,
+        }
+    }
+}
+
+/// Manage the language packs
+pub fn command_template(log: impl Logger + Sync + Clone, command: &TemplateCommand) {
+    match command.command {
+        TemplateSubCommand::Init(ref params) => {
+            let files =
+                init_language_pack(&params.templates, &params.language).unwrap_or_else(|e| {
+                    log.error(&e.to_string());
+                    exit(e.exit_code());
+                });
+            for file in files.iter() {
+                log.log(&format!("Created {}", file.display()));
+            }
+            log.success(&format!(
+                "Language pack `{}` scaffolded, generate the smoke test with `weaver gen-client --schema {} --language {} --output-dir <DIR> --with-tests`",
+                params.language,
+                params.templates.join(&params.language).join("tests/fixture.yaml").display(),
+                params.language
+            ));
+        }
+        TemplateSubCommand::ContextSchema(ref params) => {
+            let kinds = match params.kind {
+                Some(kind) => vec![kind.into()],
+                None => TemplateKind::ALL.to_vec(),
+            };
+            if let Err(e) = write_context_schemas(&log, &kinds, params.output_dir.as_deref()) {
+                log.error(&e);
+                exit(FAILURE);
+            }
+        }
+    }
+}
+
+/// Writes the context schemas of the given kinds of template, either as one
+/// file per kind in the output directory or on stdout (a single schema, or an
+/// object indexing the schemas by kind).
+fn write_context_schemas(
+    log: &impl Logger,
+    kinds: &[TemplateKind],
+    output_dir: Option<&Path>,
+) -> Result<(), String> {
+    match output_dir {
+        Some(output_dir) => {
+            std::fs::create_dir_all(output_dir).map_err(|e| e.to_string())?;
+            for kind in kinds {
+                let path = output_dir.join(format!("{}.schema.json", kind.name()));
+                let json = serde_json::to_string_pretty(&kind.context_schema())
+                    .map_err(|e| e.to_string())?;
+                std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))?;
+                log.success(&format!("Generated {}", path.display()));
+            }
+        }
+        None => {
+            let json = if let [kind] = kinds {
+                serde_json::to_string_pretty(&kind.context_schema())
+            } else {
+                serde_json::to_string_pretty(
+                    &kinds
+                        .iter()
+                        .map(|kind| (kind.name(), kind.context_schema()))
+                        .collect::<BTreeMap<_, _>>(),
+                )
+            }
+            .map_err(|e| e.to_string())?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Timing breakdown of a command (`--timings`), i.e. the durations of its
+//! phases recorded by the logger (see [`Logger::phase`]).
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+use weaver_logger::{Logger, PhaseTimings};
+
+use crate::cli::TimingsFormat;
+
+/// The known phases, in their reporting order. The other phases are reported
+/// after them, in the order of their first record.
+const PHASES: [&str; 7] = [
+    "load", "clone", "parse", "resolve", "render", "write", "format",
+];
+
+/// The timing breakdown of a command.
+#[derive(Serialize, Debug)]
+struct Timings {
+    /// The phases with their cumulated duration.
+    phases: Vec<PhaseTiming>,
+    /// The duration of the whole command, in seconds.
+    total_secs: f64,
+}
+
+/// The cumulated duration of a phase.
+#[derive(Serialize, Debug)]
+struct PhaseTiming {
+    /// The name of the phase.
+    phase: String,
+    /// The cumulated duration of the phase, in seconds.
+    duration_secs: f64,
+}
+
+/// Prints the timing breakdown of a command, in text (with the logger) or in
+/// JSON (on stderr, so it doesn't mix with the output of the command).
+pub fn print_timings(
+    log: &impl Logger,
+    timings: &PhaseTimings,
+    total: Duration,
+    format: TimingsFormat,
+) {
+    let mut phases = timings.phases();
+    phases.sort_by_key(|(phase, _)| {
+        PHASES
+            .iter()
+            .position(|known| known == phase)
+            .unwrap_or(PHASES.len())
+    });
+    let timings = Timings {
+        phases: phases
+            .into_iter()
+            .map(|(phase, duration)| PhaseTiming {
+                phase,
+                duration_secs: duration.as_secs_f64(),
+            })
+            .collect(),
+        total_secs: total.as_secs_f64(),
+    };
+
+    match format {
+        TimingsFormat::Text => {
+            log.info("Timings (the phases run in parallel are cumulated)");
+            for phase in timings.phases.iter() {
+                log.log(&format!(
+                    "  {:<10} {:>8.3}s",
+                    phase.phase, phase.duration_secs
+                ));
+            }
+            log.log(&format!("  {:<10} {:>8.3}s", "total", timings.total_secs));
+        }
+        TimingsFormat::Json => match serde_json::to_string(&timings) {
+            Ok(json) => eprintln!("{}", json),
+            Err(e) => {
+                log.error(&format!("Failed to serialize the timings: {}", e));
+            }
+        },
+    }
+}
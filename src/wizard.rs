@@ -0,0 +1,327 @@
+// SPDX-License-Identifier: Apache-2.0
+
+//! Interactive wizard of the `registry new-group` command, walking an author
+//! through the creation of a new semantic convention group. Each entry is
+//! validated as soon as it is entered (naming rules, ids already used by the
+//! registry) and asked again when invalid.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use weaver_logger::Logger;
+use weaver_semconv::attribute::{BasicRequirementLevelSpec, PrimitiveOrArrayTypeSpec};
+use weaver_semconv::group::{ConvTypeSpec, InstrumentSpec};
+use weaver_semconv::new_group::{check_name, NewAttribute, NewAttributeType, NewGroup, NewMetric};
+use weaver_semconv::refactor::RegistryFile;
+use weaver_semconv::{SemConvSpec, SemConvSpecs};
+
+use crate::registry::RegistryNewGroup;
+
+/// A line-based interactive prompt.
+pub struct Wizard<R, W> {
+    /// The answers of the author.
+    input: R,
+    /// The questions of the wizard.
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Wizard<R, W> {
+    /// Creates a wizard reading the answers from `input` and writing the
+    /// questions to `output`.
+    pub fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Asks a question and returns the trimmed answer, or the default answer
+    /// if the answer is empty. An answer is required when there is no default
+    /// answer.
+    fn ask(&mut self, question: &str, default: Option<&str>) -> Result<String, String> {
+        loop {
+            let prompt = match default {
+                Some(default) if !default.is_empty() => format!("{} [{}]: ", question, default),
+                _ => format!("{}: ", question),
+            };
+            write!(self.output, "{}", prompt)
+                .and_then(|_| self.output.flush())
+                .map_err(|e| e.to_string())?;
+
+            let mut answer = String::new();
+            if self
+                .input
+                .read_line(&mut answer)
+                .map_err(|e| e.to_string())?
+                == 0
+            {
+                return Err("New group aborted (end of input)".to_owned());
+            }
+            let answer = answer.trim();
+            match (answer.is_empty(), default) {
+                (false, _) => return Ok(answer.to_owned()),
+                (true, Some(default)) => return Ok(default.to_owned()),
+                (true, None) => self.warn("an answer is required")?,
+            }
+        }
+    }
+
+    /// Asks a question until the answer passes the given check.
+    fn ask_checked(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        check: impl Fn(&str) -> Result<(), String>,
+    ) -> Result<String, String> {
+        loop {
+            let answer = self.ask(question, default)?;
+            match check(&answer) {
+                Ok(()) => return Ok(answer),
+                Err(e) => self.warn(&e)?,
+            }
+        }
+    }
+
+    /// Asks to choose one of the given options (by name or by number).
+    fn choose<T: Clone>(
+        &mut self,
+        question: &str,
+        options: &[(&str, T)],
+        default: usize,
+    ) -> Result<T, String> {
+        let names: Vec<String> = options
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| format!("{}) {}", i + 1, name))
+            .collect();
+        let question = format!("{} ({})", question, names.join(", "));
+        let answer = self.ask_checked(&question, Some(options[default].0), |answer| {
+            option_index(options, answer)
+                .map(|_| ())
+                .ok_or_else(|| format!("`{}` is not one of the options", answer))
+        })?;
+        Ok(option_index(options, &answer)
+            .map(|index| options[index].1.clone())
+            .unwrap_or_else(|| options[default].1.clone()))
+    }
+
+    /// Asks a yes/no question.
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool, String> {
+        let default = if default { "y" } else { "n" };
+        let answer = self.ask_checked(question, Some(default), |answer| {
+            match answer.to_lowercase().as_str() {
+                "y" | "yes" | "n" | "no" => Ok(()),
+                _ => Err("answer `y` or `n`".to_owned()),
+            }
+        })?;
+        Ok(answer.to_lowercase().starts_with('y'))
+    }
+
+    /// Prints an invalid entry.
+    fn warn(&mut self, message: &str) -> Result<(), String> {
+        writeln!(self.output, "  invalid entry: {}", message).map_err(|e| e.to_string())
+    }
+}
+
+/// Returns the index of the option designated by an answer (its name or its
+/// number starting at 1).
+fn option_index<T>(options: &[(&str, T)], answer: &str) -> Option<usize> {
+    options
+        .iter()
+        .position(|(name, _)| *name == answer)
+        .or_else(|| {
+            answer
+                .parse::<usize>()
+                .ok()
+                .filter(|number| (1..=options.len()).contains(number))
+                .map(|number| number - 1)
+        })
+}
+
+/// Walks the author through the creation of a new group of the given
+/// (resolved) registry, then writes the group in its semantic convention
+/// file.
+pub fn new_group<R: BufRead, W: Write>(
+    log: &impl Logger,
+    wizard: &mut Wizard<R, W>,
+    registry: &SemConvSpecs,
+    command: &RegistryNewGroup,
+) -> Result<(), String> {
+    let r#type = wizard.choose(
+        "Group type",
+        &[
+            ("attribute_group", ConvTypeSpec::AttributeGroup),
+            ("span", ConvTypeSpec::Span),
+            ("event", ConvTypeSpec::Event),
+            ("metric", ConvTypeSpec::Metric),
+            ("metric_group", ConvTypeSpec::MetricGroup),
+            ("resource", ConvTypeSpec::Resource),
+            ("scope", ConvTypeSpec::Scope),
+        ],
+        0,
+    )?;
+    let id = wizard.ask_checked("Group id", None, |id| NewGroup::check_id(registry, id))?;
+    let brief = wizard.ask("Brief description", None)?;
+    let default_prefix = id.strip_prefix("registry.").unwrap_or(&id).to_owned();
+    let prefix = wizard.ask_checked(
+        "Attribute prefix (`-` for none)",
+        Some(default_prefix.as_str()),
+        |prefix| match prefix {
+            "-" => Ok(()),
+            prefix => check_name(prefix),
+        },
+    )?;
+    let metric = if r#type == ConvTypeSpec::Metric {
+        Some(NewMetric {
+            metric_name: wizard.ask_checked("Metric name", None, |metric_name| {
+                NewGroup::check_metric_name(registry, metric_name)
+            })?,
+            instrument: wizard.choose(
+                "Instrument",
+                &[
+                    ("counter", InstrumentSpec::Counter),
+                    ("updowncounter", InstrumentSpec::UpDownCounter),
+                    ("gauge", InstrumentSpec::Gauge),
+                    ("histogram", InstrumentSpec::Histogram),
+                ],
+                0,
+            )?,
+            unit: wizard.ask("Unit (UCUM)", Some("1"))?,
+        })
+    } else {
+        None
+    };
+    let mut group = NewGroup {
+        r#type,
+        id,
+        brief,
+        prefix: (prefix != "-").then_some(prefix),
+        metric,
+        attributes: vec![],
+    };
+
+    while wizard.confirm("Add an attribute?", group.attributes.is_empty())? {
+        let attribute = new_attribute(wizard, registry, &group)?;
+        group.attributes.push(attribute);
+    }
+
+    let yaml = group.to_yaml();
+    // The rendered group must be a valid semantic convention file.
+    _ = SemConvSpec::load_from_str(&yaml, "new group").map_err(|e| e.to_string())?;
+    log.log(&yaml);
+
+    let path = command
+        .file
+        .clone()
+        .unwrap_or_else(|| command.registry.join(group.file_name()));
+    if !wizard.confirm(&format!("Write the group to {}?", path.display()), true)? {
+        log.info("New group not written");
+        return Ok(());
+    }
+    write_group(&path, &yaml)?;
+    log.success(&format!(
+        "Group `{}` written to {}",
+        group.id,
+        path.display()
+    ));
+    Ok(())
+}
+
+/// Asks the definition of a new attribute of the group.
+fn new_attribute<R: BufRead, W: Write>(
+    wizard: &mut Wizard<R, W>,
+    registry: &SemConvSpecs,
+    group: &NewGroup,
+) -> Result<NewAttribute, String> {
+    let id = wizard.ask_checked("  Attribute id", None, |id| {
+        group.check_attribute_id(registry, id)
+    })?;
+    let r#type = wizard.choose(
+        "  Attribute type",
+        &[
+            ("string", Some(PrimitiveOrArrayTypeSpec::String)),
+            ("int", Some(PrimitiveOrArrayTypeSpec::Int)),
+            ("double", Some(PrimitiveOrArrayTypeSpec::Double)),
+            ("boolean", Some(PrimitiveOrArrayTypeSpec::Boolean)),
+            ("string[]", Some(PrimitiveOrArrayTypeSpec::Strings)),
+            ("int[]", Some(PrimitiveOrArrayTypeSpec::Ints)),
+            ("double[]", Some(PrimitiveOrArrayTypeSpec::Doubles)),
+            ("boolean[]", Some(PrimitiveOrArrayTypeSpec::Booleans)),
+            ("enum", None),
+        ],
+        0,
+    )?;
+    let r#type = match r#type {
+        Some(r#type) => NewAttributeType::PrimitiveOrArray(r#type),
+        None => {
+            let members =
+                wizard.ask_checked("  Enum members (comma-separated ids)", None, |members| {
+                    let mut ids = vec![];
+                    if split_list(members).is_empty() {
+                        return Err("an enum needs at least one member".to_owned());
+                    }
+                    for member in split_list(members) {
+                        check_name(&member)?;
+                        if ids.contains(&member) {
+                            return Err(format!("the member `{}` is duplicated", member));
+                        }
+                        ids.push(member);
+                    }
+                    Ok(())
+                })?;
+            NewAttributeType::Enum {
+                allow_custom_values: wizard.confirm("  Allow custom values?", true)?,
+                members: split_list(&members),
+            }
+        }
+    };
+    let brief = wizard.ask("  Brief description", None)?;
+    let requirement_level = wizard.choose(
+        "  Requirement level",
+        &[
+            ("recommended", BasicRequirementLevelSpec::Recommended),
+            ("required", BasicRequirementLevelSpec::Required),
+            ("opt_in", BasicRequirementLevelSpec::OptIn),
+        ],
+        0,
+    )?;
+    // The examples are expected for the string attributes.
+    let examples = match &r#type {
+        NewAttributeType::PrimitiveOrArray(
+            PrimitiveOrArrayTypeSpec::String | PrimitiveOrArrayTypeSpec::Strings,
+        ) => split_list(&wizard.ask("  Examples (comma-separated)", None)?),
+        _ => vec![],
+    };
+
+    Ok(NewAttribute {
+        id,
+        r#type,
+        brief,
+        requirement_level,
+        examples,
+    })
+}
+
+/// Splits a comma-separated list, ignoring the empty items.
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Writes the rendered group in the given semantic convention file, at the
+/// end of its groups (with their indentation) if the file already exists.
+fn write_group(path: &Path, yaml: &str) -> Result<(), String> {
+    let content = if path.exists() {
+        let target = RegistryFile::load_from_file(path).map_err(|e| e.to_string())?;
+        RegistryFile::parse("new group", yaml)
+            .and_then(|source| source.move_groups(&target, None))
+            .map_err(|e| e.to_string())?
+            .target
+    } else {
+        yaml.to_owned()
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+    }
+    std::fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))
+}